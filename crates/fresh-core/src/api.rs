@@ -187,11 +187,16 @@ pub enum PluginResponse {
         #[ts(type = "any")]
         result: Result<JsonValue, String>,
     },
-    /// Response to RequestHighlights
+    /// Response to RequestHighlights and RequestCodeHighlights
     HighlightsComputed {
         request_id: u64,
         spans: Vec<TsHighlightSpan>,
     },
+    /// Response to RequestDocumentSymbols
+    DocumentSymbols {
+        request_id: u64,
+        symbols: Vec<TsDocumentSymbol>,
+    },
     /// Response to GetBufferText with the text content
     BufferText {
         request_id: u64,
@@ -824,6 +829,13 @@ pub enum PluginCommand {
         range: Range<usize>,
     },
 
+    /// Replace a range of text in a buffer, undone in one step
+    ReplaceRange {
+        buffer_id: BufferId,
+        range: Range<usize>,
+        text: String,
+    },
+
     /// Add an overlay to a buffer, returns handle via response channel
     ///
     /// Colors can be specified as RGB tuples or theme keys. When theme keys
@@ -1345,6 +1357,19 @@ pub enum PluginCommand {
         request_id: u64,
     },
 
+    /// Request syntax highlights for a code string in the given language
+    RequestCodeHighlights {
+        code: String,
+        language: String,
+        request_id: u64,
+    },
+
+    /// Request the outline symbols of a buffer
+    RequestDocumentSymbols {
+        buffer_id: BufferId,
+        request_id: u64,
+    },
+
     /// Close a split (if not the last one)
     CloseSplit { split_id: SplitId },
 
@@ -1802,6 +1827,25 @@ pub struct TsHighlightSpan {
     pub italic: bool,
 }
 
+/// A function, class, heading or similar symbol in a buffer's outline
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct TsDocumentSymbol {
+    pub name: String,
+    /// What the symbol is, e.g. "function", "class", "struct" or "heading"
+    pub kind: String,
+    /// Number of symbols this one is nested in (0 for top level)
+    pub depth: u32,
+    /// 1-based line of the symbol's name
+    pub line: u32,
+    /// Byte offset of the symbol's name
+    pub offset: u32,
+    /// Byte offset where the whole definition or section starts
+    pub start: u32,
+    /// Byte offset where the whole definition or section ends
+    pub end: u32,
+}
+
 /// Result from spawning a process with spawnProcess
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
@@ -2304,6 +2348,20 @@ impl PluginApi {
         self.send_command(PluginCommand::DeleteRange { buffer_id, range })
     }
 
+    /// Replace a range of text in a buffer, undone in one step
+    pub fn replace_range(
+        &self,
+        buffer_id: BufferId,
+        range: Range<usize>,
+        text: String,
+    ) -> Result<(), String> {
+        self.send_command(PluginCommand::ReplaceRange {
+            buffer_id,
+            range,
+            text,
+        })
+    }
+
     /// Add an overlay (decoration) to a buffer
     /// Add an overlay to a buffer with styling options
     ///
@@ -2549,6 +2607,28 @@ impl PluginApi {
         })
     }
 
+    /// Request syntax highlights for a code string in the given language
+    pub fn highlight_code(
+        &self,
+        code: String,
+        language: String,
+        request_id: u64,
+    ) -> Result<(), String> {
+        self.send_command(PluginCommand::RequestCodeHighlights {
+            code,
+            language,
+            request_id,
+        })
+    }
+
+    /// Request the outline symbols of a buffer
+    pub fn get_document_symbols(&self, buffer_id: BufferId, request_id: u64) -> Result<(), String> {
+        self.send_command(PluginCommand::RequestDocumentSymbols {
+            buffer_id,
+            request_id,
+        })
+    }
+
     // === Query Methods ===
 
     /// Get the currently active buffer ID
@@ -0,0 +1,166 @@
+/// <reference path="./lib/fresh.d.ts" />
+
+/**
+ * Document Outline Plugin
+ *
+ * Lists the functions, classes, structs and similar definitions of a buffer,
+ * or its headings in markdown, in a live panel. Symbols come from the
+ * editor's syntax tree and are nested inside the definitions that contain
+ * them; markdown headings nest by level.
+ *
+ * - The outline is rebuilt once the buffer has stopped changing for a moment
+ * - The symbol enclosing the editor cursor is highlighted as the cursor moves
+ * - Enter jumps to the selected symbol
+ * - The outline follows the active buffer
+ */
+
+import { Finder, createLiveProvider, type FinderProvider } from "./lib/finder.ts";
+
+const editor = getEditor();
+
+// How long a buffer must stay unchanged before its outline is rebuilt
+const REFRESH_DELAY_MS = 300;
+
+// State
+let sourceBufferId: number | null = null;
+let sourceSplitId: number | null = null;
+let symbols: TsDocumentSymbol[] = [];
+// Bumped by every refresh, so a slow one can't overwrite a newer outline
+let refreshVersion = 0;
+
+// Create the live provider
+const provider = createLiveProvider(() => symbols);
+
+// Create the finder instance
+const finder = new Finder<TsDocumentSymbol>(editor, {
+  id: "outline",
+  format: (symbol) => ({
+    label: `${"  ".repeat(symbol.depth)}${symbol.name}`,
+    description: symbol.kind,
+  }),
+  syncWithEditor: true,
+  // Symbols are in document order, so the last one containing the cursor
+  // is the innermost
+  matchEditorCursor: (symbol, cursor) =>
+    cursor.bufferId === sourceBufferId &&
+    cursor.position >= symbol.start &&
+    cursor.position < symbol.end,
+  onSelect: (symbol) => {
+    if (sourceBufferId === null) return;
+    if (sourceSplitId !== null) {
+      editor.focusSplit(sourceSplitId);
+    }
+    editor.setBufferCursor(sourceBufferId, symbol.offset);
+    editor.setStatus(
+      editor.t("status.jumped_to", {
+        name: symbol.name,
+        line: String(symbol.line),
+      })
+    );
+  },
+});
+
+// Get title naming the outlined buffer
+function getTitle(): string {
+  const path = sourceBufferId !== null ? editor.getBufferPath(sourceBufferId) : "";
+  const name = path ? (path.split("/").pop() ?? path) : editor.t("panel.untitled");
+  return editor.t("panel.header", { name });
+}
+
+// Rebuild the outline of the source buffer now
+async function refresh(): Promise<void> {
+  const version = ++refreshVersion;
+  const bufferId = sourceBufferId;
+  const result = bufferId !== null ? await editor.getDocumentSymbols(bufferId) : [];
+  if (!finder.isOpen || version !== refreshVersion) return;
+
+  symbols = result;
+  provider.notify();
+  finder.updateTitle(getTitle());
+}
+
+// Rebuild the outline once edits pause
+async function scheduleRefresh(): Promise<void> {
+  const version = ++refreshVersion;
+  await editor.delay(REFRESH_DELAY_MS);
+  if (version === refreshVersion) {
+    await refresh();
+  }
+}
+
+// Commands
+globalThis.show_document_outline = async function (): Promise<void> {
+  if (finder.isOpen) {
+    await refresh();
+    return;
+  }
+
+  // Capture source context
+  sourceBufferId = editor.getActiveBufferId();
+  sourceSplitId = editor.getActiveSplitId();
+  symbols = await editor.getDocumentSymbols(sourceBufferId);
+
+  await finder.livePanel({
+    title: getTitle(),
+    provider: provider as FinderProvider<TsDocumentSymbol>,
+    ratio: 0.3,
+  });
+
+  if (symbols.length === 0) {
+    editor.setStatus(editor.t("status.no_symbols"));
+  }
+};
+
+globalThis.toggle_document_outline = function (): void {
+  if (finder.isOpen) {
+    finder.close();
+    sourceBufferId = null;
+    sourceSplitId = null;
+    symbols = [];
+  } else {
+    globalThis.show_document_outline();
+  }
+};
+
+// Event Handlers
+
+// Edits to the outlined buffer rebuild the outline once typing pauses
+globalThis.on_outline_buffer_changed = function (data: { buffer_id: number }): void {
+  if (finder.isOpen && data.buffer_id === sourceBufferId) {
+    scheduleRefresh();
+  }
+};
+
+// The outline follows the active file buffer; the panel itself is virtual
+globalThis.on_outline_buffer_activated = function (data: { buffer_id: number }): void {
+  if (!finder.isOpen || data.buffer_id === sourceBufferId) return;
+  const info = editor.getBufferInfo(data.buffer_id);
+  if (!info || info.is_virtual) return;
+
+  sourceBufferId = data.buffer_id;
+  sourceSplitId = editor.getActiveSplitId();
+  refresh();
+};
+
+// Register event handlers
+editor.on("after_insert", "on_outline_buffer_changed");
+editor.on("after_delete", "on_outline_buffer_changed");
+editor.on("buffer_activated", "on_outline_buffer_activated");
+
+// Command Registration
+editor.registerCommand(
+  "%cmd.show_document_outline",
+  "%cmd.show_document_outline_desc",
+  "show_document_outline",
+  null
+);
+
+editor.registerCommand(
+  "%cmd.toggle_document_outline",
+  "%cmd.toggle_document_outline_desc",
+  "toggle_document_outline",
+  null
+);
+
+// Initialization
+editor.debug("Document Outline plugin initialized");
@@ -20,6 +20,7 @@ const editor = getEditor();
  * - 'b' to go back in history (show blame at parent commit)
  * - 'q' to close the blame view
  * - 'y' to yank (copy) the commit hash at cursor
+ * - 'Toggle Git Blame' annotates the cursor line of any file with its last commit
  *
  * Inspired by magit's git-blame-additions feature.
  */
@@ -90,6 +91,7 @@ const blameState: BlameState = {
 const colors = {
   headerFg: [0, 0, 0] as [number, number, number],           // Black text
   headerBg: [200, 200, 200] as [number, number, number],     // Light gray background
+  inlineFg: [128, 128, 128] as [number, number, number],     // Gray inline annotation
 };
 
 // =============================================================================
@@ -131,8 +133,14 @@ async function fetchGitBlame(filePath: string, commit: string | null): Promise<B
     return [];
   }
 
+  return parseBlamePorcelain(result.stdout);
+}
+
+/**
+ * Parse the lines of git blame --porcelain output
+ */
+function parseBlamePorcelain(output: string): BlameLine[] {
   const lines: BlameLine[] = [];
-  const output = result.stdout;
   const outputLines = output.split("\n");
 
   let currentHash = "";
@@ -669,6 +677,205 @@ globalThis.git_blame_copy_hash = function(): void {
   editor.setStatus(editor.t("status.hash_copied", { short: hash.slice(0, 7), full: hash }));
 };
 
+// =============================================================================
+// Inline Blame
+// =============================================================================
+
+/**
+ * Inline blame shows who last changed the cursor line at the end of that
+ * line, as virtual text that never touches the buffer. Blame is fetched in
+ * the background the first time a file needs it and cached per file and
+ * HEAD revision. HEAD is resolved again whenever the annotation is turned
+ * on or another buffer is activated, so commits made outside the editor
+ * are picked up.
+ */
+
+const INLINE_BLAME_ID = "git-blame-inline";
+const UNCOMMITTED_HASH = "0000000000000000000000000000000000000000";
+
+const inlineBlame = {
+  enabled: false,
+  // Buffer currently showing the annotation
+  bufferId: null as number | null,
+  // Line (0-indexed) the annotation was last drawn for
+  line: -1,
+  // Bumped by every update, so a slow blame can't draw over a newer one
+  version: 0,
+  // HEAD revision of each file's repository, until the next refresh
+  revisions: new Map<string, string>(),
+  // Blame of each file, keyed by revision and path
+  cache: new Map<string, Promise<BlameLine[]>>(),
+};
+
+/**
+ * Get the HEAD revision of the repository a file is in, or null outside git
+ */
+async function getHeadRevision(filePath: string): Promise<string | null> {
+  const known = inlineBlame.revisions.get(filePath);
+  if (known !== undefined) return known;
+
+  const cwd = filePath.substring(0, filePath.lastIndexOf("/")) || ".";
+  const result = await editor.spawnProcess("git", ["rev-parse", "HEAD"], cwd);
+  if (result.exit_code !== 0) return null;
+  const revision = result.stdout.trim();
+  inlineBlame.revisions.set(filePath, revision);
+  return revision;
+}
+
+/**
+ * Get the blame of a file's saved contents, fetching it at most once per
+ * revision
+ */
+async function getInlineBlame(filePath: string): Promise<BlameLine[]> {
+  const revision = await getHeadRevision(filePath);
+  if (revision === null) return [];
+
+  const key = `${revision}:${filePath}`;
+  let blame = inlineBlame.cache.get(key);
+  if (!blame) {
+    const cwd = filePath.substring(0, filePath.lastIndexOf("/")) || ".";
+    blame = editor
+      .spawnProcess("git", ["blame", "--porcelain", "--", filePath], cwd)
+      .then((result) => (result.exit_code === 0 ? parseBlamePorcelain(result.stdout) : []));
+    inlineBlame.cache.set(key, blame);
+  }
+  return blame;
+}
+
+/**
+ * Forget the cached blame of a file, e.g. once it was saved
+ */
+function forgetInlineBlame(filePath: string): void {
+  inlineBlame.revisions.delete(filePath);
+  for (const key of inlineBlame.cache.keys()) {
+    if (key.endsWith(`:${filePath}`)) {
+      inlineBlame.cache.delete(key);
+    }
+  }
+}
+
+/**
+ * Remove the annotation from the buffer showing it
+ */
+function clearInlineBlame(): void {
+  if (inlineBlame.bufferId !== null) {
+    editor.removeVirtualText(inlineBlame.bufferId, INLINE_BLAME_ID);
+    inlineBlame.bufferId = null;
+  }
+  inlineBlame.line = -1;
+}
+
+/**
+ * Format the annotation for a blamed line
+ */
+function formatInlineBlame(line: BlameLine): string {
+  if (line.hash === UNCOMMITTED_HASH) {
+    return editor.t("inline.not_committed");
+  }
+  return editor.t("inline.annotation", {
+    hash: line.shortHash,
+    author: line.author,
+    date: line.relativeDate,
+  });
+}
+
+/**
+ * Annotate the cursor line of the active buffer with its blame
+ */
+async function updateInlineBlame(): Promise<void> {
+  const version = ++inlineBlame.version;
+  const bufferId = editor.getActiveBufferId();
+  const line = editor.getCursorLine();
+  clearInlineBlame();
+  inlineBlame.line = line;
+
+  // Line numbers of an edited buffer no longer match the saved file's blame
+  const info = editor.getBufferInfo(bufferId);
+  if (!info || info.is_virtual || info.modified || !info.path) return;
+
+  const blame = await getInlineBlame(info.path);
+  const blamed = blame.find((entry) => entry.finalLineNumber === line + 1);
+  if (!blamed) return;
+
+  const position = await editor.getLineEndPosition(line);
+  if (
+    position === null ||
+    !inlineBlame.enabled ||
+    version !== inlineBlame.version ||
+    editor.getActiveBufferId() !== bufferId
+  ) {
+    return;
+  }
+
+  editor.addVirtualText(
+    bufferId,
+    INLINE_BLAME_ID,
+    position,
+    formatInlineBlame(blamed),
+    colors.inlineFg[0],
+    colors.inlineFg[1],
+    colors.inlineFg[2],
+    true,
+    false
+  );
+  inlineBlame.bufferId = bufferId;
+}
+
+/**
+ * Toggle the blame annotation on the cursor line
+ */
+globalThis.git_blame_toggle_inline = function(): void {
+  inlineBlame.enabled = !inlineBlame.enabled;
+  if (inlineBlame.enabled) {
+    editor.setStatus(editor.t("status.inline_on"));
+    inlineBlame.revisions.clear();
+    updateInlineBlame();
+  } else {
+    inlineBlame.version++;
+    clearInlineBlame();
+    editor.setStatus(editor.t("status.inline_off"));
+  }
+};
+
+globalThis.on_git_blame_inline_cursor_moved = function(data: {
+  buffer_id: number;
+  line: number;
+}): void {
+  if (!inlineBlame.enabled) return;
+  // Moving within the annotated line keeps the annotation
+  if (data.buffer_id === inlineBlame.bufferId && data.line - 1 === inlineBlame.line) return;
+  updateInlineBlame();
+};
+
+globalThis.on_git_blame_inline_buffer_activated = function(): void {
+  if (!inlineBlame.enabled) return;
+  inlineBlame.revisions.clear();
+  updateInlineBlame();
+};
+
+// Edits hide the annotation until the buffer is saved again
+globalThis.on_git_blame_inline_buffer_changed = function(data: { buffer_id: number }): void {
+  if (inlineBlame.enabled && data.buffer_id === inlineBlame.bufferId) {
+    clearInlineBlame();
+  }
+};
+
+globalThis.on_git_blame_inline_after_save = function(data: {
+  buffer_id: number;
+  path: string;
+}): void {
+  forgetInlineBlame(data.path);
+  if (inlineBlame.enabled && data.buffer_id === editor.getActiveBufferId()) {
+    updateInlineBlame();
+  }
+};
+
+editor.on("cursor_moved", "on_git_blame_inline_cursor_moved");
+editor.on("buffer_activated", "on_git_blame_inline_buffer_activated");
+editor.on("after_insert", "on_git_blame_inline_buffer_changed");
+editor.on("after_delete", "on_git_blame_inline_buffer_changed");
+editor.on("after_file_save", "on_git_blame_inline_after_save");
+
 // =============================================================================
 // Command Registration
 // =============================================================================
@@ -694,6 +901,13 @@ editor.registerCommand(
   null
 );
 
+editor.registerCommand(
+  "%cmd.git_blame_toggle_inline",
+  "%cmd.git_blame_toggle_inline_desc",
+  "git_blame_toggle_inline",
+  null
+);
+
 // =============================================================================
 // Plugin Initialization
 // =============================================================================
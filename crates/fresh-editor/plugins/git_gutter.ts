@@ -6,13 +6,17 @@ const editor = getEditor();
  * Git Gutter Plugin
  *
  * Shows git diff indicators in the gutter for modified, added, and deleted lines.
- * Uses `git diff` to compare the current buffer content against the index (staged changes)
- * or HEAD if nothing is staged.
+ * The buffer's current content, including unsaved edits, is compared against
+ * the file's committed (HEAD) version, so indicators follow along as you type.
+ * Files outside a repository or not tracked by git get no indicators.
  *
  * Indicator symbols:
  * - │ (green): Added line
  * - │ (yellow): Modified line
  * - ▾ (red): Deleted line(s) below
+ *
+ * Commands move between changes and revert the change at the cursor to its
+ * committed version.
  */
 
 // =============================================================================
@@ -22,6 +26,13 @@ const editor = getEditor();
 const NAMESPACE = "git-gutter";
 const PRIORITY = 10; // Lower than diagnostics
 
+// How long the buffer must stay unchanged before indicators are recomputed
+const UPDATE_DELAY_MS = 150;
+
+// Beyond this many changed lines, a region is shown as one modified block
+// instead of being diffed line by line
+const MAX_EDIT_DISTANCE = 1000;
+
 // Colors (RGB)
 const COLORS = {
   added: [80, 250, 123] as [number, number, number],    // Green
@@ -47,15 +58,21 @@ interface DiffHunk {
   startLine: number;
   /** Number of lines affected */
   lineCount: number;
+  /** Committed lines this hunk replaces */
+  oldLines: string[];
 }
 
 interface BufferGitState {
   /** File path for this buffer */
   filePath: string;
+  /** Committed content of the file, or null if git doesn't track it */
+  headText: string | null;
+  /** Buffer lines the hunks were computed from */
+  lines: string[];
   /** Last known hunks for this buffer */
   hunks: DiffHunk[];
-  /** Whether we're currently updating */
-  updating: boolean;
+  /** Bumped by every update, so a slow one can't overwrite a newer one */
+  version: number;
 }
 
 // =============================================================================
@@ -65,114 +82,147 @@ interface BufferGitState {
 /** Git state per buffer */
 const bufferStates: Map<number, BufferGitState> = new Map();
 
+function ensureState(bufferId: number, filePath: string): BufferGitState {
+  let state = bufferStates.get(bufferId);
+  if (!state) {
+    state = { filePath, headText: null, lines: [], hunks: [], version: 0 };
+    bufferStates.set(bufferId, state);
+  }
+  state.filePath = filePath;
+  return state;
+}
+
 
 // =============================================================================
-// Git Diff Parsing
+// Line Diff
 // =============================================================================
 
 /**
- * Parse unified diff output to extract hunks
- * Unified diff format:
- * @@ -start,count +start,count @@
+ * Pairs of equal lines (index in `a`, index in `b`) in a shortest edit
+ * script from `a` to `b`, found with Myers' algorithm
+ *
+ * Returns null if more than MAX_EDIT_DISTANCE lines differ.
  */
-function parseDiffOutput(diffOutput: string): DiffHunk[] {
-  const hunks: DiffHunk[] = [];
-  const lines = diffOutput.split("\n");
-
-  let currentOldLine = 0;
-  let currentNewLine = 0;
-  let inHunk = false;
-  let addedStart = 0;
-  let addedCount = 0;
-  let modifiedStart = 0;
-  let modifiedCount = 0;
-  let deletedAtLine = 0;
-  let deletedCount = 0;
-
-  const flushAdded = () => {
-    if (addedCount > 0) {
-      hunks.push({ type: "added", startLine: addedStart, lineCount: addedCount });
-      addedCount = 0;
+function commonLines(a: string[], b: string[]): Array<[number, number]> | null {
+  const n = a.length;
+  const m = b.length;
+  const offset = n + m + 1;
+  const v = new Int32Array(2 * offset + 1);
+  // trace[d] holds v for diagonals -d-1..d+1 before step d
+  const trace: Int32Array[] = [];
+
+  for (let d = 0; d <= Math.min(n + m, MAX_EDIT_DISTANCE); d++) {
+    trace.push(v.slice(offset - d - 1, offset + d + 2));
+    for (let k = -d; k <= d; k += 2) {
+      let x =
+        k === -d || (k !== d && v[offset + k - 1] < v[offset + k + 1])
+          ? v[offset + k + 1]
+          : v[offset + k - 1] + 1;
+      let y = x - k;
+      while (x < n && y < m && a[x] === b[y]) {
+        x++;
+        y++;
+      }
+      v[offset + k] = x;
+      if (x >= n && y >= m) {
+        return backtrack(trace, n, m);
+      }
     }
-  };
+  }
+  return null;
+}
 
-  const flushModified = () => {
-    if (modifiedCount > 0) {
-      hunks.push({ type: "modified", startLine: modifiedStart, lineCount: modifiedCount });
-      modifiedCount = 0;
+/** Walk the Myers trace back from the end, collecting the equal lines */
+function backtrack(trace: Int32Array[], n: number, m: number): Array<[number, number]> {
+  const pairs: Array<[number, number]> = [];
+  let x = n;
+  let y = m;
+  for (let d = trace.length - 1; d >= 0; d--) {
+    const v = trace[d];
+    const at = (k: number) => v[k + d + 1];
+    const k = x - y;
+    const prevK = k === -d || (k !== d && at(k - 1) < at(k + 1)) ? k + 1 : k - 1;
+    const prevX = at(prevK);
+    const prevY = prevX - prevK;
+    while (x > prevX && y > prevY) {
+      x--;
+      y--;
+      pairs.push([x, y]);
     }
-  };
+    x = prevX;
+    y = prevY;
+  }
+  return pairs.reverse();
+}
 
-  const flushDeleted = () => {
-    if (deletedCount > 0) {
-      // Deleted lines are shown as a marker on the line after the deletion
-      hunks.push({ type: "deleted", startLine: deletedAtLine, lineCount: deletedCount });
-      deletedCount = 0;
-    }
-  };
-
-  for (const line of lines) {
-    // Match hunk header: @@ -old_start,old_count +new_start,new_count @@
-    const hunkMatch = line.match(/^@@ -(\d+)(?:,(\d+))? \+(\d+)(?:,(\d+))? @@/);
-    if (hunkMatch) {
-      // Flush any pending changes from previous hunk
-      flushAdded();
-      flushModified();
-      flushDeleted();
-
-      currentOldLine = parseInt(hunkMatch[1], 10);
-      currentNewLine = parseInt(hunkMatch[3], 10);
-      inHunk = true;
-      continue;
-    }
+/**
+ * The hunks that turn the committed lines into the buffer's lines
+ */
+function diffLines(oldLines: string[], newLines: string[]): DiffHunk[] {
+  // Lines shared at the start and end need no diffing
+  let prefix = 0;
+  while (
+    prefix < oldLines.length &&
+    prefix < newLines.length &&
+    oldLines[prefix] === newLines[prefix]
+  ) {
+    prefix++;
+  }
+  let suffix = 0;
+  while (
+    suffix < oldLines.length - prefix &&
+    suffix < newLines.length - prefix &&
+    oldLines[oldLines.length - 1 - suffix] === newLines[newLines.length - 1 - suffix]
+  ) {
+    suffix++;
+  }
+  const a = oldLines.slice(prefix, oldLines.length - suffix);
+  const b = newLines.slice(prefix, newLines.length - suffix);
 
-    if (!inHunk) continue;
-
-    if (line.startsWith("+") && !line.startsWith("+++")) {
-      // Added line
-      if (deletedCount > 0) {
-        // If there were deletions right before, this is a modification
-        if (modifiedCount === 0) {
-          modifiedStart = currentNewLine;
-        }
-        modifiedCount++;
-        deletedCount--;
-      } else {
-        // Pure addition
-        if (addedCount === 0) {
-          addedStart = currentNewLine;
-        }
-        addedCount++;
-      }
-      currentNewLine++;
-    } else if (line.startsWith("-") && !line.startsWith("---")) {
-      // Deleted line - flush any pending additions first
-      flushAdded();
+  const pairs = commonLines(a, b) ?? [];
+  pairs.push([a.length, b.length]);
 
-      if (deletedCount === 0) {
-        deletedAtLine = currentNewLine;
-      }
-      deletedCount++;
-      currentOldLine++;
-    } else if (line.startsWith(" ")) {
-      // Context line (unchanged)
-      flushAdded();
-      flushModified();
-      flushDeleted();
-      currentOldLine++;
-      currentNewLine++;
-    } else if (line === "\\ No newline at end of file") {
-      // Ignore this marker
-      continue;
+  const hunks: DiffHunk[] = [];
+  let i = 0;
+  let j = 0;
+  for (const [nextI, nextJ] of pairs) {
+    if (nextI > i || nextJ > j) {
+      const removed = a.slice(i, nextI);
+      const added = nextJ - j;
+      hunks.push({
+        type: removed.length === 0 ? "added" : added === 0 ? "deleted" : "modified",
+        startLine: prefix + j + 1,
+        lineCount: added,
+        oldLines: removed,
+      });
     }
+    i = nextI + 1;
+    j = nextJ + 1;
   }
+  return hunks;
+}
 
-  // Flush any remaining changes
-  flushAdded();
-  flushModified();
-  flushDeleted();
+/**
+ * The 0-indexed buffer lines a hunk's indicator covers
+ */
+function hunkLines(hunk: DiffHunk): [number, number] {
+  if (hunk.type === "deleted") {
+    // Deleted indicator shows on the line above the deletion
+    const line = Math.max(0, hunk.startLine - 2);
+    return [line, line];
+  }
+  return [hunk.startLine - 1, hunk.startLine - 2 + hunk.lineCount];
+}
 
-  return hunks;
+/**
+ * Byte offset of the start of a 0-indexed line
+ */
+function lineOffset(lines: string[], line: number): number {
+  let offset = 0;
+  for (let i = 0; i < line && i < lines.length; i++) {
+    offset += editor.utf8ByteLength(lines[i]) + 1;
+  }
+  return offset;
 }
 
 // =============================================================================
@@ -200,29 +250,19 @@ async function isGitTracked(filePath: string): Promise<boolean> {
 }
 
 /**
- * Get git diff for a file
- * Compares working tree against HEAD to show all uncommitted changes
- * (both staged and unstaged)
+ * Get the committed content of a file, or null if git doesn't track it
+ *
+ * A file staged but never committed has no HEAD version, so all its lines
+ * count as added.
  */
-async function getGitDiff(filePath: string): Promise<string> {
-  const cwd = getFileDirectory(filePath);
-
-  // Diff against HEAD to show all changes (staged + unstaged) vs last commit
-  const result = await editor.spawnProcess("git", [
-    "diff",
-    "HEAD",
-    "--no-color",
-    "--unified=0", // No context lines for cleaner parsing
-    "--",
-    filePath,
-  ], cwd);
-
-  // Exit code 0 = no differences, 1 = differences found, >1 = error
-  if (result.exit_code <= 1) {
-    return result.stdout;
+async function getHeadText(filePath: string): Promise<string | null> {
+  if (!(await isGitTracked(filePath))) {
+    return null;
   }
-
-  return "";
+  const cwd = getFileDirectory(filePath);
+  const name = filePath.substring(filePath.lastIndexOf("/") + 1);
+  const result = await editor.spawnProcess("git", ["show", `HEAD:./${name}`], cwd);
+  return result.exit_code === 0 ? result.stdout : "";
 }
 
 // =============================================================================
@@ -230,82 +270,76 @@ async function getGitDiff(filePath: string): Promise<string> {
 // =============================================================================
 
 /**
- * Update git gutter indicators for a buffer
+ * Reload the committed version of a buffer's file, then update its indicators
  */
-async function updateGitGutter(bufferId: number): Promise<void> {
+async function refreshGitGutter(bufferId: number): Promise<void> {
   const state = bufferStates.get(bufferId);
-  if (!state || state.updating) return;
+  if (!state) return;
 
-  state.updating = true;
-
-  try {
-    editor.debug(`Git Gutter: updating for ${state.filePath}`);
-
-    // Check if file is git tracked
-    const tracked = await isGitTracked(state.filePath);
-    if (!tracked) {
-      // Clear indicators for non-tracked files
-      editor.debug("Git Gutter: file not tracked by git");
-      editor.clearLineIndicators(bufferId, NAMESPACE);
-      state.hunks = [];
-      return;
-    }
-
-    editor.debug("Git Gutter: file is tracked, getting diff...");
+  const headText = await getHeadText(state.filePath);
+  state.headText = headText;
+  if (headText === null) {
+    // Clear indicators for non-tracked files
+    editor.debug("Git Gutter: file not tracked by git");
+    editor.clearLineIndicators(bufferId, NAMESPACE);
+    state.hunks = [];
+    return;
+  }
+  await updateGitGutter(bufferId);
+}
 
-    // Get diff
-    const diffOutput = await getGitDiff(state.filePath);
-    editor.debug(`Git Gutter: diff output length = ${diffOutput.length}`);
-    if (diffOutput.length > 0 && diffOutput.length < 500) {
-      editor.debug(`Git Gutter: diff = ${diffOutput.replace(/\n/g, "\\n")}`);
+/**
+ * Diff the buffer against its committed version and redraw its indicators
+ */
+async function updateGitGutter(bufferId: number): Promise<void> {
+  const state = bufferStates.get(bufferId);
+  if (!state || state.headText === null) return;
+
+  const version = ++state.version;
+  const text = await editor.getBufferText(bufferId, 0, editor.getBufferLength(bufferId));
+  if (version !== state.version || state.headText === null) return;
+
+  const lines = text.split("\n");
+  const hunks = diffLines(state.headText.split("\n"), lines);
+  editor.debug(`Git Gutter: ${hunks.length} hunks in ${state.filePath}`);
+
+  // Clear existing indicators
+  editor.clearLineIndicators(bufferId, NAMESPACE);
+
+  // Apply new indicators
+  for (const hunk of hunks) {
+    const color = COLORS[hunk.type];
+    const symbol = SYMBOLS[hunk.type];
+    const [first, last] = hunkLines(hunk);
+    for (let line = first; line <= last; line++) {
+      editor.setLineIndicator(
+        bufferId,
+        line,
+        NAMESPACE,
+        symbol,
+        color[0],
+        color[1],
+        color[2],
+        PRIORITY
+      );
     }
-    const hunks = parseDiffOutput(diffOutput);
-    editor.debug(`Git Gutter: parsed ${hunks.length} hunks`);
+  }
 
-    // Clear existing indicators
-    editor.clearLineIndicators(bufferId, NAMESPACE);
+  state.lines = lines;
+  state.hunks = hunks;
+}
 
-    // Apply new indicators
-    for (const hunk of hunks) {
-      const color = COLORS[hunk.type];
-      const symbol = SYMBOLS[hunk.type];
-
-      if (hunk.type === "deleted") {
-        // Deleted indicator shows on a single line
-        // Line numbers are 1-indexed in diff, but 0-indexed in editor
-        const line = Math.max(0, hunk.startLine - 1);
-        editor.setLineIndicator(
-          bufferId,
-          line,
-          NAMESPACE,
-          symbol,
-          color[0],
-          color[1],
-          color[2],
-          PRIORITY
-        );
-      } else {
-        // Added/modified indicators show on each affected line
-        for (let i = 0; i < hunk.lineCount; i++) {
-          // Line numbers are 1-indexed in diff, but 0-indexed in editor
-          const line = hunk.startLine - 1 + i;
-          editor.setLineIndicator(
-            bufferId,
-            line,
-            NAMESPACE,
-            symbol,
-            color[0],
-            color[1],
-            color[2],
-            PRIORITY
-          );
-        }
-      }
-    }
+/**
+ * Update a buffer's indicators once edits pause
+ */
+async function scheduleUpdate(bufferId: number): Promise<void> {
+  const state = bufferStates.get(bufferId);
+  if (!state || state.headText === null) return;
 
-    state.hunks = hunks;
-  } finally {
-    state.updating = false;
+  const version = ++state.version;
+  await editor.delay(UPDATE_DELAY_MS);
+  if (version === state.version) {
+    await updateGitGutter(bufferId);
   }
 }
 
@@ -328,75 +362,55 @@ globalThis.onGitGutterAfterFileOpen = function (args: {
     return true;
   }
 
-  // Initialize state for this buffer
-  bufferStates.set(bufferId, {
-    filePath,
-    hunks: [],
-    updating: false,
-  });
-
-  // Update immediately (no debounce for file open)
-  updateGitGutter(bufferId);
+  ensureState(bufferId, filePath);
+  refreshGitGutter(bufferId);
 
   return true;
 };
 
 /**
- * Handle buffer activation - update if we have state but indicators might be stale
+ * Handle buffer activation - initialize state for buffers opened before the plugin
  */
 globalThis.onGitGutterBufferActivated = function (args: {
   buffer_id: number;
 }): boolean {
   const bufferId = args.buffer_id;
 
-  // If we don't have state yet, try to initialize from buffer path
   if (!bufferStates.has(bufferId)) {
     const filePath = editor.getBufferPath(bufferId);
     if (filePath && filePath !== "") {
-      bufferStates.set(bufferId, {
-        filePath,
-        hunks: [],
-        updating: false,
-      });
-      updateGitGutter(bufferId);
+      ensureState(bufferId, filePath);
+      refreshGitGutter(bufferId);
     }
   }
-  // If we already have state, the indicators should be current
-  // (they update on file open and save)
+  // If we already have state, the indicators are current
+  // (they update on edits, file open and save)
 
   return true;
 };
 
 /**
- * Handle after file save - refresh indicators
+ * Handle after file save - the file may now be tracked, or saved under a new path
  */
 globalThis.onGitGutterAfterSave = function (args: {
   buffer_id: number;
   path: string;
 }): boolean {
-  const bufferId = args.buffer_id;
-
-  // Update state with new path (in case of save-as)
-  const state = bufferStates.get(bufferId);
-  if (state) {
-    state.filePath = args.path;
-  } else {
-    bufferStates.set(bufferId, {
-      filePath: args.path,
-      hunks: [],
-      updating: false,
-    });
-  }
-
-  // Update immediately after save (no debounce)
-  updateGitGutter(bufferId);
+  ensureState(args.buffer_id, args.path);
+  refreshGitGutter(args.buffer_id);
 
   return true;
 };
 
-// Note: Git diff compares the file on disk, not the in-memory buffer.
-// Line indicators automatically track position changes via byte-position markers.
-// A full re-diff happens on save. For unsaved changes, see buffer_modified plugin.
+/**
+ * Handle edits - recompute indicators against the committed version
+ */
+globalThis.onGitGutterBufferChanged = function (args: {
+  buffer_id: number;
+}): boolean {
+  scheduleUpdate(args.buffer_id);
+  return true;
+};
 
 /**
  * Handle buffer closed - cleanup state
@@ -424,33 +438,132 @@ globalThis.git_gutter_refresh = function (): void {
     return;
   }
 
-  // Ensure state exists
-  if (!bufferStates.has(bufferId)) {
-    bufferStates.set(bufferId, {
-      filePath,
-      hunks: [],
-      updating: false,
-    });
-  }
+  ensureState(bufferId, filePath);
 
   // Force immediate update
-  updateGitGutter(bufferId).then(() => {
+  refreshGitGutter(bufferId).then(() => {
     const state = bufferStates.get(bufferId);
     const count = state?.hunks.length || 0;
     editor.setStatus(editor.t("status.changes", { count: String(count) }));
   });
 };
 
+/**
+ * Move the cursor to the next (or previous) change, wrapping around
+ */
+function gotoChange(forward: boolean): void {
+  const bufferId = editor.getActiveBufferId();
+  const state = bufferStates.get(bufferId);
+  if (!state || state.hunks.length === 0) {
+    editor.setStatus(editor.t("status.no_changes"));
+    return;
+  }
+
+  const cursorLine = editor.getCursorLine();
+  const starts = state.hunks.map((hunk) => hunkLines(hunk)[0]);
+  let index = forward ? 0 : starts.length - 1;
+  if (forward) {
+    const next = starts.findIndex((line) => line > cursorLine);
+    if (next !== -1) index = next;
+  } else {
+    for (let i = starts.length - 1; i >= 0; i--) {
+      if (starts[i] < cursorLine) {
+        index = i;
+        break;
+      }
+    }
+  }
+
+  editor.setBufferCursor(bufferId, lineOffset(state.lines, starts[index]));
+  editor.setStatus(
+    editor.t("status.change_position", {
+      index: String(index + 1),
+      count: String(starts.length),
+    })
+  );
+}
+
+globalThis.git_gutter_next_change = function (): void {
+  gotoChange(true);
+};
+
+globalThis.git_gutter_previous_change = function (): void {
+  gotoChange(false);
+};
+
+/**
+ * Restore the change at the cursor to its committed version
+ *
+ * The hunk is recomputed from the buffer as it is now, since the
+ * indicators lag behind typing, and replaced in one undoable edit.
+ */
+globalThis.git_gutter_revert_hunk = async function (): Promise<void> {
+  const bufferId = editor.getActiveBufferId();
+  const state = bufferStates.get(bufferId);
+  if (!state || state.headText === null) {
+    editor.setStatus(editor.t("status.no_change_at_cursor"));
+    return;
+  }
+
+  const headText = state.headText;
+  const current = await editor.getBufferText(bufferId, 0, editor.getBufferLength(bufferId));
+  const lines = current.split("\n");
+  const cursorLine = editor.getCursorLine();
+  const hunk = diffLines(headText.split("\n"), lines).find((candidate) => {
+    const [first, last] = hunkLines(candidate);
+    return cursorLine >= first && cursorLine <= last;
+  });
+  if (!hunk) {
+    editor.setStatus(editor.t("status.no_change_at_cursor"));
+    return;
+  }
+
+  // Swap the hunk's lines for the committed ones, then edit only the span
+  // that differs
+  const start = hunk.startLine - 1;
+  const reverted = [
+    ...lines.slice(0, start),
+    ...hunk.oldLines,
+    ...lines.slice(start + hunk.lineCount),
+  ].join("\n");
+
+  let prefix = 0;
+  while (
+    prefix < current.length &&
+    prefix < reverted.length &&
+    current[prefix] === reverted[prefix]
+  ) {
+    prefix++;
+  }
+  let suffix = 0;
+  while (
+    suffix < current.length - prefix &&
+    suffix < reverted.length - prefix &&
+    current[current.length - 1 - suffix] === reverted[reverted.length - 1 - suffix]
+  ) {
+    suffix++;
+  }
+
+  const from = editor.utf8ByteLength(current.substring(0, prefix));
+  const to = from + editor.utf8ByteLength(current.substring(prefix, current.length - suffix));
+  const replacement = reverted.substring(prefix, reverted.length - suffix);
+  editor.replaceRange(bufferId, from, to, replacement);
+  // The edit hooks recompute the indicators
+  editor.setStatus(editor.t("status.hunk_reverted"));
+};
+
 // =============================================================================
 // Registration
 // =============================================================================
 
 // Register event handlers
-// Note: No need to register after-insert/after-delete hooks - indicators
-// automatically track position changes via byte-position markers in the editor.
+// Line indicators track position changes via byte-position markers in the
+// editor, and edits recompute them once typing pauses.
 editor.on("after_file_open", "onGitGutterAfterFileOpen");
 editor.on("buffer_activated", "onGitGutterBufferActivated");
 editor.on("after_file_save", "onGitGutterAfterSave");
+editor.on("after_insert", "onGitGutterBufferChanged");
+editor.on("after_delete", "onGitGutterBufferChanged");
 editor.on("buffer_closed", "onGitGutterBufferClosed");
 
 // Register commands
@@ -461,16 +574,33 @@ editor.registerCommand(
   null
 );
 
+editor.registerCommand(
+  "%cmd.next_change",
+  "%cmd.next_change_desc",
+  "git_gutter_next_change",
+  null
+);
+
+editor.registerCommand(
+  "%cmd.previous_change",
+  "%cmd.previous_change_desc",
+  "git_gutter_previous_change",
+  null
+);
+
+editor.registerCommand(
+  "%cmd.revert_hunk",
+  "%cmd.revert_hunk_desc",
+  "git_gutter_revert_hunk",
+  null
+);
+
 // Initialize for the current buffer
 const initBufferId = editor.getActiveBufferId();
 const initPath = editor.getBufferPath(initBufferId);
 if (initPath && initPath !== "") {
-  bufferStates.set(initBufferId, {
-    filePath: initPath,
-    hunks: [],
-    updating: false,
-  });
-  updateGitGutter(initBufferId);
+  ensureState(initBufferId, initPath);
+  refreshGitGutter(initBufferId);
 }
 
 editor.debug("Git Gutter plugin loaded");
@@ -112,6 +112,25 @@ export interface FinderConfig<T> {
 
   /** Panel-specific: sync cursor with editor */
   syncWithEditor?: boolean;
+
+  /**
+   * Panel-specific: whether an item matches the editor cursor, for
+   * syncWithEditor (default: the item's location is the cursor's file and
+   * line). The last matching item is revealed, so an item listed after the
+   * items enclosing it wins over them.
+   */
+  matchEditorCursor?: (item: T, cursor: EditorCursor) => boolean;
+}
+
+/**
+ * Editor cursor position passed to matchEditorCursor
+ */
+export interface EditorCursor {
+  bufferId: number;
+  /** Byte offset */
+  position: number;
+  /** 1-based line */
+  line: number;
 }
 
 /**
@@ -1036,17 +1055,33 @@ export class Finder<T> {
         if (!self.isPanelMode || self.panelState.bufferId === null) return;
         if (data.buffer_id === self.panelState.bufferId) return;
 
-        const filePath = self.editor.getBufferPath(data.buffer_id);
-        if (!filePath) return;
-
-        // Find matching item
-        const matchingIndex = self.panelState.entries.findIndex((entry) => {
-          if (!entry.location) return false;
-          return (
-            entry.location.file === filePath &&
-            entry.location.line === data.line
-          );
-        });
+        let matchingIndex = -1;
+        const match = self.config.matchEditorCursor;
+        if (match) {
+          const cursor = {
+            bufferId: data.buffer_id,
+            position: data.new_position,
+            line: data.line,
+          };
+          for (let i = self.panelState.items.length - 1; i >= 0; i--) {
+            if (match(self.panelState.items[i], cursor)) {
+              matchingIndex = i;
+              break;
+            }
+          }
+        } else {
+          const filePath = self.editor.getBufferPath(data.buffer_id);
+          if (!filePath) return;
+
+          // Find matching item
+          matchingIndex = self.panelState.entries.findIndex((entry) => {
+            if (!entry.location) return false;
+            return (
+              entry.location.file === filePath &&
+              entry.location.line === data.line
+            );
+          });
+        }
 
         if (matchingIndex >= 0) {
           self.revealItem(matchingIndex);
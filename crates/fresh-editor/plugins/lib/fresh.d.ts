@@ -629,6 +629,33 @@ type SpawnResult = {
 	exit_code: number;
 };
 type TextPropertiesAtCursor = Array<Record<string, unknown>>;
+type TsDocumentSymbol = {
+	name: string;
+	/**
+	* What the symbol is, e.g. "function", "class", "struct" or "heading"
+	*/
+	kind: string;
+	/**
+	* Number of symbols this one is nested in (0 for top level)
+	*/
+	depth: number;
+	/**
+	* 1-based line of the symbol's name
+	*/
+	line: number;
+	/**
+	* Byte offset of the symbol's name
+	*/
+	offset: number;
+	/**
+	* Byte offset where the whole definition or section starts
+	*/
+	start: number;
+	/**
+	* Byte offset where the whole definition or section ends
+	*/
+	end: number;
+};
 type TsHighlightSpan = {
 	start: number;
 	end: number;
@@ -779,6 +806,10 @@ interface EditorAPI {
 	*/
 	deleteRange(bufferId: number, start: number, end: number): boolean;
 	/**
+	* Replace a range in a buffer with text, undone in one step
+	*/
+	replaceRange(bufferId: number, start: number, end: number, text: string): boolean;
+	/**
 	* Insert text at cursor position in active buffer
 	*/
 	insertAtCursor(text: string): boolean;
@@ -958,6 +989,21 @@ interface EditorAPI {
 	*/
 	getHighlights(bufferId: number, start: number, end: number): Promise<TsHighlightSpan[]>;
 	/**
+	* Request syntax highlights for a code string in the given language (async)
+	* 
+	* Span offsets are bytes into `code`. Resolves to an empty list when the
+	* language has no grammar.
+	*/
+	highlightCode(code: string, language: string): Promise<TsHighlightSpan[]>;
+	/**
+	* Request the outline of a buffer (async)
+	* 
+	* Lists functions, classes, structs and similar definitions parsed from
+	* code, or headings in markdown, in document order. Resolves to an empty
+	* list when the buffer's language has no outline.
+	*/
+	getDocumentSymbols(bufferId: number): Promise<TsDocumentSymbol[]>;
+	/**
 	* Add an overlay with styling options
 	* 
 	* Colors can be specified as RGB arrays `[r, g, b]` or theme key strings.
@@ -14,12 +14,23 @@ interface MarkdownConfig {
   composeWidth: number | null;
   maxWidth: number;
   hideLineNumbers: boolean;
+  imagePlaceholder: string;
+  wordsPerMinute: number;
+}
+
+// Terminals that draw emoji at the wrong width (or not at all) get a plain
+// bracketed label in front of images instead of the picture glyph
+function terminalHasEmojiWidth(): boolean {
+  const term = editor.getEnv("TERM") ?? "";
+  return !/^(linux|dumb|vt\d+)/.test(term);
 }
 
 const config: MarkdownConfig = {
   composeWidth: null,
   maxWidth: 100,
   hideLineNumbers: true,
+  imagePlaceholder: terminalHasEmojiWidth() ? "\u{1F5BC} " : "[img] ",
+  wordsPerMinute: 200,
 };
 
 // Table column widths stored per-buffer-per-split via setViewState/getViewState.
@@ -68,6 +79,154 @@ function clearTableWidths(bufferId: number): void {
   editor.setViewState(bufferId, "table-widths", null);
 }
 
+// Indent width of one list nesting level when none has been seen yet
+const DEFAULT_LIST_INDENT_UNIT = 2;
+
+// Helper: get the list nesting unit (smallest list indent seen) for this buffer
+function getListIndentUnit(bufferId: number): number {
+  const unit = editor.getViewState(bufferId, "list-indent-unit") as number | undefined;
+  return typeof unit === "number" && unit > 0 ? unit : DEFAULT_LIST_INDENT_UNIT;
+}
+
+// Fenced code block membership per line, stored per-buffer-per-split via
+// setViewState/getViewState like the table widths. A line's state depends on
+// every fence above it, so it's carried forward from the previous line.
+interface CodeLineInfo {
+  kind: 'text' | 'fence-open' | 'fence-close' | 'content';
+  lang: string;                // Fence info string's first word, lowercased
+}
+
+// Opening or closing fence: indent, backticks, spacing, language
+const CODE_FENCE_RE = /^(\s*)(```+)(\s*)([^\s`]*)/;
+
+// Helper: get cached code block membership for this buffer
+function getCodeLines(bufferId: number): Map<number, CodeLineInfo> | undefined {
+  const obj = editor.getViewState(bufferId, "code-lines") as Record<string, CodeLineInfo> | undefined;
+  if (!obj || typeof obj !== "object") return undefined;
+  const map = new Map<number, CodeLineInfo>();
+  for (const [k, v] of Object.entries(obj)) {
+    map.set(parseInt(k, 10), v);
+  }
+  return map;
+}
+
+// Helper: store cached code block membership
+function setCodeLines(bufferId: number, codeLines: Map<number, CodeLineInfo>): void {
+  const obj: Record<string, CodeLineInfo> = {};
+  for (const [k, v] of codeLines) {
+    obj[String(k)] = v;
+  }
+  editor.setViewState(bufferId, "code-lines", obj);
+}
+
+// Whether the line after one in this state is inside a code block
+function continuesCodeBlock(info: CodeLineInfo | undefined): boolean {
+  return info !== undefined && (info.kind === 'fence-open' || info.kind === 'content');
+}
+
+// How a line takes part in thematic breaks: paragraph text (which a setext
+// underline below turns into a heading), a setext underline, or a rule.
+// Cached per line in view state like code block membership, since a `---`
+// line's meaning depends on the line above it.
+type BreakLineKind = 'paragraph' | 'setext' | 'rule' | 'other';
+
+// Thematic break: three or more of the same `-`, `*` or `_`, optionally spaced
+const THEMATIC_BREAK_RE = /^ {0,3}([-*_])(?:[ \t]*\1){2,}[ \t]*$/;
+
+// Setext heading underline: a run of `=` or `-`
+const SETEXT_UNDERLINE_RE = /^ {0,3}(?:=+|-+)[ \t]*$/;
+
+// Lines that start some other block and so can't be setext heading text
+const NON_PARAGRAPH_RE = /^\s*(?:#{1,6}(?:\s|$)|[-*+][ \t]|\d+[.)][ \t]|>|\||```)/;
+
+// Helper: get cached thematic break line kinds for this buffer
+function getBreakLines(bufferId: number): Map<number, BreakLineKind> | undefined {
+  const obj = editor.getViewState(bufferId, "break-lines") as Record<string, BreakLineKind> | undefined;
+  if (!obj || typeof obj !== "object") return undefined;
+  const map = new Map<number, BreakLineKind>();
+  for (const [k, v] of Object.entries(obj)) {
+    map.set(parseInt(k, 10), v);
+  }
+  return map;
+}
+
+// Helper: store cached thematic break line kinds
+function setBreakLines(bufferId: number, breakLines: Map<number, BreakLineKind>): void {
+  const obj: Record<string, BreakLineKind> = {};
+  for (const [k, v] of breakLines) {
+    obj[String(k)] = v;
+  }
+  editor.setViewState(bufferId, "break-lines", obj);
+}
+
+// Block quote nesting per line, cached in view state like the thematic break
+// kinds: a lazy continuation line (quote paragraph text without its own `>`
+// markers) takes its depth from the line above.
+interface QuoteLineInfo {
+  depth: number;    // Nesting level, 0 outside block quotes
+  lazy: boolean;    // Continues the quote above without `>` markers
+  open: boolean;    // Ends in quote paragraph text that a lazy line can continue
+}
+
+// One block quote marker: up to three spaces of indent, `>`, one optional space
+const QUOTE_MARKER_RE = /^ {0,3}>[ \t]?/;
+
+// Gutter bar drawn for each block quote nesting level, and its color
+const QUOTE_BAR = "▌ ";
+const QUOTE_BAR_COLOR = "syntax.string";
+
+/** Char ranges of the block quote markers at the start of a line, outermost first. */
+function quoteMarkerRanges(lineContent: string): Array<{start: number; end: number}> {
+  const ranges: Array<{start: number; end: number}> = [];
+  let pos = 0;
+  let m: RegExpMatchArray | null;
+  while ((m = lineContent.slice(pos).match(QUOTE_MARKER_RE)) !== null) {
+    ranges.push({ start: pos, end: pos + m[0].length });
+    pos += m[0].length;
+  }
+  return ranges;
+}
+
+// Helper: get cached block quote nesting for this buffer
+function getQuoteLines(bufferId: number): Map<number, QuoteLineInfo> | undefined {
+  const obj = editor.getViewState(bufferId, "quote-lines") as Record<string, QuoteLineInfo> | undefined;
+  if (!obj || typeof obj !== "object") return undefined;
+  const map = new Map<number, QuoteLineInfo>();
+  for (const [k, v] of Object.entries(obj)) {
+    map.set(parseInt(k, 10), v);
+  }
+  return map;
+}
+
+// Helper: store cached block quote nesting
+function setQuoteLines(bufferId: number, quoteLines: Map<number, QuoteLineInfo>): void {
+  const obj: Record<string, QuoteLineInfo> = {};
+  for (const [k, v] of quoteLines) {
+    obj[String(k)] = v;
+  }
+  editor.setViewState(bufferId, "quote-lines", obj);
+}
+
+// Markup that compose mode conceals, each kind switchable by its own command:
+// emphasis (including inline code), links (with images and footnotes),
+// HTML entities, table pipes and padding, and code fences. The switches are
+// kept per-buffer-per-split in view state and survive leaving compose mode.
+type ConcealElement = 'emphasis' | 'links' | 'entities' | 'tables' | 'code';
+
+type ConcealSettings = Record<ConcealElement, boolean>;
+
+// Helper: get conceal switches for this buffer (everything on by default)
+function getConcealSettings(bufferId: number): ConcealSettings {
+  const settings: ConcealSettings = { emphasis: true, links: true, entities: true, tables: true, code: true };
+  const obj = editor.getViewState(bufferId, "conceal") as Partial<ConcealSettings> | undefined;
+  if (obj && typeof obj === "object") {
+    for (const key of Object.keys(settings) as ConcealElement[]) {
+      if (typeof obj[key] === "boolean") settings[key] = obj[key] as boolean;
+    }
+  }
+  return settings;
+}
+
 // Static map of named HTML entities to their Unicode replacements
 const HTML_ENTITY_MAP: Record<string, string> = {
   nbsp: "\u00A0", amp: "&", lt: "<", gt: ">", mdash: "\u2014", ndash: "\u2013",
@@ -80,6 +239,89 @@ const HTML_ENTITY_MAP: Record<string, string> = {
   laquo: "\u00AB", raquo: "\u00BB", ensp: "\u2002", emsp: "\u2003", thinsp: "\u2009",
 };
 
+const SUPERSCRIPT_DIGITS = "\u2070\u00B9\u00B2\u00B3\u2074\u2075\u2076\u2077\u2078\u2079";
+
+// Footnote definition at the start of a line: "[^label]: text"
+const FOOTNOTE_DEF_RE = /^(\s*)\[\^([^\]\s]+)\]:[ \t]?/;
+
+// Bullet glyphs for nested list depths 0, 1, 2 (deeper levels cycle)
+const LIST_BULLET_GLYPHS = ["\u2022", "\u25E6", "\u25AA"];
+
+// Leading indent and marker of a bullet or ordered list item
+const LIST_MARKER_RE = /^([ \t]*)([-*+]|\d+\.)[ \t]+/;
+
+// Task list item: indent, bullet, spacing, then the checkbox state character
+const TASK_MARKER_RE = /^([ \t]*)([-*+])([ \t]+)\[([ xX])\](?=\s|$)[ \t]*/;
+
+// Checkbox glyphs for unchecked and checked task items
+const TASK_UNCHECKED_GLYPH = "\u2610";
+const TASK_CHECKED_GLYPH = "\u2611";
+
+/** Visual width of leading indentation, counting a tab as four columns. */
+function indentWidth(indent: string): number {
+  let width = 0;
+  for (const ch of indent) width += ch === '\t' ? 4 : 1;
+  return width;
+}
+
+interface ListMarkerConceal {
+  end: number;            // char offset just past the marker and its spacing
+  replacement: string;    // indent for the item's depth + glyph or number
+  hangingIndent: number;  // visible column where the item text starts
+  isTask: boolean;        // task items stay rendered unless the cursor is in the marker
+}
+
+/**
+ * Compute how the indent and marker of a list item line render in compose
+ * mode: two columns per nesting level, then a bullet glyph chosen by depth
+ * (ordered items keep their number, task items show a checkbox).
+ */
+function listMarkerConceal(lineContent: string, indentUnit: number): ListMarkerConceal | null {
+  const task = lineContent.match(TASK_MARKER_RE);
+  if (task) {
+    const depth = Math.floor(indentWidth(task[1]) / indentUnit);
+    const glyph = task[4] === ' ' ? TASK_UNCHECKED_GLYPH : TASK_CHECKED_GLYPH;
+    const replacement = ' '.repeat(depth * 2) + glyph + ' ';
+    return { end: task[0].length, replacement, hangingIndent: replacement.length, isTask: true };
+  }
+
+  const m = lineContent.match(LIST_MARKER_RE);
+  if (!m) return null;
+
+  const depth = Math.floor(indentWidth(m[1]) / indentUnit);
+  const isOrdered = /^\d/.test(m[2]);
+  const glyph = isOrdered ? m[2] : LIST_BULLET_GLYPHS[depth % LIST_BULLET_GLYPHS.length];
+  const replacement = ' '.repeat(depth * 2) + glyph + ' ';
+  return { end: m[0].length, replacement, hangingIndent: replacement.length, isTask: false };
+}
+
+/**
+ * Whether a list marker shows as written: any cursor on the line exposes a
+ * bullet or number, but a task checkbox only while a cursor is inside it, so
+ * toggling it from the item text shows the new glyph straight away.
+ */
+function listMarkerExposed(
+  marker: ListMarkerConceal,
+  lineContent: string,
+  byteStart: number,
+  byteEnd: number,
+  cursors: number[],
+): boolean {
+  const end = marker.isTask ? charToByte(lineContent, marker.end, byteStart) : byteEnd + 1;
+  return cursors.some(c => c >= byteStart && c < end);
+}
+
+/**
+ * Visible indicator for a footnote label: superscript digits for numeric
+ * labels ("[^12]" → "¹²"), a caret prefix for named ones ("[^note]" → "^note").
+ */
+function footnoteIndicator(label: string): string {
+  if (/^\d+$/.test(label)) {
+    return Array.from(label, d => SUPERSCRIPT_DIGITS[d.charCodeAt(0) - 48]).join('');
+  }
+  return `^${label}`;
+}
+
 // =============================================================================
 // Block-based parser for hanging indent support
 // =============================================================================
@@ -87,7 +329,7 @@ const HTML_ENTITY_MAP: Record<string, string> = {
 interface ParsedBlock {
   type: 'paragraph' | 'list-item' | 'ordered-list' | 'checkbox' | 'blockquote' |
         'heading' | 'code-fence' | 'code-content' | 'hr' | 'empty' | 'image' |
-        'table-row';
+        'table-row' | 'footnote-def';
   startByte: number;           // First byte of the line
   endByte: number;             // Byte after last char (before newline)
   leadingIndent: number;       // Spaces before marker/content
@@ -212,14 +454,14 @@ function parseMarkdownBlocks(text: string): ParsedBlock[] {
     }
 
     // Checkbox: - [ ] or - [x]
-    const checkboxMatch = line.match(/^(\s*)([-*+])\s+(\[[ x]\])\s+(.*)$/);
+    const checkboxMatch = line.match(/^(\s*)([-*+])\s+(\[[ xX]\])\s+(.*)$/);
     if (checkboxMatch) {
       const leadingIndent = checkboxMatch[1].length;
       const bullet = checkboxMatch[2];
       const checkbox = checkboxMatch[3];
       const marker = bullet + ' ' + checkbox + ' ';
       const content = checkboxMatch[4];
-      const checked = checkbox === '[x]';
+      const checked = checkbox !== '[ ]';
       blocks.push({
         type: 'checkbox',
         startByte: lineStart,
@@ -305,6 +547,28 @@ function parseMarkdownBlocks(text: string): ParsedBlock[] {
       continue;
     }
 
+    // Footnote definition: [^1]: text
+    const footnoteMatch = line.match(FOOTNOTE_DEF_RE);
+    if (footnoteMatch) {
+      const leadingIndent = footnoteMatch[1].length;
+      const marker = footnoteMatch[0].slice(leadingIndent);
+      blocks.push({
+        type: 'footnote-def',
+        startByte: lineStart,
+        endByte: lineEnd,
+        leadingIndent,
+        marker,
+        markerStartByte: lineStart + leadingIndent,
+        contentStartByte: lineStart + leadingIndent + marker.length,
+        content: line.slice(leadingIndent + marker.length),
+        // Continuation lines align after the concealed "¹ " indicator
+        hangingIndent: leadingIndent + footnoteIndicator(footnoteMatch[2]).length + 1,
+        forceHardBreak: true,
+      });
+      byteOffset = lineEnd + 1;
+      continue;
+    }
+
     // Image: ![alt](url)
     if (trimmed.match(/^!\[.*\]\(.*\)$/)) {
       blocks.push({
@@ -370,6 +634,49 @@ function isMarkdownFile(path: string): boolean {
 }
 
 
+// Count words in markdown source: whitespace-separated tokens that contain at
+// least one letter or digit, so list bullets, heading hashes, fences, table
+// pipes and checked task boxes don't count.
+function countWords(text: string): number {
+  let words = 0;
+  for (const token of text.split(/\s+/)) {
+    if (/[\p{L}\p{N}]/u.test(token) && !/^\[[xX]\]$/.test(token)) words++;
+  }
+  return words;
+}
+
+// Word count currently in the status bar, and buffers with a count in flight
+// (value = whether another edit arrived while it was running)
+let shownWordCount: { bufferId: number; words: number } | null = null;
+const wordCountsInFlight = new Map<number, boolean>();
+
+// Show the whole-document word count and reading time in the status bar.
+// Reads the buffer asynchronously; edits made while a count is running
+// coalesce into one recount. The status is only touched when the count
+// changes, and nothing here triggers a re-render of the content area.
+async function updateWordCount(bufferId: number): Promise<void> {
+  if (wordCountsInFlight.has(bufferId)) {
+    wordCountsInFlight.set(bufferId, true);
+    return;
+  }
+  wordCountsInFlight.set(bufferId, false);
+  try {
+    const text = await editor.getBufferText(bufferId, 0, editor.getBufferLength(bufferId));
+    const words = countWords(text);
+    const shown = shownWordCount;
+    const changed = !shown || shown.bufferId !== bufferId || shown.words !== words;
+    if (changed && isComposing(bufferId) && editor.getActiveBufferId() === bufferId) {
+      shownWordCount = { bufferId, words };
+      const minutes = words === 0 ? 0 : Math.max(1, Math.ceil(words / config.wordsPerMinute));
+      editor.setStatus(editor.t("status.word_count", { words: String(words), minutes: String(minutes) }));
+    }
+  } finally {
+    const again = wordCountsInFlight.get(bufferId);
+    wordCountsInFlight.delete(bufferId);
+    if (again) updateWordCount(bufferId);
+  }
+}
+
 // Enable full compose mode for a buffer (explicit toggle or restore from session).
 // Idempotent: safe to call when already in compose mode (re-applies line numbers,
 // line wrap, and layout hints — needed after session restore where Rust already has
@@ -394,6 +701,7 @@ function enableMarkdownCompose(bufferId: number): void {
 
   // Trigger a refresh so lines_changed hooks fire for visible content
   editor.refreshLines(bufferId);
+  updateWordCount(bufferId);
   editor.debug(`Markdown compose enabled for buffer ${bufferId}`);
 }
 
@@ -402,6 +710,12 @@ function disableMarkdownCompose(bufferId: number): void {
   if (isComposing(bufferId)) {
     editor.setViewState(bufferId, "last-cursor-line", null);
     clearTableWidths(bufferId);
+    editor.setViewState(bufferId, "list-indent-unit", null);
+    editor.setViewState(bufferId, "code-lines", null);
+    editor.setViewState(bufferId, "break-lines", null);
+    editor.setViewState(bufferId, "quote-lines", null);
+    editor.setViewState(bufferId, "folds", null);
+    if (shownWordCount?.bufferId === bufferId) shownWordCount = null;
 
     // Tell Rust side this buffer is back in source mode
     editor.setViewMode(bufferId, "source");
@@ -414,6 +728,7 @@ function disableMarkdownCompose(bufferId: number): void {
     editor.clearNamespace(bufferId, "md-emphasis");
     editor.clearConcealNamespace(bufferId, "md-syntax");
     editor.clearSoftBreakNamespace(bufferId, "md-wrap");
+    editor.clearConcealNamespace(bufferId, "md-fold");
 
     editor.refreshLines(bufferId);
     editor.debug(`Markdown compose disabled for buffer ${bufferId}`);
@@ -620,7 +935,8 @@ function charToByte(lineContent: string, charOffset: number, lineByteStart: numb
 // ---------------------------------------------------------------------------
 
 interface InlineSpan {
-  type: 'code' | 'bold-italic' | 'bold' | 'italic' | 'strikethrough' | 'link' | 'entity';
+  type: 'code' | 'bold-italic' | 'bold' | 'italic' | 'strikethrough' | 'link' | 'image' |
+        'entity' | 'footnote-ref' | 'footnote-def';
   matchStart: number;    // char offset of full match start
   matchEnd: number;      // char offset of full match end
   contentStart: number;  // char offset of visible content start
@@ -629,6 +945,26 @@ interface InlineSpan {
   linkUrl?: string;
 }
 
+// The conceal switch that governs a span's markup
+function spanConcealElement(type: InlineSpan['type']): ConcealElement {
+  switch (type) {
+    case 'link':
+    case 'image':
+    case 'footnote-ref':
+    case 'footnote-def':
+      return 'links';
+    case 'entity':
+      return 'entities';
+    default:
+      return 'emphasis';
+  }
+}
+
+// A span's conceal ranges, or none when its element is switched off
+function spanConcealRanges(span: InlineSpan, settings: ConcealSettings): InlineSpan['concealRanges'] {
+  return settings[spanConcealElement(span.type)] ? span.concealRanges : [];
+}
+
 /** Find all inline spans that would produce conceals in the given text. */
 function findInlineSpans(text: string): InlineSpan[] {
   const spans: InlineSpan[] = [];
@@ -685,7 +1021,40 @@ function findInlineSpans(text: string): InlineSpan[] {
     }
   }
 
-  // 3. Links
+  // 3. Images: ![alt](url) → placeholder + alt text, linked to the URL.
+  // With no alt text, the file name at the end of the URL is shown instead.
+  const imageRe = /!\[([^\]]*)\]\(([^)\s]+)(?:\s+"[^"]*")?\)/g;
+  while ((m = imageRe.exec(text)) !== null) {
+    if (inCodeSpan(m.index)) continue;
+    const ms = m.index;
+    const me = ms + m[0].length;
+    let contentStart = ms + 2;
+    let contentEnd = contentStart + m[1].length;
+    if (m[1].trim().length === 0) {
+      // Show the URL's file name: its last path segment, minus query and fragment
+      const urlStart = contentEnd + 2; // past "]("
+      const path = m[2].replace(/[?#].*$/, '').replace(/\/+$/, '');
+      contentStart = urlStart + path.lastIndexOf('/') + 1;
+      contentEnd = urlStart + path.length;
+      if (contentEnd <= contentStart) {
+        // No file name to show — fall back to the whole URL
+        contentStart = urlStart;
+        contentEnd = urlStart + m[2].length;
+      }
+    }
+    spans.push({
+      type: 'image',
+      matchStart: ms, matchEnd: me,
+      contentStart, contentEnd,
+      concealRanges: [
+        { start: ms, end: contentStart, replacement: config.imagePlaceholder },
+        { start: contentEnd, end: me, replacement: null },
+      ],
+      linkUrl: m[2],
+    });
+  }
+
+  // 4. Links
   const linkRe = /(?<!!)\[([^\]]+)\]\(([^)]+)\)/g;
   while ((m = linkRe.exec(text)) !== null) {
     if (inCodeSpan(m.index)) continue;
@@ -704,7 +1073,33 @@ function findInlineSpans(text: string): InlineSpan[] {
     });
   }
 
-  // 4. HTML entities
+  // 5. Footnotes: "[^1]" references and the "[^1]: " label of a definition line
+  const footnoteDef = text.match(FOOTNOTE_DEF_RE);
+  if (footnoteDef) {
+    const ms = footnoteDef[1].length;
+    const me = footnoteDef[0].length;
+    spans.push({
+      type: 'footnote-def',
+      matchStart: ms, matchEnd: me,
+      contentStart: ms, contentEnd: me,
+      concealRanges: [{ start: ms, end: me, replacement: footnoteIndicator(footnoteDef[2]) + ' ' }],
+    });
+  }
+  const footnoteRefRe = /\[\^([^\]\s]+)\]/g;
+  while ((m = footnoteRefRe.exec(text)) !== null) {
+    if (inCodeSpan(m.index)) continue;
+    if (footnoteDef && m.index === footnoteDef[1].length) continue;
+    const ms = m.index;
+    const me = ms + m[0].length;
+    spans.push({
+      type: 'footnote-ref',
+      matchStart: ms, matchEnd: me,
+      contentStart: ms, contentEnd: me,
+      concealRanges: [{ start: ms, end: me, replacement: footnoteIndicator(m[1]) }],
+    });
+  }
+
+  // 6. HTML entities
   const namedEntityRe = /&(nbsp|amp|lt|gt|mdash|ndash|hellip|rsquo|lsquo|rdquo|ldquo|bull|middot|copy|reg|trade|times|divide|plusmn|deg|frac12|frac14|rarr|larr|harr|uarr|darr|euro|pound|yen|cent|sect|para|laquo|raquo|ensp|emsp|thinsp);/g;
   while ((m = namedEntityRe.exec(text)) !== null) {
     if (inCodeSpan(m.index)) continue;
@@ -750,10 +1145,10 @@ function findInlineSpans(text: string): InlineSpan[] {
  * Used for table column width calculation so emphasis/link syntax is not
  * counted towards cell width.
  */
-function concealedText(text: string): string {
+function concealedText(text: string, settings: ConcealSettings): string {
   const ranges: Array<{start: number; end: number; replacement: string | null}> = [];
   for (const span of findInlineSpans(text)) {
-    ranges.push(...span.concealRanges);
+    ranges.push(...spanConcealRanges(span, settings));
   }
   ranges.sort((a, b) => a.start - b.start);
 
@@ -842,11 +1237,75 @@ function processLineConceals(
   // *this* line.  Used for table row auto-expose to avoid exposing the
   // previous row's emphasis markers.
   const cursorStrictlyOnLine = cursors.some(c => c >= byteStart && c < byteEnd);
-
-  // Skip lines inside code fences (we'd need multi-line context for this;
-  // for now, detect fence lines and code content lines)
+  const conceal = getConcealSettings(bufferId);
+
+  // --- Fenced code blocks ---
+  // Contents are styled by highlightCodeBlocks; fences hide their backticks
+  // and show the language as a label until the cursor enters them.
+  const codeLine = lineNumber !== undefined ? getCodeLines(bufferId)?.get(lineNumber) : undefined;
+  if (codeLine && codeLine.kind !== 'text') {
+    const fence = codeLine.kind !== 'content' ? lineContent.match(CODE_FENCE_RE) : null;
+    if (fence && conceal.code && !cursorOnLine) {
+      const ticksStart = fence[1].length;
+      const labelStart = ticksStart + fence[2].length + fence[3].length;
+      const labelEnd = labelStart + fence[4].length;
+      editor.addConceal(bufferId, "md-syntax",
+        charToByte(lineContent, ticksStart, byteStart),
+        charToByte(lineContent, labelStart, byteStart), null);
+      if (labelEnd > labelStart) {
+        editor.addOverlay(bufferId, "md-emphasis",
+          charToByte(lineContent, labelStart, byteStart),
+          charToByte(lineContent, labelEnd, byteStart),
+          { fg: "syntax.comment", italic: true });
+      }
+    }
+    return;
+  }
   const trimmed = lineContent.trim();
-  if (trimmed.startsWith('```')) return; // fence line itself
+  if (trimmed.startsWith('```')) return; // fence line without block context
+
+  // --- Thematic breaks and setext headings ---
+  // A rule becomes a full-width line of box drawing; a setext underline
+  // disappears and the paragraph line above it is styled as a heading.
+  if (lineNumber !== undefined) {
+    const breakLines = getBreakLines(bufferId);
+    const kind = breakLines?.get(lineNumber);
+    if (kind === 'rule' || kind === 'setext') {
+      if (!cursorOnLine) {
+        const textEnd = byteStart + editor.utf8ByteLength(lineContent.replace(/\r?\n$/, ''));
+        let rule: string | null = null;
+        if (kind === 'rule') {
+          const viewport = editor.getViewport();
+          rule = '\u2500'.repeat(config.composeWidth ?? (viewport ? viewport.width : 80));
+        }
+        editor.addConceal(bufferId, "md-syntax", byteStart, textEnd, rule);
+      }
+      return;
+    }
+    if (kind === 'paragraph' && breakLines?.get(lineNumber + 1) === 'setext') {
+      const textEnd = byteStart + editor.utf8ByteLength(lineContent.replace(/\r?\n$/, ''));
+      editor.addOverlay(bufferId, "md-emphasis", byteStart, textEnd, { fg: "syntax.keyword", bold: true });
+    }
+  }
+
+  // --- Block quotes ---
+  // Each `>` marker becomes a colored bar in the gutter. Lazy continuation
+  // lines have no markers, so their bars go in front of the first character
+  // (see the end of this function). A cursor on the line shows the markers.
+  const quote = lineNumber !== undefined ? getQuoteLines(bufferId)?.get(lineNumber) : undefined;
+  let lazyQuoteBars: string | null = null;
+  if (quote && quote.depth > 0 && !cursorOnLine) {
+    if (quote.lazy) {
+      lazyQuoteBars = QUOTE_BAR.repeat(quote.depth);
+    } else {
+      for (const range of quoteMarkerRanges(lineContent)) {
+        const rStart = charToByte(lineContent, range.start, byteStart);
+        const rEnd = charToByte(lineContent, range.end, byteStart);
+        editor.addConceal(bufferId, "md-syntax", rStart, rEnd, QUOTE_BAR);
+        editor.addOverlay(bufferId, "md-emphasis", rStart, rStart + 1, { fg: QUOTE_BAR_COLOR });
+      }
+    }
+  }
 
   // --- Table row handling ---
   // Always apply table conceals even when cursor is on the line.
@@ -855,7 +1314,7 @@ function processLineConceals(
   // break cursor navigation (stuck cursor, ghost cursors) and lose alignment.
   const truncatedByteRanges: Array<{start: number; end: number}> = [];
   let isTableRow = false;
-  if (trimmed.startsWith('|') || trimmed.endsWith('|')) {
+  if (conceal.tables && (trimmed.startsWith('|') || trimmed.endsWith('|'))) {
     isTableRow = true;
     const isSeparator = /^\|[-:\s|]+\|$/.test(trimmed);
 
@@ -878,7 +1337,7 @@ function processLineConceals(
       let maxVisualLines = 1;
       for (let ci = 0; ci < numCols; ci++) {
         // When cursor is on the row, use raw text (emphasis markers revealed).
-        const cellText = cursorStrictlyOnLine ? cells[ci].trim() : concealedText(cells[ci]).trim();
+        const cellText = cursorStrictlyOnLine ? cells[ci].trim() : concealedText(cells[ci], conceal).trim();
         const wrapW = Math.max(1, colWidths[ci] - 2); // 1 leading + 1 trailing space margin
         const wrapped = wrapText(cellText, wrapW);
         cellWrapped.push(wrapped);
@@ -961,7 +1420,7 @@ function processLineConceals(
           let padding = "";
           const cellIdx = pipeIdx - 1;
           if (!cursorStrictlyOnLine && colWidths && pipeIdx > 0 && cellIdx < cells.length && cellIdx < colWidths.length) {
-            const cellText = concealedText(cells[cellIdx]);
+            const cellText = concealedText(cells[cellIdx], conceal);
             const cellWidth = cellText.length;
             const allocatedWidth = colWidths[cellIdx];
 
@@ -1003,14 +1462,13 @@ function processLineConceals(
     if (handledByWrapping) return;
   }
 
-  // --- Image links: ![alt](url) → "Image: alt — url" ---
-  const imageRe = /^!\[([^\]]*)\]\(([^)]+)\)$/;
-  const imageMatch = trimmed.match(imageRe);
-  if (imageMatch && !cursorOnLine) {
-    const alt = imageMatch[1];
-    const url = imageMatch[2];
-    editor.addConceal(bufferId, "md-syntax", byteStart, byteEnd, `Image: ${alt} — ${url}`);
-    return;
+  // --- List markers: depth-proportional indent, per-depth bullets, checkboxes ---
+  if (!isTableRow) {
+    const listMarker = listMarkerConceal(lineContent, getListIndentUnit(bufferId));
+    if (listMarker && !listMarkerExposed(listMarker, lineContent, byteStart, byteEnd, cursors)) {
+      const markerEnd = charToByte(lineContent, listMarker.end, byteStart);
+      editor.addConceal(bufferId, "md-syntax", byteStart, markerEnd, listMarker.replacement);
+    }
   }
 
   // --- Inline spans: code, emphasis, links, entities ---
@@ -1044,12 +1502,23 @@ function processLineConceals(
         editor.addOverlay(bufferId, "md-emphasis", byteCS, byteCE, { strikethrough: true });
         break;
       case 'link':
+      case 'image':
         editor.addOverlay(bufferId, "md-emphasis", byteCS, byteCE, {
           fg: "syntax.link",
           underline: true,
           url: span.linkUrl,
         });
         break;
+      case 'footnote-ref':
+        editor.addOverlay(bufferId, "md-emphasis", byteCS, byteCE, { fg: "syntax.link" });
+        break;
+      case 'footnote-def':
+        // Style the whole definition so consecutive ones read as a footnotes block
+        editor.addOverlay(bufferId, "md-emphasis", byteMS, byteEnd, {
+          fg: "syntax.comment",
+          italic: true,
+        });
+        break;
       // entities: no overlay
     }
 
@@ -1061,13 +1530,30 @@ function processLineConceals(
     const cursorInSpan = cursors.some(c => c >= byteMS && c <= byteME);
     const skipConceal = (isTableRow && cursorStrictlyOnLine) || cursorInSpan;
     if (!skipConceal) {
-      for (const range of span.concealRanges) {
+      for (const range of spanConcealRanges(span, conceal)) {
         const rStart = charToByte(lineContent, range.start, byteStart);
         const rEnd = charToByte(lineContent, range.end, byteStart);
-        editor.addConceal(bufferId, "md-syntax", rStart, rEnd, range.replacement);
+        let replacement = range.replacement;
+        if (lazyQuoteBars !== null && range.start === 0) {
+          // Markup at the start of a lazy quote line carries the bars
+          replacement = lazyQuoteBars + (replacement ?? '');
+          lazyQuoteBars = null;
+        }
+        editor.addConceal(bufferId, "md-syntax", rStart, rEnd, replacement);
       }
     }
   }
+
+  // Lazy quote line starting with plain text: its first character is
+  // replaced by the bars followed by the character itself
+  if (lazyQuoteBars !== null) {
+    const first = String.fromCodePoint(lineContent.codePointAt(0)!);
+    editor.addConceal(bufferId, "md-syntax", byteStart,
+      charToByte(lineContent, first.length, byteStart), lazyQuoteBars + first);
+  }
+  if (quote?.lazy && !cursorOnLine) {
+    editor.addOverlay(bufferId, "md-emphasis", byteStart, byteStart + 1, { fg: QUOTE_BAR_COLOR });
+  }
 }
 
 // Last cursor line is tracked per-buffer-per-split via setViewState/getViewState
@@ -1095,6 +1581,14 @@ function processLineSoftBreaks(
   // Clear existing soft breaks for this line range
   editor.clearSoftBreaksInRange(bufferId, byteStart, byteEnd);
 
+  // Code keeps its own line structure
+  const codeLine = lineNumber !== undefined ? getCodeLines(bufferId)?.get(lineNumber) : undefined;
+  if (codeLine && codeLine.kind !== 'text') return;
+
+  // Rules and setext underlines are replaced whole by processLineConceals
+  const breakKind = lineNumber !== undefined ? getBreakLines(bufferId)?.get(lineNumber) : undefined;
+  if (breakKind === 'rule' || breakKind === 'setext') return;
+
   const viewport = editor.getViewport();
   if (!viewport) return;
   const width = config.composeWidth ?? viewport.width;
@@ -1119,8 +1613,10 @@ function processLineSoftBreaks(
     }
   }
 
+  const conceal = getConcealSettings(bufferId);
+
   // Table row wrapping: add soft breaks for multi-line cells
-  if (block.type === 'table-row' && lineNumber !== undefined) {
+  if (block.type === 'table-row' && conceal.tables && lineNumber !== undefined) {
     const trimmedLine = lineContent.trim();
     const isSep = /^\|[-:\s|]+\|$/.test(trimmedLine);
     if (!isSep) {
@@ -1136,7 +1632,7 @@ function processLineSoftBreaks(
         const numCols = Math.min(tableCells.length, colWidths.length);
         const cursorOnTableLine = cursors.some(c => c >= byteStart && c < byteEnd);
         for (let ci = 0; ci < numCols; ci++) {
-          const cellText = cursorOnTableLine ? tableCells[ci].trim() : concealedText(tableCells[ci]).trim();
+          const cellText = cursorOnTableLine ? tableCells[ci].trim() : concealedText(tableCells[ci], conceal).trim();
           const wrapW = Math.max(1, colWidths[ci] - 2);
           const wrapped = wrapText(cellText, wrapW);
           maxVisualLines = Math.max(maxVisualLines, wrapped.length);
@@ -1166,22 +1662,52 @@ function processLineSoftBreaks(
 
   if (noWrap) return;
 
-  const hangingIndent = block.hangingIndent;
+  let hangingIndent = block.hangingIndent;
 
   // Compute per-character visual width so concealed markup (emphasis
   // markers, link syntax, entities) doesn't count towards line width.
   const spans = findInlineSpans(lineContent);
   const charW = new Array<number>(lineContent.length).fill(1);
-  for (const span of spans) {
-    for (const range of span.concealRanges) {
-      for (let c = range.start; c < range.end && c < lineContent.length; c++) {
-        charW[c] = 0;
-      }
-      // Entity replacements contribute their replacement's length
-      if (range.replacement !== null && range.start < lineContent.length) {
-        charW[range.start] = range.replacement.length;
+  const concealRanges = spans.flatMap(span => spanConcealRanges(span, conceal));
+
+  // List markers are re-indented by depth unless exposed by the cursor
+  // (same condition as processLineConceals); wrapped lines hang under the
+  // item text as rendered, not as written.
+  if (block.type === 'list-item' || block.type === 'ordered-list' || block.type === 'checkbox') {
+    const listMarker = listMarkerConceal(lineContent, getListIndentUnit(bufferId));
+    if (listMarker && !listMarkerExposed(listMarker, lineContent, byteStart, byteEnd, cursors)) {
+      concealRanges.push({ start: 0, end: listMarker.end, replacement: listMarker.replacement });
+      hangingIndent = listMarker.hangingIndent;
+    }
+  }
+
+  // Block quote markers become one bar per nesting level unless exposed by
+  // the cursor (same condition as processLineConceals), so the quote text
+  // wraps in the width left beside the bars and hangs under itself.
+  const quote = lineNumber !== undefined ? getQuoteLines(bufferId)?.get(lineNumber) : undefined;
+  const quoteConcealed = quote !== undefined && quote.depth > 0 &&
+    !cursors.some(c => c >= byteStart && c <= byteEnd);
+  if (quoteConcealed) {
+    if (!quote.lazy) {
+      for (const range of quoteMarkerRanges(lineContent)) {
+        concealRanges.push({ ...range, replacement: QUOTE_BAR });
       }
     }
+    hangingIndent = quote.depth * QUOTE_BAR.length;
+  }
+
+  for (const range of concealRanges) {
+    for (let c = range.start; c < range.end && c < lineContent.length; c++) {
+      charW[c] = 0;
+    }
+    // Replacements (entities, list markers) contribute their own length
+    if (range.replacement !== null && range.start < lineContent.length) {
+      charW[range.start] = range.replacement.length;
+    }
+  }
+  // A lazy quote line's bars are drawn in front of its first character
+  if (quoteConcealed && quote.lazy && lineContent.length > 0) {
+    charW[0] += quote.depth * QUOTE_BAR.length;
   }
 
   // Walk through the line content and find word-wrap break points
@@ -1216,6 +1742,29 @@ function processLineSoftBreaks(
   }
 }
 
+/**
+ * Track the list nesting unit: the smallest non-zero indent of a list item
+ * seen so far. Like table widths it only ever narrows, so it converges as
+ * the user scrolls. Returns true if already-rendered lines need a refresh.
+ */
+function processListIndentUnit(
+  bufferId: number,
+  lines: Array<{ content: string }>,
+): boolean {
+  let smallest = Infinity;
+  for (const line of lines) {
+    const m = line.content.match(LIST_MARKER_RE);
+    const width = m ? indentWidth(m[1]) : 0;
+    if (width > 0) smallest = Math.min(smallest, width);
+  }
+  if (smallest === Infinity) return false;
+
+  const stored = editor.getViewState(bufferId, "list-indent-unit") as number | undefined;
+  if (typeof stored === "number" && stored <= smallest) return false;
+  editor.setViewState(bufferId, "list-indent-unit", smallest);
+  return typeof stored === "number";
+}
+
 /**
  * Pre-compute column widths for table groups in a batch of lines.
  * Groups consecutive table rows and computes max visible width per column.
@@ -1359,6 +1908,243 @@ function processTableAlignment(
   return needsRefresh;
 }
 
+/**
+ * Record which of a batch of lines are code block fences or contents.
+ *
+ * Each line continues the state of the line above it, taken from this batch
+ * or from the cache. Returns true when a line's state changed in a way that
+ * affects cached lines below it that aren't in this batch (e.g. a fence was
+ * typed or deleted), so already-rendered lines need processing again.
+ */
+function processCodeBlocks(
+  bufferId: number,
+  lines: Array<{ line_number: number; content: string }>,
+): boolean {
+  const codeLines = getCodeLines(bufferId) ?? new Map<number, CodeLineInfo>();
+  const delivered = new Set(lines.map(l => l.line_number));
+  let needsRefresh = false;
+
+  for (const line of lines) {
+    const above = codeLines.get(line.line_number - 1);
+    const inside = continuesCodeBlock(above);
+    const fence = line.content.match(CODE_FENCE_RE);
+
+    let info: CodeLineInfo;
+    if (fence && inside) {
+      info = { kind: 'fence-close', lang: above!.lang };
+    } else if (fence) {
+      info = { kind: 'fence-open', lang: fence[4].toLowerCase() };
+    } else if (inside) {
+      info = { kind: 'content', lang: above!.lang };
+    } else {
+      info = { kind: 'text', lang: '' };
+    }
+
+    const old = codeLines.get(line.line_number);
+    const below = line.line_number + 1;
+    if (old && (continuesCodeBlock(old) !== continuesCodeBlock(info) || old.lang !== info.lang) &&
+        !delivered.has(below) && codeLines.has(below)) {
+      needsRefresh = true;
+    }
+    codeLines.set(line.line_number, info);
+  }
+
+  setCodeLines(bufferId, codeLines);
+  return needsRefresh;
+}
+
+/**
+ * Classify delivered lines for thematic breaks (see BreakLineKind). Needs
+ * the code block state for these lines. Returns true if a cached neighbour
+ * that wasn't delivered renders differently now and needs a refresh.
+ */
+function processThematicBreaks(
+  bufferId: number,
+  lines: Array<{ line_number: number; content: string }>,
+): boolean {
+  const breakLines = getBreakLines(bufferId) ?? new Map<number, BreakLineKind>();
+  const codeLines = getCodeLines(bufferId);
+  const delivered = new Set(lines.map(l => l.line_number));
+  let needsRefresh = false;
+
+  for (const line of lines) {
+    const content = line.content.replace(/\r?\n$/, '');
+    const codeLine = codeLines?.get(line.line_number);
+    let kind: BreakLineKind;
+    if ((codeLine && codeLine.kind !== 'text') || content.trim() === '') {
+      kind = 'other';
+    } else if (SETEXT_UNDERLINE_RE.test(content) &&
+               breakLines.get(line.line_number - 1) === 'paragraph') {
+      kind = 'setext';
+    } else if (THEMATIC_BREAK_RE.test(content)) {
+      kind = 'rule';
+    } else if (NON_PARAGRAPH_RE.test(content)) {
+      kind = 'other';
+    } else {
+      kind = 'paragraph';
+    }
+
+    // A paragraph line styles as a heading when underlined, and an underline
+    // only counts under a paragraph, so changes show on both neighbours
+    const old = breakLines.get(line.line_number);
+    if (old !== undefined && old !== kind) {
+      for (const neighbour of [line.line_number - 1, line.line_number + 1]) {
+        if (!delivered.has(neighbour) && breakLines.has(neighbour)) needsRefresh = true;
+      }
+    }
+    breakLines.set(line.line_number, kind);
+  }
+
+  setBreakLines(bufferId, breakLines);
+  return needsRefresh;
+}
+
+/**
+ * Record the block quote nesting of a batch of lines (see QuoteLineInfo).
+ * Needs the code block state for these lines. Returns true when a change
+ * carries over to a cached line below that wasn't delivered, e.g. a `>`
+ * typed above a lazy continuation line.
+ */
+function processBlockQuotes(
+  bufferId: number,
+  lines: Array<{ line_number: number; content: string }>,
+): boolean {
+  const quoteLines = getQuoteLines(bufferId) ?? new Map<number, QuoteLineInfo>();
+  const codeLines = getCodeLines(bufferId);
+  const delivered = new Set(lines.map(l => l.line_number));
+  let needsRefresh = false;
+
+  for (const line of lines) {
+    const content = line.content.replace(/\r?\n$/, '');
+    const codeLine = codeLines?.get(line.line_number);
+    const above = quoteLines.get(line.line_number - 1);
+    const markers = quoteMarkerRanges(content);
+
+    let info: QuoteLineInfo = { depth: 0, lazy: false, open: false };
+    if (codeLine && codeLine.kind !== 'text') {
+      // Code blocks aren't quoted
+    } else if (markers.length > 0) {
+      const rest = content.slice(markers[markers.length - 1].end);
+      info = { depth: markers.length, lazy: false, open: rest.trim() !== '' };
+    } else if (above?.open && content.trim() !== '' &&
+               !NON_PARAGRAPH_RE.test(content) && !THEMATIC_BREAK_RE.test(content)) {
+      info = { depth: above.depth, lazy: true, open: true };
+    }
+
+    const old = quoteLines.get(line.line_number);
+    const below = line.line_number + 1;
+    if (old && (old.depth !== info.depth || old.open !== info.open) &&
+        !delivered.has(below) && quoteLines.has(below)) {
+      needsRefresh = true;
+    }
+    quoteLines.set(line.line_number, info);
+  }
+
+  setQuoteLines(bufferId, quoteLines);
+  return needsRefresh;
+}
+
+// Highlight results by language and code, so re-rendering an unchanged block
+// (e.g. on every cursor move) restyles it synchronously without flicker
+const codeHighlightCache = new Map<string, TsHighlightSpan[]>();
+const CODE_HIGHLIGHT_CACHE_LIMIT = 64;
+
+// Latest highlight request per "bufferId:line"; older responses are dropped
+const codeHighlightRequests = new Map<string, number>();
+let nextCodeHighlightRequest = 0;
+
+/**
+ * Style code block contents in a batch of lines with the syntax highlighter
+ * for the fence's language. Consecutive content lines of one block are
+ * highlighted together. Blocks without a known language get a subtle
+ * background instead.
+ */
+function highlightCodeBlocks(
+  bufferId: number,
+  lines: Array<{ line_number: number; byte_start: number; content: string }>,
+): void {
+  const codeLines = getCodeLines(bufferId);
+  if (!codeLines) return;
+
+  // Group consecutive content lines of the same block
+  const runs: Array<{ lang: string; lines: typeof lines }> = [];
+  let lastLineNum = -2;
+  for (const line of lines) {
+    const info = codeLines.get(line.line_number);
+    if (!info || info.kind !== 'content') continue;
+    const run = runs[runs.length - 1];
+    if (run && run.lang === info.lang && line.line_number === lastLineNum + 1) {
+      run.lines.push(line);
+    } else {
+      runs.push({ lang: info.lang, lines: [line] });
+    }
+    lastLineNum = line.line_number;
+  }
+
+  for (const run of runs) {
+    // Code text of the run (newlines normalised) and each line's offset in it
+    const texts = run.lines.map(l => l.content.replace(/\r?\n$/, ''));
+    const offsets: number[] = [];
+    let code = '';
+    for (const text of texts) {
+      offsets.push(editor.utf8ByteLength(code));
+      code += text + '\n';
+    }
+
+    const apply = (spans: TsHighlightSpan[]) => {
+      if (spans.length === 0) {
+        for (const line of run.lines) {
+          editor.addOverlay(bufferId, "md-emphasis", line.byte_start,
+            line.byte_start + editor.utf8ByteLength(line.content.replace(/\r?\n$/, '')),
+            { bg: "editor.current_line_bg", extend_to_line_end: true });
+        }
+        return;
+      }
+      for (const span of spans) {
+        // Spans may cross lines (block comments); split them per line
+        for (let i = 0; i < run.lines.length; i++) {
+          const lineLen = editor.utf8ByteLength(texts[i]);
+          const start = Math.max(span.start, offsets[i]);
+          const end = Math.min(span.end, offsets[i] + lineLen);
+          if (start >= end) continue;
+          const base = run.lines[i].byte_start - offsets[i];
+          editor.addOverlay(bufferId, "md-emphasis", base + start, base + end, {
+            fg: span.color,
+            bold: span.bold,
+            italic: span.italic,
+          });
+        }
+      }
+    };
+
+    // Supersede any request still in flight for these lines
+    const request = nextCodeHighlightRequest++;
+    const keys = run.lines.map(l => `${bufferId}:${l.line_number}`);
+    for (const key of keys) codeHighlightRequests.set(key, request);
+
+    if (run.lang === '') {
+      apply([]);
+      continue;
+    }
+
+    const cacheKey = `${run.lang}\0${code}`;
+    const cached = codeHighlightCache.get(cacheKey);
+    if (cached) {
+      apply(cached);
+      continue;
+    }
+
+    editor.highlightCode(code, run.lang).then(spans => {
+      if (codeHighlightCache.size >= CODE_HIGHLIGHT_CACHE_LIMIT) codeHighlightCache.clear();
+      codeHighlightCache.set(cacheKey, spans);
+      // Lines re-rendered since the request belong to a newer one
+      if (keys.some(key => codeHighlightRequests.get(key) !== request)) return;
+      for (const key of keys) codeHighlightRequests.delete(key);
+      apply(spans);
+    });
+  }
+}
+
 // lines_changed: called for newly visible or invalidated lines
 globalThis.onMarkdownLinesChanged = function(data: {
   buffer_id: number;
@@ -1385,13 +2171,20 @@ globalThis.onMarkdownLinesChanged = function(data: {
   // pick up the wider columns. The second pass will be a no-op (widths
   // already converged) so this doesn't loop.
   const tableWidthsGrew = processTableAlignment(data.buffer_id, data.lines);
+  const listUnitChanged = processListIndentUnit(data.buffer_id, data.lines);
+  const codeBlocksChanged = processCodeBlocks(data.buffer_id, data.lines);
+  const breaksChanged = processThematicBreaks(data.buffer_id, data.lines);
+  const quotesChanged = processBlockQuotes(data.buffer_id, data.lines);
 
   for (const line of data.lines) {
     processLineConceals(data.buffer_id, line.content, line.byte_start, line.byte_end, cursors, line.line_number);
     processLineSoftBreaks(data.buffer_id, line.content, line.byte_start, line.byte_end, cursors, line.line_number);
   }
+  highlightCodeBlocks(data.buffer_id, data.lines);
+  // Per-line clears above drop any fold conceal overlapping those lines
+  applyFolds(data.buffer_id);
 
-  if (tableWidthsGrew) {
+  if (tableWidthsGrew || listUnitChanged || codeBlocksChanged || breaksChanged || quotesChanged) {
     editor.refreshLines(data.buffer_id);
   }
 };
@@ -1401,7 +2194,8 @@ globalThis.onMarkdownLinesChanged = function(data: {
 // causing lines_changed to fire on the next render. processLineConceals
 // handles clearing and rebuilding atomically.
 // Marker-based positions auto-adjust with buffer edits, so existing conceals
-// remain visually correct until lines_changed rebuilds them.
+// remain visually correct until lines_changed rebuilds them. Folds are stored
+// as plain offsets in view state, so they're shifted here.
 globalThis.onMarkdownAfterInsert = function(data: {
   buffer_id: number;
   position: number;
@@ -1411,9 +2205,13 @@ globalThis.onMarkdownAfterInsert = function(data: {
 }): void {
   if (!isComposingInAnySplit(data.buffer_id)) return;
   editor.debug(`[mc] after_insert: pos=${data.position} text="${data.text.replace(/\n/g,'\\n')}" affected=${data.affected_start}..${data.affected_end}`);
+  const len = editor.utf8ByteLength(data.text);
+  shiftFolds(data.buffer_id, pos => pos >= data.position ? pos + len : pos);
+  updateWordCount(data.buffer_id);
 };
 
-// after_delete: no-op for conceals/overlays (same reasoning as after_insert).
+// after_delete: no-op for conceals/overlays (same reasoning as after_insert);
+// shifts folds, dropping any whose heading was deleted.
 globalThis.onMarkdownAfterDelete = function(data: {
   buffer_id: number;
   start: number;
@@ -1424,6 +2222,8 @@ globalThis.onMarkdownAfterDelete = function(data: {
 }): void {
   if (!isComposingInAnySplit(data.buffer_id)) return;
   editor.debug(`[mc] after_delete: start=${data.start} end=${data.end} deleted="${data.deleted_text.replace(/\n/g,'\\n')}" affected_start=${data.affected_start} deleted_len=${data.deleted_len}`);
+  shiftFolds(data.buffer_id, pos => pos >= data.end ? pos - data.deleted_len : Math.min(pos, data.start));
+  updateWordCount(data.buffer_id);
 };
 
 // cursor_moved: update cursor-aware reveal/conceal for old and new cursor lines
@@ -1441,6 +2241,22 @@ globalThis.onMarkdownCursorMoved = function(data: {
 
   editor.debug(`[mc] cursor_moved: old_pos=${data.old_position} new_pos=${data.new_position} line=${data.line} prevLine=${prevLine}`);
 
+  // Moving up onto a folded heading row lands on the newline that ends the
+  // hidden text; keep the cursor on the heading instead. Any other position
+  // inside a fold (search, go to line) opens it.
+  const folds = getFolds(data.buffer_id);
+  for (const fold of folds) {
+    if (data.new_position === fold.end) {
+      editor.setBufferCursor(data.buffer_id, fold.headingEnd);
+      return;
+    }
+  }
+  const open = folds.filter(f => !(data.new_position > f.headingEnd && data.new_position < f.end));
+  if (open.length !== folds.length) {
+    setFolds(data.buffer_id, open);
+    applyFolds(data.buffer_id);
+  }
+
   // Always refresh: even intra-line movements need conceal updates because
   // auto-expose is span-level (cursor entering/leaving an emphasis or link
   // span within the same line must toggle its syntax markers).
@@ -1565,6 +2381,184 @@ globalThis.onMarkdownComposeWidthConfirmed = function(args: {
   }
 };
 
+// =============================================================================
+// Section folding
+// =============================================================================
+
+// A folded heading. Byte offsets are kept current through after_insert and
+// after_delete, so folds survive edits elsewhere in the buffer.
+interface FoldInfo {
+  start: number;        // Heading line start
+  headingEnd: number;   // The heading's newline; hidden text starts here
+  end: number;          // Newline before the next same-or-higher heading (kept)
+  lines: number;        // Number of hidden lines
+}
+
+const HEADING_RE = /^(#{1,6})[ \t]/;
+
+// Helper: get folded sections for this buffer (per-buffer-per-split view state)
+function getFolds(bufferId: number): FoldInfo[] {
+  const folds = editor.getViewState(bufferId, "folds") as FoldInfo[] | undefined;
+  return Array.isArray(folds) ? folds : [];
+}
+
+// Helper: store folded sections for this buffer
+function setFolds(bufferId: number, folds: FoldInfo[]): void {
+  editor.setViewState(bufferId, "folds", folds.length > 0 ? folds : null);
+}
+
+// Move fold offsets through an edit; folds whose heading was deleted are dropped
+function shiftFolds(bufferId: number, shift: (pos: number) => number): void {
+  const folds = getFolds(bufferId);
+  if (folds.length === 0) return;
+  const shifted: FoldInfo[] = [];
+  for (const fold of folds) {
+    const start = shift(fold.start);
+    const headingEnd = shift(fold.headingEnd);
+    const end = shift(fold.end);
+    if (headingEnd > start && end > headingEnd) {
+      shifted.push({ start, headingEnd, end, lines: fold.lines });
+    }
+  }
+  setFolds(bufferId, shifted);
+}
+
+// Conceal the body of every folded section behind a marker on its heading row.
+// Each fold hides its line breaks too, so the section collapses into one row.
+function applyFolds(bufferId: number): void {
+  editor.clearConcealNamespace(bufferId, "md-fold");
+  for (const fold of getFolds(bufferId)) {
+    const marker = editor.t("fold.lines_hidden", { count: String(fold.lines) });
+    editor.addConceal(bufferId, "md-fold", fold.headingEnd, fold.end, ` ${marker}`);
+  }
+}
+
+/**
+ * Find the section under the heading starting at `start`, given the buffer
+ * text from that point on. The section runs to the next heading of the same
+ * or a higher level (ignoring `#` lines inside code blocks) or the end of
+ * the buffer. Returns null if the line isn't a heading or has no body.
+ */
+function findSection(text: string, start: number): FoldInfo | null {
+  const lines = text.split('\n');
+  const heading = lines[0].match(HEADING_RE);
+  if (!heading) return null;
+  const level = heading[1].length;
+
+  const headingEnd = start + editor.utf8ByteLength(lines[0]);
+  let offset = headingEnd + 1;
+  let inCodeBlock = false;
+  for (let i = 1; i < lines.length; i++) {
+    const line = lines[i];
+    if (line.trim().startsWith('```')) inCodeBlock = !inCodeBlock;
+    const next = inCodeBlock ? null : line.match(HEADING_RE);
+    if (next && next[1].length <= level) {
+      return i > 1 ? { start, headingEnd, end: offset - 1, lines: i - 1 } : null;
+    }
+    offset += editor.utf8ByteLength(line) + 1;
+  }
+
+  // Runs to the end of the buffer; a trailing newline stays visible
+  const trailingNewline = lines[lines.length - 1] === '';
+  const hidden = lines.length - 1 - (trailingNewline ? 1 : 0);
+  const end = start + editor.utf8ByteLength(text) - (trailingNewline ? 1 : 0);
+  return hidden > 0 ? { start, headingEnd, end, lines: hidden } : null;
+}
+
+// Fold or unfold the section under the heading at the cursor
+globalThis.markdownToggleFoldSection = async function(): Promise<void> {
+  const bufferId = editor.getActiveBufferId();
+  if (!isComposing(bufferId)) {
+    editor.setStatus(editor.t("status.fold_needs_compose"));
+    return;
+  }
+
+  const lineStart = await editor.getLineStartPosition(editor.getCursorLine());
+  if (lineStart === null) return;
+
+  const folds = getFolds(bufferId);
+  const existing = folds.find(f => f.start === lineStart);
+  if (existing) {
+    setFolds(bufferId, folds.filter(f => f !== existing));
+  } else {
+    const text = await editor.getBufferText(bufferId, lineStart, editor.getBufferLength(bufferId));
+    if (!HEADING_RE.test(text)) {
+      editor.setStatus(editor.t("status.fold_not_heading"));
+      return;
+    }
+    const section = findSection(text, lineStart);
+    if (!section) {
+      editor.setStatus(editor.t("status.fold_empty"));
+      return;
+    }
+    // A fold inside the new one would hide text twice; the outer fold wins
+    setFolds(bufferId, [
+      ...folds.filter(f => f.start < section.start || f.start > section.end),
+      section,
+    ]);
+  }
+
+  applyFolds(bufferId);
+  editor.refreshLines(bufferId);
+};
+
+// Check or uncheck the task list item at the cursor. Only the checkbox
+// character is edited, so lines_changed re-renders just this line.
+globalThis.markdownToggleTask = async function(): Promise<void> {
+  const bufferId = editor.getActiveBufferId();
+  const line = editor.getCursorLine();
+  const lineStart = await editor.getLineStartPosition(line);
+  const lineEnd = await editor.getLineEndPosition(line);
+  if (lineStart === null || lineEnd === null) return;
+
+  const text = await editor.getBufferText(bufferId, lineStart, lineEnd);
+  const m = text.match(TASK_MARKER_RE);
+  if (!m) {
+    editor.setStatus(editor.t("status.not_task_item"));
+    return;
+  }
+
+  // Everything before the state character is ASCII, so chars == bytes
+  const statePos = lineStart + m[1].length + m[2].length + m[3].length + 1;
+  editor.deleteRange(bufferId, statePos, statePos + 1);
+  editor.insertText(bufferId, statePos, m[4] === ' ' ? 'x' : ' ');
+};
+
+// Switch concealing of one kind of markup on or off for the current buffer.
+// Lines are re-rendered in place, so the cursor stays where it is.
+function toggleConceal(element: ConcealElement): void {
+  const bufferId = editor.getActiveBufferId();
+  const settings = getConcealSettings(bufferId);
+  settings[element] = !settings[element];
+  editor.setViewState(bufferId, "conceal", settings);
+
+  const name = editor.t(`conceal.${element}`);
+  editor.setStatus(editor.t(settings[element] ? "status.conceal_on" : "status.conceal_off", { element: name }));
+  if (isComposing(bufferId)) {
+    editor.refreshLines(bufferId);
+  }
+}
+
+globalThis.markdownToggleConcealEmphasis = function(): void {
+  toggleConceal('emphasis');
+};
+
+globalThis.markdownToggleConcealLinks = function(): void {
+  toggleConceal('links');
+};
+
+globalThis.markdownToggleConcealEntities = function(): void {
+  toggleConceal('entities');
+};
+
+globalThis.markdownToggleConcealTables = function(): void {
+  toggleConceal('tables');
+};
+
+globalThis.markdownToggleConcealCode = function(): void {
+  toggleConceal('code');
+};
+
 // Register commands
 editor.registerCommand(
   "%cmd.toggle_compose",
@@ -1580,5 +2574,54 @@ editor.registerCommand(
   null
 );
 
+editor.registerCommand(
+  "%cmd.toggle_fold_section",
+  "%cmd.toggle_fold_section_desc",
+  "markdownToggleFoldSection",
+  null
+);
+
+editor.registerCommand(
+  "%cmd.toggle_task",
+  "%cmd.toggle_task_desc",
+  "markdownToggleTask",
+  null
+);
+
+editor.registerCommand(
+  "%cmd.toggle_conceal_emphasis",
+  "%cmd.toggle_conceal_emphasis_desc",
+  "markdownToggleConcealEmphasis",
+  null
+);
+
+editor.registerCommand(
+  "%cmd.toggle_conceal_links",
+  "%cmd.toggle_conceal_links_desc",
+  "markdownToggleConcealLinks",
+  null
+);
+
+editor.registerCommand(
+  "%cmd.toggle_conceal_entities",
+  "%cmd.toggle_conceal_entities_desc",
+  "markdownToggleConcealEntities",
+  null
+);
+
+editor.registerCommand(
+  "%cmd.toggle_conceal_tables",
+  "%cmd.toggle_conceal_tables_desc",
+  "markdownToggleConcealTables",
+  null
+);
+
+editor.registerCommand(
+  "%cmd.toggle_conceal_code",
+  "%cmd.toggle_conceal_code_desc",
+  "markdownToggleConcealCode",
+  null
+);
+
 // Initialization
 editor.debug("Markdown Compose plugin loaded - use 'Markdown: Toggle Compose' command");
@@ -23,6 +23,7 @@ use serde_json::Value;
 use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
+use super::diagnostics::LSP_DIAGNOSTICS_SOURCE;
 use super::types::{LspMessageEntry, LspProgressInfo};
 use super::Editor;
 
@@ -65,15 +66,16 @@ impl Editor {
 // =============================================================================
 
 impl Editor {
-    /// Store and apply diagnostics, emit hook for plugins
-    fn store_and_apply_diagnostics(&mut self, uri: String, diagnostics: Vec<Diagnostic>) {
+    /// Replace the diagnostics `source` reports for a file, then store and
+    /// apply those of all sources together and emit hook for plugins
+    pub(super) fn store_and_apply_diagnostics(
+        &mut self,
+        uri: String,
+        source: &str,
+        diagnostics: Vec<Diagnostic>,
+    ) {
         // Store diagnostics for later retrieval by plugins
-        if diagnostics.is_empty() {
-            self.stored_diagnostics.remove(&uri);
-        } else {
-            self.stored_diagnostics
-                .insert(uri.clone(), diagnostics.clone());
-        }
+        let diagnostics = self.merge_diagnostic_source(&uri, source, diagnostics);
 
         if let Some(buffer_id) = self.apply_diagnostics_to_buffer(&uri, &diagnostics) {
             tracing::info!(
@@ -102,7 +104,7 @@ impl Editor {
             diagnostics.len(),
             uri
         );
-        self.store_and_apply_diagnostics(uri, diagnostics);
+        self.store_and_apply_diagnostics(uri, LSP_DIAGNOSTICS_SOURCE, diagnostics);
     }
 
     /// Handle LSP pulled diagnostics (pull model - LSP 3.17+)
@@ -134,7 +136,7 @@ impl Editor {
             self.diagnostic_result_ids.insert(uri.clone(), result_id);
         }
 
-        self.store_and_apply_diagnostics(uri, diagnostics);
+        self.store_and_apply_diagnostics(uri, LSP_DIAGNOSTICS_SOURCE, diagnostics);
     }
 }
 
@@ -14,7 +14,9 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use crate::app::warning_domains::WarningDomain;
+use crate::input::goto_line::GotoLineTarget;
 use crate::model::event::{BufferId, Event, LeafId};
+use crate::primitives::display_width::{byte_offset_at_visual_column, str_width};
 use crate::state::EditorState;
 use crate::view::prompt::PromptType;
 use crate::view::split::SplitViewState;
@@ -78,6 +80,11 @@ impl Editor {
         // Show appropriate status message for binary vs regular files
         if is_binary {
             self.status_message = Some(t!("buffer.opened_binary", name = display_name).to_string());
+        } else if self.is_write_protected(buffer_id) {
+            self.status_message =
+                Some(t!("buffer.opened_read_only", name = display_name).to_string());
+        } else if let Some(message) = self.decode_error_message(buffer_id) {
+            self.status_message = Some(message);
         } else {
             self.status_message = Some(t!("buffer.opened", name = display_name).to_string());
         }
@@ -85,6 +92,26 @@ impl Editor {
         Ok(buffer_id)
     }
 
+    /// Warning for a buffer whose file had bytes that could not be decoded
+    pub(super) fn decode_error_message(&self, buffer_id: BufferId) -> Option<String> {
+        let buffer = &self.buffers.get(&buffer_id)?.buffer;
+        let offset = buffer.invalid_byte_offset()?;
+        let name = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .map(|m| m.display_name.clone())
+            .unwrap_or_default();
+        Some(
+            t!(
+                "buffer.decode_error",
+                name = name,
+                encoding = buffer.encoding().display_name(),
+                offset = offset
+            )
+            .to_string(),
+        )
+    }
+
     /// Open a file without switching focus to it
     ///
     /// Creates a new buffer for the file (or returns existing buffer ID if already open)
@@ -219,6 +246,12 @@ impl Editor {
             tracing::info!("Detected binary file: {}", path.display());
         }
 
+        // Files the user can't write open read-only until Edit Anyway
+        let write_protected = file_exists && !is_binary && !self.filesystem.is_writable(path);
+        if write_protected {
+            state.editing_disabled = true;
+        }
+
         // Set show_whitespace_tabs, use_tabs, and tab_size based on language config
         // with fallback to global editor config for tab_size
         // Use the buffer's stored language (already set by from_file_with_languages)
@@ -251,6 +284,10 @@ impl Editor {
             metadata.read_only = true;
             metadata.disable_lsp(t!("buffer.binary_file").to_string());
         }
+        if write_protected {
+            metadata.read_only = true;
+            metadata.write_protected = true;
+        }
 
         // Notify LSP about the newly opened file (skip for binary files)
         if !is_binary {
@@ -260,6 +297,11 @@ impl Editor {
         // Store metadata for this buffer
         self.buffer_metadata.insert(buffer_id, metadata);
 
+        // Check the file with the diagnostics providers (skip for binary files)
+        if !is_binary {
+            self.run_diagnostics_providers(buffer_id);
+        }
+
         // Add buffer to the preferred split's tabs (but don't switch to it)
         // Uses preferred_split_for_file() to avoid opening in labeled splits (e.g., sidebars)
         let target_split = self.preferred_split_for_file();
@@ -618,6 +660,7 @@ impl Editor {
     /// This looks up the file's saved state from the global file states store
     /// and applies it to both the EditorState (cursor) and SplitViewState (viewport).
     fn restore_global_file_state(&mut self, buffer_id: BufferId, path: &Path, split_id: LeafId) {
+        use super::workspace::{clamp_saved_offset, clamp_saved_top_byte};
         use crate::workspace::PersistedFileWorkspace;
 
         // Load the per-file state for this path (lazy load from disk)
@@ -626,21 +669,24 @@ impl Editor {
             None => return, // No saved state for this file
         };
 
-        // Get the buffer to validate positions
-        let max_pos = match self.buffers.get(&buffer_id) {
-            Some(buffer) => buffer.buffer.len(),
-            None => return,
+        // Validate positions against the buffer, which may have changed on disk
+        let Some(buffer) = self.buffers.get_mut(&buffer_id).map(|b| &mut b.buffer) else {
+            return;
         };
+        let cursor_pos = clamp_saved_offset(buffer, file_state.cursor.position);
+        let anchor = file_state
+            .cursor
+            .anchor
+            .map(|a| clamp_saved_offset(buffer, a));
+        let top_byte = clamp_saved_top_byte(buffer, file_state.scroll.top_byte);
 
         // Apply cursor position and viewport (scroll) state to SplitViewState
         if let Some(view_state) = self.split_view_states.get_mut(&split_id) {
             if let Some(buf_state) = view_state.keyed_states.get_mut(&buffer_id) {
-                let cursor_pos = file_state.cursor.position.min(max_pos);
                 buf_state.cursors.primary_mut().position = cursor_pos;
-                buf_state.cursors.primary_mut().anchor =
-                    file_state.cursor.anchor.map(|a| a.min(max_pos));
+                buf_state.cursors.primary_mut().anchor = anchor;
             }
-            view_state.viewport.top_byte = file_state.scroll.top_byte;
+            view_state.viewport.top_byte = top_byte;
             view_state.viewport.left_column = file_state.scroll.left_column;
         }
     }
@@ -759,7 +805,22 @@ impl Editor {
                 // Small file mode: use exact line position
                 let max_line = state.buffer.line_count().unwrap_or(1).saturating_sub(1);
                 let actual_line = target_line.min(max_line);
-                state.buffer.line_col_to_position(actual_line, target_col)
+                // Clamp the column before the line ending so a column past
+                // the end stays on this line
+                let line_len = state
+                    .buffer
+                    .get_line(actual_line)
+                    .map(|bytes| {
+                        let ending = bytes
+                            .iter()
+                            .rev()
+                            .take_while(|&&b| b == b'\n' || b == b'\r');
+                        bytes.len() - ending.count()
+                    })
+                    .unwrap_or(0);
+                state
+                    .buffer
+                    .line_col_to_position(actual_line, target_col.min(line_len))
             };
 
             let event = Event::MoveCursor {
@@ -769,7 +830,7 @@ impl Editor {
                 old_anchor,
                 new_anchor: None,
                 old_sticky_column,
-                new_sticky_column: target_col,
+                new_sticky_column: Some(target_col),
             };
 
             let split_id = self.split_manager.active_split();
@@ -779,6 +840,116 @@ impl Editor {
         }
     }
 
+    /// Jump to a Go to Line target and center it in the view.
+    ///
+    /// Relative jumps keep the cursor's goal column, like moving up or down;
+    /// absolute and percentage jumps go to the start of the line. Returns the
+    /// 1-based line jumped to.
+    pub fn goto_line_target(&mut self, target: GotoLineTarget) -> usize {
+        let line = self.resolve_goto_line_target(target);
+        self.record_position_in_history();
+        let keep_column = matches!(target, GotoLineTarget::Relative(_))
+            && self.active_state().buffer.line_count().is_some();
+        if keep_column {
+            self.goto_line_keeping_column(line);
+        } else {
+            self.goto_line_col(line, None);
+        }
+        self.center_cursor_in_view();
+        line
+    }
+
+    /// The 1-based line a Go to Line target points at from the primary cursor
+    pub(crate) fn resolve_goto_line_target(&self, target: GotoLineTarget) -> usize {
+        let estimated_line_length = self.config.editor.estimated_line_length.max(1);
+        let buffer = &self.active_state().buffer;
+        let current_line = buffer.get_line_number(self.active_cursors().primary().position) + 1;
+        // Large files have no line index; estimate like goto_line_col does
+        let line_count = buffer
+            .line_count()
+            .unwrap_or_else(|| buffer.len() / estimated_line_length + 1);
+        target.resolve(current_line, line_count)
+    }
+
+    /// Move the primary cursor to a 1-based line, staying in its goal
+    /// visual column (sticky column) where the line is long enough
+    fn goto_line_keeping_column(&mut self, line: usize) {
+        let cursor = *self.active_cursors().primary();
+        let cursor_id = self.active_cursors().primary_id();
+        let buffer = &self.active_state().buffer;
+
+        let line_text = |line: usize| {
+            let bytes = buffer.get_line(line).unwrap_or_default();
+            String::from_utf8_lossy(&bytes)
+                .trim_end_matches(['\n', '\r'])
+                .to_string()
+        };
+        let goal_column = cursor.sticky_column.unwrap_or_else(|| {
+            let current_line = buffer.get_line_number(cursor.position);
+            let line_start = buffer.line_start_offset(current_line).unwrap_or(0);
+            let text = line_text(current_line);
+            let column = cursor.position.saturating_sub(line_start);
+            let before: String = text
+                .char_indices()
+                .take_while(|(idx, _)| *idx < column)
+                .map(|(_, ch)| ch)
+                .collect();
+            str_width(&before)
+        });
+
+        let target_line = line.saturating_sub(1);
+        let Some(line_start) = buffer.line_start_offset(target_line) else {
+            self.goto_line_col(line, None);
+            return;
+        };
+        let position =
+            line_start + byte_offset_at_visual_column(&line_text(target_line), goal_column);
+
+        let event = Event::MoveCursor {
+            cursor_id,
+            old_position: cursor.position,
+            new_position: position,
+            old_anchor: cursor.anchor,
+            new_anchor: None,
+            old_sticky_column: cursor.sticky_column,
+            new_sticky_column: Some(goal_column),
+        };
+        let buffer_id = self.active_buffer();
+        let split_id = self.split_manager.active_split();
+        let state = self.buffers.get_mut(&buffer_id).unwrap();
+        let view_state = self.split_view_states.get_mut(&split_id).unwrap();
+        state.apply(&mut view_state.cursors, &event);
+    }
+
+    /// Scroll the active split so the primary cursor is centered.
+    ///
+    /// Puts `top_byte` half a screen of source lines above the cursor, then
+    /// lets rendering finish the job on view lines, where wrapped lines and
+    /// compose-mode transforms are known (see `Viewport::center_cursor`).
+    fn center_cursor_in_view(&mut self) {
+        let buffer_id = self.active_buffer();
+        let split_id = self.split_manager.active_split();
+        let estimated_line_length = self.config.editor.estimated_line_length;
+        let (Some(state), Some(view_state)) = (
+            self.buffers.get_mut(&buffer_id),
+            self.split_view_states.get_mut(&split_id),
+        ) else {
+            return;
+        };
+
+        let cursor = view_state.cursors.primary().position;
+        let rows_above = view_state.viewport.visible_line_count() / 2;
+        let mut iter = state.buffer.line_iterator(cursor, estimated_line_length);
+        for _ in 0..rows_above {
+            if iter.prev().is_none() {
+                break;
+            }
+        }
+        view_state.viewport.top_byte = iter.current_position();
+        view_state.viewport.top_view_line_offset = 0;
+        view_state.viewport.center_cursor = true;
+    }
+
     /// Create a new empty buffer
     pub fn new_buffer(&mut self) -> BufferId {
         // Save current position before switching to new buffer
@@ -1466,7 +1637,20 @@ impl Editor {
                 .set_split_buffer(split_id, replacement_buffer);
         }
 
-        self.buffers.remove(&id);
+        let closed_path = self
+            .buffers
+            .remove(&id)
+            .and_then(|state| state.buffer.file_path().map(Path::to_path_buf));
+        // Stop watching the file once no buffer shows it
+        if let Some(path) = closed_path {
+            if !self
+                .buffers
+                .values()
+                .any(|state| state.buffer.file_path() == Some(&path))
+            {
+                self.file_watches.remove(&path);
+            }
+        }
         self.event_logs.remove(&id);
         self.seen_byte_ranges.remove(&id);
         self.buffer_metadata.remove(&id);
@@ -1491,6 +1675,11 @@ impl Editor {
             view_state.remove_from_history(id);
         }
 
+        // Closing a composite's tab closes the composite itself
+        if self.is_composite_buffer(id) {
+            self.close_composite_buffer(id);
+        }
+
         // If this was the last visible buffer, focus file explorer
         if is_last_visible_buffer {
             self.focus_file_explorer();
@@ -2009,6 +2198,17 @@ impl Editor {
         }
     }
 
+    /// Add the cursor position to the position history as its own entry
+    pub(super) fn record_position_in_history(&mut self) {
+        let cursors = self.active_cursors();
+        let position = cursors.primary().position;
+        let anchor = cursors.primary().anchor;
+        self.position_history.commit_pending_movement();
+        self.position_history
+            .record_movement(self.active_buffer(), position, anchor);
+        self.position_history.commit_pending_movement();
+    }
+
     /// Navigate back in position history
     pub fn navigate_back(&mut self) {
         // Set flag to prevent recording this navigation movement
@@ -2051,7 +2251,7 @@ impl Editor {
                     old_anchor,
                     new_anchor: target_anchor,
                     old_sticky_column,
-                    new_sticky_column: 0, // Reset sticky column for navigation
+                    new_sticky_column: None, // Reset sticky column for navigation
                 };
                 let split_id = self.split_manager.active_split();
                 let state = self.buffers.get_mut(&target_buffer).unwrap();
@@ -2091,7 +2291,7 @@ impl Editor {
                     old_anchor,
                     new_anchor: target_anchor,
                     old_sticky_column,
-                    new_sticky_column: 0, // Reset sticky column for navigation
+                    new_sticky_column: None, // Reset sticky column for navigation
                 };
                 let split_id = self.split_manager.active_split();
                 let state = self.buffers.get_mut(&target_buffer).unwrap();
@@ -10,20 +10,18 @@ use rust_i18n::t;
 use crate::input::multi_cursor::{
     add_cursor_above, add_cursor_at_next_match, add_cursor_below, AddCursorResult,
 };
-use crate::model::buffer::Buffer;
-use crate::model::cursor::Position2D;
 use crate::model::event::{CursorId, Event};
+use crate::primitives::block_selection::{block_line_range, block_rect};
+use crate::primitives::markdown_paste::transform_paste;
 use crate::primitives::word_navigation::{find_word_start_left, find_word_start_right};
+use crate::services::clipboard::DEFAULT_REGISTER;
 
 use super::Editor;
 
-/// Convert byte offset to 2D position (line, column)
-fn byte_to_2d(buffer: &Buffer, byte_pos: usize) -> Position2D {
-    let line = buffer.get_line_number(byte_pos);
-    let line_start = buffer.line_start_offset(line).unwrap_or(0);
-    let column = byte_pos.saturating_sub(line_start);
-    Position2D { line, column }
-}
+/// Pastes at least this many bytes take the large-paste path: no compose-mode
+/// transforms, no incremental re-highlighting, and the edit is applied as one
+/// event instead of a bulk edit
+const LARGE_PASTE_BYTES: usize = 1024 * 1024;
 
 // These are the clipboard and multi-cursor operations on Editor.
 //
@@ -42,6 +40,53 @@ impl Editor {
     /// If no selection exists, copies the entire current line (like VSCode/Rider/Zed).
     /// For block selections, copies only the rectangular region.
     pub fn copy_selection(&mut self) {
+        let Some((text, whole_lines)) = self.text_to_copy() else {
+            return;
+        };
+        self.clipboard.copy(text);
+        self.status_message = Some(if whole_lines {
+            t!("clipboard.copied_line").to_string()
+        } else {
+            t!("clipboard.copied").to_string()
+        });
+    }
+
+    /// Copy the current selection (or line) into a named register
+    ///
+    /// The default register `"` is the clipboard itself.
+    pub fn copy_to_register(&mut self, register: char) {
+        if register == DEFAULT_REGISTER {
+            self.copy_selection();
+            return;
+        }
+        let Some((text, _)) = self.text_to_copy() else {
+            return;
+        };
+        self.clipboard.set_register(register, text);
+        self.status_message = Some(t!("register.copied", register = register).to_string());
+    }
+
+    /// Paste the content of a named register at all cursor positions
+    ///
+    /// The default register `"` is the clipboard itself.
+    pub fn paste_from_register(&mut self, register: char) {
+        if register == DEFAULT_REGISTER {
+            self.paste();
+            return;
+        }
+        match self.clipboard.register(register).map(str::to_string) {
+            Some(text) => self.paste_text(text),
+            None => {
+                self.status_message = Some(t!("register.empty", register = register).to_string());
+            }
+        }
+    }
+
+    /// The text a copy takes, and whether it is whole lines
+    ///
+    /// That is the block selection, the selected text of every cursor, or
+    /// the line of every cursor when nothing is selected.
+    fn text_to_copy(&mut self) -> Option<(String, bool)> {
         // Check if any cursor has a block selection (takes priority)
         let has_block_selection = self
             .active_cursors()
@@ -51,11 +96,7 @@ impl Editor {
         if has_block_selection {
             // Block selection: copy rectangular region
             let text = self.copy_block_selection_text();
-            if !text.is_empty() {
-                self.clipboard.copy(text);
-                self.status_message = Some(t!("clipboard.copied").to_string());
-            }
-            return;
+            return (!text.is_empty()).then_some((text, false));
         }
 
         // Check if any cursor has a normal selection
@@ -82,10 +123,7 @@ impl Editor {
                 text.push_str(&range_text);
             }
 
-            if !text.is_empty() {
-                self.clipboard.copy(text);
-                self.status_message = Some(t!("clipboard.copied").to_string());
-            }
+            (!text.is_empty()).then_some((text, false))
         } else {
             // No selection: copy entire line(s) for each cursor
             let estimated_line_length = 80;
@@ -109,95 +147,41 @@ impl Editor {
                 }
             }
 
-            if !text.is_empty() {
-                self.clipboard.copy(text);
-                self.status_message = Some(t!("clipboard.copied_line").to_string());
-            }
+            (!text.is_empty()).then_some((text, true))
         }
     }
 
     /// Extract text from block (rectangular) selection
     ///
     /// For block selection, we need to extract a rectangular region defined by:
-    /// - The block anchor (stored as Position2D with line and column)
-    /// - The current cursor position (byte offset, converted to 2D)
+    /// - The block anchor (stored as Position2D with line and visual column)
+    /// - The current cursor position (byte offset plus its sticky visual column)
     ///
-    /// This works for both small and large files by using line_iterator
-    /// for iteration and only using 2D positions for column extraction.
+    /// Each line contributes the text under the block's visual columns, so
+    /// tabs and wide characters line up the way they are displayed.
     fn copy_block_selection_text(&mut self) -> String {
-        let estimated_line_length = 120;
-
-        // Collect block selection info from all cursors
-        let block_infos: Vec<_> = self
-            .active_cursors()
-            .iter()
-            .filter_map(|(_, cursor)| {
-                if !cursor.has_block_selection() {
-                    return None;
-                }
-                let block_anchor = cursor.block_anchor?;
-                let anchor_byte = cursor.anchor?; // byte offset of anchor
-                let cursor_byte = cursor.position;
-                Some((block_anchor, anchor_byte, cursor_byte))
-            })
-            .collect();
+        // Collect block rectangles from all cursors
+        let block_rects: Vec<_> = {
+            let state = self.active_state();
+            self.active_cursors()
+                .iter()
+                .filter_map(|(_, cursor)| {
+                    block_rect(&state.buffer, cursor, state.buffer_settings.tab_size)
+                })
+                .collect()
+        };
 
         let mut result = String::new();
 
-        for (block_anchor, anchor_byte, cursor_byte) in block_infos {
-            // Get current cursor position as 2D
-            let cursor_2d = {
-                let state = self.active_state();
-                byte_to_2d(&state.buffer, cursor_byte)
-            };
-
-            // Calculate column bounds (min and max columns for the rectangle)
-            let min_col = block_anchor.column.min(cursor_2d.column);
-            let max_col = block_anchor.column.max(cursor_2d.column);
-
-            // Calculate line bounds using byte positions
-            let start_byte = anchor_byte.min(cursor_byte);
-            let end_byte = anchor_byte.max(cursor_byte);
-
-            // Use line_iterator to iterate through lines
+        for (start_line, start_col, end_line, end_col) in block_rects {
             let state = self.active_state_mut();
-            let mut iter = state
-                .buffer
-                .line_iterator(start_byte, estimated_line_length);
-
-            // Collect lines within the block selection range
-            let mut lines_text = Vec::new();
-            loop {
-                let line_start = iter.current_position();
-
-                // Stop if we've passed the end of the selection
-                if line_start > end_byte {
-                    break;
-                }
-
-                if let Some((_offset, line_content)) = iter.next_line() {
-                    // Extract the column range from this line
-                    // Remove trailing newline for column calculation
-                    let content_without_newline = line_content.trim_end_matches(&['\n', '\r'][..]);
-                    let chars: Vec<char> = content_without_newline.chars().collect();
-
-                    // Extract characters from min_col to max_col (exclusive)
-                    let extracted: String = chars
-                        .iter()
-                        .skip(min_col)
-                        .take(max_col.saturating_sub(min_col))
-                        .collect();
-
-                    lines_text.push(extracted);
-
-                    // If this line extends past end_byte, we're done
-                    if line_start + line_content.len() > end_byte {
-                        break;
-                    }
-                } else {
-                    break;
-                }
-            }
+            let tab_size = state.buffer_settings.tab_size;
+            let lines_text: Vec<String> = (start_line..=end_line)
+                .map(|line| {
+                    let range = block_line_range(&state.buffer, line, start_col, end_col, tab_size);
+                    state.get_text_range(range.start, range.end)
+                })
+                .collect();
 
             // Join the extracted text from each line
             if !result.is_empty() && !lines_text.is_empty() {
@@ -378,6 +362,56 @@ impl Editor {
         }
     }
 
+    /// Start the clipboard history picker for Paste from History
+    ///
+    /// Entries are listed newest first, and the newest one is preselected.
+    pub(super) fn start_paste_from_history_prompt(&mut self) {
+        use crate::view::prompt::PromptType;
+
+        // Pick up anything copied in another application since the last paste
+        self.clipboard.refresh_history();
+
+        let suggestions: Vec<crate::input::commands::Suggestion> = self
+            .clipboard
+            .history()
+            .enumerate()
+            .map(|(index, text)| {
+                let line_count = text.lines().count();
+                crate::input::commands::Suggestion {
+                    text: history_preview(text),
+                    description: (line_count > 1)
+                        .then(|| t!("clipboard.history_lines", count = line_count).to_string()),
+                    value: Some(index.to_string()),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect();
+
+        if suggestions.is_empty() {
+            self.set_status_message(t!("clipboard.history_empty").to_string());
+            return;
+        }
+
+        self.prompt = Some(crate::view::prompt::Prompt::with_suggestions(
+            t!("clipboard.history_prompt").to_string(),
+            PromptType::PasteFromHistory,
+            suggestions,
+        ));
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+
+    /// Paste the clipboard history entry at `index` (0 is the newest)
+    pub(super) fn paste_from_history(&mut self, index: usize) {
+        let text = self.clipboard.history().nth(index).map(str::to_string);
+        if let Some(text) = text {
+            self.paste_text(text);
+        }
+    }
+
     /// Cut the current selection to clipboard
     ///
     /// If no selection exists, cuts the entire current line (like VSCode/Rider/Zed).
@@ -520,6 +554,8 @@ impl Editor {
     /// - Selection replacement (deletes selection before inserting)
     /// - Atomic undo (single undo step for entire operation)
     /// - Routing to prompt if one is open
+    /// - Markdown links and tables in compose mode (see [`transform_paste`])
+    /// - Large pastes without per-line work (see [`Self::apply_large_paste`])
     pub fn paste_text(&mut self, paste_text: String) {
         if paste_text.is_empty() {
             return;
@@ -545,11 +581,14 @@ impl Editor {
 
         // Convert to buffer's line ending format
         let buffer_line_ending = self.active_state().buffer.line_ending();
-        let paste_text = match buffer_line_ending {
-            crate::model::buffer::LineEnding::LF => normalized,
-            crate::model::buffer::LineEnding::CRLF => normalized.replace('\n', "\r\n"),
-            crate::model::buffer::LineEnding::CR => normalized.replace('\n', "\r"),
+        let to_buffer_line_ending = |text: &str| match buffer_line_ending {
+            crate::model::buffer::LineEnding::LF => text.to_string(),
+            crate::model::buffer::LineEnding::CRLF => text.replace('\n', "\r\n"),
+            crate::model::buffer::LineEnding::CR => text.replace('\n', "\r"),
         };
+        let paste_text = to_buffer_line_ending(&normalized);
+        let large = paste_text.len() >= LARGE_PASTE_BYTES;
+        let compose = !large && self.is_compose_mode();
 
         let mut events = Vec::new();
 
@@ -568,7 +607,7 @@ impl Editor {
             .collect();
         cursor_data.sort_by_key(|(_, _, pos)| std::cmp::Reverse(*pos));
 
-        // Get deleted text for each selection
+        // Get deleted text for each selection, and what to insert in its place
         let cursor_data_with_text: Vec<_> = {
             let state = self.active_state_mut();
             cursor_data
@@ -577,13 +616,26 @@ impl Editor {
                     let deleted_text = selection
                         .as_ref()
                         .map(|r| state.get_text_range(r.start, r.end));
-                    (cursor_id, selection, insert_position, deleted_text)
+                    let text = compose
+                        .then(|| {
+                            let at_line_start = insert_position == 0
+                                || matches!(
+                                    state
+                                        .get_text_range(insert_position - 1, insert_position)
+                                        .as_str(),
+                                    "\n" | "\r"
+                                );
+                            transform_paste(&normalized, deleted_text.as_deref(), at_line_start)
+                        })
+                        .flatten()
+                        .map_or_else(|| paste_text.clone(), |text| to_buffer_line_ending(&text));
+                    (cursor_id, selection, insert_position, deleted_text, text)
                 })
                 .collect()
         };
 
         // Build events for each cursor
-        for (cursor_id, selection, insert_position, deleted_text) in cursor_data_with_text {
+        for (cursor_id, selection, insert_position, deleted_text, text) in cursor_data_with_text {
             if let (Some(range), Some(text)) = (selection, deleted_text) {
                 events.push(Event::Delete {
                     range,
@@ -593,13 +645,15 @@ impl Editor {
             }
             events.push(Event::Insert {
                 position: insert_position,
-                text: paste_text.clone(),
+                text,
                 cursor_id,
             });
         }
 
         // Apply events with atomic undo using bulk edit for O(n) performance
-        if events.len() > 1 {
+        if large {
+            self.apply_large_paste(events);
+        } else if events.len() > 1 {
             // Use optimized bulk edit for multi-cursor paste
             if let Some(bulk_edit) = self.apply_events_as_bulk_edit(events, "Paste".to_string()) {
                 self.active_event_log_mut().append(bulk_edit);
@@ -612,6 +666,80 @@ impl Editor {
         self.status_message = Some(t!("clipboard.pasted").to_string());
     }
 
+    /// Apply a large paste as a single undo step
+    ///
+    /// Each cursor's text goes into the piece tree in one splice. The syntax
+    /// highlighter is taken out of the buffer while the text goes in and its
+    /// cache is dropped once afterwards, so the next render re-highlights only
+    /// the visible lines. Markers, plugins and the language server still see
+    /// one change per cursor, and the buffer's modified and recovery flags are
+    /// set by the first splice.
+    fn apply_large_paste(&mut self, events: Vec<Event>) {
+        let event = if events.len() == 1 {
+            events.into_iter().next()
+        } else {
+            Some(Event::Batch {
+                events,
+                description: "Paste".to_string(),
+            })
+        };
+        let Some(event) = event else {
+            return;
+        };
+        self.active_event_log_mut().append(event.clone());
+
+        let highlighter = std::mem::take(&mut self.active_state_mut().highlighter);
+        self.apply_event_to_active_buffer(&event);
+        let state = self.active_state_mut();
+        state.highlighter = highlighter;
+        state.highlighter.invalidate_all();
+    }
+
+    /// Make the primary cursor's selection the primary selection that
+    /// middle-click pastes (X11-style)
+    ///
+    /// Only mouse selections update it, leaving the clipboard untouched.
+    pub(super) fn update_primary_selection(&mut self) {
+        let Some(range) = self.active_cursors().primary().selection_range() else {
+            return;
+        };
+        if range.is_empty() {
+            return;
+        }
+        let text = self
+            .active_state_mut()
+            .get_text_range(range.start, range.end);
+        self.clipboard.set_primary(text);
+    }
+
+    /// Insert the primary selection at `position` in the active buffer
+    ///
+    /// Unlike a regular paste, cursors and selections stay on the text they
+    /// were on.
+    pub(super) fn paste_primary_at(&mut self, position: usize) {
+        let Some(text) = self.clipboard.paste_primary() else {
+            return;
+        };
+        let normalized = text.replace("\r\n", "\n").replace('\r', "\n");
+        let text = match self.active_state().buffer.line_ending() {
+            crate::model::buffer::LineEnding::LF => normalized,
+            crate::model::buffer::LineEnding::CRLF => normalized.replace('\n', "\r\n"),
+            crate::model::buffer::LineEnding::CR => normalized.replace('\n', "\r"),
+        };
+        if text.is_empty() {
+            return;
+        }
+
+        let event = Event::Insert {
+            position,
+            text,
+            cursor_id: CursorId::UNDO_SENTINEL,
+        };
+        self.active_event_log_mut().append(event.clone());
+        self.apply_event_to_active_buffer(&event);
+        self.status_message = Some(t!("clipboard.pasted").to_string());
+    }
+
     /// Set clipboard content for testing purposes
     /// This sets the internal clipboard and enables internal-only mode to avoid
     /// system clipboard interference between parallel tests
@@ -681,7 +809,7 @@ impl Editor {
                     old_anchor: primary.anchor,
                     new_anchor: Some(word_start),
                     old_sticky_column: primary.sticky_column,
-                    new_sticky_column: 0,
+                    new_sticky_column: None,
                 };
 
                 // Log and apply the event
@@ -936,3 +1064,18 @@ impl Editor {
         }
     }
 }
+
+/// Longest clipboard history preview, in characters
+const HISTORY_PREVIEW_CHARS: usize = 60;
+
+/// One-line preview of a clipboard history entry: its first non-blank line,
+/// shortened with an ellipsis when anything is left out
+fn history_preview(text: &str) -> String {
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    let first = lines.next().unwrap_or_default().trim();
+    let mut preview: String = first.chars().take(HISTORY_PREVIEW_CHARS).collect();
+    if preview.len() < first.len() || lines.next().is_some() {
+        preview.push('…');
+    }
+    preview
+}
@@ -237,6 +237,8 @@ impl Editor {
         // Remove all view states for this buffer
         self.composite_view_states
             .retain(|(_, bid), _| *bid != buffer_id);
+
+        self.close_diff_view(buffer_id);
     }
 
     /// Switch focus to the next pane in a composite buffer
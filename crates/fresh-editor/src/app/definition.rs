@@ -0,0 +1,93 @@
+//! Go to Definition without a language server
+//!
+//! The symbol under the cursor is looked up by the registered definition
+//! providers on a blocking worker. The jump records the origin in the
+//! position history, so Go Back returns to it.
+
+use std::sync::Arc;
+
+use super::Editor;
+use crate::primitives::word_navigation::find_word_range_at;
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::definition::{DefinitionLocation, DefinitionProvider, DefinitionQuery};
+use rust_i18n::t;
+
+impl Editor {
+    /// Add a Go to Definition provider, asked before the built-in ones
+    pub fn register_definition_provider(&mut self, provider: Arc<dyn DefinitionProvider>) {
+        self.definition_providers.register(provider);
+    }
+
+    /// Look up the symbol under the cursor with the definition providers
+    pub(super) fn find_definition_with_providers(&mut self) {
+        let position = self.active_cursors().primary().position;
+        let buffer = &self.active_state().buffer;
+        let range = find_word_range_at(buffer, position);
+        let symbol = String::from_utf8_lossy(&buffer.slice_bytes(range)).into_owned();
+        let path = buffer.file_path().map(|path| path.to_path_buf());
+        if !symbol.chars().any(|c| c.is_alphanumeric() || c == '_') {
+            self.set_status_message(t!("lsp.no_symbol_at_cursor").to_string());
+            return;
+        }
+        let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) else {
+            return;
+        };
+
+        self.next_definition_lookup_id += 1;
+        let request_id = self.next_definition_lookup_id;
+        self.pending_definition_lookup = Some(request_id);
+
+        let query = DefinitionQuery {
+            symbol,
+            path,
+            root: self.working_dir.clone(),
+        };
+        let providers = self.definition_providers.clone();
+        let sender = bridge.sender();
+        runtime.spawn_blocking(move || {
+            let location = providers.find_definition(&query);
+            // The receiver is gone if the editor is shutting down
+            #[allow(clippy::let_underscore_must_use)]
+            let _ = sender.send(AsyncMessage::DefinitionFound {
+                request_id,
+                location,
+            });
+        });
+        self.set_status_message(t!("lsp.searching_definition").to_string());
+    }
+
+    /// Jump to a definition found by the providers, if it answers the
+    /// latest lookup
+    pub(super) fn handle_definition_found(
+        &mut self,
+        request_id: u64,
+        location: Option<DefinitionLocation>,
+    ) {
+        if self.pending_definition_lookup != Some(request_id) {
+            return;
+        }
+        self.pending_definition_lookup = None;
+
+        let Some(location) = location else {
+            self.set_status_message(t!("lsp.no_definition").to_string());
+            return;
+        };
+
+        self.record_position_in_history();
+        if let Err(e) = self.open_file(&location.path) {
+            self.set_status_message(t!("file.error_opening", error = e.to_string()).to_string());
+            return;
+        }
+        self.goto_line_col(location.line, Some(location.column));
+        self.record_position_in_history();
+
+        self.set_status_message(
+            t!(
+                "lsp.jumped_to_definition",
+                path = location.path.display().to_string(),
+                line = location.line
+            )
+            .to_string(),
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! Diagnostics from sources other than the language server
+//!
+//! Diagnostics providers check a file on a blocking worker when it is opened
+//! and after each save, and linters or plugins can push diagnostics for a
+//! file directly. Each source's diagnostics replace only that source's
+//! earlier ones; the editor shows all sources' diagnostics together.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::diagnostics::DiagnosticsProvider;
+use lsp_types::Diagnostic;
+
+/// Source the language server's diagnostics are kept under
+pub(super) const LSP_DIAGNOSTICS_SOURCE: &str = "lsp";
+
+impl Editor {
+    /// Add a provider that checks files when they are opened and saved
+    pub fn register_diagnostics_provider(&mut self, provider: Arc<dyn DiagnosticsProvider>) {
+        self.diagnostics_providers.register(provider);
+    }
+
+    /// Replace the diagnostics `source` reports for the file at `path`
+    ///
+    /// An empty list clears them. Diagnostics of other sources are kept.
+    pub fn set_diagnostics(&mut self, path: &Path, source: &str, diagnostics: Vec<Diagnostic>) {
+        let Ok(uri) = url::Url::from_file_path(path) else {
+            return;
+        };
+        self.store_and_apply_diagnostics(uri.to_string(), source, diagnostics);
+    }
+
+    /// Check a file buffer with the diagnostics providers in the background
+    pub(super) fn run_diagnostics_providers(&mut self, buffer_id: BufferId) {
+        if self.diagnostics_providers.is_empty() {
+            return;
+        }
+        let Some(uri) = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .and_then(|metadata| metadata.file_uri())
+            .map(|uri| uri.as_str().to_string())
+        else {
+            return;
+        };
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return;
+        };
+        let (Some(path), Some(contents)) = (
+            state.buffer.file_path().map(Path::to_path_buf),
+            state.buffer.to_string(),
+        ) else {
+            return;
+        };
+        let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) else {
+            return;
+        };
+
+        let providers = self.diagnostics_providers.clone();
+        let sender = bridge.sender();
+        runtime.spawn_blocking(move || {
+            for (source, diagnostics) in providers.check(&path, &contents) {
+                // The receiver is gone if the editor is shutting down
+                #[allow(clippy::let_underscore_must_use)]
+                let _ = sender.send(AsyncMessage::ProviderDiagnostics {
+                    uri: uri.clone(),
+                    source,
+                    diagnostics,
+                });
+            }
+        });
+    }
+
+    /// Replace one source's diagnostics for `uri`, returning the diagnostics
+    /// of all its sources
+    pub(super) fn merge_diagnostic_source(
+        &mut self,
+        uri: &str,
+        source: &str,
+        diagnostics: Vec<Diagnostic>,
+    ) -> Vec<Diagnostic> {
+        let sources = self.diagnostic_sources.entry(uri.to_string()).or_default();
+        if diagnostics.is_empty() {
+            sources.remove(source);
+        } else {
+            sources.insert(source.to_string(), diagnostics);
+        }
+
+        let merged: Vec<Diagnostic> = sources.values().flatten().cloned().collect();
+        if sources.is_empty() {
+            self.diagnostic_sources.remove(uri);
+        }
+        if merged.is_empty() {
+            self.stored_diagnostics.remove(uri);
+        } else {
+            self.stored_diagnostics
+                .insert(uri.to_string(), merged.clone());
+        }
+        merged
+    }
+}
@@ -0,0 +1,279 @@
+//! Side-by-side diff views of a buffer against another text
+//!
+//! The other text (the saved file, or any file on disk) is shown read-only on
+//! the left and the buffer on the right, with hunks aligned row by row and
+//! changed spans highlighted within modified lines. Both panes scroll as one,
+//! and `n`/`p` step between changes. Edits to the buffer realign the view on
+//! the next frame.
+
+use std::path::Path;
+
+use rust_i18n::t;
+
+use crate::model::composite_buffer::{
+    AlignedRow, CompositeLayout, LineAlignment, PaneStyle, RowType, SourcePane,
+};
+use crate::model::event::BufferId;
+use crate::model::line_diff::diff_hunks;
+use crate::primitives::path_utils::expand_tilde;
+use crate::primitives::text_property::TextPropertyEntry;
+
+use super::{normalize_path, Editor};
+
+/// A diff view opened by the editor rather than by a plugin
+pub(super) struct DiffView {
+    /// Hidden read-only buffer holding the other text, closed with the view
+    old_buffer_id: BufferId,
+    /// Buffer being compared
+    buffer_id: BufferId,
+    /// Version of the buffer the alignment was computed from
+    version: u64,
+}
+
+impl Editor {
+    /// Compare the active buffer with its file on disk
+    pub fn compare_with_saved(&mut self) {
+        let buffer_id = self.active_buffer();
+        let Some(path) = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.file_path())
+            .map(Path::to_path_buf)
+        else {
+            self.set_status_message(t!("diff.no_file").to_string());
+            return;
+        };
+        let name = self.diff_buffer_name(buffer_id);
+
+        let result = self
+            .filesystem
+            .read_file(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|saved| {
+                self.open_diff_view(
+                    t!("diff.saved_title", name = name).to_string(),
+                    format!("*{name} (saved)*"),
+                    t!("diff.saved_label").to_string(),
+                    &saved,
+                    buffer_id,
+                    t!("diff.buffer_label").to_string(),
+                )
+            });
+        self.report_diff_opened(result);
+    }
+
+    /// Compare the active buffer with the file at `input`, relative to the
+    /// working directory
+    pub(super) fn compare_with_file(&mut self, input: &str) {
+        let buffer_id = self.active_buffer();
+        if self.is_composite_buffer(buffer_id) {
+            self.set_status_message(t!("diff.unavailable").to_string());
+            return;
+        }
+        let expanded_path = expand_tilde(input.trim());
+        let path = if expanded_path.is_absolute() {
+            normalize_path(&expanded_path)
+        } else {
+            normalize_path(&self.working_dir.join(&expanded_path))
+        };
+        let other_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        let name = self.diff_buffer_name(buffer_id);
+
+        let result = self
+            .filesystem
+            .read_file(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|other| {
+                self.open_diff_view(
+                    t!("diff.files_title", old = other_name, new = name).to_string(),
+                    format!("*{other_name}*"),
+                    other_name,
+                    &other,
+                    buffer_id,
+                    name,
+                )
+            });
+        self.report_diff_opened(result);
+    }
+
+    /// Show `old` next to a buffer in a side-by-side diff and focus it
+    ///
+    /// `old` goes into a hidden read-only buffer named `old_name`. Returns
+    /// the number of changes between the two.
+    pub(super) fn open_diff_view(
+        &mut self,
+        title: String,
+        old_name: String,
+        old_label: String,
+        old: &[u8],
+        buffer_id: BufferId,
+        new_label: String,
+    ) -> anyhow::Result<usize> {
+        let (new, version) = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| Some((state.buffer.to_string()?, state.buffer.version())))
+            .ok_or_else(|| anyhow::anyhow!("buffer is too large to compare"))?;
+        let hunks = diff_hunks(old, new.as_bytes());
+
+        let old_buffer_id = self.create_virtual_buffer(old_name, "special".to_string(), true);
+        if let Some(state) = self.buffers.get_mut(&old_buffer_id) {
+            state.editing_disabled = true;
+        }
+        if let Some(metadata) = self.buffer_metadata.get_mut(&old_buffer_id) {
+            metadata.hidden_from_tabs = true;
+        }
+        self.set_virtual_buffer_content(
+            old_buffer_id,
+            vec![TextPropertyEntry::text(
+                String::from_utf8_lossy(old).into_owned(),
+            )],
+        )
+        .map_err(anyhow::Error::msg)?;
+
+        let alignment = LineAlignment::from_hunks(
+            &hunks,
+            self.diff_line_count(old_buffer_id),
+            self.diff_line_count(buffer_id),
+        );
+
+        let composite_id = self.create_composite_buffer(
+            title,
+            "file-diff".to_string(),
+            CompositeLayout::SideBySide {
+                ratios: vec![0.5, 0.5],
+                show_separator: true,
+            },
+            vec![
+                SourcePane::new(old_buffer_id, old_label, false).with_style(PaneStyle::old_diff()),
+                SourcePane::new(buffer_id, new_label, false).with_style(PaneStyle::new_diff()),
+            ],
+        );
+        self.set_composite_alignment(composite_id, alignment);
+        self.diff_views.insert(
+            composite_id,
+            DiffView {
+                old_buffer_id,
+                buffer_id,
+                version,
+            },
+        );
+        self.set_active_buffer(composite_id);
+        Ok(hunks.len())
+    }
+
+    /// Realign diff views whose buffer changed since they were last aligned
+    pub(super) fn refresh_diff_views(&mut self) {
+        let stale: Vec<(BufferId, u64)> = self
+            .diff_views
+            .iter()
+            .filter_map(|(&composite_id, view)| {
+                let version = self.buffers.get(&view.buffer_id)?.buffer.version();
+                (version != view.version).then_some((composite_id, version))
+            })
+            .collect();
+
+        for (composite_id, version) in stale {
+            let Some(view) = self.diff_views.get_mut(&composite_id) else {
+                continue;
+            };
+            view.version = version;
+            let (old_buffer_id, buffer_id) = (view.old_buffer_id, view.buffer_id);
+            let text = |id: BufferId| {
+                self.buffers
+                    .get(&id)
+                    .and_then(|state| state.buffer.to_string())
+            };
+            let (Some(old), Some(new)) = (text(old_buffer_id), text(buffer_id)) else {
+                continue;
+            };
+            let hunks = diff_hunks(old.as_bytes(), new.as_bytes());
+            let alignment = LineAlignment::from_hunks(
+                &hunks,
+                self.diff_line_count(old_buffer_id),
+                self.diff_line_count(buffer_id),
+            );
+            self.set_composite_alignment(composite_id, alignment);
+        }
+    }
+
+    /// Close the hidden buffer behind a diff view once its composite closes
+    pub(super) fn close_diff_view(&mut self, composite_id: BufferId) {
+        if let Some(view) = self.diff_views.remove(&composite_id) {
+            if let Err(e) = self.force_close_buffer(view.old_buffer_id) {
+                tracing::warn!(
+                    "Failed to close diff buffer {:?}: {}",
+                    view.old_buffer_id,
+                    e
+                );
+            }
+        }
+    }
+
+    /// Move to the next or previous change in the active diff view
+    pub(super) fn goto_diff_change(&mut self, forward: bool) {
+        let buffer_id = self.active_buffer();
+        if !self.is_composite_buffer(buffer_id) {
+            self.set_status_message(t!("diff.not_in_diff_view").to_string());
+            return;
+        }
+        let split_id = self.split_manager.active_split();
+        let (Some(composite), Some(view_state)) = (
+            self.composite_buffers.get(&buffer_id),
+            self.composite_view_states.get_mut(&(split_id, buffer_id)),
+        ) else {
+            return;
+        };
+
+        // Move the cursor to the hunk's header row and scroll it to the top,
+        // so every pane starts at the hunk boundary
+        let alignment = &composite.alignment;
+        let target = if forward {
+            alignment.next_hunk_row(view_state.cursor_row)
+        } else {
+            alignment.prev_hunk_row(view_state.cursor_row)
+        };
+        let Some(row) = target else {
+            self.set_status_message(t!("diff.no_more_changes").to_string());
+            return;
+        };
+        view_state.cursor_row = row;
+        view_state.scroll_row = row;
+
+        let is_header = |row: &&AlignedRow| row.row_type == RowType::HunkHeader;
+        let rows = &alignment.rows;
+        let index = rows.iter().take(row + 1).filter(is_header).count();
+        let count = rows.iter().filter(is_header).count();
+        self.set_status_message(
+            t!("diff.change_position", index = index, count = count).to_string(),
+        );
+    }
+
+    fn report_diff_opened(&mut self, result: anyhow::Result<usize>) {
+        match result {
+            Ok(0) => self.set_status_message(t!("diff.identical").to_string()),
+            Ok(count) => self.set_status_message(t!("diff.opened", count = count).to_string()),
+            Err(e) => {
+                self.set_status_message(t!("file.error_opening", error = e.to_string()).to_string())
+            }
+        }
+    }
+
+    fn diff_line_count(&self, buffer_id: BufferId) -> usize {
+        self.buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.line_count())
+            .unwrap_or(0)
+    }
+
+    /// Display name of a buffer, for diff titles and labels
+    pub(super) fn diff_buffer_name(&self, buffer_id: BufferId) -> String {
+        self.buffer_metadata
+            .get(&buffer_id)
+            .map(|metadata| metadata.display_name.clone())
+            .unwrap_or_default()
+    }
+}
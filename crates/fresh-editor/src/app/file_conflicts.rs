@@ -0,0 +1,114 @@
+//! Files that changed on disk while their buffer has unsaved changes
+//!
+//! Unmodified buffers are reloaded as soon as their file changes. A modified
+//! buffer is queued instead, and once no other prompt is open the user picks
+//! whether to keep the buffer, load the file from disk, or compare the two
+//! side by side first.
+
+use std::path::{Path, PathBuf};
+
+use rust_i18n::t;
+
+use crate::model::event::BufferId;
+use crate::view::prompt::PromptType;
+
+use super::Editor;
+
+impl Editor {
+    /// Queue a modified buffer whose file changed on disk, asking about it
+    /// once the prompt line is free
+    pub(super) fn queue_file_conflict(&mut self, buffer_id: BufferId) {
+        if !self.pending_file_conflicts.contains(&buffer_id) {
+            self.pending_file_conflicts.push(buffer_id);
+        }
+        self.offer_next_file_conflict();
+    }
+
+    /// Ask about the next queued conflict, unless another prompt is open
+    ///
+    /// Returns true if a prompt was opened.
+    pub(super) fn offer_next_file_conflict(&mut self) -> bool {
+        if self.prompt.is_some() {
+            return false;
+        }
+        while !self.pending_file_conflicts.is_empty() {
+            let buffer_id = self.pending_file_conflicts.remove(0);
+            let Some(path) = self.conflict_file_path(buffer_id) else {
+                continue;
+            };
+            // Undoing every edit leaves nothing to lose, so just reload
+            if !self.buffers[&buffer_id].buffer.is_modified() {
+                self.reload_changed_buffer(buffer_id, &path);
+                continue;
+            }
+
+            self.start_prompt(
+                t!(
+                    "prompt.file_changed_on_disk",
+                    name = self.diff_buffer_name(buffer_id),
+                    keep_key = t!("prompt.key.keep").to_string(),
+                    load_key = t!("prompt.key.load").to_string(),
+                    diff_key = t!("prompt.key.diff").to_string()
+                )
+                .to_string(),
+                PromptType::ConfirmFileConflict { buffer_id },
+            );
+            return true;
+        }
+        false
+    }
+
+    /// Apply the answer to a file conflict prompt
+    ///
+    /// Anything but the load or diff key keeps the buffer as it is; saving it
+    /// then asks before overwriting the file.
+    pub(super) fn handle_file_conflict_choice(&mut self, input: &str, buffer_id: BufferId) {
+        let Some(path) = self.conflict_file_path(buffer_id) else {
+            return;
+        };
+        let name = self.diff_buffer_name(buffer_id);
+        let choice = input.trim().to_lowercase();
+        let load_key = t!("prompt.key.load").to_string().to_lowercase();
+        let diff_key = t!("prompt.key.diff").to_string().to_lowercase();
+
+        if choice == load_key {
+            self.reload_changed_buffer(buffer_id, &path);
+            self.set_status_message(t!("file.conflict_loaded", name = name).to_string());
+        } else if choice == diff_key {
+            match self.show_file_conflict_diff(buffer_id, &path) {
+                Ok(()) => {
+                    self.set_status_message(t!("file.conflict_diff", name = name).to_string())
+                }
+                Err(e) => self.set_status_message(
+                    t!("file.error_opening", error = e.to_string()).to_string(),
+                ),
+            }
+        } else {
+            self.set_status_message(t!("file.conflict_kept", name = name).to_string());
+        }
+    }
+
+    /// Show the file on disk next to the buffer in a side-by-side diff
+    fn show_file_conflict_diff(&mut self, buffer_id: BufferId, path: &Path) -> anyhow::Result<()> {
+        let on_disk = self.filesystem.read_file(path)?;
+        let name = self.diff_buffer_name(buffer_id);
+        self.open_diff_view(
+            t!("file.conflict_diff_title", name = name).to_string(),
+            format!("*{name} (disk)*"),
+            t!("file.conflict_disk_label").to_string(),
+            &on_disk,
+            buffer_id,
+            t!("file.conflict_buffer_label").to_string(),
+        )?;
+        Ok(())
+    }
+
+    /// Path of a conflicted buffer's file, if the buffer is still open
+    fn conflict_file_path(&self, buffer_id: BufferId) -> Option<PathBuf> {
+        self.buffers
+            .get(&buffer_id)?
+            .buffer
+            .file_path()
+            .map(Path::to_path_buf)
+    }
+}
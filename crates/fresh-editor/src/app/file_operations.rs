@@ -16,6 +16,7 @@ use lsp_types::TextDocumentContentChangeEvent;
 use rust_i18n::t;
 
 use crate::model::event::{BufferId, EventLog};
+use crate::services::async_bridge::AsyncMessage;
 use crate::services::lsp::manager::LspSpawnResult;
 use crate::state::EditorState;
 
@@ -24,6 +25,20 @@ use super::{BufferMetadata, Editor};
 impl Editor {
     /// Save the active buffer
     pub fn save(&mut self) -> anyhow::Result<()> {
+        self.run_whitespace_cleanup_before_save();
+        self.request_backup_if_enabled(self.active_buffer());
+        self.prepare_deleted_file_save(self.active_buffer())?;
+
+        // A file the user can't write goes straight to sudo
+        if self.is_write_protected(self.active_buffer()) {
+            let info = self.active_state_mut().buffer.prepare_sudo_save()?;
+            self.start_prompt(
+                t!("prompt.sudo_save_confirm").to_string(),
+                PromptType::ConfirmSudoSave { info },
+            );
+            return Ok(());
+        }
+
         let path = self
             .active_state()
             .buffer
@@ -47,6 +62,117 @@ impl Editor {
         }
     }
 
+    /// Ask a buffer to back up its file before the next save when
+    /// `editor.backup_on_save` is on. Auto-saves and re-saves after
+    /// formatting don't, so the backup keeps the version before the user's save.
+    pub(crate) fn request_backup_if_enabled(&mut self, buffer_id: BufferId) {
+        if !self.config.editor.backup_on_save {
+            return;
+        }
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.buffer.request_backup();
+        }
+    }
+
+    /// Save the active buffer with sudo, without trying a normal save first
+    pub fn save_active_with_sudo(&mut self) {
+        match self.active_state_mut().buffer.prepare_sudo_save() {
+            Ok(info) => self.save_with_sudo(info),
+            Err(e) => self.set_status_message(
+                t!("prompt.sudo_save_failed", error = e.to_string()).to_string(),
+            ),
+        }
+    }
+
+    /// Move a prepared temporary file over the active buffer's file with
+    /// sudo, keeping the original's owner and permissions
+    pub(crate) fn save_with_sudo(&mut self, info: SudoSaveRequired) {
+        // Read temp file and write via sudo (works for both local and remote)
+        let result = (|| -> anyhow::Result<()> {
+            let data = self.filesystem.read_file(&info.temp_path)?;
+            self.filesystem
+                .sudo_write(&info.dest_path, &data, info.mode, info.uid, info.gid)?;
+            // Best-effort cleanup of temp file.
+            #[allow(clippy::let_underscore_must_use)]
+            let _ = self.filesystem.remove_file(&info.temp_path);
+            Ok(())
+        })();
+
+        match result {
+            Ok(_) => {
+                if let Err(e) = self
+                    .active_state_mut()
+                    .buffer
+                    .finalize_external_save(info.dest_path.clone())
+                {
+                    tracing::warn!("Failed to finalize sudo save: {}", e);
+                    self.set_status_message(
+                        t!("prompt.sudo_save_failed", error = e.to_string()).to_string(),
+                    );
+                } else if let Err(e) = self.finalize_save(Some(info.dest_path)) {
+                    tracing::warn!("Failed to finalize save after sudo: {}", e);
+                    self.set_status_message(
+                        t!("prompt.sudo_save_failed", error = e.to_string()).to_string(),
+                    );
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Sudo save failed: {}", e);
+                self.set_status_message(
+                    t!("prompt.sudo_save_failed", error = e.to_string()).to_string(),
+                );
+                // Best-effort cleanup of temp file.
+                #[allow(clippy::let_underscore_must_use)]
+                let _ = self.filesystem.remove_file(&info.temp_path);
+            }
+        }
+    }
+
+    /// Allow editing the active buffer although its file isn't writable
+    pub fn edit_anyway(&mut self) {
+        let buffer_id = self.active_buffer();
+        let Some(metadata) = self
+            .buffer_metadata
+            .get_mut(&buffer_id)
+            .filter(|metadata| metadata.write_protected)
+        else {
+            self.set_status_message(t!("buffer.not_write_protected").to_string());
+            return;
+        };
+        metadata.read_only = false;
+        let name = metadata.display_name.clone();
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = false;
+        }
+        self.set_status_message(t!("buffer.editing_anyway", name = name).to_string());
+    }
+
+    /// Whether a buffer's file was found not writable when it was opened
+    pub(super) fn is_write_protected(&self, buffer_id: BufferId) -> bool {
+        self.buffer_metadata
+            .get(&buffer_id)
+            .is_some_and(|metadata| metadata.write_protected)
+    }
+
+    /// Recreate the directory of a buffer whose file was deleted or moved
+    /// away on disk, so saving writes a new file in its place
+    fn prepare_deleted_file_save(&self, buffer_id: BufferId) -> std::io::Result<()> {
+        let deleted = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .is_some_and(|metadata| metadata.deleted_on_disk);
+        let parent = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.file_path())
+            .and_then(Path::parent)
+            .filter(|parent| !parent.as_os_str().is_empty());
+        match parent {
+            Some(parent) if deleted => self.filesystem.create_dir_all(parent),
+            _ => Ok(()),
+        }
+    }
+
     /// Internal helper to finalize save state (mark as saved, notify LSP, etc.)
     pub(crate) fn finalize_save(&mut self, path: Option<PathBuf>) -> anyhow::Result<()> {
         let buffer_id = self.active_buffer();
@@ -92,6 +218,11 @@ impl Editor {
             event_log.mark_saved();
         }
 
+        // The file exists again
+        if let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) {
+            metadata.deleted_on_disk = false;
+        }
+
         // Update file modification time after save
         if let Some(ref p) = path {
             if let Ok(metadata) = self.filesystem.metadata(p) {
@@ -103,6 +234,7 @@ impl Editor {
 
         // Notify LSP of save
         self.notify_lsp_save_buffer(buffer_id);
+        self.run_diagnostics_providers(buffer_id);
 
         // Delete recovery file (buffer is now saved)
         if let Err(e) = self.delete_buffer_recovery(buffer_id) {
@@ -179,10 +311,16 @@ impl Editor {
 
         self.last_persistent_auto_save = self.time_source.now();
 
-        // Collect info for modified buffers that have a file path
+        // Collect info for modified buffers that have a file path. Files
+        // deleted on disk are only recreated by an explicit save, and files
+        // the user can't write are only saved with sudo.
         let mut to_save = Vec::new();
         for (id, state) in &self.buffers {
-            if state.buffer.is_modified() {
+            let skipped = self
+                .buffer_metadata
+                .get(id)
+                .is_some_and(|metadata| metadata.deleted_on_disk || metadata.write_protected);
+            if state.buffer.is_modified() && !skipped {
                 if let Some(path) = state.buffer.file_path() {
                     to_save.push((*id, path.to_path_buf()));
                 }
@@ -226,7 +364,7 @@ impl Editor {
             }
         };
 
-        if !path.exists() {
+        if !self.filesystem.exists(&path) {
             self.status_message =
                 Some(t!("status.file_not_exists", path = path.display().to_string()).to_string());
             return Ok(false);
@@ -269,6 +407,14 @@ impl Editor {
 
         // Replace the current buffer with the new state
         let buffer_id = self.active_buffer();
+        // A file that isn't writable stays read-only until Edit Anyway
+        if self
+            .buffer_metadata
+            .get(&buffer_id)
+            .is_some_and(|metadata| metadata.write_protected && metadata.read_only)
+        {
+            new_state.editing_disabled = true;
+        }
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
             *state = new_state;
             // Note: line_wrap_enabled is now in SplitViewState.viewport
@@ -322,13 +468,18 @@ impl Editor {
     /// Poll for file changes (called from main loop)
     ///
     /// Checks modification times of open files to detect external changes.
-    /// Returns true if any file was changed (requires re-render).
+    /// Files with a pushed watch are skipped. Returns true if any file was
+    /// changed (requires re-render).
     pub fn poll_file_changes(&mut self) -> bool {
         // Skip if auto-revert is disabled
         if !self.auto_revert_enabled {
             return false;
         }
 
+        // Conflicts found while another prompt was open wait for it to close
+        let offered_conflict =
+            !self.pending_file_conflicts.is_empty() && self.offer_next_file_conflict();
+
         // Check poll interval
         let poll_interval =
             std::time::Duration::from_millis(self.config.editor.auto_revert_poll_interval_ms);
@@ -339,7 +490,7 @@ impl Editor {
             poll_interval
         );
         if elapsed < poll_interval {
-            return false;
+            return offered_conflict;
         }
         self.last_auto_revert_poll = self.time_source.now();
 
@@ -348,18 +499,27 @@ impl Editor {
             .buffers
             .values()
             .filter_map(|state| state.buffer.file_path().map(PathBuf::from))
+            .filter(|path| !self.file_watches.contains_key(path))
             .collect();
 
-        let mut any_changed = false;
+        let mut any_changed = offered_conflict;
 
         for path in files_to_check {
             // Get current mtime
             let current_mtime = match self.filesystem.metadata(&path) {
-                Ok(meta) => match meta.modified {
-                    Some(mtime) => mtime,
-                    None => continue,
-                },
-                Err(_) => continue, // File might have been deleted
+                Ok(meta) => {
+                    self.set_file_deleted_on_disk(&path, false);
+                    match meta.modified {
+                        Some(mtime) => mtime,
+                        None => continue,
+                    }
+                }
+                Err(e) => {
+                    if e.kind() == std::io::ErrorKind::NotFound {
+                        any_changed |= self.set_file_deleted_on_disk(&path, true);
+                    }
+                    continue;
+                }
             };
 
             // Check if mtime has changed
@@ -605,6 +765,10 @@ impl Editor {
 
     /// Record a file's modification time (called when opening files)
     /// This is used by the polling-based auto-revert to detect external changes
+    ///
+    /// On filesystems that push change notifications (local files on Linux,
+    /// remote agents) the file is watched instead of polled. The watcher
+    /// debounces bursts of changes.
     pub(crate) fn watch_file(&mut self, path: &Path) {
         // Record current modification time for polling
         if let Ok(metadata) = self.filesystem.metadata(path) {
@@ -612,6 +776,29 @@ impl Editor {
                 self.file_mod_times.insert(path.to_path_buf(), mtime);
             }
         }
+
+        if self.file_watches.contains_key(path) {
+            return;
+        }
+        let Some(bridge) = &self.async_bridge else {
+            return;
+        };
+        let sender = bridge.sender();
+        let changed_path = path.display().to_string();
+        let on_change = Box::new(move || {
+            // The receiver is gone if the editor is shutting down
+            #[allow(clippy::let_underscore_must_use)]
+            let _ = sender.send(AsyncMessage::FileChanged {
+                path: changed_path.clone(),
+            });
+        });
+        match self.filesystem.watch_changes(path, on_change) {
+            Ok(watch) => {
+                self.file_watches.insert(path.to_path_buf(), watch);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::Unsupported => {}
+            Err(e) => tracing::debug!("Polling {:?} for changes, watch failed: {}", path, e),
+        }
     }
 
     /// Notify LSP that a file's contents changed (e.g., after revert)
@@ -764,6 +951,14 @@ impl Editor {
         // Restore user settings (tab size, indentation, etc.)
         new_state.buffer_settings = old_buffer_settings;
         // Line number visibility is in per-split BufferViewState (survives buffer replacement)
+        // A file that isn't writable stays read-only until Edit Anyway
+        if self
+            .buffer_metadata
+            .get(&buffer_id)
+            .is_some_and(|metadata| metadata.write_protected && metadata.read_only)
+        {
+            new_state.editing_disabled = true;
+        }
 
         // Replace the buffer content
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
@@ -798,6 +993,37 @@ impl Editor {
         Ok(())
     }
 
+    /// Flag or unflag the buffers of a file as deleted or moved away on disk
+    ///
+    /// Returns true if any buffer's flag changed.
+    fn set_file_deleted_on_disk(&mut self, path: &Path, deleted: bool) -> bool {
+        let buffer_ids: Vec<BufferId> = self
+            .buffers
+            .iter()
+            .filter(|(_, state)| state.buffer.file_path() == Some(path))
+            .map(|(id, _)| *id)
+            .collect();
+
+        let mut changed = false;
+        for buffer_id in buffer_ids {
+            if let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) {
+                if metadata.deleted_on_disk != deleted {
+                    metadata.deleted_on_disk = deleted;
+                    changed = true;
+                }
+            }
+        }
+
+        if changed && deleted {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string());
+            self.set_status_message(t!("status.file_deleted_on_disk", name = name).to_string());
+        }
+        changed
+    }
+
     /// Handle a file change notification (from file watcher)
     pub fn handle_file_changed(&mut self, changed_path: &str) {
         let path = PathBuf::from(changed_path);
@@ -811,6 +1037,8 @@ impl Editor {
             .collect();
 
         if buffer_ids.is_empty() {
+            // The watch outlived the file's last buffer
+            self.file_watches.remove(&path);
             return;
         }
 
@@ -821,22 +1049,23 @@ impl Editor {
                 continue;
             }
 
-            let state = match self.buffers.get(&buffer_id) {
-                Some(s) => s,
-                None => continue,
-            };
-
             // Check if the file actually changed (compare mod times)
             // We use optimistic concurrency: check mtime, and if we decide to revert,
             // re-check to handle the race where a save completed between our checks.
-            let current_mtime = match self
-                .filesystem
-                .metadata(&path)
-                .ok()
-                .and_then(|m| m.modified)
-            {
-                Some(mtime) => mtime,
-                None => continue, // Can't read file, skip
+            let current_mtime = match self.filesystem.metadata(&path) {
+                Ok(meta) => {
+                    self.set_file_deleted_on_disk(&path, false);
+                    match meta.modified {
+                        Some(mtime) => mtime,
+                        None => continue,
+                    }
+                }
+                Err(e) => {
+                    if e.kind() == std::io::ErrorKind::NotFound {
+                        self.set_file_deleted_on_disk(&path, true);
+                    }
+                    continue; // Can't read file, skip
+                }
             };
 
             let dominated_by_stored = self
@@ -849,12 +1078,20 @@ impl Editor {
                 continue;
             }
 
-            // If buffer has local modifications, show a warning (don't auto-revert)
+            let state = match self.buffers.get(&buffer_id) {
+                Some(s) => s,
+                None => continue,
+            };
+
+            // If buffer has local modifications, ask what to do (don't auto-revert).
+            // Recording the new mtime keeps the change from being reported again.
             if state.buffer.is_modified() {
                 self.status_message = Some(format!(
                     "File {} changed on disk (buffer has unsaved changes)",
                     path.display()
                 ));
+                self.watch_file(&path);
+                self.queue_file_conflict(buffer_id);
                 continue;
             }
 
@@ -873,30 +1110,36 @@ impl Editor {
                     continue;
                 }
 
-                // Check if this buffer is currently displayed in the active split
-                let is_active_buffer = buffer_id == self.active_buffer();
+                self.reload_changed_buffer(buffer_id, &path);
+            }
+        }
+    }
 
-                if is_active_buffer {
-                    // Use revert_file() which preserves viewport for active buffer
-                    if let Err(e) = self.revert_file() {
-                        tracing::error!("Failed to auto-revert file {:?}: {}", path, e);
-                    } else {
-                        tracing::info!("Auto-reverted file: {:?}", path);
-                    }
-                } else {
-                    // Use revert_buffer_by_id() which doesn't touch any viewport
-                    // This prevents corrupting the active split's viewport state
-                    if let Err(e) = self.revert_buffer_by_id(buffer_id, &path) {
-                        tracing::error!("Failed to auto-revert background file {:?}: {}", path, e);
-                    } else {
-                        tracing::info!("Auto-reverted file: {:?}", path);
-                    }
-                }
+    /// Reload a buffer whose file changed on disk, keeping cursors and scroll
+    /// position at the same byte offsets where the new content allows
+    pub(super) fn reload_changed_buffer(&mut self, buffer_id: BufferId, path: &Path) {
+        // Check if this buffer is currently displayed in the active split
+        let is_active_buffer = buffer_id == self.active_buffer();
 
-                // Update the modification time tracking for this file
-                self.watch_file(&path);
+        if is_active_buffer {
+            // Use revert_file() which preserves viewport for active buffer
+            if let Err(e) = self.revert_file() {
+                tracing::error!("Failed to auto-revert file {:?}: {}", path, e);
+            } else {
+                tracing::info!("Auto-reverted file: {:?}", path);
+            }
+        } else {
+            // Use revert_buffer_by_id() which doesn't touch any viewport
+            // This prevents corrupting the active split's viewport state
+            if let Err(e) = self.revert_buffer_by_id(buffer_id, path) {
+                tracing::error!("Failed to auto-revert background file {:?}: {}", path, e);
+            } else {
+                tracing::info!("Auto-reverted file: {:?}", path);
             }
         }
+
+        // Update the modification time tracking for this file
+        self.watch_file(path);
     }
 
     /// Check if saving would overwrite changes made by another process
@@ -293,6 +293,15 @@ impl Editor {
                 );
                 self.init_folder_open_state();
             }
+            Action::SaveWorkspaceAs => {
+                self.start_prompt(
+                    t!("workspace.save_as_prompt").to_string(),
+                    PromptType::SaveWorkspaceAs,
+                );
+            }
+            Action::SwitchWorkspace => {
+                self.start_switch_workspace_prompt();
+            }
             Action::GotoLine => self.start_prompt(
                 t!("file.goto_line_prompt").to_string(),
                 PromptType::GotoLine,
@@ -333,6 +342,17 @@ impl Editor {
             Action::ToggleAutoRevert => {
                 self.toggle_auto_revert();
             }
+            Action::CompareWithSaved => self.compare_with_saved(),
+            Action::CompareWithFile => {
+                self.start_prompt(
+                    t!("diff.compare_file_prompt").to_string(),
+                    PromptType::CompareWithFile,
+                );
+            }
+            Action::NextDiffChange => self.goto_diff_change(true),
+            Action::PreviousDiffChange => self.goto_diff_change(false),
+            Action::EditAnyway => self.edit_anyway(),
+            Action::SaveWithSudo => self.save_active_with_sudo(),
             Action::FormatBuffer => {
                 if let Err(e) = self.format_buffer() {
                     self.set_status_message(
@@ -366,6 +386,27 @@ impl Editor {
                     );
                 }
             },
+            Action::ConvertIndentationToSpaces | Action::ConvertIndentationToTabs => {
+                let to_tabs = matches!(action, Action::ConvertIndentationToTabs);
+                match self.convert_indentation(to_tabs) {
+                    Ok(true) if to_tabs => {
+                        self.set_status_message(t!("whitespace.converted_to_tabs").to_string());
+                    }
+                    Ok(true) => {
+                        self.set_status_message(t!("whitespace.converted_to_spaces").to_string());
+                    }
+                    Ok(false) => {
+                        self.set_status_message(t!("whitespace.indentation_unchanged").to_string());
+                    }
+                    Err(e) => {
+                        self.set_status_message(
+                            t!("error.convert_indentation_failed", error = e).to_string(),
+                        );
+                    }
+                }
+            }
+            Action::ToggleTrimWhitespaceOnSave => self.toggle_trim_whitespace_on_save(),
+            Action::ToggleFinalNewlineOnSave => self.toggle_final_newline_on_save(),
             Action::Copy => {
                 // Check if there's an active popup with text selection
                 let state = self.active_state();
@@ -390,18 +431,25 @@ impl Editor {
             Action::CopyWithTheme(theme) => self.copy_selection_with_theme(&theme),
             Action::Cut => {
                 if self.is_editing_disabled() {
-                    self.set_status_message(t!("buffer.editing_disabled").to_string());
+                    self.set_status_message(self.editing_disabled_message());
                     return Ok(());
                 }
                 self.cut_selection()
             }
             Action::Paste => {
                 if self.is_editing_disabled() {
-                    self.set_status_message(t!("buffer.editing_disabled").to_string());
+                    self.set_status_message(self.editing_disabled_message());
                     return Ok(());
                 }
                 self.paste()
             }
+            Action::PasteFromHistory => {
+                if self.is_editing_disabled() {
+                    self.set_status_message(self.editing_disabled_message());
+                    return Ok(());
+                }
+                self.start_paste_from_history_prompt()
+            }
             Action::YankWordForward => self.yank_word_forward(),
             Action::YankWordBackward => self.yank_word_backward(),
             Action::YankToLineEnd => self.yank_to_line_end(),
@@ -412,6 +460,7 @@ impl Editor {
             Action::Redo => {
                 self.handle_redo();
             }
+            Action::UndoBoundary => self.break_undo_group(),
             Action::ShowHelp => {
                 self.open_help_manual();
             }
@@ -470,6 +519,7 @@ impl Editor {
                 // Start Quick Open with file suggestions (default mode)
                 self.start_quick_open();
             }
+            Action::QuickOpenToggleIgnored => self.quick_open_toggle_ignored(),
             Action::ToggleLineWrap => {
                 self.config.editor.line_wrap = !self.config.editor.line_wrap;
 
@@ -603,6 +653,13 @@ impl Editor {
                     false,
                 );
             }
+            Action::ReplaceInSelection => {
+                self.start_search_prompt(
+                    t!("file.replace_prompt").to_string(),
+                    PromptType::ReplaceSearch,
+                    true,
+                );
+            }
             Action::QueryReplace => {
                 // Enable confirm mode by default for query-replace
                 self.search_confirm_each = true;
@@ -619,12 +676,24 @@ impl Editor {
                     true,
                 );
             }
+            Action::IncrementalSearch => {
+                self.incremental_search_origin = Some(self.active_cursors().primary().position);
+                self.start_search_prompt(
+                    t!("search.incremental_prompt").to_string(),
+                    PromptType::IncrementalSearch,
+                    false,
+                );
+            }
+            Action::SearchInProject => self.start_search_in_project(),
             Action::FindNext => {
                 self.find_next();
             }
-            Action::FindPrevious => {
-                self.find_previous();
-            }
+            Action::FindPrevious => match self.prompt.as_ref().map(|p| &p.prompt_type) {
+                // Shift+Enter steps back through incremental search matches
+                Some(PromptType::IncrementalSearch) => self.step_incremental_search(false),
+                Some(_) => {}
+                None => self.find_previous(),
+            },
             Action::FindSelectionNext => {
                 self.find_selection_next();
             }
@@ -669,6 +738,8 @@ impl Editor {
             Action::ToggleTabBar => self.toggle_tab_bar(),
             Action::ToggleVerticalScrollbar => self.toggle_vertical_scrollbar(),
             Action::ToggleHorizontalScrollbar => self.toggle_horizontal_scrollbar(),
+            Action::ToggleWhitespaceWarnings => self.toggle_whitespace_warnings(),
+            Action::ToggleShowWhitespace => self.toggle_show_whitespace(),
             Action::ToggleLineNumbers => self.toggle_line_numbers(),
             Action::ToggleScrollSync => self.toggle_scroll_sync(),
             Action::ToggleMouseCapture => self.toggle_mouse_capture(),
@@ -695,14 +766,44 @@ impl Editor {
                 );
             }
             Action::SetLineEnding => {
-                self.start_set_line_ending_prompt();
+                self.start_line_ending_prompt(
+                    "Line ending: ".to_string(),
+                    PromptType::SetLineEnding,
+                );
+            }
+            Action::NormalizeLineEndings => {
+                self.start_line_ending_prompt(
+                    "Normalize line endings to: ".to_string(),
+                    PromptType::NormalizeLineEndings,
+                );
             }
             Action::SetEncoding => {
-                self.start_set_encoding_prompt();
+                self.start_encoding_prompt("Encoding: ".to_string(), PromptType::SetEncoding);
+            }
+            Action::SaveWithEncoding => {
+                self.start_encoding_prompt(
+                    "Save with encoding: ".to_string(),
+                    PromptType::SaveWithEncoding,
+                );
             }
             Action::ReloadWithEncoding => {
                 self.start_reload_with_encoding_prompt();
             }
+            Action::ToggleBom => {
+                let buffer = &mut self.active_state_mut().buffer;
+                let bom = !buffer.has_bom();
+                let status = if !buffer.set_bom(bom) {
+                    t!(
+                        "status.bom_unsupported",
+                        encoding = buffer.encoding().display_name()
+                    )
+                } else if bom {
+                    t!("status.bom_added")
+                } else {
+                    t!("status.bom_removed")
+                };
+                self.set_status_message(status.to_string());
+            }
             Action::SetLanguage => {
                 self.start_set_language_prompt();
             }
@@ -839,9 +940,26 @@ impl Editor {
             Action::ToggleComment => {
                 self.toggle_comment();
             }
+            Action::JoinLines => {
+                if self.is_editing_disabled() {
+                    self.set_status_message(self.editing_disabled_message());
+                    return Ok(());
+                }
+                self.join_lines();
+            }
+            Action::Align => {
+                if self.is_editing_disabled() {
+                    self.set_status_message(self.editing_disabled_message());
+                    return Ok(());
+                }
+                self.start_prompt(t!("align.prompt").to_string(), PromptType::Align);
+            }
             Action::GoToMatchingBracket => {
                 self.goto_matching_bracket();
             }
+            Action::SelectToMatchingBracket => {
+                self.select_to_matching_bracket();
+            }
             Action::JumpToNextError => {
                 self.jump_to_next_error();
             }
@@ -860,6 +978,15 @@ impl Editor {
             Action::ListBookmarks => {
                 self.list_bookmarks();
             }
+            Action::ToggleLineBookmark => {
+                self.toggle_line_bookmark();
+            }
+            Action::NextLineBookmark => {
+                self.goto_line_bookmark(true);
+            }
+            Action::PreviousLineBookmark => {
+                self.goto_line_bookmark(false);
+            }
             Action::ToggleSearchCaseSensitive => {
                 self.search_case_sensitive = !self.search_case_sensitive;
                 let state = if self.search_case_sensitive {
@@ -870,22 +997,7 @@ impl Editor {
                 self.set_status_message(
                     t!("search.case_sensitive_state", state = state).to_string(),
                 );
-                // Update incremental highlights if in search prompt, otherwise re-run completed search
-                // Check prompt FIRST since we want to use current prompt input, not stale search_state
-                if let Some(prompt) = &self.prompt {
-                    if matches!(
-                        prompt.prompt_type,
-                        PromptType::Search
-                            | PromptType::ReplaceSearch
-                            | PromptType::QueryReplaceSearch
-                    ) {
-                        let query = prompt.input.clone();
-                        self.update_search_highlights(&query);
-                    }
-                } else if let Some(search_state) = &self.search_state {
-                    let query = search_state.query.clone();
-                    self.perform_search(&query);
-                }
+                self.refresh_search_for_options();
             }
             Action::ToggleSearchWholeWord => {
                 self.search_whole_word = !self.search_whole_word;
@@ -895,22 +1007,7 @@ impl Editor {
                     "disabled"
                 };
                 self.set_status_message(t!("search.whole_word_state", state = state).to_string());
-                // Update incremental highlights if in search prompt, otherwise re-run completed search
-                // Check prompt FIRST since we want to use current prompt input, not stale search_state
-                if let Some(prompt) = &self.prompt {
-                    if matches!(
-                        prompt.prompt_type,
-                        PromptType::Search
-                            | PromptType::ReplaceSearch
-                            | PromptType::QueryReplaceSearch
-                    ) {
-                        let query = prompt.input.clone();
-                        self.update_search_highlights(&query);
-                    }
-                } else if let Some(search_state) = &self.search_state {
-                    let query = search_state.query.clone();
-                    self.perform_search(&query);
-                }
+                self.refresh_search_for_options();
             }
             Action::ToggleSearchRegex => {
                 self.search_use_regex = !self.search_use_regex;
@@ -920,22 +1017,17 @@ impl Editor {
                     "disabled"
                 };
                 self.set_status_message(t!("search.regex_state", state = state).to_string());
-                // Update incremental highlights if in search prompt, otherwise re-run completed search
-                // Check prompt FIRST since we want to use current prompt input, not stale search_state
-                if let Some(prompt) = &self.prompt {
-                    if matches!(
-                        prompt.prompt_type,
-                        PromptType::Search
-                            | PromptType::ReplaceSearch
-                            | PromptType::QueryReplaceSearch
-                    ) {
-                        let query = prompt.input.clone();
-                        self.update_search_highlights(&query);
-                    }
-                } else if let Some(search_state) = &self.search_state {
-                    let query = search_state.query.clone();
-                    self.perform_search(&query);
-                }
+                self.refresh_search_for_options();
+            }
+            Action::ToggleSearchSmartCase => {
+                self.search_smart_case = !self.search_smart_case;
+                let state = if self.search_smart_case {
+                    "enabled"
+                } else {
+                    "disabled"
+                };
+                self.set_status_message(t!("search.smart_case_state", state = state).to_string());
+                self.refresh_search_for_options();
             }
             Action::ToggleSearchConfirmEach => {
                 self.search_confirm_each = !self.search_confirm_each;
@@ -984,6 +1076,16 @@ impl Editor {
                     self.set_status_message(t!("status.no_macro_recorded").to_string());
                 }
             }
+            Action::PromptRepeatLastMacro => {
+                if self.last_macro_register.is_some() {
+                    self.start_prompt(
+                        t!("macro.repeat_prompt").to_string(),
+                        PromptType::RepeatLastMacro,
+                    );
+                } else {
+                    self.set_status_message(t!("status.no_macro_recorded").to_string());
+                }
+            }
             Action::PromptSetBookmark => {
                 self.start_prompt("Set bookmark (0-9): ".to_string(), PromptType::SetBookmark);
             }
@@ -993,10 +1095,34 @@ impl Editor {
                     PromptType::JumpToBookmark,
                 );
             }
+            Action::CopyToRegister(register) => self.copy_to_register(register),
+            Action::PasteFromRegister(register) => {
+                if self.is_editing_disabled() {
+                    self.set_status_message(self.editing_disabled_message());
+                    return Ok(());
+                }
+                self.paste_from_register(register);
+            }
+            Action::PromptCopyToRegister => {
+                self.start_prompt(
+                    t!("register.copy_prompt").to_string(),
+                    PromptType::CopyToRegister,
+                );
+            }
+            Action::PromptPasteFromRegister => {
+                if self.is_editing_disabled() {
+                    self.set_status_message(self.editing_disabled_message());
+                    return Ok(());
+                }
+                self.start_prompt(
+                    t!("register.paste_prompt").to_string(),
+                    PromptType::PasteFromRegister,
+                );
+            }
             Action::None => {}
             Action::DeleteBackward => {
                 if self.is_editing_disabled() {
-                    self.set_status_message(t!("buffer.editing_disabled").to_string());
+                    self.set_status_message(self.editing_disabled_message());
                     return Ok(());
                 }
                 // Normal backspace handling
@@ -1010,8 +1136,7 @@ impl Editor {
                         }
                     } else {
                         for event in events {
-                            self.active_event_log_mut().append(event.clone());
-                            self.apply_event_to_active_buffer(&event);
+                            self.apply_typing_event(event);
                         }
                     }
                 }
@@ -1160,6 +1285,15 @@ impl Editor {
                 self.open_keybinding_editor();
             }
             Action::PromptConfirm => {
+                // Enter in incremental search moves to the next match instead of closing
+                if self
+                    .prompt
+                    .as_ref()
+                    .is_some_and(|p| p.prompt_type == PromptType::IncrementalSearch)
+                {
+                    self.step_incremental_search(true);
+                    return Ok(());
+                }
                 if let Some((input, prompt_type, selected_index)) = self.confirm_prompt() {
                     use super::prompt_actions::PromptResult;
                     match self.handle_prompt_confirm_input(input, prompt_type, selected_index) {
@@ -1333,16 +1467,27 @@ impl Editor {
             return Ok(());
         }
 
+        self.scroll_split_viewport(active_split, buffer_id, delta);
+        Ok(())
+    }
+
+    /// Scroll a split's viewport by `delta` rows, negative for up
+    pub(super) fn scroll_split_viewport(
+        &mut self,
+        split_id: LeafId,
+        buffer_id: BufferId,
+        delta: i32,
+    ) {
         // Get view_transform tokens from SplitViewState (if any)
         let view_transform_tokens = self
             .split_view_states
-            .get(&active_split)
+            .get(&split_id)
             .and_then(|vs| vs.view_transform.as_ref())
             .map(|vt| vt.tokens.clone());
 
         // Get mutable references to both buffer and view state
         let buffer = self.buffers.get_mut(&buffer_id).map(|s| &mut s.buffer);
-        let view_state = self.split_view_states.get_mut(&active_split);
+        let view_state = self.split_view_states.get_mut(&split_id);
 
         if let (Some(buffer), Some(view_state)) = (buffer, view_state) {
             let top_byte_before = view_state.viewport.top_byte;
@@ -1370,14 +1515,12 @@ impl Editor {
             // Skip ensure_visible so the scroll position isn't undone during render
             view_state.viewport.set_skip_ensure_visible();
             tracing::trace!(
-                "handle_mouse_scroll: delta={}, top_byte {} -> {}",
+                "scroll_split_viewport: delta={}, top_byte {} -> {}",
                 delta,
                 top_byte_before,
                 view_state.viewport.top_byte
             );
         }
-
-        Ok(())
     }
 
     /// Handle horizontal scroll (Shift+ScrollWheel or native ScrollLeft/ScrollRight)
@@ -1751,11 +1894,49 @@ impl Editor {
         }
 
         // Move cursor to be visible in the new viewport (after releasing the state borrow)
-        self.move_cursor_to_visible_area(split_id, buffer_id);
+        if let Some(from) = self.move_cursor_to_visible_area(split_id, buffer_id) {
+            self.record_scroll_jump(split_id, buffer_id, from);
+        }
 
         Ok(())
     }
 
+    /// Scroll a split so the line starting at `line_start` sits in the middle
+    /// of the viewport (used when a scrollbar marker is clicked)
+    pub(super) fn scroll_to_scrollbar_marker(
+        &mut self,
+        split_id: LeafId,
+        buffer_id: BufferId,
+        line_start: usize,
+    ) {
+        let Some(viewport_height) = self
+            .split_view_states
+            .get(&split_id)
+            .map(|vs| vs.viewport.height as usize)
+        else {
+            return;
+        };
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+
+        let line = state.buffer.get_line_number(line_start);
+        let top_line = line.saturating_sub(viewport_height / 2);
+        let top_byte = state.buffer.line_start_offset(top_line).unwrap_or(0);
+        let max_top_byte = Self::calculate_max_scroll_position(&mut state.buffer, viewport_height);
+
+        if let Some(view_state) = self.split_view_states.get_mut(&split_id) {
+            view_state.viewport.top_byte = top_byte.min(max_top_byte);
+            view_state.viewport.top_view_line_offset = 0;
+            // Skip ensure_visible so the scroll position isn't undone during render
+            view_state.viewport.set_skip_ensure_visible();
+        }
+
+        if let Some(from) = self.move_cursor_to_visible_area(split_id, buffer_id) {
+            self.record_scroll_jump(split_id, buffer_id, from);
+        }
+    }
+
     /// Handle scrollbar jump (click on track) for composite buffers.
     /// Maps the click ratio to a row-based scroll position.
     fn handle_composite_scrollbar_jump(
@@ -1855,7 +2036,13 @@ impl Editor {
 
     /// Move the cursor to a visible position within the current viewport
     /// This is called after scrollbar operations to ensure the cursor is in view
-    pub(super) fn move_cursor_to_visible_area(&mut self, split_id: LeafId, buffer_id: BufferId) {
+    ///
+    /// Returns the cursor's previous position if it had to move.
+    pub(super) fn move_cursor_to_visible_area(
+        &mut self,
+        split_id: LeafId,
+        buffer_id: BufferId,
+    ) -> Option<usize> {
         // Get viewport info from SplitViewState
         let (top_byte, viewport_height) =
             if let Some(view_state) = self.split_view_states.get(&split_id) {
@@ -1864,7 +2051,7 @@ impl Editor {
                     view_state.viewport.height as usize,
                 )
             } else {
-                return;
+                return None;
             };
 
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
@@ -1895,9 +2082,25 @@ impl Editor {
                     let cursor = view_state.cursors.primary_mut();
                     cursor.position = top_byte;
                     // Keep the existing sticky_column value so vertical navigation preserves column
+                    return Some(cursor_pos);
                 }
             }
         }
+        None
+    }
+
+    /// Record a scroll that moved the cursor away from `from` as a jump in
+    /// position history, so Go Back returns to where the cursor was
+    fn record_scroll_jump(&mut self, split_id: LeafId, buffer_id: BufferId, from: usize) {
+        let Some(to) = self
+            .split_view_states
+            .get(&split_id)
+            .map(|view_state| view_state.cursors.primary().position)
+        else {
+            return;
+        };
+        self.position_history.record_movement(buffer_id, from, None);
+        self.position_history.record_movement(buffer_id, to, None);
     }
 
     /// Calculate the maximum allowed scroll position
@@ -2300,6 +2503,12 @@ impl Editor {
             .get(&split_id)
             .and_then(|vs| vs.compose_width);
 
+        let left_column = self
+            .split_view_states
+            .get(&split_id)
+            .map(|vs| vs.viewport.left_column)
+            .unwrap_or(0);
+
         // Calculate clicked position in buffer
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
             let gutter_width = state.margins.left_total_width() as u16;
@@ -2365,14 +2574,27 @@ impl Editor {
                 None // Clear selection on normal click
             };
 
+            // Alt+click anchors a block selection that a drag then extends
+            let block_anchor =
+                (modifiers.contains(KeyModifiers::ALT) && !extend_selection).then(|| {
+                    Self::block_position_at(
+                        state,
+                        target_position,
+                        col,
+                        content_rect,
+                        gutter_width,
+                        left_column,
+                    )
+                });
+
             let event = Event::MoveCursor {
                 cursor_id: primary_cursor_id,
                 old_position,
                 new_position: target_position,
                 old_anchor,
                 new_anchor,
-                old_sticky_column: 0,
-                new_sticky_column: 0, // Reset sticky column for goto line
+                old_sticky_column: None,
+                new_sticky_column: None, // Reset sticky column for goto line
             };
 
             // Apply the event
@@ -2385,6 +2607,9 @@ impl Editor {
                 .map(|vs| &mut vs.cursors)
             {
                 state.apply(cursors, &event);
+                if let Some(cursor) = cursors.get_mut(primary_cursor_id) {
+                    cursor.clear_block_selection();
+                }
             }
 
             // Track position history
@@ -2398,6 +2623,7 @@ impl Editor {
             self.mouse_state.drag_selection_split = Some(split_id);
             // For shift+click, anchor stays at selection start; otherwise anchor at click position
             self.mouse_state.drag_selection_anchor = Some(new_anchor.unwrap_or(target_position));
+            self.mouse_state.drag_block_anchor = block_anchor;
 
             // Fire cursor_moved hook so plugins (e.g. markdown compose) can
             // update cursor-aware conceals (auto-expose emphasis markers).
@@ -2484,7 +2710,7 @@ impl Editor {
     }
 
     /// Start the line ending selection prompt
-    fn start_set_line_ending_prompt(&mut self) {
+    fn start_line_ending_prompt(&mut self, message: String, prompt_type: PromptType) {
         use crate::model::buffer::LineEnding;
 
         let current_line_ending = self.active_state().buffer.line_ending();
@@ -2520,8 +2746,8 @@ impl Editor {
             .collect();
 
         self.prompt = Some(crate::view::prompt::Prompt::with_suggestions(
-            "Line ending: ".to_string(),
-            PromptType::SetLineEnding,
+            message,
+            prompt_type,
             suggestions,
         ));
 
@@ -2535,8 +2761,8 @@ impl Editor {
         }
     }
 
-    /// Start the encoding selection prompt
-    fn start_set_encoding_prompt(&mut self) {
+    /// Start an encoding selection prompt with the current encoding preselected
+    fn start_encoding_prompt(&mut self, message: String, prompt_type: PromptType) {
         use crate::model::buffer::Encoding;
 
         let current_encoding = self.active_state().buffer.encoding();
@@ -2566,8 +2792,8 @@ impl Editor {
             .unwrap_or(0);
 
         self.prompt = Some(crate::view::prompt::Prompt::with_suggestions(
-            "Encoding: ".to_string(),
-            PromptType::SetEncoding,
+            message,
+            prompt_type,
             suggestions,
         ));
 
@@ -3364,7 +3590,7 @@ impl Editor {
     fn handle_insert_char_editor(&mut self, c: char) -> AnyhowResult<()> {
         // Check if editing is disabled (show_cursors = false)
         if self.is_editing_disabled() {
-            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            self.set_status_message(self.editing_disabled_message());
             return Ok(());
         }
 
@@ -3372,7 +3598,12 @@ impl Editor {
         self.cancel_pending_lsp_requests();
 
         if let Some(events) = self.action_to_events(Action::InsertChar(c)) {
-            if events.len() > 1 {
+            // A single cursor's insert is typing, including the cursor move
+            // into an auto-closed pair; replacing a selection or dedenting
+            // a closing bracket also deletes text and stays its own step
+            let is_typing = self.active_cursors().count() == 1
+                && !events.iter().any(|e| matches!(e, Event::Delete { .. }));
+            if events.len() > 1 && !is_typing {
                 // Multi-cursor: use optimized bulk edit (O(n) instead of O(n²))
                 let description = format!("Insert '{}'", c);
                 if let Some(bulk_edit) = self.apply_events_as_bulk_edit(events, description.clone())
@@ -3380,10 +3611,9 @@ impl Editor {
                     self.active_event_log_mut().append(bulk_edit);
                 }
             } else {
-                // Single cursor - apply normally
+                // Single cursor - group with the surrounding typing for undo
                 for event in events {
-                    self.active_event_log_mut().append(event.clone());
-                    self.apply_event_to_active_buffer(&event);
+                    self.apply_typing_event(event);
                 }
             }
         }
@@ -3415,6 +3645,7 @@ impl Editor {
 
         // Get description before moving action
         let action_description = format!("{:?}", action);
+        let is_typing = matches!(action, Action::DeleteForward);
 
         // Check if this is an editing action and editing is disabled
         let is_editing_action = matches!(
@@ -3433,7 +3664,7 @@ impl Editor {
         );
 
         if is_editing_action && self.is_editing_disabled() {
-            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            self.set_status_message(self.editing_disabled_message());
             return Ok(());
         }
 
@@ -3468,8 +3699,12 @@ impl Editor {
             } else {
                 // Single cursor - apply normally
                 for event in events {
-                    self.active_event_log_mut().append(event.clone());
-                    self.apply_event_to_active_buffer(&event);
+                    if is_typing {
+                        self.apply_typing_event(event.clone());
+                    } else {
+                        self.active_event_log_mut().append(event.clone());
+                        self.apply_event_to_active_buffer(&event);
+                    }
                     self.track_cursor_movement(&event);
                 }
             }
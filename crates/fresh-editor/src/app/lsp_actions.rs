@@ -3,6 +3,7 @@
 //! This module contains handlers for LSP actions that require complex logic,
 //! such as restarting LSP servers and managing server lifecycle.
 
+use super::diagnostics::LSP_DIAGNOSTICS_SOURCE;
 use super::Editor;
 use crate::input::commands::Suggestion;
 use crate::view::prompt::{Prompt, PromptType};
@@ -216,7 +217,7 @@ impl Editor {
             .map(|u| u.as_str().to_string());
 
         if let Some(uri_str) = uri {
-            self.stored_diagnostics.remove(&uri_str);
+            self.merge_diagnostic_source(&uri_str, LSP_DIAGNOSTICS_SOURCE, Vec::new());
             self.diagnostic_result_ids.remove(&uri_str);
         }
 
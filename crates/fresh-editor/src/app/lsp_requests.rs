@@ -264,7 +264,7 @@ impl Editor {
                     old_anchor,
                     new_anchor: None,
                     old_sticky_column,
-                    new_sticky_column: 0, // Reset sticky column for goto definition
+                    new_sticky_column: None, // Reset sticky column for goto definition
                 };
 
                 let split_id = self.split_manager.active_split();
@@ -537,6 +537,9 @@ impl Editor {
         if sent {
             self.next_lsp_request_id += 1;
             self.pending_goto_definition_request = Some(request_id);
+        } else {
+            // No language server for this buffer
+            self.find_definition_with_providers();
         }
 
         Ok(())
@@ -1682,8 +1685,11 @@ impl Editor {
             new_snapshot: Some(new_snapshot),
             old_cursors,
             new_cursors,
+            edits: Event::bulk_edit_lengths(&edits),
             description,
         };
+        self.position_history
+            .adjust_for_event(buffer_id, &bulk_edit);
 
         // Add to event log
         if let Some(event_log) = self.event_logs.get_mut(&buffer_id) {
@@ -75,7 +75,7 @@ impl Editor {
     }
 
     /// Check if line wrap is enabled in the active split.
-    fn is_line_wrap_enabled(&self) -> bool {
+    pub(super) fn is_line_wrap_enabled(&self) -> bool {
         let active_split = self.split_manager.active_split();
         self.split_view_states
             .get(&active_split)
@@ -84,7 +84,7 @@ impl Editor {
     }
 
     /// Check if compose mode is active in the current buffer.
-    fn is_compose_mode(&self) -> bool {
+    pub(super) fn is_compose_mode(&self) -> bool {
         let active_split = self.split_manager.active_split();
         self.split_view_states
             .get(&active_split)
@@ -4,8 +4,12 @@ mod calibration_actions;
 pub mod calibration_wizard;
 mod clipboard;
 mod composite_buffer_actions;
+mod definition;
+mod diagnostics;
+mod diff_view;
 pub mod event_debug;
 mod event_debug_actions;
+mod file_conflicts;
 mod file_explorer;
 pub mod file_open;
 mod file_open_input;
@@ -23,7 +27,9 @@ mod mouse_input;
 mod on_save_actions;
 mod plugin_commands;
 mod popup_actions;
+mod project_search;
 mod prompt_actions;
+mod quick_open_files;
 mod recovery_actions;
 mod regex_replace;
 mod render;
@@ -80,14 +86,15 @@ pub(crate) fn normalize_path(path: &std::path::Path) -> std::path::PathBuf {
 use self::types::{
     Bookmark, CachedLayout, EventLineInfo, InteractiveReplaceState, LspMessageEntry,
     LspProgressInfo, MacroRecordingState, MouseState, SearchState, TabContextMenu,
-    DEFAULT_BACKGROUND_FILE,
+    DEFAULT_BACKGROUND_FILE, LINE_BOOKMARK_NAMESPACE,
 };
 use crate::config::Config;
 use crate::config_io::{ConfigLayer, ConfigResolver, DirectoryContext};
-use crate::input::actions::action_to_events as convert_action_to_events;
+use crate::input::actions::action_to_events_with_auto_close as convert_action_to_events;
 use crate::input::buffer_mode::ModeRegistry;
 use crate::input::command_registry::CommandRegistry;
 use crate::input::commands::Suggestion;
+use crate::input::goto_line::GotoLineTarget;
 use crate::input::keybindings::{Action, KeyContext, KeybindingResolver};
 use crate::input::position_history::PositionHistory;
 use crate::input::quick_open::{
@@ -97,6 +104,8 @@ use crate::model::cursor::Cursors;
 use crate::model::event::{Event, EventLog, LeafId, SplitDirection, SplitId};
 use crate::model::filesystem::FileSystem;
 use crate::services::async_bridge::{AsyncBridge, AsyncMessage};
+use crate::services::definition::{DefinitionRegistry, TagsProvider};
+use crate::services::diagnostics::DiagnosticsRegistry;
 use crate::services::fs::FsManager;
 use crate::services::lsp::manager::LspManager;
 use crate::services::plugins::PluginManager;
@@ -122,7 +131,7 @@ use ratatui::{
     layout::{Constraint, Direction, Layout},
     Frame,
 };
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
@@ -396,6 +405,15 @@ pub struct Editor {
     /// Pending LSP go-to-definition request ID (if any)
     pending_goto_definition_request: Option<u64>,
 
+    /// Providers Go to Definition asks when no language server can
+    definition_providers: DefinitionRegistry,
+
+    /// Pending lookup by the definition providers (if any)
+    pending_definition_lookup: Option<u64>,
+
+    /// Id of the most recent definition lookup, to drop results of older ones
+    next_definition_lookup_id: u64,
+
     /// Pending LSP hover request ID (if any)
     pending_hover_request: Option<u64>,
 
@@ -458,6 +476,10 @@ pub struct Editor {
     /// Pending search range that should be reused when the next search is confirmed
     pending_search_range: Option<Range<usize>>,
 
+    /// Where the current incremental search started; each refinement of the
+    /// query jumps to the nearest match from here
+    incremental_search_origin: Option<usize>,
+
     /// Interactive replace state (if interactive replace is active)
     interactive_replace_state: Option<InteractiveReplaceState>,
 
@@ -484,6 +506,18 @@ pub struct Editor {
     /// File provider for Quick Open (stored separately for cache management)
     file_provider: Arc<FileProvider>,
 
+    /// Running or finished Search in Project, whose matches the prompt lists
+    project_search: Option<project_search::ProjectSearch>,
+
+    /// Id of the most recent project search, to drop results of older ones
+    next_project_search_id: u64,
+
+    /// Quick Open file query whose suggestions are still being computed
+    file_suggestions_request: Option<quick_open_files::FileSuggestionsRequest>,
+
+    /// Id of the most recent file query, to drop suggestions for older ones
+    next_file_suggestions_id: u64,
+
     /// Plugin manager (handles both enabled and disabled cases)
     plugin_manager: PluginManager,
 
@@ -526,10 +560,16 @@ pub struct Editor {
     /// Maps URI string to last result_id received from server
     diagnostic_result_ids: HashMap<String, String>,
 
-    /// Stored LSP diagnostics per URI
+    /// Stored diagnostics per URI, merged across their sources
     /// Maps file URI string to Vec of diagnostics for that file
     stored_diagnostics: HashMap<String, Vec<lsp_types::Diagnostic>>,
 
+    /// Diagnostics per URI, kept apart by source ("lsp" or a provider name)
+    diagnostic_sources: HashMap<String, BTreeMap<String, Vec<lsp_types::Diagnostic>>>,
+
+    /// Providers run on opened and saved files
+    diagnostics_providers: DiagnosticsRegistry,
+
     /// Event broadcaster for control events (observable by external systems)
     event_broadcaster: crate::model::control_event::EventBroadcaster,
 
@@ -540,6 +580,8 @@ pub struct Editor {
     search_case_sensitive: bool,
     search_whole_word: bool,
     search_use_regex: bool,
+    /// Case-insensitive search becomes case-sensitive when the query has an uppercase letter
+    search_smart_case: bool,
     /// Whether to confirm each replacement (interactive/query-replace mode)
     search_confirm_each: bool,
 
@@ -591,6 +633,15 @@ pub struct Editor {
     /// Maps file path to last known modification time
     file_mod_times: HashMap<PathBuf, std::time::SystemTime>,
 
+    /// Pushed change notifications for open files on filesystems that
+    /// support them (local files on Linux, remote agents); these files
+    /// aren't polled
+    file_watches: HashMap<PathBuf, crate::model::filesystem::FileWatch>,
+
+    /// Modified buffers whose file changed on disk, waiting to ask whether
+    /// to keep the buffer or load the file
+    pending_file_conflicts: Vec<BufferId>,
+
     /// Last known modification times for expanded directories (for file tree refresh)
     /// Maps directory path to last known modification time
     dir_mod_times: HashMap<PathBuf, std::time::SystemTime>,
@@ -608,6 +659,10 @@ pub struct Editor {
     /// Recovery service for auto-recovery-save and crash recovery
     recovery_service: RecoveryService,
 
+    /// Recovery entries for files restored after a crash, waiting for the
+    /// user to recover or discard them (the first is being prompted for)
+    pending_restore_recoveries: Vec<(BufferId, crate::services::recovery::RecoveryEntry)>,
+
     /// Request a full terminal clear and redraw on the next frame
     full_redraw_requested: bool,
 
@@ -710,6 +765,9 @@ pub struct Editor {
     composite_view_states:
         HashMap<(LeafId, BufferId), crate::view::composite_view::CompositeViewState>,
 
+    /// Diff views opened by Compare with Saved/File, keyed by composite buffer
+    diff_views: HashMap<BufferId, diff_view::DiffView>,
+
     /// Pending file opens from CLI arguments (processed after TUI starts)
     /// This allows CLI files to go through the same code path as interactive file opens,
     /// ensuring consistent error handling (e.g., encoding confirmation prompts).
@@ -960,10 +1018,19 @@ impl Editor {
         let fs_manager = Arc::new(FsManager::new(Arc::clone(&filesystem)));
 
         // Initialize command registry (always available, used by both plugins and core)
-        let command_registry = Arc::new(RwLock::new(CommandRegistry::new()));
+        let mut command_registry = CommandRegistry::new();
+        command_registry.load_usage(dir_context.command_usage_path());
+        let command_registry = Arc::new(RwLock::new(command_registry));
 
         // Initialize file provider for Quick Open (stored separately for cache management)
-        let file_provider = Arc::new(FileProvider::new());
+        let file_provider = Arc::new(FileProvider::new(Arc::clone(&filesystem)));
+
+        // The ctags-like fallback is asked after any provider registered later
+        let mut definition_providers = DefinitionRegistry::new();
+        definition_providers.register(Arc::new(TagsProvider::new(
+            Arc::clone(&filesystem),
+            Arc::clone(&file_provider),
+        )));
 
         // Initialize Quick Open registry with providers
         let mut quick_open_registry = QuickOpenRegistry::new();
@@ -1175,6 +1242,9 @@ impl Editor {
             completion_items: None,
             scheduled_completion_trigger: None,
             pending_goto_definition_request: None,
+            definition_providers,
+            pending_definition_lookup: None,
+            next_definition_lookup_id: 0,
             pending_hover_request: None,
             pending_references_request: None,
             pending_references_symbol: String::new(),
@@ -1199,6 +1269,7 @@ impl Editor {
                 "lsp-diagnostic".to_string(),
             ),
             pending_search_range: None,
+            incremental_search_origin: None,
             interactive_replace_state: None,
             lsp_status: String::new(),
             mouse_state: MouseState::default(),
@@ -1207,6 +1278,10 @@ impl Editor {
             command_registry,
             quick_open_registry,
             file_provider,
+            project_search: None,
+            next_project_search_id: 0,
+            file_suggestions_request: None,
+            next_file_suggestions_id: 0,
             plugin_manager,
             seen_byte_ranges: HashMap::new(),
             panel_ids: HashMap::new(),
@@ -1232,11 +1307,14 @@ impl Editor {
             lsp_log_messages: Vec::new(),
             diagnostic_result_ids: HashMap::new(),
             stored_diagnostics: HashMap::new(),
+            diagnostic_sources: HashMap::new(),
+            diagnostics_providers: DiagnosticsRegistry::new(),
             event_broadcaster: crate::model::control_event::EventBroadcaster::default(),
             bookmarks: HashMap::new(),
             search_case_sensitive: true,
             search_whole_word: false,
             search_use_regex: false,
+            search_smart_case: false,
             search_confirm_each: false,
             macros: HashMap::new(),
             macro_recording: None,
@@ -1253,6 +1331,8 @@ impl Editor {
             last_auto_revert_poll: time_source.now(),
             last_file_tree_poll: time_source.now(),
             file_mod_times: HashMap::new(),
+            file_watches: HashMap::new(),
+            pending_file_conflicts: Vec::new(),
             dir_mod_times: HashMap::new(),
             file_rapid_change_counts: HashMap::new(),
             file_open_state: None,
@@ -1262,8 +1342,17 @@ impl Editor {
                     enabled: recovery_enabled,
                     ..RecoveryConfig::default()
                 };
-                RecoveryService::with_config_and_dir(recovery_config, dir_context.recovery_dir())
+                let mut service = RecoveryService::with_config_and_dir(
+                    recovery_config,
+                    dir_context.recovery_dir(),
+                );
+                // Keep recovery file writes off the UI thread
+                if let Err(e) = service.enable_background_writes() {
+                    tracing::warn!("Falling back to synchronous recovery saves: {}", e);
+                }
+                service
             },
+            pending_restore_recoveries: Vec::new(),
             full_redraw_requested: false,
             time_source: time_source.clone(),
             last_auto_recovery_save: time_source.now(),
@@ -1299,6 +1388,7 @@ impl Editor {
             active_action_popup: None,
             composite_buffers: HashMap::new(),
             composite_view_states: HashMap::new(),
+            diff_views: HashMap::new(),
         };
 
         // Apply clipboard configuration
@@ -1443,6 +1533,16 @@ impl Editor {
         self.active_state().editing_disabled
     }
 
+    /// Status message explaining why editing the active buffer is blocked
+    pub(crate) fn editing_disabled_message(&self) -> String {
+        match self.buffer_metadata.get(&self.active_buffer()) {
+            Some(metadata) if metadata.write_protected => {
+                t!("buffer.write_protected", name = metadata.display_name).to_string()
+            }
+            _ => t!("buffer.editing_disabled").to_string(),
+        }
+    }
+
     /// Resolve a keybinding for the current mode
     ///
     /// First checks the global editor mode (for vi mode and other modal editing).
@@ -2112,6 +2212,10 @@ impl Editor {
         // 2. Adjust cursors in other splits that share the same buffer
         self.adjust_other_split_cursors_for_event(event);
 
+        // 2b. Keep position history entries on the same text
+        self.position_history
+            .adjust_for_event(self.active_buffer(), event);
+
         // 3. Clear search highlights on edit (Insert/Delete events)
         // This preserves highlights while navigating but clears them when modifying text
         // EXCEPT during interactive replace where we want to keep highlights visible
@@ -2343,12 +2447,15 @@ impl Editor {
             new_snapshot: Some(new_snapshot),
             old_cursors,
             new_cursors,
+            edits: Event::bulk_edit_lengths(&edits),
             description,
         };
 
         // Post-processing (layout invalidation, split cursor sync, etc.)
         self.invalidate_layouts_for_buffer(self.active_buffer());
         self.adjust_other_split_cursors_for_event(&bulk_edit);
+        self.position_history
+            .adjust_for_event(active_buf, &bulk_edit);
         // Note: Do NOT clear search overlays - markers track through edits for F3/Shift+F3
 
         // Notify LSP of the change using full document replacement.
@@ -2990,6 +3097,7 @@ impl Editor {
         });
 
         // Start the prompt
+        let incremental = prompt_type == PromptType::IncrementalSearch;
         self.start_prompt(message, prompt_type);
 
         // Pre-fill with default text if available
@@ -3002,7 +3110,11 @@ impl Editor {
             if from_history {
                 self.get_or_create_prompt_history("search").init_at_last();
             }
-            self.update_search_highlights(&text);
+            if incremental {
+                self.update_incremental_search(&text);
+            } else {
+                self.update_search_highlights(&text);
+            }
         }
     }
 
@@ -3019,7 +3131,10 @@ impl Editor {
         // Clear search highlights when starting a new search prompt
         // This ensures old highlights from previous searches don't persist
         match prompt_type {
-            PromptType::Search | PromptType::ReplaceSearch | PromptType::QueryReplaceSearch => {
+            PromptType::Search
+            | PromptType::IncrementalSearch
+            | PromptType::ReplaceSearch
+            | PromptType::QueryReplaceSearch => {
                 self.clear_search_highlights();
             }
             _ => {}
@@ -3077,8 +3192,32 @@ impl Editor {
         self.update_quick_open_suggestions(">");
     }
 
+    /// Include or skip gitignored files in Quick Open's file list
+    fn quick_open_toggle_ignored(&mut self) {
+        let include = !self.file_provider.include_ignored();
+        self.file_provider.set_include_ignored(include);
+
+        let msg = if include {
+            t!("quick_open.showing_ignored")
+        } else {
+            t!("quick_open.hiding_ignored")
+        };
+        self.set_status_message(msg.to_string());
+
+        // Refresh the list if Quick Open is showing it
+        let input = self
+            .prompt
+            .as_ref()
+            .filter(|prompt| prompt.prompt_type == PromptType::QuickOpen)
+            .map(|prompt| prompt.input.clone());
+        if let Some(input) = input {
+            self.update_quick_open_suggestions(&input);
+        }
+    }
+
     /// Update Quick Open suggestions based on current input
     fn update_quick_open_suggestions(&mut self, input: &str) {
+        self.cancel_file_suggestions();
         let suggestions = if input.starts_with('>') {
             // Command mode
             let query = &input[1..];
@@ -3103,8 +3242,10 @@ impl Editor {
             let line_str = &input[1..];
             self.get_goto_line_suggestions(line_str)
         } else {
-            // File mode (default)
-            self.get_file_suggestions(input)
+            // File mode (default): listing the project may be slow, so the
+            // suggestions arrive asynchronously
+            self.request_file_suggestions(input);
+            return;
         };
 
         if let Some(prompt) = &mut self.prompt {
@@ -3183,8 +3324,10 @@ impl Editor {
             }];
         }
 
-        if let Ok(line_num) = line_str.parse::<usize>() {
-            if line_num > 0 {
+        match GotoLineTarget::parse(line_str) {
+            Some(GotoLineTarget::Absolute(0)) | None => {}
+            Some(target) => {
+                let line_num = self.resolve_goto_line_target(target);
                 return vec![Suggestion {
                     text: t!("quick_open.goto_line", line = line_num.to_string()).to_string(),
                     description: Some(t!("quick_open.press_enter").to_string()),
@@ -3239,6 +3382,7 @@ impl Editor {
             if matches!(
                 prompt.prompt_type,
                 PromptType::Search
+                    | PromptType::IncrementalSearch
                     | PromptType::ReplaceSearch
                     | PromptType::Replace { .. }
                     | PromptType::QueryReplaceSearch
@@ -3468,6 +3612,14 @@ impl Editor {
                 PromptType::Search | PromptType::ReplaceSearch | PromptType::QueryReplaceSearch => {
                     self.clear_search_highlights();
                 }
+                PromptType::IncrementalSearch => {
+                    // Leaving incremental search keeps the cursor on the current
+                    // match and its highlights, so F3 carries on from there
+                    let query = prompt.input.clone();
+                    if !query.is_empty() {
+                        self.get_or_create_prompt_history("search").push(query);
+                    }
+                }
                 PromptType::Plugin { custom_type } => {
                     // Fire plugin hook for prompt cancellation
                     use crate::services::plugins::hooks::HookArgs;
@@ -3498,6 +3650,17 @@ impl Editor {
                             .resolve_callback(callback_id, "null".to_string());
                     }
                 }
+                PromptType::ConfirmRestoreRecovery { recovery_id } => {
+                    // Keep the recovery entry on disk and move on to the next file
+                    let recovery_id = recovery_id.clone();
+                    self.skip_restore_recovery(&recovery_id);
+                }
+                PromptType::SearchInProject => {
+                    self.cancel_project_search();
+                }
+                PromptType::QuickOpen => {
+                    self.cancel_file_suggestions();
+                }
                 _ => {}
             }
         }
@@ -3505,6 +3668,7 @@ impl Editor {
         self.prompt = None;
         self.pending_search_range = None;
         self.status_message = Some(t!("search.cancelled").to_string());
+        self.offer_next_restore_recovery();
 
         // Restore original theme if we were in SelectTheme prompt
         if let Some(original_theme) = theme_to_restore {
@@ -3570,9 +3734,13 @@ impl Editor {
                     | PromptType::SelectTheme { .. }
                     | PromptType::SelectLocale
                     | PromptType::SwitchToTab
+                    | PromptType::SwitchWorkspace
+                    | PromptType::PasteFromHistory
                     | PromptType::SetLanguage
                     | PromptType::SetEncoding
+                    | PromptType::SaveWithEncoding
                     | PromptType::SetLineEnding
+                    | PromptType::NormalizeLineEndings
                     | PromptType::Plugin { .. }
             ) {
                 // Use the selected suggestion if any
@@ -3687,9 +3855,10 @@ impl Editor {
     fn prompt_type_to_history_key(prompt_type: &crate::view::prompt::PromptType) -> Option<String> {
         use crate::view::prompt::PromptType;
         match prompt_type {
-            PromptType::Search | PromptType::ReplaceSearch | PromptType::QueryReplaceSearch => {
-                Some("search".to_string())
-            }
+            PromptType::Search
+            | PromptType::IncrementalSearch
+            | PromptType::ReplaceSearch
+            | PromptType::QueryReplaceSearch => Some("search".to_string()),
             PromptType::Replace { .. } | PromptType::QueryReplace { .. } => {
                 Some("replace".to_string())
             }
@@ -3802,6 +3971,9 @@ impl Editor {
                 // Update Quick Open suggestions based on prefix
                 self.update_quick_open_suggestions(&input);
             }
+            PromptType::SearchInProject => {
+                self.run_project_search(&input);
+            }
             PromptType::Search | PromptType::ReplaceSearch | PromptType::QueryReplaceSearch => {
                 // Update incremental search highlights as user types
                 self.update_search_highlights(&input);
@@ -3810,6 +3982,13 @@ impl Editor {
                     history.reset_navigation();
                 }
             }
+            PromptType::IncrementalSearch => {
+                // Jump to the nearest match as the query grows
+                self.update_incremental_search(&input);
+                if let Some(history) = self.prompt_histories.get_mut("search") {
+                    history.reset_navigation();
+                }
+            }
             PromptType::Replace { .. } | PromptType::QueryReplace { .. } => {
                 // Reset history navigation when user types - allows Up to navigate history
                 if let Some(history) = self.prompt_histories.get_mut("replace") {
@@ -3850,11 +4029,15 @@ impl Editor {
                 }
             }
             PromptType::SwitchToTab
+            | PromptType::SwitchWorkspace
+            | PromptType::PasteFromHistory
             | PromptType::SelectTheme { .. }
             | PromptType::StopLspServer
             | PromptType::SetLanguage
             | PromptType::SetEncoding
-            | PromptType::SetLineEnding => {
+            | PromptType::SaveWithEncoding
+            | PromptType::SetLineEnding
+            | PromptType::NormalizeLineEndings => {
                 if let Some(prompt) = &mut self.prompt {
                     prompt.filter_suggestions(false);
                 }
@@ -4187,6 +4370,32 @@ impl Editor {
                 AsyncMessage::FileOpenShortcutsLoaded(shortcuts) => {
                     self.handle_file_open_shortcuts_loaded(shortcuts);
                 }
+                AsyncMessage::ProjectSearchResults {
+                    search_id,
+                    matches,
+                    done,
+                } => {
+                    self.handle_project_search_results(search_id, matches, done);
+                }
+                AsyncMessage::DefinitionFound {
+                    request_id,
+                    location,
+                } => {
+                    self.handle_definition_found(request_id, location);
+                }
+                AsyncMessage::ProviderDiagnostics {
+                    uri,
+                    source,
+                    diagnostics,
+                } => {
+                    self.store_and_apply_diagnostics(uri, &source, diagnostics);
+                }
+                AsyncMessage::QuickOpenFileSuggestions {
+                    request_id,
+                    suggestions,
+                } => {
+                    self.handle_file_suggestions(request_id, suggestions);
+                }
                 AsyncMessage::TerminalOutput { terminal_id } => {
                     // Terminal output received - check if we should auto-jump back to terminal mode
                     tracing::trace!("Terminal output received for {:?}", terminal_id);
@@ -4618,6 +4827,13 @@ impl Editor {
             PluginCommand::DeleteRange { buffer_id, range } => {
                 self.handle_delete_range(buffer_id, range);
             }
+            PluginCommand::ReplaceRange {
+                buffer_id,
+                range,
+                text,
+            } => {
+                self.handle_replace_range(buffer_id, range, text);
+            }
             PluginCommand::InsertAtCursor { text } => {
                 self.handle_insert_at_cursor(text);
             }
@@ -4795,6 +5011,19 @@ impl Editor {
             } => {
                 self.handle_request_highlights(buffer_id, range, request_id);
             }
+            PluginCommand::RequestCodeHighlights {
+                code,
+                language,
+                request_id,
+            } => {
+                self.handle_request_code_highlights(&code, &language, request_id);
+            }
+            PluginCommand::RequestDocumentSymbols {
+                buffer_id,
+                request_id,
+            } => {
+                self.handle_request_document_symbols(buffer_id, request_id);
+            }
             PluginCommand::CloseSplit { split_id } => {
                 self.handle_close_split(split_id);
             }
@@ -6023,6 +6252,7 @@ impl Editor {
 
     /// Save a buffer to a specific file path (for :w filename)
     fn handle_save_buffer_to_path(&mut self, buffer_id: BufferId, path: std::path::PathBuf) {
+        self.request_backup_if_enabled(buffer_id);
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
             // Save to the specified path
             match state.buffer.save_to_file(&path) {
@@ -6626,8 +6856,8 @@ mod tests {
             new_position: 6,
             old_anchor: None, // TODO: Get actual old anchor
             new_anchor: None,
-            old_sticky_column: 0,
-            new_sticky_column: 0,
+            old_sticky_column: None,
+            new_sticky_column: None,
         });
 
         // Test move up
@@ -6762,8 +6992,8 @@ mod tests {
             new_position: 0,
             old_anchor: None, // TODO: Get actual old anchor
             new_anchor: None,
-            old_sticky_column: 0,
-            new_sticky_column: 0,
+            old_sticky_column: None,
+            new_sticky_column: None,
         });
 
         let events = editor.action_to_events(Action::DeleteForward);
@@ -6814,8 +7044,8 @@ mod tests {
             new_position: 0,
             old_anchor: None, // TODO: Get actual old anchor
             new_anchor: None,
-            old_sticky_column: 0,
-            new_sticky_column: 0,
+            old_sticky_column: None,
+            new_sticky_column: None,
         });
 
         let events = editor.action_to_events(Action::SelectRight);
@@ -7247,8 +7477,8 @@ mod tests {
             new_position: 10,
             old_anchor: None,
             new_anchor: None,
-            old_sticky_column: 0,
-            new_sticky_column: 0,
+            old_sticky_column: None,
+            new_sticky_column: None,
         });
 
         assert_eq!(editor.active_cursors().primary().position, 10);
@@ -7292,8 +7522,8 @@ mod tests {
             new_position: 26,
             old_anchor: None,
             new_anchor: None,
-            old_sticky_column: 0,
-            new_sticky_column: 0,
+            old_sticky_column: None,
+            new_sticky_column: None,
         });
 
         // Call goto_matching_bracket
@@ -7332,8 +7562,8 @@ mod tests {
             new_position: 0,
             old_anchor: None,
             new_anchor: None,
-            old_sticky_column: 0,
-            new_sticky_column: 0,
+            old_sticky_column: None,
+            new_sticky_column: None,
         });
 
         // Call goto_matching_bracket
@@ -7343,6 +7573,61 @@ mod tests {
         assert_eq!(editor.active_cursors().primary().position, 10);
     }
 
+    #[test]
+    fn test_select_to_matching_bracket() {
+        let config = Config::default();
+        let (dir_context, _temp) = test_dir_context();
+        let mut editor = Editor::new(
+            config,
+            80,
+            24,
+            dir_context,
+            crate::view::color_support::ColorCapability::TrueColor,
+            test_filesystem(),
+        )
+        .unwrap();
+
+        // Insert text with nested brackets
+        let cursor_id = editor.active_cursors().primary_id();
+        editor.apply_event_to_active_buffer(&Event::Insert {
+            position: 0,
+            text: "f(a[b]c) x".to_string(),
+            cursor_id,
+        });
+
+        // Cursor just after the inner ']'
+        editor.apply_event_to_active_buffer(&Event::MoveCursor {
+            cursor_id,
+            old_position: 10,
+            new_position: 6,
+            old_anchor: None,
+            new_anchor: None,
+            old_sticky_column: None,
+            new_sticky_column: None,
+        });
+
+        editor.select_to_matching_bracket();
+
+        // Selects "[b]", both brackets included
+        assert_eq!(
+            editor.active_cursors().primary().selection_range(),
+            Some(3..6)
+        );
+
+        // No bracket next to the cursor: selection is left alone
+        editor.apply_event_to_active_buffer(&Event::MoveCursor {
+            cursor_id,
+            old_position: 6,
+            new_position: 10,
+            old_anchor: Some(3),
+            new_anchor: None,
+            old_sticky_column: None,
+            new_sticky_column: None,
+        });
+        editor.select_to_matching_bracket();
+        assert_eq!(editor.active_cursors().primary().selection_range(), None);
+    }
+
     #[test]
     fn test_search_case_sensitive() {
         let config = Config::default();
@@ -7469,8 +7754,8 @@ mod tests {
             new_position: 7,
             old_anchor: None,
             new_anchor: None,
-            old_sticky_column: 0,
-            new_sticky_column: 0,
+            old_sticky_column: None,
+            new_sticky_column: None,
         });
 
         // Set bookmark '1'
@@ -7485,8 +7770,8 @@ mod tests {
             new_position: 14,
             old_anchor: None,
             new_anchor: None,
-            old_sticky_column: 0,
-            new_sticky_column: 0,
+            old_sticky_column: None,
+            new_sticky_column: None,
         });
 
         // Jump back to bookmark
@@ -9,7 +9,10 @@
 
 use super::*;
 use crate::input::keybindings::Action;
+use crate::model::cursor::Position2D;
 use crate::model::event::{ContainerId, CursorId, LeafId, SplitDirection};
+use crate::primitives::block_selection::{byte_to_visual_2d, visual_2d_to_byte};
+use crate::primitives::word_navigation::find_word_range_at;
 use crate::services::plugins::hooks::HookArgs;
 use crate::view::popup_mouse::{popup_areas_to_layout_info, PopupHitTester};
 use crate::view::prompt::PromptType;
@@ -119,12 +122,14 @@ impl Editor {
                 if is_triple_click {
                     // Triple click detected - select entire line
                     self.handle_mouse_triple_click(col, row)?;
+                    self.update_primary_selection();
                     needs_render = true;
                     return Ok(needs_render);
                 }
                 if is_double_click {
                     // Double click detected - both clicks within time threshold AND at same position
                     self.handle_mouse_double_click(col, row)?;
+                    self.update_primary_selection();
                     needs_render = true;
                     return Ok(needs_render);
                 }
@@ -152,6 +157,18 @@ impl Editor {
                     }
                 }
 
+                // The view may have scrolled since the last drag event, so
+                // extend the selection to the edge row one last time
+                if self.mouse_state.dragging_text_selection
+                    && self.mouse_state.drag_autoscroll_position.is_some()
+                {
+                    self.handle_text_selection_drag(col, row)?;
+                }
+
+                if self.mouse_state.dragging_text_selection {
+                    self.update_primary_selection();
+                }
+
                 // Stop dragging and clear drag state
                 self.mouse_state.dragging_scrollbar = None;
                 self.mouse_state.drag_start_row = None;
@@ -168,6 +185,9 @@ impl Editor {
                 self.mouse_state.dragging_text_selection = false;
                 self.mouse_state.drag_selection_split = None;
                 self.mouse_state.drag_selection_anchor = None;
+                self.mouse_state.drag_block_anchor = None;
+                self.mouse_state.drag_autoscroll_position = None;
+                self.mouse_state.drag_autoscroll_last_step = None;
                 // Clear popup scrollbar drag state
                 self.mouse_state.dragging_popup_scrollbar = None;
                 self.mouse_state.drag_start_popup_scroll = None;
@@ -291,6 +311,10 @@ impl Editor {
                 self.handle_horizontal_scroll(col, row, 3)?;
                 needs_render = true;
             }
+            MouseEventKind::Down(MouseButton::Middle) => {
+                self.handle_middle_click(col, row)?;
+                needs_render = true;
+            }
             MouseEventKind::Down(MouseButton::Right) => {
                 // Handle right-click for context menus
                 self.handle_right_click(col, row)?;
@@ -1028,7 +1052,16 @@ impl Editor {
                 return Ok(());
             };
 
-            // Move cursor to clicked position first
+            // Select the word under the click, using the same Unicode word
+            // segmentation as keyboard word motion. Clicking whitespace or
+            // punctuation selects that run instead.
+            let word = find_word_range_at(&state.buffer, target_position);
+            let (new_position, new_anchor) = if word.is_empty() {
+                (target_position, None)
+            } else {
+                (word.end, Some(word.start))
+            };
+
             let primary_cursor_id = self
                 .split_view_states
                 .get(&leaf_id)
@@ -1037,11 +1070,11 @@ impl Editor {
             let event = Event::MoveCursor {
                 cursor_id: primary_cursor_id,
                 old_position: 0,
-                new_position: target_position,
+                new_position,
                 old_anchor: None,
-                new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                new_anchor,
+                old_sticky_column: None,
+                new_sticky_column: None,
             };
 
             if let Some(event_log) = self.event_logs.get_mut(&buffer_id) {
@@ -1056,9 +1089,6 @@ impl Editor {
             }
         }
 
-        // Now select the word under cursor
-        self.handle_action(Action::SelectWord)?;
-
         Ok(())
     }
     /// Handle mouse triple click (down event)
@@ -1162,8 +1192,8 @@ impl Editor {
                 new_position: target_position,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             };
 
             if let Some(event_log) = self.event_logs.get_mut(&buffer_id) {
@@ -1452,6 +1482,23 @@ impl Editor {
         if let Some((split_id, buffer_id, scrollbar_rect, is_on_thumb)) = scrollbar_hit {
             self.focus_split(split_id, buffer_id);
 
+            // Clicking a change or search marker scrolls to its line
+            let relative_row = row.saturating_sub(scrollbar_rect.y) as usize;
+            let marker_line_start = self
+                .cached_layout
+                .scrollbar_markers
+                .get(&split_id)
+                .and_then(|markers| {
+                    markers
+                        .iter()
+                        .find(|(marker_row, _)| *marker_row == relative_row)
+                })
+                .map(|&(_, line_start)| line_start);
+            if let Some(line_start) = marker_line_start {
+                self.scroll_to_scrollbar_marker(split_id, buffer_id, line_start);
+                return Ok(());
+            }
+
             if is_on_thumb {
                 // Click on thumb - start drag from current position (don't jump)
                 self.mouse_state.dragging_scrollbar = Some(split_id);
@@ -2007,6 +2054,39 @@ impl Editor {
         Ok(())
     }
 
+    /// The (line, visual column) under the mouse for block selection
+    ///
+    /// Past the end of a line the mouse is in virtual space, so the column
+    /// comes from the screen rather than from the clamped buffer position.
+    pub(super) fn block_position_at(
+        state: &EditorState,
+        target_position: usize,
+        col: u16,
+        content_rect: ratatui::layout::Rect,
+        gutter_width: u16,
+        left_column: usize,
+    ) -> Position2D {
+        let buffer = &state.buffer;
+        let tab_size = state.buffer_settings.tab_size;
+        let pos = byte_to_visual_2d(buffer, target_position, tab_size);
+        let line_end = visual_2d_to_byte(
+            buffer,
+            Position2D {
+                line: pos.line,
+                column: usize::MAX,
+            },
+            tab_size,
+        );
+        if target_position < line_end {
+            return pos;
+        }
+        let screen_col = col.saturating_sub(content_rect.x + gutter_width) as usize + left_column;
+        Position2D {
+            line: pos.line,
+            column: pos.column.max(screen_col),
+        }
+    }
+
     /// Handle text selection drag - extends selection from anchor to current position
     fn handle_text_selection_drag(&mut self, col: u16, row: u16) -> AnyhowResult<()> {
         use crate::model::event::Event;
@@ -2042,6 +2122,15 @@ impl Editor {
             return Ok(());
         };
 
+        // Above or below the split the view keeps scrolling toward the
+        // mouse (see check_drag_autoscroll_timer), while the selection
+        // extends to the nearest edge row
+        let outside = row < content_rect.y || row >= content_rect.y + content_rect.height;
+        self.mouse_state.drag_autoscroll_position = outside.then_some((col, row));
+        if !outside {
+            self.mouse_state.drag_autoscroll_last_step = None;
+        }
+
         // Get cached view line mappings for this split
         let cached_mappings = self
             .cached_layout
@@ -2064,6 +2153,13 @@ impl Editor {
             .get(&leaf_id)
             .and_then(|vs| vs.compose_width);
 
+        let left_column = self
+            .split_view_states
+            .get(&leaf_id)
+            .map(|vs| vs.viewport.left_column)
+            .unwrap_or(0);
+        let block_anchor = self.mouse_state.drag_block_anchor;
+
         // Calculate the target position from screen coordinates
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
             let gutter_width = state.margins.left_total_width() as u16;
@@ -2087,14 +2183,37 @@ impl Editor {
                 .get(&leaf_id)
                 .map(|vs| vs.cursors.primary_id())
                 .unwrap_or(CursorId(0));
-            let event = Event::MoveCursor {
-                cursor_id: primary_cursor_id,
-                old_position: 0,
-                new_position: target_position,
-                old_anchor: None,
-                new_anchor: Some(anchor_position), // Keep anchor to maintain selection
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+            let event = if let Some(block_anchor) = block_anchor {
+                // Alt+drag: the sticky column carries the block's visual edge,
+                // which may lie past the end of the line under the mouse
+                let current = Self::block_position_at(
+                    state,
+                    target_position,
+                    col,
+                    content_rect,
+                    gutter_width,
+                    left_column,
+                );
+                let tab_size = state.buffer_settings.tab_size;
+                Event::MoveCursor {
+                    cursor_id: primary_cursor_id,
+                    old_position: 0,
+                    new_position: visual_2d_to_byte(&state.buffer, current, tab_size),
+                    old_anchor: None,
+                    new_anchor: Some(visual_2d_to_byte(&state.buffer, block_anchor, tab_size)),
+                    old_sticky_column: None,
+                    new_sticky_column: Some(current.column),
+                }
+            } else {
+                Event::MoveCursor {
+                    cursor_id: primary_cursor_id,
+                    old_position: 0,
+                    new_position: target_position,
+                    old_anchor: None,
+                    new_anchor: Some(anchor_position), // Keep anchor to maintain selection
+                    old_sticky_column: None,
+                    new_sticky_column: None,
+                }
             };
 
             if let Some(event_log) = self.event_logs.get_mut(&buffer_id) {
@@ -2106,12 +2225,79 @@ impl Editor {
                 .map(|vs| &mut vs.cursors)
             {
                 state.apply(cursors, &event);
+                if let (Some(block_anchor), Some(cursor)) =
+                    (block_anchor, cursors.get_mut(primary_cursor_id))
+                {
+                    cursor.start_block_selection(block_anchor.line, block_anchor.column);
+                }
+            }
+        }
+
+        // Only the autoscroll timer scrolls the view past the edge, one
+        // step at a time, so keep the cursor's scroll margin from jumping it
+        if outside {
+            if let Some(view_state) = self.split_view_states.get_mut(&leaf_id) {
+                view_state.viewport.set_skip_ensure_visible();
             }
         }
 
         Ok(())
     }
 
+    /// Scroll the view while a text selection drag is held above or below
+    /// its split, extending the selection to the edge row before each step
+    ///
+    /// Terminals only report drags when the mouse moves, so this is polled
+    /// from the event loop. The further past the edge, the more rows each
+    /// step scrolls. Returns true if a redraw is needed.
+    pub fn check_drag_autoscroll_timer(&mut self) -> bool {
+        const AUTOSCROLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(50);
+
+        if !self.mouse_state.dragging_text_selection {
+            return false;
+        }
+        let Some((col, row)) = self.mouse_state.drag_autoscroll_position else {
+            return false;
+        };
+        let now = self.time_source.now();
+        if self
+            .mouse_state
+            .drag_autoscroll_last_step
+            .is_some_and(|last| now.duration_since(last) < AUTOSCROLL_INTERVAL)
+        {
+            return false;
+        }
+        let Some(split_id) = self.mouse_state.drag_selection_split else {
+            return false;
+        };
+        let Some((buffer_id, content_rect)) = self
+            .cached_layout
+            .split_areas
+            .iter()
+            .find(|(sid, _, _, _, _, _)| *sid == split_id)
+            .map(|(_, bid, rect, _, _, _)| (*bid, *rect))
+        else {
+            return false;
+        };
+
+        // The cached layout shows the rows scrolled in by the last step
+        if let Err(e) = self.handle_text_selection_drag(col, row) {
+            tracing::debug!("Failed to extend selection while autoscrolling: {}", e);
+            return false;
+        }
+        let content_bottom = content_rect.y + content_rect.height;
+        let delta = if row < content_rect.y {
+            -i32::from(content_rect.y - row)
+        } else {
+            i32::from(row + 1).saturating_sub(i32::from(content_bottom))
+        };
+        if delta != 0 {
+            self.scroll_split_viewport(split_id, buffer_id, delta);
+        }
+        self.mouse_state.drag_autoscroll_last_step = Some(now);
+        true
+    }
+
     /// Handle file explorer border drag for resizing
     pub(super) fn handle_file_explorer_border_drag(&mut self, col: u16) -> AnyhowResult<()> {
         let Some((start_col, _start_row)) = self.mouse_state.drag_start_position else {
@@ -2225,6 +2411,61 @@ impl Editor {
         Ok(())
     }
 
+    /// Handle middle-click: paste the primary selection where the mouse is
+    pub(super) fn handle_middle_click(&mut self, col: u16, row: u16) -> AnyhowResult<()> {
+        let Some((split_id, buffer_id, content_rect)) = self
+            .cached_layout
+            .split_areas
+            .iter()
+            .find(|(_, _, rect, _, _, _)| {
+                col >= rect.x
+                    && col < rect.x + rect.width
+                    && row >= rect.y
+                    && row < rect.y + rect.height
+            })
+            .map(|(sid, bid, rect, _, _, _)| (*sid, *bid, *rect))
+        else {
+            return Ok(());
+        };
+        if self.is_composite_buffer(buffer_id) || self.is_terminal_buffer(buffer_id) {
+            return Ok(());
+        }
+
+        self.focus_split(split_id, buffer_id);
+        if self.is_editing_disabled() {
+            return Ok(());
+        }
+
+        let cached_mappings = self
+            .cached_layout
+            .view_line_mappings
+            .get(&split_id)
+            .cloned();
+        let Some(view_state) = self.split_view_states.get(&split_id) else {
+            return Ok(());
+        };
+        let fallback = view_state.viewport.top_byte;
+        let compose_width = view_state.compose_width;
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return Ok(());
+        };
+        let gutter_width = state.margins.left_total_width() as u16;
+
+        if let Some(position) = Self::screen_to_buffer_position(
+            col,
+            row,
+            content_rect,
+            gutter_width,
+            &cached_mappings,
+            fallback,
+            false,
+            compose_width,
+        ) {
+            self.paste_primary_at(position);
+        }
+        Ok(())
+    }
+
     /// Handle left-click on tab context menu
     pub(super) fn handle_tab_context_menu_click(
         &mut self,
@@ -11,6 +11,8 @@ use std::time::Duration;
 use super::Editor;
 use crate::config::{FormatterConfig, OnSaveAction};
 use crate::model::event::Event;
+use crate::primitives::edit_offsets::map_offset;
+use crate::primitives::indent_convert::convert_indentation_edits;
 use rust_i18n::t;
 
 /// Result of running a formatter or on-save action
@@ -36,27 +38,6 @@ impl Editor {
 
         let mut ran_any_action = false;
 
-        // Run whitespace cleanup actions first (before formatter)
-        if self.config.editor.trim_trailing_whitespace_on_save {
-            if self.trim_trailing_whitespace()? {
-                ran_any_action = true;
-            }
-        }
-
-        if self.config.editor.ensure_final_newline_on_save {
-            if self.ensure_final_newline()? {
-                ran_any_action = true;
-            }
-        }
-
-        // If whitespace cleanup made changes, re-save
-        if ran_any_action {
-            if let Err(e) = self.active_state_mut().buffer.save() {
-                return Err(format!("Failed to re-save after whitespace cleanup: {}", e));
-            }
-            self.active_event_log_mut().mark_saved();
-        }
-
         // Get language from buffer's stored state
         let language = self.active_state().language.clone();
 
@@ -452,7 +433,7 @@ impl Editor {
                 new_position: new_cursor_pos,
                 old_anchor: None,
                 new_anchor: old_anchor.map(|a| a.min(new_buffer_len)),
-                old_sticky_column: 0,
+                old_sticky_column: None,
                 new_sticky_column: old_sticky_column,
             };
             events.push(move_cursor_event);
@@ -469,51 +450,194 @@ impl Editor {
         Ok(())
     }
 
-    /// Trim trailing whitespace from all lines in the active buffer.
+    /// Apply the whitespace cleanup enabled for the active buffer before it is
+    /// written, so the cleaned text is what gets saved.
+    ///
+    /// Each change is a targeted edit rather than a full replacement, so the
+    /// untouched parts of the piece tree still map onto the file on disk and
+    /// `write_patched` only sends the changed regions. Buffers with unloaded
+    /// regions (large files) are left alone rather than loaded in full.
+    pub(crate) fn run_whitespace_cleanup_before_save(&mut self) {
+        let settings = &self.active_state().buffer_settings;
+        let trim = settings
+            .trim_trailing_whitespace_on_save
+            .unwrap_or(self.config.editor.trim_trailing_whitespace_on_save);
+        let final_newline = settings
+            .ensure_final_newline_on_save
+            .unwrap_or(self.config.editor.ensure_final_newline_on_save);
+
+        if trim {
+            if let Err(e) = self.trim_trailing_whitespace() {
+                tracing::warn!("Failed to trim trailing whitespace: {}", e);
+            }
+        }
+        if final_newline {
+            if let Err(e) = self.ensure_final_newline() {
+                tracing::warn!("Failed to ensure final newline: {}", e);
+            }
+        }
+    }
+
+    /// Trim trailing spaces and tabs from all lines in the active buffer.
     /// Returns Ok(true) if any changes were made, Ok(false) if buffer unchanged.
     pub fn trim_trailing_whitespace(&mut self) -> Result<bool, String> {
-        let content = self.active_state().buffer.to_string().unwrap_or_default();
-
-        // Process each line and trim trailing whitespace
-        let trimmed: String = content
-            .lines()
-            .map(|line| line.trim_end())
-            .collect::<Vec<_>>()
-            .join("\n");
-
-        // Preserve original trailing newline if present
-        let trimmed = if content.ends_with('\n') && !trimmed.ends_with('\n') {
-            format!("{}\n", trimmed)
-        } else {
-            trimmed
+        let Some(content) = self.active_state().buffer.to_string() else {
+            return Ok(false);
         };
 
-        if trimmed == content {
+        let edits: Vec<_> = trailing_whitespace_ranges(content.as_bytes())
+            .into_iter()
+            .map(|range| (range, String::new()))
+            .collect();
+        if edits.is_empty() {
             return Ok(false);
         }
 
-        self.replace_buffer_with_output(&trimmed)?;
+        self.apply_cleanup_edits(&content, edits, "Trim trailing whitespace");
         Ok(true)
     }
 
-    /// Ensure the buffer ends with a newline.
-    /// Returns Ok(true) if a newline was added, Ok(false) if already ends with newline.
+    /// Ensure the buffer ends with exactly one newline.
+    /// Returns Ok(true) if the end of the buffer changed, Ok(false) otherwise.
     pub fn ensure_final_newline(&mut self) -> Result<bool, String> {
-        let content = self.active_state().buffer.to_string().unwrap_or_default();
+        let state = self.active_state();
+        let Some(content) = state.buffer.to_string() else {
+            return Ok(false);
+        };
+        let line_ending = state.buffer.line_ending().as_str();
 
-        // Empty buffers don't need a newline
-        if content.is_empty() {
+        let Some(edit) = final_newline_edit(content.as_bytes(), line_ending) else {
             return Ok(false);
-        }
+        };
 
-        if content.ends_with('\n') {
+        self.apply_cleanup_edits(&content, vec![edit], "Ensure final newline");
+        Ok(true)
+    }
+
+    /// Convert the leading indentation of every line in the active buffer to
+    /// tabs (`to_tabs`) or spaces, using the buffer's tab size for tab stops.
+    /// Tabs after the indentation are left alone, and the buffer's
+    /// `use_tabs` setting follows the conversion.
+    /// Returns Ok(true) if any changes were made, Ok(false) if buffer unchanged.
+    pub fn convert_indentation(&mut self, to_tabs: bool) -> Result<bool, String> {
+        let state = self.active_state_mut();
+        let tab_size = state.buffer_settings.tab_size;
+        let Some(content) = state.buffer.to_string() else {
+            return Ok(false);
+        };
+        state.buffer_settings.use_tabs = to_tabs;
+
+        let edits = convert_indentation_edits(&content, tab_size, to_tabs);
+        if edits.is_empty() {
             return Ok(false);
         }
 
-        let with_newline = format!("{}\n", content);
-        self.replace_buffer_with_output(&with_newline)?;
+        let description = if to_tabs {
+            "Convert indentation to tabs"
+        } else {
+            "Convert indentation to spaces"
+        };
+        self.apply_cleanup_edits(&content, edits, description);
         Ok(true)
     }
+
+    /// Apply sorted, non-overlapping `(range, replacement)` edits to the active
+    /// buffer as a single undo step, keeping every cursor and selection on the
+    /// same characters.
+    fn apply_cleanup_edits(
+        &mut self,
+        content: &str,
+        edits: Vec<(std::ops::Range<usize>, String)>,
+        description: &str,
+    ) {
+        let cursors = self.active_cursors();
+        let cursor_id = cursors.primary_id();
+        let map = |pos| {
+            map_offset(
+                pos,
+                edits
+                    .iter()
+                    .map(|(range, text)| (range.clone(), text.len())),
+            )
+        };
+
+        // Explicit cursor moves take precedence over the edit events' own
+        // cursor updates in the bulk edit
+        let mut events: Vec<Event> = cursors
+            .iter()
+            .map(|(id, cursor)| Event::MoveCursor {
+                cursor_id: id,
+                old_position: cursor.position,
+                new_position: map(cursor.position),
+                old_anchor: cursor.anchor,
+                new_anchor: cursor.anchor.map(map),
+                old_sticky_column: cursor.sticky_column,
+                new_sticky_column: cursor.sticky_column,
+            })
+            .collect();
+
+        for (range, text) in edits {
+            if !range.is_empty() {
+                events.push(Event::Delete {
+                    deleted_text: content[range.clone()].to_string(),
+                    range: range.clone(),
+                    cursor_id,
+                });
+            }
+            if !text.is_empty() {
+                events.push(Event::Insert {
+                    position: range.start,
+                    text,
+                    cursor_id,
+                });
+            }
+        }
+
+        if let Some(bulk_edit) = self.apply_events_as_bulk_edit(events, description.to_string()) {
+            self.active_event_log_mut().append(bulk_edit);
+        }
+    }
+}
+
+/// Byte ranges of the spaces and tabs at the end of each line
+fn trailing_whitespace_ranges(text: &[u8]) -> Vec<std::ops::Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut run_start = None;
+    for (i, &byte) in text.iter().enumerate() {
+        match byte {
+            b' ' | b'\t' => {
+                run_start.get_or_insert(i);
+            }
+            b'\n' | b'\r' => {
+                if let Some(start) = run_start.take() {
+                    ranges.push(start..i);
+                }
+            }
+            _ => run_start = None,
+        }
+    }
+    if let Some(start) = run_start {
+        ranges.push(start..text.len());
+    }
+    ranges
+}
+
+/// Edit that makes non-empty `text` end with exactly one line ending:
+/// appends `line_ending` if there is none, or removes the extra blank lines
+fn final_newline_edit(text: &[u8], line_ending: &str) -> Option<(std::ops::Range<usize>, String)> {
+    if text.is_empty() {
+        return None;
+    }
+    let content_end = text
+        .iter()
+        .rposition(|&b| b != b'\n' && b != b'\r')
+        .map_or(0, |i| i + 1);
+    let tail = &text[content_end..];
+    if tail.is_empty() {
+        return Some((text.len()..text.len(), line_ending.to_string()));
+    }
+    let first_ending = if tail.starts_with(b"\r\n") { 2 } else { 1 };
+    (tail.len() > first_ending).then(|| (content_end + first_ending..text.len(), String::new()))
 }
 
 /// Check if a command exists in the system PATH.
@@ -684,23 +684,7 @@ impl Editor {
                 &self.theme,
                 self.config.editor.highlight_context_bytes,
             );
-
-            spans
-                .into_iter()
-                .map(|s| {
-                    let color = match s.color {
-                        ratatui::style::Color::Rgb(r, g, b) => (r, g, b),
-                        _ => (128, 128, 128), // fallback for indexed colors
-                    };
-                    fresh_core::api::TsHighlightSpan {
-                        start: s.range.start as u32,
-                        end: s.range.end as u32,
-                        color,
-                        bold: false,
-                        italic: false,
-                    }
-                })
-                .collect()
+            to_ts_highlight_spans(spans)
         } else {
             vec![]
         };
@@ -708,6 +692,56 @@ impl Editor {
         self.send_plugin_response(PluginResponse::HighlightsComputed { request_id, spans });
     }
 
+    /// Handle RequestCodeHighlights command
+    pub(super) fn handle_request_code_highlights(
+        &mut self,
+        code: &str,
+        language: &str,
+        request_id: u64,
+    ) {
+        let spans = crate::primitives::highlight_engine::highlight_string(
+            code,
+            language,
+            &self.grammar_registry,
+            &self.theme,
+        );
+        let spans = to_ts_highlight_spans(spans);
+
+        self.send_plugin_response(PluginResponse::HighlightsComputed { request_id, spans });
+    }
+
+    /// Handle RequestDocumentSymbols command
+    pub(super) fn handle_request_document_symbols(&mut self, buffer_id: BufferId, request_id: u64) {
+        let symbols = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| {
+                let source = state.buffer.to_string()?;
+                Some(crate::primitives::outline::document_outline(
+                    state.highlighter.language(),
+                    &state.language,
+                    &source,
+                ))
+            })
+            .unwrap_or_default()
+            .into_iter()
+            .map(|symbol| fresh_core::api::TsDocumentSymbol {
+                name: symbol.name,
+                kind: symbol.kind.to_string(),
+                depth: symbol.depth as u32,
+                line: symbol.line as u32,
+                offset: symbol.offset as u32,
+                start: symbol.start as u32,
+                end: symbol.end as u32,
+            })
+            .collect();
+
+        self.send_plugin_response(PluginResponse::DocumentSymbols {
+            request_id,
+            symbols,
+        });
+    }
+
     // ==================== Text Editing Commands ====================
 
     /// Handle InsertText command
@@ -769,6 +803,60 @@ impl Editor {
         }
     }
 
+    /// Handle ReplaceRange command
+    ///
+    /// The delete and insert are logged as one batch, so a single undo
+    /// restores the replaced text.
+    pub(super) fn handle_replace_range(
+        &mut self,
+        buffer_id: BufferId,
+        range: std::ops::Range<usize>,
+        text: String,
+    ) {
+        let start = range.start;
+        let delete_len = range.end.saturating_sub(range.start);
+        let insert_len = text.len();
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            let mut events = Vec::new();
+            if delete_len > 0 {
+                let deleted_text = state.get_text_range(range.start, range.end);
+                events.push(Event::Delete {
+                    range,
+                    deleted_text,
+                    cursor_id: CursorId(0),
+                });
+            }
+            if insert_len > 0 {
+                events.push(Event::Insert {
+                    position: start,
+                    text,
+                    cursor_id: CursorId(0),
+                });
+            }
+            let event = match events.len() {
+                0 => return,
+                1 => events.remove(0),
+                _ => Event::Batch {
+                    events,
+                    description: "Replace".to_string(),
+                },
+            };
+            // Apply to buffer with dummy cursors (real cursors adjusted below)
+            state.apply(&mut Cursors::default(), &event);
+            if let Some(log) = self.event_logs.get_mut(&buffer_id) {
+                log.append(event);
+            }
+        }
+        // Adjust cursors in all splits that display this buffer
+        for leaf_id in self.split_manager.splits_for_buffer(buffer_id) {
+            if let Some(view_state) = self.split_view_states.get_mut(&leaf_id) {
+                view_state
+                    .cursors
+                    .adjust_for_edit(start, delete_len, insert_len);
+            }
+        }
+    }
+
     /// Handle InsertAtCursor command
     pub(super) fn handle_insert_at_cursor(&mut self, text: String) {
         // Read cursor position first to avoid borrow conflicts
@@ -1608,3 +1696,25 @@ impl Editor {
         }
     }
 }
+
+/// Convert highlighter output into the span type sent to plugins
+fn to_ts_highlight_spans(
+    spans: Vec<crate::primitives::highlighter::HighlightSpan>,
+) -> Vec<fresh_core::api::TsHighlightSpan> {
+    spans
+        .into_iter()
+        .map(|s| {
+            let color = match s.color {
+                ratatui::style::Color::Rgb(r, g, b) => (r, g, b),
+                _ => (128, 128, 128), // fallback for indexed colors
+            };
+            fresh_core::api::TsHighlightSpan {
+                start: s.range.start as u32,
+                end: s.range.end as u32,
+                color,
+                bold: false,
+                italic: false,
+            }
+        })
+        .collect()
+}
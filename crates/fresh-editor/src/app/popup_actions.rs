@@ -144,8 +144,8 @@ impl Editor {
                     new_position: new_cursor_pos,
                     old_anchor: None,
                     new_anchor: None,
-                    old_sticky_column: 0,
-                    new_sticky_column: 0,
+                    old_sticky_column: None,
+                    new_sticky_column: None,
                 };
                 let split_id = self.split_manager.active_split();
                 let buffer_id = self.active_buffer();
@@ -0,0 +1,204 @@
+//! Search in Project
+//!
+//! The query typed in the prompt is searched across the project's files on
+//! a blocking worker. Files come from the Quick Open file list, so ignored
+//! files are skipped and remote projects are listed by the agent. Matches
+//! stream back in batches and fill the prompt's suggestions; confirming one
+//! opens its file at the match.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::project_search::{self, ProjectSearchMatch};
+use crate::view::prompt::PromptType;
+use rust_i18n::t;
+
+/// A running or finished project search
+pub(super) struct ProjectSearch {
+    /// Identifies which search a batch of results belongs to
+    id: u64,
+    /// Tells the worker to stop once the query changes or the prompt closes
+    cancelled: Arc<AtomicBool>,
+    /// Matches received so far, in the order of the prompt's suggestions
+    matches: Vec<ProjectSearchMatch>,
+}
+
+impl ProjectSearch {
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Editor {
+    /// Open the Search in Project prompt
+    pub fn start_search_in_project(&mut self) {
+        self.start_prompt_with_suggestions(
+            t!("project_search.prompt").to_string(),
+            PromptType::SearchInProject,
+            vec![],
+        );
+    }
+
+    /// Start searching the project for `query`, replacing any running search
+    ///
+    /// The query is a literal or a regex depending on the search options,
+    /// which also control case sensitivity and whole-word matching.
+    pub(super) fn run_project_search(&mut self, query: &str) {
+        self.cancel_project_search();
+        self.set_project_search_suggestions(vec![]);
+        if query.is_empty() {
+            return;
+        }
+
+        let regex = match self.build_search_regex(query) {
+            Ok(regex) => regex,
+            Err(e) => {
+                self.set_project_search_suggestions(vec![Suggestion {
+                    text: t!("error.invalid_regex", error = e.to_string()).to_string(),
+                    description: None,
+                    value: None,
+                    disabled: true,
+                    keybinding: None,
+                    source: None,
+                }]);
+                return;
+            }
+        };
+        let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) else {
+            return;
+        };
+
+        self.next_project_search_id += 1;
+        let id = self.next_project_search_id;
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.project_search = Some(ProjectSearch {
+            id,
+            cancelled: Arc::clone(&cancelled),
+            matches: Vec::new(),
+        });
+
+        let sender = bridge.sender();
+        let file_provider = Arc::clone(&self.file_provider);
+        let filesystem = Arc::clone(&self.filesystem);
+        let root = self.working_dir.clone();
+        runtime.spawn_blocking(move || {
+            let Some(files) = file_provider.project_files(&root.display().to_string(), &cancelled)
+            else {
+                return;
+            };
+            project_search::search_files(
+                filesystem.as_ref(),
+                &root,
+                &files,
+                &regex,
+                project_search::MAX_RESULTS,
+                &cancelled,
+                |matches| {
+                    // The receiver is gone if the editor is shutting down
+                    #[allow(clippy::let_underscore_must_use)]
+                    let _ = sender.send(AsyncMessage::ProjectSearchResults {
+                        search_id: id,
+                        matches,
+                        done: false,
+                    });
+                },
+            );
+            #[allow(clippy::let_underscore_must_use)]
+            let _ = sender.send(AsyncMessage::ProjectSearchResults {
+                search_id: id,
+                matches: Vec::new(),
+                done: true,
+            });
+        });
+    }
+
+    /// Stop the running project search, if any
+    pub(super) fn cancel_project_search(&mut self) {
+        if let Some(search) = self.project_search.take() {
+            search.cancel();
+        }
+    }
+
+    /// Add a batch of matches from the worker to the results list
+    pub(super) fn handle_project_search_results(
+        &mut self,
+        search_id: u64,
+        matches: Vec<ProjectSearchMatch>,
+        done: bool,
+    ) {
+        let Some(search) = self
+            .project_search
+            .as_mut()
+            .filter(|search| search.id == search_id)
+        else {
+            return;
+        };
+        search.matches.extend(matches);
+        let suggestions: Vec<Suggestion> = search.matches.iter().map(match_suggestion).collect();
+        let count = search.matches.len();
+
+        self.set_project_search_suggestions(suggestions);
+        if done {
+            let message = if count >= project_search::MAX_RESULTS {
+                t!("project_search.results_capped", count = count)
+            } else {
+                t!("project_search.results", count = count)
+            };
+            self.set_status_message(message.to_string());
+        }
+    }
+
+    /// Open the file of the selected match and put the cursor on it
+    pub(super) fn open_project_search_match(&mut self, selected_index: Option<usize>) {
+        let selected = self.project_search.take().and_then(|search| {
+            search.cancel();
+            selected_index.and_then(|index| search.matches.into_iter().nth(index))
+        });
+        let Some(selected) = selected else {
+            self.set_status_message(t!("status.no_selection").to_string());
+            return;
+        };
+
+        let path: PathBuf = self.working_dir.join(&selected.path);
+        match self.open_file(&path) {
+            Ok(_) => self.goto_line_col(selected.line, Some(selected.column)),
+            Err(e) => {
+                self.set_status_message(t!("file.error_opening", error = e.to_string()).to_string())
+            }
+        }
+    }
+
+    /// Replace the suggestions of an open Search in Project prompt
+    fn set_project_search_suggestions(&mut self, suggestions: Vec<Suggestion>) {
+        let Some(prompt) = self
+            .prompt
+            .as_mut()
+            .filter(|prompt| prompt.prompt_type == PromptType::SearchInProject)
+        else {
+            return;
+        };
+        let selected = prompt.selected_suggestion.unwrap_or(0);
+        prompt.suggestions = suggestions;
+        prompt.selected_suggestion = if prompt.suggestions.is_empty() {
+            None
+        } else {
+            Some(selected.min(prompt.suggestions.len() - 1))
+        };
+    }
+}
+
+/// The results list entry for a match: `path:line` with the line's text
+fn match_suggestion(found: &ProjectSearchMatch) -> Suggestion {
+    Suggestion {
+        text: format!("{}:{}", found.path, found.line),
+        description: Some(found.text.clone()),
+        value: None,
+        disabled: false,
+        keybinding: None,
+        source: None,
+    }
+}
@@ -9,8 +9,10 @@ use super::BufferId;
 use super::BufferMetadata;
 use super::Editor;
 use crate::config_io::{ConfigLayer, ConfigResolver};
+use crate::input::goto_line::GotoLineTarget;
 use crate::input::keybindings::Action;
 use crate::primitives::path_utils::expand_tilde;
+use crate::services::clipboard::is_register_name;
 use crate::services::plugins::hooks::HookArgs;
 use crate::view::prompt::PromptType;
 
@@ -84,27 +86,74 @@ impl Editor {
             PromptType::SaveFileAs => {
                 self.handle_save_file_as(&input);
             }
-            PromptType::Search => {
+            PromptType::SaveWorkspaceAs => {
+                self.handle_save_workspace_as(&input);
+            }
+            PromptType::SwitchWorkspace => {
+                let name = input.trim();
+                let modified_count = self.count_modified_buffers();
+                if modified_count > 0 {
+                    let discard_key = t!("prompt.key.discard").to_string();
+                    let cancel_key = t!("prompt.key.cancel").to_string();
+                    self.start_prompt(
+                        t!(
+                            "workspace.switch_modified",
+                            count = modified_count,
+                            discard_key = discard_key,
+                            cancel_key = cancel_key
+                        )
+                        .to_string(),
+                        PromptType::ConfirmSwitchWorkspace {
+                            name: name.to_string(),
+                        },
+                    );
+                } else {
+                    self.switch_workspace(name);
+                }
+            }
+            PromptType::ConfirmSwitchWorkspace { name } => {
+                let input_lower = input.trim().to_lowercase();
+                let discard_key = t!("prompt.key.discard").to_string().to_lowercase();
+                if input_lower == discard_key || input_lower == "discard" {
+                    self.switch_workspace(&name);
+                } else {
+                    self.set_status_message(t!("workspace.switch_cancelled").to_string());
+                }
+            }
+            PromptType::Search | PromptType::IncrementalSearch => {
                 self.perform_search(&input);
             }
             PromptType::ReplaceSearch => {
+                // perform_search consumes the selection scope, so keep it for the replace
+                let range = self.pending_search_range.clone();
                 self.perform_search(&input);
+                if self.build_search_regex(&input).is_err() {
+                    // The invalid regex error stays in the status bar
+                    return PromptResult::Done;
+                }
+                self.show_replace_preview();
                 self.start_prompt(
                     t!("replace.prompt", search = &input).to_string(),
                     PromptType::Replace {
                         search: input.clone(),
+                        range,
                     },
                 );
             }
-            PromptType::Replace { search } => {
-                if self.search_confirm_each {
+            PromptType::Replace { search, range } => {
+                // Replacing in a selection always replaces every match inside it
+                if self.search_confirm_each && range.is_none() {
                     self.start_interactive_replace(&search, &input);
                 } else {
-                    self.perform_replace(&search, &input);
+                    self.perform_replace(&search, &input, range);
                 }
             }
             PromptType::QueryReplaceSearch => {
                 self.perform_search(&input);
+                if self.build_search_regex(&input).is_err() {
+                    return PromptResult::Done;
+                }
+                self.show_replace_preview();
                 self.start_prompt(
                     t!("replace.query_prompt", search = &input).to_string(),
                     PromptType::QueryReplace {
@@ -116,18 +165,17 @@ impl Editor {
                 if self.search_confirm_each {
                     self.start_interactive_replace(&search, &input);
                 } else {
-                    self.perform_replace(&search, &input);
+                    self.perform_replace(&search, &input, None);
                 }
             }
             PromptType::Command => {
                 let commands = self.command_registry.read().unwrap().get_all();
                 if let Some(cmd) = commands.iter().find(|c| c.get_localized_name() == input) {
                     let action = cmd.action.clone();
-                    let cmd_name = cmd.get_localized_name();
                     self.command_registry
                         .write()
                         .unwrap()
-                        .record_usage(&cmd_name);
+                        .record_usage(&cmd.name);
                     return PromptResult::ExecuteAction(action);
                 } else {
                     self.set_status_message(
@@ -135,22 +183,16 @@ impl Editor {
                     );
                 }
             }
-            PromptType::GotoLine => match input.trim().parse::<usize>() {
-                Ok(line_num) if line_num > 0 => {
-                    self.goto_line_col(line_num, None);
-                    self.set_status_message(t!("goto.jumped", line = line_num).to_string());
-                }
-                Ok(_) => {
-                    self.set_status_message(t!("goto.line_must_be_positive").to_string());
-                }
-                Err(_) => {
-                    self.set_status_message(t!("error.invalid_line", input = &input).to_string());
-                }
-            },
+            PromptType::GotoLine => {
+                self.handle_goto_line_input(&input);
+            }
             PromptType::QuickOpen => {
                 // Handle Quick Open confirmation based on prefix
                 return self.handle_quick_open_confirm(&input, selected_index);
             }
+            PromptType::SearchInProject => {
+                self.open_project_search_match(selected_index);
+            }
             PromptType::SetBackgroundFile => {
                 if let Err(e) = self.load_ansi_background(&input) {
                     self.set_status_message(
@@ -187,6 +229,18 @@ impl Editor {
             PromptType::PlayMacro => {
                 self.handle_register_input(&input, |editor, c| editor.play_macro(c), "Macro");
             }
+            PromptType::RepeatLastMacro => match input.trim().parse::<usize>() {
+                Ok(times) if times > 0 => {
+                    if let Some(key) = self.last_macro_register {
+                        self.play_macro_times(key, times);
+                    }
+                }
+                _ => {
+                    self.set_status_message(
+                        t!("macro.invalid_repeat_count", input = &input).to_string(),
+                    );
+                }
+            },
             PromptType::SetBookmark => {
                 self.handle_register_input(&input, |editor, c| editor.set_bookmark(c), "Bookmark");
             }
@@ -197,6 +251,16 @@ impl Editor {
                     "Bookmark",
                 );
             }
+            PromptType::CopyToRegister => {
+                self.handle_clipboard_register_input(&input, |editor, c| {
+                    editor.copy_to_register(c)
+                });
+            }
+            PromptType::PasteFromRegister => {
+                self.handle_clipboard_register_input(&input, |editor, c| {
+                    editor.paste_from_register(c)
+                });
+            }
             PromptType::Plugin { custom_type } => {
                 tracing::info!(
                     "prompt_confirmed: dispatching hook for prompt_type='{}', input='{}', selected_index={:?}",
@@ -240,58 +304,15 @@ impl Editor {
                     self.set_status_message(t!("buffer.save_cancelled").to_string());
                 }
             }
+            PromptType::ConfirmFileConflict { buffer_id } => {
+                self.handle_file_conflict_choice(&input, buffer_id);
+            }
             PromptType::ConfirmSudoSave { info } => {
                 let input_lower = input.trim().to_lowercase();
                 if input_lower == "y" || input_lower == "yes" {
                     // Hide prompt before starting blocking command to clear the line
                     self.cancel_prompt();
-
-                    // Read temp file and write via sudo (works for both local and remote)
-                    let result = (|| -> anyhow::Result<()> {
-                        let data = self.filesystem.read_file(&info.temp_path)?;
-                        self.filesystem.sudo_write(
-                            &info.dest_path,
-                            &data,
-                            info.mode,
-                            info.uid,
-                            info.gid,
-                        )?;
-                        // Best-effort cleanup of temp file.
-                        #[allow(clippy::let_underscore_must_use)]
-                        let _ = self.filesystem.remove_file(&info.temp_path);
-                        Ok(())
-                    })();
-
-                    match result {
-                        Ok(_) => {
-                            if let Err(e) = self
-                                .active_state_mut()
-                                .buffer
-                                .finalize_external_save(info.dest_path.clone())
-                            {
-                                tracing::warn!("Failed to finalize sudo save: {}", e);
-                                self.set_status_message(
-                                    t!("prompt.sudo_save_failed", error = e.to_string())
-                                        .to_string(),
-                                );
-                            } else if let Err(e) = self.finalize_save(Some(info.dest_path)) {
-                                tracing::warn!("Failed to finalize save after sudo: {}", e);
-                                self.set_status_message(
-                                    t!("prompt.sudo_save_failed", error = e.to_string())
-                                        .to_string(),
-                                );
-                            }
-                        }
-                        Err(e) => {
-                            tracing::warn!("Sudo save failed: {}", e);
-                            self.set_status_message(
-                                t!("prompt.sudo_save_failed", error = e.to_string()).to_string(),
-                            );
-                            // Best-effort cleanup of temp file.
-                            #[allow(clippy::let_underscore_must_use)]
-                            let _ = self.filesystem.remove_file(&info.temp_path);
-                        }
-                    }
+                    self.save_with_sudo(info);
                 } else {
                     self.set_status_message(t!("buffer.save_cancelled").to_string());
                     // Best-effort cleanup of temp file.
@@ -312,6 +333,9 @@ impl Editor {
                     return PromptResult::EarlyReturn;
                 }
             }
+            PromptType::ConfirmRestoreRecovery { recovery_id } => {
+                self.handle_restore_recovery_choice(&input, &recovery_id);
+            }
             PromptType::ConfirmQuitWithModified => {
                 let input_lower = input.trim().to_lowercase();
                 let discard_key = t!("prompt.key.discard").to_string().to_lowercase();
@@ -420,15 +444,38 @@ impl Editor {
             PromptType::SetLineEnding => {
                 self.handle_set_line_ending(&input);
             }
+            PromptType::NormalizeLineEndings => {
+                self.handle_normalize_line_endings(&input);
+            }
             PromptType::SetEncoding => {
                 self.handle_set_encoding(&input);
             }
+            PromptType::SaveWithEncoding => {
+                if self.handle_set_encoding(&input) {
+                    if let Err(e) = self.handle_action(Action::Save) {
+                        self.set_status_message(
+                            t!("file.error_saving", error = e.to_string()).to_string(),
+                        );
+                    }
+                }
+            }
             PromptType::SetLanguage => {
                 self.handle_set_language(&input);
             }
             PromptType::ShellCommand { replace } => {
                 self.handle_shell_command(&input, replace);
             }
+            PromptType::Align => {
+                self.align_selection(&input);
+            }
+            PromptType::CompareWithFile => {
+                self.compare_with_file(&input);
+            }
+            PromptType::PasteFromHistory => {
+                if let Ok(index) = input.trim().parse::<usize>() {
+                    self.paste_from_history(index);
+                }
+            }
             PromptType::AsyncPrompt => {
                 // Resolve the pending async prompt callback with the input text
                 if let Some(callback_id) = self.pending_async_prompt_callback.take() {
@@ -478,6 +525,9 @@ impl Editor {
 
     /// Perform the actual SaveFileAs operation (called after confirmation if needed).
     pub(crate) fn perform_save_file_as(&mut self, full_path: std::path::PathBuf) {
+        self.run_whitespace_cleanup_before_save();
+        self.request_backup_if_enabled(self.active_buffer());
+
         let before_idx = self.active_event_log().current_index();
         let before_len = self.active_event_log().len();
         tracing::debug!(
@@ -496,8 +546,13 @@ impl Editor {
                     after_save_len
                 );
 
+                let was_write_protected = self.is_write_protected(self.active_buffer());
                 let metadata = BufferMetadata::with_file(full_path.clone(), &self.working_dir);
                 self.buffer_metadata.insert(self.active_buffer(), metadata);
+                // The copy just written is writable, unlike the file it came from
+                if was_write_protected {
+                    self.active_state_mut().editing_disabled = false;
+                }
 
                 // Auto-detect language if it's currently "text"
                 // This ensures syntax highlighting works immediately after "Save As"
@@ -523,6 +578,7 @@ impl Editor {
                 }
 
                 self.notify_lsp_save();
+                self.run_diagnostics_providers(self.active_buffer());
 
                 self.emit_event(
                     crate::model::control_event::events::FILE_SAVED.name,
@@ -671,20 +727,7 @@ impl Editor {
 
     /// Handle SetLineEnding prompt confirmation.
     fn handle_set_line_ending(&mut self, input: &str) {
-        use crate::model::buffer::LineEnding;
-
-        // Extract the line ending code from the input (e.g., "LF" from "LF (Unix/Linux/Mac)")
-        let trimmed = input.trim();
-        let code = trimmed.split_whitespace().next().unwrap_or(trimmed);
-
-        let line_ending = match code.to_uppercase().as_str() {
-            "LF" => Some(LineEnding::LF),
-            "CRLF" => Some(LineEnding::CRLF),
-            "CR" => Some(LineEnding::CR),
-            _ => None,
-        };
-
-        match line_ending {
+        match parse_line_ending(input) {
             Some(le) => {
                 self.active_state_mut().buffer.set_line_ending(le);
                 self.set_status_message(
@@ -697,8 +740,31 @@ impl Editor {
         }
     }
 
+    /// Handle NormalizeLineEndings prompt confirmation.
+    fn handle_normalize_line_endings(&mut self, input: &str) {
+        match parse_line_ending(input) {
+            Some(le) => {
+                self.active_state_mut()
+                    .buffer
+                    .request_line_ending_normalization(le);
+                self.set_status_message(
+                    t!(
+                        "settings.line_endings_normalized",
+                        value = le.display_name()
+                    )
+                    .to_string(),
+                );
+            }
+            None => {
+                self.set_status_message(t!("error.unknown_line_ending", input = input).to_string());
+            }
+        }
+    }
+
     /// Handle SetEncoding prompt confirmation.
-    fn handle_set_encoding(&mut self, input: &str) {
+    ///
+    /// Returns true if the input named a known encoding.
+    fn handle_set_encoding(&mut self, input: &str) -> bool {
         use crate::model::buffer::Encoding;
 
         let trimmed = input.trim();
@@ -722,9 +788,11 @@ impl Editor {
             Some(enc) => {
                 self.active_state_mut().buffer.set_encoding(enc);
                 self.set_status_message(format!("Encoding set to {}", enc.display_name()));
+                true
             }
             None => {
                 self.set_status_message(format!("Unknown encoding: {}", input));
+                false
             }
         }
     }
@@ -835,6 +903,8 @@ impl Editor {
                 // Reload the file with the specified encoding
                 if let Err(e) = self.reload_with_encoding(enc) {
                     self.set_status_message(format!("Failed to reload: {}", e));
+                } else if let Some(message) = self.decode_error_message(self.active_buffer()) {
+                    self.set_status_message(message);
                 } else {
                     self.set_status_message(format!(
                         "Reloaded with {} encoding",
@@ -908,6 +978,22 @@ impl Editor {
         }
     }
 
+    /// Handle input for a clipboard register prompt (a-z, or " for the clipboard).
+    fn handle_clipboard_register_input<F>(&mut self, input: &str, action: F)
+    where
+        F: FnOnce(&mut Self, char),
+    {
+        if let Some(c) = input.trim().chars().next() {
+            if is_register_name(c) {
+                action(self, c);
+            } else {
+                self.set_status_message(t!("register.must_be_letter").to_string());
+            }
+        } else {
+            self.set_status_message(t!("register.not_specified").to_string());
+        }
+    }
+
     /// Handle ConfirmCloseBuffer prompt. Returns true if early return is needed.
     fn handle_confirm_close_buffer(&mut self, input: &str, buffer_id: BufferId) -> bool {
         let input_lower = input.trim().to_lowercase();
@@ -1019,19 +1105,9 @@ impl Editor {
             return self.handle_quick_open_buffer(query, selected_index);
         }
 
-        if input.starts_with(':') {
+        if let Some(line_str) = input.strip_prefix(':') {
             // Go to line mode
-            let line_str = &input[1..];
-            if let Ok(line_num) = line_str.parse::<usize>() {
-                if line_num > 0 {
-                    self.goto_line_col(line_num, None);
-                    self.set_status_message(t!("goto.jumped", line = line_num).to_string());
-                } else {
-                    self.set_status_message(t!("goto.line_must_be_positive").to_string());
-                }
-            } else {
-                self.set_status_message(t!("error.invalid_line", input = line_str).to_string());
-            }
+            self.handle_goto_line_input(line_str);
             return PromptResult::Done;
         }
 
@@ -1039,6 +1115,22 @@ impl Editor {
         self.handle_quick_open_file(input, selected_index)
     }
 
+    /// Jump to a Go to Line input: `42`, `+20`, `-5` or `50%`
+    fn handle_goto_line_input(&mut self, input: &str) {
+        match GotoLineTarget::parse(input) {
+            Some(GotoLineTarget::Absolute(0)) => {
+                self.set_status_message(t!("goto.line_must_be_positive").to_string());
+            }
+            Some(target) => {
+                let line = self.goto_line_target(target);
+                self.set_status_message(t!("goto.jumped", line = line).to_string());
+            }
+            None => {
+                self.set_status_message(t!("error.invalid_line", input = input).to_string());
+            }
+        }
+    }
+
     /// Handle Quick Open command selection
     fn handle_quick_open_command(
         &mut self,
@@ -1077,11 +1169,10 @@ impl Editor {
                     .find(|c| c.get_localized_name() == suggestion.text)
                 {
                     let action = cmd.action.clone();
-                    let cmd_name = cmd.get_localized_name();
                     self.command_registry
                         .write()
                         .unwrap()
-                        .record_usage(&cmd_name);
+                        .record_usage(&cmd.name);
                     return PromptResult::ExecuteAction(action);
                 }
             }
@@ -1130,8 +1221,19 @@ impl Editor {
         input: &str,
         selected_index: Option<usize>,
     ) -> PromptResult {
+        // Until the suggestions for the typed query arrive, the selection
+        // belongs to an older query; open the best match instead
+        let selected_index = if self.file_suggestions_pending() {
+            self.cancel_file_suggestions();
+            Some(0)
+        } else {
+            selected_index
+        };
+
         // Regenerate file suggestions since prompt was already taken by confirm_prompt
         let suggestions = self.get_file_suggestions(input);
+        let cwd = self.working_dir.display().to_string();
+        let (_, line, column) = self.file_provider.split_query(input, &cwd);
 
         if let Some(idx) = selected_index {
             if let Some(suggestion) = suggestions.get(idx) {
@@ -1148,6 +1250,9 @@ impl Editor {
 
                     match self.open_file(&full_path) {
                         Ok(_) => {
+                            if let Some(line) = line {
+                                self.goto_line_col(line, column);
+                            }
                             self.set_status_message(
                                 t!("buffer.opened", name = full_path.display().to_string())
                                     .to_string(),
@@ -1175,3 +1280,17 @@ impl Editor {
         PromptResult::Done
     }
 }
+
+/// Parse a line ending from prompt input such as "LF (Unix/Linux/Mac)"
+fn parse_line_ending(input: &str) -> Option<crate::model::buffer::LineEnding> {
+    use crate::model::buffer::LineEnding;
+
+    let trimmed = input.trim();
+    let code = trimmed.split_whitespace().next().unwrap_or(trimmed);
+    match code.to_uppercase().as_str() {
+        "LF" => Some(LineEnding::LF),
+        "CRLF" => Some(LineEnding::CRLF),
+        "CR" => Some(LineEnding::CR),
+        _ => None,
+    }
+}
@@ -0,0 +1,143 @@
+//! File results for Quick Open
+//!
+//! Listing a project can be slow, especially over a remote connection, so
+//! file mode lists and filters on a blocking worker. A short debounce,
+//! polled from the event loop, means only a pause in typing starts the work,
+//! and each keystroke cancels the request for the previous query. Results carry their request's id, so a
+//! late answer to an older query is dropped rather than replacing newer
+//! results.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::services::async_bridge::AsyncMessage;
+use crate::view::prompt::PromptType;
+
+/// How long typing must pause before a query is listed and filtered
+const FILE_QUERY_DEBOUNCE: Duration = Duration::from_millis(30);
+
+/// A file query whose suggestions haven't arrived yet
+pub(super) struct FileSuggestionsRequest {
+    /// Identifies which request a set of suggestions answers
+    id: u64,
+    /// Tells the worker to stop once a newer query supersedes it
+    cancelled: Arc<AtomicBool>,
+    /// The query and when its worker may start, until the debounce passes
+    scheduled: Option<(String, Instant)>,
+}
+
+impl Editor {
+    /// Compute file suggestions for `query` in the background, superseding
+    /// any request still in flight
+    pub(super) fn request_file_suggestions(&mut self, query: &str) {
+        self.cancel_file_suggestions();
+
+        if self.tokio_runtime.is_none() || self.async_bridge.is_none() {
+            let suggestions = self.get_file_suggestions(query);
+            self.set_file_suggestions(suggestions);
+            return;
+        }
+
+        self.next_file_suggestions_id += 1;
+        self.file_suggestions_request = Some(FileSuggestionsRequest {
+            id: self.next_file_suggestions_id,
+            cancelled: Arc::new(AtomicBool::new(false)),
+            scheduled: Some((
+                query.to_string(),
+                self.time_source.now() + FILE_QUERY_DEBOUNCE,
+            )),
+        });
+    }
+
+    /// Start the worker for the latest file query once typing has paused
+    ///
+    /// Returns true if a worker was started.
+    pub fn check_file_suggestions_timer(&mut self) -> bool {
+        let now = self.time_source.now();
+        let Some(request) = self
+            .file_suggestions_request
+            .as_mut()
+            .filter(|request| request.scheduled.as_ref().is_some_and(|(_, at)| now >= *at))
+        else {
+            return false;
+        };
+        let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) else {
+            return false;
+        };
+        let Some((query, _)) = request.scheduled.take() else {
+            return false;
+        };
+
+        let id = request.id;
+        let cancelled = Arc::clone(&request.cancelled);
+        let cwd = self.working_dir.display().to_string();
+        let sender = bridge.sender();
+        let file_provider = Arc::clone(&self.file_provider);
+        runtime.spawn(async move {
+            let suggestions = tokio::task::spawn_blocking(move || {
+                file_provider.file_suggestions(&query, &cwd, &cancelled)
+            })
+            .await;
+            if let Ok(Some(suggestions)) = suggestions {
+                // The receiver is gone if the editor is shutting down
+                #[allow(clippy::let_underscore_must_use)]
+                let _ = sender.send(AsyncMessage::QuickOpenFileSuggestions {
+                    request_id: id,
+                    suggestions,
+                });
+            }
+        });
+        true
+    }
+
+    /// Stop computing file suggestions, if a request is in flight
+    pub(super) fn cancel_file_suggestions(&mut self) {
+        if let Some(request) = self.file_suggestions_request.take() {
+            request.cancelled.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Whether the file suggestions shown are for an older query than the
+    /// one typed
+    pub(super) fn file_suggestions_pending(&self) -> bool {
+        self.file_suggestions_request.is_some()
+    }
+
+    /// Show the suggestions of a file request, unless a newer query has
+    /// superseded it
+    pub(super) fn handle_file_suggestions(
+        &mut self,
+        request_id: u64,
+        suggestions: Vec<Suggestion>,
+    ) {
+        if self
+            .file_suggestions_request
+            .as_ref()
+            .is_none_or(|request| request.id != request_id)
+        {
+            return;
+        }
+        self.file_suggestions_request = None;
+        self.set_file_suggestions(suggestions);
+    }
+
+    /// Replace the suggestions of an open Quick Open prompt
+    fn set_file_suggestions(&mut self, suggestions: Vec<Suggestion>) {
+        let Some(prompt) = self
+            .prompt
+            .as_mut()
+            .filter(|prompt| prompt.prompt_type == PromptType::QuickOpen)
+        else {
+            return;
+        };
+        prompt.suggestions = suggestions;
+        prompt.selected_suggestion = if prompt.suggestions.is_empty() {
+            None
+        } else {
+            Some(0)
+        };
+    }
+}
@@ -4,12 +4,16 @@
 //! - Starting/ending recovery sessions
 //! - Checking for and listing recoverable files
 //! - Recovering buffers from crash
+//! - Offering recovery for files restored from a workspace after a crash
 //! - Auto-saving modified buffers
 //! - Cleaning up recovery files
 
 use anyhow::Result as AnyhowResult;
+use rust_i18n::t;
 
 use crate::model::event::BufferId;
+use crate::services::recovery::{RecoveryChunk, RecoveryEntry, RecoveryFormat, RecoveryResult};
+use crate::view::prompt::PromptType;
 
 use super::Editor;
 
@@ -38,32 +42,31 @@ impl Editor {
 
     /// Recover all buffers from recovery files
     /// Returns the number of buffers recovered
+    ///
+    /// Entries waiting on a restore recovery prompt are left for the user.
     pub fn recover_all_buffers(&mut self) -> AnyhowResult<usize> {
-        use crate::services::recovery::RecoveryResult;
-
         let entries = self.recovery_service.list_recoverable()?;
         let mut recovered_count = 0;
 
         for entry in entries {
+            if self
+                .pending_restore_recoveries
+                .iter()
+                .any(|(_, pending)| pending.id == entry.id)
+            {
+                continue;
+            }
             match self.recovery_service.accept_recovery(&entry) {
                 Ok(RecoveryResult::Recovered {
                     original_path,
                     content,
                 }) => {
                     // Full content recovery (new/small buffers)
-                    let text = String::from_utf8_lossy(&content).into_owned();
-
                     if let Some(path) = original_path {
                         // Open the file path (this creates the buffer)
                         match self.open_file(&path) {
-                            Ok(_) => {
-                                // Replace buffer content with recovered content
-                                let state = self.active_state_mut();
-                                let total = state.buffer.total_bytes();
-                                state.buffer.delete(0..total);
-                                state.buffer.insert(0, &text);
-                                // Mark as modified since it differs from disk
-                                state.buffer.set_modified(true);
+                            Ok(buffer_id) => {
+                                self.apply_recovered_content(buffer_id, &content);
                                 recovered_count += 1;
                                 tracing::info!("Recovered buffer: {}", path.display());
                             }
@@ -80,10 +83,8 @@ impl Editor {
                         }
                     } else {
                         // Unsaved buffer - create new buffer with recovered content
-                        self.new_buffer();
-                        let state = self.active_state_mut();
-                        state.buffer.insert(0, &text);
-                        state.buffer.set_modified(true);
+                        let buffer_id = self.new_buffer();
+                        self.apply_recovered_content(buffer_id, &content);
                         recovered_count += 1;
                         tracing::info!("Recovered unsaved buffer");
                     }
@@ -93,23 +94,8 @@ impl Editor {
                     chunks,
                 }) => {
                     // Chunked recovery for large files - apply chunks directly
-                    if self.open_file(&original_path).is_ok() {
-                        let state = self.active_state_mut();
-
-                        // Apply chunks in reverse order to preserve offsets
-                        // Each chunk: delete original_len bytes at offset, then insert content
-                        for chunk in chunks.into_iter().rev() {
-                            let text = String::from_utf8_lossy(&chunk.content).into_owned();
-                            if chunk.original_len > 0 {
-                                state
-                                    .buffer
-                                    .delete(chunk.offset..chunk.offset + chunk.original_len);
-                            }
-                            state.buffer.insert(chunk.offset, &text);
-                        }
-
-                        // Mark as modified since it differs from disk
-                        state.buffer.set_modified(true);
+                    if let Ok(buffer_id) = self.open_file(&original_path) {
+                        self.apply_recovered_chunks(buffer_id, chunks);
                         recovered_count += 1;
                         tracing::info!("Recovered buffer with chunks: {}", original_path.display());
                     }
@@ -140,6 +126,225 @@ impl Editor {
         Ok(recovered_count)
     }
 
+    /// Replace a buffer's content with recovered content
+    fn apply_recovered_content(&mut self, buffer_id: BufferId, content: &[u8]) {
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let text = String::from_utf8_lossy(content).into_owned();
+        let total = state.buffer.total_bytes();
+        state.buffer.delete(0..total);
+        state.buffer.insert(0, &text);
+        // Mark as modified since it differs from disk
+        state.buffer.set_modified(true);
+    }
+
+    /// Apply recovered chunks on top of a buffer loaded from the original file
+    fn apply_recovered_chunks(&mut self, buffer_id: BufferId, chunks: Vec<RecoveryChunk>) {
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        // Apply chunks in reverse order to preserve offsets
+        // Each chunk: delete original_len bytes at offset, then insert content
+        for chunk in chunks.into_iter().rev() {
+            let text = String::from_utf8_lossy(&chunk.content).into_owned();
+            if chunk.original_len > 0 {
+                state
+                    .buffer
+                    .delete(chunk.offset..chunk.offset + chunk.original_len);
+            }
+            state.buffer.insert(chunk.offset, &text);
+        }
+        // Mark as modified since it differs from disk
+        state.buffer.set_modified(true);
+    }
+
+    /// Queue crash recovery offers for buffers restored from a workspace
+    ///
+    /// Only runs after a crash. A restored file is offered its recovery entry
+    /// when the entry was saved no earlier than the file was last modified;
+    /// the user then recovers or discards each one in turn.
+    pub(super) fn queue_restore_recoveries(&mut self, buffer_ids: Vec<BufferId>) {
+        if !self.recovery_service.is_enabled()
+            || !self
+                .recovery_service
+                .storage()
+                .detect_crash()
+                .unwrap_or(false)
+        {
+            return;
+        }
+
+        for buffer_id in buffer_ids {
+            let Some(path) = self
+                .buffers
+                .get(&buffer_id)
+                .and_then(|state| state.buffer.file_path())
+                .map(|p| p.to_path_buf())
+            else {
+                continue;
+            };
+            let id = self.recovery_service.get_buffer_id(Some(&path));
+            let entry = match self.recovery_service.storage().load_entry(&id) {
+                Ok(Some(entry)) => entry,
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to read recovery entry for {}: {}",
+                        path.display(),
+                        e
+                    );
+                    continue;
+                }
+            };
+            if file_modified_after_recovery(&entry, &path) {
+                tracing::debug!("Skipping stale recovery entry for {}", path.display());
+                continue;
+            }
+            if !self
+                .pending_restore_recoveries
+                .iter()
+                .any(|(_, pending)| pending.id == entry.id)
+            {
+                self.pending_restore_recoveries.push((buffer_id, entry));
+            }
+        }
+
+        self.offer_next_restore_recovery();
+    }
+
+    /// Prompt for the next queued restore recovery, unless a prompt is open
+    ///
+    /// The prompt summarizes the diff between the recovered content and the
+    /// file on disk. Entries that match the file are discarded silently.
+    pub(super) fn offer_next_restore_recovery(&mut self) {
+        if self.prompt.is_some() {
+            return;
+        }
+
+        while let Some((buffer_id, entry)) = self.pending_restore_recoveries.first().cloned() {
+            let hunks = match self
+                .recovery_service
+                .storage()
+                .diff_against_original(&entry.id)
+            {
+                Ok(hunks) => hunks,
+                Err(e) => {
+                    tracing::warn!("Failed to diff recovery entry {}: {}", entry.id, e);
+                    self.pending_restore_recoveries.remove(0);
+                    continue;
+                }
+            };
+            if hunks.is_empty() {
+                self.pending_restore_recoveries.remove(0);
+                if let Err(e) = self.recovery_service.discard_recovery(&entry) {
+                    tracing::warn!("Failed to discard unchanged recovery entry: {}", e);
+                }
+                continue;
+            }
+
+            let added: usize = hunks.iter().map(|h| h.new_count).sum();
+            let removed: usize = hunks.iter().map(|h| h.old_count).sum();
+            let name = self.recovery_buffer_name(buffer_id);
+            self.start_prompt(
+                t!(
+                    "prompt.recover_on_restore",
+                    name = name,
+                    changes = hunks.len(),
+                    added = added,
+                    removed = removed,
+                    recover_key = t!("prompt.key.recover").to_string(),
+                    discard_key = t!("prompt.key.discard").to_string()
+                )
+                .to_string(),
+                PromptType::ConfirmRestoreRecovery {
+                    recovery_id: entry.id.clone(),
+                },
+            );
+            return;
+        }
+    }
+
+    /// Apply the answer to a restore recovery prompt, then offer the next one
+    pub(super) fn handle_restore_recovery_choice(&mut self, input: &str, recovery_id: &str) {
+        let Some(index) = self
+            .pending_restore_recoveries
+            .iter()
+            .position(|(_, entry)| entry.id == recovery_id)
+        else {
+            return;
+        };
+        let (buffer_id, entry) = self.pending_restore_recoveries.remove(index);
+        let name = self.recovery_buffer_name(buffer_id);
+
+        let recover_key = t!("prompt.key.recover").to_string().to_lowercase();
+        if input.trim().to_lowercase() == recover_key {
+            let result = self.recovery_service.accept_recovery(&entry);
+            match result {
+                Ok(RecoveryResult::Recovered { content, .. }) => {
+                    self.apply_recovered_content(buffer_id, &content);
+                    self.set_status_message(t!("buffer.recovered", name = name).to_string());
+                }
+                Ok(RecoveryResult::RecoveredChunks { chunks, .. }) => {
+                    self.apply_recovered_chunks(buffer_id, chunks);
+                    self.set_status_message(t!("buffer.recovered", name = name).to_string());
+                }
+                Ok(RecoveryResult::OriginalFileModified { .. }) => {
+                    self.set_status_message(
+                        t!(
+                            "buffer.recovery_failed",
+                            name = name,
+                            error = "file changed on disk"
+                        )
+                        .to_string(),
+                    );
+                }
+                Ok(RecoveryResult::Corrupted { reason, .. }) => {
+                    self.set_status_message(
+                        t!("buffer.recovery_failed", name = name, error = reason).to_string(),
+                    );
+                }
+                Ok(RecoveryResult::NotFound { .. }) => {
+                    self.set_status_message(
+                        t!(
+                            "buffer.recovery_failed",
+                            name = name,
+                            error = "recovery entry not found"
+                        )
+                        .to_string(),
+                    );
+                }
+                Err(e) => {
+                    self.set_status_message(
+                        t!("buffer.recovery_failed", name = name, error = e.to_string())
+                            .to_string(),
+                    );
+                }
+            }
+        } else {
+            if let Err(e) = self.recovery_service.discard_recovery(&entry) {
+                tracing::warn!("Failed to discard recovery entry {}: {}", entry.id, e);
+            }
+            self.set_status_message(t!("buffer.recovery_discarded", name = name).to_string());
+        }
+
+        self.offer_next_restore_recovery();
+    }
+
+    /// Leave a restore recovery entry on disk without applying it
+    pub(super) fn skip_restore_recovery(&mut self, recovery_id: &str) {
+        self.pending_restore_recoveries
+            .retain(|(_, entry)| entry.id != recovery_id);
+    }
+
+    /// Display name of a buffer for recovery messages
+    fn recovery_buffer_name(&self, buffer_id: BufferId) -> String {
+        self.buffer_metadata
+            .get(&buffer_id)
+            .map(|meta| meta.display_name.clone())
+            .unwrap_or_default()
+    }
+
     /// Discard all recovery files (user decided not to recover)
     /// Returns the number of recovery files deleted
     pub fn discard_all_recovery(&mut self) -> AnyhowResult<usize> {
@@ -179,7 +384,7 @@ impl Editor {
                 }
                 // Skip hidden buffers - they are managed by other buffers (e.g., diff sources)
                 if let Some(meta) = self.buffer_metadata.get(buffer_id) {
-                    if meta.hidden_from_tabs || meta.is_virtual() {
+                    if meta.hidden_from_tabs || meta.is_virtual() || meta.recovery_exempt {
                         return None;
                     }
                 }
@@ -255,10 +460,32 @@ impl Editor {
             if let Some(state) = self.buffers.get_mut(&buffer_id) {
                 let line_count = state.buffer.line_count();
 
-                // For large files, use chunked recovery to avoid reading entire file
-                if state.buffer.is_large_file() {
+                // Only large-file buffers track their edits against the file on
+                // disk; for those, chunked recovery avoids reading the entire file
+                // unless the edits cover most of it anyway
+                let (chunks, format) = if state.buffer.is_large_file() {
                     let chunks = state.buffer.get_recovery_chunks();
+                    let original_size = state.buffer.original_file_size().unwrap_or(0);
+                    let mut edit_ranges: Vec<_> = chunks
+                        .iter()
+                        .map(|(offset, content)| *offset..*offset + content.len())
+                        .collect();
+                    // Deletions don't show up as chunks; count them from the size change
+                    let inserted: usize = edit_ranges.iter().map(|r| r.len()).sum();
+                    let deleted =
+                        (original_size + inserted).saturating_sub(state.buffer.total_bytes());
+                    if deleted > 0 {
+                        edit_ranges.push(0..deleted);
+                    }
+                    let format = self
+                        .recovery_service
+                        .choose_recovery_strategy(original_size, &edit_ranges);
+                    (chunks, format)
+                } else {
+                    (Vec::new(), RecoveryFormat::Full)
+                };
 
+                if format == RecoveryFormat::Chunked {
                     // If no modifications, skip saving (original file is recovery)
                     if chunks.is_empty() {
                         state.buffer.set_recovery_pending(false);
@@ -302,7 +529,7 @@ impl Editor {
                         final_size
                     );
                 } else {
-                    // For small files, save full content as a single chunk
+                    // Save full content as a single chunk
                     let total_bytes = state.buffer.total_bytes();
                     let content = match state.buffer.get_text_range_mut(0, total_bytes) {
                         Ok(bytes) => bytes,
@@ -336,6 +563,11 @@ impl Editor {
         Ok(saved_count)
     }
 
+    /// Wait until all auto-recovery-saves so far are written to disk
+    pub fn flush_recovery_writes(&self) -> AnyhowResult<()> {
+        Ok(self.recovery_service.flush()?)
+    }
+
     /// Check if the active buffer is marked dirty for auto-recovery-save
     /// Used for testing to verify that edits properly trigger recovery tracking
     pub fn is_active_buffer_recovery_dirty(&self) -> bool {
@@ -346,21 +578,42 @@ impl Editor {
         }
     }
 
+    /// Get the recovery ID of a buffer
+    ///
+    /// Uses the stored ID for unnamed buffers and computes it from the path otherwise.
+    fn buffer_recovery_id(&self, buffer_id: BufferId) -> Option<String> {
+        let meta = self.buffer_metadata.get(&buffer_id);
+        if let Some(stored_id) = meta.and_then(|m| m.recovery_id.clone()) {
+            return Some(stored_id);
+        }
+        let state = self.buffers.get(&buffer_id)?;
+        let path = state.buffer.file_path().map(|p| p.to_path_buf());
+        Some(self.recovery_service.get_buffer_id(path.as_deref()))
+    }
+
+    /// Exclude a buffer from auto-recovery-save, or include it again
+    ///
+    /// Exempting a buffer also deletes any recovery files it already has.
+    pub fn set_buffer_recovery_exempt(
+        &mut self,
+        buffer_id: BufferId,
+        exempt: bool,
+    ) -> AnyhowResult<()> {
+        let Some(recovery_id) = self.buffer_recovery_id(buffer_id) else {
+            return Ok(());
+        };
+        if let Some(meta) = self.buffer_metadata.get_mut(&buffer_id) {
+            meta.recovery_exempt = exempt;
+        }
+        self.recovery_service
+            .set_buffer_recovery_exempt(&recovery_id, exempt)?;
+        Ok(())
+    }
+
     /// Delete recovery for a buffer (call after saving or closing)
     pub fn delete_buffer_recovery(&mut self, buffer_id: BufferId) -> AnyhowResult<()> {
-        // Get recovery_id: use stored one for unnamed buffers, compute from path otherwise
-        let recovery_id = {
-            let meta = self.buffer_metadata.get(&buffer_id);
-            let state = self.buffers.get(&buffer_id);
-
-            if let Some(stored_id) = meta.and_then(|m| m.recovery_id.clone()) {
-                stored_id
-            } else if let Some(state) = state {
-                let path = state.buffer.file_path().map(|p| p.to_path_buf());
-                self.recovery_service.get_buffer_id(path.as_deref())
-            } else {
-                return Ok(());
-            }
+        let Some(recovery_id) = self.buffer_recovery_id(buffer_id) else {
+            return Ok(());
         };
 
         self.recovery_service.delete_buffer_recovery(&recovery_id)?;
@@ -372,3 +625,18 @@ impl Editor {
         Ok(())
     }
 }
+
+/// Whether a file was modified after its recovery entry was last saved
+///
+/// Timestamps have one-second resolution, so an entry saved in the same
+/// second as the file still counts as newer.
+fn file_modified_after_recovery(entry: &RecoveryEntry, path: &std::path::Path) -> bool {
+    let Ok(modified) = std::fs::metadata(path).and_then(|m| m.modified()) else {
+        return false;
+    };
+    let mtime = modified
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    mtime > entry.metadata.updated_at
+}
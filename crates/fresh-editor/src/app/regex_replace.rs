@@ -1,15 +1,16 @@
 /// Pure, buffer-agnostic helpers for regex find-and-replace.
 
 /// Build a [`regex::bytes::Regex`] from user-supplied search settings.
-/// Returns `None` when `use_regex` is false.
+/// Returns `Ok(None)` when `use_regex` is false, and the compile error when
+/// the pattern is invalid (e.g. an unterminated group).
 pub fn build_regex(
     search: &str,
     use_regex: bool,
     whole_word: bool,
     case_sensitive: bool,
-) -> Option<regex::bytes::Regex> {
+) -> Result<Option<regex::bytes::Regex>, regex::Error> {
     if !use_regex {
-        return None;
+        return Ok(None);
     }
 
     let pattern = if whole_word {
@@ -21,7 +22,7 @@ pub fn build_regex(
     regex::bytes::RegexBuilder::new(&pattern)
         .case_insensitive(!case_sensitive)
         .build()
-        .ok()
+        .map(Some)
 }
 
 /// Normalize `$N` capture references to `${N}` so the regex crate doesn't
@@ -30,15 +31,15 @@ pub fn build_regex(
 fn normalize_replacement(template: &str) -> String {
     let mut out = String::with_capacity(template.len());
     let bytes = template.as_bytes();
+    // Everything before `copied` is already in `out`; text is copied in
+    // slices so multi-byte characters stay intact
+    let mut copied = 0;
     let mut i = 0;
     while i < bytes.len() {
-        if bytes[i] == b'$' && i + 1 < bytes.len() && bytes[i + 1].is_ascii_digit() {
-            // Already braced: ${ … }
-            if i + 1 < bytes.len() && bytes[i + 1] == b'{' {
-                out.push(bytes[i] as char);
-                i += 1;
-                continue;
-            }
+        if bytes[i] == b'$' && bytes.get(i + 1) == Some(&b'$') {
+            // Escaped dollar: leave `$$` for the regex crate
+            i += 2;
+        } else if bytes[i] == b'$' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit) {
             // Collect the digit run
             let start = i + 1;
             let mut end = start;
@@ -48,19 +49,26 @@ fn normalize_replacement(template: &str) -> String {
             // If the next char after digits is alphanumeric (or _), we must
             // brace it, otherwise the regex crate would include those chars
             // in the group name.  Always bracing is harmless, so just do it.
-            out.push('$');
-            out.push('{');
+            out.push_str(&template[copied..i]);
+            out.push_str("${");
             out.push_str(&template[start..end]);
             out.push('}');
+            copied = end;
             i = end;
         } else {
-            out.push(bytes[i] as char);
             i += 1;
         }
     }
+    out.push_str(&template[copied..]);
     out
 }
 
+/// Whether `pos` falls on a UTF-8 character boundary of `haystack`
+fn is_char_boundary(haystack: &[u8], pos: usize) -> bool {
+    // Continuation bytes look like 0b10xx_xxxx
+    haystack.get(pos).is_none_or(|&b| (b as i8) >= -0x40)
+}
+
 /// A single match found in a buffer, together with its expanded replacement.
 #[derive(Debug, Clone)]
 pub struct ReplaceMatch {
@@ -82,6 +90,12 @@ pub fn collect_regex_matches(
     let normalized = normalize_replacement(replacement_template);
     regex
         .captures_iter(haystack)
+        // Empty matches can land inside a multi-byte character; replacing
+        // there would split it
+        .filter(|caps| {
+            let m = caps.get(0).unwrap();
+            is_char_boundary(haystack, m.start()) && is_char_boundary(haystack, m.end())
+        })
         .map(|caps| {
             let m = caps.get(0).unwrap();
             let mut expanded = Vec::new();
@@ -118,33 +132,39 @@ mod tests {
 
     #[test]
     fn build_regex_returns_none_when_disabled() {
-        assert!(build_regex("foo", false, false, true).is_none());
+        assert!(build_regex("foo", false, false, true).unwrap().is_none());
     }
 
     #[test]
     fn build_regex_basic_pattern() {
-        let re = build_regex("foo.*bar", true, false, true).unwrap();
+        let re = build_regex("foo.*bar", true, false, true).unwrap().unwrap();
         assert!(re.is_match(b"foo123bar"));
         assert!(!re.is_match(b"baz"));
     }
 
     #[test]
     fn build_regex_case_insensitive() {
-        let re = build_regex("hello", true, false, false).unwrap();
+        let re = build_regex("hello", true, false, false).unwrap().unwrap();
         assert!(re.is_match(b"HELLO"));
         assert!(re.is_match(b"hello"));
     }
 
     #[test]
     fn build_regex_whole_word() {
-        let re = build_regex("foo", true, true, true).unwrap();
+        let re = build_regex("foo", true, true, true).unwrap().unwrap();
         assert!(re.is_match(b"foo bar"));
         assert!(!re.is_match(b"foobar"));
     }
 
+    #[test]
+    fn build_regex_reports_invalid_pattern() {
+        let err = build_regex(r"(\w+", true, false, true).unwrap_err();
+        assert!(err.to_string().contains("unclosed group"));
+    }
+
     #[test]
     fn collect_regex_matches_literal_replacement() {
-        let re = build_regex("Sig:.*", true, false, true).unwrap();
+        let re = build_regex("Sig:.*", true, false, true).unwrap().unwrap();
         let input = b"AAAAAA\nSig: hello\nBBBBBB\nSig: world\nCCCCCC";
         let matches = collect_regex_matches(&re, input, "");
 
@@ -157,7 +177,9 @@ mod tests {
 
     #[test]
     fn collect_regex_matches_with_capture_groups() {
-        let re = build_regex(r"(\w+)@(\w+)", true, false, true).unwrap();
+        let re = build_regex(r"(\w+)@(\w+)", true, false, true)
+            .unwrap()
+            .unwrap();
         let input = b"alice@example bob@test";
         let matches = collect_regex_matches(&re, input, "$2=$1");
 
@@ -168,7 +190,9 @@ mod tests {
 
     #[test]
     fn expand_replacement_with_groups() {
-        let re = build_regex(r"(\d+)-(\d+)", true, false, true).unwrap();
+        let re = build_regex(r"(\d+)-(\d+)", true, false, true)
+            .unwrap()
+            .unwrap();
         let matched = b"123-456";
         let result = expand_replacement(&re, matched, "$2/$1");
         assert_eq!(result, "456/123");
@@ -176,7 +200,7 @@ mod tests {
 
     #[test]
     fn expand_replacement_no_groups() {
-        let re = build_regex("hello", true, false, true).unwrap();
+        let re = build_regex("hello", true, false, true).unwrap().unwrap();
         let matched = b"hello";
         let result = expand_replacement(&re, matched, "world");
         assert_eq!(result, "world");
@@ -196,12 +220,39 @@ mod tests {
         assert_eq!(normalize_replacement("$name"), "$name");
         // Literal $$ → passed through ($ not followed by digit)
         assert_eq!(normalize_replacement("$$"), "$$");
+        // An escaped dollar before digits is not a group reference
+        assert_eq!(normalize_replacement("$$1"), "$$1");
+        // Multi-byte characters around references survive
+        assert_eq!(normalize_replacement("→$1·é"), "→${1}·é");
+    }
+
+    #[test]
+    fn collect_regex_matches_multibyte() {
+        let re = build_regex(r"(\w+)@(\w+)", true, false, true)
+            .unwrap()
+            .unwrap();
+        let input = "josé@hôte ✓".as_bytes();
+        let matches = collect_regex_matches(&re, input, "$2.$1");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].len, "josé@hôte".len());
+        assert_eq!(matches[0].replacement, "hôte.josé");
+
+        // Empty matches never split a character
+        let re = build_regex("x*", true, false, true).unwrap().unwrap();
+        let input = "aé".as_bytes();
+        let offsets: Vec<usize> = collect_regex_matches(&re, input, "-")
+            .iter()
+            .map(|m| m.offset)
+            .collect();
+        assert_eq!(offsets, vec![0, 1, 3]);
     }
 
     /// Matches Python: re.sub(r'bla(bla)', r'oo\1oo', 'blablabla') == 'ooblaoobla'
     #[test]
     fn collect_regex_matches_capture_group_blabla() {
-        let re = build_regex(r"bla(bla)", true, false, true).unwrap();
+        let re = build_regex(r"bla(bla)", true, false, true)
+            .unwrap()
+            .unwrap();
         let input = b"blablabla";
         let matches = collect_regex_matches(&re, input, "oo$1oo");
 
@@ -1,4 +1,15 @@
 use super::*;
+use crate::input::actions::get_auto_close_char;
+use crate::model::cursor::Cursor;
+use crate::model::event::CursorId;
+use crate::primitives::align::align_edits;
+use crate::primitives::comment_toggle::{
+    builtin_comment_tokens, toggle_comment_edits, CommentTokens,
+};
+use crate::primitives::edit_offsets::map_offset;
+use crate::primitives::grapheme::is_grapheme_boundary;
+use crate::primitives::highlighter::HighlightCategory;
+use crate::primitives::join_lines::{join_lines_edits, JoinEdit};
 use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
 
@@ -23,6 +34,9 @@ impl Editor {
         // This sets viewport positions based on the authoritative scroll_line in each group
         self.sync_scroll_groups();
 
+        // Realign diff views whose buffer was edited since the last frame
+        self.refresh_diff_views();
+
         // NOTE: Viewport sync with cursor is handled by split_rendering.rs which knows the
         // correct content area dimensions. Don't sync here with incorrect EditorState viewport size.
 
@@ -84,6 +98,7 @@ impl Editor {
             matches!(
                 p.prompt_type,
                 PromptType::Search
+                    | PromptType::IncrementalSearch
                     | PromptType::ReplaceSearch
                     | PromptType::Replace { .. }
                     | PromptType::QueryReplaceSearch
@@ -271,9 +286,16 @@ impl Editor {
                         vs.view_transform_stale = false;
                     }
 
-                    // Use the split area height as visible line count
-                    let visible_count = split_area.height as usize;
+                    // Use the split area height as visible line count, plus
+                    // any lines hidden by conceals so they're decorated too
                     let top_byte = viewport_top_byte;
+                    let visible_count =
+                        crate::view::ui::split_rendering::SplitRenderer::source_lines_to_fill(
+                            state,
+                            top_byte,
+                            split_area.height as usize,
+                            self.config.editor.estimated_line_length,
+                        );
 
                     // Get or create the seen byte ranges set for this buffer
                     let seen_byte_ranges = self.seen_byte_ranges.entry(buffer_id).or_default();
@@ -351,9 +373,22 @@ impl Editor {
             }
         }
 
+        // Apply bracket matching settings before the overlays are recomputed
+        let highlight_brackets = self.config.editor.highlight_matching_brackets;
+        let rainbow_brackets = self.config.editor.rainbow_brackets;
+        for state in self.buffers.values_mut() {
+            state.bracket_highlight_overlay.configure(
+                highlight_brackets,
+                rainbow_brackets,
+                &mut state.overlays,
+                &mut state.marker_list,
+            );
+        }
+
         // Render editor content (same for both layouts)
         let lsp_waiting = self.pending_completion_request.is_some()
-            || self.pending_goto_definition_request.is_some();
+            || self.pending_goto_definition_request.is_some()
+            || self.pending_definition_lookup.is_some();
 
         // Hide the hardware cursor when menu is open, file explorer is focused, terminal mode,
         // or settings UI is open
@@ -391,6 +426,13 @@ impl Editor {
 
         let is_maximized = self.split_manager.is_maximized();
 
+        // Search matches belong to the active buffer
+        let active_buffer = self.active_buffer();
+        let search_matches = self
+            .search_state
+            .as_ref()
+            .map(|search| (active_buffer, search.matches.as_slice()));
+
         let (
             split_areas,
             tab_layouts,
@@ -398,6 +440,7 @@ impl Editor {
             maximize_split_areas,
             view_line_mappings,
             horizontal_scrollbar_areas,
+            scrollbar_markers,
         ) = SplitRenderer::render_content(
             frame,
             editor_content_area,
@@ -427,6 +470,10 @@ impl Editor {
             self.session_mode,
             self.config.editor.show_vertical_scrollbar,
             self.config.editor.show_horizontal_scrollbar,
+            self.config.editor.show_indent_guides,
+            self.config.editor.show_whitespace_warnings,
+            self.config.editor.show_whitespace,
+            search_matches,
         );
 
         // Detect viewport changes and fire hooks
@@ -495,6 +542,7 @@ impl Editor {
 
         self.cached_layout.split_areas = split_areas;
         self.cached_layout.horizontal_scrollbar_areas = horizontal_scrollbar_areas;
+        self.cached_layout.scrollbar_markers = scrollbar_markers;
         self.cached_layout.tab_layouts = tab_layouts;
         self.cached_layout.close_split_areas = close_split_areas;
         self.cached_layout.maximize_split_areas = maximize_split_areas;
@@ -582,7 +630,13 @@ impl Editor {
         let display_name = self
             .buffer_metadata
             .get(&self.active_buffer())
-            .map(|m| m.display_name.clone())
+            .map(|m| {
+                if m.deleted_on_disk {
+                    format!("{} [deleted]", m.display_name)
+                } else {
+                    m.display_name.clone()
+                }
+            })
             .unwrap_or_else(|| "[No Name]".to_string());
         let status_message = self.status_message.clone();
         let plugin_status_message = self.plugin_status_message.clone();
@@ -2027,12 +2081,61 @@ impl Editor {
         }
 
         let buffer_id = self.active_buffer();
+        let context_bytes = self.config.editor.highlight_context_bytes;
         let state = self.buffers.get_mut(&buffer_id).unwrap();
         let cursors = &mut self
             .split_view_states
             .get_mut(&active_split)
             .unwrap()
             .cursors;
+
+        // Auto-closing can be turned off per language, and brackets and quotes
+        // typed inside strings and comments are left unpaired
+        let auto_close = self
+            .config
+            .languages
+            .get(&state.language)
+            .is_none_or(|lang_config| lang_config.auto_close);
+        let closes = matches!(action, Action::InsertChar(ch)
+            if get_auto_close_char(ch, auto_indent, &state.language).is_some());
+        let mut quoted = Vec::new();
+        if auto_close && closes {
+            let positions: Vec<usize> = cursors
+                .iter()
+                .map(|(_, cursor)| {
+                    cursor
+                        .selection_range()
+                        .map_or(cursor.position, |r| r.start)
+                })
+                .collect();
+            // Start a byte early to catch a comment ending right at a cursor
+            let start = positions
+                .iter()
+                .copied()
+                .min()
+                .unwrap_or(0)
+                .saturating_sub(1);
+            let end = positions.iter().copied().max().unwrap_or(0) + 1;
+            quoted = state
+                .highlighter
+                .highlight_categories(&state.buffer, start, end, context_bytes)
+                .into_iter()
+                .filter_map(|(range, category)| match category {
+                    HighlightCategory::Comment => Some((range, true)),
+                    HighlightCategory::String => Some((range, false)),
+                    _ => None,
+                })
+                .collect();
+        }
+        // A line comment runs to the end of the line, so typing right after
+        // its last character is still inside it
+        let auto_close_at = |pos: usize| {
+            auto_close
+                && !quoted.iter().any(|(range, is_comment)| {
+                    range.start < pos && (pos < range.end || (*is_comment && pos == range.end))
+                })
+        };
+
         convert_action_to_events(
             state,
             cursors,
@@ -2041,6 +2144,7 @@ impl Editor {
             auto_indent,
             estimated_line_length,
             viewport_height,
+            &auto_close_at,
         )
     }
 
@@ -2061,6 +2165,7 @@ impl Editor {
 
         // Note: We don't intercept BlockSelectUp/Down because block selection has
         // special semantics (setting block_anchor) that require the default handler
+        let line_wrap = self.is_line_wrap_enabled();
         let visual_action = match action {
             Action::MoveUp => VisualAction::UpDown {
                 direction: -1,
@@ -2081,27 +2186,24 @@ impl Editor {
             // When line wrapping is off, Home/End should move to the physical line
             // start/end, not the visual (horizontally-scrolled) row boundary.
             // Fall through to the standard handler which uses line_iterator.
-            Action::MoveLineEnd if self.config.editor.line_wrap => {
-                VisualAction::LineEnd { is_select: false }
-            }
-            Action::SelectLineEnd if self.config.editor.line_wrap => {
-                VisualAction::LineEnd { is_select: true }
-            }
-            Action::MoveLineStart if self.config.editor.line_wrap => {
-                VisualAction::LineStart { is_select: false }
-            }
-            Action::SelectLineStart if self.config.editor.line_wrap => {
-                VisualAction::LineStart { is_select: true }
-            }
+            Action::MoveLineEnd if line_wrap => VisualAction::LineEnd { is_select: false },
+            Action::SelectLineEnd if line_wrap => VisualAction::LineEnd { is_select: true },
+            Action::MoveLineStart if line_wrap => VisualAction::LineStart { is_select: false },
+            Action::SelectLineStart if line_wrap => VisualAction::LineStart { is_select: true },
             _ => return None, // Not a visual line action
         };
 
+        // In compose mode a second Home/End on a wrapped paragraph jumps to the
+        // logical line bounds instead of stepping one visual row at a time.
+        let compose_mode = self.is_compose_mode();
+        let estimated_line_length = self.config.editor.estimated_line_length;
+
         // First, collect cursor data we need (to avoid borrow conflicts)
         let cursor_data: Vec<_> = {
             let active_split = self.split_manager.active_split();
             let active_buffer = self.split_manager.active_buffer_id().unwrap();
             let cursors = &self.split_view_states.get(&active_split).unwrap().cursors;
-            let state = self.buffers.get(&active_buffer).unwrap();
+            let state = self.buffers.get_mut(&active_buffer).unwrap();
             cursors
                 .iter()
                 .map(|(cursor_id, cursor)| {
@@ -2124,6 +2226,17 @@ impl Editor {
                             .slice_bytes(cursor.position - 1..cursor.position);
                         prev.first() == Some(&b'\n')
                     };
+                    let logical_bounds = if compose_mode {
+                        let mut iter = state
+                            .buffer
+                            .line_iterator(cursor.position, estimated_line_length);
+                        iter.next_line().map(|(line_start, content)| {
+                            let content_len = content.trim_end_matches(['\r', '\n']).len();
+                            (line_start, line_start + content_len)
+                        })
+                    } else {
+                        None
+                    };
                     (
                         cursor_id,
                         cursor.position,
@@ -2132,6 +2245,7 @@ impl Editor {
                         cursor.deselect_on_move,
                         at_line_ending,
                         at_line_start,
+                        logical_bounds,
                     )
                 })
                 .collect()
@@ -2147,6 +2261,7 @@ impl Editor {
             deselect_on_move,
             at_line_ending,
             at_line_start,
+            logical_bounds,
         ) in cursor_data
         {
             let (new_pos, new_sticky) = match &visual_action {
@@ -2158,11 +2273,7 @@ impl Editor {
                             None => return None,
                         };
 
-                    let goal_visual_col = if sticky_column > 0 {
-                        sticky_column
-                    } else {
-                        current_visual_col
-                    };
+                    let goal_visual_col = sticky_column.unwrap_or(current_visual_col);
 
                     match self.cached_layout.move_visual_line(
                         split_id,
@@ -2170,30 +2281,48 @@ impl Editor {
                         goal_visual_col,
                         *direction,
                     ) {
-                        Some(result) => result,
+                        Some((pos, goal)) => (pos, Some(goal)),
                         None => continue, // At boundary, skip this cursor
                     }
                 }
                 VisualAction::LineEnd { .. } => {
                     // Allow advancing to next visual segment only if not at a physical line ending
                     let allow_advance = !at_line_ending;
-                    match self
+                    let row_end = self
                         .cached_layout
-                        .visual_line_end(split_id, position, allow_advance)
-                    {
-                        Some(end_pos) => (end_pos, 0),
-                        None => return None,
+                        .visual_line_end(split_id, position, false)?;
+                    match logical_bounds {
+                        Some((_, line_end)) if allow_advance && position == row_end => {
+                            (line_end, None)
+                        }
+                        _ => match self.cached_layout.visual_line_end(
+                            split_id,
+                            position,
+                            allow_advance,
+                        ) {
+                            Some(end_pos) => (end_pos, None),
+                            None => return None,
+                        },
                     }
                 }
                 VisualAction::LineStart { .. } => {
                     // Allow advancing to previous visual segment only if not at a physical line start
                     let allow_advance = !at_line_start;
-                    match self
+                    let row_start = self
                         .cached_layout
-                        .visual_line_start(split_id, position, allow_advance)
-                    {
-                        Some(start_pos) => (start_pos, 0),
-                        None => return None,
+                        .visual_line_start(split_id, position, false)?;
+                    match logical_bounds {
+                        Some((line_start, _)) if allow_advance && position == row_start => {
+                            (line_start, None)
+                        }
+                        _ => match self.cached_layout.visual_line_start(
+                            split_id,
+                            position,
+                            allow_advance,
+                        ) {
+                            Some(start_pos) => (start_pos, None),
+                            None => return None,
+                        },
                     }
                 }
             };
@@ -2256,36 +2385,12 @@ impl Editor {
             return;
         }
 
-        // Get theme colors and search settings before borrowing state
+        // Get theme colors before borrowing state
         let search_bg = self.theme.search_match_bg;
         let search_fg = self.theme.search_match_fg;
-        let case_sensitive = self.search_case_sensitive;
-        let whole_word = self.search_whole_word;
-        let use_regex = self.search_use_regex;
         let ns = self.search_namespace.clone();
 
-        // Build regex pattern if regex mode is enabled, or escape for literal search
-        let regex_pattern = if use_regex {
-            if whole_word {
-                format!(r"\b{}\b", query)
-            } else {
-                query.to_string()
-            }
-        } else {
-            let escaped = regex::escape(query);
-            if whole_word {
-                format!(r"\b{}\b", escaped)
-            } else {
-                escaped
-            }
-        };
-
-        // Build regex with case sensitivity
-        let regex = regex::RegexBuilder::new(&regex_pattern)
-            .case_insensitive(!case_sensitive)
-            .build();
-
-        let regex = match regex {
+        let regex = match self.build_search_regex(query) {
             Ok(r) => r,
             Err(_) => {
                 // Invalid regex, clear highlights and return
@@ -2328,8 +2433,11 @@ impl Editor {
         // Get the visible text
         let visible_text = state.get_text_range(visible_start, visible_end);
 
-        // Find all matches using regex
-        for mat in regex.find_iter(&visible_text) {
+        // Find all matches using regex, skipping any that split a grapheme cluster
+        for mat in regex.find_iter(&visible_text).filter(|m| {
+            is_grapheme_boundary(&visible_text, m.start())
+                && is_grapheme_boundary(&visible_text, m.end())
+        }) {
             let absolute_pos = visible_start + mat.start();
             let match_len = mat.end() - mat.start();
 
@@ -2384,11 +2492,6 @@ impl Editor {
             }
         };
 
-        // Get search settings
-        let case_sensitive = self.search_case_sensitive;
-        let whole_word = self.search_whole_word;
-        let use_regex = self.search_use_regex;
-
         // Determine search boundaries
         let (search_start, search_end) = if let Some(ref range) = search_range {
             (range.start, range.end)
@@ -2396,27 +2499,7 @@ impl Editor {
             (0, buffer_content.len())
         };
 
-        // Build regex pattern
-        let regex_pattern = if use_regex {
-            if whole_word {
-                format!(r"\b{}\b", query)
-            } else {
-                query.to_string()
-            }
-        } else {
-            let escaped = regex::escape(query);
-            if whole_word {
-                format!(r"\b{}\b", escaped)
-            } else {
-                escaped
-            }
-        };
-
-        // Build regex with case sensitivity
-        let regex = match regex::RegexBuilder::new(&regex_pattern)
-            .case_insensitive(!case_sensitive)
-            .build()
-        {
+        let regex = match self.build_search_regex(query) {
             Ok(r) => r,
             Err(e) => {
                 self.search_state = None;
@@ -2431,6 +2514,10 @@ impl Editor {
         let search_slice = &buffer_content[search_start..search_end];
         let match_ranges: Vec<(usize, usize)> = regex
             .find_iter(search_slice)
+            .filter(|m| {
+                is_grapheme_boundary(&buffer_content, search_start + m.start())
+                    && is_grapheme_boundary(&buffer_content, search_start + m.end())
+            })
             .map(|m| (search_start + m.start(), m.end() - m.start()))
             .collect();
 
@@ -2482,22 +2569,9 @@ impl Editor {
             .position(|&pos| pos >= cursor_pos)
             .unwrap_or(0);
 
-        // Move cursor to the first match
-        let match_pos = matches[current_match_index];
-        {
-            let active_split = self.split_manager.active_split();
-            let active_buffer = self.active_buffer();
-            if let Some(view_state) = self.split_view_states.get_mut(&active_split) {
-                view_state.cursors.primary_mut().position = match_pos;
-                view_state.cursors.primary_mut().anchor = None;
-                // Ensure cursor is visible
-                let cursor = *view_state.cursors.primary();
-                let state = self.buffers.get_mut(&active_buffer).unwrap();
-                view_state
-                    .viewport
-                    .ensure_visible(&mut state.buffer, &cursor);
-            }
-        }
+        // Move cursor to the first match, remembering where the search started
+        self.record_position_in_history();
+        self.move_cursor_to_match(matches[current_match_index]);
 
         let num_matches = matches.len();
 
@@ -2528,6 +2602,125 @@ impl Editor {
         self.set_status_message(msg);
     }
 
+    /// Move the primary cursor to a search match and scroll it into view
+    fn move_cursor_to_match(&mut self, match_pos: usize) {
+        let active_split = self.split_manager.active_split();
+        let active_buffer = self.active_buffer();
+        if let Some(view_state) = self.split_view_states.get_mut(&active_split) {
+            view_state.cursors.primary_mut().position = match_pos;
+            view_state.cursors.primary_mut().anchor = None;
+            // Ensure cursor is visible
+            let cursor = *view_state.cursors.primary();
+            let state = self.buffers.get_mut(&active_buffer).unwrap();
+            view_state
+                .viewport
+                .ensure_visible(&mut state.buffer, &cursor);
+        }
+    }
+
+    /// Re-run incremental search as the query changes
+    ///
+    /// Every refinement searches the whole buffer again from where the search
+    /// started, so the cursor lands on the nearest match at or after that
+    /// point (wrapping to the top), and the status bar shows a `3/17` counter.
+    pub(super) fn update_incremental_search(&mut self, query: &str) {
+        if let Some(origin) = self.incremental_search_origin {
+            self.move_cursor_to_match(origin);
+        }
+        // Drop the previous query's highlights; a query without matches shows none
+        self.clear_search_overlays();
+        if query.is_empty() {
+            self.search_state = None;
+            return;
+        }
+        self.perform_search(query);
+        self.show_search_match_counter();
+    }
+
+    /// Re-run the search after a search option was toggled
+    ///
+    /// While a search prompt is open this uses its input, since search_state
+    /// may still hold a stale query; otherwise the last search is repeated.
+    pub(super) fn refresh_search_for_options(&mut self) {
+        if let Some(prompt) = &self.prompt {
+            let query = prompt.input.clone();
+            match prompt.prompt_type {
+                PromptType::Search | PromptType::ReplaceSearch | PromptType::QueryReplaceSearch => {
+                    self.update_search_highlights(&query);
+                }
+                PromptType::IncrementalSearch => self.update_incremental_search(&query),
+                PromptType::SearchInProject => self.run_project_search(&query),
+                _ => {}
+            }
+        } else if let Some(search_state) = &self.search_state {
+            let query = search_state.query.clone();
+            self.perform_search(&query);
+        }
+    }
+
+    /// Step to the next or previous incremental search match, wrapping around
+    pub(super) fn step_incremental_search(&mut self, forward: bool) {
+        if self.search_state.is_none() {
+            return;
+        }
+        if forward {
+            self.find_next();
+        } else {
+            self.find_previous();
+        }
+        // Refining the query from here continues from the current match
+        self.incremental_search_origin = Some(self.active_cursors().primary().position);
+        self.show_search_match_counter();
+    }
+
+    /// Show the `current/total` match counter for the active search
+    fn show_search_match_counter(&mut self) {
+        let Some(search_state) = &self.search_state else {
+            return;
+        };
+        let Some(index) = search_state.current_match_index else {
+            return;
+        };
+        let total = search_state.matches.len();
+        self.set_status_message(
+            t!("search.match_counter", current = index + 1, total = total).to_string(),
+        );
+    }
+
+    /// Show how many matches a replace will touch, before it is applied
+    pub(super) fn show_replace_preview(&mut self) {
+        if let Some(search_state) = &self.search_state {
+            let count = search_state.matches.len();
+            self.set_status_message(t!("replace.preview", count = count).to_string());
+        }
+    }
+
+    /// Whether a search for `query` should match case
+    ///
+    /// With smart case, a query containing an uppercase letter is
+    /// case-sensitive even when case-sensitive search is off.
+    fn search_is_case_sensitive(&self, query: &str) -> bool {
+        self.search_case_sensitive
+            || (self.search_smart_case && query.chars().any(char::is_uppercase))
+    }
+
+    /// Build the regex for `query` from the current search options
+    pub(super) fn build_search_regex(&self, query: &str) -> Result<regex::Regex, regex::Error> {
+        let pattern = if self.search_use_regex {
+            query.to_string()
+        } else {
+            regex::escape(query)
+        };
+        let pattern = if self.search_whole_word {
+            format!(r"\b{}\b", pattern)
+        } else {
+            pattern
+        };
+        regex::RegexBuilder::new(&pattern)
+            .case_insensitive(!self.search_is_case_sensitive(query))
+            .build()
+    }
+
     /// Get current match positions from search overlays (which use markers that track edits)
     /// This ensures positions are always up-to-date even after buffer modifications
     fn get_search_match_positions(&self) -> Vec<usize> {
@@ -2584,20 +2777,8 @@ impl Editor {
             let match_pos = match_positions[next_index];
             let matches_len = match_positions.len();
 
-            {
-                let active_split = self.split_manager.active_split();
-                let active_buffer = self.active_buffer();
-                if let Some(view_state) = self.split_view_states.get_mut(&active_split) {
-                    view_state.cursors.primary_mut().position = match_pos;
-                    view_state.cursors.primary_mut().anchor = None;
-                    // Ensure cursor is visible
-                    let cursor = *view_state.cursors.primary();
-                    let state = self.buffers.get_mut(&active_buffer).unwrap();
-                    view_state
-                        .viewport
-                        .ensure_visible(&mut state.buffer, &cursor);
-                }
-            }
+            self.record_position_in_history();
+            self.move_cursor_to_match(match_pos);
 
             self.set_status_message(
                 t!(
@@ -2650,20 +2831,8 @@ impl Editor {
             let match_pos = match_positions[prev_index];
             let matches_len = match_positions.len();
 
-            {
-                let active_split = self.split_manager.active_split();
-                let active_buffer = self.active_buffer();
-                if let Some(view_state) = self.split_view_states.get_mut(&active_split) {
-                    view_state.cursors.primary_mut().position = match_pos;
-                    view_state.cursors.primary_mut().anchor = None;
-                    // Ensure cursor is visible
-                    let cursor = *view_state.cursors.primary();
-                    let state = self.buffers.get_mut(&active_buffer).unwrap();
-                    view_state
-                        .viewport
-                        .ensure_visible(&mut state.buffer, &cursor);
-                }
-            }
+            self.record_position_in_history();
+            self.move_cursor_to_match(match_pos);
 
             self.set_status_message(
                 t!(
@@ -2846,15 +3015,18 @@ impl Editor {
         }
     }
 
-    /// Perform a replace-all operation
     /// Build a compiled byte-regex for replace operations using current search settings.
-    /// Returns None when regex mode is off (plain text matching should be used).
-    fn build_replace_regex(&self, search: &str) -> Option<regex::bytes::Regex> {
+    /// Returns None when regex mode is off (plain text matching should be used),
+    /// and the compile error when the pattern is invalid.
+    fn build_replace_regex(
+        &self,
+        search: &str,
+    ) -> Result<Option<regex::bytes::Regex>, regex::Error> {
         super::regex_replace::build_regex(
             search,
             self.search_use_regex,
             self.search_whole_word,
-            self.search_case_sensitive,
+            self.search_is_case_sensitive(search),
         )
     }
 
@@ -2887,25 +3059,47 @@ impl Editor {
 
     /// Replaces all occurrences of the search query with the replacement text
     ///
+    /// With `range` set (replace in selection), only matches inside it are replaced.
+    /// An invalid regex is reported in the status bar and leaves the buffer untouched.
+    ///
     /// OPTIMIZATION: Uses BulkEdit for O(n) tree operations instead of O(n²)
     /// This directly edits the piece tree without loading the entire buffer into memory
-    pub(super) fn perform_replace(&mut self, search: &str, replacement: &str) {
+    pub(super) fn perform_replace(
+        &mut self,
+        search: &str,
+        replacement: &str,
+        range: Option<Range<usize>>,
+    ) {
         if search.is_empty() {
             self.set_status_message(t!("replace.empty_query").to_string());
             return;
         }
 
-        let compiled_regex = self.build_replace_regex(search);
+        let compiled_regex = match self.build_replace_regex(search) {
+            Ok(regex) => regex,
+            Err(e) => {
+                self.set_status_message(
+                    t!("error.invalid_regex", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+        };
+        let (scope_start, scope_end) = match range {
+            Some(ref range) => (range.start, range.end.min(self.active_state().buffer.len())),
+            None => (0, self.active_state().buffer.len()),
+        };
 
         // Find all matches first (before making any modifications)
         // Each match is (position, length, expanded_replacement)
         let matches: Vec<(usize, usize, String)> = if let Some(ref regex) = compiled_regex {
             // Regex mode: load buffer content as bytes and find all matches
             // with capture group expansion in the replacement template
-            let buffer_bytes = {
+            let scope_bytes = {
                 let state = self.active_state_mut();
-                let total_bytes = state.buffer.len();
-                match state.buffer.get_text_range_mut(0, total_bytes) {
+                match state
+                    .buffer
+                    .get_text_range_mut(scope_start, scope_end - scope_start)
+                {
                     Ok(bytes) => bytes,
                     Err(e) => {
                         tracing::warn!("Failed to load buffer for replace: {}", e);
@@ -2914,22 +3108,21 @@ impl Editor {
                     }
                 }
             };
-            super::regex_replace::collect_regex_matches(regex, &buffer_bytes, replacement)
+            super::regex_replace::collect_regex_matches(regex, &scope_bytes, replacement)
                 .into_iter()
-                .map(|m| (m.offset, m.len, m.replacement))
+                .map(|m| (scope_start + m.offset, m.len, m.replacement))
                 .collect()
         } else {
             // Plain text mode - replacement is used literally
             let state = self.active_state();
-            let buffer_len = state.buffer.len();
             let mut matches = Vec::new();
-            let mut current_pos = 0;
+            let mut current_pos = scope_start;
 
-            while current_pos < buffer_len {
+            while current_pos < scope_end {
                 if let Some(offset) = state.buffer.find_next_in_range(
                     search,
                     current_pos,
-                    Some(current_pos..buffer_len),
+                    Some(current_pos..scope_end),
                 ) {
                     matches.push((offset, search.len(), replacement.to_string()));
                     current_pos = offset + search.len();
@@ -3005,7 +3198,15 @@ impl Editor {
             return;
         }
 
-        let compiled_regex = self.build_replace_regex(search);
+        let compiled_regex = match self.build_replace_regex(search) {
+            Ok(regex) => regex,
+            Err(e) => {
+                self.set_status_message(
+                    t!("error.invalid_regex", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+        };
 
         // Find the first match lazily (don't find all matches upfront)
         let start_pos = self.active_cursors().primary().position;
@@ -3337,7 +3538,7 @@ impl Editor {
                 old_anchor,
                 new_anchor: None,
                 old_sticky_column,
-                new_sticky_column: 0,
+                new_sticky_column: None,
             },
             Event::Delete {
                 range: range.clone(),
@@ -3418,7 +3619,7 @@ impl Editor {
         let cursor_id = self.active_cursors().primary_id();
 
         // When line wrap is on, use the visual (soft-wrapped) line boundaries
-        if self.config.editor.line_wrap {
+        if self.is_line_wrap_enabled() {
             let split_id = self.split_manager.active_split();
             if let Some(new_pos) =
                 self.smart_home_visual_line(split_id, cursor.position, estimated_line_length)
@@ -3430,7 +3631,7 @@ impl Editor {
                     old_anchor: cursor.anchor,
                     new_anchor: None,
                     old_sticky_column: cursor.sticky_column,
-                    new_sticky_column: 0,
+                    new_sticky_column: None,
                 };
                 self.active_event_log_mut().append(event.clone());
                 self.apply_event_to_active_buffer(&event);
@@ -3468,7 +3669,7 @@ impl Editor {
                 old_anchor: cursor.anchor,
                 new_anchor: None,
                 old_sticky_column: cursor.sticky_column,
-                new_sticky_column: 0,
+                new_sticky_column: None,
             };
 
             self.active_event_log_mut().append(event.clone());
@@ -3484,6 +3685,8 @@ impl Editor {
     /// On a **continuation** (wrapped) row the cursor moves to the visual row
     /// start; if already there it advances to the previous visual row's start
     /// so that repeated Home presses walk all the way back to position 0.
+    /// In compose mode the second press goes straight to the physical line
+    /// start, so Home twice reaches the start of a wrapped paragraph.
     fn smart_home_visual_line(
         &mut self,
         split_id: LeafId,
@@ -3526,7 +3729,9 @@ impl Editor {
             }
         } else {
             // Continuation row: go to visual line start, or advance backward
-            if cursor_pos == visual_start {
+            if cursor_pos == visual_start && self.is_compose_mode() {
+                Some(phys_line_start)
+            } else if cursor_pos == visual_start {
                 // Already at start – advance to previous visual row's start
                 self.cached_layout
                     .visual_line_start(split_id, cursor_pos, true)
@@ -3536,27 +3741,31 @@ impl Editor {
         }
     }
 
+    /// Comment markers for the active buffer
+    ///
+    /// The comment token comes from the language config, falling back to the
+    /// built-in tokens for the buffer's language or syntax.
+    fn active_comment_tokens(&self) -> Option<CommentTokens> {
+        let state = self.active_state();
+        self.config
+            .languages
+            .get(&state.language)
+            .and_then(|lang_config| lang_config.comment_prefix.as_deref())
+            .map(|prefix| CommentTokens::Line(prefix.trim_end().to_string()))
+            .or_else(|| builtin_comment_tokens(&state.language))
+            .or_else(|| {
+                state
+                    .highlighter
+                    .syntax_name()
+                    .and_then(builtin_comment_tokens)
+            })
+    }
+
     /// Toggle comment on the current line or selection
     pub(super) fn toggle_comment(&mut self) {
-        // Determine comment prefix from language config
-        // If no language detected or no comment prefix configured, do nothing
-        let language = &self.active_state().language;
-        let comment_prefix = self
-            .config
-            .languages
-            .get(language)
-            .and_then(|lang_config| lang_config.comment_prefix.clone());
-
-        let comment_prefix: String = match comment_prefix {
-            Some(prefix) => {
-                // Ensure there's a trailing space for consistent formatting
-                if prefix.ends_with(' ') {
-                    prefix
-                } else {
-                    format!("{} ", prefix)
-                }
-            }
-            None => return, // No comment prefix for this language, do nothing
+        // No comment syntax known for this language: do nothing
+        let Some(tokens) = self.active_comment_tokens() else {
+            return;
         };
 
         let estimated_line_length = self.config.editor.estimated_line_length;
@@ -3565,165 +3774,318 @@ impl Editor {
         let cursor_id = self.active_cursors().primary_id();
         let state = self.active_state_mut();
 
-        // Save original selection info to restore after edit
-        let original_anchor = cursor.anchor;
-        let original_position = cursor.position;
-        let had_selection = original_anchor.is_some();
-
-        let (start_pos, end_pos) = if let Some(range) = cursor.selection_range() {
-            (range.start, range.end)
-        } else {
-            let iter = state
-                .buffer
-                .line_iterator(cursor.position, estimated_line_length);
-            let line_start = iter.current_position();
-            (line_start, cursor.position)
+        let (start_pos, end_pos) = match cursor.selection_range() {
+            Some(range) => (range.start, range.end),
+            None => (cursor.position, cursor.position),
         };
 
-        // Find all line starts in the range
+        // Collect the lines touched by the cursor or selection. A selection
+        // ending at the start of a line doesn't include that line.
         let buffer_len = state.buffer.len();
-        let mut line_starts = Vec::new();
+        let mut lines = Vec::new();
         let mut iter = state.buffer.line_iterator(start_pos, estimated_line_length);
-        let mut current_pos = iter.current_position();
-        line_starts.push(current_pos);
-
-        while let Some((_, content)) = iter.next_line() {
-            current_pos += content.len();
-            if current_pos >= end_pos || current_pos >= buffer_len {
+        while let Some((line_start, content)) = iter.next_line() {
+            let line_end = line_start + content.len();
+            let text = content.trim_end_matches(['\n', '\r']).to_string();
+            lines.push((line_start, text));
+            if line_end >= end_pos || line_end >= buffer_len {
                 break;
             }
-            let next_iter = state
-                .buffer
-                .line_iterator(current_pos, estimated_line_length);
-            let next_start = next_iter.current_position();
-            if next_start != *line_starts.last().unwrap() {
-                line_starts.push(next_start);
-            }
-            iter = state
-                .buffer
-                .line_iterator(current_pos, estimated_line_length);
         }
+        let line_refs: Vec<(usize, &str)> = lines
+            .iter()
+            .map(|(start, text)| (*start, text.as_str()))
+            .collect();
 
-        // Determine if we should comment or uncomment
-        // If all lines are commented, uncomment; otherwise comment
-        let all_commented = line_starts.iter().all(|&line_start| {
-            let line_bytes = state
-                .buffer
-                .slice_bytes(line_start..buffer_len.min(line_start + comment_prefix.len() + 10));
-            let line_str = String::from_utf8_lossy(&line_bytes);
-            let trimmed = line_str.trim_start();
-            trimmed.starts_with(comment_prefix.trim())
-        });
+        let (commenting, edits) = toggle_comment_edits(&line_refs, &tokens);
+        if edits.is_empty() {
+            return;
+        }
 
-        let mut events = Vec::new();
-        // Track (edit_position, byte_delta) for calculating new cursor positions
-        // delta is positive for insertions, negative for deletions
-        let mut position_deltas: Vec<(usize, isize)> = Vec::new();
-
-        if all_commented {
-            // Uncomment: remove comment prefix from each line
-            for &line_start in line_starts.iter().rev() {
-                let line_bytes = state
-                    .buffer
-                    .slice_bytes(line_start..buffer_len.min(line_start + 100));
-                let line_str = String::from_utf8_lossy(&line_bytes);
-
-                // Find where the comment prefix starts (after leading whitespace)
-                let leading_ws: usize = line_str
-                    .chars()
-                    .take_while(|c| c.is_whitespace() && *c != '\n')
-                    .map(|c| c.len_utf8())
-                    .sum();
-                let rest = &line_str[leading_ws..];
-
-                if rest.starts_with(comment_prefix.trim()) {
-                    let remove_len = if rest.starts_with(&comment_prefix) {
-                        comment_prefix.len()
-                    } else {
-                        comment_prefix.trim().len()
-                    };
-                    let deleted_text = String::from_utf8_lossy(&state.buffer.slice_bytes(
-                        line_start + leading_ws..line_start + leading_ws + remove_len,
-                    ))
-                    .to_string();
-                    events.push(Event::Delete {
-                        range: (line_start + leading_ws)..(line_start + leading_ws + remove_len),
-                        deleted_text,
-                        cursor_id,
-                    });
-                    position_deltas.push((line_start, -(remove_len as isize)));
+        // Edit events don't move the cursor; the MoveCursor below keeps the
+        // cursor and selection on the same text
+        let mut events: Vec<Event> = edits
+            .iter()
+            .rev()
+            .map(|edit| {
+                if edit.range.is_empty() {
+                    Event::Insert {
+                        position: edit.range.start,
+                        text: edit.text.clone(),
+                        cursor_id: CursorId::UNDO_SENTINEL,
+                    }
+                } else {
+                    Event::Delete {
+                        range: edit.range.clone(),
+                        deleted_text: state.get_text_range(edit.range.start, edit.range.end),
+                        cursor_id: CursorId::UNDO_SENTINEL,
+                    }
                 }
-            }
-        } else {
-            // Comment: add comment prefix to each line
-            let prefix_len = comment_prefix.len();
-            for &line_start in line_starts.iter().rev() {
-                events.push(Event::Insert {
-                    position: line_start,
-                    text: comment_prefix.to_string(),
-                    cursor_id,
-                });
-                position_deltas.push((line_start, prefix_len as isize));
-            }
-        }
+            })
+            .collect();
+        let map = |pos| map_offset(pos, edits.iter().map(|e| (e.range.clone(), e.text.len())));
+        events.push(Event::MoveCursor {
+            cursor_id,
+            old_position: cursor.position,
+            new_position: map(cursor.position),
+            old_anchor: cursor.anchor,
+            new_anchor: cursor.anchor.map(map),
+            old_sticky_column: cursor.sticky_column,
+            new_sticky_column: None,
+        });
 
-        if events.is_empty() {
-            return;
+        let action_desc = if commenting { "Comment" } else { "Uncomment" };
+
+        // Use optimized bulk edit for multi-line comment toggle
+        let description = format!("{} lines", action_desc);
+        if let Some(bulk_edit) = self.apply_events_as_bulk_edit(events, description) {
+            self.active_event_log_mut().append(bulk_edit);
         }
 
-        let action_desc = if all_commented {
-            "Uncomment"
+        self.set_status_message(
+            t!("lines.action", action = action_desc, count = lines.len()).to_string(),
+        );
+    }
+
+    /// Join each cursor's line with the next one, or the lines of its selection
+    ///
+    /// A line comment token (or `>` in markdown) repeated on the joined lines
+    /// is dropped. A cursor without a selection ends up at the join point; a
+    /// selection ends up covering the joined line.
+    pub(super) fn join_lines(&mut self) {
+        let leader = if self.active_state().language == "markdown" {
+            Some(">".to_string())
         } else {
-            "Comment"
+            match self.active_comment_tokens() {
+                Some(CommentTokens::Line(token)) => Some(token),
+                _ => None,
+            }
         };
+        let cursors: Vec<(CursorId, Cursor)> = self
+            .active_cursors()
+            .iter()
+            .map(|(id, cursor)| (id, *cursor))
+            .collect();
+        let state = self.active_state_mut();
+        let buffer_len = state.buffer.len();
 
-        // If there was a selection, add a MoveCursor event to restore it
-        if had_selection {
-            // Sort deltas by position ascending for calculation
-            position_deltas.sort_by_key(|(pos, _)| *pos);
-
-            // Calculate cumulative shift for a position based on edits at or before that position
-            let calc_shift = |original_pos: usize| -> isize {
-                let mut shift: isize = 0;
-                for (edit_pos, delta) in &position_deltas {
-                    if *edit_pos < original_pos {
-                        shift += delta;
-                    }
+        // Line ranges to join: a selection's lines, or the cursor's line and
+        // the next. A selection ending at the start of a line doesn't include
+        // that line.
+        let cursor_lines: Vec<(usize, usize)> = cursors
+            .iter()
+            .map(|(_, cursor)| {
+                let (start, end) = match cursor.selection_range() {
+                    Some(range) => (range.start, range.end),
+                    None => (cursor.position, cursor.position),
+                };
+                let start_line = state.buffer.get_line_number(start);
+                let mut end_line = state.buffer.get_line_number(end);
+                if end_line > start_line && state.buffer.line_start_offset(end_line) == Some(end) {
+                    end_line -= 1;
                 }
-                shift
-            };
+                (start_line, end_line.max(start_line + 1))
+            })
+            .collect();
+        let mut regions = cursor_lines.clone();
+        regions.sort_unstable();
+        let mut merged: Vec<(usize, usize)> = Vec::with_capacity(regions.len());
+        for (start_line, end_line) in regions {
+            match merged.last_mut() {
+                Some(last) if start_line <= last.1 => last.1 = last.1.max(end_line),
+                _ => merged.push((start_line, end_line)),
+            }
+        }
 
-            let anchor_shift = calc_shift(original_anchor.unwrap_or(0));
-            let position_shift = calc_shift(original_position);
+        // For each region: its first line's line number, the start and end
+        // (before the final line ending) of its text, and the join edits
+        let mut joins = Vec::with_capacity(merged.len());
+        for (start_line, end_line) in merged {
+            let Some(start) = state.buffer.line_start_offset(start_line) else {
+                continue;
+            };
+            let end = state
+                .buffer
+                .line_start_offset(end_line + 1)
+                .unwrap_or(buffer_len);
+            let text = state.get_text_range(start, end);
+            let mut offset = start;
+            let lines: Vec<(usize, &str)> = text
+                .split_inclusive('\n')
+                .map(|line| {
+                    let entry = (offset, line.trim_end_matches(['\n', '\r']));
+                    offset += line.len();
+                    entry
+                })
+                .collect();
+            let text_end = lines.last().map_or(start, |(s, line)| s + line.len());
+            let edits = join_lines_edits(&lines, leader.as_deref());
+            joins.push((start_line, start, text_end, edits));
+        }
+        if joins.iter().all(|(_, _, _, edits)| edits.is_empty()) {
+            return;
+        }
 
-            let new_anchor = (original_anchor.unwrap_or(0) as isize + anchor_shift).max(0) as usize;
-            let new_position = (original_position as isize + position_shift).max(0) as usize;
+        // Edit events don't move the cursors; the MoveCursor events below
+        // place them in the joined text
+        let mut events = Vec::new();
+        for edit in joins
+            .iter()
+            .rev()
+            .flat_map(|(_, _, _, edits)| edits.iter().rev())
+        {
+            if !edit.range.is_empty() {
+                events.push(Event::Delete {
+                    range: edit.range.clone(),
+                    deleted_text: state.get_text_range(edit.range.start, edit.range.end),
+                    cursor_id: CursorId::UNDO_SENTINEL,
+                });
+            }
+            if !edit.text.is_empty() {
+                events.push(Event::Insert {
+                    position: edit.range.start,
+                    text: edit.text.clone(),
+                    cursor_id: CursorId::UNDO_SENTINEL,
+                });
+            }
+        }
 
+        let delta = |edits: &[JoinEdit]| -> isize {
+            edits
+                .iter()
+                .map(|edit| edit.text.len() as isize - edit.range.len() as isize)
+                .sum()
+        };
+        for ((cursor_id, cursor), &(cursor_line, _)) in cursors.iter().zip(&cursor_lines) {
+            // The region holding the cursor and the shift from the ones before it
+            let index = joins
+                .iter()
+                .rposition(|(start_line, ..)| *start_line <= cursor_line)
+                .unwrap_or(0);
+            let shift: isize = joins[..index]
+                .iter()
+                .map(|(_, _, _, edits)| delta(edits))
+                .sum();
+            let shifted = |pos: usize| (pos as isize + shift) as usize;
+            let (_, start, text_end, edits) = &joins[index];
+            let (new_position, new_anchor) = match (cursor.anchor, edits.first()) {
+                (Some(_), Some(_)) => (
+                    shifted((*text_end as isize + delta(edits)) as usize),
+                    Some(shifted(*start)),
+                ),
+                (None, Some(edit)) => (shifted(edit.range.start), None),
+                (anchor, None) => (shifted(cursor.position), anchor.map(shifted)),
+            };
             events.push(Event::MoveCursor {
-                cursor_id,
-                old_position: original_position,
+                cursor_id: *cursor_id,
+                old_position: cursor.position,
                 new_position,
-                old_anchor: original_anchor,
-                new_anchor: Some(new_anchor),
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_anchor: cursor.anchor,
+                new_anchor,
+                old_sticky_column: cursor.sticky_column,
+                new_sticky_column: None,
             });
         }
 
-        // Use optimized bulk edit for multi-line comment toggle
-        let description = format!("{} lines", action_desc);
-        if let Some(bulk_edit) = self.apply_events_as_bulk_edit(events, description) {
+        if let Some(bulk_edit) = self.apply_events_as_bulk_edit(events, "Join lines".to_string()) {
             self.active_event_log_mut().append(bulk_edit);
         }
+    }
 
-        self.set_status_message(
-            t!(
-                "lines.action",
-                action = action_desc,
-                count = line_starts.len()
-            )
-            .to_string(),
-        );
+    /// Align the lines of the primary selection on `delimiter`
+    ///
+    /// Lines touched by the selection are aligned as a whole; a selection
+    /// ending at the start of a line doesn't include that line.
+    pub(super) fn align_selection(&mut self, delimiter: &str) {
+        if delimiter.is_empty() {
+            return;
+        }
+        let cursor = *self.active_cursors().primary();
+        let cursor_id = self.active_cursors().primary_id();
+        let Some(range) = cursor.selection_range() else {
+            self.set_status_message(t!("align.no_selection").to_string());
+            return;
+        };
+        let state = self.active_state_mut();
+        let buffer_len = state.buffer.len();
+
+        let start_line = state.buffer.get_line_number(range.start);
+        let mut end_line = state.buffer.get_line_number(range.end);
+        if end_line > start_line && state.buffer.line_start_offset(end_line) == Some(range.end) {
+            end_line -= 1;
+        }
+        let Some(start) = state.buffer.line_start_offset(start_line) else {
+            return;
+        };
+        let end = state
+            .buffer
+            .line_start_offset(end_line + 1)
+            .unwrap_or(buffer_len);
+        let text = state.get_text_range(start, end);
+        let mut offset = start;
+        let lines: Vec<(usize, &str)> = text
+            .split_inclusive('\n')
+            .map(|line| {
+                let entry = (offset, line.trim_end_matches(['\n', '\r']));
+                offset += line.len();
+                entry
+            })
+            .collect();
+
+        let edits = align_edits(&lines, delimiter);
+        if edits.is_empty() {
+            return;
+        }
+        let changed_lines = lines
+            .iter()
+            .filter(|(line_start, line)| {
+                edits.iter().any(|edit| {
+                    edit.range.start >= *line_start && edit.range.end <= line_start + line.len()
+                })
+            })
+            .count();
+
+        // Edit events don't move the cursor; the MoveCursor below keeps the
+        // selection on the same text
+        let mut events = Vec::new();
+        for edit in edits.iter().rev() {
+            if !edit.range.is_empty() {
+                events.push(Event::Delete {
+                    range: edit.range.clone(),
+                    deleted_text: state.get_text_range(edit.range.start, edit.range.end),
+                    cursor_id: CursorId::UNDO_SENTINEL,
+                });
+            }
+            if !edit.text.is_empty() {
+                events.push(Event::Insert {
+                    position: edit.range.start,
+                    text: edit.text.clone(),
+                    cursor_id: CursorId::UNDO_SENTINEL,
+                });
+            }
+        }
+        let map = |pos: usize| -> usize {
+            let shift: isize = edits
+                .iter()
+                .filter(|edit| edit.range.end <= pos)
+                .map(|edit| edit.text.len() as isize - edit.range.len() as isize)
+                .sum();
+            (pos as isize + shift) as usize
+        };
+        events.push(Event::MoveCursor {
+            cursor_id,
+            old_position: cursor.position,
+            new_position: map(cursor.position),
+            old_anchor: cursor.anchor,
+            new_anchor: cursor.anchor.map(map),
+            old_sticky_column: cursor.sticky_column,
+            new_sticky_column: None,
+        });
+
+        if let Some(bulk_edit) = self.apply_events_as_bulk_edit(events, "Align".to_string()) {
+            self.active_event_log_mut().append(bulk_edit);
+        }
+        self.set_status_message(t!("align.done", count = changed_lines).to_string());
     }
 
     /// Go to matching bracket
@@ -3815,7 +4177,7 @@ impl Editor {
                 old_anchor: cursor.anchor,
                 new_anchor: None,
                 old_sticky_column: cursor.sticky_column,
-                new_sticky_column: 0,
+                new_sticky_column: None,
             };
             self.active_event_log_mut().append(event.clone());
             self.apply_event_to_active_buffer(&event);
@@ -3824,6 +4186,74 @@ impl Editor {
         }
     }
 
+    /// Select from the bracket at (or just before) the cursor to its match,
+    /// both brackets included. When the buffer has syntax highlighting,
+    /// brackets inside strings and comments are skipped.
+    pub(super) fn select_to_matching_bracket(&mut self) {
+        use crate::primitives::bracket_match::{match_bracket_at, BracketMatch};
+
+        // How far either side of the cursor to look for the partner bracket
+        const SEARCH_BYTES: usize = 64 * 1024;
+
+        let cursor = *self.active_cursors().primary();
+        let cursor_id = self.active_cursors().primary_id();
+        let buffer_id = self.active_buffer();
+        let context_bytes = self.config.editor.highlight_context_bytes;
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+
+        let buffer_len = state.buffer.len();
+        let pos = cursor.position.min(buffer_len);
+        let start = pos.saturating_sub(SEARCH_BYTES);
+        let end = (pos + SEARCH_BYTES).min(buffer_len);
+        let bytes = state.buffer.slice_bytes(start..end);
+
+        // The window may cut characters at either end; keep whole ones only
+        let lead = bytes.iter().take_while(|&&b| (b & 0xC0) == 0x80).count();
+        let text = match std::str::from_utf8(&bytes[lead..]) {
+            Ok(text) => text,
+            Err(e) => std::str::from_utf8(&bytes[lead..lead + e.valid_up_to()]).unwrap_or_default(),
+        };
+        let base = start + lead;
+
+        let quoted: Vec<_> = state
+            .highlighter
+            .highlight_categories(&state.buffer, base, base + text.len(), context_bytes)
+            .into_iter()
+            .filter(|(_, category)| {
+                matches!(
+                    category,
+                    HighlightCategory::String | HighlightCategory::Comment
+                )
+            })
+            .map(|(range, _)| range)
+            .collect();
+        let skip = |offset: usize| quoted.iter().any(|range| range.contains(&(base + offset)));
+
+        match match_bracket_at(text, pos.saturating_sub(base), skip) {
+            BracketMatch::Matched(range) => {
+                let event = Event::MoveCursor {
+                    cursor_id,
+                    old_position: cursor.position,
+                    new_position: base + range.end,
+                    old_anchor: cursor.anchor,
+                    new_anchor: Some(base + range.start),
+                    old_sticky_column: cursor.sticky_column,
+                    new_sticky_column: None,
+                };
+                self.active_event_log_mut().append(event.clone());
+                self.apply_event_to_active_buffer(&event);
+            }
+            BracketMatch::Unmatched => {
+                self.set_status_message(t!("diagnostics.bracket_no_match").to_string());
+            }
+            BracketMatch::NoBracket => {
+                self.set_status_message(t!("diagnostics.bracket_none").to_string());
+            }
+        }
+    }
+
     /// Jump to next error/diagnostic
     pub(super) fn jump_to_next_error(&mut self) {
         let diagnostic_ns = self.lsp_diagnostic_namespace.clone();
@@ -3871,7 +4301,7 @@ impl Editor {
                 old_anchor: cursor.anchor,
                 new_anchor: None,
                 old_sticky_column: cursor.sticky_column,
-                new_sticky_column: 0,
+                new_sticky_column: None,
             };
             self.active_event_log_mut().append(event.clone());
             self.apply_event_to_active_buffer(&event);
@@ -3939,7 +4369,7 @@ impl Editor {
                 old_anchor: cursor.anchor,
                 new_anchor: None,
                 old_sticky_column: cursor.sticky_column,
-                new_sticky_column: 0,
+                new_sticky_column: None,
             };
             self.active_event_log_mut().append(event.clone());
             self.apply_event_to_active_buffer(&event);
@@ -4124,12 +4554,18 @@ impl Editor {
     }
 
     /// Play back a recorded macro synchronously.
+    pub(super) fn play_macro(&mut self, key: char) {
+        self.play_macro_times(key, 1);
+    }
+
+    /// Play back a recorded macro `times` times in a row.
     ///
     /// All actions are executed in a tight loop. Between each action,
     /// `recompute_layout` is called so that visual-line movements
     /// (MoveLineEnd, etc.) see correct, up-to-date layout information.
-    /// Drawing is deferred until the next render cycle.
-    pub(super) fn play_macro(&mut self, key: char) {
+    /// Drawing is deferred until the next render cycle. Each replay is
+    /// undone as a single step.
+    pub(super) fn play_macro_times(&mut self, key: char, times: usize) {
         // Prevent recursive macro playback
         if self.macro_playing {
             return;
@@ -4145,17 +4581,41 @@ impl Editor {
             let action_count = actions.len();
             let width = self.cached_layout.last_frame_width;
             let height = self.cached_layout.last_frame_height;
-            for action in actions {
-                if let Err(e) = self.handle_action(action) {
-                    tracing::warn!("Macro action failed: {}", e);
+            for _ in 0..times {
+                // A replay may switch buffers, so group the edits in every log
+                let starts: Vec<(BufferId, usize)> = self
+                    .event_logs
+                    .iter_mut()
+                    .map(|(&id, log)| {
+                        log.break_undo_group();
+                        (id, log.current_index())
+                    })
+                    .collect();
+                for action in actions.iter().cloned() {
+                    if let Err(e) = self.handle_action(action) {
+                        tracing::warn!("Macro action failed: {}", e);
+                    }
+                    self.recompute_layout(width, height);
+                }
+                for (id, start) in starts {
+                    if let Some(log) = self.event_logs.get_mut(&id) {
+                        log.join_undo_steps_since(start);
+                    }
                 }
-                self.recompute_layout(width, height);
             }
             self.macro_playing = false;
 
-            self.set_status_message(
-                t!("macro.played", key = key, count = action_count).to_string(),
-            );
+            let message = if times == 1 {
+                t!("macro.played", key = key, count = action_count)
+            } else {
+                t!(
+                    "macro.played_times",
+                    key = key,
+                    times = times,
+                    count = action_count
+                )
+            };
+            self.set_status_message(message.to_string());
         } else {
             self.set_status_message(t!("macro.not_found", key = key).to_string());
         }
@@ -4178,7 +4638,8 @@ impl Editor {
                 | Action::ListMacros
                 | Action::PromptRecordMacro
                 | Action::PromptPlayMacro
-                | Action::PlayLastMacro => {}
+                | Action::PlayLastMacro
+                | Action::PromptRepeatLastMacro => {}
                 // When recording PromptConfirm, capture the current prompt text
                 // so it can be replayed correctly
                 Action::PromptConfirm => {
@@ -4265,6 +4726,9 @@ impl Editor {
             lsp_opened_with: std::collections::HashSet::new(),
             hidden_from_tabs: false,
             recovery_id: None,
+            recovery_exempt: false,
+            deleted_on_disk: false,
+            write_protected: false,
         };
         self.buffer_metadata.insert(buffer_id, metadata);
 
@@ -4340,6 +4804,9 @@ impl Editor {
             lsp_opened_with: std::collections::HashSet::new(),
             hidden_from_tabs: false,
             recovery_id: None,
+            recovery_exempt: false,
+            deleted_on_disk: false,
+            write_protected: false,
         };
         self.buffer_metadata.insert(buffer_id, metadata);
 
@@ -4389,7 +4856,7 @@ impl Editor {
                 old_anchor: cursor.anchor,
                 new_anchor: None,
                 old_sticky_column: cursor.sticky_column,
-                new_sticky_column: 0,
+                new_sticky_column: None,
             };
 
             self.active_event_log_mut().append(event.clone());
@@ -4435,6 +4902,123 @@ impl Editor {
         self.set_status_message(t!("bookmark.list", list = list_str).to_string());
     }
 
+    /// Gutter marker of a line bookmark
+    pub(super) fn line_bookmark_indicator(&self) -> crate::view::margin::LineIndicator {
+        crate::view::margin::LineIndicator::new("◆", self.theme.diagnostic_info_fg, 15)
+    }
+
+    /// Toggle a line bookmark on the primary cursor's line
+    ///
+    /// The bookmark is anchored at the start of the line, so it follows the
+    /// line as text is inserted or deleted above it.
+    pub(super) fn toggle_line_bookmark(&mut self) {
+        let position = self.active_cursors().primary().position;
+        let indicator = self.line_bookmark_indicator();
+        let state = self.active_state_mut();
+        let line = state.buffer.get_line_number(position);
+        let existing = state
+            .margins
+            .line_indicator_positions(LINE_BOOKMARK_NAMESPACE)
+            .into_iter()
+            .find(|&(_, pos)| state.buffer.get_line_number(pos) == line);
+
+        if let Some((marker_id, _)) = existing {
+            state
+                .margins
+                .remove_line_indicator(marker_id, LINE_BOOKMARK_NAMESPACE);
+            self.set_status_message(t!("bookmark.line_removed", line = line + 1).to_string());
+        } else {
+            let line_start = state.buffer.line_start_offset(line).unwrap_or(position);
+            state.margins.set_line_indicator(
+                line_start,
+                LINE_BOOKMARK_NAMESPACE.to_string(),
+                indicator,
+            );
+            self.set_status_message(t!("bookmark.line_added", line = line + 1).to_string());
+        }
+    }
+
+    /// Move to the next (or previous) line bookmark, going through the open
+    /// buffers in tab order and wrapping around
+    pub(super) fn goto_line_bookmark(&mut self, forward: bool) {
+        let active_buffer = self.active_buffer();
+        let mut buffer_order = self
+            .split_view_states
+            .get(&self.split_manager.active_split())
+            .map(|vs| vs.open_buffers.clone())
+            .unwrap_or_default();
+        let mut other_buffers: Vec<BufferId> = self
+            .buffers
+            .keys()
+            .filter(|id| !buffer_order.contains(id))
+            .copied()
+            .collect();
+        other_buffers.sort_by_key(|id| id.0);
+        buffer_order.extend(other_buffers);
+
+        // (tab index, line, buffer, position) of every bookmark, in order
+        let bookmarks: Vec<(usize, usize, BufferId, usize)> = buffer_order
+            .iter()
+            .enumerate()
+            .filter_map(|(index, id)| self.buffers.get(id).map(|state| (index, *id, state)))
+            .flat_map(|(index, id, state)| {
+                state
+                    .margins
+                    .line_indicator_positions(LINE_BOOKMARK_NAMESPACE)
+                    .into_iter()
+                    .map(move |(_, pos)| (index, state.buffer.get_line_number(pos), id, pos))
+            })
+            .collect();
+        if bookmarks.is_empty() {
+            self.set_status_message(t!("bookmark.no_line_bookmarks").to_string());
+            return;
+        }
+
+        let current_index = buffer_order
+            .iter()
+            .position(|&id| id == active_buffer)
+            .unwrap_or(0);
+        let current_line = self
+            .active_state()
+            .buffer
+            .get_line_number(self.active_cursors().primary().position);
+        let current = (current_index, current_line);
+        let target = if forward {
+            bookmarks
+                .iter()
+                .find(|&&(index, line, ..)| (index, line) > current)
+                .or(bookmarks.first())
+        } else {
+            bookmarks
+                .iter()
+                .rev()
+                .find(|&&(index, line, ..)| (index, line) < current)
+                .or(bookmarks.last())
+        };
+        let Some(&(_, line, buffer_id, position)) = target else {
+            return;
+        };
+
+        self.record_position_in_history();
+        if buffer_id != active_buffer {
+            self.set_active_buffer(buffer_id);
+        }
+        let cursor = *self.active_cursors().primary();
+        let cursor_id = self.active_cursors().primary_id();
+        let event = Event::MoveCursor {
+            cursor_id,
+            old_position: cursor.position,
+            new_position: position,
+            old_anchor: cursor.anchor,
+            new_anchor: None,
+            old_sticky_column: cursor.sticky_column,
+            new_sticky_column: None,
+        };
+        self.active_event_log_mut().append(event.clone());
+        self.apply_event_to_active_buffer(&event);
+        self.set_status_message(t!("bookmark.line_jumped", line = line + 1).to_string());
+    }
+
     /// Clear the search history
     /// Used primarily for testing to ensure test isolation
     pub fn clear_search_history(&mut self) {
@@ -157,63 +157,11 @@ impl Editor {
         }
     }
 
-    /// Adjust cursors in other splits that share the same buffer after an edit
+    /// Adjust cursors and scroll positions in other splits that share the same
+    /// buffer after an edit, so their viewports keep showing the same text
     pub(crate) fn adjust_other_split_cursors_for_event(&mut self, event: &Event) {
-        // Handle BulkEdit - cursors are managed by the event
-        if let Event::BulkEdit { new_cursors, .. } = event {
-            // Get the current buffer and split
-            let current_buffer_id = self.active_buffer();
-            let current_split_id = self.split_manager.active_split();
-
-            // Find all other splits that share the same buffer
-            let splits_for_buffer = self.split_manager.splits_for_buffer(current_buffer_id);
-
-            // Get buffer length to clamp cursor positions
-            let buffer_len = self
-                .buffers
-                .get(&current_buffer_id)
-                .map(|s| s.buffer.len())
-                .unwrap_or(0);
-
-            // Reset cursors in each other split to primary cursor position
-            for split_id in splits_for_buffer {
-                if split_id == current_split_id {
-                    continue;
-                }
-
-                if let Some(view_state) = self.split_view_states.get_mut(&split_id) {
-                    // Use the primary cursor position from the event
-                    if let Some((_, pos, _)) = new_cursors.first() {
-                        let new_pos = (*pos).min(buffer_len);
-                        view_state.cursors.primary_mut().position = new_pos;
-                        view_state.cursors.primary_mut().anchor = None;
-                    }
-                }
-            }
-            return;
-        }
-
         // Find the edit parameters from the event
-        let adjustments = match event {
-            Event::Insert { position, text, .. } => {
-                vec![(*position, 0, text.len())]
-            }
-            Event::Delete { range, .. } => {
-                vec![(range.start, range.len(), 0)]
-            }
-            Event::Batch { events, .. } => {
-                // Collect all edits from the batch
-                events
-                    .iter()
-                    .filter_map(|e| match e {
-                        Event::Insert { position, text, .. } => Some((*position, 0, text.len())),
-                        Event::Delete { range, .. } => Some((range.start, range.len(), 0)),
-                        _ => None,
-                    })
-                    .collect()
-            }
-            _ => vec![],
-        };
+        let adjustments = event.edit_lengths();
 
         if adjustments.is_empty() {
             return;
@@ -237,6 +185,23 @@ impl Editor {
                     view_state
                         .cursors
                         .adjust_for_edit(*edit_pos, *old_len, *new_len);
+
+                    // Edits above the top line shift it; a deletion that swallows
+                    // the top line leaves the viewport on the line it ends on
+                    let top_byte = view_state.viewport.top_byte;
+                    if *edit_pos < top_byte {
+                        view_state.viewport.top_byte = if top_byte >= edit_pos + old_len {
+                            top_byte - old_len + new_len
+                        } else {
+                            self.buffers
+                                .get(&current_buffer_id)
+                                .and_then(|state| {
+                                    let line = state.buffer.get_line_number(*edit_pos);
+                                    state.buffer.line_start_offset(line)
+                                })
+                                .unwrap_or(*edit_pos)
+                        };
+                    }
                 }
             }
         }
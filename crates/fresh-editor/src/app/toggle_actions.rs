@@ -112,6 +112,58 @@ impl Editor {
         self.set_status_message(status.to_string());
     }
 
+    /// Toggle highlighting of trailing whitespace and mixed indentation
+    pub fn toggle_whitespace_warnings(&mut self) {
+        self.config.editor.show_whitespace_warnings = !self.config.editor.show_whitespace_warnings;
+        let status = if self.config.editor.show_whitespace_warnings {
+            t!("toggle.whitespace_warnings_shown")
+        } else {
+            t!("toggle.whitespace_warnings_hidden")
+        };
+        self.set_status_message(status.to_string());
+    }
+
+    /// Toggle drawing glyphs for spaces, tabs and line endings
+    pub fn toggle_show_whitespace(&mut self) {
+        self.config.editor.show_whitespace = !self.config.editor.show_whitespace;
+        let status = if self.config.editor.show_whitespace {
+            t!("toggle.whitespace_shown")
+        } else {
+            t!("toggle.whitespace_hidden")
+        };
+        self.set_status_message(status.to_string());
+    }
+
+    /// Toggle trimming trailing whitespace on save for the active buffer
+    pub fn toggle_trim_whitespace_on_save(&mut self) {
+        let default = self.config.editor.trim_trailing_whitespace_on_save;
+        let settings = &mut self.active_state_mut().buffer_settings;
+        let enabled = !settings.trim_trailing_whitespace_on_save.unwrap_or(default);
+        settings.trim_trailing_whitespace_on_save = Some(enabled);
+
+        let status = if enabled {
+            t!("toggle.trim_whitespace_on_save_enabled")
+        } else {
+            t!("toggle.trim_whitespace_on_save_disabled")
+        };
+        self.set_status_message(status.to_string());
+    }
+
+    /// Toggle ensuring a final newline on save for the active buffer
+    pub fn toggle_final_newline_on_save(&mut self) {
+        let default = self.config.editor.ensure_final_newline_on_save;
+        let settings = &mut self.active_state_mut().buffer_settings;
+        let enabled = !settings.ensure_final_newline_on_save.unwrap_or(default);
+        settings.ensure_final_newline_on_save = Some(enabled);
+
+        let status = if enabled {
+            t!("toggle.final_newline_on_save_enabled")
+        } else {
+            t!("toggle.final_newline_on_save_disabled")
+        };
+        self.set_status_message(status.to_string());
+    }
+
     /// Reset buffer settings (tab_size, use_tabs, show_whitespace_tabs) to config defaults
     pub fn reset_buffer_settings(&mut self) {
         let buffer_id = self.active_buffer();
@@ -138,6 +190,8 @@ impl Editor {
             state.buffer_settings.tab_size = tab_size;
             state.buffer_settings.use_tabs = use_tabs;
             state.buffer_settings.show_whitespace_tabs = show_whitespace_tabs;
+            state.buffer_settings.trim_trailing_whitespace_on_save = None;
+            state.buffer_settings.ensure_final_newline_on_save = None;
         }
 
         self.set_status_message(t!("toggle.buffer_settings_reset").to_string());
@@ -10,6 +10,9 @@ use std::path::{Path, PathBuf};
 
 pub const DEFAULT_BACKGROUND_FILE: &str = "scripts/landscape-wide.txt";
 
+/// Margin namespace of the line bookmarks
+pub const LINE_BOOKMARK_NAMESPACE: &str = "bookmarks";
+
 /// Pre-calculated line information for an event
 /// Calculated BEFORE buffer modification so line numbers are accurate
 #[derive(Debug, Clone, Default)]
@@ -120,6 +123,19 @@ pub struct BufferMetadata {
     /// For file-backed buffers, recovery ID is computed from the path hash.
     /// For unnamed buffers, this is generated once and reused across auto-saves.
     pub recovery_id: Option<String>,
+
+    /// Whether this buffer is excluded from auto-recovery-save
+    /// (scratch buffers, generated output, huge logs)
+    pub recovery_exempt: bool,
+
+    /// Whether the buffer's file was deleted or moved away on disk; the next
+    /// save recreates it
+    pub deleted_on_disk: bool,
+
+    /// Whether the current user can't write the buffer's file, as detected
+    /// when it was opened. Editing is blocked until Edit Anyway, and saves go
+    /// through sudo.
+    pub write_protected: bool,
 }
 
 impl BufferMetadata {
@@ -175,6 +191,9 @@ impl BufferMetadata {
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: false,
             recovery_id: None,
+            recovery_exempt: false,
+            deleted_on_disk: false,
+            write_protected: false,
         }
     }
 
@@ -194,6 +213,9 @@ impl BufferMetadata {
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: false,
             recovery_id: None,
+            recovery_exempt: false,
+            deleted_on_disk: false,
+            write_protected: false,
         }
     }
 
@@ -232,6 +254,9 @@ impl BufferMetadata {
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: false,
             recovery_id: None,
+            recovery_exempt: false,
+            deleted_on_disk: false,
+            write_protected: false,
         }
     }
 
@@ -332,6 +357,9 @@ impl BufferMetadata {
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: false,
             recovery_id: None,
+            recovery_exempt: false,
+            deleted_on_disk: false,
+            write_protected: false,
         }
     }
 
@@ -349,6 +377,9 @@ impl BufferMetadata {
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: true,
             recovery_id: None,
+            recovery_exempt: false,
+            deleted_on_disk: false,
+            write_protected: false,
         }
     }
 
@@ -652,6 +683,13 @@ pub(super) struct MouseState {
     pub drag_selection_split: Option<LeafId>,
     /// The buffer byte position where the selection anchor is
     pub drag_selection_anchor: Option<usize>,
+    /// Block selection anchor (line, visual column) when dragging with Alt held
+    pub drag_block_anchor: Option<crate::model::cursor::Position2D>,
+    /// Mouse position while a text selection drag is held above or below
+    /// the split, which keeps the view scrolling toward it
+    pub drag_autoscroll_position: Option<(u16, u16)>,
+    /// When the view last scrolled for `drag_autoscroll_position`
+    pub drag_autoscroll_last_step: Option<std::time::Instant>,
     /// Tab drag state (for drag-to-split functionality)
     pub dragging_tab: Option<TabDragState>,
     /// Whether we're currently dragging a popup scrollbar (popup index)
@@ -746,6 +784,9 @@ pub(crate) struct CachedLayout {
     /// Horizontal scrollbar areas per split
     /// (split_id, buffer_id, horizontal_scrollbar_rect, max_content_width, thumb_start_col, thumb_end_col)
     pub horizontal_scrollbar_areas: Vec<(LeafId, BufferId, Rect, usize, usize, usize)>,
+    /// Vertical scrollbar markers per split for changed lines and search matches
+    /// (scrollbar_row, line_start_byte)
+    pub scrollbar_markers: HashMap<LeafId, Vec<(usize, usize)>>,
     /// Split separator positions for drag resize
     /// (container_id, direction, x, y, length)
     pub separator_areas: Vec<(ContainerId, SplitDirection, u16, u16, u16)>,
@@ -1,13 +1,31 @@
 //! Undo and redo action handlers.
 
+use std::time::Duration;
+
 use super::Editor;
-use rust_i18n::t;
+use crate::model::event::Event;
 
 impl Editor {
+    /// Log and apply an edit made by typing, so it's undone together with
+    /// the typing right before it (see `EventLog::append_typing`)
+    pub(super) fn apply_typing_event(&mut self, event: Event) {
+        let now = self.time_source.now();
+        let window = Duration::from_millis(self.config.editor.undo_group_timeout_ms);
+        self.active_event_log_mut()
+            .append_typing(event.clone(), now, window);
+        self.apply_event_to_active_buffer(&event);
+    }
+
+    /// Start a new undo step, so the next edit isn't undone together with
+    /// the typing before it
+    pub fn break_undo_group(&mut self) {
+        self.active_event_log_mut().break_undo_group();
+    }
+
     /// Handle Undo action - revert the last edit operation.
     pub fn handle_undo(&mut self) {
         if self.is_editing_disabled() {
-            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            self.set_status_message(self.editing_disabled_message());
             return;
         }
 
@@ -38,7 +56,7 @@ impl Editor {
     /// Handle Redo action - reapply an undone edit operation.
     pub fn handle_redo(&mut self) {
         if self.is_editing_disabled() {
-            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            self.set_status_message(self.editing_disabled_message());
             return;
         }
 
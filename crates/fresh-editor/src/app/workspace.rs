@@ -22,12 +22,13 @@
 //!
 //! Performance: O(1) ≈ 10ms (lazy load) vs O(n) ≈ 1000ms (log replay)
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{hash_map::Entry, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 use crate::state::EditorState;
 
+use crate::model::buffer::Buffer;
 use crate::model::event::{BufferId, LeafId, SplitDirection, SplitId};
 use crate::services::terminal::TerminalId;
 use crate::state::ViewMode;
@@ -36,10 +37,12 @@ use crate::workspace::{
     FileExplorerState, PersistedFileWorkspace, SearchOptions, SerializedBookmark, SerializedCursor,
     SerializedFileState, SerializedScroll, SerializedSplitDirection, SerializedSplitNode,
     SerializedSplitViewState, SerializedTabRef, SerializedTerminalWorkspace, SerializedViewMode,
-    Workspace, WorkspaceConfigOverrides, WorkspaceError, WorkspaceHistories, WORKSPACE_VERSION,
+    Workspace, WorkspaceConfigOverrides, WorkspaceError, WorkspaceHistories, WorkspaceSummary,
+    WORKSPACE_VERSION,
 };
+use rust_i18n::t;
 
-use super::types::Bookmark;
+use super::types::{Bookmark, LINE_BOOKMARK_NAMESPACE};
 use super::Editor;
 
 /// Workspace persistence state tracker
@@ -256,12 +259,15 @@ impl Editor {
             case_sensitive: self.search_case_sensitive,
             whole_word: self.search_whole_word,
             use_regex: self.search_use_regex,
+            smart_case: self.search_smart_case,
             confirm_each: self.search_confirm_each,
         };
 
         // Capture bookmarks
         let bookmarks =
             serialize_bookmarks(&self.bookmarks, &self.buffer_metadata, &self.working_dir);
+        let line_bookmarks =
+            serialize_line_bookmarks(&self.buffers, &self.buffer_metadata, &self.working_dir);
 
         // Capture external files (files outside working_dir)
         // These are stored as absolute paths since they can't be made relative
@@ -278,6 +284,7 @@ impl Editor {
 
         Workspace {
             version: WORKSPACE_VERSION,
+            name: None,
             working_dir: self.working_dir.clone(),
             split_layout,
             active_split_id: SplitId::from(self.split_manager.active_split()).0,
@@ -287,6 +294,7 @@ impl Editor {
             histories,
             search_options,
             bookmarks,
+            line_bookmarks,
             terminals,
             external_files,
             saved_at: std::time::SystemTime::now()
@@ -312,6 +320,146 @@ impl Editor {
         workspace.save()
     }
 
+    /// Save the current state as a named session for this working directory
+    pub fn save_workspace_as(&mut self, name: &str) -> Result<(), WorkspaceError> {
+        self.sync_all_terminal_backing_files();
+        self.save_all_global_file_states();
+
+        let mut workspace = self.capture_workspace();
+        workspace.name = Some(name.to_string());
+        workspace.save()
+    }
+
+    /// List the named sessions saved for this working directory
+    pub fn list_workspaces(&self) -> Result<Vec<WorkspaceSummary>, WorkspaceError> {
+        Workspace::list_named(&self.working_dir)
+    }
+
+    /// Replace the open buffers and split layout with a named session
+    ///
+    /// Returns false if no session with that name exists. Open buffers are
+    /// closed without asking, so callers must confirm unsaved changes first.
+    pub fn restore_workspace(&mut self, name: &str) -> Result<bool, WorkspaceError> {
+        let Some(workspace) = Workspace::load_named(&self.working_dir, name)? else {
+            return Ok(false);
+        };
+        self.close_all_for_workspace_switch();
+        self.apply_workspace(&workspace)?;
+        Ok(true)
+    }
+
+    /// Collapse to a single split showing a fresh empty buffer, closing every
+    /// other split and buffer
+    fn close_all_for_workspace_switch(&mut self) {
+        let active_split = self.split_manager.active_split();
+        let other_splits: Vec<LeafId> = self
+            .split_manager
+            .root()
+            .get_leaves_with_rects(ratatui::layout::Rect::default())
+            .into_iter()
+            .map(|(leaf_id, _, _)| leaf_id)
+            .filter(|&leaf_id| leaf_id != active_split)
+            .collect();
+        for leaf_id in other_splits {
+            if let Err(e) = self.split_manager.close_split(leaf_id) {
+                tracing::warn!("Failed to close split during workspace switch: {}", e);
+                continue;
+            }
+            self.split_view_states.remove(&leaf_id);
+        }
+
+        let keep = self.new_buffer();
+        let to_close: Vec<BufferId> = self
+            .buffers
+            .keys()
+            .copied()
+            .filter(|&id| id != keep)
+            .collect();
+        for buffer_id in to_close {
+            if let Err(e) = self.force_close_buffer(buffer_id) {
+                tracing::warn!("Failed to close buffer during workspace switch: {}", e);
+            }
+        }
+        self.bookmarks
+            .retain(|_, bookmark| self.buffers.contains_key(&bookmark.buffer_id));
+    }
+
+    /// Handle the "Save Workspace As" prompt
+    pub(super) fn handle_save_workspace_as(&mut self, input: &str) {
+        let name = input.trim();
+        if name.is_empty() {
+            self.set_status_message(t!("workspace.name_empty").to_string());
+            return;
+        }
+        match self.save_workspace_as(name) {
+            Ok(()) => self.set_status_message(t!("workspace.saved", name = name).to_string()),
+            Err(e) => self
+                .set_status_message(t!("workspace.save_failed", error = e.to_string()).to_string()),
+        }
+    }
+
+    /// Open the workspace switcher listing saved sessions
+    pub(super) fn start_switch_workspace_prompt(&mut self) {
+        let workspaces = match self.list_workspaces() {
+            Ok(workspaces) => workspaces,
+            Err(e) => {
+                self.set_status_message(
+                    t!("workspace.switch_failed", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+        };
+        if workspaces.is_empty() {
+            self.set_status_message(t!("workspace.none_saved").to_string());
+            return;
+        }
+
+        let suggestions = workspaces
+            .into_iter()
+            .map(|summary| {
+                let saved_at =
+                    std::time::UNIX_EPOCH + std::time::Duration::from_secs(summary.saved_at);
+                crate::input::commands::Suggestion {
+                    text: summary.name.clone(),
+                    description: Some(
+                        t!(
+                            "workspace.summary",
+                            count = summary.open_files,
+                            time = super::file_open::format_modified(saved_at)
+                        )
+                        .to_string(),
+                    ),
+                    value: Some(summary.name),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect();
+
+        self.prompt = Some(crate::view::prompt::Prompt::with_suggestions(
+            t!("workspace.switch_prompt").to_string(),
+            crate::view::prompt::PromptType::SwitchWorkspace,
+            suggestions,
+        ));
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+
+    /// Switch to a named session, reporting the outcome in the status bar
+    pub(super) fn switch_workspace(&mut self, name: &str) {
+        match self.restore_workspace(name) {
+            Ok(true) => self.set_status_message(t!("workspace.switched", name = name).to_string()),
+            Ok(false) => {
+                self.set_status_message(t!("workspace.not_found", name = name).to_string())
+            }
+            Err(e) => self.set_status_message(
+                t!("workspace.switch_failed", error = e.to_string()).to_string(),
+            ),
+        }
+    }
+
     /// Save global file states for all open file buffers
     fn save_all_global_file_states(&self) {
         // Collect all file states from all splits
@@ -464,6 +612,7 @@ impl Editor {
         self.search_case_sensitive = workspace.search_options.case_sensitive;
         self.search_whole_word = workspace.search_options.whole_word;
         self.search_use_regex = workspace.search_options.use_regex;
+        self.search_smart_case = workspace.search_options.smart_case;
         self.search_confirm_each = workspace.search_options.confirm_each;
 
         // 3. Restore histories (merge with any existing)
@@ -582,11 +731,21 @@ impl Editor {
         }
 
         // 6. Rebuild split layout from the saved tree
+        // A pane whose file no longer exists keeps its place in the layout
+        // with an empty placeholder buffer instead of borrowing another pane's
+        let mut pane_buffers = path_to_buffer.clone();
+        for rel_path in collect_leaf_file_paths(&workspace.split_layout) {
+            if let Entry::Vacant(entry) = pane_buffers.entry(rel_path) {
+                tracing::debug!("Using placeholder pane for missing file {:?}", entry.key());
+                entry.insert(self.create_placeholder_buffer());
+            }
+        }
+
         // Map old split IDs to new ones as we create splits
         let mut split_id_map: HashMap<usize, SplitId> = HashMap::new();
         self.restore_split_node(
             &workspace.split_layout,
-            &path_to_buffer,
+            &pane_buffers,
             &terminal_buffer_map,
             &workspace.split_states,
             &mut split_id_map,
@@ -618,6 +777,25 @@ impl Editor {
             }
         }
 
+        for bookmark in &workspace.line_bookmarks {
+            if let Some(&buffer_id) = path_to_buffer.get(&bookmark.file_path) {
+                let indicator = self.line_bookmark_indicator();
+                if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                    let pos = bookmark.position.min(state.buffer.len());
+                    state.margins.set_line_indicator(
+                        pos,
+                        LINE_BOOKMARK_NAMESPACE.to_string(),
+                        indicator,
+                    );
+                }
+            }
+        }
+
+        // 8. After a crash, offer recovered changes for the restored files
+        let mut restored_buffers: Vec<BufferId> = path_to_buffer.values().copied().collect();
+        restored_buffers.sort_by_key(|id| id.0);
+        self.queue_restore_recoveries(restored_buffers);
+
         tracing::debug!(
             "Workspace restore complete: {} splits, {} buffers",
             self.split_view_states.len(),
@@ -645,6 +823,33 @@ impl Editor {
         Ok(())
     }
 
+    /// Create an empty, unnamed buffer to stand in for a pane whose file is gone
+    ///
+    /// Unlike `new_buffer`, this does not switch to the buffer; the split
+    /// restore assigns it to its pane.
+    fn create_placeholder_buffer(&mut self) -> BufferId {
+        let buffer_id = BufferId(self.next_buffer_id);
+        self.next_buffer_id += 1;
+
+        let mut state = EditorState::new(
+            self.terminal_width,
+            self.terminal_height,
+            self.config.editor.large_file_threshold_bytes as usize,
+            std::sync::Arc::clone(&self.filesystem),
+        );
+        state
+            .margins
+            .configure_for_line_numbers(self.config.editor.line_numbers);
+        self.buffers.insert(buffer_id, state);
+        self.event_logs
+            .insert(buffer_id, crate::model::event::EventLog::new());
+        let mut metadata = super::types::BufferMetadata::new();
+        // Nothing worth recovering in an empty stand-in
+        metadata.recovery_exempt = true;
+        self.buffer_metadata.insert(buffer_id, metadata);
+        buffer_id
+    }
+
     /// Restore a terminal from serialized workspace metadata.
     ///
     /// Uses the incremental streaming architecture for fast restore:
@@ -1015,23 +1220,31 @@ impl Editor {
                 Some(id) => id,
                 None => continue,
             };
-            let max_pos = self
-                .buffers
-                .get(&buffer_id)
-                .map(|b| b.buffer.len())
-                .unwrap_or(0);
+            // The file may have changed on disk since the workspace was saved
+            let Some(buffer) = self.buffers.get_mut(&buffer_id).map(|b| &mut b.buffer) else {
+                continue;
+            };
+            let cursor_pos = clamp_saved_offset(buffer, file_state.cursor.position);
+            let anchor = file_state
+                .cursor
+                .anchor
+                .map(|a| clamp_saved_offset(buffer, a));
+            let top_byte = clamp_saved_top_byte(buffer, file_state.scroll.top_byte);
 
             // Ensure keyed state exists for this buffer
             let buf_state = view_state.ensure_buffer_state(buffer_id);
 
-            let cursor_pos = file_state.cursor.position.min(max_pos);
             buf_state.cursors.primary_mut().position = cursor_pos;
-            buf_state.cursors.primary_mut().anchor =
-                file_state.cursor.anchor.map(|a| a.min(max_pos));
+            buf_state.cursors.primary_mut().anchor = anchor;
             buf_state.cursors.primary_mut().sticky_column = file_state.cursor.sticky_column;
 
-            buf_state.viewport.top_byte = file_state.scroll.top_byte.min(max_pos);
-            buf_state.viewport.top_view_line_offset = file_state.scroll.top_view_line_offset;
+            buf_state.viewport.top_byte = top_byte;
+            // A wrapped-line offset only makes sense for the line it was saved on
+            buf_state.viewport.top_view_line_offset = if top_byte == file_state.scroll.top_byte {
+                file_state.scroll.top_view_line_offset
+            } else {
+                0
+            };
             buf_state.viewport.left_column = file_state.scroll.left_column;
             buf_state.viewport.set_skip_resize_sync();
 
@@ -1083,6 +1296,20 @@ impl Editor {
     }
 }
 
+/// Clamp a saved byte offset to a buffer whose file may have changed on disk
+///
+/// The offset is limited to the buffer length and snapped back to a character
+/// boundary, so a shrunk or rewritten file never gets a cursor mid-character.
+pub(super) fn clamp_saved_offset(buffer: &Buffer, offset: usize) -> usize {
+    buffer.snap_to_char_boundary(offset.min(buffer.len()))
+}
+
+/// Clamp a saved viewport top to the start of the line containing it
+pub(super) fn clamp_saved_top_byte(buffer: &mut Buffer, top_byte: usize) -> usize {
+    let top_byte = top_byte.min(buffer.len());
+    buffer.line_iterator(top_byte, 80).current_position()
+}
+
 /// Helper: Get the buffer ID from the first leaf node in a split tree
 fn get_first_leaf_buffer(
     node: &SerializedSplitNode,
@@ -1326,7 +1553,54 @@ fn serialize_bookmarks(
         .collect()
 }
 
+fn serialize_line_bookmarks(
+    buffers: &HashMap<BufferId, EditorState>,
+    buffer_metadata: &HashMap<BufferId, super::types::BufferMetadata>,
+    working_dir: &Path,
+) -> Vec<SerializedBookmark> {
+    let mut buffer_ids: Vec<BufferId> = buffers.keys().copied().collect();
+    buffer_ids.sort_by_key(|id| id.0);
+    buffer_ids
+        .into_iter()
+        .filter_map(|buffer_id| {
+            let rel_path = buffer_metadata
+                .get(&buffer_id)
+                .and_then(|meta| meta.file_path())
+                .and_then(|abs_path| abs_path.strip_prefix(working_dir).ok())?;
+            let state = buffers.get(&buffer_id)?;
+            Some(
+                state
+                    .margins
+                    .line_indicator_positions(LINE_BOOKMARK_NAMESPACE)
+                    .into_iter()
+                    .map(|(_, position)| SerializedBookmark {
+                        file_path: rel_path.to_path_buf(),
+                        position,
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .flatten()
+        .collect()
+}
+
+/// Collect the file shown in each leaf of a serialized split tree
+fn collect_leaf_file_paths(node: &SerializedSplitNode) -> Vec<PathBuf> {
+    match node {
+        SerializedSplitNode::Leaf { file_path, .. } => file_path.iter().cloned().collect(),
+        SerializedSplitNode::Terminal { .. } => Vec::new(),
+        SerializedSplitNode::Split { first, second, .. } => {
+            let mut paths = collect_leaf_file_paths(first);
+            paths.extend(collect_leaf_file_paths(second));
+            paths
+        }
+    }
+}
+
 /// Collect all unique file paths from split_states
+///
+/// Tabs are used when a split recorded them; older workspaces only list
+/// `open_files`.
 fn collect_file_paths_from_states(
     split_states: &HashMap<usize, SerializedSplitViewState>,
 ) -> Vec<PathBuf> {
@@ -495,6 +495,27 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Display"))]
     pub show_horizontal_scrollbar: bool,
 
+    /// Draw faint vertical guides at each indentation level of indented lines.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub show_indent_guides: bool,
+
+    /// Highlight trailing whitespace and indentation that mixes tabs and spaces.
+    /// Can be toggled at runtime via command palette.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub show_whitespace_warnings: bool,
+
+    /// Draw spaces as `·`, tabs as `→` and line endings as `↵` (LF) or `¶` (CRLF).
+    /// The glyphs are dimmed and only change what is drawn, not the buffer.
+    /// Can be toggled at runtime via command palette.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub show_whitespace: bool,
+
     /// Use the terminal's default background color instead of the theme's editor background.
     /// When enabled, the editor background inherits from the terminal emulator,
     /// allowing transparency or custom terminal backgrounds to show through.
@@ -529,6 +550,15 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Editing"))]
     pub auto_indent: bool,
 
+    /// Typing that continues where the last keystroke left off, within this
+    /// many milliseconds of it, is undone in one step. Newlines, saving,
+    /// cursor jumps and other commands start a new step.
+    /// Set to 0 to undo each typed character separately.
+    /// Default: 1000ms
+    #[serde(default = "default_undo_group_timeout")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub undo_group_timeout_ms: u64,
+
     /// Minimum lines to keep visible above/below cursor when scrolling
     #[serde(default = "default_scroll_offset")]
     #[schemars(extend("x-section" = "Editing"))]
@@ -549,11 +579,19 @@ pub struct EditorConfig {
     pub trim_trailing_whitespace_on_save: bool,
 
     /// Ensure files end with a newline when saving.
+    /// Empty files are left empty.
     /// Default: false
     #[serde(default = "default_false")]
     #[schemars(extend("x-section" = "Editing"))]
     pub ensure_final_newline_on_save: bool,
 
+    /// Copy the file on disk to a backup named `<file>~` before each save
+    /// replaces it. The backup keeps the original's permissions.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub backup_on_save: bool,
+
     // ===== Bracket Matching =====
     /// Highlight matching bracket pairs when cursor is on a bracket.
     /// Default: true
@@ -821,6 +859,10 @@ fn default_double_click_time() -> u64 {
     500 // 500ms window for detecting double-clicks
 }
 
+fn default_undo_group_timeout() -> u64 {
+    1000 // 1s between keystrokes still groups them for undo
+}
+
 fn default_auto_revert_poll_interval() -> u64 {
     2000 // 2 seconds between file mtime checks
 }
@@ -834,6 +876,7 @@ impl Default for EditorConfig {
         Self {
             tab_size: default_tab_size(),
             auto_indent: true,
+            undo_group_timeout_ms: default_undo_group_timeout(),
             line_numbers: true,
             relative_line_numbers: false,
             scroll_offset: default_scroll_offset(),
@@ -858,6 +901,7 @@ impl Default for EditorConfig {
             default_line_ending: LineEndingOption::default(),
             trim_trailing_whitespace_on_save: false,
             ensure_final_newline_on_save: false,
+            backup_on_save: false,
             highlight_matching_brackets: true,
             rainbow_brackets: true,
             cursor_style: CursorStyle::default(),
@@ -873,6 +917,9 @@ impl Default for EditorConfig {
             show_tab_bar: true,
             show_vertical_scrollbar: true,
             show_horizontal_scrollbar: false,
+            show_indent_guides: false,
+            show_whitespace_warnings: false,
+            show_whitespace: false,
             use_terminal_bg: false,
             rulers: Vec::new(),
         }
@@ -1150,6 +1197,10 @@ pub struct LanguageConfig {
     #[serde(default = "default_true")]
     pub auto_indent: bool,
 
+    /// Whether to auto-close brackets and quotes (requires `editor.auto_indent`)
+    #[serde(default = "default_true")]
+    pub auto_close: bool,
+
     /// Preferred highlighter backend (auto, tree-sitter, or textmate)
     #[serde(default)]
     pub highlighter: HighlighterPreference,
@@ -1618,6 +1669,13 @@ impl MenuConfig {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.edit.replace_in_selection").to_string(),
+                        action: "replace_in_selection".to_string(),
+                        args: HashMap::new(),
+                        when: Some(context_keys::HAS_SELECTION.to_string()),
+                        checkbox: None,
+                    },
                     MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: t!("menu.edit.delete_line").to_string(),
@@ -2290,6 +2348,7 @@ impl Config {
                 grammar: "rust".to_string(),
                 comment_prefix: Some("//".to_string()),
                 auto_indent: true,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2314,6 +2373,7 @@ impl Config {
                 grammar: "javascript".to_string(),
                 comment_prefix: Some("//".to_string()),
                 auto_indent: true,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2338,6 +2398,7 @@ impl Config {
                 grammar: "typescript".to_string(),
                 comment_prefix: Some("//".to_string()),
                 auto_indent: true,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2362,6 +2423,7 @@ impl Config {
                 grammar: "python".to_string(),
                 comment_prefix: Some("#".to_string()),
                 auto_indent: true,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2390,6 +2452,7 @@ impl Config {
                 grammar: "c".to_string(),
                 comment_prefix: Some("//".to_string()),
                 auto_indent: true,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2421,6 +2484,7 @@ impl Config {
                 grammar: "cpp".to_string(),
                 comment_prefix: Some("//".to_string()),
                 auto_indent: true,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2445,6 +2509,7 @@ impl Config {
                 grammar: "c_sharp".to_string(),
                 comment_prefix: Some("//".to_string()),
                 auto_indent: true,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2479,6 +2544,7 @@ impl Config {
                 grammar: "bash".to_string(),
                 comment_prefix: Some("#".to_string()),
                 auto_indent: true,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2502,6 +2568,7 @@ impl Config {
                 grammar: "make".to_string(),
                 comment_prefix: Some("#".to_string()),
                 auto_indent: false,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2521,6 +2588,7 @@ impl Config {
                 grammar: "dockerfile".to_string(),
                 comment_prefix: Some("#".to_string()),
                 auto_indent: true,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2540,6 +2608,7 @@ impl Config {
                 grammar: "json".to_string(),
                 comment_prefix: None,
                 auto_indent: true,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2564,6 +2633,7 @@ impl Config {
                 grammar: "toml".to_string(),
                 comment_prefix: Some("#".to_string()),
                 auto_indent: true,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2583,6 +2653,7 @@ impl Config {
                 grammar: "yaml".to_string(),
                 comment_prefix: Some("#".to_string()),
                 auto_indent: true,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2607,6 +2678,7 @@ impl Config {
                 grammar: "markdown".to_string(),
                 comment_prefix: None,
                 auto_indent: false,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2627,6 +2699,7 @@ impl Config {
                 grammar: "go".to_string(),
                 comment_prefix: Some("//".to_string()),
                 auto_indent: true,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: false,
@@ -2651,6 +2724,7 @@ impl Config {
                 grammar: "odin".to_string(),
                 comment_prefix: Some("//".to_string()),
                 auto_indent: true,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: false,
@@ -2670,6 +2744,7 @@ impl Config {
                 grammar: "zig".to_string(),
                 comment_prefix: Some("//".to_string()),
                 auto_indent: true,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2689,6 +2764,7 @@ impl Config {
                 grammar: "java".to_string(),
                 comment_prefix: Some("//".to_string()),
                 auto_indent: true,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2715,6 +2791,7 @@ impl Config {
                 grammar: "latex".to_string(),
                 comment_prefix: Some("%".to_string()),
                 auto_indent: true,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2734,6 +2811,7 @@ impl Config {
                 grammar: "go".to_string(), // Templ uses Go-like syntax
                 comment_prefix: Some("//".to_string()),
                 auto_indent: true,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2754,6 +2832,7 @@ impl Config {
                 grammar: "Git Rebase Todo".to_string(),
                 comment_prefix: Some("#".to_string()),
                 auto_indent: false,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2778,6 +2857,7 @@ impl Config {
                 grammar: "Git Commit Message".to_string(),
                 comment_prefix: Some("#".to_string()),
                 auto_indent: false,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2802,6 +2882,7 @@ impl Config {
                 grammar: "Gitignore".to_string(),
                 comment_prefix: Some("#".to_string()),
                 auto_indent: false,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2821,6 +2902,7 @@ impl Config {
                 grammar: "Git Config".to_string(),
                 comment_prefix: Some("#".to_string()),
                 auto_indent: true,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2840,6 +2922,7 @@ impl Config {
                 grammar: "Git Attributes".to_string(),
                 comment_prefix: Some("#".to_string()),
                 auto_indent: false,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2859,6 +2942,7 @@ impl Config {
                 grammar: "Typst".to_string(),
                 comment_prefix: Some("//".to_string()),
                 auto_indent: true,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -3534,6 +3618,7 @@ mod tests {
                 grammar: "go".to_string(),
                 comment_prefix: Some("//".to_string()),
                 auto_indent: true,
+                auto_close: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: false, // Go hides tab indicators
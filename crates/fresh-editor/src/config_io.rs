@@ -991,6 +991,11 @@ impl DirectoryContext {
         self.data_dir.join(format!("{}_history.json", safe_name))
     }
 
+    /// Get the command palette usage file path (recent and frequent commands)
+    pub fn command_usage_path(&self) -> std::path::PathBuf {
+        self.data_dir.join("command_usage.json")
+    }
+
     /// Get the search history file path (legacy, calls generic method)
     pub fn search_history_path(&self) -> std::path::PathBuf {
         self.prompt_history_path("search")
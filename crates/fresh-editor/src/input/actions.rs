@@ -5,6 +5,10 @@ use crate::input::line_move::{move_lines, LineMoveDirection};
 use crate::model::buffer::{Buffer, LineEnding};
 use crate::model::cursor::{Cursors, Position2D, SelectionMode};
 use crate::model::event::{CursorId, Event};
+use crate::primitives::block_selection::{
+    block_cursor_2d, block_line_range, block_rect, byte_to_visual_2d, step_visual_column,
+    virtual_space_padding, visual_2d_to_byte,
+};
 use crate::primitives::display_width::{byte_offset_at_visual_column, str_width};
 use crate::primitives::word_navigation::{
     find_word_end, find_word_end_right, find_word_start, find_word_start_left,
@@ -22,29 +26,6 @@ enum BlockDirection {
     Down,
 }
 
-/// Convert byte offset to 2D position (line, column)
-fn byte_to_2d(buffer: &Buffer, byte_pos: usize) -> Position2D {
-    let line = buffer.get_line_number(byte_pos);
-    let line_start = buffer.line_start_offset(line).unwrap_or(0);
-    let column = byte_pos.saturating_sub(line_start);
-    Position2D { line, column }
-}
-
-/// Convert 2D position to byte offset
-fn pos_2d_to_byte(buffer: &Buffer, pos: Position2D) -> usize {
-    let line_start = buffer.line_start_offset(pos.line).unwrap_or(0);
-    // Get line content to check bounds
-    let line_content = buffer.get_line(pos.line).unwrap_or_default();
-    // Clamp column to line length (excluding newline)
-    let line_len = if line_content.last() == Some(&b'\n') {
-        line_content.len().saturating_sub(1)
-    } else {
-        line_content.len()
-    };
-    let clamped_col = pos.column.min(line_len);
-    line_start + clamped_col
-}
-
 /// Calculate the visual column (display width) at the cursor position.
 /// Returns (visual_column, byte_column_within_line).
 fn calculate_visual_column(
@@ -153,6 +134,34 @@ fn collect_line_starts(
     line_starts
 }
 
+/// Expand a selection covering several lines to span them whole
+///
+/// Returns the start of the first line and the end (before the line ending)
+/// of the last line in `line_starts`. A selection within a single line is
+/// returned unchanged, along with `false`.
+fn expand_to_full_lines(
+    buffer: &mut Buffer,
+    start_pos: usize,
+    end_pos: usize,
+    line_starts: &[usize],
+    estimated_line_length: usize,
+) -> (usize, usize, bool) {
+    let (Some(&first), Some(&last)) = (line_starts.first(), line_starts.last()) else {
+        return (start_pos, end_pos, false);
+    };
+    if first == last {
+        return (start_pos, end_pos, false);
+    }
+    let mut iter = buffer.line_iterator(last, estimated_line_length);
+    let last_end = match iter.next_line() {
+        Some((line_start, line_content)) => {
+            line_start + content_len_without_line_ending(&line_content)
+        }
+        None => last,
+    };
+    (first, last_end, true)
+}
+
 /// Calculate how much leading whitespace to remove from a line for dedent
 ///
 /// Returns (chars_to_remove, deleted_text) where chars_to_remove is the number
@@ -185,7 +194,7 @@ fn add_move_cursor_event(
     new_position: usize,
     old_anchor: Option<usize>,
     new_anchor: Option<usize>,
-    old_sticky_column: usize,
+    old_sticky_column: Option<usize>,
 ) {
     events.push(Event::MoveCursor {
         cursor_id,
@@ -194,7 +203,7 @@ fn add_move_cursor_event(
         old_anchor,
         new_anchor,
         old_sticky_column,
-        new_sticky_column: 0,
+        new_sticky_column: None,
     });
 }
 
@@ -205,6 +214,7 @@ fn block_select_action(
     events: &mut Vec<Event>,
     direction: BlockDirection,
 ) {
+    let tab_size = state.buffer_settings.tab_size;
     // Get line count for bounds checking
     let total_lines = {
         let len = state.buffer.len();
@@ -216,35 +226,28 @@ fn block_select_action(
     };
 
     for (cursor_id, cursor) in cursors.iter() {
-        let current_2d = byte_to_2d(&state.buffer, cursor.position);
-
-        // If not in block mode, start block selection
-        let block_anchor =
-            if cursor.selection_mode != SelectionMode::Block || cursor.block_anchor.is_none() {
-                current_2d
-            } else {
-                cursor.block_anchor.unwrap()
-            };
+        // Block columns are visual; an existing block resumes from the
+        // cursor's sticky column, which may lie past the end of its line
+        let (block_anchor, current_2d) = match cursor.block_anchor {
+            Some(anchor) if cursor.selection_mode == SelectionMode::Block => {
+                (anchor, block_cursor_2d(&state.buffer, cursor, tab_size))
+            }
+            _ => {
+                let current_2d = byte_to_visual_2d(&state.buffer, cursor.position, tab_size);
+                (current_2d, current_2d)
+            }
+        };
 
         // Calculate new 2D position based on direction
         let new_2d = match direction {
             BlockDirection::Left => Position2D {
                 line: current_2d.line,
-                column: current_2d.column.saturating_sub(1),
+                column: step_visual_column(&state.buffer, current_2d, false, tab_size),
+            },
+            BlockDirection::Right => Position2D {
+                line: current_2d.line,
+                column: step_visual_column(&state.buffer, current_2d, true, tab_size),
             },
-            BlockDirection::Right => {
-                // Get current line length to bound the column
-                let line_content = state.buffer.get_line(current_2d.line).unwrap_or_default();
-                let line_len = if line_content.last() == Some(&b'\n') {
-                    line_content.len().saturating_sub(1)
-                } else {
-                    line_content.len()
-                };
-                Position2D {
-                    line: current_2d.line,
-                    column: (current_2d.column + 1).min(line_len),
-                }
-            }
             BlockDirection::Up => {
                 if current_2d.line > 0 {
                     Position2D {
@@ -268,10 +271,10 @@ fn block_select_action(
         };
 
         // Convert new 2D position back to byte offset
-        let new_byte_pos = pos_2d_to_byte(&state.buffer, new_2d);
+        let new_byte_pos = visual_2d_to_byte(&state.buffer, new_2d, tab_size);
 
         // Store the byte anchor for the event system (for undo/redo compatibility)
-        let byte_anchor = pos_2d_to_byte(&state.buffer, block_anchor);
+        let byte_anchor = visual_2d_to_byte(&state.buffer, block_anchor, tab_size);
 
         events.push(Event::MoveCursor {
             cursor_id,
@@ -280,7 +283,7 @@ fn block_select_action(
             old_anchor: cursor.anchor,
             new_anchor: Some(byte_anchor),
             old_sticky_column: cursor.sticky_column,
-            new_sticky_column: new_2d.column,
+            new_sticky_column: Some(new_2d.column),
         });
 
         // Note: We need to set block selection mode after the event is processed
@@ -294,7 +297,7 @@ fn block_select_action(
     let buffer_ref = &state.buffer;
     cursors.map(|cursor| {
         if cursor.selection_mode != SelectionMode::Block || cursor.block_anchor.is_none() {
-            let current_2d = byte_to_2d(buffer_ref, cursor.position);
+            let current_2d = byte_to_visual_2d(buffer_ref, cursor.position, tab_size);
             cursor.start_block_selection(current_2d.line, current_2d.column);
         }
     });
@@ -313,60 +316,40 @@ pub fn clear_block_selection_if_active(cursors: &mut Cursors) {
 /// Convert block selection to multiple cursors with normal selections.
 /// Each cursor will have a selection covering that line's portion of the block.
 /// This should be called before action processing so normal multi-cursor logic applies.
-/// Returns events to add the new cursors (if any).
+/// Returns events to add the new cursors (if any), and for each cursor left
+/// at the end of a line short of the block, the spaces that pad that line
+/// out to the block's left edge (see [`pad_virtual_space_inserts`]).
 fn convert_block_selection_to_cursors(
     state: &mut EditorState,
     cursors: &mut Cursors,
-) -> Vec<Event> {
+) -> (Vec<Event>, Vec<(CursorId, String)>) {
     let mut events = Vec::new();
+    let mut padding = Vec::new();
+    let tab_size = state.buffer_settings.tab_size;
 
     // Check if any cursor has a block selection
-    let block_info: Option<(CursorId, Position2D, Position2D)> =
-        cursors.iter().find_map(|(cursor_id, cursor)| {
-            if cursor.has_block_selection() {
-                let block_anchor = cursor.block_anchor?;
-                let cursor_2d = byte_to_2d(&state.buffer, cursor.position);
-                Some((cursor_id, block_anchor, cursor_2d))
-            } else {
-                None
-            }
-        });
+    let block_info = cursors.iter().find_map(|(cursor_id, cursor)| {
+        block_rect(&state.buffer, cursor, tab_size).map(|rect| (cursor_id, rect))
+    });
 
-    let Some((primary_cursor_id, block_anchor, cursor_2d)) = block_info else {
-        return events;
+    let Some((primary_cursor_id, (min_line, min_col, max_line, max_col))) = block_info else {
+        return (events, padding);
     };
-
-    // Calculate block rectangle bounds
-    let min_line = block_anchor.line.min(cursor_2d.line);
-    let max_line = block_anchor.line.max(cursor_2d.line);
-    let min_col = block_anchor.column.min(cursor_2d.column);
-    let max_col = block_anchor.column.max(cursor_2d.column);
-
-    // Calculate cursor positions for each line
-    let mut cursor_positions: Vec<(usize, usize)> = Vec::new(); // (position, anchor)
-
-    for line in min_line..=max_line {
-        let line_start = state.buffer.line_start_offset(line).unwrap_or(0);
-        let line_content = state.buffer.get_line(line).unwrap_or_default();
-
-        // Calculate line length excluding newline
-        let line_len = if line_content.last() == Some(&b'\n') {
-            line_content.len().saturating_sub(1)
-        } else {
-            line_content.len()
-        };
-
-        // Clamp columns to actual line length
-        let actual_min_col = min_col.min(line_len);
-        let actual_max_col = max_col.min(line_len);
-
-        let anchor = line_start + actual_min_col;
-        let position = line_start + actual_max_col;
-
-        cursor_positions.push((position, anchor));
-    }
+    let line_padding = |line| virtual_space_padding(&state.buffer, line, min_col, tab_size);
+
+    // Each line's portion of the block as (position, anchor); lines shorter
+    // than the block get a caret at their end
+    let cursor_positions: Vec<(usize, usize)> = (min_line..=max_line)
+        .map(|line| {
+            let range = block_line_range(&state.buffer, line, min_col, max_col, tab_size);
+            (range.end, range.start)
+        })
+        .collect();
 
     // Update the primary cursor to have a normal selection on the first line
+    if let Some((_, text)) = line_padding(min_line) {
+        padding.push((primary_cursor_id, text));
+    }
     if let Some((position, anchor)) = cursor_positions.first().copied() {
         if let Some(cursor) = cursors.get_mut(primary_cursor_id) {
             cursor.position = position;
@@ -381,9 +364,12 @@ fn convert_block_selection_to_cursors(
 
     // Add new cursors for remaining lines
     let mut next_cursor_id = cursors.count();
-    for (position, anchor) in cursor_positions.into_iter().skip(1) {
+    for (line, (position, anchor)) in (min_line..=max_line).zip(cursor_positions).skip(1) {
         let cursor_id = CursorId(next_cursor_id);
         next_cursor_id += 1;
+        if let Some((_, text)) = line_padding(line) {
+            padding.push((cursor_id, text));
+        }
 
         events.push(Event::AddCursor {
             cursor_id,
@@ -396,7 +382,43 @@ fn convert_block_selection_to_cursors(
         });
     }
 
-    events
+    (events, padding)
+}
+
+/// Prefix each padded cursor's inserted text with its padding
+///
+/// Typing into a block that starts past the end of a short line first fills
+/// the gap with spaces, so every row gets the text at the same column. The
+/// padding is part of the cursor's own insert, so the edit stays one step
+/// and the cursor ends up after the typed text.
+fn pad_virtual_space_inserts(events: &mut [Event], padding: &[(CursorId, String)]) {
+    let padding_for = |id: &CursorId| {
+        padding
+            .iter()
+            .find(|(cursor_id, _)| cursor_id == id)
+            .map(|(_, text)| text.as_str())
+    };
+    for event in events {
+        match event {
+            Event::Insert {
+                text, cursor_id, ..
+            } => {
+                if let Some(pad) = padding_for(cursor_id) {
+                    text.insert_str(0, pad);
+                }
+            }
+            Event::MoveCursor {
+                cursor_id,
+                new_position,
+                ..
+            } => {
+                if let Some(pad) = padding_for(cursor_id) {
+                    *new_position += pad.len();
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
 /// Get the matching close character for auto-pairing.
@@ -495,8 +517,8 @@ fn handle_skip_over_with_dedent(
             new_position: line_start + correct_indent + 1,
             old_anchor: None,
             new_anchor: None,
-            old_sticky_column: 0,
-            new_sticky_column: 0,
+            old_sticky_column: None,
+            new_sticky_column: None,
         });
         return true;
     }
@@ -511,8 +533,8 @@ fn handle_skip_over(events: &mut Vec<Event>, cursor_id: CursorId, insert_positio
         new_position: insert_position + 1,
         old_anchor: None,
         new_anchor: None,
-        old_sticky_column: 0,
-        new_sticky_column: 0,
+        old_sticky_column: None,
+        new_sticky_column: None,
     });
 }
 
@@ -580,8 +602,8 @@ fn handle_auto_close(
         new_position: insert_position + 1,
         old_anchor: None,
         new_anchor: None,
-        old_sticky_column: 0,
-        new_sticky_column: 0,
+        old_sticky_column: None,
+        new_sticky_column: None,
     });
 }
 
@@ -673,6 +695,7 @@ fn insert_char_events(
     ch: char,
     tab_size: usize,
     auto_indent: bool,
+    auto_close_at: &dyn Fn(usize) -> bool,
 ) {
     let is_closing_delimiter = matches!(ch, '}' | ')' | ']');
     let auto_close_char = get_auto_close_char(ch, auto_indent, &state.language);
@@ -735,7 +758,7 @@ fn insert_char_events(
 
         // Try auto-close
         if let Some(close_char) = auto_close_char {
-            if should_auto_close(data.char_after) {
+            if should_auto_close(data.char_after) && auto_close_at(data.insert_position) {
                 handle_auto_close(events, data.cursor_id, ch, close_char, data.insert_position);
                 continue;
             }
@@ -803,6 +826,156 @@ fn transform_case<F>(
     }
 }
 
+/// Ordering options for the sort-lines actions
+#[derive(Debug, Clone, Copy, Default)]
+struct SortLinesOptions {
+    descending: bool,
+    case_insensitive: bool,
+    numeric: bool,
+    unique: bool,
+}
+
+/// Parse the number a line starts with (ignoring leading whitespace)
+fn leading_number(line: &str) -> Option<f64> {
+    let trimmed = line.trim_start();
+    let bytes = trimmed.as_bytes();
+    let mut end = usize::from(matches!(bytes.first(), Some(b'-' | b'+')));
+    let digits_start = end;
+    while bytes.get(end).is_some_and(u8::is_ascii_digit) {
+        end += 1;
+    }
+    if end == digits_start {
+        return None;
+    }
+    if bytes.get(end) == Some(&b'.') && bytes.get(end + 1).is_some_and(u8::is_ascii_digit) {
+        end += 1;
+        while bytes.get(end).is_some_and(u8::is_ascii_digit) {
+            end += 1;
+        }
+    }
+    trimmed[..end].parse().ok()
+}
+
+/// Compare two lines according to the sort options
+///
+/// In numeric mode, lines starting with a number come first ordered by value;
+/// the remaining lines follow in lexicographic order.
+fn compare_lines(a: &str, b: &str, options: SortLinesOptions) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+    let ordering = if options.numeric {
+        match (leading_number(a), leading_number(b)) {
+            (Some(x), Some(y)) => x.total_cmp(&y),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => a.cmp(b),
+        }
+    } else if options.case_insensitive {
+        a.to_lowercase().cmp(&b.to_lowercase())
+    } else {
+        a.cmp(b)
+    };
+    if options.descending {
+        ordering.reverse()
+    } else {
+        ordering
+    }
+}
+
+/// Sort the lines covered by each selection, or the whole buffer if nothing is selected.
+///
+/// Selections are widened to whole lines, and selections on touching lines are
+/// sorted together as one region. The sort is stable, and the line ending after
+/// the last line of a region is left in place.
+fn sort_lines(
+    state: &mut EditorState,
+    cursors: &Cursors,
+    events: &mut Vec<Event>,
+    options: SortLinesOptions,
+) {
+    let buffer_len = state.buffer.len();
+    let line_span = |buffer: &Buffer, start: usize, end: usize| {
+        let start_line = buffer.get_line_number(start);
+        let mut end_line = buffer.get_line_number(end);
+        // A selection ending at the start of a line doesn't include that line
+        if end_line > start_line && buffer.line_start_offset(end_line) == Some(end) {
+            end_line -= 1;
+        }
+        (start_line, end_line)
+    };
+
+    let mut regions: Vec<(CursorId, usize, usize)> = cursors
+        .iter()
+        .filter_map(|(cursor_id, cursor)| {
+            let range = cursor.selection_range()?;
+            let (start_line, end_line) = line_span(&state.buffer, range.start, range.end);
+            Some((cursor_id, start_line, end_line))
+        })
+        .collect();
+    if regions.is_empty() {
+        let (start_line, end_line) = line_span(&state.buffer, 0, buffer_len);
+        regions.push((cursors.primary_id(), start_line, end_line));
+    }
+
+    // Merge regions on overlapping or adjacent lines
+    regions.sort_by_key(|&(_, start_line, _)| start_line);
+    let mut merged: Vec<(CursorId, usize, usize)> = Vec::with_capacity(regions.len());
+    for region in regions {
+        match merged.last_mut() {
+            Some(last) if region.1 <= last.2 + 1 => last.2 = last.2.max(region.2),
+            _ => merged.push(region),
+        }
+    }
+
+    let line_ending = state.buffer.line_ending().as_str();
+    // Process regions in reverse order to avoid position shifts
+    for (cursor_id, start_line, end_line) in merged.into_iter().rev() {
+        let Some(start) = state.buffer.line_start_offset(start_line) else {
+            continue;
+        };
+        let mut end = state
+            .buffer
+            .line_start_offset(end_line + 1)
+            .unwrap_or(buffer_len);
+        let mut text = state.get_text_range(start, end);
+        // Leave the final line ending in place
+        if text.ends_with('\n') {
+            text.pop();
+            end -= 1;
+            if text.ends_with('\r') {
+                text.pop();
+                end -= 1;
+            }
+        }
+
+        // Unlike str::lines, keep a blank last line as a line of its own
+        let mut lines: Vec<&str> = text
+            .split('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+            .collect();
+        if lines.len() < 2 {
+            continue;
+        }
+        lines.sort_by(|a, b| compare_lines(a, b, options));
+        if options.unique {
+            lines.dedup();
+        }
+        let sorted_text = lines.join(line_ending);
+
+        if sorted_text != text {
+            events.push(Event::Delete {
+                range: start..end,
+                deleted_text: text,
+                cursor_id,
+            });
+            events.push(Event::Insert {
+                position: start,
+                text: sorted_text,
+                cursor_id,
+            });
+        }
+    }
+}
+
 /// Convert an action into a sequence of events that can be applied to the editor state
 ///
 /// # Parameters
@@ -824,6 +997,34 @@ pub fn action_to_events(
     auto_indent: bool,
     estimated_line_length: usize,
     viewport_height: u16,
+) -> Option<Vec<Event>> {
+    action_to_events_with_auto_close(
+        state,
+        cursors,
+        action,
+        tab_size,
+        auto_indent,
+        estimated_line_length,
+        viewport_height,
+        &|_| true,
+    )
+}
+
+/// Like [`action_to_events`], with `auto_close_at(offset)` deciding whether
+/// a bracket or quote typed at `offset` gets its closing partner
+///
+/// The editor uses this to turn auto-closing off per language and inside
+/// strings and comments. It only applies when `auto_indent` is enabled.
+#[allow(clippy::too_many_arguments)]
+pub fn action_to_events_with_auto_close(
+    state: &mut EditorState,
+    cursors: &mut Cursors,
+    action: Action,
+    tab_size: usize,
+    auto_indent: bool,
+    estimated_line_length: usize,
+    viewport_height: u16,
+    auto_close_at: &dyn Fn(usize) -> bool,
 ) -> Option<Vec<Event>> {
     // For virtual buffers with hidden cursors, ignore movement and editing actions
     if !state.show_cursors && action.is_movement_or_editing() {
@@ -834,18 +1035,30 @@ pub fn action_to_events(
 
     // Convert block selection to multi-cursor before processing editing actions
     // This allows normal multi-cursor logic to handle typing, deletion, etc.
+    let mut padding = Vec::new();
     if action.is_editing() {
-        let cursor_events = convert_block_selection_to_cursors(state, cursors);
+        let (cursor_events, cursor_padding) = convert_block_selection_to_cursors(state, cursors);
         for event in &cursor_events {
             state.apply(cursors, event);
         }
         events.extend(cursor_events);
+        padding = cursor_padding;
     }
 
     match action {
         // Character input - insert at each cursor
         Action::InsertChar(ch) => {
-            insert_char_events(state, cursors, &mut events, ch, tab_size, auto_indent);
+            let first = events.len();
+            insert_char_events(
+                state,
+                cursors,
+                &mut events,
+                ch,
+                tab_size,
+                auto_indent,
+                auto_close_at,
+            );
+            pad_virtual_space_inserts(&mut events[first..], &padding);
         }
 
         Action::InsertNewline => {
@@ -1011,7 +1224,7 @@ pub fn action_to_events(
                             old_anchor: None, // No selection after bracket expansion
                             new_anchor: None,
                             old_sticky_column: cursor.sticky_column,
-                            new_sticky_column: cursor_line_end, // Reset sticky column
+                            new_sticky_column: None, // Reset sticky column
                         });
                     }
                 }
@@ -1064,6 +1277,20 @@ pub fn action_to_events(
                     }
                 }
 
+                // A selection over several lines grows to cover them whole
+                let (start_pos, end_pos) = if has_selection {
+                    let (start, end, _) = expand_to_full_lines(
+                        &mut state.buffer,
+                        start_pos,
+                        end_pos,
+                        &line_starts,
+                        estimated_line_length,
+                    );
+                    (start, end)
+                } else {
+                    (start_pos, end_pos)
+                };
+
                 // Store cursor info for later restoration
                 cursor_info.push((
                     cursor_id,
@@ -1178,6 +1405,15 @@ pub fn action_to_events(
                         // Add to global set (automatically deduplicates and sorts)
                         all_line_starts.extend(line_starts.iter());
 
+                        // A selection over several lines grows to cover them whole
+                        let (start_pos, end_pos, full_lines) = expand_to_full_lines(
+                            &mut state.buffer,
+                            start_pos,
+                            end_pos,
+                            &line_starts,
+                            estimated_line_length,
+                        );
+
                         // Store cursor info for later restoration
                         cursor_info.push((
                             cursor_id,
@@ -1186,6 +1422,7 @@ pub fn action_to_events(
                             cursor.sticky_column,
                             start_pos,
                             end_pos,
+                            full_lines,
                         ));
                     }
                 }
@@ -1203,15 +1440,23 @@ pub fn action_to_events(
 
                 // Calculate new selection positions and add MoveCursor events
                 let indent_len = tab_str.len();
-                for (cursor_id, old_position, old_anchor, old_sticky_column, start_pos, end_pos) in
-                    cursor_info
+                for (
+                    cursor_id,
+                    old_position,
+                    old_anchor,
+                    old_sticky_column,
+                    start_pos,
+                    end_pos,
+                    full_lines,
+                ) in cursor_info
                 {
                     // Count how many indents were inserted at or before each position
                     // Use <= for anchor because we insert at line starts, and positions >= line_start shift
+                    // (a full-line selection keeps its anchor before the new indent)
                     // Use < for position to avoid double-counting the indent at position itself
                     let indents_at_or_before_anchor = all_line_starts
                         .iter()
-                        .filter(|&&pos| pos <= start_pos)
+                        .filter(|&&pos| pos < start_pos || (pos == start_pos && !full_lines))
                         .count();
                     let indents_before_position =
                         all_line_starts.iter().filter(|&&pos| pos < end_pos).count();
@@ -1266,7 +1511,7 @@ pub fn action_to_events(
                     old_anchor: cursor.anchor,
                     new_anchor,
                     old_sticky_column: cursor.sticky_column,
-                    new_sticky_column: 0, // Reset sticky column on horizontal movement
+                    new_sticky_column: None, // Reset sticky column on horizontal movement
                 });
             }
         }
@@ -1289,7 +1534,7 @@ pub fn action_to_events(
                     old_anchor: cursor.anchor,
                     new_anchor,
                     old_sticky_column: cursor.sticky_column,
-                    new_sticky_column: 0, // Reset sticky column on horizontal movement
+                    new_sticky_column: None, // Reset sticky column on horizontal movement
                 });
             }
         }
@@ -1304,11 +1549,7 @@ pub fn action_to_events(
                 );
 
                 // Use sticky_column if set (now stores visual column), otherwise use current visual column
-                let goal_visual_column = if cursor.sticky_column > 0 {
-                    cursor.sticky_column
-                } else {
-                    current_visual_column
-                };
+                let goal_visual_column = cursor.sticky_column.unwrap_or(current_visual_column);
 
                 // Now create iterator for navigation
                 let mut iter = state
@@ -1335,7 +1576,7 @@ pub fn action_to_events(
                         old_anchor: cursor.anchor,
                         new_anchor,
                         old_sticky_column: cursor.sticky_column,
-                        new_sticky_column: goal_visual_column, // Preserve the goal visual column
+                        new_sticky_column: Some(goal_visual_column), // Preserve the goal visual column
                     });
                 }
             }
@@ -1351,11 +1592,7 @@ pub fn action_to_events(
                 );
 
                 // Use sticky_column if set (now stores visual column), otherwise use current visual column
-                let goal_visual_column = if cursor.sticky_column > 0 {
-                    cursor.sticky_column
-                } else {
-                    current_visual_column
-                };
+                let goal_visual_column = cursor.sticky_column.unwrap_or(current_visual_column);
 
                 // Now create iterator for navigation
                 let mut iter = state
@@ -1385,7 +1622,7 @@ pub fn action_to_events(
                         old_anchor: cursor.anchor,
                         new_anchor,
                         old_sticky_column: cursor.sticky_column,
-                        new_sticky_column: goal_visual_column, // Preserve the goal visual column
+                        new_sticky_column: Some(goal_visual_column), // Preserve the goal visual column
                     });
                 }
             }
@@ -1410,7 +1647,7 @@ pub fn action_to_events(
                         old_anchor: cursor.anchor,
                         new_anchor,
                         old_sticky_column: cursor.sticky_column,
-                        new_sticky_column: 0, // Reset sticky column
+                        new_sticky_column: None, // Reset sticky column
                     });
                 }
             }
@@ -1439,7 +1676,7 @@ pub fn action_to_events(
                         old_anchor: cursor.anchor,
                         new_anchor,
                         old_sticky_column: cursor.sticky_column,
-                        new_sticky_column: 0, // Reset sticky column
+                        new_sticky_column: None, // Reset sticky column
                     });
                 }
             }
@@ -1461,7 +1698,7 @@ pub fn action_to_events(
                     old_anchor: cursor.anchor,
                     new_anchor,
                     old_sticky_column: cursor.sticky_column,
-                    new_sticky_column: 0, // Reset sticky column
+                    new_sticky_column: None, // Reset sticky column
                 });
             }
         }
@@ -1482,7 +1719,7 @@ pub fn action_to_events(
                     old_anchor: cursor.anchor,
                     new_anchor,
                     old_sticky_column: cursor.sticky_column,
-                    new_sticky_column: 0, // Reset sticky column
+                    new_sticky_column: None, // Reset sticky column
                 });
             }
         }
@@ -1503,7 +1740,7 @@ pub fn action_to_events(
                     old_anchor: cursor.anchor,
                     new_anchor,
                     old_sticky_column: cursor.sticky_column,
-                    new_sticky_column: 0, // Reset sticky column
+                    new_sticky_column: None, // Reset sticky column
                 });
             }
         }
@@ -1523,7 +1760,7 @@ pub fn action_to_events(
                     old_anchor: cursor.anchor,
                     new_anchor,
                     old_sticky_column: cursor.sticky_column,
-                    new_sticky_column: 0, // Reset sticky column
+                    new_sticky_column: None, // Reset sticky column
                 });
             }
         }
@@ -1544,7 +1781,7 @@ pub fn action_to_events(
                     old_anchor: cursor.anchor,
                     new_anchor,
                     old_sticky_column: cursor.sticky_column,
-                    new_sticky_column: 0, // Reset sticky column
+                    new_sticky_column: None, // Reset sticky column
                 });
             }
         }
@@ -1560,11 +1797,7 @@ pub fn action_to_events(
                 let current_column = cursor.position - current_line_start;
 
                 // Use sticky_column if set, otherwise use current column
-                let goal_column = if cursor.sticky_column > 0 {
-                    cursor.sticky_column
-                } else {
-                    current_column
-                };
+                let goal_column = cursor.sticky_column.unwrap_or(current_column);
 
                 let mut new_pos = cursor.position;
                 for _ in 0..lines_to_move {
@@ -1590,7 +1823,7 @@ pub fn action_to_events(
                     old_anchor: cursor.anchor,
                     new_anchor,
                     old_sticky_column: cursor.sticky_column,
-                    new_sticky_column: goal_column, // Preserve the goal column
+                    new_sticky_column: Some(goal_column), // Preserve the goal column
                 });
             }
         }
@@ -1606,11 +1839,7 @@ pub fn action_to_events(
                 let current_column = cursor.position - current_line_start;
 
                 // Use sticky_column if set, otherwise use current column
-                let goal_column = if cursor.sticky_column > 0 {
-                    cursor.sticky_column
-                } else {
-                    current_column
-                };
+                let goal_column = cursor.sticky_column.unwrap_or(current_column);
 
                 // Consume current line
                 iter.next_line();
@@ -1640,7 +1869,7 @@ pub fn action_to_events(
                     old_anchor: cursor.anchor,
                     new_anchor,
                     old_sticky_column: cursor.sticky_column,
-                    new_sticky_column: goal_column, // Preserve the goal column
+                    new_sticky_column: Some(goal_column), // Preserve the goal column
                 });
             }
         }
@@ -1660,7 +1889,7 @@ pub fn action_to_events(
                     old_anchor: cursor.anchor,
                     new_anchor: Some(anchor),
                     old_sticky_column: cursor.sticky_column,
-                    new_sticky_column: 0, // Reset sticky column
+                    new_sticky_column: None, // Reset sticky column
                 });
             }
         }
@@ -1678,7 +1907,7 @@ pub fn action_to_events(
                     old_anchor: cursor.anchor,
                     new_anchor: Some(anchor),
                     old_sticky_column: cursor.sticky_column,
-                    new_sticky_column: 0, // Reset sticky column
+                    new_sticky_column: None, // Reset sticky column
                 });
             }
         }
@@ -1693,11 +1922,7 @@ pub fn action_to_events(
                 let anchor = cursor.anchor.unwrap_or(cursor.position);
 
                 // Use sticky_column if set, otherwise use current column
-                let goal_column = if cursor.sticky_column > 0 {
-                    cursor.sticky_column
-                } else {
-                    current_column
-                };
+                let goal_column = cursor.sticky_column.unwrap_or(current_column);
 
                 if let Some((prev_line_start, prev_line_content)) = iter.prev() {
                     let prev_line_len = prev_line_content.trim_end_matches('\n').len();
@@ -1710,7 +1935,7 @@ pub fn action_to_events(
                         old_anchor: cursor.anchor,
                         new_anchor: Some(anchor),
                         old_sticky_column: cursor.sticky_column,
-                        new_sticky_column: goal_column, // Preserve the goal column
+                        new_sticky_column: Some(goal_column), // Preserve the goal column
                     });
                 }
             }
@@ -1726,11 +1951,7 @@ pub fn action_to_events(
                 let anchor = cursor.anchor.unwrap_or(cursor.position);
 
                 // Use sticky_column if set, otherwise use current column
-                let goal_column = if cursor.sticky_column > 0 {
-                    cursor.sticky_column
-                } else {
-                    current_column
-                };
+                let goal_column = cursor.sticky_column.unwrap_or(current_column);
 
                 // Skip current line, then get next line
                 iter.next_line();
@@ -1745,7 +1966,7 @@ pub fn action_to_events(
                         old_anchor: cursor.anchor,
                         new_anchor: Some(anchor),
                         old_sticky_column: cursor.sticky_column,
-                        new_sticky_column: goal_column, // Preserve the goal column
+                        new_sticky_column: Some(goal_column), // Preserve the goal column
                     });
                 }
             }
@@ -1780,7 +2001,7 @@ pub fn action_to_events(
                     old_anchor: cursor.anchor,
                     new_anchor: Some(anchor),
                     old_sticky_column: cursor.sticky_column,
-                    new_sticky_column: 0,
+                    new_sticky_column: None,
                 });
             }
         }
@@ -1817,7 +2038,7 @@ pub fn action_to_events(
                     old_anchor: cursor.anchor,
                     new_anchor: Some(anchor),
                     old_sticky_column: cursor.sticky_column,
-                    new_sticky_column: 0,
+                    new_sticky_column: None,
                 });
             }
         }
@@ -1837,7 +2058,7 @@ pub fn action_to_events(
                         old_anchor: cursor.anchor,
                         new_anchor: Some(anchor),
                         old_sticky_column: cursor.sticky_column,
-                        new_sticky_column: 0, // Reset sticky column
+                        new_sticky_column: None, // Reset sticky column
                     });
                 }
             }
@@ -1862,7 +2083,7 @@ pub fn action_to_events(
                         old_anchor: cursor.anchor,
                         new_anchor: Some(anchor),
                         old_sticky_column: cursor.sticky_column,
-                        new_sticky_column: 0, // Reset sticky column
+                        new_sticky_column: None, // Reset sticky column
                     });
                 }
             }
@@ -1879,7 +2100,7 @@ pub fn action_to_events(
                     old_anchor: cursor.anchor,
                     new_anchor: Some(anchor),
                     old_sticky_column: cursor.sticky_column,
-                    new_sticky_column: 0, // Reset sticky column
+                    new_sticky_column: None, // Reset sticky column
                 });
             }
         }
@@ -1895,7 +2116,7 @@ pub fn action_to_events(
                     old_anchor: cursor.anchor,
                     new_anchor: Some(anchor),
                     old_sticky_column: cursor.sticky_column,
-                    new_sticky_column: 0, // Reset sticky column
+                    new_sticky_column: None, // Reset sticky column
                 });
             }
         }
@@ -1911,7 +2132,7 @@ pub fn action_to_events(
                     old_anchor: cursor.anchor,
                     new_anchor: Some(anchor),
                     old_sticky_column: cursor.sticky_column,
-                    new_sticky_column: 0, // Reset sticky column
+                    new_sticky_column: None, // Reset sticky column
                 });
             }
         }
@@ -1926,7 +2147,7 @@ pub fn action_to_events(
                     old_anchor: cursor.anchor,
                     new_anchor: Some(anchor),
                     old_sticky_column: cursor.sticky_column,
-                    new_sticky_column: 0, // Reset sticky column
+                    new_sticky_column: None, // Reset sticky column
                 });
             }
         }
@@ -1942,7 +2163,7 @@ pub fn action_to_events(
                     old_anchor: cursor.anchor,
                     new_anchor: Some(anchor),
                     old_sticky_column: cursor.sticky_column,
-                    new_sticky_column: 0, // Reset sticky column
+                    new_sticky_column: None, // Reset sticky column
                 });
             }
         }
@@ -1958,11 +2179,7 @@ pub fn action_to_events(
                 let anchor = cursor.anchor.unwrap_or(cursor.position);
 
                 // Use sticky_column if set, otherwise use current column
-                let goal_column = if cursor.sticky_column > 0 {
-                    cursor.sticky_column
-                } else {
-                    current_column
-                };
+                let goal_column = cursor.sticky_column.unwrap_or(current_column);
 
                 let mut new_pos = cursor.position;
                 for _ in 0..lines_to_move {
@@ -1982,7 +2199,7 @@ pub fn action_to_events(
                     old_anchor: cursor.anchor,
                     new_anchor: Some(anchor),
                     old_sticky_column: cursor.sticky_column,
-                    new_sticky_column: goal_column, // Preserve the goal column
+                    new_sticky_column: Some(goal_column), // Preserve the goal column
                 });
             }
         }
@@ -1998,11 +2215,7 @@ pub fn action_to_events(
                 let anchor = cursor.anchor.unwrap_or(cursor.position);
 
                 // Use sticky_column if set, otherwise use current column
-                let goal_column = if cursor.sticky_column > 0 {
-                    cursor.sticky_column
-                } else {
-                    current_column
-                };
+                let goal_column = cursor.sticky_column.unwrap_or(current_column);
 
                 // Consume current line
                 iter.next_line();
@@ -2026,7 +2239,7 @@ pub fn action_to_events(
                     old_anchor: cursor.anchor,
                     new_anchor: Some(anchor),
                     old_sticky_column: cursor.sticky_column,
-                    new_sticky_column: goal_column, // Preserve the goal column
+                    new_sticky_column: Some(goal_column), // Preserve the goal column
                 });
             }
         }
@@ -2043,7 +2256,7 @@ pub fn action_to_events(
                 old_anchor: primary_cursor.anchor,
                 new_anchor: Some(0),
                 old_sticky_column: primary_cursor.sticky_column,
-                new_sticky_column: 0, // Reset sticky column
+                new_sticky_column: None, // Reset sticky column
             });
             // Note: RemoveSecondaryCursors is handled in handle_key, not as an event
         }
@@ -2065,7 +2278,7 @@ pub fn action_to_events(
                         old_anchor: cursor.anchor,
                         new_anchor: Some(word_start),
                         old_sticky_column: cursor.sticky_column,
-                        new_sticky_column: 0, // Reset sticky column
+                        new_sticky_column: None, // Reset sticky column
                     });
                 }
             }
@@ -2334,45 +2547,39 @@ pub fn action_to_events(
         }
 
         Action::SortLines => {
-            // Sort selected lines alphabetically
-            // Process cursors in reverse order to avoid position shifts
-            let line_ending = state.buffer.line_ending().as_str();
-            let mut selections: Vec<_> = cursors
-                .iter()
-                .filter_map(|(cursor_id, cursor)| {
-                    cursor.selection_range().map(|range| (cursor_id, range))
-                })
-                .collect();
-            selections.sort_by_key(|(_, range)| std::cmp::Reverse(range.start));
-
-            for (cursor_id, range) in selections {
-                let text = state.get_text_range(range.start, range.end);
-                // Split into lines, preserving the original line ending style
-                let mut lines: Vec<&str> = text.lines().collect();
-                // Check if original text ends with a newline
-                let ends_with_newline = text.ends_with('\n') || text.ends_with("\r\n");
-
-                if lines.len() > 1 {
-                    lines.sort();
-                    let mut sorted_text = lines.join(line_ending);
-                    if ends_with_newline {
-                        sorted_text.push_str(line_ending);
-                    }
+            sort_lines(state, cursors, &mut events, SortLinesOptions::default());
+        }
 
-                    if sorted_text != text {
-                        events.push(Event::Delete {
-                            range: range.clone(),
-                            deleted_text: text,
-                            cursor_id,
-                        });
-                        events.push(Event::Insert {
-                            position: range.start,
-                            text: sorted_text,
-                            cursor_id,
-                        });
-                    }
-                }
-            }
+        Action::SortLinesDescending => {
+            let options = SortLinesOptions {
+                descending: true,
+                ..Default::default()
+            };
+            sort_lines(state, cursors, &mut events, options);
+        }
+
+        Action::SortLinesCaseInsensitive => {
+            let options = SortLinesOptions {
+                case_insensitive: true,
+                ..Default::default()
+            };
+            sort_lines(state, cursors, &mut events, options);
+        }
+
+        Action::SortLinesNumeric => {
+            let options = SortLinesOptions {
+                numeric: true,
+                ..Default::default()
+            };
+            sort_lines(state, cursors, &mut events, options);
+        }
+
+        Action::SortLinesUnique => {
+            let options = SortLinesOptions {
+                unique: true,
+                ..Default::default()
+            };
+            sort_lines(state, cursors, &mut events, options);
         }
 
         Action::OpenLine => {
@@ -2443,25 +2650,29 @@ pub fn action_to_events(
                     cursor_id,
                 });
 
-                // Move cursor to start of the newly duplicated line.
-                // After the Insert, apply_insert places cursor at line_end + insert_len.
-                // The new line starts at line_end (if original had trailing newline)
-                // or line_end + line_ending.len() (if we prepended a newline).
+                // Move the cursor (and selection) onto the copy below, at the
+                // same offsets within the lines. After the Insert, apply_insert
+                // places the cursor at line_end + insert_len with no selection.
+                // The copy starts at line_end (if the original had a trailing
+                // newline) or line_end + line_ending.len() (if we prepended one).
                 let new_line_start = if has_trailing_newline {
                     line_end
                 } else {
                     line_end + line_ending.len()
                 };
-                let cursor = cursors.get(cursor_id);
-                let old_sticky = cursor.map(|c| c.sticky_column).unwrap_or(0);
+                let Some(cursor) = cursors.get(cursor_id) else {
+                    continue;
+                };
+                let to_copy =
+                    |pos: usize| new_line_start + pos.clamp(line_start, line_end) - line_start;
                 events.push(Event::MoveCursor {
                     cursor_id,
                     old_position: line_end + insert_len,
-                    new_position: new_line_start,
+                    new_position: to_copy(cursor.position),
                     old_anchor: None,
-                    new_anchor: None,
-                    old_sticky_column: old_sticky,
-                    new_sticky_column: 0,
+                    new_anchor: cursor.anchor.map(to_copy),
+                    old_sticky_column: cursor.sticky_column,
+                    new_sticky_column: cursor.sticky_column,
                 });
             }
         }
@@ -2522,6 +2733,8 @@ pub fn action_to_events(
         | Action::SaveAs
         | Action::Open
         | Action::SwitchProject
+        | Action::SaveWorkspaceAs
+        | Action::SwitchWorkspace
         | Action::New
         | Action::Close
         | Action::CloseTab
@@ -2541,6 +2754,7 @@ pub fn action_to_events(
         | Action::CopyWithTheme(_)
         | Action::Cut
         | Action::Paste
+        | Action::PasteFromHistory
         | Action::YankWordForward
         | Action::YankWordBackward
         | Action::YankToLineEnd
@@ -2550,6 +2764,7 @@ pub fn action_to_events(
         | Action::AddCursorBelow
         | Action::CommandPalette
         | Action::QuickOpen
+        | Action::QuickOpenToggleIgnored
         | Action::ShowHelp
         | Action::ToggleLineWrap
         | Action::ToggleComposeMode
@@ -2559,7 +2774,9 @@ pub fn action_to_events(
         | Action::ToggleMaximizeSplit
         | Action::Undo
         | Action::Redo
+        | Action::UndoBoundary
         | Action::GoToMatchingBracket
+        | Action::SelectToMatchingBracket
         | Action::JumpToNextError
         | Action::JumpToPreviousError
         | Action::ShowKeyboardShortcuts
@@ -2569,13 +2786,19 @@ pub fn action_to_events(
         | Action::ClearWarnings
         | Action::SmartHome
         | Action::ToggleComment
+        | Action::JoinLines
+        | Action::Align
         | Action::SetBookmark(_)
         | Action::JumpToBookmark(_)
         | Action::ClearBookmark(_)
         | Action::ListBookmarks
+        | Action::ToggleLineBookmark
+        | Action::NextLineBookmark
+        | Action::PreviousLineBookmark
         | Action::ToggleSearchCaseSensitive
         | Action::ToggleSearchWholeWord
         | Action::ToggleSearchRegex
+        | Action::ToggleSearchSmartCase
         | Action::ToggleSearchConfirmEach
         | Action::StartMacroRecording
         | Action::StopMacroRecording
@@ -2586,8 +2809,13 @@ pub fn action_to_events(
         | Action::PromptRecordMacro
         | Action::PromptPlayMacro
         | Action::PlayLastMacro
+        | Action::PromptRepeatLastMacro
         | Action::PromptSetBookmark
         | Action::PromptJumpToBookmark
+        | Action::CopyToRegister(_)
+        | Action::PasteFromRegister(_)
+        | Action::PromptCopyToRegister
+        | Action::PromptPasteFromRegister
         | Action::PromptConfirm
         | Action::PromptConfirmWithText(_)
         | Action::PromptCancel
@@ -2630,6 +2858,8 @@ pub fn action_to_events(
         | Action::ToggleTabBar
         | Action::ToggleVerticalScrollbar
         | Action::ToggleHorizontalScrollbar
+        | Action::ToggleWhitespaceWarnings
+        | Action::ToggleShowWhitespace
         | Action::FocusFileExplorer
         | Action::FocusEditor
         | Action::SetBackground
@@ -2667,12 +2897,15 @@ pub fn action_to_events(
         | Action::ToggleMouseCapture
         | Action::DumpConfig
         | Action::Search
+        | Action::IncrementalSearch
+        | Action::SearchInProject
         | Action::FindInSelection
         | Action::FindNext
         | Action::FindPrevious
         | Action::FindSelectionNext
         | Action::FindSelectionPrevious
         | Action::Replace
+        | Action::ReplaceInSelection
         | Action::QueryReplace
         | Action::MenuActivate
         | Action::MenuClose
@@ -2693,9 +2926,19 @@ pub fn action_to_events(
         | Action::SelectLocale
         | Action::Revert
         | Action::ToggleAutoRevert
+        | Action::CompareWithSaved
+        | Action::CompareWithFile
+        | Action::NextDiffChange
+        | Action::PreviousDiffChange
+        | Action::EditAnyway
+        | Action::SaveWithSudo
         | Action::FormatBuffer
         | Action::TrimTrailingWhitespace
+        | Action::ConvertIndentationToSpaces
+        | Action::ConvertIndentationToTabs
         | Action::EnsureFinalNewline
+        | Action::ToggleTrimWhitespaceOnSave
+        | Action::ToggleFinalNewlineOnSave
         | Action::OpenTerminal
         | Action::CloseTerminal
         | Action::FocusTerminal
@@ -2714,8 +2957,11 @@ pub fn action_to_events(
         | Action::SettingsDecrement
         | Action::SetTabSize
         | Action::SetLineEnding
+        | Action::NormalizeLineEndings
         | Action::SetEncoding
+        | Action::SaveWithEncoding
         | Action::ReloadWithEncoding
+        | Action::ToggleBom
         | Action::SetLanguage
         | Action::ToggleIndentationStyle
         | Action::ToggleTabIndicators
@@ -2764,7 +3010,7 @@ pub fn action_to_events(
                         old_anchor: cursor.anchor,
                         new_anchor: Some(line_start),
                         old_sticky_column: cursor.sticky_column,
-                        new_sticky_column: 0, // Reset sticky column
+                        new_sticky_column: None, // Reset sticky column
                     });
                 }
             }
@@ -2785,7 +3031,7 @@ pub fn action_to_events(
                         old_anchor: cursor.anchor,
                         new_anchor: Some(anchor),
                         old_sticky_column: cursor.sticky_column,
-                        new_sticky_column: 0, // Reset sticky column
+                        new_sticky_column: None, // Reset sticky column
                     });
                 } else {
                     // No selection - select from cursor to end of current word
@@ -2813,7 +3059,7 @@ pub fn action_to_events(
                         old_anchor: cursor.anchor,
                         new_anchor: Some(final_start),
                         old_sticky_column: cursor.sticky_column,
-                        new_sticky_column: 0, // Reset sticky column
+                        new_sticky_column: None, // Reset sticky column
                     });
                 }
             }
@@ -2868,8 +3114,8 @@ mod tests {
                 new_position: 6,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -2925,8 +3171,8 @@ mod tests {
                 new_position: 0,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -2986,8 +3232,8 @@ mod tests {
                 new_position: 2, // "B"
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -3037,8 +3283,8 @@ mod tests {
                 new_position: 0, // "A"
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -3088,8 +3334,8 @@ mod tests {
                 new_position: 0,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -3136,8 +3382,8 @@ mod tests {
                 new_position: 2, // "B"
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -3184,8 +3430,8 @@ mod tests {
                 new_position: 2, // "B"
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -3253,8 +3499,8 @@ mod tests {
                 new_position: target_start,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -3319,8 +3565,8 @@ mod tests {
                 new_position: selection_end,
                 old_anchor: None,
                 new_anchor: Some(selection_start),
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -3451,8 +3697,8 @@ mod tests {
                 new_position: 3,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -3474,7 +3720,8 @@ mod tests {
                 "Cursor should move to end of shorter line"
             );
             assert_eq!(
-                *new_sticky_column, 3,
+                *new_sticky_column,
+                Some(3),
                 "Sticky column should preserve original column"
             );
         } else {
@@ -3495,7 +3742,11 @@ mod tests {
         } = &events[0]
         {
             assert_eq!(*new_position, 13, "Cursor should move back to column 3");
-            assert_eq!(*new_sticky_column, 3, "Sticky column should be preserved");
+            assert_eq!(
+                *new_sticky_column,
+                Some(3),
+                "Sticky column should be preserved"
+            );
         } else {
             panic!("Expected MoveCursor event");
         }
@@ -3530,8 +3781,8 @@ mod tests {
                 new_position: 13,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -3553,7 +3804,8 @@ mod tests {
                 "Cursor should move to end of shorter line"
             );
             assert_eq!(
-                *new_sticky_column, 3,
+                *new_sticky_column,
+                Some(3),
                 "Sticky column should preserve original column"
             );
         } else {
@@ -3574,7 +3826,11 @@ mod tests {
         } = &events[0]
         {
             assert_eq!(*new_position, 3, "Cursor should move back to column 3");
-            assert_eq!(*new_sticky_column, 3, "Sticky column should be preserved");
+            assert_eq!(
+                *new_sticky_column,
+                Some(3),
+                "Sticky column should be preserved"
+            );
         } else {
             panic!("Expected MoveCursor event");
         }
@@ -3609,8 +3865,8 @@ mod tests {
                 new_position: 0,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -3655,8 +3911,8 @@ mod tests {
                 new_position: 6,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -3704,8 +3960,8 @@ mod tests {
                 new_position: 0,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -3755,8 +4011,8 @@ mod tests {
                 new_position: 5,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -3887,8 +4143,8 @@ mod tests {
                 new_position: 0,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -4019,8 +4275,8 @@ mod tests {
                 new_position: target_line_start,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -4087,8 +4343,8 @@ mod tests {
                 new_position: 13,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -4440,8 +4696,8 @@ mod tests {
                 new_position: 0,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -4505,8 +4761,8 @@ mod tests {
                 new_position: 7, // end of "bar"
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -4518,8 +4774,8 @@ mod tests {
                 new_position: 3, // end of "foo"
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -4573,8 +4829,8 @@ mod tests {
                 new_position: 0,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -4728,8 +4984,8 @@ mod tests {
                 new_position: 0,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -4864,8 +5120,8 @@ mod tests {
                 new_position: 1,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -4921,8 +5177,8 @@ mod tests {
                 new_position: 1,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -4974,8 +5230,8 @@ mod tests {
                 new_position: 1,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -5027,8 +5283,8 @@ mod tests {
                 new_position: 1,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -5081,8 +5337,8 @@ mod tests {
                 new_position: 1,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -5135,8 +5391,8 @@ mod tests {
                 new_position: 2,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
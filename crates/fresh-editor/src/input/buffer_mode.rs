@@ -106,6 +106,24 @@ impl ModeRegistry {
 
         registry.register(special_mode);
 
+        // Built-in side-by-side diff views (Compare with Saved/File)
+        let file_diff_mode = BufferMode::new("file-diff")
+            .with_parent("special")
+            .with_binding(KeyCode::Char('n'), KeyModifiers::NONE, "next_diff_change")
+            .with_binding(
+                KeyCode::Char('p'),
+                KeyModifiers::NONE,
+                "previous_diff_change",
+            )
+            .with_binding(KeyCode::Char(']'), KeyModifiers::NONE, "next_diff_change")
+            .with_binding(
+                KeyCode::Char('['),
+                KeyModifiers::NONE,
+                "previous_diff_change",
+            );
+
+        registry.register(file_diff_mode);
+
         registry
     }
 
@@ -334,6 +352,20 @@ mod tests {
         assert!(registry.has_mode("special"));
     }
 
+    #[test]
+    fn test_file_diff_mode_extends_special() {
+        let registry = ModeRegistry::new();
+        assert!(registry.is_read_only("file-diff"));
+        assert_eq!(
+            registry.resolve_keybinding("file-diff", KeyCode::Char('n'), KeyModifiers::NONE),
+            Some("next_diff_change".to_string())
+        );
+        assert_eq!(
+            registry.resolve_keybinding("file-diff", KeyCode::Char('q'), KeyModifiers::NONE),
+            Some("close".to_string())
+        );
+    }
+
     #[test]
     fn test_special_mode_keybindings() {
         let registry = ModeRegistry::new();
@@ -7,8 +7,23 @@ use crate::input::commands::{get_all_commands, Command, Suggestion};
 use crate::input::fuzzy::fuzzy_match;
 use crate::input::keybindings::Action;
 use crate::input::keybindings::KeyContext;
+use crate::input::keybindings::ShortcutQuery;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 
+/// Command usage persisted across sessions
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CommandUsage {
+    /// Recently used command names, most recent first
+    #[serde(default)]
+    recent: Vec<String>,
+    /// How many times each command has been run
+    #[serde(default)]
+    counts: HashMap<String, u32>,
+}
+
 /// Registry for managing editor commands
 ///
 /// Supports both built-in commands and dynamically registered plugin commands.
@@ -23,18 +38,39 @@ pub struct CommandRegistry {
     /// Command usage history (most recent first)
     /// Used to sort command palette suggestions by recency
     command_history: Vec<String>,
+
+    /// How many times each command has been run
+    usage_counts: HashMap<String, u32>,
+
+    /// File that usage is saved to after each recorded command
+    usage_path: Option<PathBuf>,
 }
 
 impl CommandRegistry {
     /// Maximum number of commands to keep in history
     const MAX_HISTORY_SIZE: usize = 50;
 
+    /// Score for a command bound to the key combination typed as the
+    /// query, above any name match
+    const SHORTCUT_MATCH_SCORE: i32 = 1000;
+
+    /// Score boost for the most recently used command, shrinking by
+    /// `RECENCY_STEP` for each more recent one
+    const RECENCY_BOOST: i32 = 30;
+    const RECENCY_STEP: i32 = 3;
+
+    /// Score boost per use of a command, capped at `MAX_FREQUENCY_BOOST`
+    const FREQUENCY_BOOST: i32 = 2;
+    const MAX_FREQUENCY_BOOST: i32 = 20;
+
     /// Create a new command registry with built-in commands
     pub fn new() -> Self {
         Self {
             builtin_commands: get_all_commands(),
             plugin_commands: Arc::new(RwLock::new(Vec::new())),
             command_history: Vec::new(),
+            usage_counts: HashMap::new(),
+            usage_path: None,
         }
     }
 
@@ -43,10 +79,47 @@ impl CommandRegistry {
         self.builtin_commands = get_all_commands();
     }
 
+    /// Load command usage from `path` and save it there from now on
+    ///
+    /// A missing or unreadable file starts from empty usage.
+    pub fn load_usage(&mut self, path: PathBuf) {
+        let usage = if path.exists() {
+            std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|json| {
+                    serde_json::from_str::<CommandUsage>(&json).map_err(|e| e.to_string())
+                })
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to load command usage from {:?}: {}", path, e);
+                    CommandUsage::default()
+                })
+        } else {
+            CommandUsage::default()
+        };
+        self.command_history = usage.recent;
+        self.command_history.truncate(Self::MAX_HISTORY_SIZE);
+        self.usage_counts = usage.counts;
+        self.usage_path = Some(path);
+    }
+
+    /// Write command usage to the file given to `load_usage`
+    fn save_usage(&self, path: &Path) -> std::io::Result<()> {
+        let usage = CommandUsage {
+            recent: self.command_history.clone(),
+            counts: self.usage_counts.clone(),
+        };
+        let json = serde_json::to_string_pretty(&usage).map_err(std::io::Error::other)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, json)
+    }
+
     /// Record that a command was used (for history/sorting)
     ///
-    /// This moves the command to the front of the history list.
-    /// Recently used commands appear first in suggestions.
+    /// This moves the command to the front of the history list and bumps
+    /// its use count. Recently and frequently used commands rank higher in
+    /// suggestions.
     pub fn record_usage(&mut self, command_name: &str) {
         // Remove existing entry if present
         self.command_history.retain(|name| name != command_name);
@@ -58,6 +131,17 @@ impl CommandRegistry {
         if self.command_history.len() > Self::MAX_HISTORY_SIZE {
             self.command_history.truncate(Self::MAX_HISTORY_SIZE);
         }
+
+        *self
+            .usage_counts
+            .entry(command_name.to_string())
+            .or_insert(0) += 1;
+
+        if let Some(path) = &self.usage_path {
+            if let Err(e) = self.save_usage(path) {
+                tracing::warn!("Failed to save command usage to {:?}: {}", path, e);
+            }
+        }
     }
 
     /// Get the position of a command in history (0 = most recent)
@@ -68,6 +152,17 @@ impl CommandRegistry {
             .position(|name| name == command_name)
     }
 
+    /// Match score boost for a recently or frequently used command
+    fn usage_boost(&self, command_name: &str, history_pos: Option<usize>) -> i32 {
+        let recency = history_pos.map_or(0, |pos| {
+            (Self::RECENCY_BOOST - Self::RECENCY_STEP * pos as i32).max(0)
+        });
+        let uses = self.usage_counts.get(command_name).copied().unwrap_or(0);
+        let frequency =
+            (Self::FREQUENCY_BOOST * uses.min(100) as i32).min(Self::MAX_FREQUENCY_BOOST);
+        recency + frequency
+    }
+
     /// Register a new command (typically from a plugin)
     ///
     /// If a command with the same name already exists, it will be replaced.
@@ -159,7 +254,7 @@ impl CommandRegistry {
     ///
     /// When query is empty, commands are sorted by recency (most recently used first).
     /// When query is not empty, commands are sorted by match quality (fzf-style scoring)
-    /// with recency as tiebreaker for equal scores.
+    /// boosted for recent and frequent use, with recency as tiebreaker for equal scores.
     /// Disabled commands always appear after enabled ones.
     pub fn filter(
         &self,
@@ -200,12 +295,17 @@ impl CommandRegistry {
         let make_suggestion =
             |cmd: &Command, score: i32, localized_name: String, localized_desc: String| {
                 let mut available = is_available(cmd);
-                if cmd.action == Action::FindInSelection && !selection_active {
+                if matches!(
+                    cmd.action,
+                    Action::FindInSelection | Action::ReplaceInSelection
+                ) && !selection_active
+                {
                     available = false;
                 }
                 let keybinding =
                     keybinding_resolver.get_keybinding_for_action(&cmd.action, current_context);
                 let history_pos = self.history_position(&cmd.name);
+                let score = score + self.usage_boost(&cmd.name, history_pos);
 
                 let suggestion = Suggestion::with_source(
                     localized_name,
@@ -217,25 +317,34 @@ impl CommandRegistry {
                 (suggestion, history_pos, score)
             };
 
-        // First, try to match by name only
+        // A query like "C-p" also finds the commands bound to that key
+        let shortcut = ShortcutQuery::parse(query);
+        let is_bound_to_shortcut = |cmd: &Command| -> bool {
+            shortcut.as_ref().is_some_and(|shortcut| {
+                keybinding_resolver
+                    .get_keybinding_for_action(&cmd.action, current_context)
+                    .is_some_and(|keybinding| shortcut.matches(&keybinding))
+            })
+        };
+
+        // First, try to match by name (or keybinding) only
         // Commands with unmet custom contexts are completely hidden
         let mut suggestions: Vec<(Suggestion, Option<usize>, i32)> = commands
             .iter()
             .filter(|cmd| is_visible(cmd))
             .filter_map(|cmd| {
                 let localized_name = cmd.get_localized_name();
-                let name_result = fuzzy_match(query, &localized_name);
-                if name_result.matched {
-                    let localized_desc = cmd.get_localized_description();
-                    Some(make_suggestion(
-                        cmd,
-                        name_result.score,
-                        localized_name,
-                        localized_desc,
-                    ))
+                let score = if is_bound_to_shortcut(cmd) {
+                    Self::SHORTCUT_MATCH_SCORE
                 } else {
-                    None
-                }
+                    let name_result = fuzzy_match(query, &localized_name);
+                    if !name_result.matched {
+                        return None;
+                    }
+                    name_result.score
+                };
+                let localized_desc = cmd.get_localized_description();
+                Some(make_suggestion(cmd, score, localized_name, localized_desc))
             })
             .collect();
 
@@ -782,4 +891,96 @@ mod tests {
             );
         }
     }
+
+    /// Filter with a query in the normal context, returning suggestion names
+    fn filter_names(registry: &CommandRegistry, query: &str) -> Vec<String> {
+        use crate::config::Config;
+        use crate::input::keybindings::KeybindingResolver;
+
+        let keybindings = KeybindingResolver::new(&Config::default());
+        registry
+            .filter(
+                query,
+                KeyContext::Normal,
+                &keybindings,
+                false,
+                &std::collections::HashSet::new(),
+                None,
+            )
+            .into_iter()
+            .map(|s| s.text)
+            .collect()
+    }
+
+    fn register_toggle_compose(registry: &CommandRegistry) {
+        registry.register(Command {
+            name: "Markdown: Toggle Compose".to_string(),
+            description: "".to_string(),
+            action: Action::None,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Plugin("markdown_compose".to_string()),
+        });
+    }
+
+    #[test]
+    fn test_word_boundary_matches_rank_above_earlier_names() {
+        let registry = CommandRegistry::new();
+        register_toggle_compose(&registry);
+
+        let results = filter_names(&registry, "tc");
+        let position = |name: &str| results.iter().position(|n| n == name).unwrap();
+        // "Dedent Selection" matches t..c mid-word and sorts earlier alphabetically
+        assert!(position("Markdown: Toggle Compose") < position("Dedent Selection"));
+    }
+
+    #[test]
+    fn test_recent_command_ranks_higher_on_next_open() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let usage_path = temp_dir.path().join("command_usage.json");
+
+        let mut registry = CommandRegistry::new();
+        register_toggle_compose(&registry);
+        registry.load_usage(usage_path.clone());
+        let results = filter_names(&registry, "tc");
+        let position =
+            |results: &[String], name: &str| results.iter().position(|n| n == name).unwrap();
+        assert!(
+            position(&results, "Toggle Comment") < position(&results, "Markdown: Toggle Compose")
+        );
+
+        registry.record_usage("Markdown: Toggle Compose");
+
+        // Usage is persisted, so a fresh registry ranks it first too
+        let mut reopened = CommandRegistry::new();
+        register_toggle_compose(&reopened);
+        reopened.load_usage(usage_path);
+        assert_eq!(
+            reopened.usage_counts.get("Markdown: Toggle Compose"),
+            Some(&1)
+        );
+        let results = filter_names(&reopened, "tc");
+        assert_eq!(results[0], "Markdown: Toggle Compose");
+    }
+
+    #[test]
+    fn test_equal_scores_keep_alphabetical_order() {
+        let registry = CommandRegistry::new();
+        for name in ["Zeta Widget", "Alpha Widget", "Mid Widget"] {
+            registry.register(Command {
+                name: name.to_string(),
+                description: "".to_string(),
+                action: Action::None,
+                contexts: vec![],
+                custom_contexts: vec![],
+                source: CommandSource::Builtin,
+            });
+        }
+
+        let results: Vec<String> = filter_names(&registry, "widget")
+            .into_iter()
+            .filter(|n| n.ends_with(" Widget"))
+            .collect();
+        assert_eq!(results, vec!["Alpha Widget", "Mid Widget", "Zeta Widget"]);
+    }
 }
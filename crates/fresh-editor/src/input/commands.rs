@@ -183,6 +183,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.save_workspace_as",
+        desc_key: "cmd.save_workspace_as_desc",
+        action: || Action::SaveWorkspaceAs,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.switch_workspace",
+        desc_key: "cmd.switch_workspace_desc",
+        action: || Action::SwitchWorkspace,
+        contexts: &[],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.save_file",
         desc_key: "cmd.save_file_desc",
@@ -232,6 +246,48 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.compare_with_saved",
+        desc_key: "cmd.compare_with_saved_desc",
+        action: || Action::CompareWithSaved,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.compare_with_file",
+        desc_key: "cmd.compare_with_file_desc",
+        action: || Action::CompareWithFile,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.next_diff_change",
+        desc_key: "cmd.next_diff_change_desc",
+        action: || Action::NextDiffChange,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.previous_diff_change",
+        desc_key: "cmd.previous_diff_change_desc",
+        action: || Action::PreviousDiffChange,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.edit_anyway",
+        desc_key: "cmd.edit_anyway_desc",
+        action: || Action::EditAnyway,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.save_with_sudo",
+        desc_key: "cmd.save_with_sudo_desc",
+        action: || Action::SaveWithSudo,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.format_buffer",
         desc_key: "cmd.format_buffer_desc",
@@ -253,6 +309,34 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.convert_indentation_to_spaces",
+        desc_key: "cmd.convert_indentation_to_spaces_desc",
+        action: || Action::ConvertIndentationToSpaces,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.convert_indentation_to_tabs",
+        desc_key: "cmd.convert_indentation_to_tabs_desc",
+        action: || Action::ConvertIndentationToTabs,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.toggle_trim_whitespace_on_save",
+        desc_key: "cmd.toggle_trim_whitespace_on_save_desc",
+        action: || Action::ToggleTrimWhitespaceOnSave,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.toggle_final_newline_on_save",
+        desc_key: "cmd.toggle_final_newline_on_save_desc",
+        action: || Action::ToggleFinalNewlineOnSave,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.quit",
         desc_key: "cmd.quit_desc",
@@ -282,6 +366,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.undo_boundary",
+        desc_key: "cmd.undo_boundary_desc",
+        action: || Action::UndoBoundary,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.copy",
         desc_key: "cmd.copy_desc",
@@ -310,6 +401,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.paste_from_history",
+        desc_key: "cmd.paste_from_history_desc",
+        action: || Action::PasteFromHistory,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.delete_line",
         desc_key: "cmd.delete_line_desc",
@@ -366,6 +464,34 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.sort_lines_descending",
+        desc_key: "cmd.sort_lines_descending_desc",
+        action: || Action::SortLinesDescending,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.sort_lines_case_insensitive",
+        desc_key: "cmd.sort_lines_case_insensitive_desc",
+        action: || Action::SortLinesCaseInsensitive,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.sort_lines_numeric",
+        desc_key: "cmd.sort_lines_numeric_desc",
+        action: || Action::SortLinesNumeric,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.sort_lines_unique",
+        desc_key: "cmd.sort_lines_unique_desc",
+        action: || Action::SortLinesUnique,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.open_line",
         desc_key: "cmd.open_line_desc",
@@ -590,6 +716,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.normalize_line_endings",
+        desc_key: "cmd.normalize_line_endings_desc",
+        action: || Action::NormalizeLineEndings,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.set_encoding",
         desc_key: "cmd.set_encoding_desc",
@@ -597,6 +730,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.save_with_encoding",
+        desc_key: "cmd.save_with_encoding_desc",
+        action: || Action::SaveWithEncoding,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.reload_with_encoding",
         desc_key: "cmd.reload_with_encoding_desc",
@@ -604,6 +744,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.toggle_bom",
+        desc_key: "cmd.toggle_bom_desc",
+        action: || Action::ToggleBom,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.set_language",
         desc_key: "cmd.set_language_desc",
@@ -703,6 +850,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal, FileExplorer, Terminal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.toggle_whitespace_warnings",
+        desc_key: "cmd.toggle_whitespace_warnings_desc",
+        action: || Action::ToggleWhitespaceWarnings,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.toggle_show_whitespace",
+        desc_key: "cmd.toggle_show_whitespace_desc",
+        action: || Action::ToggleShowWhitespace,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.focus_file_explorer",
         desc_key: "cmd.focus_file_explorer_desc",
@@ -766,6 +927,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[FileExplorer],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.quick_open_toggle_ignored",
+        desc_key: "cmd.quick_open_toggle_ignored_desc",
+        action: || Action::QuickOpenToggleIgnored,
+        contexts: &[],
+        custom_contexts: &[],
+    },
     // View
     CommandDef {
         name_key: "cmd.toggle_line_wrap",
@@ -796,6 +964,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.incremental_search",
+        desc_key: "cmd.incremental_search_desc",
+        action: || Action::IncrementalSearch,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.search_in_project",
+        desc_key: "cmd.search_in_project_desc",
+        action: || Action::SearchInProject,
+        contexts: &[],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.find_in_selection",
         desc_key: "cmd.find_in_selection_desc",
@@ -838,6 +1020,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.replace_in_selection",
+        desc_key: "cmd.replace_in_selection_desc",
+        action: || Action::ReplaceInSelection,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.query_replace",
         desc_key: "cmd.query_replace_desc",
@@ -952,6 +1141,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.join_lines",
+        desc_key: "cmd.join_lines_desc",
+        action: || Action::JoinLines,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.align",
+        desc_key: "cmd.align_desc",
+        action: || Action::Align,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.dedent_selection",
         desc_key: "cmd.dedent_selection_desc",
@@ -966,6 +1169,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.select_to_matching_bracket",
+        desc_key: "cmd.select_to_matching_bracket_desc",
+        action: || Action::SelectToMatchingBracket,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     // Error navigation
     CommandDef {
         name_key: "cmd.jump_to_next_error",
@@ -997,6 +1207,27 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.toggle_line_bookmark",
+        desc_key: "cmd.toggle_line_bookmark_desc",
+        action: || Action::ToggleLineBookmark,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.next_line_bookmark",
+        desc_key: "cmd.next_line_bookmark_desc",
+        action: || Action::NextLineBookmark,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.previous_line_bookmark",
+        desc_key: "cmd.previous_line_bookmark_desc",
+        action: || Action::PreviousLineBookmark,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.list_macros",
         desc_key: "cmd.list_macros_desc",
@@ -1032,6 +1263,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.repeat_last_macro",
+        desc_key: "cmd.repeat_last_macro_desc",
+        action: || Action::PromptRepeatLastMacro,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.set_bookmark",
         desc_key: "cmd.set_bookmark_desc",
@@ -1046,6 +1284,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.copy_to_register",
+        desc_key: "cmd.copy_to_register_desc",
+        action: || Action::PromptCopyToRegister,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.paste_from_register",
+        desc_key: "cmd.paste_from_register_desc",
+        action: || Action::PromptPasteFromRegister,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     // Help
     CommandDef {
         name_key: "cmd.show_manual",
@@ -0,0 +1,96 @@
+//! Targets for the Go to Line prompt.
+//!
+//! The prompt accepts an absolute line (`42`), an offset from the cursor's
+//! line (`+20`, `-5`) or a position through the file (`50%`).
+
+/// A parsed Go to Line input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GotoLineTarget {
+    /// 1-based line number
+    Absolute(usize),
+    /// Lines below (positive) or above (negative) the cursor's line
+    Relative(isize),
+    /// Percentage of the way through the file
+    Percent(usize),
+}
+
+impl GotoLineTarget {
+    /// Parse prompt input, ignoring surrounding whitespace
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        if let Some(percent) = input.strip_suffix('%') {
+            return digits(percent).map(Self::Percent);
+        }
+        if let Some(offset) = input.strip_prefix('+') {
+            return digits(offset).and_then(|n| isize::try_from(n).ok().map(Self::Relative));
+        }
+        if let Some(offset) = input.strip_prefix('-') {
+            return digits(offset)
+                .and_then(|n| isize::try_from(n).ok().map(|n| Self::Relative(-n)));
+        }
+        digits(input).map(Self::Absolute)
+    }
+
+    /// The 1-based line this target points at, clamped to the file
+    ///
+    /// `current_line` is the cursor's 1-based line. Percentages round down,
+    /// so `0%` is the first line and `100%` the last.
+    pub fn resolve(self, current_line: usize, line_count: usize) -> usize {
+        let last = line_count.max(1);
+        let line = match self {
+            Self::Absolute(line) => line,
+            Self::Relative(offset) => current_line.saturating_add_signed(offset),
+            Self::Percent(percent) => last.saturating_mul(percent.min(100)) / 100,
+        };
+        line.clamp(1, last)
+    }
+}
+
+/// Parse a non-empty run of ASCII digits (no sign, no spaces)
+fn digits(s: &str) -> Option<usize> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            GotoLineTarget::parse("42"),
+            Some(GotoLineTarget::Absolute(42))
+        );
+        assert_eq!(
+            GotoLineTarget::parse(" +20 "),
+            Some(GotoLineTarget::Relative(20))
+        );
+        assert_eq!(
+            GotoLineTarget::parse("-5"),
+            Some(GotoLineTarget::Relative(-5))
+        );
+        assert_eq!(
+            GotoLineTarget::parse("50%"),
+            Some(GotoLineTarget::Percent(50))
+        );
+        for invalid in ["", "abc", "+", "-", "%", "5 %", "+-3", "1.5", "50%%"] {
+            assert_eq!(GotoLineTarget::parse(invalid), None, "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn test_resolve_clamps_to_file() {
+        assert_eq!(GotoLineTarget::Absolute(7).resolve(1, 10), 7);
+        assert_eq!(GotoLineTarget::Absolute(500).resolve(1, 10), 10);
+        assert_eq!(GotoLineTarget::Relative(3).resolve(4, 10), 7);
+        assert_eq!(GotoLineTarget::Relative(-3).resolve(4, 10), 1);
+        assert_eq!(GotoLineTarget::Relative(20).resolve(4, 10), 10);
+        assert_eq!(GotoLineTarget::Percent(50).resolve(1, 200), 100);
+        assert_eq!(GotoLineTarget::Percent(0).resolve(5, 200), 1);
+        assert_eq!(GotoLineTarget::Percent(100).resolve(5, 200), 200);
+        assert_eq!(GotoLineTarget::Percent(250).resolve(5, 200), 200);
+    }
+}
@@ -107,6 +107,89 @@ pub fn format_keybinding(keycode: &KeyCode, modifiers: &KeyModifiers) -> String
     result
 }
 
+/// A command palette query naming a key combination, like "C-p" or "ctrl+shift+f"
+///
+/// Modifiers can be written Emacs-style (`C-`, `M-`, `S-`), spelled out
+/// (`Ctrl+`, `Alt+`, `Shift+`) or as macOS symbols. At least one modifier is
+/// required, so plain words are never taken for keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortcutQuery {
+    modifiers: KeyModifiers,
+    key: String,
+}
+
+impl ShortcutQuery {
+    /// Parse a query, returning None if it doesn't name a key combination
+    pub fn parse(query: &str) -> Option<Self> {
+        let (modifiers, key) = split_shortcut_modifiers(query.trim());
+        if modifiers.is_empty() || key.is_empty() {
+            return None;
+        }
+        Some(Self {
+            modifiers,
+            key: normalize_shortcut_key(key),
+        })
+    }
+
+    /// Whether a keybinding formatted by [`format_keybinding`] is this combination
+    pub fn matches(&self, keybinding: &str) -> bool {
+        let (modifiers, key) = split_shortcut_modifiers(keybinding);
+        modifiers == self.modifiers && normalize_shortcut_key(key) == self.key
+    }
+}
+
+/// Split leading modifiers off a key combination, returning them and the key
+fn split_shortcut_modifiers(mut rest: &str) -> (KeyModifiers, &str) {
+    let mut modifiers = KeyModifiers::NONE;
+    loop {
+        // macOS symbols have no separator
+        let symbol = [
+            ('⌃', KeyModifiers::CONTROL),
+            ('⌥', KeyModifiers::ALT),
+            ('⇧', KeyModifiers::SHIFT),
+        ]
+        .into_iter()
+        .find_map(|(symbol, modifier)| Some((rest.strip_prefix(symbol)?, modifier)));
+        if let Some((after, modifier)) = symbol {
+            modifiers |= modifier;
+            rest = after;
+            continue;
+        }
+
+        // A separator at the start is the key itself, as in "Ctrl+-"
+        let Some(sep) = rest.find(['+', '-']).filter(|&sep| sep > 0) else {
+            break;
+        };
+        modifiers |= match rest[..sep].to_lowercase().as_str() {
+            "c" | "ctrl" | "control" => KeyModifiers::CONTROL,
+            "m" | "a" | "alt" | "meta" | "opt" | "option" => KeyModifiers::ALT,
+            "s" | "shift" => KeyModifiers::SHIFT,
+            _ => break,
+        };
+        rest = &rest[sep + 1..];
+    }
+    (modifiers, rest)
+}
+
+/// Lowercase a key name and map common aliases to the names `format_keybinding` uses
+fn normalize_shortcut_key(key: &str) -> String {
+    let key = key.to_lowercase();
+    let alias = match key.as_str() {
+        "pageup" => "pgup",
+        "pagedown" => "pgdn",
+        "delete" => "del",
+        "escape" => "esc",
+        "return" | "ret" => "enter",
+        "spc" => "space",
+        "left" => "←",
+        "right" => "→",
+        "up" => "↑",
+        "down" => "↓",
+        _ => return key,
+    };
+    alias.to_string()
+}
+
 /// Returns a priority score for a keybinding key.
 /// Lower scores indicate canonical/preferred keys, higher scores indicate terminal equivalents.
 /// This helps ensure deterministic selection when multiple keybindings exist for an action.
@@ -315,6 +398,7 @@ pub enum Action {
     CopyWithTheme(String),
     Cut,
     Paste,
+    PasteFromHistory,
 
     // Vi-style yank (copy without selection, then restore cursor)
     YankWordForward,
@@ -333,6 +417,8 @@ pub enum Action {
     SaveAs,
     Open,
     SwitchProject,
+    SaveWorkspaceAs,
+    SwitchWorkspace,
     New,
     Close,
     CloseTab,
@@ -341,13 +427,24 @@ pub enum Action {
     Detach,
     Revert,
     ToggleAutoRevert,
+    CompareWithSaved,
+    CompareWithFile,
+    NextDiffChange,
+    PreviousDiffChange,
+    EditAnyway,
+    SaveWithSudo,
     FormatBuffer,
     TrimTrailingWhitespace,
     EnsureFinalNewline,
+    ConvertIndentationToSpaces,
+    ConvertIndentationToTabs,
+    ToggleTrimWhitespaceOnSave,
+    ToggleFinalNewlineOnSave,
 
     // Navigation
     GotoLine,
     GoToMatchingBracket,
+    SelectToMatchingBracket,
     JumpToNextError,
     JumpToPreviousError,
 
@@ -355,17 +452,23 @@ pub enum Action {
     SmartHome,
     DedentSelection,
     ToggleComment,
+    JoinLines,
+    Align,
 
     // Bookmarks
     SetBookmark(char),
     JumpToBookmark(char),
     ClearBookmark(char),
     ListBookmarks,
+    ToggleLineBookmark,
+    NextLineBookmark,
+    PreviousLineBookmark,
 
     // Search options
     ToggleSearchCaseSensitive,
     ToggleSearchWholeWord,
     ToggleSearchRegex,
+    ToggleSearchSmartCase,
     ToggleSearchConfirmEach,
 
     // Macros
@@ -378,14 +481,22 @@ pub enum Action {
     PromptRecordMacro,
     PromptPlayMacro,
     PlayLastMacro,
+    PromptRepeatLastMacro,
 
     // Bookmarks (prompt-based)
     PromptSetBookmark,
     PromptJumpToBookmark,
 
+    // Named clipboard registers
+    CopyToRegister(char),
+    PasteFromRegister(char),
+    PromptCopyToRegister,
+    PromptPasteFromRegister,
+
     // Undo/redo
     Undo,
     Redo,
+    UndoBoundary,
 
     // View
     ScrollUp,
@@ -399,6 +510,7 @@ pub enum Action {
     CommandPalette, // TODO: Consider dropping this now that we have QuickOpen
     /// Quick Open - unified prompt with prefix-based provider routing
     QuickOpen,
+    QuickOpenToggleIgnored,
     ToggleLineWrap,
     ToggleComposeMode,
     SetComposeWidth,
@@ -486,6 +598,10 @@ pub enum Action {
     // Scrollbar visibility
     ToggleVerticalScrollbar,
     ToggleHorizontalScrollbar,
+    // Trailing whitespace / mixed indentation highlighting
+    ToggleWhitespaceWarnings,
+    // Whitespace glyphs (·, →, ↵)
+    ToggleShowWhitespace,
     FocusFileExplorer,
     FocusEditor,
     FileExplorerUp,
@@ -530,8 +646,11 @@ pub enum Action {
     // Buffer settings (per-buffer overrides)
     SetTabSize,
     SetLineEnding,
+    NormalizeLineEndings,
     SetEncoding,
+    SaveWithEncoding,
     ReloadWithEncoding,
+    ToggleBom,
     SetLanguage,
     ToggleIndentationStyle,
     ToggleTabIndicators,
@@ -544,12 +663,15 @@ pub enum Action {
 
     // Search and replace
     Search,
+    IncrementalSearch,
+    SearchInProject,
     FindInSelection,
     FindNext,
     FindPrevious,
     FindSelectionNext,     // Quick find next occurrence of selection (Ctrl+F3)
     FindSelectionPrevious, // Quick find previous occurrence of selection (Ctrl+Shift+F3)
     Replace,
+    ReplaceInSelection,
     QueryReplace, // Interactive replace (y/n/!/q for each match)
 
     // Menu navigation
@@ -593,9 +715,13 @@ pub enum Action {
     ShellCommandReplace, // Run shell command on buffer/selection, replace content
 
     // Case conversion
-    ToUpperCase, // Convert selection to uppercase
-    ToLowerCase, // Convert selection to lowercase
-    SortLines,   // Sort selected lines alphabetically
+    ToUpperCase,              // Convert selection to uppercase
+    ToLowerCase,              // Convert selection to lowercase
+    SortLines,                // Sort selected lines alphabetically
+    SortLinesDescending,      // Sort selected lines in reverse alphabetical order
+    SortLinesCaseInsensitive, // Sort selected lines ignoring case
+    SortLinesNumeric,         // Sort selected lines by their leading number
+    SortLinesUnique,          // Sort selected lines and remove duplicates
 
     // Input calibration
     CalibrateInput, // Open the input calibration wizard
@@ -727,6 +853,7 @@ impl Action {
             "copy" => Copy,
             "cut" => Cut,
             "paste" => Paste,
+            "paste_from_history" => PasteFromHistory,
 
             "yank_word_forward" => YankWordForward,
             "yank_word_backward" => YankWordBackward,
@@ -742,6 +869,8 @@ impl Action {
             "save_as" => SaveAs,
             "open" => Open,
             "switch_project" => SwitchProject,
+            "save_workspace_as" => SaveWorkspaceAs,
+            "switch_workspace" => SwitchWorkspace,
             "new" => New,
             "close" => Close,
             "close_tab" => CloseTab,
@@ -750,21 +879,38 @@ impl Action {
             "detach" => Detach,
             "revert" => Revert,
             "toggle_auto_revert" => ToggleAutoRevert,
+            "compare_with_saved" => CompareWithSaved,
+            "compare_with_file" => CompareWithFile,
+            "next_diff_change" => NextDiffChange,
+            "previous_diff_change" => PreviousDiffChange,
+            "edit_anyway" => EditAnyway,
+            "save_with_sudo" => SaveWithSudo,
             "format_buffer" => FormatBuffer,
+            "convert_indentation_to_spaces" => ConvertIndentationToSpaces,
+            "convert_indentation_to_tabs" => ConvertIndentationToTabs,
+            "toggle_trim_whitespace_on_save" => ToggleTrimWhitespaceOnSave,
+            "toggle_final_newline_on_save" => ToggleFinalNewlineOnSave,
             "goto_line" => GotoLine,
             "goto_matching_bracket" => GoToMatchingBracket,
+            "select_to_matching_bracket" => SelectToMatchingBracket,
             "jump_to_next_error" => JumpToNextError,
             "jump_to_previous_error" => JumpToPreviousError,
 
             "smart_home" => SmartHome,
             "dedent_selection" => DedentSelection,
             "toggle_comment" => ToggleComment,
+            "join_lines" => JoinLines,
+            "align" => Align,
 
             "list_bookmarks" => ListBookmarks,
+            "toggle_line_bookmark" => ToggleLineBookmark,
+            "next_line_bookmark" => NextLineBookmark,
+            "previous_line_bookmark" => PreviousLineBookmark,
 
             "toggle_search_case_sensitive" => ToggleSearchCaseSensitive,
             "toggle_search_whole_word" => ToggleSearchWholeWord,
             "toggle_search_regex" => ToggleSearchRegex,
+            "toggle_search_smart_case" => ToggleSearchSmartCase,
             "toggle_search_confirm_each" => ToggleSearchConfirmEach,
 
             "start_macro_recording" => StartMacroRecording,
@@ -774,11 +920,15 @@ impl Action {
             "prompt_record_macro" => PromptRecordMacro,
             "prompt_play_macro" => PromptPlayMacro,
             "play_last_macro" => PlayLastMacro,
+            "prompt_repeat_last_macro" => PromptRepeatLastMacro,
             "prompt_set_bookmark" => PromptSetBookmark,
             "prompt_jump_to_bookmark" => PromptJumpToBookmark,
+            "prompt_copy_to_register" => PromptCopyToRegister,
+            "prompt_paste_from_register" => PromptPasteFromRegister,
 
             "undo" => Undo,
             "redo" => Redo,
+            "undo_boundary" => UndoBoundary,
 
             "scroll_up" => ScrollUp,
             "scroll_down" => ScrollDown,
@@ -790,6 +940,7 @@ impl Action {
             "clear_warnings" => ClearWarnings,
             "command_palette" => CommandPalette,
             "quick_open" => QuickOpen,
+            "quick_open_toggle_ignored" => QuickOpenToggleIgnored,
             "toggle_line_wrap" => ToggleLineWrap,
             "toggle_compose_mode" => ToggleComposeMode,
             "set_compose_width" => SetComposeWidth,
@@ -852,6 +1003,8 @@ impl Action {
             "toggle_tab_bar" => ToggleTabBar,
             "toggle_vertical_scrollbar" => ToggleVerticalScrollbar,
             "toggle_horizontal_scrollbar" => ToggleHorizontalScrollbar,
+            "toggle_whitespace_warnings" => ToggleWhitespaceWarnings,
+            "toggle_show_whitespace" => ToggleShowWhitespace,
             "focus_file_explorer" => FocusFileExplorer,
             "focus_editor" => FocusEditor,
             "file_explorer_up" => FileExplorerUp,
@@ -896,8 +1049,11 @@ impl Action {
 
             "set_tab_size" => SetTabSize,
             "set_line_ending" => SetLineEnding,
+            "normalize_line_endings" => NormalizeLineEndings,
             "set_encoding" => SetEncoding,
+            "save_with_encoding" => SaveWithEncoding,
             "reload_with_encoding" => ReloadWithEncoding,
+            "toggle_bom" => ToggleBom,
             "toggle_indentation_style" => ToggleIndentationStyle,
             "toggle_tab_indicators" => ToggleTabIndicators,
             "reset_buffer_settings" => ResetBufferSettings,
@@ -905,12 +1061,15 @@ impl Action {
             "dump_config" => DumpConfig,
 
             "search" => Search,
+            "incremental_search" => IncrementalSearch,
+            "search_in_project" => SearchInProject,
             "find_in_selection" => FindInSelection,
             "find_next" => FindNext,
             "find_previous" => FindPrevious,
             "find_selection_next" => FindSelectionNext,
             "find_selection_previous" => FindSelectionPrevious,
             "replace" => Replace,
+            "replace_in_selection" => ReplaceInSelection,
             "query_replace" => QueryReplace,
 
             "menu_activate" => MenuActivate,
@@ -934,6 +1093,10 @@ impl Action {
             "to_upper_case" => ToUpperCase,
             "to_lower_case" => ToLowerCase,
             "sort_lines" => SortLines,
+            "sort_lines_descending" => SortLinesDescending,
+            "sort_lines_case_insensitive" => SortLinesCaseInsensitive,
+            "sort_lines_numeric" => SortLinesNumeric,
+            "sort_lines_unique" => SortLinesUnique,
 
             "calibrate_input" => CalibrateInput,
             "event_debug" => EventDebug,
@@ -960,6 +1123,8 @@ impl Action {
             "play_macro" => PlayMacro,
             "toggle_macro_recording" => ToggleMacroRecording,
             "show_macro" => ShowMacro,
+            "copy_to_register" => CopyToRegister,
+            "paste_from_register" => PasteFromRegister,
         }
         custom {
             "copy_with_theme" => {
@@ -1016,6 +1181,7 @@ impl Action {
                 | Action::SelectAll
                 | Action::SelectWord
                 | Action::SelectLine
+                | Action::SelectToMatchingBracket
                 | Action::ExpandSelection
                 // Block selection
                 | Action::BlockSelectLeft
@@ -1805,6 +1971,7 @@ impl KeybindingResolver {
             Action::CopyWithTheme(theme) => t!("action.copy_with_theme", theme = theme),
             Action::Cut => t!("action.cut"),
             Action::Paste => t!("action.paste"),
+            Action::PasteFromHistory => t!("action.paste_from_history"),
             Action::YankWordForward => t!("action.yank_word_forward"),
             Action::YankWordBackward => t!("action.yank_word_backward"),
             Action::YankToLineEnd => t!("action.yank_to_line_end"),
@@ -1817,6 +1984,8 @@ impl KeybindingResolver {
             Action::SaveAs => t!("action.save_as"),
             Action::Open => t!("action.open"),
             Action::SwitchProject => t!("action.switch_project"),
+            Action::SaveWorkspaceAs => t!("action.save_workspace_as"),
+            Action::SwitchWorkspace => t!("action.switch_workspace"),
             Action::New => t!("action.new"),
             Action::Close => t!("action.close"),
             Action::CloseTab => t!("action.close_tab"),
@@ -1825,23 +1994,40 @@ impl KeybindingResolver {
             Action::Detach => t!("action.detach"),
             Action::Revert => t!("action.revert"),
             Action::ToggleAutoRevert => t!("action.toggle_auto_revert"),
+            Action::CompareWithSaved => t!("action.compare_with_saved"),
+            Action::CompareWithFile => t!("action.compare_with_file"),
+            Action::NextDiffChange => t!("action.next_diff_change"),
+            Action::PreviousDiffChange => t!("action.previous_diff_change"),
+            Action::EditAnyway => t!("action.edit_anyway"),
+            Action::SaveWithSudo => t!("action.save_with_sudo"),
             Action::FormatBuffer => t!("action.format_buffer"),
             Action::TrimTrailingWhitespace => t!("action.trim_trailing_whitespace"),
             Action::EnsureFinalNewline => t!("action.ensure_final_newline"),
+            Action::ConvertIndentationToSpaces => t!("action.convert_indentation_to_spaces"),
+            Action::ConvertIndentationToTabs => t!("action.convert_indentation_to_tabs"),
+            Action::ToggleTrimWhitespaceOnSave => t!("action.toggle_trim_whitespace_on_save"),
+            Action::ToggleFinalNewlineOnSave => t!("action.toggle_final_newline_on_save"),
             Action::GotoLine => t!("action.goto_line"),
             Action::GoToMatchingBracket => t!("action.goto_matching_bracket"),
+            Action::SelectToMatchingBracket => t!("action.select_to_matching_bracket"),
             Action::JumpToNextError => t!("action.jump_to_next_error"),
             Action::JumpToPreviousError => t!("action.jump_to_previous_error"),
             Action::SmartHome => t!("action.smart_home"),
             Action::DedentSelection => t!("action.dedent_selection"),
             Action::ToggleComment => t!("action.toggle_comment"),
+            Action::JoinLines => t!("action.join_lines"),
+            Action::Align => t!("action.align"),
             Action::SetBookmark(c) => t!("action.set_bookmark", key = c),
             Action::JumpToBookmark(c) => t!("action.jump_to_bookmark", key = c),
             Action::ClearBookmark(c) => t!("action.clear_bookmark", key = c),
             Action::ListBookmarks => t!("action.list_bookmarks"),
+            Action::ToggleLineBookmark => t!("action.toggle_line_bookmark"),
+            Action::NextLineBookmark => t!("action.next_line_bookmark"),
+            Action::PreviousLineBookmark => t!("action.previous_line_bookmark"),
             Action::ToggleSearchCaseSensitive => t!("action.toggle_search_case_sensitive"),
             Action::ToggleSearchWholeWord => t!("action.toggle_search_whole_word"),
             Action::ToggleSearchRegex => t!("action.toggle_search_regex"),
+            Action::ToggleSearchSmartCase => t!("action.toggle_search_smart_case"),
             Action::ToggleSearchConfirmEach => t!("action.toggle_search_confirm_each"),
             Action::StartMacroRecording => t!("action.start_macro_recording"),
             Action::StopMacroRecording => t!("action.stop_macro_recording"),
@@ -1852,10 +2038,16 @@ impl KeybindingResolver {
             Action::PromptRecordMacro => t!("action.prompt_record_macro"),
             Action::PromptPlayMacro => t!("action.prompt_play_macro"),
             Action::PlayLastMacro => t!("action.play_last_macro"),
+            Action::PromptRepeatLastMacro => t!("action.prompt_repeat_last_macro"),
             Action::PromptSetBookmark => t!("action.prompt_set_bookmark"),
+            Action::CopyToRegister(c) => t!("action.copy_to_register", key = c),
+            Action::PasteFromRegister(c) => t!("action.paste_from_register", key = c),
+            Action::PromptCopyToRegister => t!("action.prompt_copy_to_register"),
+            Action::PromptPasteFromRegister => t!("action.prompt_paste_from_register"),
             Action::PromptJumpToBookmark => t!("action.prompt_jump_to_bookmark"),
             Action::Undo => t!("action.undo"),
             Action::Redo => t!("action.redo"),
+            Action::UndoBoundary => t!("action.undo_boundary"),
             Action::ScrollUp => t!("action.scroll_up"),
             Action::ScrollDown => t!("action.scroll_down"),
             Action::ShowHelp => t!("action.show_help"),
@@ -1866,6 +2058,7 @@ impl KeybindingResolver {
             Action::ClearWarnings => t!("action.clear_warnings"),
             Action::CommandPalette => t!("action.command_palette"),
             Action::QuickOpen => t!("action.quick_open"),
+            Action::QuickOpenToggleIgnored => t!("action.quick_open_toggle_ignored"),
             Action::ToggleLineWrap => t!("action.toggle_line_wrap"),
             Action::ToggleComposeMode => t!("action.toggle_compose_mode"),
             Action::SetComposeWidth => t!("action.set_compose_width"),
@@ -1927,6 +2120,8 @@ impl KeybindingResolver {
             Action::ToggleTabBar => t!("action.toggle_tab_bar"),
             Action::ToggleVerticalScrollbar => t!("action.toggle_vertical_scrollbar"),
             Action::ToggleHorizontalScrollbar => t!("action.toggle_horizontal_scrollbar"),
+            Action::ToggleWhitespaceWarnings => t!("action.toggle_whitespace_warnings"),
+            Action::ToggleShowWhitespace => t!("action.toggle_show_whitespace"),
             Action::FocusFileExplorer => t!("action.focus_file_explorer"),
             Action::FocusEditor => t!("action.focus_editor"),
             Action::FileExplorerUp => t!("action.file_explorer_up"),
@@ -1967,20 +2162,26 @@ impl KeybindingResolver {
             Action::RemoveRuler => t!("action.remove_ruler"),
             Action::SetTabSize => t!("action.set_tab_size"),
             Action::SetLineEnding => t!("action.set_line_ending"),
+            Action::NormalizeLineEndings => t!("action.normalize_line_endings"),
             Action::SetEncoding => t!("action.set_encoding"),
+            Action::SaveWithEncoding => t!("action.save_with_encoding"),
             Action::ReloadWithEncoding => t!("action.reload_with_encoding"),
+            Action::ToggleBom => t!("action.toggle_bom"),
             Action::SetLanguage => t!("action.set_language"),
             Action::ToggleIndentationStyle => t!("action.toggle_indentation_style"),
             Action::ToggleTabIndicators => t!("action.toggle_tab_indicators"),
             Action::ResetBufferSettings => t!("action.reset_buffer_settings"),
             Action::DumpConfig => t!("action.dump_config"),
             Action::Search => t!("action.search"),
+            Action::IncrementalSearch => t!("action.incremental_search"),
+            Action::SearchInProject => t!("action.search_in_project"),
             Action::FindInSelection => t!("action.find_in_selection"),
             Action::FindNext => t!("action.find_next"),
             Action::FindPrevious => t!("action.find_previous"),
             Action::FindSelectionNext => t!("action.find_selection_next"),
             Action::FindSelectionPrevious => t!("action.find_selection_previous"),
             Action::Replace => t!("action.replace"),
+            Action::ReplaceInSelection => t!("action.replace_in_selection"),
             Action::QueryReplace => t!("action.query_replace"),
             Action::MenuActivate => t!("action.menu_activate"),
             Action::MenuClose => t!("action.menu_close"),
@@ -2021,6 +2222,10 @@ impl KeybindingResolver {
             Action::ToUpperCase => t!("action.to_uppercase"),
             Action::ToLowerCase => t!("action.to_lowercase"),
             Action::SortLines => t!("action.sort_lines"),
+            Action::SortLinesDescending => t!("action.sort_lines_descending"),
+            Action::SortLinesCaseInsensitive => t!("action.sort_lines_case_insensitive"),
+            Action::SortLinesNumeric => t!("action.sort_lines_numeric"),
+            Action::SortLinesUnique => t!("action.sort_lines_unique"),
             Action::CalibrateInput => t!("action.calibrate_input"),
             Action::EventDebug => t!("action.event_debug"),
             Action::OpenKeybindingEditor => "Keybinding Editor".into(),
@@ -2740,4 +2945,23 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_shortcut_query_matches_formatted_keybindings() {
+        let ctrl_p = ShortcutQuery::parse("C-p").unwrap();
+        assert!(ctrl_p.matches("Ctrl+P"));
+        assert!(ctrl_p.matches("⌃P"));
+        assert!(!ctrl_p.matches("Ctrl+Shift+P"));
+        assert!(!ctrl_p.matches("Alt+P"));
+
+        let query = ShortcutQuery::parse("shift+ctrl+pagedown").unwrap();
+        assert!(query.matches("Ctrl+Shift+PgDn"));
+        assert!(ShortcutQuery::parse("M-x").unwrap().matches("Alt+X"));
+        assert!(ShortcutQuery::parse("Ctrl+-").unwrap().matches("Ctrl+-"));
+
+        // Plain words and bare modifiers aren't shortcuts
+        assert_eq!(ShortcutQuery::parse("save"), None);
+        assert_eq!(ShortcutQuery::parse("go-to"), None);
+        assert_eq!(ShortcutQuery::parse("C-"), None);
+    }
 }
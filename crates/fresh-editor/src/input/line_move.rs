@@ -6,6 +6,16 @@ use crate::model::event::{CursorId, Event};
 use crate::state::EditorState;
 use std::ops::Range;
 
+/// Cursor state captured before a line move: ID, selection, position,
+/// anchor and sticky column
+type CursorSnapshot = (
+    CursorId,
+    Option<Range<usize>>,
+    usize,
+    Option<usize>,
+    Option<usize>,
+);
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum LineMoveDirection {
     Up,
@@ -222,19 +232,18 @@ pub(crate) fn move_lines(
         return;
     }
 
-    let cursor_snapshots: Vec<(CursorId, Option<Range<usize>>, usize, Option<usize>, usize)> =
-        cursors
-            .iter()
-            .map(|(cursor_id, cursor)| {
-                (
-                    cursor_id,
-                    cursor.selection_range().map(|range| range.clone()),
-                    cursor.position,
-                    cursor.anchor,
-                    cursor.sticky_column,
-                )
-            })
-            .collect();
+    let cursor_snapshots: Vec<CursorSnapshot> = cursors
+        .iter()
+        .map(|(cursor_id, cursor)| {
+            (
+                cursor_id,
+                cursor.selection_range(),
+                cursor.position,
+                cursor.anchor,
+                cursor.sticky_column,
+            )
+        })
+        .collect();
 
     let ranges: Vec<LineByteRange> = {
         let buffer = &mut state.buffer;
@@ -8,6 +8,7 @@ pub mod command_registry;
 pub mod commands;
 pub mod composite_router;
 pub mod fuzzy;
+pub mod goto_line;
 pub mod handler;
 pub mod input_history;
 pub mod key_translator;
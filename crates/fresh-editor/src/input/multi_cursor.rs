@@ -63,8 +63,36 @@ fn adjust_position_for_newline(state: &mut EditorState, position: usize) -> usiz
     position
 }
 
+/// Whether a character counts as part of a word for whole-word matching
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+/// Check that `range` is not directly preceded or followed by a word character
+fn is_whole_word_at(state: &mut EditorState, range: &std::ops::Range<usize>) -> bool {
+    let before_start = state.buffer.prev_char_boundary(range.start);
+    let after_end = state.buffer.next_char_boundary(range.end);
+    let word_before = before_start < range.start
+        && state
+            .get_text_range(before_start, range.start)
+            .chars()
+            .next()
+            .is_some_and(is_word_char);
+    let word_after = after_end > range.end
+        && state
+            .get_text_range(range.end, after_end)
+            .chars()
+            .next()
+            .is_some_and(is_word_char);
+    !word_before && !word_after
+}
+
 /// Add a cursor at the next occurrence of the selected text
 /// If no selection, selects the entire word at cursor position first
+///
+/// A selection that is exactly one whole word (what the first Ctrl+D
+/// selects) only matches other whole words; any other selection matches as
+/// a plain substring. Matches that overlap an existing selection are skipped.
 pub fn add_cursor_at_next_match(state: &mut EditorState, cursors: &Cursors) -> AddCursorResult {
     // Get the selected text from the primary cursor
     let primary = cursors.primary();
@@ -126,6 +154,7 @@ pub fn add_cursor_at_next_match(state: &mut EditorState, cursors: &Cursors) -> A
     // Extract the selected text
     let pattern = state.get_text_range(selection_range.start, selection_range.end);
     let pattern_len = pattern.len();
+    let whole_word = pattern.chars().all(is_word_char) && is_whole_word_at(state, &selection_range);
 
     // Start searching from the end of the current selection
     let mut search_start = selection_range.end;
@@ -147,16 +176,17 @@ pub fn add_cursor_at_next_match(state: &mut EditorState, cursors: &Cursors) -> A
         // Calculate the range of the found match
         let match_range = match_pos..(match_pos + pattern_len);
 
-        // Check if any existing cursor overlaps with this match
+        // Check if any existing cursor's selection overlaps with this match
         let is_occupied = cursors.iter().any(|(_, c)| {
             if let Some(r) = c.selection_range() {
-                r == match_range
+                r.start < match_range.end && match_range.start < r.end
             } else {
                 false
             }
         });
+        let is_candidate = !is_occupied && (!whole_word || is_whole_word_at(state, &match_range));
 
-        if !is_occupied {
+        if is_candidate {
             // Found a free match!
             let match_start = match_pos;
             let match_end = match_pos + pattern_len;
@@ -178,7 +208,8 @@ pub fn add_cursor_at_next_match(state: &mut EditorState, cursors: &Cursors) -> A
         // Let's refine the search start. We want to search *after* this occupied match.
         // If match_pos is behind us, we wrapped.
 
-        let next_start = match_pos + pattern_len;
+        // Step one byte so a free match overlapping this one is still found
+        let next_start = match_pos + 1;
 
         // Simple cycle detection: if we are stuck on the same spot or have cycled through the whole buffer
         // Ideally we check if we've visited this match_pos before, but checking if we passed initial_start again is a decent proxy
@@ -14,7 +14,7 @@
 ///
 /// This matches VS Code's behavior where you can navigate back through your
 /// editing trail, not through every single keystroke.
-use crate::model::event::BufferId;
+use crate::model::event::{BufferId, Event};
 
 /// A single entry in the position history
 #[derive(Clone, Debug, PartialEq)]
@@ -211,6 +211,42 @@ impl PositionHistory {
         }
     }
 
+    /// Keep the positions in `buffer_id` on the same text after an edit that
+    /// replaced `old_len` bytes at `edit_pos` with `new_len` bytes
+    ///
+    /// Positions inside the replaced text move to its end, like cursors.
+    pub fn adjust_for_edit(
+        &mut self,
+        buffer_id: BufferId,
+        edit_pos: usize,
+        old_len: usize,
+        new_len: usize,
+    ) {
+        let adjust = |offset: usize| {
+            if offset < edit_pos {
+                offset
+            } else if offset >= edit_pos + old_len {
+                offset - old_len + new_len
+            } else {
+                edit_pos + new_len
+            }
+        };
+        let pending = self.pending_movement.as_mut().map(|p| &mut p.start_entry);
+        for entry in self.entries.iter_mut().chain(pending) {
+            if entry.buffer_id == buffer_id {
+                entry.position = adjust(entry.position);
+                entry.anchor = entry.anchor.map(adjust);
+            }
+        }
+    }
+
+    /// Adjust the positions in `buffer_id` for the edits of an event
+    pub fn adjust_for_event(&mut self, buffer_id: BufferId, event: &Event) {
+        for (edit_pos, old_len, new_len) in event.edit_lengths() {
+            self.adjust_for_edit(buffer_id, edit_pos, old_len, new_len);
+        }
+    }
+
     /// Check if we can go back
     pub fn can_go_back(&self) -> bool {
         match self.current_index {
@@ -448,4 +484,27 @@ mod tests {
         assert!(!history.can_go_back());
         assert!(!history.can_go_forward());
     }
+
+    #[test]
+    fn test_adjust_for_edit() {
+        let mut history = PositionHistory::new();
+        history.push(make_entry(1, 5));
+        history.push(PositionEntry::new(BufferId(1), 30, Some(20)));
+        history.push(make_entry(2, 30));
+        history.record_movement(BufferId(1), 100, None);
+
+        // Insert before the later positions of buffer 1
+        history.adjust_for_edit(BufferId(1), 10, 0, 4);
+        // Delete a range containing the anchor
+        history.adjust_for_edit(BufferId(1), 22, 4, 0);
+
+        history.commit_pending_movement();
+        assert_eq!(history.current(), Some(&make_entry(1, 100)));
+        assert_eq!(history.back(), Some(&make_entry(2, 30)));
+        assert_eq!(
+            history.back(),
+            Some(&PositionEntry::new(BufferId(1), 30, Some(22)))
+        );
+        assert_eq!(history.back(), Some(&make_entry(1, 5)));
+    }
 }
@@ -0,0 +1,135 @@
+//! Gitignore filtering for the Quick Open file finder
+//!
+//! Rules come from every `.gitignore` in the project plus
+//! `.git/info/exclude`. Rules in a deeper `.gitignore` take precedence over
+//! the ones above it, `!pattern` re-includes a path, and nothing inside an
+//! ignored directory can be re-included, matching git's behavior.
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+use std::path::{Path, PathBuf};
+
+/// Gitignore rules of a project, each set scoped to the directory it came from
+#[derive(Debug, Default)]
+pub struct ProjectIgnore {
+    /// Rule sets ordered shallowest first; directories are relative to the
+    /// project root
+    rules: Vec<(PathBuf, Gitignore)>,
+}
+
+impl ProjectIgnore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the rules of an ignore file that applies to `dir` (relative to the
+    /// project root)
+    ///
+    /// Of two rule sets for the same directory, the one added last wins, so
+    /// add `.git/info/exclude` before the root `.gitignore`.
+    pub fn add_rules(&mut self, dir: &Path, contents: &str) {
+        let mut builder = GitignoreBuilder::new(dir);
+        for line in contents.lines() {
+            if let Err(e) = builder.add_line(None, line) {
+                tracing::debug!("Skipping invalid ignore rule {:?}: {}", line, e);
+            }
+        }
+        let gitignore = match builder.build() {
+            Ok(gitignore) => gitignore,
+            Err(e) => {
+                tracing::debug!("Failed to build ignore rules for {:?}: {}", dir, e);
+                return;
+            }
+        };
+
+        let depth = dir.components().count();
+        let index = self
+            .rules
+            .partition_point(|(rules_dir, _)| rules_dir.components().count() <= depth);
+        self.rules.insert(index, (dir.to_path_buf(), gitignore));
+    }
+
+    /// Whether `path` (relative to the project root) is ignored, by its own
+    /// rules or because one of its parent directories is
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        path.ancestors()
+            .skip(1)
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .any(|dir| self.is_ignored_entry(dir, true))
+            || self.is_ignored_entry(path, is_dir)
+    }
+
+    /// Whether the rules matching `path` itself ignore it; the deepest rule
+    /// set with a match decides
+    fn is_ignored_entry(&self, path: &Path, is_dir: bool) -> bool {
+        for (dir, gitignore) in self.rules.iter().rev() {
+            if path == dir || !path.starts_with(dir) {
+                continue;
+            }
+            match gitignore.matched(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ignored(ignore: &ProjectIgnore, path: &str) -> bool {
+        ignore.is_ignored(Path::new(path), false)
+    }
+
+    #[test]
+    fn test_negation_reincludes_files() {
+        let mut ignore = ProjectIgnore::new();
+        ignore.add_rules(Path::new(""), "*.log\n!keep.log\n");
+
+        assert!(ignored(&ignore, "debug.log"));
+        assert!(ignored(&ignore, "src/trace.log"));
+        assert!(!ignored(&ignore, "keep.log"));
+        assert!(!ignored(&ignore, "src/main.rs"));
+    }
+
+    #[test]
+    fn test_nested_gitignore_overrides_parent() {
+        let mut ignore = ProjectIgnore::new();
+        ignore.add_rules(Path::new(""), "*.gen\n/build/\n");
+        ignore.add_rules(Path::new("schemas"), "!*.gen\nlocal/\n");
+
+        assert!(ignored(&ignore, "api.gen"));
+        assert!(!ignored(&ignore, "schemas/api.gen"));
+        assert!(ignored(&ignore, "schemas/local/notes.txt"));
+        // Anchored rules only apply relative to their own directory
+        assert!(ignored(&ignore, "build/out.o"));
+        assert!(!ignored(&ignore, "src/build/mod.rs"));
+        assert!(!ignored(&ignore, "local/notes.txt"));
+    }
+
+    #[test]
+    fn test_files_in_ignored_directory_cannot_be_reincluded() {
+        let mut ignore = ProjectIgnore::new();
+        ignore.add_rules(Path::new(""), "target/\n!target/keep.txt\n");
+
+        assert!(ignore.is_ignored(Path::new("target"), true));
+        assert!(ignored(&ignore, "target/keep.txt"));
+        assert!(ignored(&ignore, "target/debug/app"));
+        // A file named like the directory rule isn't a directory
+        assert!(!ignored(&ignore, "target"));
+    }
+
+    #[test]
+    fn test_gitignore_takes_precedence_over_exclude() {
+        let mut ignore = ProjectIgnore::new();
+        ignore.add_rules(Path::new(""), "*.txt\nscratch/\n");
+        ignore.add_rules(Path::new(""), "!notes.txt\n");
+
+        assert!(ignored(&ignore, "todo.txt"));
+        assert!(!ignored(&ignore, "notes.txt"));
+        assert!(ignored(&ignore, "scratch/a.rs"));
+    }
+}
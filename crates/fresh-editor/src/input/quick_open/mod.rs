@@ -11,6 +11,7 @@
 //! Providers are registered with a prefix and handle suggestion generation
 //! and selection for their domain.
 
+pub mod gitignore;
 pub mod providers;
 
 pub use providers::{BufferProvider, CommandProvider, FileProvider, GotoLineProvider};
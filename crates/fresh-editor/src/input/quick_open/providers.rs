@@ -6,10 +6,20 @@
 //! - BufferProvider: Switch between open buffers (prefix: "#")
 //! - GotoLineProvider: Go to a specific line (prefix: ":")
 
+use super::gitignore::ProjectIgnore;
 use super::{QuickOpenContext, QuickOpenProvider, QuickOpenResult};
 use crate::input::commands::Suggestion;
 use crate::input::fuzzy::fuzzy_match;
+use crate::model::filesystem::{FileSystem, StdFileSystem};
+use crate::primitives::path_utils::split_line_col_suffix;
 use rust_i18n::t;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Cancellation flag for work that is never cancelled
+static NOT_CANCELLED: AtomicBool = AtomicBool::new(false);
 
 // ============================================================================
 // Command Provider (prefix: ">")
@@ -321,9 +331,17 @@ impl QuickOpenProvider for GotoLineProvider {
 
 /// Provider for finding files in the project
 ///
-/// This is the default provider (empty prefix) that provides file suggestions
-/// using git ls-files, fd, find, or directory traversal.
+/// This is the default provider (empty prefix). In a git work tree it lists
+/// the files git shows, tracked ones and untracked ones that aren't ignored,
+/// with git running on the host that owns the files. Elsewhere it walks the
+/// project tree, skipping files ignored by `.gitignore` or
+/// `.git/info/exclude`. Including ignored files lists everything, in one
+/// request on remote agents with server-side find.
 pub struct FileProvider {
+    /// Filesystem the project lives on
+    filesystem: Arc<dyn FileSystem + Send + Sync>,
+    /// Whether gitignored files are listed too
+    include_ignored: AtomicBool,
     /// Cached file list (populated lazily)
     file_cache: std::sync::Arc<std::sync::RwLock<Option<Vec<FileEntry>>>>,
     /// Frecency data for ranking
@@ -343,8 +361,19 @@ struct FrecencyData {
 }
 
 impl FileProvider {
-    pub fn new() -> Self {
+    /// Most files listed for a project
+    const MAX_FILES: usize = 50000;
+
+    /// Most files shown for a query
+    const MAX_SUGGESTIONS: usize = 100;
+
+    /// Files fuzzy-matched between checks for cancellation
+    const FILTER_BATCH: usize = 1024;
+
+    pub fn new(filesystem: Arc<dyn FileSystem + Send + Sync>) -> Self {
         Self {
+            filesystem,
+            include_ignored: AtomicBool::new(false),
             file_cache: std::sync::Arc::new(std::sync::RwLock::new(None)),
             frecency: std::sync::Arc::new(std::sync::RwLock::new(std::collections::HashMap::new())),
         }
@@ -357,6 +386,25 @@ impl FileProvider {
         }
     }
 
+    /// Relative paths of the project's files, as listed for Quick Open
+    ///
+    /// Returns `None` if `cancelled` was set before the listing finished.
+    pub fn project_files(&self, cwd: &str, cancelled: &AtomicBool) -> Option<Vec<String>> {
+        let files = self.load_files(cwd, cancelled)?;
+        Some(files.into_iter().map(|file| file.relative_path).collect())
+    }
+
+    /// Whether files ignored by `.gitignore` are listed
+    pub fn include_ignored(&self) -> bool {
+        self.include_ignored.load(Ordering::Relaxed)
+    }
+
+    /// List or hide gitignored files, rescanning the project on next use
+    pub fn set_include_ignored(&self, include: bool) {
+        self.include_ignored.store(include, Ordering::Relaxed);
+        self.clear_cache();
+    }
+
     /// Record file access for frecency ranking
     pub fn record_access(&self, path: &str) {
         if let Ok(mut frecency) = self.frecency.write() {
@@ -369,6 +417,29 @@ impl FileProvider {
         }
     }
 
+    /// Split a `:line` or `:line:col` suffix off a file query, so that
+    /// `main.rs:42` finds `main.rs` and opens it at line 42
+    ///
+    /// A query that is exactly the path of a project file is kept whole, so
+    /// files with colons in their names can still be picked.
+    pub fn split_query<'a>(
+        &self,
+        query: &'a str,
+        cwd: &str,
+    ) -> (&'a str, Option<usize>, Option<usize>) {
+        let (path, line, column) = split_line_col_suffix(query);
+        if line.is_none()
+            || self
+                .load_files(cwd, &NOT_CANCELLED)
+                .unwrap_or_default()
+                .iter()
+                .any(|file| file.relative_path == query)
+        {
+            return (query, None, None);
+        }
+        (path, line, column)
+    }
+
     fn get_frecency_score(&self, path: &str) -> f64 {
         if let Ok(frecency) = self.frecency.read() {
             if let Some(data) = frecency.get(path) {
@@ -396,20 +467,27 @@ impl FileProvider {
     }
 
     /// Load files from the project directory
-    fn load_files(&self, cwd: &str) -> Vec<FileEntry> {
+    ///
+    /// Returns `None` if `cancelled` was set before the listing finished; a
+    /// partial listing is never cached.
+    fn load_files(&self, cwd: &str, cancelled: &AtomicBool) -> Option<Vec<FileEntry>> {
         // Check cache first
         if let Ok(cache) = self.file_cache.read() {
             if let Some(files) = cache.as_ref() {
-                return files.clone();
+                return Some(files.clone());
             }
         }
 
-        // Try different file discovery methods
-        let files = self
-            .try_git_files(cwd)
-            .or_else(|| self.try_fd_files(cwd))
-            .or_else(|| self.try_find_files(cwd))
-            .unwrap_or_else(Vec::new);
+        let root = Path::new(cwd);
+        let mut files = match self.try_list_files(root, cancelled) {
+            Ok(paths) => paths
+                .into_iter()
+                .map(|path| path.to_string_lossy().into_owned())
+                .collect(),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => return None,
+            Err(_) => self.walk_files(root, cancelled)?,
+        };
+        files.sort();
 
         // Add frecency scores
         let files: Vec<FileEntry> = files
@@ -425,130 +503,104 @@ impl FileProvider {
             *cache = Some(files.clone());
         }
 
-        files
-    }
-
-    fn try_git_files(&self, cwd: &str) -> Option<Vec<String>> {
-        let output = std::process::Command::new("git")
-            .args(["ls-files", "--cached", "--others", "--exclude-standard"])
-            .current_dir(cwd)
-            .output()
-            .ok()?;
-
-        if !output.status.success() {
-            return None;
-        }
-
-        let files: Vec<String> = String::from_utf8_lossy(&output.stdout)
-            .lines()
-            .filter(|line| !line.is_empty() && !line.starts_with(".git/"))
-            .map(|s| s.to_string())
-            .collect();
-
         Some(files)
     }
 
-    fn try_fd_files(&self, cwd: &str) -> Option<Vec<String>> {
-        let output = std::process::Command::new("fd")
-            .args([
-                "--type",
-                "f",
-                "--hidden",
-                "--exclude",
-                ".git",
-                "--max-results",
-                "50000",
-            ])
-            .current_dir(cwd)
-            .output()
-            .ok()?;
-
-        if !output.status.success() {
-            return None;
+    /// Rules from `.git/info/exclude`, the base for a project's ignore rules
+    fn excluded_rules(&self, root: &Path) -> ProjectIgnore {
+        let mut ignore = ProjectIgnore::new();
+        if let Ok(contents) = self.filesystem.read_file(&root.join(".git/info/exclude")) {
+            ignore.add_rules(Path::new(""), &String::from_utf8_lossy(&contents));
         }
-
-        let files: Vec<String> = String::from_utf8_lossy(&output.stdout)
-            .lines()
-            .filter(|line| !line.is_empty())
-            .map(|s| s.to_string())
-            .collect();
-
-        Some(files)
-    }
-
-    fn try_find_files(&self, cwd: &str) -> Option<Vec<String>> {
-        let output = std::process::Command::new("find")
-            .args([
-                ".",
-                "-type",
-                "f",
-                "-not",
-                "-path",
-                "*/.git/*",
-                "-not",
-                "-path",
-                "*/node_modules/*",
-                "-not",
-                "-path",
-                "*/target/*",
-                "-not",
-                "-path",
-                "*/__pycache__/*",
-            ])
-            .current_dir(cwd)
-            .output()
-            .ok()?;
-
-        if !output.status.success() {
-            return None;
+        ignore
+    }
+
+    /// List the project in a single request, when the filesystem can
+    ///
+    /// Without ignored files this is the listing git shows, which prunes
+    /// ignored directories as it goes and keeps tracked files even if an
+    /// ignore rule matches them. It fails outside a git work tree, and the
+    /// caller walks the tree instead.
+    fn try_list_files(&self, root: &Path, cancelled: &AtomicBool) -> io::Result<Vec<PathBuf>> {
+        if self.include_ignored() {
+            self.filesystem
+                .find_files(root, "**", Self::MAX_FILES, cancelled)
+        } else {
+            self.filesystem.git_files(root, Self::MAX_FILES, cancelled)
         }
-
-        let files: Vec<String> = String::from_utf8_lossy(&output.stdout)
-            .lines()
-            .filter(|line| !line.is_empty())
-            .map(|s| s.trim_start_matches("./").to_string())
-            .take(50000)
-            .collect();
-
-        Some(files)
     }
-}
 
-impl Default for FileProvider {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl QuickOpenProvider for FileProvider {
-    fn prefix(&self) -> &str {
-        ""
-    }
+    /// Walk the project tree, applying each directory's `.gitignore` on the
+    /// way down so ignored directories are never entered
+    ///
+    /// `.git` and symlinked directories (which could loop) are skipped.
+    /// Returns `None` if `cancelled` is set during the walk.
+    fn walk_files(&self, root: &Path, cancelled: &AtomicBool) -> Option<Vec<String>> {
+        let include_ignored = self.include_ignored();
+        let mut ignore = self.excluded_rules(root);
+        let mut files = Vec::new();
+        let mut dirs = vec![PathBuf::new()];
+
+        while let Some(dir) = dirs.pop() {
+            if cancelled.load(Ordering::Relaxed) {
+                return None;
+            }
+            let abs_dir = root.join(&dir);
+            if !include_ignored {
+                if let Ok(contents) = self.filesystem.read_file(&abs_dir.join(".gitignore")) {
+                    ignore.add_rules(&dir, &String::from_utf8_lossy(&contents));
+                }
+            }
+            let Ok(entries) = self.filesystem.read_dir(&abs_dir) else {
+                continue;
+            };
 
-    fn name(&self) -> &str {
-        "Files"
-    }
+            for entry in entries {
+                if entry.name == ".git" || (entry.is_symlink() && entry.is_dir()) {
+                    continue;
+                }
+                let path = dir.join(&entry.name);
+                if !include_ignored && ignore.is_ignored(&path, entry.is_dir()) {
+                    continue;
+                }
+                if entry.is_dir() {
+                    dirs.push(path);
+                } else {
+                    files.push(path.to_string_lossy().into_owned());
+                    if files.len() >= Self::MAX_FILES {
+                        return Some(files);
+                    }
+                }
+            }
+        }
 
-    fn hint(&self) -> &str {
-        "Files"
+        Some(files)
     }
 
-    fn suggestions(&self, query: &str, context: &QuickOpenContext) -> Vec<Suggestion> {
-        let files = self.load_files(&context.cwd);
+    /// File suggestions for a Quick Open query, best matches first
+    ///
+    /// Lists the project first if it isn't cached. Returns `None` if
+    /// `cancelled` was set before the suggestions were ready.
+    pub fn file_suggestions(
+        &self,
+        query: &str,
+        cwd: &str,
+        cancelled: &AtomicBool,
+    ) -> Option<Vec<Suggestion>> {
+        let files = self.load_files(cwd, cancelled)?;
+        let (query, _, _) = self.split_query(query, cwd);
 
         if files.is_empty() {
-            return vec![Suggestion {
+            return Some(vec![Suggestion {
                 text: t!("quick_open.no_files").to_string(),
                 description: None,
                 value: None,
                 disabled: true,
                 keybinding: None,
                 source: None,
-            }];
+            }]);
         }
 
-        let max_results = 100;
-
         let mut scored_files: Vec<(FileEntry, i32)> = if query.is_empty() {
             // Sort by frecency when no query
             let mut files = files;
@@ -559,41 +611,72 @@ impl QuickOpenProvider for FileProvider {
             });
             files
                 .into_iter()
-                .take(max_results)
+                .take(Self::MAX_SUGGESTIONS)
                 .map(|f| (f, 0))
                 .collect()
         } else {
             // Filter and score by fuzzy match
-            files
-                .into_iter()
-                .filter_map(|file| {
+            let mut scored = Vec::new();
+            for batch in files.chunks(Self::FILTER_BATCH) {
+                if cancelled.load(Ordering::Relaxed) {
+                    return None;
+                }
+                scored.extend(batch.iter().filter_map(|file| {
                     let match_result = fuzzy_match(query, &file.relative_path);
                     if match_result.matched {
                         // Boost score by frecency (normalized)
                         let frecency_boost = (file.frecency_score / 100.0).min(20.0) as i32;
-                        Some((file, match_result.score + frecency_boost))
+                        Some((file.clone(), match_result.score + frecency_boost))
                     } else {
                         None
                     }
-                })
-                .collect()
+                }));
+            }
+            scored
         };
 
         // Sort by score
         scored_files.sort_by(|a, b| b.1.cmp(&a.1));
-        scored_files.truncate(max_results);
+        scored_files.truncate(Self::MAX_SUGGESTIONS);
 
-        scored_files
-            .into_iter()
-            .map(|(file, _)| Suggestion {
-                text: file.relative_path.clone(),
-                description: None,
-                value: Some(file.relative_path),
-                disabled: false,
-                keybinding: None,
-                source: None,
-            })
-            .collect()
+        Some(
+            scored_files
+                .into_iter()
+                .map(|(file, _)| Suggestion {
+                    text: file.relative_path.clone(),
+                    description: None,
+                    value: Some(file.relative_path),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Default for FileProvider {
+    fn default() -> Self {
+        Self::new(Arc::new(StdFileSystem))
+    }
+}
+
+impl QuickOpenProvider for FileProvider {
+    fn prefix(&self) -> &str {
+        ""
+    }
+
+    fn name(&self) -> &str {
+        "Files"
+    }
+
+    fn hint(&self) -> &str {
+        "Files"
+    }
+
+    fn suggestions(&self, query: &str, context: &QuickOpenContext) -> Vec<Suggestion> {
+        self.file_suggestions(query, &context.cwd, &NOT_CANCELLED)
+            .unwrap_or_default()
     }
 
     fn on_select(
@@ -610,10 +693,11 @@ impl QuickOpenProvider for FileProvider {
                     // Record access for frecency
                     self.record_access(path);
 
+                    let (_, line, column) = self.split_query(query, &context.cwd);
                     return QuickOpenResult::OpenFile {
                         path: path.clone(),
-                        line: None,
-                        column: None,
+                        line,
+                        column,
                     };
                 }
             }
@@ -723,4 +807,121 @@ mod tests {
             _ => panic!("Expected GotoLine result"),
         }
     }
+
+    /// A project with an ignored `target/` directory, a negated log rule,
+    /// a nested `.gitignore` and a `.git/info/exclude` entry
+    fn make_ignore_fixture() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let files = [
+            (".gitignore", "target/\n*.log\n!keep.log\n"),
+            (".git/info/exclude", "scratch.txt\n"),
+            (".git/HEAD", "ref: refs/heads/main\n"),
+            ("src/main.rs", "fn main() {}\n"),
+            ("src/generated/.gitignore", "*.rs\n"),
+            ("src/generated/api.rs", ""),
+            ("target/debug/app", ""),
+            ("target/release/app", ""),
+            ("debug.log", ""),
+            ("keep.log", ""),
+            ("scratch.txt", ""),
+        ];
+        for (path, contents) in files {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+        dir
+    }
+
+    fn listed_files(provider: &FileProvider, root: &Path) -> Vec<String> {
+        provider
+            .project_files(&root.to_string_lossy(), &NOT_CANCELLED)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_file_provider_skips_gitignored_files() {
+        let dir = make_ignore_fixture();
+        let provider = FileProvider::default();
+
+        assert_eq!(
+            listed_files(&provider, dir.path()),
+            vec![
+                ".gitignore",
+                "keep.log",
+                "src/generated/.gitignore",
+                "src/main.rs"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_file_provider_toggle_includes_ignored_files() {
+        let dir = make_ignore_fixture();
+        let provider = FileProvider::default();
+        assert!(!listed_files(&provider, dir.path()).contains(&"target/debug/app".to_string()));
+
+        provider.set_include_ignored(true);
+        let files = listed_files(&provider, dir.path());
+        assert!(files.contains(&"target/debug/app".to_string()));
+        assert!(files.contains(&"target/release/app".to_string()));
+        assert!(files.contains(&"scratch.txt".to_string()));
+        assert!(files.contains(&"src/generated/api.rs".to_string()));
+        // .git internals are never listed
+        assert!(!files.iter().any(|file| file.starts_with(".git/")));
+
+        provider.set_include_ignored(false);
+        assert!(!listed_files(&provider, dir.path()).contains(&"target/debug/app".to_string()));
+    }
+
+    #[test]
+    fn test_file_provider_cancelled_listing_is_not_cached() {
+        let dir = make_ignore_fixture();
+        let provider = FileProvider::default();
+        let cwd = dir.path().to_string_lossy();
+
+        assert!(provider
+            .file_suggestions("main", &cwd, &AtomicBool::new(true))
+            .is_none());
+        let suggestions = provider
+            .file_suggestions("main", &cwd, &NOT_CANCELLED)
+            .unwrap();
+        assert_eq!(suggestions[0].text, "src/main.rs");
+    }
+
+    #[test]
+    fn test_file_provider_lists_git_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .is_ok_and(|output| output.status.success())
+        };
+        if !git(&["init", "-q"]) {
+            eprintln!("Skipping test: git is not available");
+            return;
+        }
+        let files = [
+            (".gitignore", "target/\n*.log\n"),
+            ("src/main.rs", "fn main() {}\n"),
+            ("target/debug/app", ""),
+            ("debug.log", ""),
+            ("release.log", ""),
+        ];
+        for (path, contents) in files {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+        // A tracked file stays listed even though an ignore rule matches it
+        assert!(git(&["add", "-f", "release.log"]));
+
+        let provider = FileProvider::default();
+        assert_eq!(
+            listed_files(&provider, dir.path()),
+            vec![".gitignore", "release.log", "src/main.rs"]
+        );
+    }
 }
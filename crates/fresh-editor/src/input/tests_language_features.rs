@@ -248,4 +248,56 @@ mod tests {
             ),
         }
     }
+
+    #[test]
+    fn test_ctrl_d_overlapping_matches() {
+        let (mut state, mut cursors) = create_state("aaaa");
+        // Select "aa" at 0..2 (a substring, since it is not a whole word)
+        cursors.primary_mut().position = 2;
+        cursors.primary_mut().set_anchor(0);
+
+        // 1..2 and 1..3 overlap the selection; the next free match is 2..4
+        match perform_add_cursor_at_next_match(&mut state, &mut cursors) {
+            AddCursorResult::Success { cursor, .. } => {
+                assert_eq!(cursor.selection_range(), Some(2..4));
+            }
+            _ => panic!("Failed to add cursor after overlapping match"),
+        }
+
+        // Every remaining match overlaps an existing selection
+        assert!(matches!(
+            perform_add_cursor_at_next_match(&mut state, &mut cursors),
+            AddCursorResult::Failed { .. }
+        ));
+    }
+
+    #[test]
+    fn test_ctrl_d_whole_word_skips_substrings() {
+        let (mut state, mut cursors) = create_state("foo foobar barfoo foo");
+        // Select the whole word "foo"
+        cursors.primary_mut().position = 3;
+        cursors.primary_mut().set_anchor(0);
+
+        match perform_add_cursor_at_next_match(&mut state, &mut cursors) {
+            AddCursorResult::Success { cursor, .. } => {
+                assert_eq!(cursor.selection_range(), Some(18..21));
+            }
+            _ => panic!("Failed to add cursor at whole-word match"),
+        }
+    }
+
+    #[test]
+    fn test_ctrl_d_substring_selection_matches_inside_words() {
+        let (mut state, mut cursors) = create_state("foo foobar");
+        // Select "fo" - part of a word, so matches are substrings
+        cursors.primary_mut().position = 2;
+        cursors.primary_mut().set_anchor(0);
+
+        match perform_add_cursor_at_next_match(&mut state, &mut cursors) {
+            AddCursorResult::Success { cursor, .. } => {
+                assert_eq!(cursor.selection_range(), Some(4..6));
+            }
+            _ => panic!("Failed to add cursor at substring match"),
+        }
+    }
 }
@@ -5,6 +5,7 @@ use crossterm::event::{
     MouseEvent,
 };
 use fresh::input::key_translator::KeyTranslator;
+use fresh::primitives::path_utils::split_line_col_suffix;
 #[cfg(target_os = "linux")]
 use fresh::services::gpm::{gpm_to_crossterm, GpmClient};
 use fresh::services::terminal_modes::{self, KeyboardConfig, TerminalModes};
@@ -746,13 +747,11 @@ fn handle_first_run_setup(
 /// - file.txt:10:5
 /// - /path/to/file.txt:10:5
 ///
-/// For Windows paths like C:\path\file.txt:10:5, we handle the drive letter
-/// prefix properly using std::path APIs.
+/// Windows drive letters (C:\path\file.txt:10:5) are never mistaken for a
+/// line number; see `split_line_col_suffix`.
 ///
 /// If the full path exists as a file, it's used as-is (handles files with colons in name).
 fn parse_file_location(input: &str) -> FileLocation {
-    use std::path::{Component, Path};
-
     let full_path = PathBuf::from(input);
 
     // If the full path exists as a file, use it directly
@@ -765,72 +764,11 @@ fn parse_file_location(input: &str) -> FileLocation {
         };
     }
 
-    // Check if the path has a Windows drive prefix using std::path
-    let has_prefix = Path::new(input)
-        .components()
-        .next()
-        .map(|c| matches!(c, Component::Prefix(_)))
-        .unwrap_or(false);
-
-    // Calculate where to start looking for :line:col
-    // For Windows paths with prefix (e.g., "C:"), skip past the drive letter and colon
-    let search_start = if has_prefix {
-        // Find the first colon (the drive letter separator) and skip it
-        input.find(':').map(|i| i + 1).unwrap_or(0)
-    } else {
-        0
-    };
-
-    // Find the last colon(s) that could be line:col
-    let suffix = &input[search_start..];
-
-    // Try to parse from the end: look for :col and :line patterns
-    // We work backwards to find numeric suffixes
-    let parts: Vec<&str> = suffix.rsplitn(3, ':').collect();
-
-    match parts.as_slice() {
-        // Could be "col", "line", "rest" or just parts of the path
-        [maybe_col, maybe_line, rest] => {
-            if let (Ok(line), Ok(col)) = (maybe_line.parse::<usize>(), maybe_col.parse::<usize>()) {
-                // Both parsed as numbers: file:line:col
-                let path_str = if has_prefix {
-                    format!("{}{}", &input[..search_start], rest)
-                } else {
-                    rest.to_string()
-                };
-                return FileLocation {
-                    path: PathBuf::from(path_str),
-                    line: Some(line),
-                    column: Some(col),
-                };
-            }
-            // Fall through - not valid line:col format
-        }
-        // Could be "line", "rest" or just parts of the path
-        [maybe_line, rest] => {
-            if let Ok(line) = maybe_line.parse::<usize>() {
-                // Parsed as number: file:line
-                let path_str = if has_prefix {
-                    format!("{}{}", &input[..search_start], rest)
-                } else {
-                    rest.to_string()
-                };
-                return FileLocation {
-                    path: PathBuf::from(path_str),
-                    line: Some(line),
-                    column: None,
-                };
-            }
-            // Fall through - not valid line format
-        }
-        _ => {}
-    }
-
-    // No valid line:col suffix found, treat the whole thing as a path
+    let (path, line, column) = split_line_col_suffix(input);
     FileLocation {
-        path: full_path,
-        line: None,
-        column: None,
+        path: PathBuf::from(path),
+        line,
+        column,
     }
 }
 
@@ -862,35 +800,12 @@ fn parse_location(input: &str) -> ParsedLocation {
                 && !path_and_rest.is_empty()
             {
                 // Now parse path:line:col from path_and_rest
-                // We need to distinguish between path components and line:col suffixes
-                // Strategy: work backwards, try to parse numeric suffixes
-
-                let parts: Vec<&str> = path_and_rest.rsplitn(3, ':').collect();
-
-                let (path, line, column) = match parts.as_slice() {
-                    [maybe_col, maybe_line, rest] => {
-                        if let (Ok(line), Ok(col)) =
-                            (maybe_line.parse::<usize>(), maybe_col.parse::<usize>())
-                        {
-                            (rest.to_string(), Some(line), Some(col))
-                        } else {
-                            (path_and_rest.to_string(), None, None)
-                        }
-                    }
-                    [maybe_line, rest] => {
-                        if let Ok(line) = maybe_line.parse::<usize>() {
-                            (rest.to_string(), Some(line), None)
-                        } else {
-                            (path_and_rest.to_string(), None, None)
-                        }
-                    }
-                    _ => (path_and_rest.to_string(), None, None),
-                };
+                let (path, line, column) = split_line_col_suffix(path_and_rest);
 
                 return ParsedLocation::Remote(RemoteLocation {
                     user: user.to_string(),
                     host: host.to_string(),
-                    path,
+                    path: path.to_string(),
                     line,
                     column,
                 });
@@ -943,6 +858,8 @@ fn connect_remote(remote: &RemoteLocation) -> AnyhowResult<FilesystemResult> {
         host: remote.host.clone(),
         port: None, // TODO: support port in remote location parsing
         identity_file: None,
+        reconnect: remote::ReconnectPolicy::default(),
+        keepalive_interval: Some(remote::DEFAULT_KEEPALIVE_INTERVAL),
     };
 
     // Establish SSH connection (this is async, so we block on it)
@@ -2750,11 +2667,21 @@ where
             needs_render = true;
         }
 
+        // Keep scrolling while a selection drag is held past a split's edge
+        if editor.check_drag_autoscroll_timer() {
+            needs_render = true;
+        }
+
         // Check semantic highlight debounce timer
         if editor.check_semantic_highlight_timer() {
             needs_render = true;
         }
 
+        // Start the Quick Open file query once typing pauses
+        if editor.check_file_suggestions_timer() {
+            needs_render = true;
+        }
+
         // Check completion trigger timer (debounced quick suggestions)
         if editor.check_completion_trigger_timer() {
             needs_render = true;
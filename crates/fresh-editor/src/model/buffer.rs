@@ -1,7 +1,7 @@
 /// Text buffer that uses PieceTree with integrated line tracking
 /// Architecture where the tree is the single source of truth for text and line information
 use crate::model::encoding;
-use crate::model::filesystem::{FileMetadata, FileSystem, WriteOp};
+use crate::model::filesystem::{FileMapping, FileMetadata, FileSystem, WriteOp};
 use crate::model::piece_tree::{
     BufferData, BufferLocation, Cursor, PieceInfo, PieceRangeIter, PieceTree, Position,
     StringBuffer, TreeStats,
@@ -48,6 +48,35 @@ impl std::fmt::Display for SudoSaveRequired {
 
 impl std::error::Error for SudoSaveRequired {}
 
+/// Error returned when the destination filesystem reports too little free
+/// space to hold the file being saved.
+///
+/// The save is refused before any bytes are written, so the existing file
+/// is left untouched.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InsufficientDiskSpace {
+    /// Destination path of the save
+    pub path: PathBuf,
+    /// Bytes the save needs
+    pub required: u64,
+    /// Bytes reported free on the destination filesystem
+    pub available: u64,
+}
+
+impl std::fmt::Display for InsufficientDiskSpace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Not enough disk space to save {}: need {} bytes, {} available",
+            self.path.display(),
+            self.required,
+            self.available
+        )
+    }
+}
+
+impl std::error::Error for InsufficientDiskSpace {}
+
 /// Error returned when a large file has a non-resynchronizable encoding
 /// and requires user confirmation before loading the entire file into memory.
 ///
@@ -88,6 +117,17 @@ pub const LOAD_CHUNK_SIZE: usize = 1024 * 1024;
 /// Chunk alignment for lazy loading (64 KB)
 pub const CHUNK_ALIGNMENT: usize = 64 * 1024;
 
+/// Minimum size for reading a large file through a memory mapping (32 MB)
+/// Smaller large files are loaded in chunks
+pub const MMAP_THRESHOLD: usize = 32 * 1024 * 1024;
+
+/// Path of the backup written before saving over `path` (`path~`)
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push("~");
+    PathBuf::from(name)
+}
+
 /// Line ending format used in the file
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum LineEnding {
@@ -120,6 +160,55 @@ impl LineEnding {
     }
 }
 
+/// Number of each kind of line ending in a piece of text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LineEndingCounts {
+    pub lf: usize,
+    pub crlf: usize,
+    pub cr: usize,
+}
+
+impl LineEndingCounts {
+    /// Count the line endings in `bytes`
+    pub fn count(bytes: &[u8]) -> Self {
+        let mut counts = Self::default();
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\r' if bytes.get(i + 1) == Some(&b'\n') => {
+                    counts.crlf += 1;
+                    i += 1;
+                }
+                b'\r' => counts.cr += 1,
+                b'\n' => counts.lf += 1,
+                _ => {}
+            }
+            i += 1;
+        }
+        counts
+    }
+
+    /// The most common line ending, LF if there is no clear winner
+    pub fn dominant(&self) -> LineEnding {
+        if self.crlf > self.lf && self.crlf > self.cr {
+            LineEnding::CRLF
+        } else if self.cr > self.lf && self.cr > self.crlf {
+            LineEnding::CR
+        } else {
+            LineEnding::LF
+        }
+    }
+
+    /// Whether more than one kind of line ending occurs
+    pub fn is_mixed(&self) -> bool {
+        [self.lf, self.crlf, self.cr]
+            .iter()
+            .filter(|&&count| count > 0)
+            .count()
+            > 1
+    }
+}
+
 /// A write recipe built from the piece tree for saving
 struct WriteRecipe {
     /// The source file path for Copy operations (if any)
@@ -163,6 +252,31 @@ impl WriteRecipe {
             .any(|a| matches!(a, RecipeAction::Copy { .. }))
     }
 
+    /// Merge consecutive Copy actions that read contiguous source ranges.
+    ///
+    /// Scattered edits split the original file into many adjacent pieces;
+    /// without merging, each becomes a separate seek/copy on the remote side.
+    fn coalesce_copies(&mut self) {
+        let mut merged: Vec<RecipeAction> = Vec::with_capacity(self.actions.len());
+        for action in self.actions.drain(..) {
+            if let (
+                Some(RecipeAction::Copy { offset, len }),
+                RecipeAction::Copy {
+                    offset: next_offset,
+                    len: next_len,
+                },
+            ) = (merged.last_mut(), action)
+            {
+                if *offset + *len == next_offset {
+                    *len += next_len;
+                    continue;
+                }
+            }
+            merged.push(action);
+        }
+        self.actions = merged;
+    }
+
     /// Flatten all Insert operations into a single buffer.
     /// Only valid when has_copy_ops() returns false.
     fn flatten_inserts(&self) -> Vec<u8> {
@@ -263,6 +377,17 @@ pub struct TextBuffer {
     /// changed the line ending format and needs conversion on save.
     original_line_ending: LineEnding,
 
+    /// Whether the file had more than one kind of line ending when loaded
+    mixed_line_endings: bool,
+
+    /// Convert every line ending to `line_ending` on the next save, even if
+    /// the format did not change (set by `request_line_ending_normalization`)
+    normalize_pending: bool,
+
+    /// Copy the file on disk to its backup path before the next save
+    /// replaces it (set by `request_backup`)
+    backup_pending: bool,
+
     /// Text encoding format detected from the file (or default for new files)
     encoding: Encoding,
 
@@ -270,6 +395,14 @@ pub struct TextBuffer {
     /// Similar to original_line_ending, tracks what the file had when loaded.
     original_encoding: Encoding,
 
+    /// Whether a byte order mark is written at the start of the file on save.
+    /// The BOM itself is never part of the in-memory text.
+    bom: bool,
+
+    /// Offset in the file of the first byte that could not be decoded when
+    /// loading (it was replaced with U+FFFD). Cleared on save.
+    invalid_byte: Option<usize>,
+
     /// The file size on disk after the last save.
     /// Used for chunked recovery to know the original file size for reconstruction.
     /// Updated when loading from file or after saving.
@@ -311,8 +444,13 @@ impl TextBuffer {
             is_binary: false,
             line_ending,
             original_line_ending: line_ending,
+            mixed_line_endings: false,
+            normalize_pending: false,
+            backup_pending: false,
             encoding,
             original_encoding: encoding,
+            bom: false,
+            invalid_byte: None,
             saved_file_size: None,
             version: 0,
         }
@@ -369,8 +507,13 @@ impl TextBuffer {
             fs,
             line_ending,
             original_line_ending: line_ending,
+            mixed_line_endings: false,
+            normalize_pending: false,
+            backup_pending: false,
             encoding: Encoding::Utf8, // Binary files treated as raw bytes (no conversion)
             original_encoding: Encoding::Utf8,
+            bom: false,
+            invalid_byte: None,
             piece_tree,
             saved_root,
             buffers: vec![buffer],
@@ -389,11 +532,14 @@ impl TextBuffer {
     pub fn from_bytes(content: Vec<u8>, fs: Arc<dyn FileSystem + Send + Sync>) -> Self {
         // Auto-detect encoding and convert to UTF-8 if needed
         let (encoding, utf8_content) = Self::detect_and_convert_encoding(&content);
+        let bom = encoding::detect_bom(&content, encoding);
+        let invalid_byte = encoding::find_invalid_byte(&content, encoding);
 
         let bytes = utf8_content.len();
 
         // Auto-detect line ending format from content
         let line_ending = Self::detect_line_ending(&utf8_content);
+        let mixed_line_endings = LineEndingCounts::count(&utf8_content).is_mixed();
 
         // Create initial StringBuffer with ID 0
         let buffer = StringBuffer::new(0, utf8_content);
@@ -411,8 +557,13 @@ impl TextBuffer {
             fs,
             line_ending,
             original_line_ending: line_ending,
+            mixed_line_endings,
+            normalize_pending: false,
+            backup_pending: false,
             encoding,
             original_encoding: encoding,
+            bom,
+            invalid_byte,
             piece_tree,
             saved_root,
             buffers: vec![buffer],
@@ -435,11 +586,14 @@ impl TextBuffer {
     ) -> Self {
         // Convert from specified encoding to UTF-8
         let utf8_content = encoding::convert_to_utf8(&content, encoding);
+        let bom = encoding::detect_bom(&content, encoding);
+        let invalid_byte = encoding::find_invalid_byte(&content, encoding);
 
         let bytes = utf8_content.len();
 
         // Auto-detect line ending format from content
         let line_ending = Self::detect_line_ending(&utf8_content);
+        let mixed_line_endings = LineEndingCounts::count(&utf8_content).is_mixed();
 
         // Create initial StringBuffer with ID 0
         let buffer = StringBuffer::new(0, utf8_content);
@@ -457,8 +611,13 @@ impl TextBuffer {
             fs,
             line_ending,
             original_line_ending: line_ending,
+            mixed_line_endings,
+            normalize_pending: false,
+            backup_pending: false,
             encoding,
             original_encoding: encoding,
+            bom,
+            invalid_byte,
             piece_tree,
             saved_root,
             buffers: vec![buffer],
@@ -501,8 +660,13 @@ impl TextBuffer {
             is_binary: false,
             line_ending,
             original_line_ending: line_ending,
+            mixed_line_endings: false,
+            normalize_pending: false,
+            backup_pending: false,
             encoding,
             original_encoding: encoding,
+            bom: false,
+            invalid_byte: None,
             saved_file_size: None,
             version: 0,
         }
@@ -653,7 +817,7 @@ impl TextBuffer {
         fs: Arc<dyn FileSystem + Send + Sync>,
         force_full_load: bool,
     ) -> anyhow::Result<Self> {
-        use crate::model::piece_tree::{BufferData, BufferLocation};
+        use crate::model::piece_tree::BufferLocation;
 
         // Read a sample of the file to detect encoding and whether it's binary
         // We read the first 8KB for detection
@@ -706,16 +870,10 @@ impl TextBuffer {
 
         // UTF-8/ASCII files can use lazy loading
         let line_ending = Self::detect_line_ending(&sample);
+        let mixed_line_endings = LineEndingCounts::count(&sample).is_mixed();
 
-        // Create an unloaded buffer that references the entire file
-        let buffer = StringBuffer {
-            id: 0,
-            data: BufferData::Unloaded {
-                file_path: path.to_path_buf(),
-                file_offset: 0,
-                bytes: file_size,
-            },
-        };
+        // Create a mapped or unloaded buffer that references the entire file
+        let buffer = Self::large_file_base_buffer(&*fs, path, file_size);
 
         // Create piece tree with a single piece covering the whole file
         // No line feed count (None) since we're not computing line indexing
@@ -745,13 +903,123 @@ impl TextBuffer {
             is_binary,
             line_ending,
             original_line_ending: line_ending,
+            mixed_line_endings,
+            normalize_pending: false,
+            backup_pending: false,
             encoding,
             original_encoding: encoding,
+            bom: false,
+            invalid_byte: None,
             saved_file_size: Some(file_size),
             version: 0,
         })
     }
 
+    /// The single base buffer covering a large file
+    ///
+    /// Files of at least MMAP_THRESHOLD are read through a mapping when the
+    /// filesystem can provide one, so unmodified regions never live on the
+    /// heap. Otherwise the buffer is unloaded and read in chunks on demand.
+    fn large_file_base_buffer(fs: &dyn FileSystem, path: &Path, file_size: usize) -> StringBuffer {
+        if file_size >= MMAP_THRESHOLD {
+            match fs.map_file(path) {
+                Ok(Some(mapping)) if mapping.len() == file_size => {
+                    return StringBuffer::new_mapped(0, mapping, path.to_path_buf(), 0, file_size);
+                }
+                Ok(_) => {}
+                Err(e) => tracing::debug!("Failed to map {:?}, reading in chunks: {}", path, e),
+            }
+        }
+        StringBuffer::new_unloaded(0, path.to_path_buf(), 0, file_size)
+    }
+
+    /// The mapping this buffer reads `path` through, if any
+    fn file_mapping(&self, path: &Path) -> Option<&FileMapping> {
+        self.buffers.iter().find_map(|buffer| match &buffer.data {
+            BufferData::Mapped {
+                mapping, file_path, ..
+            } if file_path == path => Some(mapping),
+            _ => None,
+        })
+    }
+
+    /// Stop reading from a file mapping once the file changes size on disk
+    ///
+    /// Touching a mapped page past the end of a truncated file faults, so when
+    /// the size no longer matches the mapping, regions the file still covers
+    /// are copied to the heap and the rest fall back to `read_range`, which
+    /// reports an error instead. Returns true if the mapping was dropped.
+    pub fn check_file_mapping(&mut self) -> bool {
+        let Some((path, mapped_len)) = self.buffers.iter().find_map(|buffer| match &buffer.data {
+            BufferData::Mapped {
+                mapping, file_path, ..
+            } => Some((file_path.clone(), mapping.len())),
+            _ => None,
+        }) else {
+            return false;
+        };
+
+        // A deleted file keeps its mapping valid, so only a size change matters
+        let Ok(metadata) = self.fs.metadata(&path) else {
+            return false;
+        };
+        let file_size = metadata.size as usize;
+        if file_size == mapped_len {
+            return false;
+        }
+
+        tracing::warn!(
+            "{:?} changed size from {} to {} bytes, copying mapped regions",
+            path,
+            mapped_len,
+            file_size
+        );
+        for buffer in &mut self.buffers {
+            let BufferData::Mapped {
+                mapping,
+                file_path,
+                file_offset,
+                bytes,
+            } = &buffer.data
+            else {
+                continue;
+            };
+            buffer.data = if file_offset + bytes <= file_size {
+                BufferData::Loaded {
+                    data: mapping.as_bytes()[*file_offset..file_offset + bytes].to_vec(),
+                    line_starts: None,
+                }
+            } else {
+                BufferData::Unloaded {
+                    file_path: file_path.clone(),
+                    file_offset: *file_offset,
+                    bytes: *bytes,
+                }
+            };
+        }
+        true
+    }
+
+    /// Bytes of `path` for a Copy action, served from the mapping if there is one
+    fn read_source_range(
+        &self,
+        path: &Path,
+        offset: u64,
+        len: usize,
+    ) -> io::Result<std::borrow::Cow<'_, [u8]>> {
+        let offset = offset as usize;
+        match self.file_mapping(path) {
+            Some(mapping) if offset + len <= mapping.len() => Ok(std::borrow::Cow::Borrowed(
+                &mapping.as_bytes()[offset..offset + len],
+            )),
+            _ => Ok(std::borrow::Cow::Owned(self.fs.read_range(
+                path,
+                offset as u64,
+                len,
+            )?)),
+        }
+    }
+
     /// Save the buffer to its associated file
     pub fn save(&mut self) -> anyhow::Result<()> {
         if let Some(path) = &self.file_path {
@@ -764,6 +1032,29 @@ impl TextBuffer {
         }
     }
 
+    /// Write the buffer's contents to a temporary file, to be moved over its
+    /// file with sudo
+    ///
+    /// For saving files the user can't write without first trying a normal
+    /// save. The returned info is the same a failed save reports.
+    pub fn prepare_sudo_save(&mut self) -> anyhow::Result<SudoSaveRequired> {
+        let Some(dest_path) = self.file_path.clone() else {
+            anyhow::bail!(io::Error::new(
+                io::ErrorKind::NotFound,
+                "No file path associated with buffer",
+            ));
+        };
+        self.check_file_mapping();
+
+        let original_metadata = self.fs.metadata_if_exists(&dest_path);
+        let recipe = self.build_write_recipe()?;
+        let (temp_path, mut temp_file) = self.create_temp_file(&dest_path)?;
+        self.write_recipe_to_file(&mut temp_file, &recipe)?;
+        temp_file.sync_all()?;
+        drop(temp_file);
+        Ok(self.sudo_save_info(temp_path, &dest_path, original_metadata))
+    }
+
     /// Check if we should use in-place writing to preserve file ownership.
     /// Returns true if the file exists and is owned by a different user.
     /// On Unix, only root or the file owner can change file ownership with chown.
@@ -791,7 +1082,8 @@ impl TextBuffer {
         // 2. The source file exists
         // 3. No line ending conversion is needed
         // 4. No encoding conversion is needed
-        let needs_line_ending_conversion = self.line_ending != self.original_line_ending;
+        let needs_line_ending_conversion =
+            self.line_ending != self.original_line_ending || self.normalize_pending;
         // We need encoding conversion if:
         // - NOT a binary file (binary files preserve raw bytes), AND
         // - Either the encoding changed from the original, OR
@@ -813,8 +1105,8 @@ impl TextBuffer {
         let mut insert_data: Vec<Vec<u8>> = Vec::new();
         let mut actions: Vec<RecipeAction> = Vec::new();
 
-        // Add BOM as the first piece if the target encoding has one
-        if let Some(bom) = target_encoding.bom_bytes() {
+        // Add BOM as the first piece if the file has one and the target encoding supports it
+        if let Some(bom) = target_encoding.bom_bytes().filter(|_| self.bom) {
             insert_data.push(bom.to_vec());
             actions.push(RecipeAction::Insert { index: 0 });
         }
@@ -878,8 +1170,23 @@ impl TextBuffer {
                     actions.push(RecipeAction::Insert { index });
                 }
 
+                // Mapped original file: Copy when possible, else send like loaded data
+                BufferData::Mapped {
+                    file_path,
+                    file_offset,
+                    ..
+                } if matches!(piece_view.location, BufferLocation::Stored(_))
+                    && src_path_for_copy.is_some_and(|src| file_path == src) =>
+                {
+                    actions.push(RecipeAction::Copy {
+                        offset: (*file_offset + piece_view.buffer_offset) as u64,
+                        len: piece_view.bytes as u64,
+                    });
+                }
+
                 // Loaded data: send as Insert
-                BufferData::Loaded { data, .. } => {
+                BufferData::Loaded { .. } | BufferData::Mapped { .. } => {
+                    let data = buffer.get_data().unwrap_or_default();
                     let start = piece_view.buffer_offset;
                     let end = start + piece_view.bytes;
                     let chunk = &data[start..end];
@@ -904,11 +1211,13 @@ impl TextBuffer {
             }
         }
 
-        Ok(WriteRecipe {
+        let mut recipe = WriteRecipe {
             src_path: src_path_for_copy.map(|p| p.to_path_buf()),
             insert_data,
             actions,
-        })
+        };
+        recipe.coalesce_copies();
+        Ok(recipe)
     }
 
     /// Create a temporary file for saving.
@@ -1032,9 +1341,27 @@ impl TextBuffer {
     ///
     /// If the line ending format has been changed (via set_line_ending), all content
     /// will be converted to the new format during save.
+    ///
+    /// After `request_backup`, the existing file is first copied to `path~`.
     pub fn save_to_file<P: AsRef<Path>>(&mut self, path: P) -> anyhow::Result<()> {
         let dest_path = path.as_ref();
         let total = self.total_bytes();
+        self.check_file_mapping();
+
+        // The backup is a full copy of the file on disk, so it needs room too
+        let backup_size = if self.backup_pending {
+            self.fs.metadata(dest_path).map_or(0, |meta| meta.size)
+        } else {
+            0
+        };
+        let required = total as u64 + backup_size;
+        if required > 0 {
+            self.check_available_space(dest_path, required)?;
+        }
+
+        if std::mem::take(&mut self.backup_pending) {
+            self.write_backup(dest_path);
+        }
 
         // Handle empty files
         if total == 0 {
@@ -1066,6 +1393,7 @@ impl TextBuffer {
                 self.fs.write_file(dest_path, &data)
             } else {
                 let src_for_patch = recipe.src_path.as_deref().unwrap_or(dest_path);
+                let ops = self.resolve_mapped_copies(src_for_patch, ops);
                 self.fs.write_patched(src_for_patch, dest_path, &ops)
             };
 
@@ -1091,6 +1419,61 @@ impl TextBuffer {
         Ok(())
     }
 
+    /// Copy the current contents of `dest_path` to its backup path.
+    ///
+    /// Runs before the new contents are written, so the backup always holds
+    /// what was on disk before this save. The copy keeps the original's
+    /// permissions; on remote filesystems it happens on the agent's host.
+    /// A failed backup is logged and doesn't stop the save.
+    fn write_backup(&self, dest_path: &Path) {
+        if !self.fs.exists(dest_path) {
+            return;
+        }
+        let backup = backup_path(dest_path);
+        if let Err(e) = self.fs.copy(dest_path, &backup) {
+            tracing::warn!("Failed to write backup {}: {}", backup.display(), e);
+        }
+    }
+
+    /// Turn Copy ops into Inserts borrowed from the source file's mapping, so
+    /// a local save doesn't read the unchanged regions from disk again
+    fn resolve_mapped_copies<'a>(
+        &'a self,
+        src_path: &Path,
+        ops: Vec<WriteOp<'a>>,
+    ) -> Vec<WriteOp<'a>> {
+        let Some(mapping) = self.file_mapping(src_path) else {
+            return ops;
+        };
+        let bytes = mapping.as_bytes();
+        ops.into_iter()
+            .map(|op| match op {
+                WriteOp::Copy { offset, len } if (offset + len) as usize <= bytes.len() => {
+                    WriteOp::Insert {
+                        data: &bytes[offset as usize..(offset + len) as usize],
+                    }
+                }
+                op => op,
+            })
+            .collect()
+    }
+
+    /// Refuse to start a save that clearly won't fit on the destination.
+    ///
+    /// Saves write a complete new copy before replacing the original, so the
+    /// full size is needed regardless of the existing file. Filesystems that
+    /// can't report free space skip the check.
+    fn check_available_space(&self, dest_path: &Path, required: u64) -> anyhow::Result<()> {
+        match self.fs.available_space(dest_path) {
+            Ok(available) if available < required => Err(anyhow::anyhow!(InsufficientDiskSpace {
+                path: dest_path.to_path_buf(),
+                required,
+                available,
+            })),
+            _ => Ok(()),
+        }
+    }
+
     /// Write using in-place mode to preserve file ownership.
     ///
     /// This is used when the file is owned by a different user and we need
@@ -1232,7 +1615,7 @@ impl TextBuffer {
                     let src_path = recipe.src_path.as_ref().ok_or_else(|| {
                         io::Error::new(io::ErrorKind::InvalidData, "Copy action without source")
                     })?;
-                    let data = self.fs.read_range(src_path, *offset, *len as usize)?;
+                    let data = self.read_source_range(src_path, *offset, *len as usize)?;
                     out_file.write_all(&data)?;
                 }
                 RecipeAction::Insert { index } => {
@@ -1259,8 +1642,9 @@ impl TextBuffer {
         self.consolidate_after_save(dest_path, new_size);
 
         self.mark_saved_snapshot();
-        self.original_line_ending = self.line_ending;
+        self.mark_line_endings_saved();
         self.original_encoding = self.encoding;
+        self.invalid_byte = None;
         Ok(())
     }
 
@@ -1276,8 +1660,9 @@ impl TextBuffer {
         self.consolidate_after_save(&dest_path, new_size);
 
         self.mark_saved_snapshot();
-        self.original_line_ending = self.line_ending;
+        self.mark_line_endings_saved();
         self.original_encoding = self.encoding;
+        self.invalid_byte = None;
         Ok(())
     }
 
@@ -1295,14 +1680,7 @@ impl TextBuffer {
     /// Consolidate large file piece tree into a single piece pointing to the new file.
     /// This ensures that subsequent operations correctly reference the new content and offsets.
     fn consolidate_large_file(&mut self, path: &Path, file_size: usize) {
-        let buffer = StringBuffer {
-            id: 0,
-            data: BufferData::Unloaded {
-                file_path: path.to_path_buf(),
-                file_offset: 0,
-                bytes: file_size,
-            },
-        };
+        let buffer = Self::large_file_base_buffer(&*self.fs, path, file_size);
 
         self.piece_tree = if file_size > 0 {
             PieceTree::new(BufferLocation::Stored(0), 0, file_size, None)
@@ -1351,6 +1729,17 @@ impl TextBuffer {
         dest_path: &Path,
         original_metadata: Option<FileMetadata>,
     ) -> anyhow::Error {
+        anyhow::anyhow!(self.sudo_save_info(temp_path, dest_path, original_metadata))
+    }
+
+    /// What saving `temp_path` over `dest_path` with sudo needs to keep the
+    /// original file's owner and permissions
+    fn sudo_save_info(
+        &self,
+        temp_path: PathBuf,
+        dest_path: &Path,
+        original_metadata: Option<FileMetadata>,
+    ) -> SudoSaveRequired {
         #[cfg(unix)]
         let (uid, gid, mode) = if let Some(ref meta) = original_metadata {
             (
@@ -1369,13 +1758,13 @@ impl TextBuffer {
 
         let _ = original_metadata; // suppress unused warning on non-Unix
 
-        anyhow::anyhow!(SudoSaveRequired {
+        SudoSaveRequired {
             temp_path,
             dest_path: dest_path.to_path_buf(),
             uid,
             gid,
             mode,
-        })
+        }
     }
 
     /// Get the total number of bytes in the document
@@ -2093,7 +2482,10 @@ impl TextBuffer {
     ///
     /// This is called before rendering with &mut access to pre-load all data
     /// that will be needed for the viewport. It estimates the number of bytes
-    /// needed based on the line count and pre-loads them.
+    /// needed based on the line count and pre-loads them, plus one viewport's
+    /// worth of margin above and below so scrolling a page rarely waits on I/O.
+    /// Unloaded regions are fetched in LOAD_CHUNK_SIZE chunks, so only the
+    /// chunks around the viewport are ever read from disk.
     ///
     /// # Arguments
     /// * `start_offset` - The byte offset where the viewport starts
@@ -2102,17 +2494,24 @@ impl TextBuffer {
     /// # Returns
     /// Ok(()) if preparation succeeded, Err if loading failed
     pub fn prepare_viewport(&mut self, start_offset: usize, line_count: usize) -> Result<()> {
+        self.check_file_mapping();
+
         // Estimate how many bytes we need (pessimistic assumption)
         // Average line length is typically 80-100 bytes, but we use 200 to be safe
         let estimated_bytes = line_count.saturating_mul(200);
 
-        // Cap the estimate at the remaining bytes in the document
-        let remaining_bytes = self.total_bytes().saturating_sub(start_offset);
-        let bytes_to_load = estimated_bytes.min(remaining_bytes);
+        // Extend by the margin on both sides, capped at the document bounds
+        let load_start = start_offset.saturating_sub(estimated_bytes);
+        let load_end = start_offset
+            .saturating_add(estimated_bytes.saturating_mul(2))
+            .min(self.total_bytes());
+        if load_start >= load_end {
+            return Ok(());
+        }
 
         // Pre-load with full chunk-splitting support
         // This may load more than we need, but ensures all data is available
-        self.get_text_range_mut(start_offset, bytes_to_load)?;
+        self.get_text_range_mut(load_start, load_end - load_start)?;
 
         Ok(())
     }
@@ -2316,6 +2715,37 @@ impl TextBuffer {
         self.mark_content_modified();
     }
 
+    /// Rewrite every line ending to `line_ending` on the next save
+    ///
+    /// Unlike `set_line_ending`, this also converts a file with mixed line
+    /// endings whose dominant format already matches.
+    pub fn request_line_ending_normalization(&mut self, line_ending: LineEnding) {
+        self.line_ending = line_ending;
+        self.normalize_pending = true;
+        self.mark_content_modified();
+    }
+
+    /// Back up the file on disk to `path~` when the next save replaces it
+    pub fn request_backup(&mut self) {
+        self.backup_pending = true;
+    }
+
+    /// Whether the file has more than one kind of line ending
+    ///
+    /// Detected on load; cleared once a save converts the line endings.
+    pub fn has_mixed_line_endings(&self) -> bool {
+        self.mixed_line_endings && !self.normalize_pending
+    }
+
+    /// Record that the line endings on disk now match `line_ending`
+    fn mark_line_endings_saved(&mut self) {
+        if self.line_ending != self.original_line_ending || self.normalize_pending {
+            self.mixed_line_endings = false;
+        }
+        self.normalize_pending = false;
+        self.original_line_ending = self.line_ending;
+    }
+
     /// Set the default line ending format for a new/empty buffer
     ///
     /// Unlike `set_line_ending`, this does NOT mark the buffer as modified.
@@ -2336,6 +2766,7 @@ impl TextBuffer {
     /// On save, the buffer content will be converted to the new encoding.
     pub fn set_encoding(&mut self, encoding: Encoding) {
         self.encoding = encoding;
+        self.bom = encoding.has_bom();
         self.mark_content_modified();
     }
 
@@ -2346,49 +2777,56 @@ impl TextBuffer {
     pub fn set_default_encoding(&mut self, encoding: Encoding) {
         self.encoding = encoding;
         self.original_encoding = encoding;
+        self.bom = encoding.has_bom();
     }
 
-    /// Detect the line ending format from a sample of bytes
-    ///
-    /// Uses majority voting: counts CRLF, LF-only, and CR-only occurrences
-    /// and returns the most common format.
-    pub fn detect_line_ending(bytes: &[u8]) -> LineEnding {
-        // Only check the first 8KB for line ending detection (same as binary detection)
-        let check_len = bytes.len().min(8 * 1024);
-        let sample = &bytes[..check_len];
+    /// Offset of the first byte of the loaded file that could not be decoded
+    /// in the buffer's encoding
+    pub fn invalid_byte_offset(&self) -> Option<usize> {
+        self.invalid_byte
+    }
 
-        let mut crlf_count = 0;
-        let mut lf_only_count = 0;
-        let mut cr_only_count = 0;
+    /// Whether the file is saved with a byte order mark
+    pub fn has_bom(&self) -> bool {
+        self.bom
+    }
 
-        let mut i = 0;
-        while i < sample.len() {
-            if sample[i] == b'\r' {
-                // Check if this is CRLF
-                if i + 1 < sample.len() && sample[i + 1] == b'\n' {
-                    crlf_count += 1;
-                    i += 2; // Skip both \r and \n
-                    continue;
+    /// Add or remove the byte order mark written on save
+    ///
+    /// UTF-8 switches between the `Utf8` and `Utf8Bom` encodings, while UTF-16
+    /// keeps its encoding. Returns false if the encoding has no BOM.
+    pub fn set_bom(&mut self, bom: bool) -> bool {
+        match self.encoding {
+            Encoding::Utf8 | Encoding::Ascii | Encoding::Utf8Bom => {
+                let encoding = if bom {
+                    Encoding::Utf8Bom
                 } else {
-                    // CR only (old Mac format)
-                    cr_only_count += 1;
+                    Encoding::Utf8
+                };
+                if encoding != self.encoding {
+                    self.set_encoding(encoding);
                 }
-            } else if sample[i] == b'\n' {
-                // LF only (Unix format)
-                lf_only_count += 1;
+                true
             }
-            i += 1;
+            Encoding::Utf16Le | Encoding::Utf16Be => {
+                if bom != self.bom {
+                    self.bom = bom;
+                    self.mark_content_modified();
+                }
+                true
+            }
+            _ => false,
         }
+    }
 
-        // Use majority voting to determine line ending
-        if crlf_count > lf_only_count && crlf_count > cr_only_count {
-            LineEnding::CRLF
-        } else if cr_only_count > lf_only_count && cr_only_count > crlf_count {
-            LineEnding::CR
-        } else {
-            // Default to LF if no clear winner or if LF wins
-            LineEnding::LF
-        }
+    /// Detect the line ending format from a sample of bytes
+    ///
+    /// Uses majority voting: counts CRLF, LF-only, and CR-only occurrences
+    /// and returns the most common format.
+    pub fn detect_line_ending(bytes: &[u8]) -> LineEnding {
+        // Only check the first 8KB for line ending detection (same as binary detection)
+        let check_len = bytes.len().min(8 * 1024);
+        LineEndingCounts::count(&bytes[..check_len]).dominant()
     }
 
     /// Detect the text encoding from a sample of bytes
@@ -4244,6 +4682,160 @@ mod tests {
             }
         }
 
+        #[test]
+        fn test_prepare_viewport_reads_only_nearby_chunks() {
+            use crate::services::fs::{SlowFileSystem, SlowFsConfig};
+            use std::sync::atomic::Ordering;
+
+            let temp_dir = TempDir::new().unwrap();
+            let file_path = temp_dir.path().join("huge.txt");
+
+            // About 8MB of 80-byte lines
+            let line = [b"x".repeat(79), b"\n".to_vec()].concat();
+            let line_count = LOAD_CHUNK_SIZE * 8 / line.len();
+            let file_size = line_count * line.len();
+            let mut file = File::create(&file_path).unwrap();
+            for _ in 0..line_count {
+                file.write_all(&line).unwrap();
+            }
+            file.flush().unwrap();
+
+            let slow_fs = SlowFileSystem::new(Arc::new(StdFileSystem), SlowFsConfig::none());
+            let metrics = slow_fs.metrics().clone();
+            let bytes_read = || metrics.bytes_read.load(Ordering::SeqCst);
+
+            // Opening only reads the detection sample
+            let mut buffer = TextBuffer::load_from_file(&file_path, 1, Arc::new(slow_fs)).unwrap();
+            assert!(buffer.large_file);
+            assert_eq!(bytes_read(), 8 * 1024);
+
+            // The first viewport loads the first chunk only
+            metrics.reset();
+            buffer.prepare_viewport(0, 50).unwrap();
+            assert!(bytes_read() <= LOAD_CHUNK_SIZE, "read {}", bytes_read());
+
+            // Scrolling deep into the file loads the chunk around that viewport
+            metrics.reset();
+            let middle = LOAD_CHUNK_SIZE * 5;
+            buffer.prepare_viewport(middle, 50).unwrap();
+            assert!(bytes_read() <= LOAD_CHUNK_SIZE * 2, "read {}", bytes_read());
+
+            // Scrolling within the loaded chunk reads nothing more
+            metrics.reset();
+            buffer.prepare_viewport(middle + 4000, 50).unwrap();
+            assert_eq!(bytes_read(), 0);
+
+            // Edits layer over the partially loaded file
+            buffer.insert_bytes(middle, b"EDIT".to_vec());
+            assert_eq!(buffer.get_text_range_mut(middle, 6).unwrap(), b"EDITxx");
+            assert_eq!(buffer.total_bytes(), file_size + 4);
+            assert!(!buffer.buffers.iter().all(|b| b.is_loaded()));
+        }
+
+        /// Lines of `line NNNNNNNN\n` totalling at least `min_size` bytes
+        fn numbered_lines(min_size: usize) -> Vec<u8> {
+            let mut content = Vec::with_capacity(min_size + 16);
+            let mut i = 0;
+            while content.len() < min_size {
+                content.extend_from_slice(format!("line {:08}\n", i).as_bytes());
+                i += 1;
+            }
+            content
+        }
+
+        #[test]
+        fn test_mapped_large_file_matches_in_memory_buffer() {
+            let temp_dir = TempDir::new().unwrap();
+            let file_path = temp_dir.path().join("huge.txt");
+            let content = numbered_lines(50 * 1024 * 1024);
+            std::fs::write(&file_path, &content).unwrap();
+            let len = content.len();
+
+            let mut mapped = TextBuffer::load_from_file(&file_path, 1, test_fs()).unwrap();
+            assert!(matches!(mapped.buffers[0].data, BufferData::Mapped { .. }));
+            let mut in_memory = TextBuffer::from_bytes(content, test_fs());
+
+            // Same edits at the start, middle and end
+            for buffer in [&mut mapped, &mut in_memory] {
+                buffer.insert_bytes(len, b"tail\n".to_vec());
+                buffer.delete_bytes(len / 2, 1000);
+                buffer.insert_bytes(len / 2, b"middle".to_vec());
+                buffer.insert_bytes(0, b"head\n".to_vec());
+            }
+
+            assert_eq!(mapped.total_bytes(), in_memory.total_bytes());
+            for offset in [
+                0,
+                1000,
+                len / 2 - 10,
+                len / 2 + 5000,
+                mapped.total_bytes() - 20,
+            ] {
+                assert_eq!(
+                    mapped.get_text_range_mut(offset, 64).unwrap(),
+                    in_memory.get_text_range_mut(offset, 64).unwrap(),
+                    "Mismatch at offset {}",
+                    offset
+                );
+            }
+            // Only the edits live on the heap; the mapped base is never chunked
+            assert!(mapped
+                .buffers
+                .iter()
+                .skip(1)
+                .all(|b| b.get_data().is_some_and(|d| d.len() < 64)));
+
+            let mapped_out = temp_dir.path().join("mapped_out.txt");
+            let memory_out = temp_dir.path().join("memory_out.txt");
+            mapped.save_to_file(&mapped_out).unwrap();
+            in_memory.save_to_file(&memory_out).unwrap();
+            assert!(
+                std::fs::read(&mapped_out).unwrap() == std::fs::read(&memory_out).unwrap(),
+                "Saved files differ"
+            );
+
+            // Saving over the mapped file itself keeps the content intact
+            let expected = std::fs::read(&mapped_out).unwrap();
+            let mut mapped = TextBuffer::load_from_file(&file_path, 1, test_fs()).unwrap();
+            mapped.insert_bytes(len, b"tail\n".to_vec());
+            mapped.delete_bytes(len / 2, 1000);
+            mapped.insert_bytes(len / 2, b"middle".to_vec());
+            mapped.insert_bytes(0, b"head\n".to_vec());
+            mapped.save().unwrap();
+            assert!(std::fs::read(&file_path).unwrap() == expected);
+        }
+
+        #[test]
+        fn test_mapped_file_shrinking_falls_back_to_reads() {
+            let temp_dir = TempDir::new().unwrap();
+            let file_path = temp_dir.path().join("huge.txt");
+            let content = numbered_lines(MMAP_THRESHOLD);
+            std::fs::write(&file_path, &content).unwrap();
+
+            let mut buffer = TextBuffer::load_from_file(&file_path, 1, test_fs()).unwrap();
+            assert!(matches!(buffer.buffers[0].data, BufferData::Mapped { .. }));
+            assert!(!buffer.check_file_mapping());
+
+            // Another process truncates the file underneath the mapping
+            let half = content.len() / 2;
+            std::fs::OpenOptions::new()
+                .write(true)
+                .open(&file_path)
+                .unwrap()
+                .set_len(half as u64)
+                .unwrap();
+
+            assert!(buffer.check_file_mapping());
+            assert!(!buffer
+                .buffers
+                .iter()
+                .any(|b| matches!(b.data, BufferData::Mapped { .. })));
+
+            // The part still on disk reads normally, the rest is an error
+            assert_eq!(buffer.get_text_range_mut(0, 100).unwrap(), &content[..100]);
+            assert!(buffer.get_text_range_mut(content.len() - 100, 100).is_err());
+        }
+
         /// Test that save_to_file works correctly with partially loaded large files
         /// This is a regression test for a bug where saving would silently produce
         /// an empty file if any buffer regions were still unloaded.
@@ -4715,6 +5307,93 @@ mod tests {
         );
     }
 
+    // ===== Write Recipe Tests =====
+
+    #[test]
+    fn test_coalesce_contiguous_copies() {
+        let mut recipe = WriteRecipe {
+            src_path: Some(PathBuf::from("/src")),
+            insert_data: vec![],
+            actions: (0..1000)
+                .map(|i| RecipeAction::Copy {
+                    offset: i * 10,
+                    len: 10,
+                })
+                .collect(),
+        };
+        recipe.coalesce_copies();
+
+        assert_eq!(recipe.actions.len(), 1);
+        assert!(matches!(
+            recipe.actions[0],
+            RecipeAction::Copy {
+                offset: 0,
+                len: 10_000
+            }
+        ));
+    }
+
+    #[test]
+    fn test_coalesce_keeps_gaps_and_inserts() {
+        let mut recipe = WriteRecipe {
+            src_path: Some(PathBuf::from("/src")),
+            insert_data: vec![b"x".to_vec()],
+            actions: vec![
+                RecipeAction::Copy { offset: 0, len: 5 },
+                RecipeAction::Copy { offset: 5, len: 5 },
+                // Gap in the source: a deletion
+                RecipeAction::Copy { offset: 20, len: 5 },
+                RecipeAction::Insert { index: 0 },
+                // Contiguous with the previous copy, but separated by an insert
+                RecipeAction::Copy { offset: 25, len: 5 },
+            ],
+        };
+        recipe.coalesce_copies();
+
+        let ops: Vec<(u64, u64)> = recipe
+            .actions
+            .iter()
+            .filter_map(|a| match a {
+                RecipeAction::Copy { offset, len } => Some((*offset, *len)),
+                RecipeAction::Insert { .. } => None,
+            })
+            .collect();
+        assert_eq!(ops, vec![(0, 10), (20, 5), (25, 5)]);
+        assert_eq!(recipe.actions.len(), 4);
+    }
+
+    #[test]
+    fn test_write_recipe_coalesces_undone_edits() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("large.txt");
+        let content: Vec<u8> = (0..10_000).map(|i| b'a' + (i % 26) as u8).collect();
+        std::fs::write(&file_path, &content).unwrap();
+
+        // Large file mode keeps the original content unloaded, so it saves via Copy ops
+        let mut buffer = TextBuffer::load_from_file(&file_path, 1024, test_fs()).unwrap();
+
+        // Insert and remove text at scattered offsets, splitting the original piece
+        for offset in (100..9_000).step_by(500) {
+            buffer.insert_bytes(offset, b"tmp".to_vec());
+            buffer.delete_bytes(offset, 3);
+        }
+
+        let recipe = buffer.build_write_recipe().unwrap();
+        assert_eq!(recipe.actions.len(), 1, "actions: {:?}", recipe.actions);
+        assert!(matches!(
+            recipe.actions[0],
+            RecipeAction::Copy {
+                offset: 0,
+                len: 10_000
+            }
+        ));
+
+        buffer.save_to_file(&file_path).unwrap();
+        assert_eq!(std::fs::read(&file_path).unwrap(), content);
+    }
+
     // ===== Line Ending Conversion Tests =====
 
     mod line_ending_conversion {
@@ -4779,6 +5458,44 @@ mod tests {
             assert!(buffer.is_modified());
         }
 
+        #[test]
+        fn test_line_ending_counts() {
+            let counts = LineEndingCounts::count(b"a\nb\r\nc\r\nd\re");
+            assert_eq!(
+                counts,
+                LineEndingCounts {
+                    lf: 1,
+                    crlf: 2,
+                    cr: 1
+                }
+            );
+            assert_eq!(counts.dominant(), LineEnding::CRLF);
+            assert!(counts.is_mixed());
+            assert!(!LineEndingCounts::count(b"a\r\nb\r\n").is_mixed());
+        }
+
+        #[test]
+        fn test_normalize_mixed_line_endings_on_save() {
+            use tempfile::TempDir;
+
+            let temp_dir = TempDir::new().unwrap();
+            let file_path = temp_dir.path().join("mixed.txt");
+            std::fs::write(&file_path, b"a\nb\nc\r\n").unwrap();
+
+            let mut buffer =
+                TextBuffer::load_from_file(&file_path, DEFAULT_LARGE_FILE_THRESHOLD, test_fs())
+                    .unwrap();
+            assert_eq!(buffer.line_ending(), LineEnding::LF);
+            assert!(buffer.has_mixed_line_endings());
+
+            // The dominant format already matches, so only normalizing converts
+            buffer.request_line_ending_normalization(LineEnding::LF);
+            assert!(buffer.is_modified());
+            buffer.save().unwrap();
+            assert_eq!(std::fs::read(&file_path).unwrap(), b"a\nb\nc\n");
+            assert!(!buffer.has_mixed_line_endings());
+        }
+
         #[test]
         fn test_set_default_line_ending_does_not_mark_modified() {
             let mut buffer = TextBuffer::empty(test_fs());
@@ -4926,6 +5643,227 @@ mod tests {
         }
     }
 
+    mod save_disk_space {
+        use super::*;
+        use crate::model::filesystem::{
+            DirEntry, FileMetadata, FilePermissions, FileReader, FileWriter, WriteOp,
+        };
+        use std::io;
+        use std::path::{Path, PathBuf};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tempfile::TempDir;
+
+        /// A filesystem wrapper that reports a fixed amount of free space and
+        /// counts every operation that could write file content.
+        struct LowDiskFileSystem {
+            inner: StdFileSystem,
+            available: u64,
+            writes: AtomicUsize,
+        }
+
+        impl LowDiskFileSystem {
+            fn new(available: u64) -> Self {
+                Self {
+                    inner: StdFileSystem,
+                    available,
+                    writes: AtomicUsize::new(0),
+                }
+            }
+
+            fn record_write(&self) {
+                self.writes.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        impl FileSystem for LowDiskFileSystem {
+            fn available_space(&self, _path: &Path) -> io::Result<u64> {
+                Ok(self.available)
+            }
+
+            fn read_file(&self, path: &Path) -> io::Result<Vec<u8>> {
+                self.inner.read_file(path)
+            }
+
+            fn read_range(&self, path: &Path, offset: u64, len: usize) -> io::Result<Vec<u8>> {
+                self.inner.read_range(path, offset, len)
+            }
+
+            fn write_file(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+                self.record_write();
+                self.inner.write_file(path, data)
+            }
+
+            fn write_patched(
+                &self,
+                src_path: &Path,
+                dst_path: &Path,
+                ops: &[WriteOp],
+            ) -> io::Result<()> {
+                self.record_write();
+                self.inner.write_patched(src_path, dst_path, ops)
+            }
+
+            fn create_file(&self, path: &Path) -> io::Result<Box<dyn FileWriter>> {
+                self.record_write();
+                self.inner.create_file(path)
+            }
+
+            fn open_file(&self, path: &Path) -> io::Result<Box<dyn FileReader>> {
+                self.inner.open_file(path)
+            }
+
+            fn open_file_for_write(&self, path: &Path) -> io::Result<Box<dyn FileWriter>> {
+                self.record_write();
+                self.inner.open_file_for_write(path)
+            }
+
+            fn open_file_for_append(&self, path: &Path) -> io::Result<Box<dyn FileWriter>> {
+                self.record_write();
+                self.inner.open_file_for_append(path)
+            }
+
+            fn set_file_length(&self, path: &Path, len: u64) -> io::Result<()> {
+                self.record_write();
+                self.inner.set_file_length(path, len)
+            }
+
+            fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+                self.inner.rename(from, to)
+            }
+
+            fn copy(&self, from: &Path, to: &Path) -> io::Result<u64> {
+                self.record_write();
+                self.inner.copy(from, to)
+            }
+
+            fn remove_file(&self, path: &Path) -> io::Result<()> {
+                self.inner.remove_file(path)
+            }
+
+            fn remove_dir(&self, path: &Path) -> io::Result<()> {
+                self.inner.remove_dir(path)
+            }
+
+            fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+                self.inner.metadata(path)
+            }
+
+            fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+                self.inner.symlink_metadata(path)
+            }
+
+            fn is_dir(&self, path: &Path) -> io::Result<bool> {
+                self.inner.is_dir(path)
+            }
+
+            fn is_file(&self, path: &Path) -> io::Result<bool> {
+                self.inner.is_file(path)
+            }
+
+            fn set_permissions(
+                &self,
+                path: &Path,
+                permissions: &FilePermissions,
+            ) -> io::Result<()> {
+                self.inner.set_permissions(path, permissions)
+            }
+
+            fn read_dir(&self, path: &Path) -> io::Result<Vec<DirEntry>> {
+                self.inner.read_dir(path)
+            }
+
+            fn create_dir(&self, path: &Path) -> io::Result<()> {
+                self.inner.create_dir(path)
+            }
+
+            fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+                self.inner.create_dir_all(path)
+            }
+
+            fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+                self.inner.canonicalize(path)
+            }
+
+            fn current_uid(&self) -> u32 {
+                self.inner.current_uid()
+            }
+
+            fn sudo_write(
+                &self,
+                path: &Path,
+                data: &[u8],
+                mode: u32,
+                uid: u32,
+                gid: u32,
+            ) -> io::Result<()> {
+                self.record_write();
+                self.inner.sudo_write(path, data, mode, uid, gid)
+            }
+        }
+
+        /// A save larger than the reported free space is rejected before any write.
+        #[test]
+        fn test_save_rejected_when_disk_space_insufficient() {
+            let temp_dir = TempDir::new().unwrap();
+            let file_path = temp_dir.path().join("big.txt");
+            std::fs::write(&file_path, "original content\n").unwrap();
+
+            let fs = Arc::new(LowDiskFileSystem::new(16));
+            let content = "x".repeat(1024);
+            let mut buffer = TextBuffer::from_bytes(content.into_bytes(), fs.clone());
+
+            let err = buffer.save_to_file(&file_path).unwrap_err();
+            let space_err = err
+                .downcast_ref::<InsufficientDiskSpace>()
+                .unwrap_or_else(|| panic!("Expected InsufficientDiskSpace error, got: {:?}", err));
+            assert_eq!(space_err.path, file_path);
+            assert_eq!(space_err.required, 1024);
+            assert_eq!(space_err.available, 16);
+
+            assert_eq!(fs.writes.load(Ordering::SeqCst), 0, "No bytes written");
+            assert_eq!(
+                std::fs::read_to_string(&file_path).unwrap(),
+                "original content\n"
+            );
+        }
+
+        /// A save that fits in the reported free space goes through as usual.
+        #[test]
+        fn test_save_allowed_when_disk_space_sufficient() {
+            let temp_dir = TempDir::new().unwrap();
+            let file_path = temp_dir.path().join("small.txt");
+
+            let fs = Arc::new(LowDiskFileSystem::new(1024));
+            let mut buffer = TextBuffer::from_bytes(b"hello\n".to_vec(), fs.clone());
+
+            buffer.save_to_file(&file_path).unwrap();
+            assert!(fs.writes.load(Ordering::SeqCst) > 0);
+            assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "hello\n");
+        }
+
+        /// A requested backup needs room for a copy of the file on disk, and
+        /// is not written when the save is refused.
+        #[test]
+        fn test_backup_counts_toward_required_space() {
+            let temp_dir = TempDir::new().unwrap();
+            let file_path = temp_dir.path().join("backed.txt");
+            std::fs::write(&file_path, "original content\n").unwrap();
+
+            let fs = Arc::new(LowDiskFileSystem::new(20));
+            let mut buffer = TextBuffer::from_bytes(b"new content\n".to_vec(), fs.clone());
+            buffer.request_backup();
+
+            let err = buffer.save_to_file(&file_path).unwrap_err();
+            let space_err = err.downcast_ref::<InsufficientDiskSpace>().unwrap();
+            assert_eq!(space_err.required, 12 + 17);
+            assert!(!temp_dir.path().join("backed.txt~").exists());
+            assert_eq!(
+                std::fs::read_to_string(&file_path).unwrap(),
+                "original content\n"
+            );
+        }
+    }
+
     mod large_file_encoding_tests {
         use super::*;
 
@@ -5343,6 +6281,55 @@ mod property_tests {
         }
     }
 
+    proptest! {
+        #[test]
+        fn prop_coalesced_recipe_after_edits_on_large_file(
+            text in prop::collection::vec(prop_oneof![(b'a'..=b'z').prop_map(|c| c), Just(b'\n')], 200..2000),
+            operations in operation_strategy()
+        ) {
+            use tempfile::TempDir;
+
+            let temp_dir = TempDir::new().unwrap();
+            let file_path = temp_dir.path().join("test.txt");
+            std::fs::write(&file_path, &text).unwrap();
+
+            // Large file mode so unmodified regions become Copy ops
+            let mut buffer = TextBuffer::load_from_file(&file_path, 100, test_fs()).unwrap();
+            // Unloaded content can't be read back with get_all_text, so mirror the edits
+            let mut expected = text.clone();
+
+            for op in operations {
+                match op {
+                    Operation::Insert { offset, text } => {
+                        let offset = offset.min(buffer.total_bytes());
+                        expected.splice(offset..offset, text.iter().copied());
+                        buffer.insert_bytes(offset, text);
+                    }
+                    Operation::Delete { offset, bytes } => {
+                        if offset < buffer.total_bytes() {
+                            let bytes = bytes.min(buffer.total_bytes() - offset);
+                            if bytes > 0 {
+                                expected.drain(offset..offset + bytes);
+                                buffer.delete_bytes(offset, bytes);
+                            }
+                        }
+                    }
+                }
+            }
+
+            let recipe = buffer.build_write_recipe().expect("build_write_recipe should succeed");
+            let output = apply_recipe(&buffer, &recipe);
+            prop_assert_eq!(output, expected, "Coalesced recipe should match buffer content");
+
+            // No two adjacent Copy ops should be mergeable
+            for pair in recipe.actions.windows(2) {
+                if let [RecipeAction::Copy { offset, len }, RecipeAction::Copy { offset: next, .. }] = pair {
+                    prop_assert_ne!(offset + len, *next, "Adjacent contiguous copies left unmerged");
+                }
+            }
+        }
+    }
+
     /// Helper to apply a WriteRecipe and return the resulting bytes
     fn apply_recipe(buffer: &TextBuffer, recipe: &WriteRecipe) -> Vec<u8> {
         let mut output = Vec::new();
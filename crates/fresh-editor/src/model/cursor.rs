@@ -29,8 +29,9 @@ pub struct Cursor {
     pub anchor: Option<usize>,
 
     /// Desired column for vertical navigation
-    /// When moving up/down, try to stay in this column
-    pub sticky_column: usize,
+    /// When moving up/down, try to stay in this column.
+    /// `None` means no goal column is set; `Some(0)` is a real column 0.
+    pub sticky_column: Option<usize>,
 
     /// Selection mode (normal or block)
     pub selection_mode: SelectionMode,
@@ -50,7 +51,7 @@ impl Cursor {
         Self {
             position,
             anchor: None,
-            sticky_column: 0,
+            sticky_column: None,
             selection_mode: SelectionMode::Normal,
             block_anchor: None,
             deselect_on_move: true, // Default: movement clears selection
@@ -62,7 +63,7 @@ impl Cursor {
         Self {
             position: end,
             anchor: Some(start),
-            sticky_column: 0,
+            sticky_column: None,
             selection_mode: SelectionMode::Normal,
             block_anchor: None,
             deselect_on_move: true, // Default: movement clears selection
@@ -8,8 +8,8 @@
 //! # Encoding Detection Strategy
 //!
 //! 1. **BOM Detection**: Check for Byte Order Marks (UTF-8 BOM, UTF-16 LE/BE)
-//! 2. **UTF-8 Validation**: Fast path for most modern files
-//! 3. **UTF-16 Heuristics**: Detect UTF-16 without BOM via null byte patterns
+//! 2. **UTF-16 Heuristics**: Detect UTF-16 without BOM via null byte patterns
+//! 3. **UTF-8 Validation**: Fast path for most modern files
 //! 4. **Binary Detection**: Check for control characters that indicate binary content
 //! 5. **Statistical Detection**: Use chardetng for legacy encoding detection
 //! 6. **Fallback**: Default to Windows-1252 for ambiguous cases
@@ -226,8 +226,8 @@ pub fn detect_encoding(bytes: &[u8]) -> Encoding {
 /// # Detection Strategy
 ///
 /// 1. Check for BOM (Byte Order Mark) - highest priority, definitely not binary
-/// 2. Try UTF-8 validation (fast path for most files), definitely not binary
-/// 3. Check for UTF-16 patterns without BOM, definitely not binary
+/// 2. Check for UTF-16 patterns without BOM, definitely not binary
+/// 3. Try UTF-8 validation (fast path for most files), definitely not binary
 /// 4. Check for binary control characters (null bytes, etc.) - if found, it's binary
 /// 5. Use chardetng for statistical detection of legacy encodings
 /// 6. If encoding detection is uncertain, default to Windows-1252
@@ -248,7 +248,43 @@ pub fn detect_encoding_or_binary(bytes: &[u8]) -> (Encoding, bool) {
         return (Encoding::Utf16Be, false);
     }
 
-    // 2. Try UTF-8 validation (fast path for most modern files)
+    // 2. Check for UTF-16 without BOM (common in some Windows files)
+    // Heuristic: Look for patterns of null bytes alternating with printable chars
+    // The non-null byte should be printable (0x20-0x7E) or a valid high byte.
+    // This runs before UTF-8 validation because NUL-interleaved ASCII is also
+    // valid UTF-8 and would otherwise be flagged as binary.
+    //
+    // Note: Unlike UTF-8 below, this heuristic is robust to sample truncation because:
+    // - We use statistical pattern matching (50% threshold), not strict validation
+    // - chunks(2) naturally handles odd-length samples by dropping the last byte
+    // - Losing 1 pair out of ~4096 doesn't affect the detection threshold
+    if sample.len() >= 4 {
+        let is_printable_or_high = |b: u8| (0x20..=0x7E).contains(&b) || b >= 0x80;
+
+        // Align to even boundary to ensure we only process complete 2-byte pairs
+        let aligned_len = sample.len() & !1; // Round down to even
+        let aligned_sample = &sample[..aligned_len];
+
+        let le_pairs = aligned_sample
+            .chunks(2)
+            .filter(|chunk| chunk[1] == 0 && is_printable_or_high(chunk[0]))
+            .count();
+        let be_pairs = aligned_sample
+            .chunks(2)
+            .filter(|chunk| chunk[0] == 0 && is_printable_or_high(chunk[1]))
+            .count();
+        let pair_count = aligned_len / 2;
+
+        // If more than 50% of pairs look like valid UTF-16 text, it's text
+        if le_pairs > pair_count / 2 {
+            return (Encoding::Utf16Le, false);
+        }
+        if be_pairs > pair_count / 2 {
+            return (Encoding::Utf16Be, false);
+        }
+    }
+
+    // 3. Try UTF-8 validation (fast path for most modern files)
     // Note: When we truncate to 8KB, we may cut in the middle of a multi-byte UTF-8 sequence.
     // We need to handle this case - if most of the sample is valid UTF-8 and the only error
     // is an incomplete sequence at the very end, we should still detect it as UTF-8.
@@ -284,40 +320,6 @@ pub fn detect_encoding_or_binary(bytes: &[u8]) -> (Encoding, bool) {
         return (Encoding::Utf8, false);
     }
 
-    // 3. Check for UTF-16 without BOM (common in some Windows files)
-    // Heuristic: Look for patterns of null bytes alternating with printable chars
-    // The non-null byte should be printable (0x20-0x7E) or a valid high byte
-    //
-    // Note: Unlike UTF-8 above, this heuristic is robust to sample truncation because:
-    // - We use statistical pattern matching (50% threshold), not strict validation
-    // - chunks(2) naturally handles odd-length samples by dropping the last byte
-    // - Losing 1 pair out of ~4096 doesn't affect the detection threshold
-    if sample.len() >= 4 {
-        let is_printable_or_high = |b: u8| (0x20..=0x7E).contains(&b) || b >= 0x80;
-
-        // Align to even boundary to ensure we only process complete 2-byte pairs
-        let aligned_len = sample.len() & !1; // Round down to even
-        let aligned_sample = &sample[..aligned_len];
-
-        let le_pairs = aligned_sample
-            .chunks(2)
-            .filter(|chunk| chunk[1] == 0 && is_printable_or_high(chunk[0]))
-            .count();
-        let be_pairs = aligned_sample
-            .chunks(2)
-            .filter(|chunk| chunk[0] == 0 && is_printable_or_high(chunk[1]))
-            .count();
-        let pair_count = aligned_len / 2;
-
-        // If more than 50% of pairs look like valid UTF-16 text, it's text
-        if le_pairs > pair_count / 2 {
-            return (Encoding::Utf16Le, false);
-        }
-        if be_pairs > pair_count / 2 {
-            return (Encoding::Utf16Be, false);
-        }
-    }
-
     // 4. Check for binary indicators EARLY (before chardetng)
     // Binary files often contain control characters and null bytes that should not
     // appear in any valid text encoding. Check this before chardetng because
@@ -543,6 +545,51 @@ pub fn detect_and_convert(bytes: &[u8]) -> (Encoding, Vec<u8>) {
     }
 }
 
+/// Whether a file in `encoding` carries a byte order mark, judging by its raw bytes
+///
+/// `Utf8Bom` always has one; UTF-16 only if the bytes start with it, since
+/// UTF-16 can also be detected without a BOM.
+pub fn detect_bom(bytes: &[u8], encoding: Encoding) -> bool {
+    encoding == Encoding::Utf8Bom
+        || encoding
+            .bom_bytes()
+            .is_some_and(|bom| bytes.starts_with(bom))
+}
+
+/// Byte offset of the first sequence in `bytes` that is invalid in `encoding`
+///
+/// Decoding replaces such sequences with U+FFFD; this lets callers report
+/// where that happened. UTF-8 content is stored as-is and never reports.
+pub fn find_invalid_byte(bytes: &[u8], encoding: Encoding) -> Option<usize> {
+    if matches!(
+        encoding,
+        Encoding::Utf8 | Encoding::Utf8Bom | Encoding::Ascii
+    ) {
+        return None;
+    }
+    let start = encoding
+        .bom_bytes()
+        .filter(|bom| bytes.starts_with(bom))
+        .map_or(0, |bom| bom.len());
+    let data = &bytes[start..];
+
+    let mut decoder = encoding.to_encoding_rs().new_decoder_without_bom_handling();
+    let mut out = vec![0u8; decoder.max_utf8_buffer_length_without_replacement(data.len())?];
+    let mut read_total = 0;
+    loop {
+        let (result, read, _) =
+            decoder.decode_to_utf8_without_replacement(&data[read_total..], &mut out, true);
+        read_total += read;
+        match result {
+            encoding_rs::DecoderResult::InputEmpty => return None,
+            encoding_rs::DecoderResult::OutputFull => {}
+            encoding_rs::DecoderResult::Malformed(bad, extra) => {
+                return Some(start + read_total - extra as usize - bad as usize);
+            }
+        }
+    }
+}
+
 /// Convert bytes from a specific encoding to UTF-8
 ///
 /// Used when opening a file with a user-specified encoding instead of auto-detection.
@@ -559,11 +606,10 @@ pub fn convert_to_utf8(bytes: &[u8], encoding: Encoding) -> Vec<u8> {
         }
         Encoding::Utf8Bom => {
             // Skip the BOM (3 bytes) if present and use the rest
-            if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) && bytes.len() > 3 {
-                bytes[3..].to_vec()
-            } else {
-                bytes.to_vec()
-            }
+            bytes
+                .strip_prefix(&[0xEF, 0xBB, 0xBF])
+                .unwrap_or(bytes)
+                .to_vec()
         }
         Encoding::Utf16Le | Encoding::Utf16Be => {
             // Decode UTF-16 to UTF-8
@@ -672,6 +718,45 @@ mod tests {
         assert_eq!(detect_encoding(&utf16_le_bom), Encoding::Utf16Le);
     }
 
+    #[test]
+    fn test_detect_utf16_le_without_bom() {
+        // NUL-interleaved ASCII is valid UTF-8 too, but must not be read as binary
+        let utf16_le: Vec<u8> = "Hello\n"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        assert_eq!(
+            detect_encoding_or_binary(&utf16_le),
+            (Encoding::Utf16Le, false)
+        );
+    }
+
+    #[test]
+    fn test_detect_bom() {
+        assert!(detect_bom(&[0xFF, 0xFE, b'H', 0x00], Encoding::Utf16Le));
+        assert!(!detect_bom(&[b'H', 0x00, b'i', 0x00], Encoding::Utf16Le));
+        assert!(!detect_bom(b"Hi", Encoding::Utf8));
+        // A BOM-only UTF-8 file decodes to empty text
+        assert!(detect_bom(&[0xEF, 0xBB, 0xBF], Encoding::Utf8Bom));
+        assert!(convert_to_utf8(&[0xEF, 0xBB, 0xBF], Encoding::Utf8Bom).is_empty());
+    }
+
+    #[test]
+    fn test_find_invalid_byte() {
+        // Latin-1 maps every byte, so it never fails
+        assert_eq!(
+            find_invalid_byte(&[0x63, 0xE9, 0xFF], Encoding::Latin1),
+            None
+        );
+        // 0x81 0x20 is not a valid Shift-JIS sequence
+        let sjis = [b'a', b'b', 0x81, 0x20, b'c'];
+        assert_eq!(find_invalid_byte(&sjis, Encoding::ShiftJis), Some(2));
+        // Offsets count the BOM; a lone low surrogate is invalid UTF-16
+        let utf16 = [0xFF, 0xFE, b'a', 0x00, 0x00, 0xDC];
+        assert_eq!(find_invalid_byte(&utf16, Encoding::Utf16Le), Some(4));
+        assert_eq!(find_invalid_byte(&[0xFF, 0xFE], Encoding::Utf8), None);
+    }
+
     #[test]
     fn test_detect_binary() {
         let binary_data = [0x00, 0x01, 0x02, 0x03];
@@ -5,6 +5,7 @@ pub use fresh_core::{BufferId, ContainerId, CursorId, LeafId, SplitDirection, Sp
 use serde::{Deserialize, Serialize};
 use std::ops::Range;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Core event types representing all possible state changes
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,8 +31,8 @@ pub enum Event {
         new_position: usize,
         old_anchor: Option<usize>,
         new_anchor: Option<usize>,
-        old_sticky_column: usize,
-        new_sticky_column: usize,
+        old_sticky_column: Option<usize>,
+        new_sticky_column: Option<usize>,
     },
 
     /// Add a new cursor
@@ -213,6 +214,9 @@ pub enum Event {
         old_cursors: Vec<(CursorId, usize, Option<usize>)>,
         /// Cursor states after the edit
         new_cursors: Vec<(CursorId, usize, Option<usize>)>,
+        /// The edits as (position, deleted length, inserted length), in the
+        /// order they were applied, for keeping other positions on their text
+        edits: Vec<(usize, usize, usize)>,
         /// Human-readable description
         description: String,
     },
@@ -445,15 +449,22 @@ impl Event {
                 new_snapshot,
                 old_cursors,
                 new_cursors,
+                edits,
                 description,
             } => {
                 // Inverse swaps both snapshots and cursor states
                 // For undo: old becomes new, new becomes old
+                // The edits are undone last to first
                 Some(Self::BulkEdit {
                     old_snapshot: new_snapshot.clone(),
                     new_snapshot: old_snapshot.clone(),
                     old_cursors: new_cursors.clone(),
                     new_cursors: old_cursors.clone(),
+                    edits: edits
+                        .iter()
+                        .rev()
+                        .map(|&(position, deleted, inserted)| (position, inserted, deleted))
+                        .collect(),
                     description: format!("Undo: {}", description),
                 })
             }
@@ -471,6 +482,37 @@ impl Event {
         }
     }
 
+    /// The buffer edits of this event as (position, deleted length, inserted
+    /// length), in the order they apply
+    pub fn edit_lengths(&self) -> Vec<(usize, usize, usize)> {
+        match self {
+            Self::Insert { position, text, .. } => vec![(*position, 0, text.len())],
+            Self::Delete { range, .. } => vec![(range.start, range.len(), 0)],
+            Self::Batch { events, .. } => events.iter().flat_map(Self::edit_lengths).collect(),
+            Self::BulkEdit { edits, .. } => edits.clone(),
+            _ => vec![],
+        }
+    }
+
+    /// The `edits` of a `BulkEdit` made of (position, delete_len, insert_text)
+    /// edits sorted by position descending
+    ///
+    /// Edits at the same position apply to the original text together, so
+    /// they are merged into one.
+    pub fn bulk_edit_lengths(edits: &[(usize, usize, String)]) -> Vec<(usize, usize, usize)> {
+        let mut lengths: Vec<(usize, usize, usize)> = Vec::new();
+        for (position, delete_len, text) in edits {
+            match lengths.last_mut() {
+                Some(last) if last.0 == *position => {
+                    last.1 += delete_len;
+                    last.2 += text.len();
+                }
+                _ => lengths.push((*position, *delete_len, text.len())),
+            }
+        }
+        lengths
+    }
+
     /// Returns true if this event is a write action (modifies state in a way that should be undoable)
     /// Returns false for readonly actions like cursor movement, scrolling, viewport changes, etc.
     ///
@@ -523,6 +565,10 @@ pub struct LogEntry {
 
     /// Optional description for debugging
     pub description: Option<String>,
+
+    /// Undone and redone together with the write action before it
+    #[serde(default)]
+    pub joins_previous: bool,
 }
 
 impl LogEntry {
@@ -534,6 +580,7 @@ impl LogEntry {
                 .unwrap()
                 .as_millis() as u64,
             description: None,
+            joins_previous: false,
         }
     }
 
@@ -557,6 +604,23 @@ pub struct Snapshot {
     pub cursor_positions: Vec<(CursorId, usize, Option<usize>)>,
 }
 
+/// Whether a typed edit inserted or deleted text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TypingKind {
+    Insert,
+    Delete,
+}
+
+/// The typed edits of the current undo group
+#[derive(Debug, Clone, Copy)]
+struct TypingRun {
+    kind: TypingKind,
+    /// Where the next typed edit has to happen to continue the run
+    position: usize,
+    /// When the last edit of the run was typed
+    last_typed: Instant,
+}
+
 /// The event log - append-only log of all events
 pub struct EventLog {
     /// All logged events
@@ -578,6 +642,9 @@ pub struct EventLog {
     /// Index at which the buffer was last saved (for tracking modified status)
     /// When current_index equals saved_at_index, the buffer is not modified
     saved_at_index: Option<usize>,
+
+    /// Typing that the next typed edit may join for undo
+    typing_run: Option<TypingRun>,
 }
 
 impl EventLog {
@@ -591,12 +658,14 @@ impl EventLog {
             #[cfg(feature = "runtime")]
             stream_file: None,
             saved_at_index: Some(0), // New buffer starts at "saved" state (index 0)
+            typing_run: None,
         }
     }
 
     /// Mark the current position as the saved point
     /// Call this when the buffer is saved to disk
     pub fn mark_saved(&mut self) {
+        self.typing_run = None;
         self.saved_at_index = Some(self.current_index);
     }
 
@@ -703,7 +772,112 @@ impl EventLog {
     }
 
     /// Append an event to the log
+    ///
+    /// Edits and cursor movements end the undo group of typed edits (see
+    /// [`Self::append_typing`]); UI events like overlays and popups don't.
     pub fn append(&mut self, event: Event) -> usize {
+        if event.is_write_action() || matches!(event, Event::MoveCursor { .. }) {
+            self.typing_run = None;
+        }
+        self.push(event, false)
+    }
+
+    /// Append an edit made by typing, undone in one step with the typing
+    /// before it if it continues that typing
+    ///
+    /// An insert continues inserts that ended where it starts, and a delete
+    /// continues deletes that ended next to it, as long as it comes within
+    /// `window` of the previous keystroke. Typing moves the cursor into an
+    /// auto-closed pair or over a closing bracket without ending the run.
+    /// Edits that insert or delete a newline, other edits, other cursor
+    /// movements, saving and
+    /// [`Self::break_undo_group`] start a new undo step. A zero `window`
+    /// gives each typed edit its own step.
+    pub fn append_typing(&mut self, event: Event, now: Instant, window: Duration) -> usize {
+        let run = match &event {
+            Event::Insert { position, text, .. } if !text.contains('\n') => Some(TypingRun {
+                kind: TypingKind::Insert,
+                position: position + text.len(),
+                last_typed: now,
+            }),
+            Event::Delete {
+                range,
+                deleted_text,
+                ..
+            } if !deleted_text.contains('\n') => Some(TypingRun {
+                kind: TypingKind::Delete,
+                position: range.start,
+                last_typed: now,
+            }),
+            _ => None,
+        };
+
+        let previous_run = self.typing_run.take();
+        // Moving the cursor into an auto-closed pair, or over a closing
+        // bracket, continues the typing it follows
+        let run = match (&event, previous_run) {
+            (
+                Event::MoveCursor {
+                    old_position,
+                    new_position,
+                    ..
+                },
+                Some(previous),
+            ) if previous.kind == TypingKind::Insert
+                && *old_position == previous.position
+                && now.duration_since(previous.last_typed) <= window =>
+            {
+                Some(TypingRun {
+                    kind: TypingKind::Insert,
+                    position: *new_position,
+                    last_typed: now,
+                })
+            }
+            _ => run,
+        };
+
+        let joins_previous = match (previous_run, &event) {
+            _ if window.is_zero() || self.can_redo() => false,
+            (Some(previous), _) if now.duration_since(previous.last_typed) > window => false,
+            (Some(previous), Event::Insert { position, .. }) => {
+                previous.kind == TypingKind::Insert && *position == previous.position
+            }
+            (Some(previous), Event::Delete { range, .. }) => {
+                previous.kind == TypingKind::Delete
+                    && (range.end == previous.position || range.start == previous.position)
+            }
+            _ => false,
+        };
+        let joins_previous = joins_previous && run.is_some();
+
+        let index = self.push(event, joins_previous);
+        self.typing_run = run;
+        index
+    }
+
+    /// Start a new undo step, so the next typed edit isn't undone together
+    /// with the typing before it
+    pub fn break_undo_group(&mut self) {
+        self.typing_run = None;
+    }
+
+    /// Make everything logged since `start` a single undo step
+    ///
+    /// Used so a replayed macro is undone at once. Does nothing if the log
+    /// was undone past `start` in the meantime.
+    pub fn join_undo_steps_since(&mut self, start: usize) {
+        self.typing_run = None;
+        let Some(entries) = self.entries.get_mut(start..self.current_index) else {
+            return;
+        };
+        let mut first_write = true;
+        for entry in entries.iter_mut().filter(|e| e.event.is_write_action()) {
+            entry.joins_previous = !first_write;
+            first_write = false;
+        }
+    }
+
+    fn push(&mut self, event: Event, joins_previous: bool) -> usize {
         // When redo history exists (after undo), only write actions are logged.
         // Non-write events (MoveCursor, Scroll, etc.) are still applied to the
         // editor state but not recorded in the log, preserving redo history.
@@ -746,7 +920,8 @@ impl EventLog {
             }
         }
 
-        let entry = LogEntry::new(event);
+        let mut entry = LogEntry::new(event);
+        entry.joins_previous = joins_previous;
         self.entries.push(entry);
         self.current_index = self.entries.len();
 
@@ -788,6 +963,7 @@ impl EventLog {
     /// Collects all events up to and including the first write action, returns their inverses
     /// This processes readonly events (like scrolling) and stops at write events (like Insert/Delete)
     pub fn undo(&mut self) -> Vec<Event> {
+        self.typing_run = None;
         let mut inverse_events = Vec::new();
         let mut found_write_action = false;
 
@@ -796,9 +972,10 @@ impl EventLog {
             self.current_index -= 1;
             let event = &self.entries[self.current_index].event;
 
-            // Check if this is a write action - we'll stop after processing it
+            // Check if this is a write action - we'll stop after processing it,
+            // unless it's grouped with the write action before it
             if event.is_write_action() {
-                found_write_action = true;
+                found_write_action = !self.entries[self.current_index].joins_previous;
             }
 
             // Try to get the inverse of this event
@@ -815,15 +992,18 @@ impl EventLog {
     /// Collects the first write action plus all readonly events after it (until next write action)
     /// This processes readonly events (like scrolling) with write events (like Insert/Delete)
     pub fn redo(&mut self) -> Vec<Event> {
+        self.typing_run = None;
         let mut events = Vec::new();
         let mut found_write_action = false;
 
         // Keep moving forward to collect write action and subsequent readonly events
         while self.can_redo() {
             let event = self.entries[self.current_index].event.clone();
+            let joins_previous = self.entries[self.current_index].joins_previous;
 
-            // If we've already found a write action and this is another write action, stop
-            if found_write_action && event.is_write_action() {
+            // If we've already found a write action and this is another write action
+            // outside its undo group, stop
+            if found_write_action && event.is_write_action() && !joins_previous {
                 // Don't include this event, it's the next write action
                 break;
             }
@@ -1146,8 +1326,8 @@ mod tests {
             new_position: 1,
             old_anchor: None,
             new_anchor: None,
-            old_sticky_column: 0,
-            new_sticky_column: 0,
+            old_sticky_column: None,
+            new_sticky_column: None,
         });
         assert_eq!(log.current_index(), 2);
 
@@ -1164,8 +1344,8 @@ mod tests {
             new_position: 0,
             old_anchor: None,
             new_anchor: None,
-            old_sticky_column: 0,
-            new_sticky_column: 0,
+            old_sticky_column: None,
+            new_sticky_column: None,
         });
         assert!(
             log.can_redo(),
@@ -1262,4 +1442,95 @@ mod tests {
             "Should not be at saved position after undo + new edit"
         );
     }
+
+    fn typed(position: usize, text: &str) -> Event {
+        Event::Insert {
+            position,
+            text: text.to_string(),
+            cursor_id: CursorId(0),
+        }
+    }
+
+    #[test]
+    fn test_typing_is_undone_in_groups() {
+        let mut log = EventLog::new();
+        let window = Duration::from_millis(1000);
+        let start = Instant::now();
+
+        // A word typed in one go is one undo step
+        for (i, c) in ["a", "b", "c"].iter().enumerate() {
+            log.append_typing(typed(i, c), start, window);
+        }
+        // A newline starts a new step, and so does the word after it
+        log.append_typing(typed(3, "\n"), start, window);
+        log.append_typing(typed(4, "d"), start, window);
+        log.append_typing(typed(5, "e"), start, window);
+        // Typing elsewhere starts a new step
+        log.append_typing(typed(0, "x"), start, window);
+        // So does a pause longer than the window
+        let later = start + Duration::from_millis(1500);
+        log.append_typing(typed(1, "y"), later, window);
+
+        let undo_sizes: Vec<usize> = std::iter::from_fn(|| {
+            let events = log.undo();
+            (!events.is_empty()).then_some(events.len())
+        })
+        .collect();
+        assert_eq!(undo_sizes, vec![1, 1, 2, 1, 3]);
+
+        // Redo replays the same groups
+        assert_eq!(log.redo().len(), 3);
+        assert_eq!(log.redo().len(), 1);
+        assert_eq!(log.redo().len(), 2);
+    }
+
+    #[test]
+    fn test_undo_group_boundaries() {
+        let mut log = EventLog::new();
+        let window = Duration::from_millis(1000);
+        let now = Instant::now();
+
+        log.append_typing(typed(0, "a"), now, window);
+        log.break_undo_group();
+        log.append_typing(typed(1, "b"), now, window);
+        log.mark_saved();
+        log.append_typing(typed(2, "c"), now, window);
+        log.append(Event::MoveCursor {
+            cursor_id: CursorId(0),
+            old_position: 3,
+            new_position: 3,
+            old_anchor: None,
+            new_anchor: None,
+            old_sticky_column: None,
+            new_sticky_column: None,
+        });
+        log.append_typing(typed(3, "d"), now, window);
+        // A zero window turns grouping off
+        log.append_typing(typed(4, "e"), now, Duration::ZERO);
+
+        assert!(log.entries().iter().all(|entry| !entry.joins_previous));
+    }
+
+    #[test]
+    fn test_join_undo_steps_since() {
+        let mut log = EventLog::new();
+        log.append(typed(0, "a"));
+        let start = log.current_index();
+        log.append(typed(1, "b"));
+        log.append(typed(2, "\n"));
+        log.append(typed(3, "c"));
+        log.join_undo_steps_since(start);
+
+        assert_eq!(log.undo().len(), 3);
+        assert_eq!(log.undo().len(), 1);
+        assert_eq!(log.redo().len(), 1);
+        assert_eq!(log.redo().len(), 3);
+
+        // Undone past the start, the groups stay as they are
+        log.undo();
+        log.undo();
+        log.join_undo_steps_since(start);
+        assert_eq!(log.redo().len(), 1);
+        assert_eq!(log.redo().len(), 3);
+    }
 }
@@ -11,6 +11,8 @@
 
 use std::io::{self, Read, Seek, Write};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 use std::time::SystemTime;
 
 // ============================================================================
@@ -104,6 +106,10 @@ pub struct FileMetadata {
     pub is_hidden: bool,
     /// Whether the file is read-only
     pub is_readonly: bool,
+    /// Whether the path itself is a symlink (only set by `symlink_metadata`)
+    pub is_symlink: bool,
+    /// Target of the symlink, if `is_symlink` and the target could be read
+    pub symlink_target: Option<PathBuf>,
     /// File owner UID (Unix only)
     #[cfg(unix)]
     pub uid: Option<u32>,
@@ -121,6 +127,8 @@ impl FileMetadata {
             permissions: None,
             is_hidden: false,
             is_readonly: false,
+            is_symlink: false,
+            symlink_target: None,
             #[cfg(unix)]
             uid: None,
             #[cfg(unix)]
@@ -151,6 +159,13 @@ impl FileMetadata {
         self.permissions = Some(permissions);
         self
     }
+
+    /// Builder: mark as a symlink with an optional target
+    pub fn with_symlink(mut self, target: Option<PathBuf>) -> Self {
+        self.is_symlink = true;
+        self.symlink_target = target;
+        self
+    }
 }
 
 impl Default for FileMetadata {
@@ -292,6 +307,70 @@ impl Seek for StdFileReader {
 
 impl FileReader for StdFileReader {}
 
+// ============================================================================
+// File Mapping
+// ============================================================================
+
+/// Read-only view of a whole file's content, such as a memory mapping
+///
+/// Large files backed by a mapping are read straight from the page cache
+/// instead of being copied into heap chunks.
+#[derive(Clone)]
+pub struct FileMapping(Arc<dyn AsRef<[u8]> + Send + Sync>);
+
+impl FileMapping {
+    /// Wrap any byte container (a memory map, or a Vec in tests)
+    pub fn new(bytes: impl AsRef<[u8]> + Send + Sync + 'static) -> Self {
+        Self(Arc::new(bytes))
+    }
+
+    /// The mapped bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        (*self.0).as_ref()
+    }
+
+    /// Length of the mapping in bytes
+    pub fn len(&self) -> usize {
+        self.as_bytes().len()
+    }
+
+    /// Whether the mapping is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl std::fmt::Debug for FileMapping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "FileMapping({} bytes)", self.len())
+    }
+}
+
+// ============================================================================
+// File Watching
+// ============================================================================
+
+/// A watch started by [`FileSystem::watch_changes`]; dropping it stops the
+/// watch
+pub struct FileWatch {
+    _guard: Box<dyn Send>,
+}
+
+impl FileWatch {
+    /// Wrap whatever keeps the watch alive until it is dropped
+    pub fn new(guard: impl Send + 'static) -> Self {
+        Self {
+            _guard: Box::new(guard),
+        }
+    }
+}
+
+impl std::fmt::Debug for FileWatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("FileWatch")
+    }
+}
+
 // ============================================================================
 // FileSystem Trait
 // ============================================================================
@@ -316,6 +395,14 @@ pub trait FileSystem: Send + Sync {
     /// Read a range of bytes from a file (for lazy loading large files)
     fn read_range(&self, path: &Path, offset: u64, len: usize) -> io::Result<Vec<u8>>;
 
+    /// Map a file read-only so large files can be read without copying them
+    ///
+    /// Returns `None` when the backend can't map files (the default), in which
+    /// case callers fall back to `read_range`.
+    fn map_file(&self, _path: &Path) -> io::Result<Option<FileMapping>> {
+        Ok(None)
+    }
+
     /// Write data to file atomically (temp file + rename)
     fn write_file(&self, path: &Path, data: &[u8]) -> io::Result<()>;
 
@@ -372,7 +459,11 @@ pub trait FileSystem: Send + Sync {
     /// Rename/move a file or directory atomically
     fn rename(&self, from: &Path, to: &Path) -> io::Result<()>;
 
-    /// Copy a file (fallback when rename fails across filesystems)
+    /// Copy a file's contents and permissions, returning the bytes copied
+    ///
+    /// Also the fallback when rename fails across filesystems. Remote
+    /// filesystems copy on the remote host, so the data never crosses the
+    /// connection.
     fn copy(&self, from: &Path, to: &Path) -> io::Result<u64>;
 
     /// Remove a file
@@ -381,6 +472,23 @@ pub trait FileSystem: Send + Sync {
     /// Remove an empty directory
     fn remove_dir(&self, path: &Path) -> io::Result<()>;
 
+    /// Remove a directory and everything beneath it
+    ///
+    /// The default implementation walks the tree with `read_dir` and removes
+    /// entries one by one. Backends that can delete a whole tree in a single
+    /// operation (std, remote agent) should override this.
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()> {
+        for entry in self.read_dir(path)? {
+            // Symlinks are removed as links, never followed
+            if entry.entry_type == EntryType::Directory {
+                self.remove_dir_all(&entry.path)?;
+            } else {
+                self.remove_file(&entry.path)?;
+            }
+        }
+        self.remove_dir(path)
+    }
+
     // ========================================================================
     // Metadata Operations
     // ========================================================================
@@ -389,8 +497,28 @@ pub trait FileSystem: Send + Sync {
     fn metadata(&self, path: &Path) -> io::Result<FileMetadata>;
 
     /// Get symlink metadata (doesn't follow symlinks)
+    ///
+    /// For a symlink, `is_symlink` is set and `symlink_target` holds the link target.
     fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata>;
 
+    /// Read the target of a symlink
+    fn read_link(&self, _path: &Path) -> io::Result<PathBuf> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "read_link not supported by this filesystem",
+        ))
+    }
+
+    /// Get the free space in bytes available to the current user on the
+    /// filesystem containing `path` (or its parent directory, if `path` does
+    /// not exist yet). Filesystems that can't report this return `Unsupported`.
+    fn available_space(&self, _path: &Path) -> io::Result<u64> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "available_space not supported by this filesystem",
+        ))
+    }
+
     /// Check if path exists
     fn exists(&self, path: &Path) -> bool {
         self.metadata(path).is_ok()
@@ -401,6 +529,32 @@ pub trait FileSystem: Send + Sync {
         self.metadata(path).ok()
     }
 
+    /// Get metadata for several paths at once, in input order.
+    ///
+    /// Each path succeeds or fails independently. Remote implementations
+    /// override this to batch the lookups into a single round-trip.
+    fn stat_many(&self, paths: &[PathBuf]) -> Vec<io::Result<FileMetadata>> {
+        paths.iter().map(|p| self.metadata(p)).collect()
+    }
+
+    /// Watch a file for changes made by other programs.
+    ///
+    /// `on_change` runs on a background thread each time a burst of changes
+    /// settles. The watch lasts until the returned [`FileWatch`] is dropped.
+    /// Filesystems that can't push change notifications return
+    /// `Unsupported`; callers then poll [`metadata`](Self::metadata) for a
+    /// new modification time.
+    fn watch_changes(
+        &self,
+        _path: &Path,
+        _on_change: Box<dyn Fn() + Send + Sync>,
+    ) -> io::Result<FileWatch> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "watch_changes not supported by this filesystem",
+        ))
+    }
+
     /// Check if path is a directory
     fn is_dir(&self, path: &Path) -> io::Result<bool>;
 
@@ -417,6 +571,46 @@ pub trait FileSystem: Send + Sync {
     /// List entries in a directory (non-recursive)
     fn read_dir(&self, path: &Path) -> io::Result<Vec<DirEntry>>;
 
+    /// Find files under `root` whose relative path matches a glob pattern,
+    /// in a single walk on the host that owns the files.
+    ///
+    /// Returns at most `max_results` paths (0 for unlimited), relative to
+    /// `root`. Once `cancelled` is set the walk stops and fails with
+    /// `Interrupted`. Filesystems without a server-side walk return
+    /// `Unsupported`; callers then walk the tree with
+    /// [`read_dir`](Self::read_dir).
+    fn find_files(
+        &self,
+        _root: &Path,
+        _pattern: &str,
+        _max_results: usize,
+        _cancelled: &AtomicBool,
+    ) -> io::Result<Vec<PathBuf>> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "find_files not supported by this filesystem",
+        ))
+    }
+
+    /// List the files git shows for the work tree at `root`: tracked files,
+    /// plus untracked ones its ignore rules don't exclude.
+    ///
+    /// Git skips ignored directories without entering them. Paths are
+    /// relative to `root`, at most `max_results` of them (0 for unlimited).
+    /// Fails if `root` isn't in a git work tree or git isn't available, and
+    /// with `Interrupted` once `cancelled` is set.
+    fn git_files(
+        &self,
+        _root: &Path,
+        _max_results: usize,
+        _cancelled: &AtomicBool,
+    ) -> io::Result<Vec<PathBuf>> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "git_files not supported by this filesystem",
+        ))
+    }
+
     /// Create a directory
     fn create_dir(&self, path: &Path) -> io::Result<()>;
 
@@ -455,6 +649,17 @@ pub trait FileSystem: Send + Sync {
         }
     }
 
+    /// Check whether the current user may write the file at `path`
+    ///
+    /// Files without any write permission bits count as read-only, even for
+    /// users who could write them anyway. Paths whose metadata can't be read
+    /// count as writable, leaving the error to the save.
+    fn is_writable(&self, path: &Path) -> bool {
+        self.metadata(path)
+            .map(|meta| !meta.is_readonly)
+            .unwrap_or(true)
+    }
+
     /// Get a temporary file path for atomic writes
     fn temp_path_for(&self, path: &Path) -> PathBuf {
         path.with_extension("tmp")
@@ -643,6 +848,8 @@ impl StdFileSystem {
                 permissions: Some(FilePermissions::from_std(meta.permissions())),
                 is_hidden: Self::is_hidden(path),
                 is_readonly: meta.permissions().readonly(),
+                is_symlink: false,
+                symlink_target: None,
                 uid: Some(meta.uid()),
                 gid: Some(meta.gid()),
             }
@@ -655,6 +862,8 @@ impl StdFileSystem {
                 permissions: Some(FilePermissions::from_std(meta.permissions())),
                 is_hidden: Self::is_hidden(path),
                 is_readonly: meta.permissions().readonly(),
+                is_symlink: false,
+                symlink_target: None,
             }
         }
     }
@@ -674,6 +883,16 @@ impl FileSystem for StdFileSystem {
         Ok(buffer)
     }
 
+    #[cfg(feature = "runtime")]
+    fn map_file(&self, path: &Path) -> io::Result<Option<FileMapping>> {
+        let file = std::fs::File::open(path)?;
+        // SAFETY: the map is read-only. Buffers check the file size before each
+        // render and save (TextBuffer::check_file_mapping) and stop reading from
+        // the map once the file has shrunk underneath it.
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        Ok(Some(FileMapping::new(map)))
+    }
+
     fn write_file(&self, path: &Path, data: &[u8]) -> io::Result<()> {
         let original_metadata = self.metadata_if_exists(path);
         let temp_path = self.temp_path_for(path);
@@ -741,6 +960,10 @@ impl FileSystem for StdFileSystem {
         std::fs::remove_dir(path)
     }
 
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()> {
+        std::fs::remove_dir_all(path)
+    }
+
     // Metadata Operations
     fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
         let meta = std::fs::metadata(path)?;
@@ -749,7 +972,16 @@ impl FileSystem for StdFileSystem {
 
     fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
         let meta = std::fs::symlink_metadata(path)?;
-        Ok(Self::build_metadata(path, &meta))
+        let result = Self::build_metadata(path, &meta);
+        if meta.file_type().is_symlink() {
+            Ok(result.with_symlink(std::fs::read_link(path).ok()))
+        } else {
+            Ok(result)
+        }
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        std::fs::read_link(path)
     }
 
     fn is_dir(&self, path: &Path) -> io::Result<bool> {
@@ -795,6 +1027,126 @@ impl FileSystem for StdFileSystem {
         Ok(entries)
     }
 
+    fn git_files(
+        &self,
+        root: &Path,
+        max_results: usize,
+        cancelled: &AtomicBool,
+    ) -> io::Result<Vec<PathBuf>> {
+        let output = std::process::Command::new("git")
+            .args([
+                "ls-files",
+                "-z",
+                "--cached",
+                "--others",
+                "--exclude-standard",
+            ])
+            .current_dir(root)
+            .stderr(std::process::Stdio::null())
+            .output()?;
+        if !output.status.success() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("not a git work tree: {}", root.display()),
+            ));
+        }
+        if cancelled.load(std::sync::atomic::Ordering::Relaxed) {
+            return Err(io::Error::new(
+                io::ErrorKind::Interrupted,
+                "listing cancelled",
+            ));
+        }
+        let limit = if max_results == 0 {
+            usize::MAX
+        } else {
+            max_results
+        };
+        Ok(output
+            .stdout
+            .split(|&byte| byte == 0)
+            // Untracked nested repositories are listed as `dir/`
+            .filter(|path| !path.is_empty() && !path.ends_with(b"/"))
+            .take(limit)
+            .map(|path| PathBuf::from(String::from_utf8_lossy(path).into_owned()))
+            .collect())
+    }
+
+    /// Watches the file's directory with inotify, so a file replaced by a
+    /// rename (as editors and formatters save) is still noticed
+    #[cfg(all(target_os = "linux", feature = "runtime"))]
+    fn watch_changes(
+        &self,
+        path: &Path,
+        on_change: Box<dyn Fn() + Send + Sync>,
+    ) -> io::Result<FileWatch> {
+        use nix::errno::Errno;
+        use nix::poll::{poll, PollFd, PollFlags, PollTimeout};
+        use nix::sys::inotify::{AddWatchFlags, InitFlags, Inotify};
+        use std::os::fd::AsFd;
+        use std::sync::atomic::Ordering;
+
+        /// How long a file must stay quiet before a burst of changes is reported
+        const SETTLE_MS: u16 = 50;
+
+        /// Stops the watch thread when the watch is dropped
+        struct StopOnDrop(Arc<AtomicBool>);
+        impl Drop for StopOnDrop {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::Relaxed);
+            }
+        }
+
+        let name = path
+            .file_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not a file path"))?
+            .to_os_string();
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let inotify = Inotify::init(InitFlags::IN_NONBLOCK | InitFlags::IN_CLOEXEC)?;
+        inotify.add_watch(
+            dir,
+            AddWatchFlags::IN_MODIFY
+                | AddWatchFlags::IN_ATTRIB
+                | AddWatchFlags::IN_CLOSE_WRITE
+                | AddWatchFlags::IN_CREATE
+                | AddWatchFlags::IN_DELETE
+                | AddWatchFlags::IN_MOVED_FROM
+                | AddWatchFlags::IN_MOVED_TO,
+        )?;
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = Arc::clone(&stop);
+        std::thread::Builder::new()
+            .name("file-watch".to_string())
+            .spawn(move || {
+                let mut changed = false;
+                while !stopped.load(Ordering::Relaxed) {
+                    let mut fds = [PollFd::new(inotify.as_fd(), PollFlags::POLLIN)];
+                    match poll(&mut fds, PollTimeout::from(SETTLE_MS)) {
+                        Ok(0) => {
+                            if std::mem::take(&mut changed) {
+                                on_change();
+                            }
+                        }
+                        Ok(_) => match inotify.read_events() {
+                            Ok(events) => {
+                                changed |= events
+                                    .iter()
+                                    .any(|event| event.name.as_deref() == Some(name.as_os_str()));
+                            }
+                            Err(Errno::EAGAIN) => {}
+                            Err(_) => return,
+                        },
+                        Err(Errno::EINTR) => {}
+                        Err(_) => return,
+                    }
+                }
+            })?;
+        Ok(FileWatch::new(StopOnDrop(stop)))
+    }
+
     fn create_dir(&self, path: &Path) -> io::Result<()> {
         std::fs::create_dir(path)
     }
@@ -821,6 +1173,29 @@ impl FileSystem for StdFileSystem {
         }
     }
 
+    fn is_writable(&self, path: &Path) -> bool {
+        let Ok(meta) = std::fs::metadata(path) else {
+            return true;
+        };
+        if meta.permissions().readonly() {
+            return false;
+        }
+        // access() also catches files owned by others and read-only mounts
+        #[cfg(all(unix, feature = "runtime"))]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            let Ok(c_path) = std::ffi::CString::new(path.as_os_str().as_bytes()) else {
+                return true;
+            };
+            // SAFETY: c_path is a valid NUL-terminated string that outlives the call
+            unsafe { libc::access(c_path.as_ptr(), libc::W_OK) == 0 }
+        }
+        #[cfg(not(all(unix, feature = "runtime")))]
+        {
+            true
+        }
+    }
+
     fn sudo_write(
         &self,
         path: &Path,
@@ -957,6 +1332,10 @@ impl FileSystem for NoopFileSystem {
         Self::unsupported()
     }
 
+    fn read_link(&self, _path: &Path) -> io::Result<PathBuf> {
+        Self::unsupported()
+    }
+
     fn is_dir(&self, _path: &Path) -> io::Result<bool> {
         Self::unsupported()
     }
@@ -1119,6 +1498,24 @@ mod tests {
         assert_eq!(fs.read_file(&path).unwrap(), b"updated");
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_is_writable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let fs = StdFileSystem;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("file.txt");
+        fs.write_file(&path, b"content").unwrap();
+        assert!(fs.is_writable(&path));
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o444)).unwrap();
+        assert!(!fs.is_writable(&path));
+
+        // Missing files are left for the save to report
+        assert!(fs.is_writable(&temp_dir.path().join("missing.txt")));
+    }
+
     #[test]
     fn test_write_patched_default_impl() {
         // Test that the default write_patched implementation works correctly
@@ -1185,4 +1582,38 @@ mod tests {
         let result = fs.read_file(&dst_path).unwrap();
         assert_eq!(result, b"All new content");
     }
+
+    #[test]
+    #[cfg(all(target_os = "linux", feature = "runtime"))]
+    fn test_watch_changes_sees_replaced_file() {
+        let fs = StdFileSystem;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("watched.txt");
+        fs.write_file(&path, b"initial").unwrap();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let watch = fs
+            .watch_changes(&path, Box::new(move || tx.send(()).unwrap()))
+            .unwrap();
+        let timeout = std::time::Duration::from_secs(5);
+
+        // Changes to other files in the directory aren't reported
+        fs.write_file(&temp_dir.path().join("other.txt"), b"other")
+            .unwrap();
+        assert!(rx
+            .recv_timeout(std::time::Duration::from_millis(300))
+            .is_err());
+
+        // write_file replaces the file with a rename
+        fs.write_file(&path, b"updated").unwrap();
+        rx.recv_timeout(timeout).unwrap();
+
+        // Nothing is reported once the watch is dropped
+        drop(watch);
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        fs.write_file(&path, b"again").unwrap();
+        assert!(rx
+            .recv_timeout(std::time::Duration::from_millis(300))
+            .is_err());
+    }
 }
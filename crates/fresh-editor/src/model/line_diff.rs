@@ -4,6 +4,7 @@
 //! insertions, deletions, and modifications. It uses a longest common subsequence (LCS)
 //! approach to identify which lines are unchanged, then marks the ranges that differ.
 
+use super::composite_buffer::DiffHunk;
 use std::ops::Range;
 
 /// Type of change detected for a line range
@@ -82,6 +83,178 @@ pub fn diff_lines(saved: &[u8], current: &[u8]) -> LineDiff {
     }
 }
 
+/// Upper bound on the work (lines times edit distance) [`diff_hunks`] spends
+/// before reporting everything between the common prefix and suffix as a
+/// single hunk
+const MAX_DIFF_WORK: usize = 100_000_000;
+
+/// Compare two byte slices line by line and return the hunks that turn `saved` into `current`.
+///
+/// Each hunk covers a maximal run of lines that are not matched on either
+/// side: `old_count == 0` is a pure insertion, `new_count == 0` a pure
+/// deletion, and anything else a change. Line indices are 0-based.
+///
+/// Uses Myers' O(ND) algorithm in linear space, so a few edits in a large
+/// file stay cheap. Inputs that differ too much to diff within
+/// [`MAX_DIFF_WORK`] come back as one hunk.
+pub fn diff_hunks(saved: &[u8], current: &[u8]) -> Vec<DiffHunk> {
+    if saved == current {
+        return vec![];
+    }
+
+    let saved_lines: Vec<&[u8]> = saved.split(|&b| b == b'\n').collect();
+    let current_lines: Vec<&[u8]> = current.split(|&b| b == b'\n').collect();
+
+    let max_cost = MAX_DIFF_WORK / (saved_lines.len() + current_lines.len());
+    let mut matches = Vec::new();
+    myers_matches(&saved_lines, &current_lines, (0, 0), max_cost, &mut matches);
+    let end = LineMatch {
+        saved_idx: saved_lines.len(),
+        current_idx: current_lines.len(),
+    };
+
+    let mut hunks = Vec::new();
+    let (mut saved_pos, mut current_pos) = (0, 0);
+    for m in matches.into_iter().chain(std::iter::once(end)) {
+        if m.saved_idx > saved_pos || m.current_idx > current_pos {
+            hunks.push(DiffHunk::new(
+                saved_pos,
+                m.saved_idx - saved_pos,
+                current_pos,
+                m.current_idx - current_pos,
+            ));
+        }
+        saved_pos = m.saved_idx + 1;
+        current_pos = m.current_idx + 1;
+    }
+    hunks
+}
+
+/// Append the lines matched by a shortest edit script from `saved` to
+/// `current` to `out`, in order, with indices shifted by `offset`.
+///
+/// If the edit distance exceeds `2 * max_cost`, only the common prefix and
+/// suffix are matched.
+fn myers_matches(
+    saved: &[&[u8]],
+    current: &[&[u8]],
+    offset: (usize, usize),
+    max_cost: usize,
+    out: &mut Vec<LineMatch>,
+) {
+    let push_run = |out: &mut Vec<LineMatch>, saved_start: usize, current_start: usize, len| {
+        out.extend((0..len).map(|i| LineMatch {
+            saved_idx: offset.0 + saved_start + i,
+            current_idx: offset.1 + current_start + i,
+        }));
+    };
+
+    let prefix = saved
+        .iter()
+        .zip(current)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = saved[prefix..]
+        .iter()
+        .rev()
+        .zip(current[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    push_run(out, 0, 0, prefix);
+
+    let saved_mid = &saved[prefix..saved.len() - suffix];
+    let current_mid = &current[prefix..current.len() - suffix];
+    let snake = if saved_mid.is_empty() || current_mid.is_empty() {
+        None
+    } else {
+        middle_snake(saved_mid, current_mid, max_cost)
+    };
+    if let Some((x0, y0, x1, y1)) = snake {
+        // Both sides differ at each end, so the edit distance is at least 2
+        // and each half costs strictly less than the whole, which fit
+        let mid_offset = (offset.0 + prefix, offset.1 + prefix);
+        myers_matches(
+            &saved_mid[..x0],
+            &current_mid[..y0],
+            mid_offset,
+            usize::MAX,
+            out,
+        );
+        push_run(out, prefix + x0, prefix + y0, x1 - x0);
+        myers_matches(
+            &saved_mid[x1..],
+            &current_mid[y1..],
+            (mid_offset.0 + x1, mid_offset.1 + y1),
+            usize::MAX,
+            out,
+        );
+    }
+
+    push_run(out, saved.len() - suffix, current.len() - suffix, suffix);
+}
+
+/// Find the middle snake of a shortest edit script from `a` to `b`
+///
+/// Searches forward from the start and backward from the end until the two
+/// meet, returning the snake `(x0, y0)..(x1, y1)` where they do. Gives up
+/// with `None` once the edit distance is known to exceed `2 * max_cost`.
+fn middle_snake(a: &[&[u8]], b: &[&[u8]], max_cost: usize) -> Option<(usize, usize, usize, usize)> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let delta = n - m;
+    let odd = delta % 2 != 0;
+    let d_max = ((n + m + 1) / 2).min(isize::try_from(max_cost).unwrap_or(isize::MAX));
+
+    // Furthest x reached on each diagonal k = x - y, indexed by k + offset;
+    // the backward search works on the reversed sequences
+    let offset = d_max + 1;
+    let mut forward = vec![0isize; 2 * d_max as usize + 3];
+    let mut backward = vec![0isize; 2 * d_max as usize + 3];
+    let furthest = |v: &[isize], k: isize, d: isize| {
+        if k == -d || (k != d && v[(k - 1 + offset) as usize] < v[(k + 1 + offset) as usize]) {
+            v[(k + 1 + offset) as usize]
+        } else {
+            v[(k - 1 + offset) as usize] + 1
+        }
+    };
+
+    for d in 0..=d_max {
+        for k in (-d..=d).step_by(2) {
+            let mut x = furthest(&forward, k, d);
+            let mut y = x - k;
+            let (x0, y0) = (x, y);
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            forward[(k + offset) as usize] = x;
+            let back_k = delta - k;
+            if odd && back_k.abs() < d && x + backward[(back_k + offset) as usize] >= n {
+                return Some((x0 as usize, y0 as usize, x as usize, y as usize));
+            }
+        }
+        for k in (-d..=d).step_by(2) {
+            let mut x = furthest(&backward, k, d);
+            let mut y = x - k;
+            let (x0, y0) = (x, y);
+            while x < n && y < m && a[(n - x - 1) as usize] == b[(m - y - 1) as usize] {
+                x += 1;
+                y += 1;
+            }
+            backward[(k + offset) as usize] = x;
+            let forward_k = delta - k;
+            if !odd && forward_k.abs() <= d && x + forward[(forward_k + offset) as usize] >= n {
+                return Some((
+                    (n - x) as usize,
+                    (m - y) as usize,
+                    (n - x0) as usize,
+                    (m - y0) as usize,
+                ));
+            }
+        }
+    }
+    None
+}
+
 /// Represents a match between saved and current line indices
 #[derive(Debug, Clone, Copy)]
 struct LineMatch {
@@ -486,6 +659,34 @@ mod tests {
         assert!(!diff.equal);
         assert_eq!(diff.changed_lines, vec![0..1]);
     }
+
+    fn spans(hunks: &[DiffHunk]) -> Vec<(usize, usize, usize, usize)> {
+        hunks
+            .iter()
+            .map(|h| (h.old_start, h.old_count, h.new_start, h.new_count))
+            .collect()
+    }
+
+    #[test]
+    fn test_hunks_identical() {
+        assert!(diff_hunks(b"a\nb\n", b"a\nb\n").is_empty());
+    }
+
+    #[test]
+    fn test_hunks_insert_delete_change() {
+        let saved = b"a\nb\nc\nd\ne\n";
+        let current = b"a\nnew\nb\nd\nE\n";
+        assert_eq!(
+            spans(&diff_hunks(saved, current)),
+            vec![(1, 0, 1, 1), (2, 1, 3, 0), (4, 1, 4, 1)]
+        );
+    }
+
+    #[test]
+    fn test_hunks_from_empty() {
+        assert_eq!(spans(&diff_hunks(b"", b"a\nb\n")), vec![(0, 0, 0, 2)]);
+        assert_eq!(spans(&diff_hunks(b"a\nb\n", b"")), vec![(0, 2, 0, 0)]);
+    }
 }
 
 #[cfg(test)]
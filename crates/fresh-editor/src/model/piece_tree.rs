@@ -1,3 +1,4 @@
+use crate::model::filesystem::FileMapping;
 use std::io;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -9,7 +10,8 @@ pub struct Position {
     pub column: usize, // Byte offset within the line
 }
 
-/// Data storage for a buffer - either loaded in memory or unloaded (file reference)
+/// Data storage for a buffer - loaded in memory, mapped from the file, or
+/// unloaded (file reference)
 #[derive(Debug, Clone)]
 pub enum BufferData {
     /// Loaded in memory with optional line indexing
@@ -23,6 +25,13 @@ pub enum BufferData {
         file_offset: usize, // Where in file this buffer starts
         bytes: usize,       // Length of this region
     },
+    /// Read in place from a read-only mapping of the file (no line indexing)
+    Mapped {
+        mapping: FileMapping,
+        file_path: PathBuf,
+        file_offset: usize, // Where in the mapping this buffer starts
+        bytes: usize,       // Length of this region
+    },
 }
 
 /// A string buffer containing a chunk of text data and its line metadata
@@ -74,9 +83,31 @@ impl StringBuffer {
         }
     }
 
-    /// Check if buffer is loaded
+    /// Create buffer for a region of a mapped file
+    pub fn new_mapped(
+        id: usize,
+        mapping: FileMapping,
+        file_path: PathBuf,
+        file_offset: usize,
+        bytes: usize,
+    ) -> Self {
+        StringBuffer {
+            id,
+            data: BufferData::Mapped {
+                mapping,
+                file_path,
+                file_offset,
+                bytes,
+            },
+        }
+    }
+
+    /// Check if buffer is loaded (mapped data counts as loaded)
     pub fn is_loaded(&self) -> bool {
-        matches!(self.data, BufferData::Loaded { .. })
+        matches!(
+            self.data,
+            BufferData::Loaded { .. } | BufferData::Mapped { .. }
+        )
     }
 
     /// Get data reference if loaded, None if unloaded
@@ -86,6 +117,12 @@ impl StringBuffer {
     pub(crate) fn get_data(&self) -> Option<&[u8]> {
         match &self.data {
             BufferData::Loaded { data, .. } => Some(data),
+            BufferData::Mapped {
+                mapping,
+                file_offset,
+                bytes,
+                ..
+            } => Some(&mapping.as_bytes()[*file_offset..*file_offset + *bytes]),
             BufferData::Unloaded { .. } => None,
         }
     }
@@ -118,7 +155,7 @@ impl StringBuffer {
     pub fn get_line_starts(&self) -> Option<&[usize]> {
         match &self.data {
             BufferData::Loaded { line_starts, .. } => line_starts.as_deref(),
-            BufferData::Unloaded { .. } => None,
+            BufferData::Unloaded { .. } | BufferData::Mapped { .. } => None,
         }
     }
 
@@ -126,7 +163,7 @@ impl StringBuffer {
     /// Returns error if buffer is not unloaded or if I/O fails
     pub fn load(&mut self, fs: &dyn crate::model::filesystem::FileSystem) -> io::Result<()> {
         match &self.data {
-            BufferData::Loaded { .. } => Ok(()), // Already loaded
+            BufferData::Loaded { .. } | BufferData::Mapped { .. } => Ok(()), // Already loaded
             BufferData::Unloaded {
                 file_path,
                 file_offset,
@@ -195,7 +232,8 @@ impl StringBuffer {
                     chunk_bytes,
                 ))
             }
-            BufferData::Loaded { .. } => None, // Can't create chunk from loaded buffer
+            // Can't create chunk from loaded or mapped buffer
+            BufferData::Loaded { .. } | BufferData::Mapped { .. } => None,
         }
     }
 
@@ -217,7 +255,7 @@ impl StringBuffer {
             BufferData::Loaded { line_starts, .. } => line_starts
                 .as_ref()
                 .map(|starts| starts.len().saturating_sub(1)),
-            BufferData::Unloaded { .. } => None,
+            BufferData::Unloaded { .. } | BufferData::Mapped { .. } => None,
         }
     }
 
@@ -241,8 +279,8 @@ impl StringBuffer {
 
                 start_offset
             }
-            BufferData::Unloaded { .. } => {
-                // Can't append to unloaded buffer
+            BufferData::Unloaded { .. } | BufferData::Mapped { .. } => {
+                // Can't append to unloaded or read-only mapped buffer
                 0
             }
         }
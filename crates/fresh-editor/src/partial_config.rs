@@ -138,6 +138,7 @@ fn merge_partial<T: Merge + Clone>(target: &mut Option<T>, other: &Option<T>) {
 pub struct PartialEditorConfig {
     pub tab_size: Option<usize>,
     pub auto_indent: Option<bool>,
+    pub undo_group_timeout_ms: Option<u64>,
     pub line_numbers: Option<bool>,
     pub relative_line_numbers: Option<bool>,
     pub scroll_offset: Option<usize>,
@@ -162,6 +163,7 @@ pub struct PartialEditorConfig {
     pub default_line_ending: Option<LineEndingOption>,
     pub trim_trailing_whitespace_on_save: Option<bool>,
     pub ensure_final_newline_on_save: Option<bool>,
+    pub backup_on_save: Option<bool>,
     pub highlight_matching_brackets: Option<bool>,
     pub rainbow_brackets: Option<bool>,
     pub cursor_style: Option<CursorStyle>,
@@ -177,6 +179,9 @@ pub struct PartialEditorConfig {
     pub show_tab_bar: Option<bool>,
     pub show_vertical_scrollbar: Option<bool>,
     pub show_horizontal_scrollbar: Option<bool>,
+    pub show_indent_guides: Option<bool>,
+    pub show_whitespace_warnings: Option<bool>,
+    pub show_whitespace: Option<bool>,
     pub use_terminal_bg: Option<bool>,
     pub rulers: Option<Vec<usize>>,
 }
@@ -185,6 +190,8 @@ impl Merge for PartialEditorConfig {
     fn merge_from(&mut self, other: &Self) {
         self.tab_size.merge_from(&other.tab_size);
         self.auto_indent.merge_from(&other.auto_indent);
+        self.undo_group_timeout_ms
+            .merge_from(&other.undo_group_timeout_ms);
         self.line_numbers.merge_from(&other.line_numbers);
         self.relative_line_numbers
             .merge_from(&other.relative_line_numbers);
@@ -227,6 +234,7 @@ impl Merge for PartialEditorConfig {
             .merge_from(&other.trim_trailing_whitespace_on_save);
         self.ensure_final_newline_on_save
             .merge_from(&other.ensure_final_newline_on_save);
+        self.backup_on_save.merge_from(&other.backup_on_save);
         self.highlight_matching_brackets
             .merge_from(&other.highlight_matching_brackets);
         self.rainbow_brackets.merge_from(&other.rainbow_brackets);
@@ -252,6 +260,11 @@ impl Merge for PartialEditorConfig {
             .merge_from(&other.show_vertical_scrollbar);
         self.show_horizontal_scrollbar
             .merge_from(&other.show_horizontal_scrollbar);
+        self.show_indent_guides
+            .merge_from(&other.show_indent_guides);
+        self.show_whitespace_warnings
+            .merge_from(&other.show_whitespace_warnings);
+        self.show_whitespace.merge_from(&other.show_whitespace);
         self.use_terminal_bg.merge_from(&other.use_terminal_bg);
         self.rulers.merge_from(&other.rulers);
     }
@@ -375,6 +388,7 @@ pub struct PartialLanguageConfig {
     pub grammar: Option<String>,
     pub comment_prefix: Option<String>,
     pub auto_indent: Option<bool>,
+    pub auto_close: Option<bool>,
     pub highlighter: Option<HighlighterPreference>,
     pub textmate_grammar: Option<std::path::PathBuf>,
     pub show_whitespace_tabs: Option<bool>,
@@ -392,6 +406,7 @@ impl Merge for PartialLanguageConfig {
         self.grammar.merge_from(&other.grammar);
         self.comment_prefix.merge_from(&other.comment_prefix);
         self.auto_indent.merge_from(&other.auto_indent);
+        self.auto_close.merge_from(&other.auto_close);
         self.highlighter.merge_from(&other.highlighter);
         self.textmate_grammar.merge_from(&other.textmate_grammar);
         self.show_whitespace_tabs
@@ -430,6 +445,7 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
         Self {
             tab_size: Some(cfg.tab_size),
             auto_indent: Some(cfg.auto_indent),
+            undo_group_timeout_ms: Some(cfg.undo_group_timeout_ms),
             line_numbers: Some(cfg.line_numbers),
             relative_line_numbers: Some(cfg.relative_line_numbers),
             scroll_offset: Some(cfg.scroll_offset),
@@ -454,6 +470,7 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             default_line_ending: Some(cfg.default_line_ending.clone()),
             trim_trailing_whitespace_on_save: Some(cfg.trim_trailing_whitespace_on_save),
             ensure_final_newline_on_save: Some(cfg.ensure_final_newline_on_save),
+            backup_on_save: Some(cfg.backup_on_save),
             highlight_matching_brackets: Some(cfg.highlight_matching_brackets),
             rainbow_brackets: Some(cfg.rainbow_brackets),
             cursor_style: Some(cfg.cursor_style),
@@ -471,6 +488,9 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             show_tab_bar: Some(cfg.show_tab_bar),
             show_vertical_scrollbar: Some(cfg.show_vertical_scrollbar),
             show_horizontal_scrollbar: Some(cfg.show_horizontal_scrollbar),
+            show_indent_guides: Some(cfg.show_indent_guides),
+            show_whitespace_warnings: Some(cfg.show_whitespace_warnings),
+            show_whitespace: Some(cfg.show_whitespace),
             use_terminal_bg: Some(cfg.use_terminal_bg),
             rulers: Some(cfg.rulers.clone()),
         }
@@ -483,6 +503,9 @@ impl PartialEditorConfig {
         crate::config::EditorConfig {
             tab_size: self.tab_size.unwrap_or(defaults.tab_size),
             auto_indent: self.auto_indent.unwrap_or(defaults.auto_indent),
+            undo_group_timeout_ms: self
+                .undo_group_timeout_ms
+                .unwrap_or(defaults.undo_group_timeout_ms),
             line_numbers: self.line_numbers.unwrap_or(defaults.line_numbers),
             relative_line_numbers: self
                 .relative_line_numbers
@@ -543,6 +566,7 @@ impl PartialEditorConfig {
             ensure_final_newline_on_save: self
                 .ensure_final_newline_on_save
                 .unwrap_or(defaults.ensure_final_newline_on_save),
+            backup_on_save: self.backup_on_save.unwrap_or(defaults.backup_on_save),
             highlight_matching_brackets: self
                 .highlight_matching_brackets
                 .unwrap_or(defaults.highlight_matching_brackets),
@@ -578,6 +602,13 @@ impl PartialEditorConfig {
             show_horizontal_scrollbar: self
                 .show_horizontal_scrollbar
                 .unwrap_or(defaults.show_horizontal_scrollbar),
+            show_indent_guides: self
+                .show_indent_guides
+                .unwrap_or(defaults.show_indent_guides),
+            show_whitespace_warnings: self
+                .show_whitespace_warnings
+                .unwrap_or(defaults.show_whitespace_warnings),
+            show_whitespace: self.show_whitespace.unwrap_or(defaults.show_whitespace),
             use_terminal_bg: self.use_terminal_bg.unwrap_or(defaults.use_terminal_bg),
             rulers: self.rulers.unwrap_or_else(|| defaults.rulers.clone()),
         }
@@ -727,6 +758,7 @@ impl From<&LanguageConfig> for PartialLanguageConfig {
             grammar: Some(cfg.grammar.clone()),
             comment_prefix: cfg.comment_prefix.clone(),
             auto_indent: Some(cfg.auto_indent),
+            auto_close: Some(cfg.auto_close),
             highlighter: Some(cfg.highlighter),
             textmate_grammar: cfg.textmate_grammar.clone(),
             show_whitespace_tabs: Some(cfg.show_whitespace_tabs),
@@ -751,6 +783,7 @@ impl PartialLanguageConfig {
                 .comment_prefix
                 .or_else(|| defaults.comment_prefix.clone()),
             auto_indent: self.auto_indent.unwrap_or(defaults.auto_indent),
+            auto_close: self.auto_close.unwrap_or(defaults.auto_close),
             highlighter: self.highlighter.unwrap_or(defaults.highlighter),
             textmate_grammar: self
                 .textmate_grammar
@@ -937,6 +970,7 @@ impl Default for LanguageConfig {
             grammar: String::new(),
             comment_prefix: None,
             auto_indent: true,
+            auto_close: true,
             highlighter: HighlighterPreference::default(),
             textmate_grammar: None,
             show_whitespace_tabs: true,
@@ -0,0 +1,129 @@
+//! Aligning lines on a delimiter.
+//!
+//! The first occurrence of the delimiter on each line is padded into a
+//! column; later occurrences move along with the rest of the line. Widths
+//! are measured visually, so wide characters count for two columns.
+//! Whitespace before the delimiter is replaced by the padding, keeping a
+//! single space if any line had one. Lines without the delimiter are left
+//! alone.
+
+use crate::primitives::display_width::str_width;
+use std::ops::Range;
+
+/// A replacement of `range` (in the original text) with `text`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlignEdit {
+    pub range: Range<usize>,
+    pub text: String,
+}
+
+/// The text of a line before its first delimiter
+struct Cell {
+    /// Byte range of the whitespace between the cell's content and the delimiter
+    gap: Range<usize>,
+    /// Visual width of the cell's content
+    width: usize,
+}
+
+/// Edits that align `delimiter` across `lines`, sorted by position
+///
+/// Each line is given as its start offset and its text without the line
+/// ending.
+pub fn align_edits(lines: &[(usize, &str)], delimiter: &str) -> Vec<AlignEdit> {
+    if delimiter.is_empty() {
+        return Vec::new();
+    }
+
+    let rows: Vec<(usize, &str, Cell)> = lines
+        .iter()
+        .filter_map(|&(start, text)| {
+            let delimiter_at = text.find(delimiter)?;
+            let content = text[..delimiter_at].trim_end();
+            let cell = Cell {
+                gap: start + content.len()..start + delimiter_at,
+                width: str_width(content),
+            };
+            Some((start, text, cell))
+        })
+        .collect();
+
+    // Width and spacing of the column across the lines with the delimiter
+    let (width, spaced) = rows
+        .iter()
+        .fold((0, false), |(width, spaced), (_, _, cell)| {
+            (width.max(cell.width), spaced || !cell.gap.is_empty())
+        });
+
+    let mut edits = Vec::new();
+    for (line_start, text, cell) in rows {
+        let padding = " ".repeat(width - cell.width + usize::from(spaced));
+        let gap = &text[cell.gap.start - line_start..cell.gap.end - line_start];
+        if gap != padding {
+            edits.push(AlignEdit {
+                range: cell.gap,
+                text: padding,
+            });
+        }
+    }
+    edits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Apply edits (sorted by position) to `text`
+    fn apply(text: &str, edits: &[AlignEdit]) -> String {
+        let mut result = text.to_string();
+        for edit in edits.iter().rev() {
+            result.replace_range(edit.range.clone(), &edit.text);
+        }
+        result
+    }
+
+    /// Split `text` into (line start, line text) pairs
+    fn lines(text: &str) -> Vec<(usize, &str)> {
+        let mut start = 0;
+        text.split('\n')
+            .map(|line| {
+                let entry = (start, line);
+                start += line.len() + 1;
+                entry
+            })
+            .collect()
+    }
+
+    fn align(text: &str, delimiter: &str) -> String {
+        apply(text, &align_edits(&lines(text), delimiter))
+    }
+
+    #[test]
+    fn test_align_assignments() {
+        assert_eq!(
+            align("a = 1\nlonger    = 2\n\nmid = 3", "="),
+            "a      = 1\nlonger = 2\n\nmid    = 3"
+        );
+        // Without whitespace before the delimiter none is added
+        assert_eq!(align("a:1\nbbb:2", ":"), "a  :1\nbbb:2");
+    }
+
+    #[test]
+    fn test_align_first_occurrence_only() {
+        assert_eq!(
+            align("a | bb | c\nccc | d | e", "|"),
+            "a   | bb | c\nccc | d | e"
+        );
+    }
+
+    #[test]
+    fn test_align_uses_visual_width() {
+        let aligned = align("名前 = 1\nab = 2", "=");
+        assert_eq!(aligned, "名前 = 1\nab   = 2");
+    }
+
+    #[test]
+    fn test_already_aligned_is_unchanged() {
+        assert!(align_edits(&lines("a  = 1\nbc = 2"), "=").is_empty());
+        assert!(align_edits(&lines("no delimiter"), "=").is_empty());
+    }
+}
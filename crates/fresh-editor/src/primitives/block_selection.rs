@@ -0,0 +1,230 @@
+//! Geometry for block (rectangular) selections.
+//!
+//! A block selection spans from its anchor to the cursor, both given as
+//! (line, visual column). Visual columns account for tabs and wide
+//! characters, and may lie past the end of a short line (virtual space).
+
+use crate::model::buffer::Buffer;
+use crate::model::cursor::{Cursor, Position2D};
+use crate::primitives::display_width::char_width;
+use std::ops::Range;
+
+/// Text of a line without its line ending
+fn line_text(buffer: &Buffer, line: usize) -> String {
+    let bytes = buffer.get_line(line).unwrap_or_default();
+    String::from_utf8_lossy(&bytes)
+        .trim_end_matches(['\n', '\r'])
+        .to_string()
+}
+
+/// Display width of `ch` when it starts at visual column `col`
+fn cell_width(ch: char, col: usize, tab_size: usize) -> usize {
+    if ch == '\t' {
+        let tab_size = tab_size.max(1);
+        tab_size - (col % tab_size)
+    } else {
+        char_width(ch)
+    }
+}
+
+/// Visual column at byte offset `byte` of `text`
+fn column_at(text: &str, byte: usize, tab_size: usize) -> usize {
+    text.char_indices()
+        .take_while(|(idx, _)| *idx < byte)
+        .fold(0, |col, (_, ch)| col + cell_width(ch, col, tab_size))
+}
+
+/// Byte offset of the character covering visual column `column` of `text`,
+/// or the end of the text if the column is past it
+fn byte_at(text: &str, column: usize, tab_size: usize) -> usize {
+    let mut col = 0;
+    for (idx, ch) in text.char_indices() {
+        let width = cell_width(ch, col, tab_size);
+        if column < col + width {
+            return idx;
+        }
+        col += width;
+    }
+    text.len()
+}
+
+/// Convert a byte offset to (line, visual column)
+pub fn byte_to_visual_2d(buffer: &Buffer, byte_pos: usize, tab_size: usize) -> Position2D {
+    let line = buffer.get_line_number(byte_pos);
+    let line_start = buffer.line_start_offset(line).unwrap_or(0);
+    let text = line_text(buffer, line);
+    let column = column_at(&text, byte_pos.saturating_sub(line_start), tab_size);
+    Position2D { line, column }
+}
+
+/// Convert (line, visual column) to a byte offset
+///
+/// Columns in virtual space clamp to the end of the line.
+pub fn visual_2d_to_byte(buffer: &Buffer, pos: Position2D, tab_size: usize) -> usize {
+    let line_start = buffer.line_start_offset(pos.line).unwrap_or(0);
+    let text = line_text(buffer, pos.line);
+    line_start + byte_at(&text, pos.column, tab_size)
+}
+
+/// Visual column one character left or right of `pos` on its line
+///
+/// Stepping left out of virtual space moves one column at a time; stepping
+/// right stops at the end of the line.
+pub fn step_visual_column(
+    buffer: &Buffer,
+    pos: Position2D,
+    forward: bool,
+    tab_size: usize,
+) -> usize {
+    let text = line_text(buffer, pos.line);
+    let byte = byte_at(&text, pos.column, tab_size);
+    if forward {
+        match text[byte..].chars().next() {
+            Some(ch) => column_at(&text, byte + ch.len_utf8(), tab_size),
+            None => pos.column,
+        }
+    } else if pos.column > column_at(&text, byte, tab_size) {
+        pos.column - 1
+    } else {
+        match text[..byte].chars().next_back() {
+            Some(ch) => column_at(&text, byte - ch.len_utf8(), tab_size),
+            None => pos.column,
+        }
+    }
+}
+
+/// The (line, visual column) of a block selection's cursor corner
+///
+/// The sticky column keeps the cursor's virtual column while it passes
+/// through lines shorter than the block.
+pub fn block_cursor_2d(buffer: &Buffer, cursor: &Cursor, tab_size: usize) -> Position2D {
+    let pos = byte_to_visual_2d(buffer, cursor.position, tab_size);
+    Position2D {
+        line: pos.line,
+        column: cursor.sticky_column.unwrap_or(pos.column),
+    }
+}
+
+/// Normalized rectangle (start_line, start_col, end_line, end_col) of a
+/// cursor's block selection; the end column is exclusive
+pub fn block_rect(
+    buffer: &Buffer,
+    cursor: &Cursor,
+    tab_size: usize,
+) -> Option<(usize, usize, usize, usize)> {
+    if !cursor.has_block_selection() {
+        return None;
+    }
+    let anchor = cursor.block_anchor?;
+    let current = block_cursor_2d(buffer, cursor, tab_size);
+    Some((
+        anchor.line.min(current.line),
+        anchor.column.min(current.column),
+        anchor.line.max(current.line),
+        anchor.column.max(current.column),
+    ))
+}
+
+/// Byte range of `line` covered by the visual columns `start_col..end_col`
+///
+/// A tab or wide character straddling an edge is included whole. Columns
+/// past the end of the line clamp to the line end, so a line shorter than
+/// the block yields an empty range at its end.
+pub fn block_line_range(
+    buffer: &Buffer,
+    line: usize,
+    start_col: usize,
+    end_col: usize,
+    tab_size: usize,
+) -> Range<usize> {
+    let line_start = buffer.line_start_offset(line).unwrap_or(0);
+    let text = line_text(buffer, line);
+
+    let mut start = None;
+    let mut end = text.len();
+    let mut col = 0;
+    for (idx, ch) in text.char_indices() {
+        let width = cell_width(ch, col, tab_size);
+        // Zero-width characters stay with the character before them
+        if width > 0 {
+            if start.is_none() && col + width > start_col {
+                start = Some(idx);
+            }
+            if col >= end_col {
+                end = idx;
+                break;
+            }
+        }
+        col += width;
+    }
+
+    let start = start.unwrap_or(text.len()).min(end);
+    // A zero-width block is a column of carets, never a selection
+    let end = if start_col == end_col { start } else { end };
+    line_start + start..line_start + end
+}
+
+/// Spaces that extend `line` out to visual column `column`, and the byte
+/// offset of the line end where they go
+///
+/// Returns None if the line already reaches the column. Typing into a block
+/// that starts in virtual space pads short lines this way, so every row gets
+/// the text at the same column.
+pub fn virtual_space_padding(
+    buffer: &Buffer,
+    line: usize,
+    column: usize,
+    tab_size: usize,
+) -> Option<(usize, String)> {
+    let line_start = buffer.line_start_offset(line)?;
+    let text = line_text(buffer, line);
+    let width = column_at(&text, text.len(), tab_size);
+    (width < column).then(|| (line_start + text.len(), " ".repeat(column - width)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visual_columns_with_tabs_and_wide_chars() {
+        let buffer = Buffer::from_str_test("ab\tx\n日本xy\n");
+        let text = buffer.to_string().unwrap();
+        // The tab expands to column 4
+        let x = text.find('x').unwrap();
+        assert_eq!(
+            byte_to_visual_2d(&buffer, x, 4),
+            Position2D { line: 0, column: 4 }
+        );
+        // Each CJK character is two columns wide
+        let pos = visual_2d_to_byte(&buffer, Position2D { line: 1, column: 4 }, 4);
+        assert_eq!(&text[pos..pos + 1], "x");
+    }
+
+    #[test]
+    fn test_block_line_range() {
+        let buffer = Buffer::from_str_test("abcdef\n日本語\nab\n");
+        let line_start = |line| buffer.line_start_offset(line).unwrap();
+
+        assert_eq!(block_line_range(&buffer, 0, 1, 3, 4), 1..3);
+        // Columns 1 and 2 are halves of 日 and 本, so both are covered whole
+        let l1 = line_start(1);
+        assert_eq!(block_line_range(&buffer, 1, 1, 3, 4), l1..l1 + 6);
+        // A short line clamps to its end
+        let l2 = line_start(2);
+        assert_eq!(block_line_range(&buffer, 2, 3, 5, 4), l2 + 2..l2 + 2);
+        // A zero-width block never selects
+        assert_eq!(block_line_range(&buffer, 1, 3, 3, 4), l1 + 3..l1 + 3);
+    }
+
+    #[test]
+    fn test_virtual_space_padding() {
+        let buffer = Buffer::from_str_test("ab\n日本語\n");
+        assert_eq!(
+            virtual_space_padding(&buffer, 0, 4, 4),
+            Some((2, "  ".to_string()))
+        );
+        // Wide characters already reach column 4
+        assert_eq!(virtual_space_padding(&buffer, 1, 4, 4), None);
+    }
+}
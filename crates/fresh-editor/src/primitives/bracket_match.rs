@@ -0,0 +1,194 @@
+//! Bracket pair matching for select-to-matching-bracket.
+//!
+//! Works on a text slice: finds the bracket under (or just before) a byte
+//! offset, then scans for its partner while tracking nesting. Brackets that
+//! the caller reports as inside a string or comment are ignored, unless the
+//! starting bracket is itself inside one.
+
+use std::ops::Range;
+
+/// Bracket pairs that can be matched, including Unicode angle and corner
+/// brackets. ASCII `<`/`>` are left out since they are usually operators.
+pub const BRACKET_PAIRS: &[(char, char)] = &[
+    ('(', ')'),
+    ('[', ']'),
+    ('{', '}'),
+    ('⟨', '⟩'),
+    ('〈', '〉'),
+    ('《', '》'),
+    ('「', '」'),
+    ('『', '』'),
+    ('【', '】'),
+    ('〔', '〕'),
+];
+
+/// Outcome of looking for a bracket pair at a position
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BracketMatch {
+    /// No bracket at or just before the position
+    NoBracket,
+    /// A bracket was found but has no partner
+    Unmatched,
+    /// Byte range covering both brackets and everything between them
+    Matched(Range<usize>),
+}
+
+/// The pair a bracket belongs to and whether it opens
+fn bracket_pair(ch: char) -> Option<(char, char, bool)> {
+    BRACKET_PAIRS.iter().find_map(|&(open, close)| {
+        if ch == open {
+            Some((open, close, true))
+        } else if ch == close {
+            Some((open, close, false))
+        } else {
+            None
+        }
+    })
+}
+
+/// Find the bracket pair for the bracket at `pos` in `text`
+///
+/// The bracket under the cursor wins; otherwise a bracket ending at `pos`
+/// (cursor just after it) is used. `skip(offset)` returns true for byte
+/// offsets inside strings or comments.
+pub fn match_bracket_at(text: &str, pos: usize, skip: impl Fn(usize) -> bool) -> BracketMatch {
+    if pos > text.len() || !text.is_char_boundary(pos) {
+        return BracketMatch::NoBracket;
+    }
+
+    let under = text[pos..]
+        .chars()
+        .next()
+        .and_then(|ch| bracket_pair(ch).map(|pair| (pos, ch, pair)));
+    let before = || {
+        text[..pos]
+            .char_indices()
+            .next_back()
+            .and_then(|(start, ch)| bracket_pair(ch).map(|pair| (start, ch, pair)))
+    };
+    let Some((start, ch, (open, close, forward))) = under.or_else(before) else {
+        return BracketMatch::NoBracket;
+    };
+
+    let honor_skip = !skip(start);
+    let counts = |offset: usize| !honor_skip || !skip(offset);
+    let mut depth = 0usize;
+
+    if forward {
+        for (offset, c) in text[start..].char_indices() {
+            let offset = start + offset;
+            if (c != open && c != close) || !counts(offset) {
+                continue;
+            }
+            if c == open {
+                depth += 1;
+            } else {
+                depth -= 1;
+                if depth == 0 {
+                    return BracketMatch::Matched(start..offset + c.len_utf8());
+                }
+            }
+        }
+    } else {
+        let end = start + ch.len_utf8();
+        for (offset, c) in text[..end].char_indices().rev() {
+            if (c != open && c != close) || !counts(offset) {
+                continue;
+            }
+            if c == close {
+                depth += 1;
+            } else {
+                depth -= 1;
+                if depth == 0 {
+                    return BracketMatch::Matched(offset..end);
+                }
+            }
+        }
+    }
+    BracketMatch::Unmatched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_skip(_: usize) -> bool {
+        false
+    }
+
+    #[test]
+    fn test_nested_brackets() {
+        let text = "f(a, (b), [c])";
+        assert_eq!(
+            match_bracket_at(text, 1, no_skip),
+            BracketMatch::Matched(1..14)
+        );
+        assert_eq!(
+            match_bracket_at(text, 5, no_skip),
+            BracketMatch::Matched(5..8)
+        );
+        // From the closing bracket
+        assert_eq!(
+            match_bracket_at(text, 13, no_skip),
+            BracketMatch::Matched(1..14)
+        );
+    }
+
+    #[test]
+    fn test_cursor_before_and_after_bracket() {
+        let text = "x {y} z";
+        // Just before `{`: the bracket under the cursor
+        assert_eq!(
+            match_bracket_at(text, 2, no_skip),
+            BracketMatch::Matched(2..5)
+        );
+        // Just after `}`: the bracket before the cursor
+        assert_eq!(
+            match_bracket_at(text, 5, no_skip),
+            BracketMatch::Matched(2..5)
+        );
+        // Not next to a bracket
+        assert_eq!(match_bracket_at(text, 0, no_skip), BracketMatch::NoBracket);
+    }
+
+    #[test]
+    fn test_unicode_brackets() {
+        let text = "「外『内』」 ⟨a⟩";
+        let end = text.find(' ').unwrap();
+        assert_eq!(
+            match_bracket_at(text, 0, no_skip),
+            BracketMatch::Matched(0..end)
+        );
+        let angle = text.find('⟨').unwrap();
+        assert_eq!(
+            match_bracket_at(text, text.len(), no_skip),
+            BracketMatch::Matched(angle..text.len())
+        );
+    }
+
+    #[test]
+    fn test_unmatched_bracket() {
+        assert_eq!(
+            match_bracket_at("(a (b)", 0, no_skip),
+            BracketMatch::Unmatched
+        );
+        assert_eq!(match_bracket_at("a]", 1, no_skip), BracketMatch::Unmatched);
+    }
+
+    #[test]
+    fn test_skips_brackets_in_strings() {
+        let text = "(a \")\" b)";
+        let string = text.find('"').unwrap()..text.rfind('"').unwrap() + 1;
+        let skip = |offset: usize| string.contains(&offset);
+        assert_eq!(
+            match_bracket_at(text, 0, skip),
+            BracketMatch::Matched(0..text.len())
+        );
+        // A bracket inside the string still matches naively
+        let inner = text.find(')').unwrap();
+        assert_eq!(
+            match_bracket_at(text, inner, |offset| string.contains(&offset)),
+            BracketMatch::Matched(0..inner + 1)
+        );
+    }
+}
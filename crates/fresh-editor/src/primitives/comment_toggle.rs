@@ -0,0 +1,356 @@
+//! Line comment toggling.
+//!
+//! Comments are added at the smallest indentation of the affected lines, so
+//! the markers line up in a column. Lines are uncommented only when every
+//! non-blank line is already commented. Languages without a line comment
+//! wrap each line in their block comment markers instead.
+
+use std::ops::Range;
+
+/// The markers used to comment out a line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommentTokens {
+    /// A token that comments to the end of the line, like `//` or `#`
+    Line(String),
+    /// Opening and closing markers, like `/*` and `*/`
+    Block(String, String),
+}
+
+/// Line comment tokens and the languages (ids or syntax names) using them
+const LINE_COMMENTS: &[(&str, &[&str])] = &[
+    (
+        "//",
+        &[
+            "rust",
+            "c",
+            "cpp",
+            "c++",
+            "c_sharp",
+            "csharp",
+            "c#",
+            "go",
+            "java",
+            "javascript",
+            "typescript",
+            "tsx",
+            "jsx",
+            "php",
+            "swift",
+            "kotlin",
+            "scala",
+            "dart",
+            "zig",
+            "odin",
+            "pascal",
+            "typst",
+            "templ",
+            "groovy",
+            "protobuf",
+            "objective-c",
+            "d",
+            "jsonc",
+            "glsl",
+            "hlsl",
+            "wgsl",
+        ],
+    ),
+    (
+        "#",
+        &[
+            "python",
+            "bash",
+            "shell",
+            "sh",
+            "zsh",
+            "fish",
+            "bourne again shell (bash)",
+            "ruby",
+            "perl",
+            "r",
+            "makefile",
+            "dockerfile",
+            "toml",
+            "yaml",
+            "nix",
+            "elixir",
+            "powershell",
+            "julia",
+            "cmake",
+            "gitignore",
+            "gitconfig",
+            "gitattributes",
+            "git-rebase",
+            "git-commit",
+            "tcl",
+            "nim",
+            "crystal",
+            "graphql",
+            "hcl",
+            "terraform",
+            "starlark",
+            "just",
+        ],
+    ),
+    (
+        "--",
+        &["lua", "sql", "haskell", "elm", "ada", "vhdl", "purescript"],
+    ),
+    (
+        ";",
+        &[
+            "lisp",
+            "common lisp",
+            "scheme",
+            "racket",
+            "clojure",
+            "emacs lisp",
+            "ini",
+            "assembly",
+            "asm",
+            "nasm",
+        ],
+    ),
+    (
+        "%",
+        &["latex", "tex", "erlang", "matlab", "octave", "prolog"],
+    ),
+    ("\"", &["vim", "vimscript", "viml"]),
+    ("REM", &["batch file", "batch", "bat"]),
+];
+
+/// Block comment markers for languages without a line comment
+const BLOCK_COMMENTS: &[(&str, &str, &[&str])] = &[
+    ("/*", "*/", &["css"]),
+    (
+        "<!--",
+        "-->",
+        &["html", "xml", "svg", "markdown", "vue", "svelte"],
+    ),
+    ("(*", "*)", &["ocaml", "f#", "fsharp"]),
+];
+
+/// Comment markers for a language, looked up by language id or syntax name
+///
+/// Used when the language configuration has no `comment_prefix`. Names are
+/// matched case-insensitively.
+pub fn builtin_comment_tokens(name: &str) -> Option<CommentTokens> {
+    let name = name.to_ascii_lowercase();
+    let line = LINE_COMMENTS
+        .iter()
+        .find(|(_, names)| names.contains(&name.as_str()))
+        .map(|(token, _)| CommentTokens::Line(token.to_string()));
+    line.or_else(|| {
+        BLOCK_COMMENTS
+            .iter()
+            .find(|(_, _, names)| names.contains(&name.as_str()))
+            .map(|(open, close, _)| CommentTokens::Block(open.to_string(), close.to_string()))
+    })
+}
+
+/// A replacement of `range` (in the original text) with `text`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommentEdit {
+    pub range: Range<usize>,
+    pub text: String,
+}
+
+/// Edits that toggle comments on `lines`, sorted by position
+///
+/// Each line is given as its start offset and its text without the line
+/// ending. Returns whether the lines are being commented (as opposed to
+/// uncommented) along with the edits.
+pub fn toggle_comment_edits(
+    lines: &[(usize, &str)],
+    tokens: &CommentTokens,
+) -> (bool, Vec<CommentEdit>) {
+    let non_blank: Vec<(usize, &str)> = lines
+        .iter()
+        .copied()
+        .filter(|(_, text)| !text.trim().is_empty())
+        .collect();
+    // A block of blank lines is commented as-is
+    let targets = if non_blank.is_empty() {
+        lines.to_vec()
+    } else {
+        non_blank
+    };
+
+    let all_commented = targets
+        .iter()
+        .all(|(_, text)| comment_span(text, tokens).is_some());
+    if all_commented && !targets.is_empty() {
+        let edits = targets
+            .iter()
+            .flat_map(|&(start, text)| {
+                comment_span(text, tokens)
+                    .into_iter()
+                    .flatten()
+                    .map(move |range| CommentEdit {
+                        range: start + range.start..start + range.end,
+                        text: String::new(),
+                    })
+            })
+            .collect();
+        return (false, edits);
+    }
+
+    let indent = targets
+        .iter()
+        .map(|(_, text)| indent_len(text))
+        .min()
+        .unwrap_or(0);
+    let mut edits = Vec::new();
+    for &(start, text) in &targets {
+        let at = start + indent.min(text.len());
+        match tokens {
+            CommentTokens::Line(token) => edits.push(CommentEdit {
+                range: at..at,
+                text: format!("{} ", token),
+            }),
+            CommentTokens::Block(open, close) => {
+                edits.push(CommentEdit {
+                    range: at..at,
+                    text: format!("{} ", open),
+                });
+                let end = start + text.len();
+                edits.push(CommentEdit {
+                    range: end..end,
+                    text: format!(" {}", close),
+                });
+            }
+        }
+    }
+    (true, edits)
+}
+
+/// Byte length of a line's leading spaces and tabs
+fn indent_len(text: &str) -> usize {
+    text.len() - text.trim_start_matches([' ', '\t']).len()
+}
+
+/// The ranges (relative to the line) to delete to uncomment `text`, or
+/// `None` if the line isn't commented
+///
+/// One space after an opening marker (and before a closing one) is removed
+/// along with it.
+fn comment_span(text: &str, tokens: &CommentTokens) -> Option<Vec<Range<usize>>> {
+    let indent = indent_len(text);
+    let rest = &text[indent..];
+    let open = match tokens {
+        CommentTokens::Line(token) | CommentTokens::Block(token, _) => token,
+    };
+    if !rest.starts_with(open.as_str()) {
+        return None;
+    }
+    let mut open_end = indent + open.len();
+    if text[open_end..].starts_with(' ') {
+        open_end += 1;
+    }
+
+    match tokens {
+        CommentTokens::Line(_) => {
+            let marker = indent..open_end;
+            Some(vec![marker])
+        }
+        CommentTokens::Block(_, close) => {
+            let body_end = text.trim_end_matches([' ', '\t']).len();
+            let close_start = body_end.checked_sub(close.len())?;
+            if close_start < indent + open.len() || !text[..body_end].ends_with(close.as_str()) {
+                return None;
+            }
+            let close_start = if close_start > open_end && text[..close_start].ends_with(' ') {
+                close_start - 1
+            } else {
+                close_start
+            };
+            Some(vec![
+                indent..open_end.min(close_start),
+                close_start..body_end,
+            ])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Apply edits (sorted by position) to `text`
+    fn apply(text: &str, edits: &[CommentEdit]) -> String {
+        let mut result = text.to_string();
+        for edit in edits.iter().rev() {
+            result.replace_range(edit.range.clone(), &edit.text);
+        }
+        result
+    }
+
+    /// Split `text` into (line start, line text) pairs
+    fn lines(text: &str) -> Vec<(usize, &str)> {
+        let mut start = 0;
+        text.split('\n')
+            .map(|line| {
+                let entry = (start, line);
+                start += line.len() + 1;
+                entry
+            })
+            .collect()
+    }
+
+    fn toggle(text: &str, tokens: &CommentTokens) -> (bool, String) {
+        let (commenting, edits) = toggle_comment_edits(&lines(text), tokens);
+        (commenting, apply(text, &edits))
+    }
+
+    #[test]
+    fn test_line_comments_align_to_min_indent() {
+        let slashes = CommentTokens::Line("//".into());
+        let text = "    fn a() {\n        b();\n\n    }";
+        let (commenting, commented) = toggle(text, &slashes);
+        assert!(commenting);
+        assert_eq!(commented, "    // fn a() {\n    //     b();\n\n    // }");
+
+        let (commenting, uncommented) = toggle(&commented, &slashes);
+        assert!(!commenting);
+        assert_eq!(uncommented, text);
+    }
+
+    #[test]
+    fn test_partially_commented_lines_are_commented() {
+        let hash = CommentTokens::Line("#".into());
+        let (commenting, result) = toggle("# a\nb", &hash);
+        assert!(commenting);
+        assert_eq!(result, "# # a\n# b");
+    }
+
+    #[test]
+    fn test_uncomment_without_space_after_token() {
+        let hash = CommentTokens::Line("#".into());
+        assert_eq!(toggle("#a\n  # b", &hash).1, "a\n  b");
+    }
+
+    #[test]
+    fn test_block_comment_fallback() {
+        let css = CommentTokens::Block("/*".into(), "*/".into());
+        let text = "a { color: red; }\n  b {}";
+        let (_, commented) = toggle(text, &css);
+        assert_eq!(commented, "/* a { color: red; } */\n/*   b {} */");
+        assert_eq!(toggle(&commented, &css).1, text);
+    }
+
+    #[test]
+    fn test_builtin_tokens() {
+        assert_eq!(
+            builtin_comment_tokens("Lua"),
+            Some(CommentTokens::Line("--".into()))
+        );
+        assert_eq!(
+            builtin_comment_tokens("c_sharp"),
+            Some(CommentTokens::Line("//".into()))
+        );
+        assert_eq!(
+            builtin_comment_tokens("html"),
+            Some(CommentTokens::Block("<!--".into(), "-->".into()))
+        );
+        assert_eq!(builtin_comment_tokens("text"), None);
+    }
+}
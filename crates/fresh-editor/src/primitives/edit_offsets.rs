@@ -0,0 +1,48 @@
+//! Mapping offsets through a batch of text edits.
+//!
+//! Commands that rewrite several ranges at once (comment toggling, on-save
+//! cleanups) apply them as one bulk edit and need to know where each cursor
+//! and selection anchor ends up afterwards.
+
+use std::ops::Range;
+
+/// Map an offset in the original text through edits sorted by position
+///
+/// Each edit is the range it replaces and the length of its replacement.
+/// Offsets inside deleted text move to the start of the deletion, and an
+/// offset where text is inserted stays before the insertion.
+pub fn map_offset(pos: usize, edits: impl IntoIterator<Item = (Range<usize>, usize)>) -> usize {
+    let mut mapped = pos as isize;
+    for (range, new_len) in edits {
+        if range.start >= pos {
+            break;
+        }
+        if range.end > pos {
+            mapped -= (pos - range.start) as isize;
+            break;
+        }
+        mapped += new_len as isize - range.len() as isize;
+    }
+    mapped.max(0) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_offset() {
+        // "ab\ncd" with "// " inserted at the start of both lines
+        let edits = [(0..0, 3), (3..3, 3)];
+        // The start of a line stays before the inserted marker
+        assert_eq!(map_offset(0, edits.clone()), 0);
+        assert_eq!(map_offset(1, edits.clone()), 4);
+        assert_eq!(map_offset(4, edits), 10);
+
+        // "// ab" with the marker removed
+        let edits = [(0..3, 0)];
+        // Offsets inside the removed range move to its start
+        assert_eq!(map_offset(1, edits.clone()), 0);
+        assert_eq!(map_offset(4, edits), 1);
+    }
+}
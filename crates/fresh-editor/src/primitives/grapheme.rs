@@ -9,7 +9,7 @@
 //! A grapheme cluster is what a user perceives as a single character.
 //! For example, Thai "ที่" looks like one character but is 3 Unicode code points.
 
-use unicode_segmentation::UnicodeSegmentation;
+use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
 
 /// Find the byte position of the previous grapheme cluster boundary.
 ///
@@ -97,6 +97,20 @@ pub fn grapheme_at(s: &str, pos: usize) -> Option<(&str, usize, usize)> {
     None
 }
 
+/// Check whether a byte position lies on a grapheme cluster boundary.
+///
+/// The start and end of the string are boundaries; positions inside a
+/// cluster (or inside a UTF-8 sequence) are not.
+#[inline]
+pub fn is_grapheme_boundary(s: &str, pos: usize) -> bool {
+    if pos > s.len() || !s.is_char_boundary(pos) {
+        return false;
+    }
+    GraphemeCursor::new(pos, s.len(), true)
+        .is_boundary(s, 0)
+        .unwrap_or(true)
+}
+
 /// Count the number of grapheme clusters in a string.
 ///
 /// This is what users would count as "characters".
@@ -218,6 +232,18 @@ mod tests {
         assert!(grapheme_at(s, 11).is_none()); // past end
     }
 
+    #[test]
+    fn test_is_grapheme_boundary() {
+        // "e" followed by combining acute accent, then "x"
+        let s = "e\u{0301}x";
+        assert!(is_grapheme_boundary(s, 0));
+        assert!(!is_grapheme_boundary(s, 1)); // before the combining mark
+        assert!(is_grapheme_boundary(s, 3));
+        assert!(is_grapheme_boundary(s, s.len()));
+        // Inside a UTF-8 sequence
+        assert!(!is_grapheme_boundary(s, 2));
+    }
+
     #[test]
     fn test_empty_string() {
         let s = "";
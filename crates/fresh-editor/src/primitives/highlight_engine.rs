@@ -25,7 +25,7 @@ use crate::view::theme::Theme;
 use std::ops::Range;
 use std::path::Path;
 use std::sync::Arc;
-use syntect::parsing::SyntaxSet;
+use syntect::parsing::{ParseState, ScopeStack, SyntaxSet};
 
 /// Map TextMate scope to highlight category
 fn scope_to_category(scope: &str) -> Option<HighlightCategory> {
@@ -198,6 +198,12 @@ pub enum HighlightEngine {
 ///
 /// This struct handles the lifetime complexities of syntect by storing
 /// the syntax set and using indices rather than references.
+///
+/// The parsed region is cached line by line together with the parser state
+/// at the start of each line. An edit marks the lines it touches as dirty,
+/// and the next highlight re-lexes from the first dirty line only until the
+/// parser state at a line start matches the cached one again, so an edit
+/// costs a few lines unless it opens or closes a multi-line construct.
 pub struct TextMateEngine {
     syntax_set: Arc<SyntaxSet>,
     syntax_index: usize,
@@ -210,8 +216,25 @@ pub struct TextMateEngine {
 
 #[derive(Debug, Clone)]
 struct TextMateCache {
+    /// Byte range of the buffer the cached lines cover
     range: Range<usize>,
+    /// The lines of `range`, in order
+    lines: Vec<CachedLine>,
+}
+
+/// A lexed line and the parser state it was lexed from
+#[derive(Debug, Clone)]
+struct CachedLine {
+    /// Length in bytes, including the line terminator
+    len: usize,
+    /// Parser state at the start of the line
+    state: ParseState,
+    /// Scope stack at the start of the line
+    scopes: ScopeStack,
+    /// Spans with byte ranges relative to the start of the line
     spans: Vec<CachedSpan>,
+    /// Edited since it was lexed; may now hold several lines
+    dirty: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -223,6 +246,20 @@ struct CachedSpan {
 /// Maximum bytes to parse in a single operation
 const MAX_PARSE_BYTES: usize = 1024 * 1024;
 
+/// End of the line starting at `start`, past its terminator (`\n`, `\r\n` or `\r`)
+fn line_end(bytes: &[u8], start: usize) -> usize {
+    let mut end = start;
+    while end < bytes.len() {
+        match bytes[end] {
+            b'\n' => return end + 1,
+            b'\r' if bytes.get(end + 1) == Some(&b'\n') => return end + 2,
+            b'\r' => return end + 1,
+            _ => end += 1,
+        }
+    }
+    end
+}
+
 impl TextMateEngine {
     /// Create a new TextMate engine for the given syntax
     pub fn new(syntax_set: Arc<SyntaxSet>, syntax_index: usize) -> Self {
@@ -267,7 +304,25 @@ impl TextMateEngine {
         theme: &Theme,
         context_bytes: usize,
     ) -> Vec<HighlightSpan> {
-        use syntect::parsing::{ParseState, ScopeStack};
+        self.highlight_categories(buffer, viewport_start, viewport_end, context_bytes)
+            .into_iter()
+            .map(|(range, category)| HighlightSpan {
+                range,
+                color: highlight_color(category, theme),
+            })
+            .collect()
+    }
+
+    /// Like [`Self::highlight_viewport`], returning each span's category
+    /// instead of its color
+    pub fn highlight_categories(
+        &mut self,
+        buffer: &Buffer,
+        viewport_start: usize,
+        viewport_end: usize,
+        context_bytes: usize,
+    ) -> Vec<(Range<usize>, HighlightCategory)> {
+        self.relex_dirty_lines(buffer);
 
         // Check cache validity
         if let Some(cache) = &self.cache {
@@ -275,17 +330,7 @@ impl TextMateEngine {
                 && cache.range.end >= viewport_end
                 && self.last_buffer_len == buffer.len()
             {
-                return cache
-                    .spans
-                    .iter()
-                    .filter(|span| {
-                        span.range.start < viewport_end && span.range.end > viewport_start
-                    })
-                    .map(|span| HighlightSpan {
-                        range: span.range.clone(),
-                        color: highlight_color(span.category, theme),
-                    })
-                    .collect();
+                return Self::spans_in(cache, viewport_start, viewport_end);
             }
         }
 
@@ -297,141 +342,199 @@ impl TextMateEngine {
             return Vec::new();
         }
 
+        // Get content
+        let content = buffer.slice_bytes(parse_start..parse_end);
+        if std::str::from_utf8(&content).is_err() {
+            return Vec::new();
+        }
+
         let syntax = &self.syntax_set.syntaxes()[self.syntax_index];
         let mut state = ParseState::new(syntax);
-        let mut spans = Vec::new();
+        let mut scopes = ScopeStack::new();
+        let mut lines = Vec::new();
+        let mut pos = 0;
+        while pos < content.len() {
+            let end = line_end(&content, pos);
+            let followed = end < content.len();
+            lines.push(self.lex_line(&mut state, &mut scopes, &content[pos..end], followed));
+            pos = end;
+        }
 
-        // Get content
-        let content = buffer.slice_bytes(parse_start..parse_end);
-        let content_str = match std::str::from_utf8(&content) {
-            Ok(s) => s,
-            Err(_) => return Vec::new(),
+        // Update cache
+        let cache = TextMateCache {
+            range: parse_start..parse_end,
+            lines,
         };
+        let spans = Self::spans_in(&cache, viewport_start, viewport_end);
+        self.cache = Some(cache);
+        self.last_buffer_len = buffer.len();
+        spans
+    }
 
-        // Parse line by line - manually track line boundaries to handle CRLF correctly
-        // str::lines() strips both \n and \r\n, losing the distinction
-        let content_bytes = content_str.as_bytes();
-        let mut pos = 0;
-        let mut current_offset = parse_start;
-        let mut current_scopes = ScopeStack::new();
-
-        while pos < content_bytes.len() {
-            let line_start = pos;
-            let mut line_end = pos;
-
-            // Scan for line ending (find \n or \r\n or end of content)
-            while line_end < content_bytes.len() {
-                if content_bytes[line_end] == b'\n' {
-                    line_end += 1;
-                    break;
-                } else if content_bytes[line_end] == b'\r' {
-                    if line_end + 1 < content_bytes.len() && content_bytes[line_end + 1] == b'\n' {
-                        line_end += 2; // CRLF
-                    } else {
-                        line_end += 1; // CR only
-                    }
-                    break;
+    /// Lex one line, advancing `state` and `scopes` past it
+    ///
+    /// `line` includes its terminator. `followed` is whether the parsed region
+    /// goes on after the line, in which case syntect is given a `\n` even if
+    /// the region was cut off before one.
+    fn lex_line(
+        &self,
+        state: &mut ParseState,
+        scopes: &mut ScopeStack,
+        line: &[u8],
+        followed: bool,
+    ) -> CachedLine {
+        let mut cached = CachedLine {
+            len: line.len(),
+            state: state.clone(),
+            scopes: scopes.clone(),
+            spans: Vec::new(),
+            dirty: false,
+        };
+
+        let Ok(line_str) = std::str::from_utf8(line) else {
+            return cached;
+        };
+
+        // Remove trailing \r\n or \n, then add single \n for syntect
+        let line_content = line_str.trim_end_matches(&['\r', '\n'][..]);
+        let line_for_syntect = if followed || line_str.ends_with('\n') {
+            format!("{}\n", line_content)
+        } else {
+            line_content.to_string()
+        };
+
+        let Ok(ops) = state.parse_line(&line_for_syntect, &self.syntax_set) else {
+            return cached;
+        };
+
+        // Convert operations to spans
+        // Note: syntect offsets are relative to line_for_syntect, which matches
+        // the line up to its terminator
+        let spans = &mut cached.spans;
+        let mut syntect_offset = 0;
+        let line_content_len = line_content.len();
+
+        for (op_offset, op) in ops {
+            // Handle any text before this operation (but only within content, not newline)
+            let clamped_op_offset = op_offset.min(line_content_len);
+            if clamped_op_offset > syntect_offset {
+                if let Some(category) = Self::scope_stack_to_category(scopes) {
+                    spans.push(CachedSpan {
+                        range: syntect_offset..clamped_op_offset,
+                        category,
+                    });
                 }
-                line_end += 1;
             }
+            syntect_offset = clamped_op_offset;
 
-            // Get the line content and actual byte length
-            let line_bytes = &content_bytes[line_start..line_end];
-            let actual_line_byte_len = line_bytes.len();
-
-            // Create line string for syntect - strip CR if present, ensure single \n
-            let line_str = match std::str::from_utf8(line_bytes) {
-                Ok(s) => s,
-                Err(_) => {
-                    pos = line_end;
-                    current_offset += actual_line_byte_len;
-                    continue;
-                }
-            };
+            // Scope stack errors are non-fatal for highlighting
+            #[allow(clippy::let_underscore_must_use)]
+            let _ = scopes.apply(&op);
+        }
 
-            // Remove trailing \r\n or \n, then add single \n for syntect
-            let line_content = line_str.trim_end_matches(&['\r', '\n'][..]);
-            let line_for_syntect = if line_end < content_bytes.len() || line_str.ends_with('\n') {
-                format!("{}\n", line_content)
-            } else {
-                line_content.to_string()
-            };
+        // Handle remaining text on line (content only, not line ending)
+        if syntect_offset < line_content_len {
+            if let Some(category) = Self::scope_stack_to_category(scopes) {
+                spans.push(CachedSpan {
+                    range: syntect_offset..line_content_len,
+                    category,
+                });
+            }
+        }
 
-            let ops = match state.parse_line(&line_for_syntect, &self.syntax_set) {
-                Ok(ops) => ops,
-                Err(_) => {
-                    pos = line_end;
-                    current_offset += actual_line_byte_len;
-                    continue;
-                }
-            };
+        Self::merge_adjacent_spans(spans);
+        cached
+    }
 
-            // Convert operations to spans
-            // Note: syntect offsets are relative to line_for_syntect, but we need
-            // to map them to the actual buffer positions
-            let mut syntect_offset = 0;
-            let line_content_len = line_content.len();
-
-            for (op_offset, op) in ops {
-                // Handle any text before this operation (but only within content, not newline)
-                let clamped_op_offset = op_offset.min(line_content_len);
-                if clamped_op_offset > syntect_offset {
-                    if let Some(category) = Self::scope_stack_to_category(&current_scopes) {
-                        let byte_start = current_offset + syntect_offset;
-                        let byte_end = current_offset + clamped_op_offset;
-                        if byte_start < byte_end {
-                            spans.push(CachedSpan {
-                                range: byte_start..byte_end,
-                                category,
-                            });
-                        }
-                    }
-                }
-                syntect_offset = clamped_op_offset;
+    /// Re-lex the lines edited since they were cached
+    ///
+    /// Lexing goes on past the edited lines until it reaches the start of an
+    /// unedited line in the parser state that line was lexed from, since
+    /// everything from there on would come out the same. The result is the
+    /// same as parsing the whole cached region again.
+    fn relex_dirty_lines(&mut self, buffer: &Buffer) {
+        let Some(mut cache) = self.cache.take() else {
+            return;
+        };
+        if !cache.lines.iter().any(|line| line.dirty) {
+            self.cache = Some(cache);
+            return;
+        }
+        if cache.range.end > buffer.len() {
+            return;
+        }
 
-                // Scope stack errors are non-fatal for highlighting
-                #[allow(clippy::let_underscore_must_use)]
-                let _ = current_scopes.apply(&op);
+        let mut i = 0;
+        let mut line_start = cache.range.start;
+        while i < cache.lines.len() {
+            if !cache.lines[i].dirty {
+                line_start += cache.lines[i].len;
+                i += 1;
+                continue;
             }
 
-            // Handle remaining text on line (content only, not line ending)
-            if syntect_offset < line_content_len {
-                if let Some(category) = Self::scope_stack_to_category(&current_scopes) {
-                    let byte_start = current_offset + syntect_offset;
-                    let byte_end = current_offset + line_content_len;
-                    if byte_start < byte_end {
-                        spans.push(CachedSpan {
-                            range: byte_start..byte_end,
-                            category,
-                        });
+            let content = buffer.slice_bytes(line_start..cache.range.end);
+            if std::str::from_utf8(&content).is_err() {
+                return;
+            }
+            let mut state = cache.lines[i].state.clone();
+            let mut scopes = cache.lines[i].scopes.clone();
+            let mut relexed = Vec::new();
+            let mut pos = 0;
+            // The first cached line starting at or after `pos`
+            let mut next = i;
+            let mut next_start = 0;
+            while pos < content.len() {
+                while next < cache.lines.len() && next_start < pos {
+                    next_start += cache.lines[next].len;
+                    next += 1;
+                }
+                if pos > 0 && next_start == pos && next < cache.lines.len() {
+                    let line = &cache.lines[next];
+                    if !line.dirty && line.state == state && line.scopes == scopes {
+                        break;
                     }
                 }
+
+                let end = line_end(&content, pos);
+                let followed = end < content.len();
+                relexed.push(self.lex_line(&mut state, &mut scopes, &content[pos..end], followed));
+                pos = end;
+            }
+            if pos >= content.len() {
+                next = cache.lines.len();
             }
 
-            // Advance by actual byte length (including real line terminator)
-            pos = line_end;
-            current_offset += actual_line_byte_len;
+            let count = relexed.len();
+            cache.lines.splice(i..next, relexed);
+            line_start += pos;
+            i += count;
         }
 
-        // Merge adjacent spans
-        Self::merge_adjacent_spans(&mut spans);
-
-        // Update cache
-        self.cache = Some(TextMateCache {
-            range: parse_start..parse_end,
-            spans: spans.clone(),
-        });
-        self.last_buffer_len = buffer.len();
+        self.cache = Some(cache);
+    }
 
-        // Filter and resolve colors
+    /// The cached spans overlapping the viewport
+    fn spans_in(
+        cache: &TextMateCache,
+        viewport_start: usize,
+        viewport_end: usize,
+    ) -> Vec<(Range<usize>, HighlightCategory)> {
+        let mut spans = Vec::new();
+        let mut line_start = cache.range.start;
+        for line in &cache.lines {
+            if line_start >= viewport_end {
+                break;
+            }
+            for span in &line.spans {
+                let range = line_start + span.range.start..line_start + span.range.end;
+                if range.start < viewport_end && range.end > viewport_start {
+                    spans.push((range, span.category));
+                }
+            }
+            line_start += line.len;
+        }
         spans
-            .into_iter()
-            .filter(|span| span.range.start < viewport_end && span.range.end > viewport_start)
-            .map(|span| HighlightSpan {
-                range: span.range,
-                color: highlight_color(span.category, theme),
-            })
-            .collect()
     }
 
     /// Map scope stack to highlight category
@@ -467,13 +570,61 @@ impl TextMateEngine {
         spans.truncate(write_idx + 1);
     }
 
-    /// Invalidate cache for edited range
-    pub fn invalidate_range(&mut self, edit_range: Range<usize>) {
-        if let Some(cache) = &self.cache {
-            if edit_range.start < cache.range.end && edit_range.end > cache.range.start {
-                self.cache = None;
-            }
+    /// Update the cache for an edit that replaced `old_len` bytes at
+    /// `position` with `new_len` bytes
+    ///
+    /// Edits before the cached region shift it, edits inside it mark the
+    /// lines they touch for re-lexing, and edits straddling its start or end
+    /// drop it.
+    pub fn invalidate_edit(&mut self, position: usize, old_len: usize, new_len: usize) {
+        self.last_buffer_len = (self.last_buffer_len + new_len).saturating_sub(old_len);
+        let Some(cache) = &mut self.cache else {
+            return;
+        };
+        let old_end = position + old_len;
+        let resize = |offset: usize| (offset + new_len).saturating_sub(old_len);
+
+        if position > cache.range.end {
+            return;
+        }
+        if position < cache.range.start && old_end <= cache.range.start {
+            cache.range = resize(cache.range.start)..resize(cache.range.end);
+            return;
+        }
+        if position < cache.range.start
+            || old_end > cache.range.end
+            || cache.lines.is_empty()
+            || resize(cache.range.end) - cache.range.start > MAX_PARSE_BYTES
+        {
+            self.cache = None;
+            return;
         }
+
+        // From the line holding the byte before the edit (a line break could
+        // be joined to it) through the line holding the edit's end
+        let target = position.saturating_sub(1).max(cache.range.start);
+        let mut first = 0;
+        let mut start = cache.range.start;
+        while first + 1 < cache.lines.len() && start + cache.lines[first].len <= target {
+            start += cache.lines[first].len;
+            first += 1;
+        }
+        let mut last = first;
+        let mut end = start + cache.lines[first].len;
+        while last + 1 < cache.lines.len() && end <= old_end {
+            last += 1;
+            end += cache.lines[last].len;
+        }
+
+        let merged = CachedLine {
+            len: end - start + new_len - old_len,
+            state: cache.lines[first].state.clone(),
+            scopes: cache.lines[first].scopes.clone(),
+            spans: Vec::new(),
+            dirty: true,
+        };
+        cache.lines.splice(first..=last, [merged]);
+        cache.range.end = resize(cache.range.end);
     }
 
     /// Invalidate all cache
@@ -710,11 +861,35 @@ impl HighlightEngine {
         }
     }
 
-    /// Invalidate cache for an edited range
-    pub fn invalidate_range(&mut self, edit_range: Range<usize>) {
+    /// Like [`Self::highlight_viewport`], returning each span's category
+    /// instead of its color
+    ///
+    /// Use this to tell what kind of text is at a position, e.g. whether
+    /// it's inside a string or comment, independent of the theme.
+    pub fn highlight_categories(
+        &mut self,
+        buffer: &Buffer,
+        viewport_start: usize,
+        viewport_end: usize,
+        context_bytes: usize,
+    ) -> Vec<(Range<usize>, HighlightCategory)> {
+        match self {
+            Self::TreeSitter(h) => {
+                h.highlight_categories(buffer, viewport_start, viewport_end, context_bytes)
+            }
+            Self::TextMate(h) => {
+                h.highlight_categories(buffer, viewport_start, viewport_end, context_bytes)
+            }
+            Self::None => Vec::new(),
+        }
+    }
+
+    /// Update the cache for an edit that replaced `old_len` bytes at
+    /// `position` with `new_len` bytes
+    pub fn invalidate_edit(&mut self, position: usize, old_len: usize, new_len: usize) {
         match self {
-            Self::TreeSitter(h) => h.invalidate_range(edit_range),
-            Self::TextMate(h) => h.invalidate_range(edit_range),
+            Self::TreeSitter(h) => h.invalidate_range(position..position + old_len.max(new_len)),
+            Self::TextMate(h) => h.invalidate_edit(position, old_len, new_len),
             Self::None => {}
         }
     }
@@ -773,8 +948,6 @@ pub fn highlight_string(
     registry: &GrammarRegistry,
     theme: &Theme,
 ) -> Vec<HighlightSpan> {
-    use syntect::parsing::{ParseState, ScopeStack};
-
     // Find syntax by language token (handles aliases like "py" -> Python)
     let syntax = match registry.syntax_set().find_syntax_by_token(lang_hint) {
         Some(s) => s,
@@ -1134,4 +1307,125 @@ mod tests {
         assert_eq!(engine.backend_name(), "textmate");
         assert!(engine.has_highlighting());
     }
+
+    fn rust_textmate_engine() -> TextMateEngine {
+        let registry =
+            GrammarRegistry::load(&crate::primitives::grammar::LocalGrammarLoader::embedded_only());
+        match HighlightEngine::for_file(Path::new("test.rs"), &registry) {
+            HighlightEngine::TextMate(engine) => *engine,
+            _ => panic!("Expected TextMate engine for .rs file"),
+        }
+    }
+
+    fn span_ranges(spans: Vec<HighlightSpan>) -> Vec<(Range<usize>, ratatui::style::Color)> {
+        spans.into_iter().map(|s| (s.range, s.color)).collect()
+    }
+
+    enum Edit {
+        Insert(usize, &'static str),
+        Delete(Range<usize>),
+    }
+
+    /// Apply an edit to the buffer and tell the engine about it
+    fn apply_edit(buffer: &mut Buffer, engine: &mut TextMateEngine, edit: &Edit) {
+        match edit {
+            Edit::Insert(position, text) => {
+                buffer.insert(*position, text);
+                engine.invalidate_edit(*position, 0, text.len());
+            }
+            Edit::Delete(range) => {
+                buffer.delete(range.clone());
+                engine.invalidate_edit(range.start, range.len(), 0);
+            }
+        }
+    }
+
+    /// After each edit, highlighting the edited cache matches a fresh parse
+    #[test]
+    fn test_incremental_highlight_matches_full_parse() {
+        let theme = Theme::load_builtin(theme::THEME_LIGHT).unwrap();
+        let line = "fn f() { let s = \"text\"; } // note\n";
+        let content = line.repeat(100);
+        let mut buffer = Buffer::from_str(&content, 0, test_fs());
+        let mut engine = rust_textmate_engine();
+        let len = buffer.len();
+        engine.highlight_viewport(&buffer, 0, len, &theme, len);
+
+        let line_start = |n: usize| n * line.len();
+        let edits = [
+            // Typing inside a line
+            Edit::Insert(line_start(3) + 3, "x"),
+            Edit::Insert(line_start(3) + 4, "yz"),
+            // Open a block comment spanning the rest of the buffer
+            Edit::Insert(line_start(10), "/*"),
+            // Close it many lines further down
+            Edit::Insert(line_start(60) + 2, "*/"),
+            // Open a string that runs across lines
+            Edit::Insert(line_start(80), "\""),
+            // Join two lines, then split one
+            Edit::Delete(line_start(40) - 1..line_start(40)),
+            Edit::Insert(line_start(20) + 8, "\n"),
+            // Remove the comment opener again
+            Edit::Delete(line_start(10)..line_start(10) + 2),
+            // Edit at the very end
+            Edit::Insert(content.len() + 6, "let end = 1;"),
+            Edit::Delete(0..line.len()),
+        ];
+        for edit in &edits {
+            apply_edit(&mut buffer, &mut engine, edit);
+            assert!(engine.cache.is_some(), "edit inside the cache keeps it");
+
+            let len = buffer.len();
+            let incremental = span_ranges(engine.highlight_viewport(&buffer, 0, len, &theme, len));
+            let mut fresh = rust_textmate_engine();
+            let full = span_ranges(fresh.highlight_viewport(&buffer, 0, len, &theme, len));
+            assert_eq!(incremental, full);
+        }
+    }
+
+    /// An edit marks only the lines it touches for re-lexing
+    #[test]
+    fn test_incremental_highlight_relexes_only_edited_line() {
+        let theme = Theme::load_builtin(theme::THEME_LIGHT).unwrap();
+        let content = "let a = 1;\n".repeat(50);
+        let mut buffer = Buffer::from_str(&content, 0, test_fs());
+        let mut engine = rust_textmate_engine();
+        let len = buffer.len();
+        engine.highlight_viewport(&buffer, 0, len, &theme, len);
+
+        apply_edit(&mut buffer, &mut engine, &Edit::Insert(22, "b"));
+        let cache = engine.cache.as_ref().unwrap();
+        assert_eq!(cache.lines.iter().filter(|line| line.dirty).count(), 1);
+        assert_eq!(
+            cache.lines.len(),
+            49,
+            "edited lines are merged until re-lexed"
+        );
+
+        let len = buffer.len();
+        engine.highlight_viewport(&buffer, 0, len, &theme, len);
+        let cache = engine.cache.as_ref().unwrap();
+        assert_eq!(cache.lines.len(), 50);
+        assert!(cache.lines.iter().all(|line| !line.dirty));
+    }
+
+    /// Edits before a cached region in the middle of the buffer shift it
+    #[test]
+    fn test_incremental_highlight_shifts_for_edits_before_cache() {
+        let theme = Theme::load_builtin(theme::THEME_LIGHT).unwrap();
+        let content = "let s = \"a\"; // c\n".repeat(200);
+        let mut buffer = Buffer::from_str(&content, 0, test_fs());
+        let mut engine = rust_textmate_engine();
+        let (start, end, context) = (1800, 2200, 180);
+        engine.highlight_viewport(&buffer, start, end, &theme, context);
+
+        apply_edit(&mut buffer, &mut engine, &Edit::Insert(10, "xyz"));
+        let incremental =
+            span_ranges(engine.highlight_viewport(&buffer, start + 3, end + 3, &theme, context));
+        let mut fresh = rust_textmate_engine();
+        let full =
+            span_ranges(fresh.highlight_viewport(&buffer, start + 3, end + 3, &theme, context));
+        assert_eq!(incremental, full);
+        assert!(!incremental.is_empty());
+    }
 }
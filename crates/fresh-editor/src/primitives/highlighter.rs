@@ -108,23 +108,38 @@ impl Highlighter {
         theme: &Theme,
         context_bytes: usize,
     ) -> Vec<HighlightSpan> {
+        self.highlight_categories(buffer, viewport_start, viewport_end, context_bytes)
+            .into_iter()
+            .map(|(range, category)| HighlightSpan {
+                range,
+                color: highlight_color(category, theme),
+            })
+            .collect()
+    }
+
+    /// Like [`Self::highlight_viewport`], returning each span's category
+    /// instead of its color
+    pub fn highlight_categories(
+        &mut self,
+        buffer: &Buffer,
+        viewport_start: usize,
+        viewport_end: usize,
+        context_bytes: usize,
+    ) -> Vec<(Range<usize>, HighlightCategory)> {
         // Check if cache is valid for this range
         if let Some(cache) = &self.cache {
             if cache.range.start <= viewport_start
                 && cache.range.end >= viewport_end
                 && self.last_buffer_len == buffer.len()
             {
-                // Cache hit! Filter spans to the requested range and resolve colors from theme
+                // Cache hit! Filter spans to the requested range
                 return cache
                     .spans
                     .iter()
                     .filter(|span| {
                         span.range.start < viewport_end && span.range.end > viewport_start
                     })
-                    .map(|span| HighlightSpan {
-                        range: span.range.clone(),
-                        color: highlight_color(span.category, theme),
-                    })
+                    .map(|span| (span.range.clone(), span.category))
                     .collect();
             }
         }
@@ -202,14 +217,11 @@ impl Highlighter {
         });
         self.last_buffer_len = buffer.len();
 
-        // Filter to requested viewport and resolve colors from theme
+        // Filter to requested viewport
         cached_spans
             .into_iter()
             .filter(|span| span.range.start < viewport_end && span.range.end > viewport_start)
-            .map(|span| HighlightSpan {
-                range: span.range,
-                color: highlight_color(span.category, theme),
-            })
+            .map(|span| (span.range, span.category))
             .collect()
     }
 
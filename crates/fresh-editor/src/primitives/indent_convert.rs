@@ -0,0 +1,84 @@
+//! Converting leading indentation between tabs and spaces.
+//!
+//! Only the whitespace at the start of each line is rewritten; tabs and
+//! runs of spaces after the first non-blank character are left alone. Tab
+//! stops are every `tab_size` columns, matching how tabs are rendered.
+
+use std::ops::Range;
+
+/// Edits that rewrite the indentation of every line in `text`
+///
+/// With `to_tabs`, each indent is rebuilt from as many tabs as fit, padded
+/// with spaces; otherwise every tab in the indent expands to spaces. Lines
+/// whose indentation already has the target form produce no edit. Edits
+/// are sorted by position and don't overlap.
+pub fn convert_indentation_edits(
+    text: &str,
+    tab_size: usize,
+    to_tabs: bool,
+) -> Vec<(Range<usize>, String)> {
+    let tab_size = tab_size.max(1);
+    let mut edits = Vec::new();
+    let mut line_start = 0;
+    for line in text.split_inclusive('\n') {
+        let indent_len = line.len() - line.trim_start_matches([' ', '\t']).len();
+        let indent = &line[..indent_len];
+        let width = indent.chars().fold(0, |col, ch| match ch {
+            '\t' => col + tab_size - col % tab_size,
+            _ => col + 1,
+        });
+        let replacement = if to_tabs {
+            "\t".repeat(width / tab_size) + &" ".repeat(width % tab_size)
+        } else {
+            " ".repeat(width)
+        };
+        if replacement != indent {
+            edits.push((line_start..line_start + indent_len, replacement));
+        }
+        line_start += line.len();
+    }
+    edits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert(text: &str, tab_size: usize, to_tabs: bool) -> String {
+        let mut result = text.to_string();
+        for (range, replacement) in convert_indentation_edits(text, tab_size, to_tabs)
+            .into_iter()
+            .rev()
+        {
+            result.replace_range(range, &replacement);
+        }
+        result
+    }
+
+    #[test]
+    fn test_tabs_to_spaces_leaves_inner_tabs() {
+        assert_eq!(
+            convert("\tfoo\tbar\n\t\tbaz\nqux\n", 4, false),
+            "    foo\tbar\n        baz\nqux\n"
+        );
+        // A tab after spaces only fills up to the next tab stop
+        assert_eq!(convert("  \tx", 4, false), "    x");
+    }
+
+    #[test]
+    fn test_spaces_to_tabs_keeps_remainder() {
+        assert_eq!(
+            convert("    a  b\n      c\n  d\n", 4, true),
+            "\ta  b\n\t  c\n  d\n"
+        );
+        assert_eq!(convert("        x", 8, true), "\tx");
+    }
+
+    #[test]
+    fn test_unchanged_lines_produce_no_edits() {
+        assert!(convert_indentation_edits("\ta\n\t\tb\nc", 4, true).is_empty());
+        assert!(convert_indentation_edits("  a\nb\t\n", 4, false).is_empty());
+        // Whitespace-only lines are converted too, including CRLF endings
+        assert_eq!(convert("\t\r\n", 2, false), "  \r\n");
+    }
+}
@@ -0,0 +1,161 @@
+//! Joining lines.
+//!
+//! Each line break, along with the trailing whitespace before it and the
+//! indentation after it, is replaced by a single space. No space is added
+//! after an opening bracket, before a closing bracket, or for a blank line.
+//! When the first line starts with a leader (a line comment token, or `>` in
+//! markdown), the same leader is dropped from the lines joined onto it, so a
+//! comment or quote stays one comment or quote.
+
+use std::ops::Range;
+
+/// A replacement of `range` (in the original text) with `text`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JoinEdit {
+    pub range: Range<usize>,
+    pub text: String,
+}
+
+/// Edits that join consecutive `lines` into one, sorted by position
+///
+/// Each line is given as its start offset and its text without the line
+/// ending. Trailing blank lines are removed along with the line breaks
+/// before them.
+pub fn join_lines_edits(lines: &[(usize, &str)], leader: Option<&str>) -> Vec<JoinEdit> {
+    let Some((&(first_start, first_text), rest)) = lines.split_first() else {
+        return Vec::new();
+    };
+    let marker = leader.and_then(|leader| line_marker(first_text, leader));
+
+    let joined = first_text.trim_end();
+    let mut last_char = joined.chars().last();
+    let mut joined_end = first_start + joined.len();
+    let mut edits = Vec::new();
+    // End of the blank lines seen since the last joined content
+    let mut pending_end = None;
+
+    for &(start, text) in rest {
+        let mut content_start = indent_len(text);
+        if let Some(marker) = marker {
+            let after = &text[content_start..];
+            if after.starts_with(marker)
+                && after[marker.len()..]
+                    .chars()
+                    .next()
+                    .is_none_or(char::is_whitespace)
+            {
+                content_start += marker.len();
+                content_start += indent_len(&text[content_start..]);
+            }
+        }
+        let content = text[content_start..].trim_end();
+        let Some(first_char) = content.chars().next() else {
+            pending_end = Some(start + text.len());
+            continue;
+        };
+
+        let separator = match (last_char, first_char) {
+            (None, _) | (Some('(' | '[' | '{'), _) | (_, ')' | ']' | '}') => "",
+            _ => " ",
+        };
+        edits.push(JoinEdit {
+            range: joined_end..start + content_start,
+            text: separator.to_string(),
+        });
+        last_char = content.chars().last();
+        joined_end = start + content_start + content.len();
+        pending_end = None;
+    }
+
+    if let Some(end) = pending_end {
+        edits.push(JoinEdit {
+            range: joined_end..end,
+            text: String::new(),
+        });
+    }
+    edits
+}
+
+/// Byte length of a line's leading whitespace
+fn indent_len(text: &str) -> usize {
+    text.len() - text.trim_start().len()
+}
+
+/// The leader starting `text` (after indentation), if any
+///
+/// Punctuation directly following the leader is part of it, so doc comments
+/// like `///` or `//!` only match the same marker on the following lines.
+fn line_marker<'a>(text: &'a str, leader: &str) -> Option<&'a str> {
+    let rest = &text[indent_len(text)..];
+    if leader.is_empty() || !rest.starts_with(leader) {
+        return None;
+    }
+    let extra = rest[leader.len()..]
+        .chars()
+        .take_while(char::is_ascii_punctuation)
+        .map(char::len_utf8)
+        .sum::<usize>();
+    Some(&rest[..leader.len() + extra])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Apply edits (sorted by position) to `text`
+    fn apply(text: &str, edits: &[JoinEdit]) -> String {
+        let mut result = text.to_string();
+        for edit in edits.iter().rev() {
+            result.replace_range(edit.range.clone(), &edit.text);
+        }
+        result
+    }
+
+    /// Split `text` into (line start, line text) pairs
+    fn lines(text: &str) -> Vec<(usize, &str)> {
+        let mut start = 0;
+        text.split('\n')
+            .map(|line| {
+                let entry = (start, line);
+                start += line.len() + 1;
+                entry
+            })
+            .collect()
+    }
+
+    fn join(text: &str, leader: Option<&str>) -> String {
+        apply(text, &join_lines_edits(&lines(text), leader))
+    }
+
+    #[test]
+    fn test_join_collapses_whitespace() {
+        assert_eq!(join("let x =  \n    1 +\n\t2;", None), "let x = 1 + 2;");
+        assert_eq!(join("    indented\n  next  ", None), "    indented next  ");
+        assert_eq!(join("single", None), "single");
+    }
+
+    #[test]
+    fn test_join_brackets() {
+        assert_eq!(join("call(\n    a,\n    b\n)", None), "call(a, b)");
+        assert_eq!(join("let v = [\n  1\n];", None), "let v = [1];");
+        assert_eq!(join("if x {\n    y\n}", None), "if x {y}");
+    }
+
+    #[test]
+    fn test_join_blank_lines() {
+        assert_eq!(join("a\n\n   \nb", None), "a b");
+        assert_eq!(join("a\n\n", None), "a");
+        assert_eq!(join("\n  b", None), "b");
+    }
+
+    #[test]
+    fn test_join_drops_repeated_leader() {
+        assert_eq!(join("// one\n  // two", Some("//")), "// one two");
+        assert_eq!(join("/// one\n/// two", Some("//")), "/// one two");
+        // A different marker is kept as text
+        assert_eq!(join("// one\n/// two", Some("//")), "// one /// two");
+        // Code isn't treated as a comment
+        assert_eq!(join("a // x\n// y", Some("//")), "a // x // y");
+        assert_eq!(join("> quoted\n> text", Some(">")), "> quoted text");
+    }
+}
@@ -0,0 +1,201 @@
+//! Markdown-aware paste transformations used in compose mode.
+//!
+//! Recognizes two kinds of clipboard content and rewrites them as markdown:
+//! - A bare `http`/`https` URL pasted over a one-line selection becomes a
+//!   link to the selected text: `[selection](url)`.
+//! - Tab-separated rows (at least two rows of at least two columns, every row
+//!   the same width) pasted at the start of a line become a pipe table with
+//!   the first row as header and columns padded to line up.
+//!
+//! Anything else is left for a plain insert.
+
+use super::display_width::str_width;
+
+/// Rewrite pasted text as markdown, or `None` to paste it unchanged
+///
+/// `pasted` must use `\n` line endings. `selection` is the text the paste
+/// replaces, and `at_line_start` tells whether the paste lands at the start
+/// of a line.
+pub fn transform_paste(
+    pasted: &str,
+    selection: Option<&str>,
+    at_line_start: bool,
+) -> Option<String> {
+    if let Some(selection) = selection {
+        if let Some(url) = bare_url(pasted) {
+            if !selection.trim().is_empty() && !selection.contains('\n') {
+                return Some(format!("[{selection}]({url})"));
+            }
+        }
+    }
+    if at_line_start {
+        return tsv_to_table(pasted);
+    }
+    None
+}
+
+/// The URL if `text` is a single http(s) URL that is safe inside `(...)`
+fn bare_url(text: &str) -> Option<&str> {
+    let url = text.trim();
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    if rest.is_empty() || rest.starts_with('/') {
+        return None;
+    }
+    if url
+        .chars()
+        .any(|c| c.is_whitespace() || c.is_control() || matches!(c, '<' | '>' | '`'))
+    {
+        return None;
+    }
+    // Unbalanced parentheses would end the link destination early
+    let mut depth = 0usize;
+    for c in url.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.checked_sub(1)?,
+            _ => {}
+        }
+    }
+    (depth == 0).then_some(url)
+}
+
+/// Convert tab-separated rows to an aligned pipe table
+fn tsv_to_table(text: &str) -> Option<String> {
+    let (body, trailing_newline) = match text.strip_suffix('\n') {
+        Some(body) => (body, true),
+        None => (text, false),
+    };
+    if !body.contains('\t') {
+        return None;
+    }
+
+    let rows: Vec<Vec<String>> = body
+        .split('\n')
+        .map(|line| {
+            line.split('\t')
+                .map(|cell| cell.trim().replace('|', "\\|"))
+                .collect()
+        })
+        .collect();
+
+    let columns = rows[0].len();
+    // An empty first cell means indented text (e.g. code), not a table
+    if rows.len() < 2
+        || columns < 2
+        || rows
+            .iter()
+            .any(|row| row.len() != columns || row[0].is_empty())
+    {
+        return None;
+    }
+
+    let widths: Vec<usize> = (0..columns)
+        .map(|col| {
+            rows.iter()
+                .map(|row| str_width(&row[col]))
+                .max()
+                .unwrap_or(0)
+                .max(3)
+        })
+        .collect();
+
+    let format_row = |cells: &[String]| -> String {
+        let mut line = String::from("|");
+        for (cell, width) in cells.iter().zip(&widths) {
+            let padding = width - str_width(cell);
+            line.push(' ');
+            line.push_str(cell);
+            line.push_str(&" ".repeat(padding));
+            line.push_str(" |");
+        }
+        line
+    };
+
+    let mut lines = Vec::with_capacity(rows.len() + 1);
+    lines.push(format_row(&rows[0]));
+    let separator: Vec<String> = widths.iter().map(|&w| "-".repeat(w)).collect();
+    lines.push(format_row(&separator));
+    lines.extend(rows[1..].iter().map(|row| format_row(row)));
+
+    let mut table = lines.join("\n");
+    if trailing_newline {
+        table.push('\n');
+    }
+    Some(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_over_selection_becomes_link() {
+        assert_eq!(
+            transform_paste("https://example.com/a?b=c", Some("the docs"), false),
+            Some("[the docs](https://example.com/a?b=c)".to_string())
+        );
+        assert_eq!(
+            transform_paste(
+                "  http://en.wikipedia.org/wiki/Rust_(language)\n",
+                Some("Rust"),
+                true
+            ),
+            Some("[Rust](http://en.wikipedia.org/wiki/Rust_(language))".to_string())
+        );
+    }
+
+    #[test]
+    fn test_url_detection_is_conservative() {
+        for pasted in [
+            "ftp://example.com",
+            "https://",
+            "https:///path",
+            "see https://example.com",
+            "https://example.com/a b",
+            "https://example.com/a)",
+            "https://example.com/<x>",
+        ] {
+            assert_eq!(
+                transform_paste(pasted, Some("text"), false),
+                None,
+                "{pasted}"
+            );
+        }
+        // Needs a selection, and a single-line one
+        assert_eq!(transform_paste("https://example.com", None, false), None);
+        assert_eq!(
+            transform_paste("https://example.com", Some(""), false),
+            None
+        );
+        assert_eq!(
+            transform_paste("https://example.com", Some("two\nlines"), false),
+            None
+        );
+    }
+
+    #[test]
+    fn test_tsv_becomes_aligned_table() {
+        assert_eq!(
+            transform_paste("Name\tQty\napple\t3\nkiwi|fruit\t12\n", None, true),
+            Some(
+                "| Name        | Qty |\n\
+                 | ----------- | --- |\n\
+                 | apple       | 3   |\n\
+                 | kiwi\\|fruit | 12  |\n"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_tsv_detection_is_conservative() {
+        // Ragged rows, a single row, a single column, indented code
+        for pasted in ["a\tb\nc\n", "a\tb\n", "a\nb\n", "\tfoo\n\tbar\n"] {
+            assert_eq!(transform_paste(pasted, None, true), None, "{pasted:?}");
+        }
+        // Only at the start of a line
+        assert_eq!(transform_paste("a\tb\nc\td\n", None, false), None);
+    }
+}
@@ -13,16 +13,26 @@
 //! | Syntax highlighting | `textmate_engine` | `highlight_engine` |
 //! | Auto-indentation | `indent_pattern` | `indent` |
 //! | Reference highlighting | `reference_highlight_text` | `reference_highlighter` |
+//!
+//! The document outline (`outline`) needs tree-sitter and is runtime-only.
 
 // Pure modules - available for both runtime and WASM
+pub mod align;
+pub mod bracket_match;
+pub mod comment_toggle;
 pub mod display_width;
+pub mod edit_offsets;
 pub mod grapheme;
+pub mod indent_convert;
+pub mod join_lines;
 pub mod line_wrapping;
+pub mod markdown_paste;
 pub mod path_utils;
 pub mod snippet;
 pub mod text_property;
 
 // Modules depending on model::buffer - available for both runtime and WASM
+pub mod block_selection;
 pub mod line_iterator;
 pub mod word_navigation;
 
@@ -63,4 +73,6 @@ pub mod highlighter;
 #[cfg(feature = "runtime")]
 pub mod indent;
 #[cfg(feature = "runtime")]
+pub mod outline;
+#[cfg(feature = "runtime")]
 pub mod reference_highlighter;
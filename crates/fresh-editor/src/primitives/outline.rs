@@ -0,0 +1,329 @@
+//! Document outline: the symbols of a buffer, in order and nested
+//!
+//! Code is parsed with tree-sitter and every function, class, struct and
+//! similar definition becomes a symbol, nested inside the definitions that
+//! contain it. Markdown headings form the outline of markdown documents,
+//! nested the way section folding treats them: a section runs to the next
+//! heading of the same or a higher level, and `#` lines inside fenced code
+//! blocks are not headings.
+
+use crate::primitives::highlighter::Language;
+use fresh_languages::tree_sitter::{Node, Parser};
+
+/// A definition or heading in a document's outline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutlineSymbol {
+    /// Identifier of the definition or heading text
+    pub name: String,
+    /// What the symbol is, e.g. "function", "struct" or "heading"
+    pub kind: &'static str,
+    /// Number of symbols this one is nested in
+    pub depth: usize,
+    /// 1-based line of the name
+    pub line: usize,
+    /// Byte offset of the name
+    pub offset: usize,
+    /// Start of the whole definition or section
+    pub start: usize,
+    /// End of the whole definition or section
+    pub end: usize,
+}
+
+/// Definition node kinds across the tree-sitter grammars, with the symbol
+/// kind they are listed as
+const SYMBOL_NODES: &[(&str, &str)] = &[
+    ("function_item", "function"),
+    ("function_signature_item", "function"),
+    ("function_definition", "function"),
+    ("function_declaration", "function"),
+    ("generator_function_declaration", "function"),
+    ("procedure_declaration", "function"),
+    ("method_definition", "method"),
+    ("method_declaration", "method"),
+    ("constructor_declaration", "method"),
+    ("method", "method"),
+    ("singleton_method", "method"),
+    ("class_definition", "class"),
+    ("class_declaration", "class"),
+    ("abstract_class_declaration", "class"),
+    ("class_specifier", "class"),
+    ("class", "class"),
+    ("record_declaration", "class"),
+    ("struct_item", "struct"),
+    ("struct_specifier", "struct"),
+    ("struct_declaration", "struct"),
+    ("union_item", "struct"),
+    ("union_specifier", "struct"),
+    ("enum_item", "enum"),
+    ("enum_specifier", "enum"),
+    ("enum_declaration", "enum"),
+    ("trait_item", "trait"),
+    ("trait_declaration", "trait"),
+    ("interface_declaration", "interface"),
+    ("type_alias_declaration", "type"),
+    ("type_spec", "type"),
+    ("impl_item", "impl"),
+    ("mod_item", "module"),
+    ("module", "module"),
+    ("namespace_definition", "module"),
+    ("namespace_declaration", "module"),
+    ("macro_definition", "macro"),
+];
+
+/// C-family specifiers are definitions only when they have a body; without
+/// one they just name a type
+const SPECIFIER_NODES: &[&str] = &[
+    "class_specifier",
+    "struct_specifier",
+    "union_specifier",
+    "enum_specifier",
+];
+
+/// Declarator nodes that wrap the name of a C or C++ function
+const DECLARATOR_NODES: &[&str] = &[
+    "function_declarator",
+    "pointer_declarator",
+    "reference_declarator",
+    "parenthesized_declarator",
+];
+
+/// The outline of a buffer in `language`, whose grammar id is `grammar`
+///
+/// Markdown is outlined by its headings. Other languages need a tree-sitter
+/// grammar; buffers without one have no outline.
+pub fn document_outline(
+    language: Option<&Language>,
+    grammar: &str,
+    source: &str,
+) -> Vec<OutlineSymbol> {
+    if grammar == "markdown" {
+        return markdown_outline(source);
+    }
+    language.map_or_else(Vec::new, |language| code_outline(language, source))
+}
+
+/// Markdown headings, nested by level
+pub fn markdown_outline(source: &str) -> Vec<OutlineSymbol> {
+    let mut symbols: Vec<OutlineSymbol> = Vec::new();
+    // Headings whose section is still open, as (level, index into symbols)
+    let mut open: Vec<(usize, usize)> = Vec::new();
+    let mut in_code_block = false;
+    let mut line_start = 0;
+
+    for (index, line) in source.split_inclusive('\n').enumerate() {
+        let start = line_start;
+        line_start += line.len();
+        let text = line.trim_end_matches(['\n', '\r']);
+        if text.trim().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        let level = text.bytes().take_while(|&b| b == b'#').count();
+        if in_code_block
+            || !(1..=6).contains(&level)
+            || !matches!(text.as_bytes().get(level), Some(b' ' | b'\t'))
+        {
+            continue;
+        }
+
+        while let Some(&(open_level, symbol)) = open.last() {
+            if open_level < level {
+                break;
+            }
+            symbols[symbol].end = start;
+            open.pop();
+        }
+        let name = text[level..].trim();
+        symbols.push(OutlineSymbol {
+            name: name.to_string(),
+            kind: "heading",
+            depth: open.len(),
+            line: index + 1,
+            offset: start + text.len() - text[level..].trim_start().len(),
+            start,
+            end: source.len(),
+        });
+        open.push((level, symbols.len() - 1));
+    }
+    symbols
+}
+
+/// Definitions found by parsing `source` as `language`
+pub fn code_outline(language: &Language, source: &str) -> Vec<OutlineSymbol> {
+    let ts_language = match language {
+        Language::Rust => fresh_languages::tree_sitter_rust::LANGUAGE.into(),
+        Language::Python => fresh_languages::tree_sitter_python::LANGUAGE.into(),
+        Language::JavaScript => fresh_languages::tree_sitter_javascript::LANGUAGE.into(),
+        Language::TypeScript => fresh_languages::tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+        Language::Go => fresh_languages::tree_sitter_go::LANGUAGE.into(),
+        Language::C => fresh_languages::tree_sitter_c::LANGUAGE.into(),
+        Language::Cpp => fresh_languages::tree_sitter_cpp::LANGUAGE.into(),
+        Language::Java => fresh_languages::tree_sitter_java::LANGUAGE.into(),
+        Language::Php => fresh_languages::tree_sitter_php::LANGUAGE_PHP.into(),
+        Language::Ruby => fresh_languages::tree_sitter_ruby::LANGUAGE.into(),
+        Language::Bash => fresh_languages::tree_sitter_bash::LANGUAGE.into(),
+        Language::Lua => fresh_languages::tree_sitter_lua::LANGUAGE.into(),
+        Language::CSharp => fresh_languages::tree_sitter_c_sharp::LANGUAGE.into(),
+        Language::Odin => fresh_languages::tree_sitter_odin::LANGUAGE.into(),
+        // None of the definition kinds above occur in these grammars
+        Language::Pascal | Language::Json | Language::HTML | Language::CSS => return Vec::new(),
+    };
+
+    let mut parser = Parser::new();
+    if parser.set_language(&ts_language).is_err() {
+        tracing::warn!("Failed to set language for outline parser");
+        return Vec::new();
+    }
+    let Some(tree) = parser.parse(source, None) else {
+        return Vec::new();
+    };
+
+    let mut symbols = Vec::new();
+    collect_symbols(tree.root_node(), source, 0, &mut symbols);
+    symbols
+}
+
+/// Add the definitions in `node` and below to `symbols`, in source order
+fn collect_symbols(node: Node, source: &str, depth: usize, symbols: &mut Vec<OutlineSymbol>) {
+    let mut child_depth = depth;
+    if let Some(symbol) = symbol_for_node(node, source, depth) {
+        symbols.push(symbol);
+        child_depth += 1;
+    }
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        collect_symbols(child, source, child_depth, symbols);
+    }
+}
+
+/// The outline entry for `node`, if it is a named definition
+fn symbol_for_node(node: Node, source: &str, depth: usize) -> Option<OutlineSymbol> {
+    let kind = node.kind();
+    let (_, symbol_kind) = SYMBOL_NODES
+        .iter()
+        .find(|(node_kind, _)| *node_kind == kind)?;
+    if SPECIFIER_NODES.contains(&kind) && node.child_by_field_name("body").is_none() {
+        return None;
+    }
+
+    let name_node = if kind == "impl_item" {
+        node.child_by_field_name("type")?
+    } else {
+        match node.child_by_field_name("name") {
+            Some(name) => name,
+            None => declarator_name(node)?,
+        }
+    };
+    let name = source.get(name_node.byte_range())?;
+    let name = if kind == "impl_item" {
+        // `impl Trait for Type` is listed by both names
+        match node.child_by_field_name("trait") {
+            Some(trait_node) => format!("{} for {}", source.get(trait_node.byte_range())?, name),
+            None => name.to_string(),
+        }
+    } else {
+        name.to_string()
+    };
+
+    Some(OutlineSymbol {
+        name,
+        kind: symbol_kind,
+        depth,
+        line: name_node.start_position().row + 1,
+        offset: name_node.start_byte(),
+        start: node.start_byte(),
+        end: node.end_byte(),
+    })
+}
+
+/// The name of a C or C++ function definition, found through its declarators
+fn declarator_name(node: Node) -> Option<Node> {
+    let mut declarator = node.child_by_field_name("declarator")?;
+    while DECLARATOR_NODES.contains(&declarator.kind()) {
+        declarator = declarator.child_by_field_name("declarator")?;
+    }
+    Some(declarator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(symbols: &[OutlineSymbol]) -> Vec<(&str, &str, usize)> {
+        symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.kind, s.depth))
+            .collect()
+    }
+
+    #[test]
+    fn test_code_outline_nests_definitions() {
+        let source = r#"struct Point { x: i32 }
+
+impl Point {
+    fn new() -> Self { todo!() }
+}
+
+fn main() {}
+"#;
+        let symbols = code_outline(&Language::Rust, source);
+
+        assert_eq!(
+            names(&symbols),
+            vec![
+                ("Point", "struct", 0),
+                ("Point", "impl", 0),
+                ("new", "function", 1),
+                ("main", "function", 0),
+            ]
+        );
+        assert_eq!(symbols[2].line, 4);
+        assert_eq!(&source[symbols[2].offset..symbols[2].offset + 3], "new");
+        assert!(symbols[1].start < symbols[2].start && symbols[2].end < symbols[1].end);
+    }
+
+    #[test]
+    fn test_code_outline_finds_c_function_names() {
+        let source = "struct node { int v; };\nstatic int *lookup(struct node *n) { return 0; }\n";
+        let symbols = code_outline(&Language::C, source);
+
+        assert_eq!(
+            names(&symbols),
+            vec![("node", "struct", 0), ("lookup", "function", 0)]
+        );
+    }
+
+    #[test]
+    fn test_markdown_outline_follows_heading_levels() {
+        let source = r#"# Title
+
+## Intro
+
+```sh
+# not a heading
+```
+
+### Detail
+
+## Usage
+#hashtag
+"#;
+        let symbols = markdown_outline(source);
+
+        assert_eq!(
+            names(&symbols),
+            vec![
+                ("Title", "heading", 0),
+                ("Intro", "heading", 1),
+                ("Detail", "heading", 2),
+                ("Usage", "heading", 1),
+            ]
+        );
+        // A section ends at the next heading of the same or a higher level
+        let usage = source.find("## Usage").unwrap();
+        assert_eq!(symbols[1].end, usage);
+        assert_eq!(symbols[2].end, usage);
+        assert_eq!(symbols[0].end, source.len());
+        assert_eq!(&source[symbols[3].offset..], "Usage\n#hashtag\n");
+    }
+}
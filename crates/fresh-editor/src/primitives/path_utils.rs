@@ -23,6 +23,67 @@ pub fn expand_tilde(path: &str) -> PathBuf {
     }
 }
 
+/// Split a trailing `:line` or `:line:col` off a file path.
+///
+/// Returns the path part with the line and column as written (1-based).
+/// Supports formats:
+/// - `file.txt` -> (`file.txt`, None, None)
+/// - `file.txt:10` -> (`file.txt`, Some(10), None)
+/// - `file.txt:10:5` -> (`file.txt`, Some(10), Some(5))
+///
+/// The colon of a Windows drive letter (`C:\file.txt:10`) is never taken
+/// for a suffix, and a suffix that isn't all digits (`foo:bar`, `foo:bar:10`)
+/// leaves the whole input as the path. Callers that can check the
+/// filesystem should prefer an existing file named by the full input, since
+/// names like `notes:10` are legal on Unix.
+pub fn split_line_col_suffix(input: &str) -> (&str, Option<usize>, Option<usize>) {
+    let start = drive_prefix_len(input);
+    let parse = |part: &str| {
+        if !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()) {
+            part.parse::<usize>().ok()
+        } else {
+            None
+        }
+    };
+
+    let parts: Vec<&str> = input[start..].rsplitn(3, ':').collect();
+    match parts.as_slice() {
+        [col, line, rest] if !rest.is_empty() || start > 0 => match (parse(line), parse(col)) {
+            (Some(line), Some(col)) => (&input[..start + rest.len()], Some(line), Some(col)),
+            _ => (input, None, None),
+        },
+        [line, rest] if !rest.is_empty() || start > 0 => match parse(line) {
+            Some(line) => (&input[..start + rest.len()], Some(line), None),
+            None => (input, None, None),
+        },
+        _ => (input, None, None),
+    }
+}
+
+/// Length of a Windows drive prefix such as `C:` at the start of `input`
+///
+/// Absolute drive paths (`C:\`, `C:/`) are recognized on every platform;
+/// other prefixes (`C:file`, `\\?\C:`) are left to `std::path`, which only
+/// knows them on Windows.
+fn drive_prefix_len(input: &str) -> usize {
+    let bytes = input.as_bytes();
+    if bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && matches!(bytes[2], b'\\' | b'/')
+    {
+        return 2;
+    }
+    let prefix = std::path::Path::new(input)
+        .components()
+        .next()
+        .and_then(|c| match c {
+            std::path::Component::Prefix(prefix) => Some(prefix.as_os_str().len()),
+            _ => None,
+        });
+    prefix.unwrap_or(0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -62,4 +123,46 @@ mod tests {
             assert_eq!(result, home);
         }
     }
+
+    #[test]
+    fn test_split_line_col_suffix() {
+        assert_eq!(split_line_col_suffix("foo.txt"), ("foo.txt", None, None));
+        assert_eq!(
+            split_line_col_suffix("foo.txt:42"),
+            ("foo.txt", Some(42), None)
+        );
+        assert_eq!(
+            split_line_col_suffix("src/foo.rs:42:10"),
+            ("src/foo.rs", Some(42), Some(10))
+        );
+        // Non-numeric suffixes are part of the name
+        assert_eq!(split_line_col_suffix("foo:bar"), ("foo:bar", None, None));
+        assert_eq!(
+            split_line_col_suffix("foo:bar:10"),
+            ("foo:bar:10", None, None)
+        );
+        assert_eq!(
+            split_line_col_suffix("foo.txt:+3"),
+            ("foo.txt:+3", None, None)
+        );
+        // A bare number is a file name, not a line
+        assert_eq!(split_line_col_suffix("42"), ("42", None, None));
+        assert_eq!(split_line_col_suffix(":42"), (":42", None, None));
+    }
+
+    #[test]
+    fn test_split_line_col_suffix_windows_drive() {
+        assert_eq!(
+            split_line_col_suffix(r"C:\src\main.rs"),
+            (r"C:\src\main.rs", None, None)
+        );
+        assert_eq!(
+            split_line_col_suffix(r"C:\src\main.rs:3"),
+            (r"C:\src\main.rs", Some(3), None)
+        );
+        assert_eq!(
+            split_line_col_suffix("D:/src/main.rs:3:2"),
+            ("D:/src/main.rs", Some(3), Some(2))
+        );
+    }
 }
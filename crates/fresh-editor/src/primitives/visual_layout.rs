@@ -17,13 +17,13 @@
 use crate::primitives::ansi::AnsiParser;
 use crate::primitives::display_width::char_width;
 
-/// Standard tab width for terminal display
-pub const TAB_WIDTH: usize = 8;
-
 /// Calculate tab expansion width at a given column
+///
+/// `tab_size` is the buffer's tab width; 0 is treated as 1.
 #[inline]
-pub fn tab_expansion_width(col: usize) -> usize {
-    TAB_WIDTH - (col % TAB_WIDTH)
+pub fn tab_expansion_width(col: usize, tab_size: usize) -> usize {
+    let tab_size = tab_size.max(1);
+    tab_size - (col % tab_size)
 }
 
 /// Per-line mappings that support all visual layout operations with O(1) lookups
@@ -96,11 +96,12 @@ pub struct LineMappingsBuilder {
     mappings: LineMappings,
     current_visual_col: usize,
     ansi_parser: Option<AnsiParser>,
+    tab_size: usize,
 }
 
 impl LineMappingsBuilder {
     /// Create a new builder, optionally with ANSI parsing enabled
-    pub fn new(has_ansi: bool) -> Self {
+    pub fn new(has_ansi: bool, tab_size: usize) -> Self {
         Self {
             mappings: LineMappings::default(),
             current_visual_col: 0,
@@ -109,6 +110,7 @@ impl LineMappingsBuilder {
             } else {
                 None
             },
+            tab_size,
         }
     }
 
@@ -130,7 +132,7 @@ impl LineMappingsBuilder {
 
         // Regular character (possibly zero-width Unicode)
         let width = if ch == '\t' {
-            tab_expansion_width(self.current_visual_col)
+            tab_expansion_width(self.current_visual_col, self.tab_size)
         } else {
             char_width(ch)
         };
@@ -150,7 +152,7 @@ impl LineMappingsBuilder {
 
     /// Add a tab character with custom expansion
     pub fn add_tab(&mut self, source_byte: Option<usize>) -> usize {
-        let width = tab_expansion_width(self.current_visual_col);
+        let width = tab_expansion_width(self.current_visual_col, self.tab_size);
         let char_idx = self.mappings.char_source_bytes.len();
 
         self.mappings.char_source_bytes.push(source_byte);
@@ -180,7 +182,7 @@ impl LineMappingsBuilder {
 ///
 /// This is the canonical function for visual width calculation.
 /// Use this instead of `str_width()` when the text may contain ANSI codes or tabs.
-pub fn visual_width(s: &str, start_col: usize) -> usize {
+pub fn visual_width(s: &str, start_col: usize, tab_size: usize) -> usize {
     if !s.contains('\x1b') && !s.contains('\t') {
         // Fast path: no special handling needed
         return crate::primitives::display_width::str_width(s);
@@ -194,7 +196,7 @@ pub fn visual_width(s: &str, start_col: usize) -> usize {
             continue; // ANSI escape char, skip
         }
         if ch == '\t' {
-            col += tab_expansion_width(col);
+            col += tab_expansion_width(col, tab_size);
         } else {
             col += char_width(ch);
         }
@@ -206,7 +208,7 @@ pub fn visual_width(s: &str, start_col: usize) -> usize {
 /// Convert byte offset to visual column (ANSI-aware, tab-aware)
 ///
 /// Given a byte offset within the string, returns the visual column at that position.
-pub fn byte_to_visual_col(s: &str, byte_offset: usize) -> usize {
+pub fn byte_to_visual_col(s: &str, byte_offset: usize, tab_size: usize) -> usize {
     let clamped_offset = byte_offset.min(s.len());
 
     if !s.contains('\x1b') && !s.contains('\t') {
@@ -226,7 +228,7 @@ pub fn byte_to_visual_col(s: &str, byte_offset: usize) -> usize {
         if parser.parse_char(ch).is_some() {
             // Visible character
             if ch == '\t' {
-                col += tab_expansion_width(col);
+                col += tab_expansion_width(col, tab_size);
             } else {
                 col += char_width(ch);
             }
@@ -243,7 +245,7 @@ pub fn byte_to_visual_col(s: &str, byte_offset: usize) -> usize {
 ///
 /// Given a visual column, returns the byte offset of the character at or after that column.
 /// If the visual column is beyond the string's width, returns the string's length.
-pub fn visual_col_to_byte(s: &str, target_visual_col: usize) -> usize {
+pub fn visual_col_to_byte(s: &str, target_visual_col: usize, tab_size: usize) -> usize {
     if !s.contains('\x1b') && !s.contains('\t') {
         // Fast path: use simple character iteration (no ANSI, no tabs)
         let mut col = 0;
@@ -265,7 +267,7 @@ pub fn visual_col_to_byte(s: &str, target_visual_col: usize) -> usize {
         if parser.parse_char(ch).is_some() {
             // Visible character - check if target falls within this char's range
             let width = if ch == '\t' {
-                tab_expansion_width(col)
+                tab_expansion_width(col, tab_size)
             } else {
                 char_width(ch)
             };
@@ -290,8 +292,9 @@ pub fn build_line_mappings(
     text: &str,
     source_bytes: impl Iterator<Item = Option<usize>>,
     has_ansi: bool,
+    tab_size: usize,
 ) -> LineMappings {
-    let mut builder = LineMappingsBuilder::new(has_ansi);
+    let mut builder = LineMappingsBuilder::new(has_ansi, tab_size);
     let mut source_iter = source_bytes;
 
     for ch in text.chars() {
@@ -308,92 +311,96 @@ mod tests {
 
     #[test]
     fn test_visual_width_ascii() {
-        assert_eq!(visual_width("Hello", 0), 5);
-        assert_eq!(visual_width("", 0), 0);
+        assert_eq!(visual_width("Hello", 0, 8), 5);
+        assert_eq!(visual_width("", 0, 8), 0);
     }
 
     #[test]
     fn test_visual_width_with_tabs() {
         // Tab at column 0 expands to 8 spaces
-        assert_eq!(visual_width("\t", 0), 8);
+        assert_eq!(visual_width("\t", 0, 8), 8);
         // Tab at column 4 expands to 4 spaces
-        assert_eq!(visual_width("1234\t", 0), 8);
+        assert_eq!(visual_width("1234\t", 0, 8), 8);
         // "12" (2) + tab (6 to reach 8) = 8
-        assert_eq!(visual_width("12\t", 0), 8);
+        assert_eq!(visual_width("12\t", 0, 8), 8);
+        // The tab width comes from the buffer's tab size
+        assert_eq!(visual_width("\t", 0, 4), 4);
+        assert_eq!(visual_width("12\t", 0, 4), 4);
+        assert_eq!(visual_width("\t", 3, 2), 1);
     }
 
     #[test]
     fn test_visual_width_with_ansi() {
         // ANSI escape sequences should have zero width
-        assert_eq!(visual_width("\x1b[31mRed\x1b[0m", 0), 3);
-        assert_eq!(visual_width("\x1b[1;31;4mBold\x1b[0m", 0), 4);
+        assert_eq!(visual_width("\x1b[31mRed\x1b[0m", 0, 8), 3);
+        assert_eq!(visual_width("\x1b[1;31;4mBold\x1b[0m", 0, 8), 4);
     }
 
     #[test]
     fn test_visual_width_cjk() {
         // CJK characters are 2 columns each
-        assert_eq!(visual_width("你好", 0), 4);
-        assert_eq!(visual_width("Hello你好", 0), 9);
+        assert_eq!(visual_width("你好", 0, 8), 4);
+        assert_eq!(visual_width("Hello你好", 0, 8), 9);
     }
 
     #[test]
     fn test_byte_to_visual_col_simple() {
         let s = "Hello";
-        assert_eq!(byte_to_visual_col(s, 0), 0);
-        assert_eq!(byte_to_visual_col(s, 1), 1);
-        assert_eq!(byte_to_visual_col(s, 5), 5);
+        assert_eq!(byte_to_visual_col(s, 0, 8), 0);
+        assert_eq!(byte_to_visual_col(s, 1, 8), 1);
+        assert_eq!(byte_to_visual_col(s, 5, 8), 5);
     }
 
     #[test]
     fn test_byte_to_visual_col_with_ansi() {
         // "\x1b[31m" is 5 bytes, "Red" is 3 bytes
         let s = "\x1b[31mRed";
-        assert_eq!(byte_to_visual_col(s, 0), 0); // At ESC
-        assert_eq!(byte_to_visual_col(s, 5), 0); // At 'R' (ANSI prefix has 0 width)
-        assert_eq!(byte_to_visual_col(s, 6), 1); // At 'e'
-        assert_eq!(byte_to_visual_col(s, 8), 3); // Past end
+        assert_eq!(byte_to_visual_col(s, 0, 8), 0); // At ESC
+        assert_eq!(byte_to_visual_col(s, 5, 8), 0); // At 'R' (ANSI prefix has 0 width)
+        assert_eq!(byte_to_visual_col(s, 6, 8), 1); // At 'e'
+        assert_eq!(byte_to_visual_col(s, 8, 8), 3); // Past end
     }
 
     #[test]
     fn test_byte_to_visual_col_with_cjk() {
         // "你" is 3 bytes and 2 columns
         let s = "a你b";
-        assert_eq!(byte_to_visual_col(s, 0), 0); // 'a'
-        assert_eq!(byte_to_visual_col(s, 1), 1); // '你' start
-        assert_eq!(byte_to_visual_col(s, 4), 3); // 'b'
+        assert_eq!(byte_to_visual_col(s, 0, 8), 0); // 'a'
+        assert_eq!(byte_to_visual_col(s, 1, 8), 1); // '你' start
+        assert_eq!(byte_to_visual_col(s, 4, 8), 3); // 'b'
     }
 
     #[test]
     fn test_visual_col_to_byte_simple() {
         let s = "Hello";
-        assert_eq!(visual_col_to_byte(s, 0), 0);
-        assert_eq!(visual_col_to_byte(s, 3), 3);
-        assert_eq!(visual_col_to_byte(s, 5), 5);
-        assert_eq!(visual_col_to_byte(s, 10), 5); // Past end
+        assert_eq!(visual_col_to_byte(s, 0, 8), 0);
+        assert_eq!(visual_col_to_byte(s, 3, 8), 3);
+        assert_eq!(visual_col_to_byte(s, 5, 8), 5);
+        assert_eq!(visual_col_to_byte(s, 10, 8), 5); // Past end
     }
 
     #[test]
     fn test_visual_col_to_byte_with_ansi() {
         // "\x1b[31m" is 5 bytes, "Red" is 3 bytes
         let s = "\x1b[31mRed";
-        assert_eq!(visual_col_to_byte(s, 0), 5); // Visual col 0 = 'R' at byte 5
-        assert_eq!(visual_col_to_byte(s, 1), 6); // Visual col 1 = 'e' at byte 6
-        assert_eq!(visual_col_to_byte(s, 3), 8); // Past end
+        assert_eq!(visual_col_to_byte(s, 0, 8), 5); // Visual col 0 = 'R' at byte 5
+        assert_eq!(visual_col_to_byte(s, 1, 8), 6); // Visual col 1 = 'e' at byte 6
+        assert_eq!(visual_col_to_byte(s, 3, 8), 8); // Past end
     }
 
     #[test]
     fn test_visual_col_to_byte_with_cjk() {
         // "a你b" - 'a' at 0, '你' at 1-3, 'b' at 4
         let s = "a你b";
-        assert_eq!(visual_col_to_byte(s, 0), 0); // 'a'
-        assert_eq!(visual_col_to_byte(s, 1), 1); // '你' (both cols 1 and 2 map to byte 1)
-        assert_eq!(visual_col_to_byte(s, 2), 1); // Still '你'
-        assert_eq!(visual_col_to_byte(s, 3), 4); // 'b'
+        assert_eq!(visual_col_to_byte(s, 0, 8), 0); // 'a'
+        assert_eq!(visual_col_to_byte(s, 1, 8), 1); // '你' (both cols 1 and 2 map to byte 1)
+        assert_eq!(visual_col_to_byte(s, 2, 8), 1); // Still '你'
+        assert_eq!(visual_col_to_byte(s, 3, 8), 4); // 'b'
     }
 
     #[test]
     fn test_line_mappings_builder_simple() {
-        let mut builder = LineMappingsBuilder::new(false);
+        let mut builder = LineMappingsBuilder::new(false, 8);
         builder.add_char('H', Some(0));
         builder.add_char('i', Some(1));
 
@@ -409,7 +416,7 @@ mod tests {
 
     #[test]
     fn test_line_mappings_builder_with_cjk() {
-        let mut builder = LineMappingsBuilder::new(false);
+        let mut builder = LineMappingsBuilder::new(false, 8);
         builder.add_char('a', Some(0)); // 1 column
         builder.add_char('你', Some(1)); // 2 columns
         builder.add_char('b', Some(4)); // 1 column
@@ -431,7 +438,7 @@ mod tests {
 
     #[test]
     fn test_line_mappings_builder_with_ansi() {
-        let mut builder = LineMappingsBuilder::new(true);
+        let mut builder = LineMappingsBuilder::new(true, 8);
 
         // Simulate "\x1b[31mA" - ANSI prefix (5 chars) + 'A'
         builder.add_char('\x1b', Some(0));
@@ -460,7 +467,7 @@ mod tests {
 
     #[test]
     fn test_line_mappings_cursor_on_ansi() {
-        let mut builder = LineMappingsBuilder::new(true);
+        let mut builder = LineMappingsBuilder::new(true, 8);
 
         // "\x1b[31mHi" - cursor at byte 0 (ESC) should work
         builder.add_char('\x1b', Some(0));
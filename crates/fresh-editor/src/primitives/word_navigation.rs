@@ -1,6 +1,7 @@
 //! Word boundary detection and navigation helpers
 
 use crate::model::buffer::Buffer;
+use std::ops::Range;
 
 /// Check if a byte is a word character (alphanumeric or underscore)
 pub fn is_word_char(byte: u8) -> bool {
@@ -199,17 +200,20 @@ pub fn find_word_end(buffer: &Buffer, pos: usize) -> usize {
     start + result
 }
 
-use crate::primitives::grapheme::{next_grapheme_boundary, prev_grapheme_boundary};
+use unicode_segmentation::UnicodeSegmentation;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum CharClass {
     Word,
     Whitespace,
     Punctuation,
+    Emoji,
 }
 
 fn get_grapheme_class(g: &str) -> CharClass {
-    if g.chars().any(|c| c.is_alphanumeric() || c == '_') {
+    if g.chars().any(is_emoji_char) {
+        CharClass::Emoji
+    } else if g.chars().any(|c| c.is_alphanumeric() || c == '_') {
         CharClass::Word
     } else if g.chars().all(|c| c.is_whitespace()) {
         CharClass::Whitespace
@@ -218,7 +222,74 @@ fn get_grapheme_class(g: &str) -> CharClass {
     }
 }
 
+/// Check if a character is a pictographic emoji or the selector that asks for
+/// emoji presentation (ZWJ sequences are whole grapheme clusters already)
+fn is_emoji_char(c: char) -> bool {
+    matches!(c as u32,
+        0x1F000..=0x1FAFF // Pictographs, emoticons, symbols, regional indicators
+        | 0x2600..=0x27BF // Miscellaneous symbols and dingbats
+        | 0xFE0F          // Emoji presentation selector
+    )
+}
+
+/// Check if a character belongs to a CJK script written without spaces,
+/// where every character is treated as a word of its own
+fn is_cjk_char(c: char) -> bool {
+    matches!(c as u32,
+        0x3040..=0x30FF     // Hiragana, Katakana
+        | 0x31F0..=0x31FF   // Katakana phonetic extensions
+        | 0x3400..=0x4DBF   // CJK unified ideographs extension A
+        | 0x4E00..=0x9FFF   // CJK unified ideographs
+        | 0xAC00..=0xD7AF   // Hangul syllables
+        | 0xF900..=0xFAFF   // CJK compatibility ideographs
+        | 0xFF66..=0xFF9F   // Halfwidth Katakana
+        | 0x20000..=0x2FFFF // CJK unified ideographs extensions B onwards
+    )
+}
+
+/// A run of text that word motion steps over as one unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct WordToken {
+    start: usize,
+    end: usize,
+    class: CharClass,
+}
+
+/// Split text into the units word motion stops at.
+///
+/// Words are found with Unicode word boundaries (UAX #29) over grapheme
+/// clusters, then split where the character class changes, so `foo.bar`
+/// is still three units. CJK characters and emoji clusters (including ZWJ
+/// sequences) are one unit each; runs of whitespace or punctuation are
+/// merged into one unit.
+fn word_tokens(text: &str) -> Vec<WordToken> {
+    let mut tokens: Vec<WordToken> = Vec::new();
+    for (seg_start, segment) in text.split_word_bound_indices() {
+        let mut joinable = false; // Whether a Word grapheme may extend the last token
+        for (offset, g) in segment.grapheme_indices(true) {
+            let start = seg_start + offset;
+            let end = start + g.len();
+            let class = get_grapheme_class(g);
+            let cjk = g.chars().any(is_cjk_char);
+            let extend = match class {
+                CharClass::Word => joinable && !cjk,
+                CharClass::Whitespace | CharClass::Punctuation => true,
+                CharClass::Emoji => false,
+            };
+            match tokens.last_mut() {
+                Some(last) if extend && last.class == class && last.end == start => last.end = end,
+                _ => tokens.push(WordToken { start, end, class }),
+            }
+            joinable = class == CharClass::Word && !cjk;
+        }
+    }
+    tokens
+}
+
 /// Find the start of the word to the left of the given position
+///
+/// Ctrl+Left behavior: skip whitespace to the left, then jump to the start
+/// of the word, punctuation run, CJK character or emoji before it.
 pub fn find_word_start_left(buffer: &Buffer, pos: usize) -> usize {
     if pos == 0 {
         return 0;
@@ -236,46 +307,17 @@ pub fn find_word_start_left(buffer: &Buffer, pos: usize) -> usize {
     // Convert to string safely (replacing invalid sequences which might happen at the start boundary)
     let text = String::from_utf8_lossy(&bytes);
 
-    // The end of the string corresponds to `pos`
-    let mut current_idx = text.len();
-
-    // 1. Consume whitespace to the left
-    while current_idx > 0 {
-        let prev = prev_grapheme_boundary(&text, current_idx);
-        let g = &text[prev..current_idx];
-        if get_grapheme_class(g) == CharClass::Whitespace {
-            current_idx = prev;
-        } else {
-            break;
-        }
-    }
-
-    if current_idx == 0 {
-        // We consumed everything up to the start of our chunk
-        // Best we can do is return the start of the chunk
-        // (In reality, words > 1000 chars are rare)
-        // We calculate absolute position:
-        // logic: bytes.len() - current_idx is the delta we moved back
-        let delta = text.len() - current_idx;
-        return actual_pos.saturating_sub(delta);
-    }
-
-    // 2. Identify class of the token we hit
-    let prev = prev_grapheme_boundary(&text, current_idx);
-    let target_class = get_grapheme_class(&text[prev..current_idx]);
-
-    // 3. Consume all characters of the same class
-    while current_idx > 0 {
-        let prev = prev_grapheme_boundary(&text, current_idx);
-        let g = &text[prev..current_idx];
-        if get_grapheme_class(g) == target_class {
-            current_idx = prev;
-        } else {
-            break;
-        }
-    }
-
-    let delta = text.len() - current_idx;
+    // The end of the string corresponds to `pos`; offsets are measured back
+    // from it, since a lossy start boundary may change the text's length.
+    // If only whitespace is left, the best we can do is the start of the
+    // chunk (in reality, words > 1000 chars are rare).
+    let target = word_tokens(&text)
+        .into_iter()
+        .rev()
+        .find(|t| t.class != CharClass::Whitespace)
+        .map_or(0, |t| t.start);
+
+    let delta = text.len() - target;
     actual_pos.saturating_sub(delta)
 }
 
@@ -285,6 +327,8 @@ pub fn find_word_start_left(buffer: &Buffer, pos: usize) -> usize {
 /// - From within a word: jump to the end of the current word
 /// - From whitespace: skip whitespace, then jump to end of next token (word or punctuation)
 /// - From punctuation: consume all punctuation, then stop
+///
+/// CJK characters and emoji clusters are tokens of their own.
 pub fn find_word_end_right(buffer: &Buffer, pos: usize) -> usize {
     let buf_len = buffer.len();
     if pos >= buf_len {
@@ -297,69 +341,16 @@ pub fn find_word_end_right(buffer: &Buffer, pos: usize) -> usize {
     let bytes = buffer.slice_bytes(start..end);
     let text = String::from_utf8_lossy(&bytes);
 
-    let mut current_idx = 0;
-    if current_idx >= text.len() {
-        return start;
-    }
-
-    // Look at the grapheme at current position
-    let next_bound = next_grapheme_boundary(&text, current_idx);
-    let start_class = get_grapheme_class(&text[current_idx..next_bound]);
-
-    match start_class {
-        CharClass::Word => {
-            // In a word: jump to end of current word
-            while current_idx < text.len() {
-                let next = next_grapheme_boundary(&text, current_idx);
-                let g = &text[current_idx..next];
-                if get_grapheme_class(g) == CharClass::Word {
-                    current_idx = next;
-                } else {
-                    break;
-                }
-            }
-        }
-        CharClass::Whitespace => {
-            // On whitespace: skip whitespace, then consume next token
-            while current_idx < text.len() {
-                let next = next_grapheme_boundary(&text, current_idx);
-                let g = &text[current_idx..next];
-                if get_grapheme_class(g) == CharClass::Whitespace {
-                    current_idx = next;
-                } else {
-                    break;
-                }
-            }
-            // Consume the token we landed on (word or punctuation)
-            if current_idx < text.len() {
-                let next = next_grapheme_boundary(&text, current_idx);
-                let landed_class = get_grapheme_class(&text[current_idx..next]);
-                while current_idx < text.len() {
-                    let next = next_grapheme_boundary(&text, current_idx);
-                    let g = &text[current_idx..next];
-                    if get_grapheme_class(g) == landed_class {
-                        current_idx = next;
-                    } else {
-                        break;
-                    }
-                }
-            }
-        }
-        CharClass::Punctuation => {
-            // On punctuation: consume all punctuation, then stop
-            while current_idx < text.len() {
-                let next = next_grapheme_boundary(&text, current_idx);
-                let g = &text[current_idx..next];
-                if get_grapheme_class(g) == CharClass::Punctuation {
-                    current_idx = next;
-                } else {
-                    break;
-                }
-            }
-        }
-    }
+    let tokens = word_tokens(&text);
+    let mut tokens = tokens.iter();
+    let end = match tokens.next() {
+        // On whitespace: skip it, then consume the token we land on
+        Some(t) if t.class == CharClass::Whitespace => tokens.next().unwrap_or(t).end,
+        Some(t) => t.end,
+        None => 0,
+    };
 
-    start + current_idx
+    start + end
 }
 
 /// Find the start of the word to the right of the given position
@@ -375,52 +366,65 @@ pub fn find_word_start_right(buffer: &Buffer, pos: usize) -> usize {
     let bytes = buffer.slice_bytes(start..end);
     let text = String::from_utf8_lossy(&bytes);
 
-    let mut current_idx = 0;
-    if current_idx >= text.len() {
-        return start;
-    }
-
-    // Look at the grapheme at current position
-    let next_bound = next_grapheme_boundary(&text, current_idx);
-    let start_class = get_grapheme_class(&text[current_idx..next_bound]);
+    let tokens = word_tokens(&text);
+    let mut tokens = tokens.iter().peekable();
+    let end = match tokens.next() {
+        // 1. If starting on whitespace, just consume it and stop
+        Some(t) if t.class == CharClass::Whitespace => t.end,
+        // 2. Otherwise consume the token, then any whitespace after it to
+        //    land at the start of the next token
+        Some(t) => match tokens.peek() {
+            Some(next) if next.class == CharClass::Whitespace => next.end,
+            _ => t.end,
+        },
+        None => 0,
+    };
+
+    start + end
+}
 
-    // 1. If starting on whitespace, just consume it and stop
-    if start_class == CharClass::Whitespace {
-        while current_idx < text.len() {
-            let next = next_grapheme_boundary(&text, current_idx);
-            let g = &text[current_idx..next];
-            if get_grapheme_class(g) == CharClass::Whitespace {
-                current_idx = next;
-            } else {
-                break;
-            }
-        }
-        return start + current_idx;
-    }
+/// Find the word under the given position, for double-click selection
+///
+/// Uses the same segmentation as word motion (see `word_tokens`), so a click
+/// selects a whole Unicode word, a single CJK character or emoji cluster, a
+/// run of punctuation, or a run of whitespace. The range stays within the
+/// line; a position at the end of a line picks the unit before it. Returns
+/// an empty range on an empty line.
+pub fn find_word_range_at(buffer: &Buffer, pos: usize) -> Range<usize> {
+    let buf_len = buffer.len();
+    let pos = pos.min(buf_len);
 
-    // 2. Otherwise (Word or Punctuation), consume all characters of same class
-    while current_idx < text.len() {
-        let next = next_grapheme_boundary(&text, current_idx);
-        let g = &text[current_idx..next];
-        if get_grapheme_class(g) == start_class {
-            current_idx = next;
-        } else {
-            break;
-        }
-    }
+    // Only read a small window around the position for efficiency
+    let start = pos.saturating_sub(1000);
+    let end = (pos + 1000).min(buf_len);
+    let bytes = buffer.slice_bytes(start..end);
 
-    // 3. Then consume subsequent whitespace to land at start of next token
-    while current_idx < text.len() {
-        let next = next_grapheme_boundary(&text, current_idx);
-        let g = &text[current_idx..next];
-        if get_grapheme_class(g) == CharClass::Whitespace {
-            current_idx = next;
-        } else {
-            break;
-        }
+    // The window may cut characters at either end; keep whole ones only
+    let lead = bytes.iter().take_while(|&&b| (b & 0xC0) == 0x80).count();
+    let text = match std::str::from_utf8(&bytes[lead..]) {
+        Ok(text) => text,
+        Err(e) => std::str::from_utf8(&bytes[lead..lead + e.valid_up_to()]).unwrap_or_default(),
+    };
+    let base = start + lead;
+    let offset = pos.saturating_sub(base).min(text.len());
+
+    let line_start = text[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let mut line_end = text[offset..].find('\n').map_or(text.len(), |i| offset + i);
+    if text[..line_end].ends_with('\r') && line_end > offset {
+        line_end -= 1;
+    }
+    let line = &text[line_start..line_end];
+    let rel = offset - line_start;
+
+    let tokens = word_tokens(line);
+    let token = tokens
+        .iter()
+        .find(|t| t.start <= rel && rel < t.end)
+        .or_else(|| tokens.iter().find(|t| t.end == rel));
+    match token {
+        Some(t) => base + line_start + t.start..base + line_start + t.end,
+        None => pos..pos,
     }
-
-    start + current_idx
 }
 
 #[cfg(test)]
@@ -470,6 +474,95 @@ mod tests {
         assert_eq!(find_word_start_right(&buffer, 6), 12); // From "world" to "test"
     }
 
+    #[test]
+    fn test_word_motion_unicode_words() {
+        // "café" 0..5, "naïve" 6..12, 日 13..16, 本 16..19, 語 19..22, 😀 23..27, 👍 27..31
+        let buffer = Buffer::from_str_test("café naïve 日本語 😀👍");
+        let mut pos = 0;
+        let mut stops = Vec::new();
+        while pos < buffer.len() {
+            pos = find_word_end_right(&buffer, pos);
+            stops.push(pos);
+        }
+        assert_eq!(stops, vec![5, 12, 16, 19, 22, 27, 31]);
+
+        let mut stops = Vec::new();
+        while pos > 0 {
+            pos = find_word_start_left(&buffer, pos);
+            stops.push(pos);
+        }
+        assert_eq!(stops, vec![27, 23, 19, 16, 13, 6, 0]);
+
+        assert_eq!(find_word_start_right(&buffer, 0), 6); // "café " to "naïve"
+        assert_eq!(find_word_start_right(&buffer, 13), 16); // One ideograph
+        assert_eq!(find_word_start_right(&buffer, 22), 23); // Whitespace only
+    }
+
+    #[test]
+    fn test_word_motion_zwj_emoji_is_one_unit() {
+        // Family emoji: three people joined by ZWJ, 18 bytes
+        let buffer = Buffer::from_str_test("a \u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467} b");
+        assert_eq!(find_word_end_right(&buffer, 1), 20);
+        assert_eq!(find_word_start_left(&buffer, 20), 2);
+        assert_eq!(find_word_start_right(&buffer, 2), 21);
+    }
+
+    #[test]
+    fn test_find_word_range_at_unicode() {
+        let text = "say naïve 日本語 😀👍 wait...what  end\nnext";
+        let buffer = Buffer::from_str_test(text);
+        let range_of = |needle: &str| {
+            let start = text.find(needle).unwrap();
+            start..start + needle.len()
+        };
+        let inside = |needle: &str, chars: usize| {
+            let start = text.find(needle).unwrap();
+            start
+                + needle
+                    .chars()
+                    .take(chars)
+                    .map(char::len_utf8)
+                    .sum::<usize>()
+        };
+
+        assert_eq!(
+            find_word_range_at(&buffer, inside("naïve", 2)),
+            range_of("naïve")
+        );
+        assert_eq!(find_word_range_at(&buffer, inside("本", 0)), range_of("本"));
+        assert_eq!(find_word_range_at(&buffer, inside("👍", 0)), range_of("👍"));
+        assert_eq!(
+            find_word_range_at(&buffer, inside("...", 1)),
+            range_of("...")
+        );
+        assert_eq!(
+            find_word_range_at(&buffer, inside("  end", 1)),
+            range_of("  ")
+        );
+        // End of line picks the word before it, without crossing the newline
+        assert_eq!(
+            find_word_range_at(&buffer, inside("\n", 0)),
+            range_of("end")
+        );
+        assert_eq!(find_word_range_at(&buffer, text.len()), range_of("next"));
+    }
+
+    #[test]
+    fn test_find_word_range_at_empty_line() {
+        let buffer = Buffer::from_str_test("a\n\nb");
+        assert_eq!(find_word_range_at(&buffer, 2), 2..2);
+    }
+
+    #[test]
+    fn test_word_motion_keeps_code_boundaries() {
+        // UAX #29 joins "foo.bar" and "can't" into one word; punctuation still stops motion
+        let buffer = Buffer::from_str_test("foo.bar can't");
+        assert_eq!(find_word_end_right(&buffer, 0), 3);
+        assert_eq!(find_word_end_right(&buffer, 3), 4);
+        assert_eq!(find_word_end_right(&buffer, 4), 7);
+        assert_eq!(find_word_start_left(&buffer, 13), 12);
+    }
+
     // ========================================================================
     // Tests for byte-level word navigation (shared by Buffer and String)
     // ========================================================================
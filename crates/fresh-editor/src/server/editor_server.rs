@@ -323,6 +323,12 @@ impl EditorServer {
                 if editor.check_mouse_hover_timer() {
                     needs_render = true;
                 }
+                if editor.check_drag_autoscroll_timer() {
+                    needs_render = true;
+                }
+                if editor.check_file_suggestions_timer() {
+                    needs_render = true;
+                }
             }
 
             // Render and broadcast if needed
@@ -2,11 +2,20 @@
 //!
 //! Parses raw bytes from the client into crossterm events.
 //! This allows the server to handle all input parsing, keeping the client ultra-light.
+//! Bracketed pastes arrive as a single `Event::Paste` rather than keystrokes, so
+//! pasted newlines don't trigger auto-indent.
 
 use crossterm::event::{
     Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
 };
 
+/// Sequence ending a bracketed paste (the start is `ESC [ 200 ~`)
+const PASTE_END: &[u8] = b"\x1b[201~";
+
+/// Most bytes of a bracketed paste held at once; a longer paste, or one whose
+/// end sequence never arrives, is passed on in pieces of about this size
+const MAX_PASTE_BYTES: usize = 1024 * 1024;
+
 /// Parser state for incremental input parsing
 #[derive(Debug, Default)]
 pub struct InputParser {
@@ -14,6 +23,8 @@ pub struct InputParser {
     buffer: Vec<u8>,
     /// Maximum buffer size before we give up on an escape sequence
     max_buffer_size: usize,
+    /// Raw bytes of a bracketed paste in progress
+    paste: Option<Vec<u8>>,
 }
 
 impl InputParser {
@@ -21,6 +32,7 @@ impl InputParser {
         Self {
             buffer: Vec::with_capacity(32),
             max_buffer_size: 256,
+            paste: None,
         }
     }
 
@@ -29,6 +41,30 @@ impl InputParser {
         let mut events = Vec::new();
 
         for &byte in bytes {
+            // Pasted bytes are taken verbatim until the end sequence
+            if let Some(paste) = self.paste.as_mut() {
+                paste.push(byte);
+                if paste.ends_with(PASTE_END) {
+                    paste.truncate(paste.len() - PASTE_END.len());
+                    let text = String::from_utf8_lossy(paste).into_owned();
+                    events.push(Event::Paste(text));
+                    self.paste = None;
+                } else if paste.len() >= MAX_PASTE_BYTES {
+                    // Hold back what may be the start of the end sequence or
+                    // of a character split across pieces
+                    let mut split = paste.len() - (PASTE_END.len() - 1);
+                    if let Err(e) = std::str::from_utf8(&paste[..split]) {
+                        if e.error_len().is_none() {
+                            split = e.valid_up_to();
+                        }
+                    }
+                    let rest = paste.split_off(split);
+                    events.push(Event::Paste(String::from_utf8_lossy(paste).into_owned()));
+                    *paste = rest;
+                }
+                continue;
+            }
+
             self.buffer.push(byte);
 
             // Try to parse the buffer
@@ -37,6 +73,10 @@ impl InputParser {
                     events.push(event);
                     self.buffer.clear();
                 }
+                ParseResult::PasteStart => {
+                    self.buffer.clear();
+                    self.paste = Some(Vec::new());
+                }
                 ParseResult::Incomplete => {
                     // Need more bytes
                     if self.buffer.len() > self.max_buffer_size {
@@ -193,6 +233,9 @@ impl InputParser {
 
     /// Parse tilde sequences: CSI number ~
     fn parse_tilde_sequence(&self, params: &[u8]) -> ParseResult {
+        if params == b"200" {
+            return ParseResult::PasteStart;
+        }
         let (num, modifiers) = self.parse_num_and_modifiers(params);
 
         let keycode = match num {
@@ -399,6 +442,8 @@ impl InputParser {
 enum ParseResult {
     /// Successfully parsed a complete event
     Complete(Event),
+    /// Start of a bracketed paste
+    PasteStart,
     /// Need more bytes to complete the sequence
     Incomplete,
     /// Invalid sequence
@@ -625,4 +670,50 @@ mod tests {
             _ => panic!("Expected mouse motion event"),
         }
     }
+
+    #[test]
+    fn test_bracketed_paste_is_one_event() {
+        let mut parser = InputParser::new();
+        let text = "fn main() {\n    if x {\n\ty();\n    }\n}\n";
+        let input = format!("a\x1b[200~{text}\x1b[201~b");
+        let events = parser.parse(input.as_bytes());
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[1], Event::Paste(text.to_string()));
+        assert_eq!(
+            events[2],
+            Event::Key(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::NONE))
+        );
+    }
+
+    #[test]
+    fn test_bracketed_paste_split_across_reads() {
+        let mut parser = InputParser::new();
+        assert!(parser.parse(b"\x1b[20").is_empty());
+        assert!(parser.parse("0~héllo\x1b[A\x1b[2".as_bytes()).is_empty());
+        // Escape sequences inside the paste are kept as text
+        assert_eq!(
+            parser.parse(b"01~"),
+            vec![Event::Paste("héllo\x1b[A".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_long_bracketed_paste_is_passed_on_in_pieces() {
+        let mut parser = InputParser::new();
+        let text = "é".repeat(MAX_PASTE_BYTES);
+        let events = parser.parse(format!("\x1b[200~{text}").as_bytes());
+        // The paste is flushed before its end arrives, without splitting a character
+        assert_eq!(events.len(), 2);
+        assert!(parser.paste.as_ref().unwrap().len() < MAX_PASTE_BYTES);
+
+        let events = [events, parser.parse(b"\x1b[201~")].concat();
+        let pasted: String = events
+            .into_iter()
+            .map(|event| match event {
+                Event::Paste(piece) => piece,
+                other => panic!("unexpected event {other:?}"),
+            })
+            .collect();
+        assert_eq!(pasted, text);
+    }
 }
@@ -167,6 +167,32 @@ pub enum AsyncMessage {
     /// File open dialog: async shortcuts (Windows drive letters) loaded
     FileOpenShortcutsLoaded(Vec<crate::app::file_open::NavigationShortcut>),
 
+    /// A batch of Search in Project matches, with `done` set on the last one
+    ProjectSearchResults {
+        search_id: u64,
+        matches: Vec<crate::services::project_search::ProjectSearchMatch>,
+        done: bool,
+    },
+
+    /// Result of looking up a definition with the definition providers
+    DefinitionFound {
+        request_id: u64,
+        location: Option<crate::services::definition::DefinitionLocation>,
+    },
+
+    /// Diagnostics a diagnostics provider reported for a file
+    ProviderDiagnostics {
+        uri: String,
+        source: String,
+        diagnostics: Vec<Diagnostic>,
+    },
+
+    /// Quick Open file suggestions for the query of request `request_id`
+    QuickOpenFileSuggestions {
+        request_id: u64,
+        suggestions: Vec<crate::input::commands::Suggestion>,
+    },
+
     /// Terminal output received (triggers redraw)
     TerminalOutput { terminal_id: TerminalId },
 
@@ -7,12 +7,30 @@
 //! - Supports copying HTML-formatted text for rich text editors
 //! - Gracefully falls back to internal clipboard if system clipboard is unavailable
 //! - Respects clipboard configuration to disable problematic methods
+//! - Keeps an X11-style primary selection for middle-click paste
+//! - Keeps named registers (`a`-`z`) for the session, separate from the clipboard
+//! - Keeps a history of recent copies, including ones made in other applications
 
 use crossterm::clipboard::CopyToClipboard;
 use crossterm::execute;
+use std::collections::{HashMap, VecDeque};
 use std::io::{stdout, Write};
 use std::sync::Mutex;
 
+/// The register that stands for the clipboard itself
+pub const DEFAULT_REGISTER: char = '"';
+
+/// Whether `name` is a register: `a`-`z` or the default register
+pub fn is_register_name(name: char) -> bool {
+    name.is_ascii_lowercase() || name == DEFAULT_REGISTER
+}
+
+/// Most entries kept in the clipboard history
+const HISTORY_MAX_ENTRIES: usize = 50;
+
+/// Most bytes kept in the clipboard history, across all entries
+const HISTORY_MAX_BYTES: usize = 4 * 1024 * 1024;
+
 /// Global clipboard holder to maintain X11 clipboard ownership for the application lifetime.
 /// On X11, the clipboard owner must stay alive to respond to paste requests from other apps.
 static SYSTEM_CLIPBOARD: Mutex<Option<arboard::Clipboard>> = Mutex::new(None);
@@ -22,6 +40,12 @@ static SYSTEM_CLIPBOARD: Mutex<Option<arboard::Clipboard>> = Mutex::new(None);
 pub struct Clipboard {
     /// Internal clipboard content (always available)
     internal: String,
+    /// Primary selection content, pasted by middle-click
+    primary: String,
+    /// Named register contents, by register name
+    registers: HashMap<char, String>,
+    /// Recently copied texts, newest first, without duplicates
+    history: VecDeque<String>,
     /// When true, paste() uses internal clipboard only (for testing)
     internal_only: bool,
     /// When true, OSC 52 escape sequences are used for clipboard copy
@@ -35,6 +59,9 @@ impl Clipboard {
     pub fn new() -> Self {
         Self {
             internal: String::new(),
+            primary: String::new(),
+            registers: HashMap::new(),
+            history: VecDeque::new(),
             internal_only: false,
             use_osc52: true,
             use_system_clipboard: true,
@@ -60,6 +87,7 @@ impl Clipboard {
     /// This allows pasting styled/colored text into applications that support rich text.
     /// Returns true if successful, false otherwise.
     pub fn copy_html(&mut self, html: &str, plain_text: &str) -> bool {
+        self.record_history(plain_text);
         self.internal = plain_text.to_string();
 
         if !self.use_system_clipboard {
@@ -103,6 +131,7 @@ impl Clipboard {
     ///
     /// Methods can be disabled via clipboard configuration.
     pub fn copy(&mut self, text: String) {
+        self.record_history(&text);
         self.internal = text.clone();
 
         // Try OSC 52 first (works in modern terminals)
@@ -168,24 +197,13 @@ impl Clipboard {
         }
 
         // Try arboard crate via the static clipboard (reads from system clipboard)
-        if self.use_system_clipboard {
-            if let Ok(mut guard) = SYSTEM_CLIPBOARD.lock() {
-                // Create clipboard if it doesn't exist yet
-                if guard.is_none() {
-                    if let Ok(cb) = arboard::Clipboard::new() {
-                        *guard = Some(cb);
-                    }
-                }
-
-                if let Some(clipboard) = guard.as_mut() {
-                    if let Ok(text) = clipboard.get_text() {
-                        if !text.is_empty() {
-                            self.internal = text.clone();
-                            return Some(text);
-                        }
-                    }
-                }
+        if let Some(text) = self.read_system_clipboard() {
+            if text != self.internal {
+                // Copied in another application
+                self.record_history(&text);
             }
+            self.internal = text.clone();
+            return Some(text);
         }
 
         // Fall back to internal clipboard
@@ -196,6 +214,115 @@ impl Clipboard {
         }
     }
 
+    /// Read non-empty text from the system clipboard, if it is enabled
+    fn read_system_clipboard(&self) -> Option<String> {
+        if !self.use_system_clipboard {
+            return None;
+        }
+        let mut guard = SYSTEM_CLIPBOARD.lock().ok()?;
+        // Create clipboard if it doesn't exist yet
+        if guard.is_none() {
+            if let Ok(cb) = arboard::Clipboard::new() {
+                *guard = Some(cb);
+            }
+        }
+        guard
+            .as_mut()?
+            .get_text()
+            .ok()
+            .filter(|text| !text.is_empty())
+    }
+
+    /// Add a copy made in another application to the history
+    ///
+    /// Does nothing in internal-only mode or where the system clipboard
+    /// can't be read.
+    pub fn refresh_history(&mut self) {
+        if self.internal_only {
+            return;
+        }
+        if let Some(text) = self.read_system_clipboard() {
+            if text != self.internal {
+                self.record_history(&text);
+                self.internal = text;
+            }
+        }
+    }
+
+    /// Recently copied texts, newest first
+    pub fn history(&self) -> impl Iterator<Item = &str> {
+        self.history.iter().map(String::as_str)
+    }
+
+    /// Add `text` to the front of the history
+    ///
+    /// An earlier copy of the same text is removed, and the oldest entries
+    /// are dropped to stay within the entry and byte limits. Text larger
+    /// than the byte limit on its own isn't recorded.
+    fn record_history(&mut self, text: &str) {
+        if text.is_empty() || text.len() > HISTORY_MAX_BYTES {
+            return;
+        }
+        self.history.retain(|entry| entry != text);
+        self.history.push_front(text.to_string());
+        self.history.truncate(HISTORY_MAX_ENTRIES);
+        let mut total = 0;
+        let within_limit = self
+            .history
+            .iter()
+            .take_while(|entry| {
+                total += entry.len();
+                total <= HISTORY_MAX_BYTES
+            })
+            .count();
+        self.history.truncate(within_limit);
+    }
+
+    /// Set the primary selection, separate from the clipboard
+    ///
+    /// On Linux this also takes over the system primary selection so other
+    /// applications can middle-click paste it.
+    pub fn set_primary(&mut self, text: String) {
+        if self.use_system_clipboard && !self.internal_only {
+            set_system_primary(&text);
+        }
+        self.primary = text;
+    }
+
+    /// Get text for a middle-click paste
+    ///
+    /// On Linux this is the system primary selection, falling back to the
+    /// one set in the editor. Other platforms have no primary selection, so
+    /// the clipboard is pasted instead.
+    pub fn paste_primary(&mut self) -> Option<String> {
+        if !cfg!(target_os = "linux") {
+            return self.paste();
+        }
+        if self.use_system_clipboard && !self.internal_only {
+            if let Some(text) = get_system_primary() {
+                return Some(text);
+            }
+        }
+        if self.primary.is_empty() {
+            None
+        } else {
+            Some(self.primary.clone())
+        }
+    }
+
+    /// Store text in a named register
+    pub fn set_register(&mut self, name: char, text: String) {
+        self.registers.insert(name, text);
+    }
+
+    /// Get the content of a named register, if it holds any text
+    pub fn register(&self, name: char) -> Option<&str> {
+        self.registers
+            .get(&name)
+            .map(String::as_str)
+            .filter(|text| !text.is_empty())
+    }
+
     /// Get the internal clipboard content without checking system clipboard
     pub fn get_internal(&self) -> &str {
         &self.internal
@@ -243,6 +370,59 @@ impl Clipboard {
     }
 }
 
+/// Claim the X11/Wayland primary selection with `text`
+#[cfg(target_os = "linux")]
+fn set_system_primary(text: &str) {
+    use arboard::{LinuxClipboardKind, SetExtLinux};
+
+    if let Ok(mut guard) = SYSTEM_CLIPBOARD.lock() {
+        if guard.is_none() {
+            match arboard::Clipboard::new() {
+                Ok(cb) => *guard = Some(cb),
+                Err(e) => {
+                    tracing::debug!("arboard clipboard init failed for primary: {}", e);
+                    return;
+                }
+            }
+        }
+        if let Some(clipboard) = guard.as_mut() {
+            if let Err(e) = clipboard
+                .set()
+                .clipboard(LinuxClipboardKind::Primary)
+                .text(text)
+            {
+                tracing::debug!("arboard primary selection copy failed: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn set_system_primary(_text: &str) {}
+
+/// Read the X11/Wayland primary selection, if it holds any text
+#[cfg(target_os = "linux")]
+fn get_system_primary() -> Option<String> {
+    use arboard::{GetExtLinux, LinuxClipboardKind};
+
+    let mut guard = SYSTEM_CLIPBOARD.lock().ok()?;
+    if guard.is_none() {
+        *guard = Some(arboard::Clipboard::new().ok()?);
+    }
+    let text = guard
+        .as_mut()?
+        .get()
+        .clipboard(LinuxClipboardKind::Primary)
+        .text()
+        .ok()?;
+    (!text.is_empty()).then_some(text)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn get_system_primary() -> Option<String> {
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -287,6 +467,31 @@ mod tests {
         assert!(!clipboard.use_system_clipboard);
     }
 
+    #[test]
+    fn test_history_deduplicates_and_caps() {
+        let mut clipboard = Clipboard::new();
+        clipboard.record_history("one");
+        clipboard.record_history("two");
+        clipboard.record_history("one");
+        assert_eq!(clipboard.history().collect::<Vec<_>>(), ["one", "two"]);
+
+        for i in 0..HISTORY_MAX_ENTRIES + 5 {
+            clipboard.record_history(&i.to_string());
+        }
+        assert_eq!(clipboard.history().count(), HISTORY_MAX_ENTRIES);
+        let newest = (HISTORY_MAX_ENTRIES + 4).to_string();
+        assert_eq!(clipboard.history().next(), Some(newest.as_str()));
+
+        // A large copy pushes older entries out to stay within the byte limit
+        clipboard.record_history(&"x".repeat(HISTORY_MAX_BYTES - 1));
+        assert_eq!(clipboard.history().count(), 1);
+        // One larger than the limit isn't kept, and older entries stay
+        clipboard.record_history("z");
+        clipboard.record_history(&"y".repeat(HISTORY_MAX_BYTES + 1));
+        assert_eq!(clipboard.history().count(), 2);
+        assert_eq!(clipboard.history().next(), Some("z"));
+    }
+
     #[test]
     fn test_clipboard_internal_only_mode() {
         let mut clipboard = Clipboard::new();
@@ -299,4 +504,17 @@ mod tests {
         clipboard.copy("internal only".to_string());
         assert_eq!(clipboard.get_internal(), "internal only");
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_primary_selection_separate_from_clipboard() {
+        let mut clipboard = Clipboard::new();
+        clipboard.set_internal_only(true);
+        assert_eq!(clipboard.paste_primary(), None);
+
+        clipboard.set_internal("copied".to_string());
+        clipboard.set_primary("selected".to_string());
+        assert_eq!(clipboard.paste_primary().as_deref(), Some("selected"));
+        assert_eq!(clipboard.get_internal(), "copied");
+    }
 }
@@ -0,0 +1,237 @@
+//! Go to Definition providers
+//!
+//! A [`DefinitionProvider`] resolves a symbol to the place it is defined.
+//! Go to Definition asks the registered providers when no language server
+//! handles the buffer, most recently registered first, until one finds a
+//! definition. The built-in [`TagsProvider`] is a ctags-like fallback that
+//! scans the project for a line declaring the symbol.
+
+use crate::input::quick_open::FileProvider;
+use crate::model::filesystem::FileSystem;
+use crate::services::project_search::is_binary;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+/// Where a symbol is defined
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefinitionLocation {
+    /// Absolute path of the file
+    pub path: PathBuf,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based byte column of the symbol, as Go to Line expects
+    pub column: usize,
+}
+
+/// A symbol to find the definition of
+#[derive(Debug, Clone)]
+pub struct DefinitionQuery {
+    /// The identifier under the cursor
+    pub symbol: String,
+    /// File the symbol is used in, if the buffer has one
+    pub path: Option<PathBuf>,
+    /// Project root
+    pub root: PathBuf,
+}
+
+/// Resolves symbols to their definitions
+pub trait DefinitionProvider: Send + Sync {
+    /// Name for logs
+    fn name(&self) -> &str;
+
+    /// Find where `query.symbol` is defined
+    ///
+    /// Runs on a worker thread, so it may block on I/O.
+    fn find_definition(&self, query: &DefinitionQuery) -> Option<DefinitionLocation>;
+}
+
+/// The providers Go to Definition asks, in order of precedence
+#[derive(Clone, Default)]
+pub struct DefinitionRegistry {
+    /// Providers in registration order; later ones take precedence
+    providers: Vec<Arc<dyn DefinitionProvider>>,
+}
+
+impl DefinitionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a provider, asked before the ones registered earlier
+    pub fn register(&mut self, provider: Arc<dyn DefinitionProvider>) {
+        self.providers.push(provider);
+    }
+
+    /// Ask each provider in turn for the definition of `query.symbol`
+    pub fn find_definition(&self, query: &DefinitionQuery) -> Option<DefinitionLocation> {
+        self.providers.iter().rev().find_map(|provider| {
+            let location = provider.find_definition(query)?;
+            tracing::debug!(
+                "{} found {} at {}:{}",
+                provider.name(),
+                query.symbol,
+                location.path.display(),
+                location.line
+            );
+            Some(location)
+        })
+    }
+}
+
+/// Keywords that introduce a definition in common languages
+const DEFINITION_KEYWORDS: &[&str] = &[
+    "fn",
+    "def",
+    "func",
+    "function",
+    "class",
+    "struct",
+    "enum",
+    "union",
+    "trait",
+    "interface",
+    "type",
+    "mod",
+    "module",
+    "macro_rules!",
+    "#define",
+];
+
+/// Keywords that declare a variable or constant
+///
+/// Inside a function these are mostly locals, so they only count as
+/// definitions at the top level of a file, with no indentation.
+const BINDING_KEYWORDS: &[&str] = &["const", "static", "let", "var", "val"];
+
+/// Finds definitions by scanning the project's files, like ctags
+///
+/// A definition is a keyword such as `fn`, `def` or `class` followed by the
+/// symbol. The file the symbol is used in is searched first, then the rest
+/// of the project in Quick Open's file order, so ignored files are skipped.
+pub struct TagsProvider {
+    filesystem: Arc<dyn FileSystem + Send + Sync>,
+    file_provider: Arc<FileProvider>,
+}
+
+impl TagsProvider {
+    pub fn new(
+        filesystem: Arc<dyn FileSystem + Send + Sync>,
+        file_provider: Arc<FileProvider>,
+    ) -> Self {
+        Self {
+            filesystem,
+            file_provider,
+        }
+    }
+}
+
+impl DefinitionProvider for TagsProvider {
+    fn name(&self) -> &str {
+        "tags"
+    }
+
+    fn find_definition(&self, query: &DefinitionQuery) -> Option<DefinitionLocation> {
+        let regex = definition_regex(&query.symbol)?;
+        let files = self
+            .file_provider
+            .project_files(&query.root.display().to_string(), &AtomicBool::new(false))?;
+        let current = query
+            .path
+            .as_deref()
+            .and_then(|path| path.strip_prefix(&query.root).ok());
+        let mut paths = current.map(Path::to_path_buf).into_iter().chain(
+            files
+                .iter()
+                .map(PathBuf::from)
+                .filter(|path| Some(path.as_path()) != current),
+        );
+
+        paths.find_map(|path| {
+            let path = query.root.join(path);
+            let contents = self.filesystem.read_file(&path).ok()?;
+            if is_binary(&contents) {
+                return None;
+            }
+            let (line, column) = find_definition_in(&String::from_utf8_lossy(&contents), &regex)?;
+            Some(DefinitionLocation { path, line, column })
+        })
+    }
+}
+
+/// A regex matching a line that defines `symbol`, capturing the symbol in
+/// group 1 or, for a top-level binding, group 2
+///
+/// Returns `None` if `symbol` isn't an identifier.
+fn definition_regex(symbol: &str) -> Option<Regex> {
+    if symbol.is_empty() || !symbol.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+    let alternatives = |keywords: &[&str]| {
+        keywords
+            .iter()
+            .map(|keyword| regex::escape(keyword))
+            .collect::<Vec<_>>()
+            .join("|")
+    };
+    let symbol = regex::escape(symbol);
+    Regex::new(&format!(
+        r"(?:^|[^\w#])(?:{})\s+({symbol})\b|^(?:(?:pub(?:\([^)]*\))?|export)\s+)*(?:{})\s+(?:mut\s+)?({symbol})\b",
+        alternatives(DEFINITION_KEYWORDS),
+        alternatives(BINDING_KEYWORDS),
+    ))
+    .ok()
+}
+
+/// The 1-based line and byte column of the first definition `regex` finds
+/// in `contents`
+fn find_definition_in(contents: &str, regex: &Regex) -> Option<(usize, usize)> {
+    contents.lines().enumerate().find_map(|(index, line)| {
+        let captures = regex.captures(line)?;
+        let symbol = captures.get(1).or_else(|| captures.get(2))?;
+        Some((index + 1, symbol.start() + 1))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(symbol: &str, contents: &str) -> Option<(usize, usize)> {
+        find_definition_in(contents, &definition_regex(symbol).unwrap())
+    }
+
+    #[test]
+    fn test_finds_definitions_across_languages() {
+        assert_eq!(find("parse", "use x;\n\npub fn parse() {}\n"), Some((3, 8)));
+        assert_eq!(find("Parser", "class Parser:\n"), Some((1, 7)));
+        assert_eq!(find("MAX", "#define MAX 10\n"), Some((1, 9)));
+        assert_eq!(
+            find("helper", "export async function helper() {}"),
+            Some((1, 23))
+        );
+    }
+
+    #[test]
+    fn test_ignores_uses_and_longer_names() {
+        assert_eq!(find("parse", "let x = parse();\nfn parse_all() {}\n"), None);
+        assert_eq!(
+            find("Parser", "x = Parser()\nstruct Parser;\n"),
+            Some((2, 8))
+        );
+        assert!(definition_regex("a.b").is_none());
+        assert!(definition_regex("").is_none());
+    }
+
+    #[test]
+    fn test_bindings_only_count_at_top_level() {
+        assert_eq!(find("count", "fn main() {\n    let count = 1;\n}\n"), None);
+        assert_eq!(
+            find("LIMIT", "fn f() {}\npub(crate) const LIMIT: usize = 3;\n"),
+            Some((2, 18))
+        );
+        assert_eq!(find("config", "export const config = {};\n"), Some((1, 14)));
+        assert_eq!(find("total", "let mut total = 0;\n"), Some((1, 9)));
+    }
+}
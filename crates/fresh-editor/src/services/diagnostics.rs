@@ -0,0 +1,65 @@
+//! Diagnostics providers
+//!
+//! A [`DiagnosticsProvider`] checks a file and reports problems in it, like a
+//! linter. The registered providers run when a file is opened and each time
+//! it is saved. Every provider's results are kept apart from the language
+//! server's and from other providers', so one source replacing its
+//! diagnostics leaves the rest in place.
+
+use lsp_types::Diagnostic;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Checks files for problems
+pub trait DiagnosticsProvider: Send + Sync {
+    /// Name the diagnostics are reported under
+    fn name(&self) -> &str;
+
+    /// The problems in the file at `path`, whose contents are `contents`
+    ///
+    /// Runs on a worker thread, so it may block, e.g. on a linter process.
+    fn check(&self, path: &Path, contents: &str) -> Vec<Diagnostic>;
+}
+
+/// The providers run on opened and saved files
+#[derive(Clone, Default)]
+pub struct DiagnosticsRegistry {
+    providers: Vec<Arc<dyn DiagnosticsProvider>>,
+}
+
+impl DiagnosticsRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a provider
+    pub fn register(&mut self, provider: Arc<dyn DiagnosticsProvider>) {
+        self.providers.push(provider);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.providers.is_empty()
+    }
+
+    /// Run every provider on a file, returning each one's diagnostics under
+    /// its name
+    ///
+    /// Diagnostics without a source are attributed to their provider.
+    pub fn check(&self, path: &Path, contents: &str) -> Vec<(String, Vec<Diagnostic>)> {
+        self.providers
+            .iter()
+            .map(|provider| {
+                let name = provider.name().to_string();
+                let diagnostics = provider
+                    .check(path, contents)
+                    .into_iter()
+                    .map(|mut diagnostic| {
+                        diagnostic.source.get_or_insert_with(|| name.clone());
+                        diagnostic
+                    })
+                    .collect();
+                (name, diagnostics)
+            })
+            .collect()
+    }
+}
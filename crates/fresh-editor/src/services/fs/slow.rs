@@ -6,11 +6,11 @@
 //! drives, slow disks, etc.).
 
 use crate::model::filesystem::{
-    DirEntry, FileMetadata, FilePermissions, FileReader, FileSystem, FileWriter,
+    DirEntry, FileMetadata, FilePermissions, FileReader, FileSystem, FileWatch, FileWriter,
 };
 use std::io;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -88,6 +88,8 @@ pub struct BackendMetrics {
     pub write_file_calls: AtomicUsize,
     /// Number of other calls
     pub other_calls: AtomicUsize,
+    /// Total bytes returned by read_file and read_range
+    pub bytes_read: AtomicUsize,
 }
 
 impl BackendMetrics {
@@ -103,6 +105,7 @@ impl BackendMetrics {
         self.read_file_calls.store(0, Ordering::SeqCst);
         self.write_file_calls.store(0, Ordering::SeqCst);
         self.other_calls.store(0, Ordering::SeqCst);
+        self.bytes_read.store(0, Ordering::SeqCst);
     }
 
     /// Get total number of filesystem calls
@@ -165,13 +168,21 @@ impl FileSystem for SlowFileSystem {
     fn read_file(&self, path: &Path) -> io::Result<Vec<u8>> {
         self.add_delay(self.config.read_file_delay);
         self.metrics.read_file_calls.fetch_add(1, Ordering::SeqCst);
-        self.inner.read_file(path)
+        let data = self.inner.read_file(path)?;
+        self.metrics
+            .bytes_read
+            .fetch_add(data.len(), Ordering::SeqCst);
+        Ok(data)
     }
 
     fn read_range(&self, path: &Path, offset: u64, len: usize) -> io::Result<Vec<u8>> {
         self.add_delay(self.config.read_file_delay);
         self.metrics.read_file_calls.fetch_add(1, Ordering::SeqCst);
-        self.inner.read_range(path, offset, len)
+        let data = self.inner.read_range(path, offset, len)?;
+        self.metrics
+            .bytes_read
+            .fetch_add(data.len(), Ordering::SeqCst);
+        Ok(data)
     }
 
     fn write_file(&self, path: &Path, data: &[u8]) -> io::Result<()> {
@@ -234,6 +245,12 @@ impl FileSystem for SlowFileSystem {
         self.inner.remove_dir(path)
     }
 
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()> {
+        self.add_delay(self.config.other_delay);
+        self.metrics.other_calls.fetch_add(1, Ordering::SeqCst);
+        self.inner.remove_dir_all(path)
+    }
+
     fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
         self.add_delay(self.config.metadata_delay);
         self.metrics.metadata_calls.fetch_add(1, Ordering::SeqCst);
@@ -246,6 +263,34 @@ impl FileSystem for SlowFileSystem {
         self.inner.symlink_metadata(path)
     }
 
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        self.add_delay(self.config.metadata_delay);
+        self.metrics.metadata_calls.fetch_add(1, Ordering::SeqCst);
+        self.inner.read_link(path)
+    }
+
+    fn available_space(&self, path: &Path) -> io::Result<u64> {
+        self.add_delay(self.config.metadata_delay);
+        self.metrics.metadata_calls.fetch_add(1, Ordering::SeqCst);
+        self.inner.available_space(path)
+    }
+
+    fn is_writable(&self, path: &Path) -> bool {
+        self.add_delay(self.config.metadata_delay);
+        self.metrics.metadata_calls.fetch_add(1, Ordering::SeqCst);
+        self.inner.is_writable(path)
+    }
+
+    fn watch_changes(
+        &self,
+        path: &Path,
+        on_change: Box<dyn Fn() + Send + Sync>,
+    ) -> io::Result<FileWatch> {
+        self.add_delay(self.config.metadata_delay);
+        self.metrics.metadata_calls.fetch_add(1, Ordering::SeqCst);
+        self.inner.watch_changes(path, on_change)
+    }
+
     fn is_dir(&self, path: &Path) -> io::Result<bool> {
         self.add_delay(self.config.other_delay);
         self.metrics.other_calls.fetch_add(1, Ordering::SeqCst);
@@ -270,6 +315,29 @@ impl FileSystem for SlowFileSystem {
         self.inner.read_dir(path)
     }
 
+    fn find_files(
+        &self,
+        root: &Path,
+        pattern: &str,
+        max_results: usize,
+        cancelled: &AtomicBool,
+    ) -> io::Result<Vec<PathBuf>> {
+        self.add_delay(self.config.read_dir_delay);
+        self.metrics.read_dir_calls.fetch_add(1, Ordering::SeqCst);
+        self.inner.find_files(root, pattern, max_results, cancelled)
+    }
+
+    fn git_files(
+        &self,
+        root: &Path,
+        max_results: usize,
+        cancelled: &AtomicBool,
+    ) -> io::Result<Vec<PathBuf>> {
+        self.add_delay(self.config.read_dir_delay);
+        self.metrics.read_dir_calls.fetch_add(1, Ordering::SeqCst);
+        self.inner.git_files(root, max_results, cancelled)
+    }
+
     fn create_dir(&self, path: &Path) -> io::Result<()> {
         self.add_delay(self.config.other_delay);
         self.metrics.other_calls.fetch_add(1, Ordering::SeqCst);
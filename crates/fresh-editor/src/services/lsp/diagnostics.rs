@@ -6,6 +6,7 @@ use crate::model::buffer::Buffer;
 use crate::state::EditorState;
 use crate::view::overlay::{Overlay, OverlayFace, OverlayNamespace};
 use lsp_types::{Diagnostic, DiagnosticSeverity};
+use ratatui::style::{Color, Modifier, Style};
 use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
@@ -111,6 +112,25 @@ pub fn apply_diagnostics_to_state_cached(
     }
 }
 
+/// Overlay priority of each severity; more severe diagnostics draw on top
+const ERROR_PRIORITY: i32 = 100;
+const WARNING_PRIORITY: i32 = 50;
+const INFO_PRIORITY: i32 = 30;
+const HINT_PRIORITY: i32 = 10;
+
+/// Color of the gutter marker for a diagnostic overlay of `priority`
+pub fn diagnostic_gutter_color(priority: i32, theme: &crate::view::theme::Theme) -> Color {
+    if priority >= ERROR_PRIORITY {
+        theme.diagnostic_error_fg
+    } else if priority >= WARNING_PRIORITY {
+        theme.diagnostic_warning_fg
+    } else if priority >= INFO_PRIORITY {
+        theme.diagnostic_info_fg
+    } else {
+        theme.diagnostic_hint_fg
+    }
+}
+
 /// Convert an LSP diagnostic to an overlay (range, face, priority)
 /// Returns None if the diagnostic cannot be converted (invalid range, etc.)
 pub fn diagnostic_to_overlay(
@@ -130,34 +150,20 @@ pub fn diagnostic_to_overlay(
     let start_byte = buffer.lsp_position_to_byte(start_line, start_char);
     let end_byte = buffer.lsp_position_to_byte(end_line, end_char);
 
-    // Determine overlay face based on diagnostic severity using theme colors
-    let (face, priority) = match diagnostic.severity {
-        Some(DiagnosticSeverity::ERROR) => (
-            OverlayFace::Background {
-                color: theme.diagnostic_error_bg,
-            },
-            100, // Highest priority
-        ),
-        Some(DiagnosticSeverity::WARNING) => (
-            OverlayFace::Background {
-                color: theme.diagnostic_warning_bg,
-            },
-            50, // Medium priority
-        ),
-        Some(DiagnosticSeverity::INFORMATION) => (
-            OverlayFace::Background {
-                color: theme.diagnostic_info_bg,
-            },
-            30, // Lower priority
-        ),
-        Some(DiagnosticSeverity::HINT) | None => (
-            OverlayFace::Background {
-                color: theme.diagnostic_hint_bg,
-            },
-            10, // Lowest priority
-        ),
+    // Determine highlight color based on diagnostic severity using theme colors
+    let (color, priority) = match diagnostic.severity {
+        Some(DiagnosticSeverity::ERROR) => (theme.diagnostic_error_bg, ERROR_PRIORITY),
+        Some(DiagnosticSeverity::WARNING) => (theme.diagnostic_warning_bg, WARNING_PRIORITY),
+        Some(DiagnosticSeverity::INFORMATION) => (theme.diagnostic_info_bg, INFO_PRIORITY),
+        Some(DiagnosticSeverity::HINT) | None => (theme.diagnostic_hint_bg, HINT_PRIORITY),
         _ => return None, // Unknown severity
     };
+    // Underline the range on top of the highlight, keeping the syntax colors
+    let face = OverlayFace::Style {
+        style: Style::default()
+            .bg(color)
+            .add_modifier(Modifier::UNDERLINED),
+    };
 
     Some((start_byte..end_byte, face, priority))
 }
@@ -263,10 +269,12 @@ mod tests {
         assert_eq!(priority, 100); // Error has highest priority
 
         match face {
-            OverlayFace::Background { color } => {
-                assert_eq!(color, theme.diagnostic_error_bg);
+            OverlayFace::Style { style } => {
+                assert_eq!(style.bg, Some(theme.diagnostic_error_bg));
+                assert!(style.add_modifier.contains(Modifier::UNDERLINED));
+                assert_eq!(style.fg, None);
             }
-            _ => panic!("Expected Background face"),
+            _ => panic!("Expected Style face"),
         }
     }
 
@@ -304,10 +312,12 @@ mod tests {
         assert_eq!(priority, 50); // Warning has medium priority
 
         match face {
-            OverlayFace::Background { color } => {
-                assert_eq!(color, theme.diagnostic_warning_bg);
+            OverlayFace::Style { style } => {
+                assert_eq!(style.bg, Some(theme.diagnostic_warning_bg));
+                assert!(style.add_modifier.contains(Modifier::UNDERLINED));
+                assert_eq!(style.fg, None);
             }
-            _ => panic!("Expected Background face"),
+            _ => panic!("Expected Style face"),
         }
     }
 
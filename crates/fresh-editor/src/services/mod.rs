@@ -5,6 +5,8 @@
 
 pub mod async_bridge;
 pub mod clipboard;
+pub mod definition;
+pub mod diagnostics;
 pub mod fs;
 #[cfg(target_os = "linux")]
 pub mod gpm;
@@ -12,6 +14,7 @@ pub mod log_dirs;
 pub mod lsp;
 pub mod plugins;
 pub mod process_limits;
+pub mod project_search;
 pub mod recovery;
 pub mod release_checker;
 pub mod remote;
@@ -0,0 +1,201 @@
+//! Project-wide text search
+//!
+//! Searches a list of project files line by line with a regex, reading each
+//! file through a [`FileSystem`] so remote projects are searched over the
+//! agent connection. Binary files and very large files are skipped, and the
+//! search stops after a fixed number of matches so it stays responsive.
+
+use crate::model::filesystem::FileSystem;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Most matches reported for one search
+pub const MAX_RESULTS: usize = 1000;
+
+/// Files larger than this are not searched
+const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// How much of a file is checked for NUL bytes to detect binary content
+const BINARY_CHECK_LEN: usize = 8000;
+
+/// Longest line text kept for display, in characters
+const MAX_TEXT_LEN: usize = 200;
+
+/// Files whose metadata is looked up in one batch
+const STAT_BATCH: usize = 256;
+
+/// A line matching a project search
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectSearchMatch {
+    /// Path relative to the project root
+    pub path: String,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based byte column where the match starts, as Go to Line expects
+    pub column: usize,
+    /// The matching line, trimmed and shortened for display
+    pub text: String,
+}
+
+/// Whether file contents look binary: a NUL byte near the start, as git checks
+pub fn is_binary(contents: &[u8]) -> bool {
+    contents[..contents.len().min(BINARY_CHECK_LEN)].contains(&0)
+}
+
+/// Find the first match of `regex` on each line of `contents`
+pub fn search_contents(path: &str, contents: &str, regex: &Regex) -> Vec<ProjectSearchMatch> {
+    contents
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let found = regex.find(line)?;
+            Some(ProjectSearchMatch {
+                path: path.to_string(),
+                line: index + 1,
+                column: found.start() + 1,
+                text: line.trim().chars().take(MAX_TEXT_LEN).collect(),
+            })
+        })
+        .collect()
+}
+
+/// Search `files` (relative to `root`) in order
+///
+/// Each file's matches are passed to `on_matches` as soon as the file is
+/// searched. Stops once `max_results` matches were reported or `cancelled`
+/// is set. Returns the number of matches reported.
+pub fn search_files(
+    filesystem: &dyn FileSystem,
+    root: &Path,
+    files: &[String],
+    regex: &Regex,
+    max_results: usize,
+    cancelled: &AtomicBool,
+    mut on_matches: impl FnMut(Vec<ProjectSearchMatch>),
+) -> usize {
+    let mut found = 0;
+    for batch in files.chunks(STAT_BATCH) {
+        let paths: Vec<PathBuf> = batch.iter().map(|file| root.join(file)).collect();
+        let metadata = filesystem.stat_many(&paths);
+
+        for ((file, path), metadata) in batch.iter().zip(&paths).zip(metadata) {
+            if cancelled.load(Ordering::Relaxed) {
+                return found;
+            }
+            if !metadata.is_ok_and(|metadata| metadata.size <= MAX_FILE_SIZE) {
+                continue;
+            }
+            let Ok(contents) = filesystem.read_file(path) else {
+                continue;
+            };
+            if is_binary(&contents) {
+                continue;
+            }
+
+            let mut matches = search_contents(file, &String::from_utf8_lossy(&contents), regex);
+            matches.truncate(max_results - found);
+            if matches.is_empty() {
+                continue;
+            }
+            found += matches.len();
+            on_matches(matches);
+            if found >= max_results {
+                return found;
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::filesystem::StdFileSystem;
+
+    #[test]
+    fn test_search_contents_reports_line_and_column() {
+        let regex = Regex::new("needle").unwrap();
+        let matches = search_contents("a.txt", "hay\n  héy needle needle\nneedle\n", &regex);
+
+        assert_eq!(
+            matches,
+            vec![
+                ProjectSearchMatch {
+                    path: "a.txt".to_string(),
+                    line: 2,
+                    // Columns count bytes
+                    column: 8,
+                    text: "héy needle needle".to_string(),
+                },
+                ProjectSearchMatch {
+                    path: "a.txt".to_string(),
+                    line: 3,
+                    column: 1,
+                    text: "needle".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_binary_detection() {
+        assert!(is_binary(b"PNG\0\x01\x02"));
+        assert!(!is_binary("plain text, ünïcode".as_bytes()));
+        assert!(!is_binary(b""));
+    }
+
+    #[test]
+    fn test_search_files_skips_binary_and_caps_results() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "x\nx\nx\n").unwrap();
+        std::fs::write(dir.path().join("b.bin"), b"x\0x\n").unwrap();
+        std::fs::write(dir.path().join("c.txt"), "x\n").unwrap();
+        let files: Vec<String> = ["a.txt", "b.bin", "c.txt", "missing.txt"]
+            .iter()
+            .map(|file| file.to_string())
+            .collect();
+        let regex = Regex::new("x").unwrap();
+        let cancelled = AtomicBool::new(false);
+
+        let mut matches = Vec::new();
+        let found = search_files(
+            &StdFileSystem,
+            dir.path(),
+            &files,
+            &regex,
+            MAX_RESULTS,
+            &cancelled,
+            |batch| matches.extend(batch),
+        );
+        assert_eq!(found, 4);
+        assert!(matches.iter().all(|m| m.path != "b.bin"));
+
+        let mut matches = Vec::new();
+        let found = search_files(
+            &StdFileSystem,
+            dir.path(),
+            &files,
+            &regex,
+            2,
+            &cancelled,
+            |batch| matches.extend(batch),
+        );
+        assert_eq!(found, 2);
+        assert_eq!(matches.len(), 2);
+
+        cancelled.store(true, Ordering::Relaxed);
+        assert_eq!(
+            search_files(
+                &StdFileSystem,
+                dir.path(),
+                &files,
+                &regex,
+                MAX_RESULTS,
+                &cancelled,
+                |_| {}
+            ),
+            0
+        );
+    }
+}
@@ -0,0 +1,112 @@
+//! At-rest encryption for recovery files
+//!
+//! Chunk files of an encrypted entry are sealed with XChaCha20-Poly1305 under
+//! a key derived from the user's passphrase with Argon2id. The salt is shared
+//! by the whole recovery directory so the key is derived once per session.
+//! Every save draws a fresh nonce, stored in the entry's metadata, and chunk
+//! `i` is sealed with that nonce mixed with `i`.
+
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use std::io;
+use zeroize::Zeroizing;
+
+/// Length of the directory-wide Argon2 salt
+pub const SALT_LEN: usize = 16;
+
+/// Length of the per-entry XChaCha20 nonce
+pub const NONCE_LEN: usize = 24;
+
+/// Chunk index used to seal an entry's key check (never a real chunk index)
+pub(crate) const KEY_CHECK_INDEX: u64 = u64::MAX;
+
+/// Symmetric key for recovery files, wiped from memory on drop
+#[derive(Clone)]
+pub struct RecoveryKey(Zeroizing<[u8; 32]>);
+
+impl RecoveryKey {
+    /// Derive a key from a passphrase and the recovery directory's salt
+    pub fn derive(passphrase: &str, salt: &[u8]) -> io::Result<Self> {
+        let mut key = Zeroizing::new([0u8; 32]);
+        argon2::Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), salt, key.as_mut())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+        Ok(Self(key))
+    }
+
+    /// Encrypt the chunk at `index` of an entry saved with `nonce`
+    pub(crate) fn seal(
+        &self,
+        nonce: &[u8; NONCE_LEN],
+        index: u64,
+        data: &[u8],
+    ) -> io::Result<Vec<u8>> {
+        self.cipher()
+            .encrypt(&chunk_nonce(nonce, index), data)
+            .map_err(|_| io::Error::other("failed to encrypt recovery data"))
+    }
+
+    /// Decrypt the chunk at `index`, failing with [`WrongRecoveryKey`] if the
+    /// key doesn't match or the data was tampered with
+    pub(crate) fn open(
+        &self,
+        nonce: &[u8; NONCE_LEN],
+        index: u64,
+        data: &[u8],
+    ) -> io::Result<Vec<u8>> {
+        self.cipher()
+            .decrypt(&chunk_nonce(nonce, index), data)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, WrongRecoveryKey))
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(self.0.as_ref().into())
+    }
+}
+
+impl std::fmt::Debug for RecoveryKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("RecoveryKey([REDACTED])")
+    }
+}
+
+/// Fill an array from the OS random number generator
+pub(crate) fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
+
+/// Nonce for one chunk: the entry nonce with the chunk index XORed into its tail
+fn chunk_nonce(nonce: &[u8; NONCE_LEN], index: u64) -> XNonce {
+    let mut chunk = *nonce;
+    for (byte, mix) in chunk[NONCE_LEN - 8..].iter_mut().zip(index.to_le_bytes()) {
+        *byte ^= mix;
+    }
+    XNonce::from(chunk)
+}
+
+/// An encrypted recovery entry was read without a passphrase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecoveryKeyRequired;
+
+impl std::fmt::Display for RecoveryKeyRequired {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("recovery entry is encrypted; a passphrase is required to read it")
+    }
+}
+
+impl std::error::Error for RecoveryKeyRequired {}
+
+/// An encrypted recovery entry couldn't be decrypted with the given passphrase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WrongRecoveryKey;
+
+impl std::fmt::Display for WrongRecoveryKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("recovery entry could not be decrypted: wrong passphrase or corrupted file")
+    }
+}
+
+impl std::error::Error for WrongRecoveryKey {}
@@ -16,6 +16,7 @@
 //! ```text
 //! ~/.local/share/fresh/recovery/
 //! ├── session.lock           # Session info (PID, start time)
+//! ├── encryption.salt        # Passphrase salt (only when encryption is used)
 //! ├── {hash}.meta.json       # Recovery metadata with chunk index
 //! ├── {hash}.chunk.0         # Chunk 0 binary content
 //! ├── {hash}.chunk.1         # Chunk 1 binary content
@@ -49,18 +50,24 @@
 //! recovery.end_session()?;
 //! ```
 
+mod encryption;
 mod storage;
 pub mod types;
+mod writer;
 
+pub use encryption::{RecoveryKey, RecoveryKeyRequired, WrongRecoveryKey};
 pub use storage::RecoveryStorage;
 pub use types::{
     generate_buffer_id, path_hash, ChunkMeta, ChunkedRecoveryData, ChunkedRecoveryIndex,
-    InplaceWriteRecovery, RecoveryChunk, RecoveryEntry, RecoveryMetadata, RecoveryResult,
-    SessionInfo, MAX_CHUNK_SIZE,
+    InplaceWriteRecovery, RecoveryChunk, RecoveryCompression, RecoveryEncryption, RecoveryEntry,
+    RecoveryFormat, RecoveryMetadata, RecoveryResult, RetentionSummary, SalvagedRecovery,
+    SessionInfo, COMPRESSION_THRESHOLD, MAX_CHUNK_SIZE,
 };
+pub use writer::{RecoveryWriter, SaveJob};
 
 use std::collections::HashMap;
 use std::io;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 
@@ -71,6 +78,11 @@ pub struct RecoveryConfig {
     pub enabled: bool,
     /// Maximum age of recovery files before cleanup (in seconds)
     pub max_recovery_age_secs: u64,
+    /// Original file size from which chunked recovery is considered
+    pub chunked_min_file_size: usize,
+    /// Largest fraction of the original file that edits may touch for
+    /// chunked recovery to be used
+    pub chunked_max_edit_ratio: f64,
 }
 
 impl Default for RecoveryConfig {
@@ -78,6 +90,33 @@ impl Default for RecoveryConfig {
         Self {
             enabled: true,
             max_recovery_age_secs: 7 * 24 * 60 * 60, // 7 days
+            chunked_min_file_size: 64 * 1024,
+            chunked_max_edit_ratio: 0.5,
+        }
+    }
+}
+
+impl RecoveryConfig {
+    /// Pick chunked or full recovery for a file of `original_size` bytes
+    ///
+    /// `edit_ranges` are the modified regions (`offset..offset + len`); only
+    /// their total length matters. Chunked recovery is used when the file is
+    /// at least `chunked_min_file_size` and the edits touch no more than
+    /// `chunked_max_edit_ratio` of it, otherwise the whole buffer is saved.
+    pub fn choose_recovery_strategy(
+        &self,
+        original_size: usize,
+        edit_ranges: &[Range<usize>],
+    ) -> RecoveryFormat {
+        if original_size == 0 || original_size < self.chunked_min_file_size {
+            return RecoveryFormat::Full;
+        }
+
+        let edited: usize = edit_ranges.iter().map(|r| r.len()).sum();
+        if edited as f64 <= original_size as f64 * self.chunked_max_edit_ratio {
+            RecoveryFormat::Chunked
+        } else {
+            RecoveryFormat::Full
         }
     }
 }
@@ -96,6 +135,8 @@ pub struct RecoveryService {
     last_save_times: HashMap<String, Instant>,
     /// Session started flag
     session_started: bool,
+    /// Worker performing saves off the calling thread, when enabled
+    writer: Option<RecoveryWriter>,
 }
 
 impl RecoveryService {
@@ -106,16 +147,18 @@ impl RecoveryService {
             config: RecoveryConfig::default(),
             last_save_times: HashMap::new(),
             session_started: false,
+            writer: None,
         })
     }
 
     /// Create a new recovery service with custom config
     pub fn with_config(config: RecoveryConfig) -> io::Result<Self> {
         Ok(Self {
-            storage: RecoveryStorage::new()?,
+            storage: Self::storage_for(&config, RecoveryStorage::new()?),
             config,
             last_save_times: HashMap::new(),
             session_started: false,
+            writer: None,
         })
     }
 
@@ -127,19 +170,26 @@ impl RecoveryService {
             config: RecoveryConfig::default(),
             last_save_times: HashMap::new(),
             session_started: false,
+            writer: None,
         }
     }
 
     /// Create a new recovery service with custom config and storage directory
     pub fn with_config_and_dir(config: RecoveryConfig, storage_dir: PathBuf) -> Self {
         Self {
-            storage: RecoveryStorage::with_dir(storage_dir),
+            storage: Self::storage_for(&config, RecoveryStorage::with_dir(storage_dir)),
             config,
             last_save_times: HashMap::new(),
             session_started: false,
+            writer: None,
         }
     }
 
+    fn storage_for(config: &RecoveryConfig, mut storage: RecoveryStorage) -> RecoveryStorage {
+        storage.set_writes_enabled(config.enabled);
+        storage
+    }
+
     /// Check if recovery is enabled
     pub fn is_enabled(&self) -> bool {
         self.config.enabled
@@ -150,6 +200,69 @@ impl RecoveryService {
         &self.storage
     }
 
+    /// Encrypt recovery files with a key derived from `passphrase`
+    pub fn enable_encryption(&mut self, passphrase: &str) -> io::Result<()> {
+        self.storage.enable_encryption(passphrase)?;
+        self.restart_writer()
+    }
+
+    /// Turn recovery on or off as a whole
+    ///
+    /// While off, nothing is written to the recovery directory.
+    pub fn set_enabled(&mut self, enabled: bool) -> io::Result<()> {
+        self.settle_writes();
+        self.config.enabled = enabled;
+        self.storage.set_writes_enabled(enabled);
+        self.restart_writer()
+    }
+
+    /// Exclude a buffer from recovery, or include it again
+    ///
+    /// Exempting a buffer deletes any recovery files it already has, and no
+    /// new ones are written for it until the exemption is lifted.
+    pub fn set_buffer_recovery_exempt(&mut self, buffer_id: &str, exempt: bool) -> io::Result<()> {
+        // A queued save must not land after the exempt buffer's files are gone
+        self.settle_writes();
+        self.storage.set_recovery_exempt(buffer_id, exempt)?;
+        self.last_save_times.remove(buffer_id);
+        self.restart_writer()
+    }
+
+    /// Whether a buffer is excluded from recovery
+    pub fn is_buffer_recovery_exempt(&self, buffer_id: &str) -> bool {
+        self.storage.is_recovery_exempt(buffer_id)
+    }
+
+    /// Give the writer a fresh copy of the storage after its settings change
+    fn restart_writer(&mut self) -> io::Result<()> {
+        // The writer saves through its own copy of the storage
+        if self.writer.is_some() {
+            self.flush()?;
+            self.writer = Some(RecoveryWriter::spawn(self.storage.clone())?);
+        }
+        Ok(())
+    }
+
+    /// Perform `save_buffer` writes on a background thread
+    ///
+    /// Saves queued for the same buffer are coalesced into the latest one.
+    /// Operations that delete recovery files wait for queued saves first, so
+    /// a late write can't bring a deleted entry back.
+    pub fn enable_background_writes(&mut self) -> io::Result<()> {
+        if self.writer.is_none() {
+            self.writer = Some(RecoveryWriter::spawn(self.storage.clone())?);
+        }
+        Ok(())
+    }
+
+    /// Block until all queued background saves are on disk
+    pub fn flush(&self) -> io::Result<()> {
+        match &self.writer {
+            Some(writer) => writer.flush(),
+            None => Ok(()),
+        }
+    }
+
     // ========================================================================
     // Session management
     // ========================================================================
@@ -184,10 +297,15 @@ impl RecoveryService {
 
     /// End the session cleanly (call on normal editor shutdown)
     pub fn end_session(&mut self) -> io::Result<()> {
-        if !self.config.enabled || !self.session_started {
+        // Recovery may have been turned off since the session started; the
+        // lock and files from before then still need cleaning up
+        if !self.session_started {
             return Ok(());
         }
 
+        // Queued saves would otherwise land after the cleanup
+        self.settle_writes();
+
         // Clean up all recovery files (user chose to close normally)
         let cleaned = self.storage.cleanup_all()?;
         tracing::info!("Cleaned up {} recovery files", cleaned);
@@ -215,8 +333,8 @@ impl RecoveryService {
     ///
     /// Returns true if recovery_pending is true. The recovery_pending flag is now
     /// tracked on the buffer itself (TextBuffer.recovery_pending) rather than in this service.
-    pub fn needs_auto_recovery_save(&self, _buffer_id: &str, recovery_pending: bool) -> bool {
-        if !self.config.enabled {
+    pub fn needs_auto_recovery_save(&self, buffer_id: &str, recovery_pending: bool) -> bool {
+        if !self.config.enabled || self.is_buffer_recovery_exempt(buffer_id) {
             return false;
         }
 
@@ -224,6 +342,17 @@ impl RecoveryService {
         recovery_pending
     }
 
+    /// Pick chunked or full recovery for a buffer (see
+    /// [`RecoveryConfig::choose_recovery_strategy`])
+    pub fn choose_recovery_strategy(
+        &self,
+        original_size: usize,
+        edit_ranges: &[Range<usize>],
+    ) -> RecoveryFormat {
+        self.config
+            .choose_recovery_strategy(original_size, edit_ranges)
+    }
+
     /// Get buffer ID for a path
     pub fn get_buffer_id(&self, path: Option<&Path>) -> String {
         self.storage.get_buffer_id(path)
@@ -260,19 +389,32 @@ impl RecoveryService {
         original_file_size: usize,
         final_size: usize,
     ) -> io::Result<()> {
-        if !self.config.enabled {
+        if !self.config.enabled || self.is_buffer_recovery_exempt(buffer_id) {
             return Ok(());
         }
 
-        self.storage.save_recovery(
-            buffer_id,
-            chunks,
-            original_path,
-            buffer_name,
-            line_count,
-            original_file_size,
-            final_size,
-        )?;
+        match &self.writer {
+            Some(writer) => writer.enqueue(SaveJob {
+                id: buffer_id.to_string(),
+                chunks,
+                original_path: original_path.map(Path::to_path_buf),
+                buffer_name: buffer_name.map(str::to_string),
+                line_count,
+                original_file_size,
+                final_size,
+            })?,
+            None => {
+                self.storage.save_recovery(
+                    buffer_id,
+                    chunks,
+                    original_path,
+                    buffer_name,
+                    line_count,
+                    original_file_size,
+                    final_size,
+                )?;
+            }
+        }
         self.last_save_times
             .insert(buffer_id.to_string(), Instant::now());
 
@@ -291,6 +433,7 @@ impl RecoveryService {
             return Ok(());
         }
 
+        self.settle_writes();
         self.storage.delete_recovery(buffer_id)?;
         self.last_save_times.remove(buffer_id);
 
@@ -401,14 +544,24 @@ impl RecoveryService {
 
     /// Discard recovery for an entry
     pub fn discard_recovery(&mut self, entry: &RecoveryEntry) -> io::Result<()> {
+        self.settle_writes();
         self.storage.delete_recovery(&entry.id)
     }
 
     /// Discard all recovery files
     pub fn discard_all_recovery(&mut self) -> io::Result<usize> {
+        self.settle_writes();
         self.storage.cleanup_all()
     }
 
+    /// Wait for queued saves before deleting recovery files
+    fn settle_writes(&self) {
+        // The files are about to be deleted, so a failed save only gets logged
+        if let Err(e) = self.flush() {
+            tracing::warn!("Pending recovery saves failed: {}", e);
+        }
+    }
+
     // ========================================================================
     // Maintenance
     // ========================================================================
@@ -419,6 +572,7 @@ impl RecoveryService {
             return Ok(0);
         }
 
+        self.settle_writes();
         let entries = self.storage.list_entries()?;
         let mut cleaned = 0;
 
@@ -450,6 +604,7 @@ impl Default for RecoveryService {
             config: RecoveryConfig::default(),
             last_save_times: HashMap::new(),
             session_started: false,
+            writer: None,
         })
     }
 }
@@ -467,10 +622,149 @@ mod tests {
             config: RecoveryConfig::default(),
             last_save_times: HashMap::new(),
             session_started: false,
+            writer: None,
         };
         (service, temp_dir)
     }
 
+    #[test]
+    fn test_strategy_large_file_small_edit_is_chunked() {
+        let config = RecoveryConfig::default();
+        let original_size = 100 * 1024 * 1024;
+        let edit = 5_000_000..5_000_050;
+
+        assert_eq!(
+            config.choose_recovery_strategy(original_size, &[edit]),
+            RecoveryFormat::Chunked
+        );
+    }
+
+    #[test]
+    fn test_strategy_small_file_is_full() {
+        let config = RecoveryConfig::default();
+        let edit = 10..12;
+
+        assert_eq!(
+            config.choose_recovery_strategy(4096, &[edit]),
+            RecoveryFormat::Full
+        );
+        // New buffers have no original to apply chunks to
+        assert_eq!(
+            config.choose_recovery_strategy(0, &[]),
+            RecoveryFormat::Full
+        );
+    }
+
+    #[test]
+    fn test_strategy_rewrite_everything_is_full() {
+        let config = RecoveryConfig::default();
+        let original_size = 100 * 1024 * 1024;
+        let everything = 0..original_size;
+
+        assert_eq!(
+            config.choose_recovery_strategy(original_size, &[everything]),
+            RecoveryFormat::Full
+        );
+        // Many scattered edits add up
+        let edits: Vec<_> = (0..100)
+            .map(|i| i * 1024 * 1024..i * 1024 * 1024 + 600 * 1024)
+            .collect();
+        assert_eq!(
+            config.choose_recovery_strategy(original_size, &edits),
+            RecoveryFormat::Full
+        );
+    }
+
+    #[test]
+    fn test_strategy_thresholds_are_configurable() {
+        let config = RecoveryConfig {
+            chunked_min_file_size: 1000,
+            chunked_max_edit_ratio: 0.1,
+            ..RecoveryConfig::default()
+        };
+
+        let edit = |len: usize| vec![Range { start: 0, end: len }];
+
+        assert_eq!(
+            config.choose_recovery_strategy(999, &edit(1)),
+            RecoveryFormat::Full
+        );
+        assert_eq!(
+            config.choose_recovery_strategy(1000, &edit(100)),
+            RecoveryFormat::Chunked
+        );
+        assert_eq!(
+            config.choose_recovery_strategy(1000, &edit(101)),
+            RecoveryFormat::Full
+        );
+    }
+
+    #[test]
+    fn test_background_saves_do_not_outlive_delete() {
+        let (mut service, _temp) = create_test_service();
+        service.enable_background_writes().unwrap();
+
+        for i in 0..50 {
+            let content = format!("edit {i}").into_bytes();
+            let len = content.len();
+            let chunks = vec![RecoveryChunk::new(0, 0, content)];
+            service
+                .save_buffer("buf", chunks, None, None, None, 0, len)
+                .unwrap();
+        }
+        service.flush().unwrap();
+        let data = service.storage().read_chunked_content("buf").unwrap();
+        assert_eq!(data.unwrap().chunks[0].content, b"edit 49");
+
+        let chunks = vec![RecoveryChunk::new(0, 0, b"late".to_vec())];
+        service
+            .save_buffer("buf", chunks, None, None, None, 0, 4)
+            .unwrap();
+        service.delete_buffer_recovery("buf").unwrap();
+        service.flush().unwrap();
+        assert!(service.list_recoverable().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_exempting_buffer_drops_queued_and_saved_recovery() {
+        let (mut service, _temp) = create_test_service();
+        service.enable_background_writes().unwrap();
+
+        let chunks = vec![RecoveryChunk::new(0, 0, b"scratch".to_vec())];
+        service
+            .save_buffer("scratch", chunks, None, None, None, 0, 7)
+            .unwrap();
+        service.set_buffer_recovery_exempt("scratch", true).unwrap();
+        assert!(!service.needs_auto_recovery_save("scratch", true));
+
+        let chunks = vec![RecoveryChunk::new(0, 0, b"more".to_vec())];
+        service
+            .save_buffer("scratch", chunks, None, None, None, 0, 4)
+            .unwrap();
+        service.flush().unwrap();
+        assert!(service.list_recoverable().unwrap().is_empty());
+
+        service
+            .set_buffer_recovery_exempt("scratch", false)
+            .unwrap();
+        assert!(service.needs_auto_recovery_save("scratch", true));
+    }
+
+    #[test]
+    fn test_disabled_service_writes_nothing() {
+        let (mut service, temp) = create_test_service();
+        service.set_enabled(false).unwrap();
+
+        service.start_session().unwrap();
+        let chunks = vec![RecoveryChunk::new(0, 0, b"data".to_vec())];
+        service
+            .save_buffer("buf", chunks, None, None, None, 0, 4)
+            .unwrap();
+
+        assert!(!temp.path().join("session.lock").exists());
+        assert!(service.list_recoverable().unwrap().is_empty());
+    }
+
     #[test]
     fn test_session_lifecycle() {
         let (mut service, _temp) = create_test_service();
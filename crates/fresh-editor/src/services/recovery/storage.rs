@@ -3,23 +3,38 @@
 //! This module handles reading and writing recovery files with atomic operations
 //! to ensure data integrity even during crashes.
 
+use super::encryption::{
+    random_bytes, RecoveryKey, RecoveryKeyRequired, KEY_CHECK_INDEX, NONCE_LEN, SALT_LEN,
+};
 use super::types::{
     generate_buffer_id, path_hash, ChunkedRecoveryData, ChunkedRecoveryIndex, RecoveryChunk,
-    RecoveryEntry, RecoveryMetadata, SessionInfo,
+    RecoveryCompression, RecoveryEncryption, RecoveryEntry, RecoveryMetadata, RetentionSummary,
+    SalvagedRecovery, SessionInfo,
 };
 use crate::input::input_history::get_data_dir;
+use crate::model::composite_buffer::DiffHunk;
+use crate::model::line_diff::diff_hunks;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use std::borrow::Cow;
+use std::collections::HashSet;
 use std::fs::{self, File};
-use std::io::{self, Write};
+use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 /// Recovery storage manager
 ///
 /// Handles all file I/O for the recovery system with atomic operations.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RecoveryStorage {
     /// Base directory for recovery files
     recovery_dir: PathBuf,
+    /// Key for encrypting new entries and reading encrypted ones
+    key: Option<RecoveryKey>,
+    /// Global switch; when off, nothing is written to the recovery directory
+    writes_enabled: bool,
+    /// Buffer IDs that must never be written to recovery
+    exempt: HashSet<String>,
 }
 
 impl RecoveryStorage {
@@ -29,16 +44,23 @@ impl RecoveryStorage {
     const CONTENT_EXT: &'static str = "content";
     /// Session lock file name
     const SESSION_LOCK: &'static str = "session.lock";
+    /// Salt shared by all encrypted entries
+    const ENCRYPTION_SALT: &'static str = "encryption.salt";
 
     /// Create a new recovery storage manager
     pub fn new() -> io::Result<Self> {
         let recovery_dir = Self::get_recovery_dir()?;
-        Ok(Self { recovery_dir })
+        Ok(Self::with_dir(recovery_dir))
     }
 
     /// Create a recovery storage with a custom directory (for testing)
     pub fn with_dir(recovery_dir: PathBuf) -> Self {
-        Self { recovery_dir }
+        Self {
+            recovery_dir,
+            key: None,
+            writes_enabled: true,
+            exempt: HashSet::new(),
+        }
     }
 
     /// Get the recovery directory path
@@ -57,6 +79,110 @@ impl RecoveryStorage {
         &self.recovery_dir
     }
 
+    // ========================================================================
+    // Encryption
+    // ========================================================================
+
+    /// Encrypt new entries with a key derived from `passphrase`
+    ///
+    /// The same passphrase is needed to read encrypted entries back, including
+    /// after a crash. Entries already on disk are not re-encrypted.
+    pub fn enable_encryption(&mut self, passphrase: &str) -> io::Result<()> {
+        let salt = self.read_or_create_salt()?;
+        self.key = Some(RecoveryKey::derive(passphrase, &salt)?);
+        Ok(())
+    }
+
+    /// Write new entries in plaintext and forget the key
+    pub fn disable_encryption(&mut self) {
+        self.key = None;
+    }
+
+    /// Whether new entries are written encrypted
+    pub fn encryption_enabled(&self) -> bool {
+        self.key.is_some()
+    }
+
+    fn read_or_create_salt(&self) -> io::Result<Vec<u8>> {
+        let salt_path = self.recovery_dir.join(Self::ENCRYPTION_SALT);
+        match fs::read(&salt_path) {
+            Ok(salt) if salt.len() == SALT_LEN => Ok(salt),
+            Ok(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid recovery salt file {}", salt_path.display()),
+            )),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                self.ensure_dir()?;
+                let salt = random_bytes::<SALT_LEN>();
+                self.atomic_write(&salt_path, &salt)?;
+                Ok(salt.to_vec())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Key for an encrypted entry, checked against the entry's key check
+    fn key_for(&self, encryption: &RecoveryEncryption) -> io::Result<&RecoveryKey> {
+        let key = self
+            .key
+            .as_ref()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::PermissionDenied, RecoveryKeyRequired))?;
+        let key_check = Self::decode_base64(&encryption.key_check)?;
+        key.open(
+            &Self::decode_nonce(encryption)?,
+            KEY_CHECK_INDEX,
+            &key_check,
+        )?;
+        Ok(key)
+    }
+
+    fn decode_nonce(encryption: &RecoveryEncryption) -> io::Result<[u8; NONCE_LEN]> {
+        Self::decode_base64(&encryption.nonce)?
+            .try_into()
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Invalid recovery nonce"))
+    }
+
+    fn decode_base64(value: &str) -> io::Result<Vec<u8>> {
+        BASE64
+            .decode(value)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    // ========================================================================
+    // Write policy
+    // ========================================================================
+
+    /// Turn all writes to the recovery directory on or off
+    ///
+    /// While off, saves and session lock updates are skipped. Existing files
+    /// are left alone and can still be read, listed and deleted.
+    pub fn set_writes_enabled(&mut self, enabled: bool) {
+        self.writes_enabled = enabled;
+    }
+
+    /// Whether writes to the recovery directory are enabled
+    pub fn writes_enabled(&self) -> bool {
+        self.writes_enabled
+    }
+
+    /// Exclude a buffer from recovery, or include it again
+    ///
+    /// Exempting a buffer deletes any recovery files it already has.
+    pub fn set_recovery_exempt(&mut self, id: &str, exempt: bool) -> io::Result<()> {
+        if exempt {
+            self.exempt.insert(id.to_string());
+            self.delete_recovery(id)
+        } else {
+            self.exempt.remove(id);
+            Ok(())
+        }
+    }
+
+    /// Whether a buffer is excluded from recovery
+    pub fn is_recovery_exempt(&self, id: &str) -> bool {
+        self.exempt.contains(id)
+    }
+
     // ========================================================================
     // Session lock management
     // ========================================================================
@@ -68,9 +194,12 @@ impl RecoveryStorage {
 
     /// Create a session lock file for crash detection
     pub fn create_session_lock(&self) -> io::Result<SessionInfo> {
+        let info = SessionInfo::new();
+        if !self.writes_enabled {
+            return Ok(info);
+        }
         self.ensure_dir()?;
 
-        let info = SessionInfo::new();
         let json = serde_json::to_string_pretty(&info).map_err(io::Error::other)?;
 
         self.atomic_write(&self.session_lock_path(), json.as_bytes())?;
@@ -80,7 +209,7 @@ impl RecoveryStorage {
     /// Update the session lock timestamp (heartbeat)
     pub fn update_session_lock(&self) -> io::Result<()> {
         let path = self.session_lock_path();
-        if path.exists() {
+        if self.writes_enabled && path.exists() {
             // Just update the file's mtime by rewriting it
             let info = SessionInfo::new();
             let json = serde_json::to_string_pretty(&info).map_err(io::Error::other)?;
@@ -186,7 +315,11 @@ impl RecoveryStorage {
     /// ## File Layout
     ///
     /// - `{id}.meta.json` - Contains RecoveryMetadata with embedded ChunkedRecoveryIndex
-    /// - `{id}.chunk.0`, `{id}.chunk.1`, ... - Raw binary content for each chunk
+    /// - `{id}.chunk.0`, `{id}.chunk.1`, ... - Binary content for each chunk,
+    ///   zstd-compressed when the chunks total more than `COMPRESSION_THRESHOLD`
+    ///
+    /// Returns `None` without touching the disk when writes are disabled or
+    /// the buffer is exempt from recovery.
     #[allow(clippy::too_many_arguments)]
     pub fn save_recovery(
         &self,
@@ -197,7 +330,10 @@ impl RecoveryStorage {
         line_count: Option<usize>,
         original_file_size: usize,
         final_size: usize,
-    ) -> io::Result<RecoveryMetadata> {
+    ) -> io::Result<Option<RecoveryMetadata>> {
+        if !self.writes_enabled || self.is_recovery_exempt(id) {
+            return Ok(None);
+        }
         self.ensure_dir()?;
 
         let (meta_path, _content_path) = self.recovery_paths(id);
@@ -207,15 +343,33 @@ impl RecoveryStorage {
 
         // Create chunked data structure
         let chunked_data = ChunkedRecoveryData::new(original_file_size, final_size, chunks);
+        let content_size: usize = chunked_data.chunks.iter().map(|c| c.content.len()).sum();
+        let compression = RecoveryCompression::for_size(content_size);
+        // A fresh nonce per save, so rewritten chunks never reuse one
+        let nonce = self.key.as_ref().map(|_| random_bytes::<NONCE_LEN>());
 
         // Write each chunk to a separate file
         let mut total_chunk_bytes = 0u64;
         for (i, chunk) in chunked_data.chunks.iter().enumerate() {
             let chunk_path = self.chunk_path(id, i);
-            self.atomic_write(&chunk_path, &chunk.content)?;
+            let data = Self::compress(&chunk.content, compression)?;
+            match (&self.key, &nonce) {
+                (Some(key), Some(nonce)) => {
+                    self.atomic_write(&chunk_path, &key.seal(nonce, i as u64, &data)?)?
+                }
+                _ => self.atomic_write(&chunk_path, &data)?,
+            }
             total_chunk_bytes += chunk.content.len() as u64;
         }
 
+        let encryption = match (&self.key, &nonce) {
+            (Some(key), Some(nonce)) => Some(RecoveryEncryption {
+                nonce: BASE64.encode(nonce),
+                key_check: BASE64.encode(key.seal(nonce, KEY_CHECK_INDEX, &[])?),
+            }),
+            _ => None,
+        };
+
         // Create the index (metadata without binary content)
         let index = chunked_data.to_index();
 
@@ -255,6 +409,8 @@ impl RecoveryStorage {
 
         // Update metadata fields
         metadata.original_file_size = original_file_size;
+        metadata.compression = compression;
+        metadata.encryption = encryption;
         metadata.update(total_chunk_bytes, line_count, chunked_data.chunks.len());
 
         // Create combined metadata with embedded chunk index
@@ -274,7 +430,7 @@ impl RecoveryStorage {
         let meta_json = serde_json::to_string_pretty(&meta_file).map_err(io::Error::other)?;
         self.atomic_write(&meta_path, meta_json.as_bytes())?;
 
-        Ok(metadata)
+        Ok(Some(metadata))
     }
 
     /// Read chunked recovery index from metadata file
@@ -299,6 +455,24 @@ impl RecoveryStorage {
         Ok(meta_file.chunked_index)
     }
 
+    /// Decoder for an entry's chunk files (plain if there's no metadata)
+    fn chunk_decoder(&self, id: &str) -> io::Result<ChunkDecoder<'_>> {
+        let Some(metadata) = self.read_metadata(id)? else {
+            return Ok(ChunkDecoder {
+                compression: RecoveryCompression::None,
+                encryption: None,
+            });
+        };
+        let encryption = match &metadata.encryption {
+            Some(encryption) => Some((self.key_for(encryption)?, Self::decode_nonce(encryption)?)),
+            None => None,
+        };
+        Ok(ChunkDecoder {
+            compression: metadata.compression,
+            encryption,
+        })
+    }
+
     /// Read chunked recovery data (loads index and all chunk content from files)
     ///
     /// This reads the chunk index from metadata and loads each chunk's binary
@@ -310,6 +484,8 @@ impl RecoveryStorage {
             None => return Ok(None),
         };
 
+        let decoder = self.chunk_decoder(id)?;
+
         // Load content for each chunk from its file
         let mut chunks = Vec::with_capacity(index.chunks.len());
         for (i, chunk_meta) in index.chunks.iter().enumerate() {
@@ -321,7 +497,7 @@ impl RecoveryStorage {
                 ));
             }
 
-            let content = fs::read(&chunk_path)?;
+            let content = decoder.decode(fs::read(&chunk_path)?, i as u64)?;
 
             chunks.push(RecoveryChunk {
                 offset: chunk_meta.offset,
@@ -368,29 +544,219 @@ impl RecoveryStorage {
             ));
         }
 
-        // Apply chunks to reconstruct content
-        let mut result = Vec::with_capacity(chunked_data.final_size);
+        Ok(Self::apply_chunks(
+            &original_content,
+            &chunked_data.chunks,
+            chunked_data.final_size,
+        ))
+    }
+
+    /// Apply sorted chunks to the original content
+    ///
+    /// Offsets past the end of the original are clamped, so damaged chunk
+    /// metadata can't cause a panic.
+    fn apply_chunks(original: &[u8], chunks: &[RecoveryChunk], final_size: usize) -> Vec<u8> {
+        let mut result = Vec::with_capacity(final_size);
         let mut original_pos = 0;
 
-        for chunk in &chunked_data.chunks {
+        for chunk in chunks {
             // Copy unchanged content before this chunk
-            if chunk.offset > original_pos {
-                result.extend_from_slice(&original_content[original_pos..chunk.offset]);
+            let offset = chunk.offset.min(original.len());
+            if offset > original_pos {
+                result.extend_from_slice(&original[original_pos..offset]);
             }
 
             // Insert the modified chunk content
             result.extend_from_slice(&chunk.content);
 
             // Move past the replaced region in the original
-            original_pos = chunk.offset + chunk.original_len;
+            original_pos = original_pos.max(offset.saturating_add(chunk.original_len));
         }
 
         // Copy any remaining content after the last chunk
-        if original_pos < original_content.len() {
-            result.extend_from_slice(&original_content[original_pos..]);
+        if original_pos < original.len() {
+            result.extend_from_slice(&original[original_pos..]);
         }
 
-        Ok(result)
+        result
+    }
+
+    /// Load an entry, salvaging what's readable if it's damaged
+    ///
+    /// Unlike [`Self::load_entry`] and [`Self::reconstruct_from_chunks`], a
+    /// missing, truncated or undecodable chunk doesn't fail the load: chunked
+    /// entries apply every chunk before the first bad one and report how many
+    /// were skipped, and full entries keep whatever part of the content is
+    /// readable. If the metadata itself is unreadable, a lone chunk file is
+    /// taken to be the full content. Returns `None` if there's no entry.
+    pub fn load_entry_lenient(&self, id: &str) -> io::Result<Option<SalvagedRecovery>> {
+        let (meta_path, _) = self.recovery_paths(id);
+        if !meta_path.exists() {
+            return Ok(None);
+        }
+
+        let mut problems = Vec::new();
+        let parsed = self
+            .read_metadata(id)
+            .and_then(|m| Ok((m, self.read_chunked_index(id)?)));
+        let (metadata, index) = match parsed {
+            Ok((Some(metadata), Some(index))) => (metadata, index),
+            Ok(_) => return self.salvage_without_metadata(id, "no chunk index".to_string()),
+            Err(e) => return self.salvage_without_metadata(id, e.to_string()),
+        };
+
+        let decoder = self.chunk_decoder(id)?;
+        let mut chunks = Vec::with_capacity(index.chunks.len());
+        let mut chunks_skipped = 0;
+        for (i, chunk_meta) in index.chunks.iter().enumerate() {
+            let (content, error) = match fs::read(self.chunk_path(id, i)) {
+                Ok(data) => decoder.decode_partial(data, i as u64),
+                Err(e) => (Vec::new(), Some(e)),
+            };
+            let problem = match error {
+                Some(e) => Some(format!("chunk {i} is unreadable: {e}")),
+                None if content.len() != chunk_meta.size => Some(format!(
+                    "chunk {i} has {} bytes, expected {}",
+                    content.len(),
+                    chunk_meta.size
+                )),
+                None => None,
+            };
+            if let Some(problem) = problem {
+                problems.push(problem);
+                chunks_skipped = index.chunks.len() - i;
+                // A full entry is its chunks back to back, so a damaged
+                // chunk's readable prefix is still good content
+                if metadata.original_file_size == 0 && !content.is_empty() {
+                    chunks.push(RecoveryChunk::new(
+                        chunk_meta.offset,
+                        chunk_meta.original_len,
+                        content,
+                    ));
+                }
+                break;
+            }
+            chunks.push(RecoveryChunk::new(
+                chunk_meta.offset,
+                chunk_meta.original_len,
+                content,
+            ));
+        }
+        let mut partial = chunks_skipped > 0;
+
+        let content = if metadata.original_file_size == 0 {
+            chunks.into_iter().flat_map(|c| c.content).collect()
+        } else {
+            let original_path = metadata.original_path.as_deref().ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Chunked recovery entry has no original file path",
+                )
+            })?;
+            let original = fs::read(original_path)?;
+            if original.len() != metadata.original_file_size {
+                problems.push(format!(
+                    "original file is {} bytes, expected {}",
+                    original.len(),
+                    metadata.original_file_size
+                ));
+                partial = true;
+            }
+            Self::apply_chunks(&original, &chunks, index.final_size)
+        };
+
+        Ok(Some(SalvagedRecovery {
+            metadata: Some(metadata),
+            content,
+            partial,
+            chunks_skipped,
+            problems,
+        }))
+    }
+
+    /// Salvage an entry whose metadata can't be parsed
+    fn salvage_without_metadata(
+        &self,
+        id: &str,
+        reason: String,
+    ) -> io::Result<Option<SalvagedRecovery>> {
+        let chunk_paths = self.list_chunk_paths(id)?;
+        let problem = format!("metadata is unreadable: {reason}");
+        let [chunk_path] = chunk_paths.as_slice() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "{problem}; cannot tell how to apply {} chunk files",
+                    chunk_paths.len()
+                ),
+            ));
+        };
+
+        // Without metadata, guess the encoding from the data itself
+        let data = fs::read(chunk_path)?;
+        let (content, error) = if data.starts_with(&ZSTD_MAGIC) {
+            Self::decompress_partial(data, RecoveryCompression::Zstd)
+        } else {
+            (data, None)
+        };
+        let mut problems = vec![
+            problem,
+            "assuming the only chunk is the full content".into(),
+        ];
+        if let Some(e) = error {
+            problems.push(format!("chunk 0 is unreadable: {e}"));
+        }
+
+        Ok(Some(SalvagedRecovery {
+            metadata: None,
+            content,
+            partial: true,
+            chunks_skipped: 0,
+            problems,
+        }))
+    }
+
+    /// Line-level diff of an entry's recovered content against the file on disk
+    ///
+    /// Chunked entries are reconstructed from the original file, full entries
+    /// are their single chunk. Returns no hunks when the recovered content
+    /// matches the file, and one hunk adding every line when there's no
+    /// original file (unsaved buffer, or the file has since been removed).
+    pub fn diff_against_original(&self, id: &str) -> io::Result<Vec<DiffHunk>> {
+        let metadata = self
+            .read_metadata(id)?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Recovery entry not found"))?;
+        let original = metadata.original_path.as_deref().filter(|p| p.exists());
+
+        let recovered = if metadata.original_file_size > 0 {
+            let original = original.ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "Original file not found. Chunked recovery requires the original file.",
+                )
+            })?;
+            self.reconstruct_from_chunks(id, original)?
+        } else {
+            self.read_chunked_content(id)?
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Chunk content not found"))?
+                .chunks
+                .into_iter()
+                .flat_map(|c| c.content)
+                .collect()
+        };
+
+        match original {
+            Some(path) => Ok(diff_hunks(&fs::read(path)?, &recovered)),
+            None => {
+                let newlines = recovered.iter().filter(|&&b| b == b'\n').count();
+                let lines = newlines + usize::from(recovered.last().is_some_and(|&b| b != b'\n'));
+                if lines == 0 {
+                    Ok(Vec::new())
+                } else {
+                    Ok(vec![DiffHunk::new(0, 0, 0, lines)])
+                }
+            }
+        }
     }
 
     /// Read recovery metadata
@@ -412,11 +778,24 @@ impl RecoveryStorage {
         if !content_path.exists() {
             return Ok(None);
         }
-        Ok(Some(fs::read(&content_path)?))
+        let decoder = self.chunk_decoder(id)?;
+        Ok(Some(decoder.decode(fs::read(&content_path)?, 0)?))
     }
 
     /// Load a complete recovery entry
+    ///
+    /// Encrypted entries fail with [`RecoveryKeyRequired`] when no passphrase
+    /// is set and with [`super::WrongRecoveryKey`] when it doesn't match.
     pub fn load_entry(&self, id: &str) -> io::Result<Option<RecoveryEntry>> {
+        let entry = self.load_entry_metadata(id)?;
+        if let Some(encryption) = entry.as_ref().and_then(|e| e.metadata.encryption.as_ref()) {
+            self.key_for(encryption)?;
+        }
+        Ok(entry)
+    }
+
+    /// Load an entry without checking the key (metadata is never encrypted)
+    fn load_entry_metadata(&self, id: &str) -> io::Result<Option<RecoveryEntry>> {
         let (meta_path, content_path) = self.recovery_paths(id);
 
         if !meta_path.exists() {
@@ -465,6 +844,9 @@ impl RecoveryStorage {
     }
 
     /// List all recovery entries
+    ///
+    /// Encrypted entries are listed whether or not the key is set, so they
+    /// can be shown and cleaned up; reading their content needs the key.
     pub fn list_entries(&self) -> io::Result<Vec<RecoveryEntry>> {
         if !self.recovery_dir.exists() {
             return Ok(Vec::new());
@@ -481,7 +863,7 @@ impl RecoveryStorage {
                 if name.ends_with(&format!(".{}", Self::META_EXT)) {
                     // Extract the ID (everything before .meta.json)
                     let id = name.trim_end_matches(&format!(".{}", Self::META_EXT));
-                    if let Ok(Some(entry)) = self.load_entry(id) {
+                    if let Ok(Some(entry)) = self.load_entry_metadata(id) {
                         entries.push(entry);
                     }
                 }
@@ -558,6 +940,64 @@ impl RecoveryStorage {
         Ok(cleaned)
     }
 
+    /// Total on-disk size of an entry's metadata, chunk and content files
+    fn entry_disk_size(&self, id: &str) -> u64 {
+        let (meta_path, content_path) = self.recovery_paths(id);
+        let chunk_paths = self.list_chunk_paths(id).unwrap_or_default();
+        [meta_path, content_path]
+            .iter()
+            .chain(chunk_paths.iter())
+            .filter_map(|p| fs::metadata(p).ok())
+            .map(|m| m.len())
+            .sum()
+    }
+
+    /// Bound the recovery directory by entry count, age and total size
+    ///
+    /// Deletes the oldest entries (by `updated_at`) until at most
+    /// `max_entries` remain, none was updated more than `max_age` ago, and
+    /// together they take at most `max_total_bytes` on disk. The session lock
+    /// is never touched.
+    pub fn enforce_retention(
+        &self,
+        max_entries: usize,
+        max_age: Duration,
+        max_total_bytes: u64,
+    ) -> io::Result<RetentionSummary> {
+        let mut summary = RetentionSummary::default();
+        let mut kept = 0;
+        let mut kept_bytes = 0u64;
+        let mut evicting = false;
+
+        // Newest first: keep entries until one breaks a limit, then drop the rest
+        for entry in self.list_entries()? {
+            let size = self.entry_disk_size(&entry.id);
+            evicting = evicting
+                || kept >= max_entries
+                || entry.age_seconds() > max_age.as_secs()
+                || kept_bytes + size > max_total_bytes;
+
+            if evicting {
+                self.delete_recovery(&entry.id)?;
+                summary.entries_removed += 1;
+                summary.bytes_reclaimed += size;
+            } else {
+                kept += 1;
+                kept_bytes += size;
+            }
+        }
+
+        if summary.entries_removed > 0 {
+            tracing::info!(
+                "Recovery retention removed {} entries ({} bytes)",
+                summary.entries_removed,
+                summary.bytes_reclaimed
+            );
+        }
+
+        Ok(summary)
+    }
+
     /// Clean up all recovery files (after successful recovery or user dismissal)
     pub fn cleanup_all(&self) -> io::Result<usize> {
         if !self.recovery_dir.exists() {
@@ -571,8 +1011,8 @@ impl RecoveryStorage {
             let path = entry.path();
 
             if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                // Don't delete the session lock
-                if name == Self::SESSION_LOCK {
+                // Don't delete the session lock or the key's salt
+                if name == Self::SESSION_LOCK || name == Self::ENCRYPTION_SALT {
                     continue;
                 }
 
@@ -660,6 +1100,43 @@ impl RecoveryStorage {
     // Helper methods
     // ========================================================================
 
+    /// Encode chunk content for disk
+    fn compress(content: &[u8], compression: RecoveryCompression) -> io::Result<Cow<'_, [u8]>> {
+        match compression {
+            RecoveryCompression::None => Ok(Cow::Borrowed(content)),
+            RecoveryCompression::Zstd => {
+                // Fast level: recovery saves run on the autosave timer
+                Ok(Cow::Owned(zstd::encode_all(content, 1)?))
+            }
+        }
+    }
+
+    /// Decode chunk content read from disk, keeping what decodes before an error
+    fn decompress_partial(
+        data: Vec<u8>,
+        compression: RecoveryCompression,
+    ) -> (Vec<u8>, Option<io::Error>) {
+        match compression {
+            RecoveryCompression::None => (data, None),
+            RecoveryCompression::Zstd => {
+                let mut content = Vec::new();
+                // read_to_end keeps the bytes read before a failure
+                let error = zstd::Decoder::new(data.as_slice())
+                    .and_then(|mut decoder| decoder.read_to_end(&mut content))
+                    .err();
+                (content, error)
+            }
+        }
+    }
+
+    /// Decode chunk content read from disk
+    fn decompress(data: Vec<u8>, compression: RecoveryCompression) -> io::Result<Vec<u8>> {
+        match compression {
+            RecoveryCompression::None => Ok(data),
+            RecoveryCompression::Zstd => zstd::decode_all(data.as_slice()),
+        }
+    }
+
     /// Perform an atomic write: write to temp file, then rename
     ///
     /// TODO: Migrate to async I/O to avoid blocking the main thread during recovery saves.
@@ -683,22 +1160,53 @@ impl RecoveryStorage {
 
 impl Default for RecoveryStorage {
     fn default() -> Self {
-        Self::new().unwrap_or_else(|_| Self {
-            recovery_dir: PathBuf::from("/tmp/fresh-recovery"),
-        })
+        Self::new().unwrap_or_else(|_| Self::with_dir(PathBuf::from("/tmp/fresh-recovery")))
+    }
+}
+
+/// How to turn an entry's chunk files back into content
+struct ChunkDecoder<'a> {
+    compression: RecoveryCompression,
+    /// Verified key and entry nonce, for encrypted entries
+    encryption: Option<(&'a RecoveryKey, [u8; NONCE_LEN])>,
+}
+
+impl ChunkDecoder<'_> {
+    /// Decrypt (if needed) and then decompress the file for chunk `index`
+    fn decode(&self, data: Vec<u8>, index: u64) -> io::Result<Vec<u8>> {
+        let data = match &self.encryption {
+            Some((key, nonce)) => key.open(nonce, index, &data)?,
+            None => data,
+        };
+        RecoveryStorage::decompress(data, self.compression)
+    }
+
+    /// Like [`Self::decode`], but returns whatever decodes before an error
+    fn decode_partial(&self, data: Vec<u8>, index: u64) -> (Vec<u8>, Option<io::Error>) {
+        let data = match &self.encryption {
+            // Authenticated encryption is all or nothing
+            Some((key, nonce)) => match key.open(nonce, index, &data) {
+                Ok(data) => data,
+                Err(e) => return (Vec::new(), Some(e)),
+            },
+            None => data,
+        };
+        RecoveryStorage::decompress_partial(data, self.compression)
     }
 }
 
+/// Leading bytes of every zstd frame
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::recovery::types::COMPRESSION_THRESHOLD;
     use tempfile::TempDir;
 
     fn create_test_storage() -> (RecoveryStorage, TempDir) {
         let temp_dir = TempDir::new().unwrap();
-        let storage = RecoveryStorage {
-            recovery_dir: temp_dir.path().to_path_buf(),
-        };
+        let storage = RecoveryStorage::with_dir(temp_dir.path().to_path_buf());
         (storage, temp_dir)
     }
 
@@ -734,6 +1242,7 @@ mod tests {
         let chunks = vec![RecoveryChunk::new(0, 0, content.to_vec())];
         let metadata = storage
             .save_recovery(&id, chunks, Some(path), None, Some(1), 0, content.len())
+            .unwrap()
             .unwrap();
 
         assert_eq!(metadata.content_size, content.len() as u64);
@@ -789,6 +1298,70 @@ mod tests {
         assert!(storage.load_entry(id).unwrap().is_none());
     }
 
+    /// Names of files in the recovery directory belonging to `id`
+    fn files_for(storage: &RecoveryStorage, id: &str) -> Vec<String> {
+        let Ok(dir) = fs::read_dir(storage.base_dir()) else {
+            return Vec::new();
+        };
+        dir.filter_map(|e| e.ok()?.file_name().into_string().ok())
+            .filter(|name| name.starts_with(&format!("{id}.")))
+            .collect()
+    }
+
+    #[test]
+    fn test_exempt_buffer_writes_no_files() {
+        let (mut storage, _temp) = create_test_storage();
+        storage.set_recovery_exempt("scratch", true).unwrap();
+
+        let chunks = vec![RecoveryChunk::new(0, 0, b"scratch text".to_vec())];
+        let saved = storage
+            .save_recovery("scratch", chunks, None, Some("*scratch*"), None, 0, 12)
+            .unwrap();
+
+        assert!(saved.is_none());
+        assert!(files_for(&storage, "scratch").is_empty());
+        assert!(storage.list_entries().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_exempting_buffer_removes_stale_entry() {
+        let (mut storage, _temp) = create_test_storage();
+        for id in ["log", "kept"] {
+            let chunks = vec![RecoveryChunk::new(0, 0, b"content".to_vec())];
+            storage
+                .save_recovery(id, chunks, None, None, None, 0, 7)
+                .unwrap();
+        }
+        assert!(!files_for(&storage, "log").is_empty());
+
+        storage.set_recovery_exempt("log", true).unwrap();
+        assert!(files_for(&storage, "log").is_empty());
+        assert!(storage.load_entry("kept").unwrap().is_some());
+
+        // Lifting the exemption allows saving again
+        storage.set_recovery_exempt("log", false).unwrap();
+        let chunks = vec![RecoveryChunk::new(0, 0, b"content".to_vec())];
+        storage
+            .save_recovery("log", chunks, None, None, None, 0, 7)
+            .unwrap();
+        assert!(storage.load_entry("log").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_disabled_writes_leave_directory_untouched() {
+        let (mut storage, temp) = create_test_storage();
+        storage.set_writes_enabled(false);
+
+        storage.create_session_lock().unwrap();
+        let chunks = vec![RecoveryChunk::new(0, 0, b"content".to_vec())];
+        let saved = storage
+            .save_recovery("buf", chunks, None, None, None, 0, 7)
+            .unwrap();
+
+        assert!(saved.is_none());
+        assert!(fs::read_dir(temp.path()).unwrap().next().is_none());
+    }
+
     #[test]
     fn test_cleanup_orphans() {
         let (storage, _temp) = create_test_storage();
@@ -860,6 +1433,7 @@ mod tests {
                 original_size,
                 final_size,
             )
+            .unwrap()
             .unwrap();
 
         // Verify metadata
@@ -897,6 +1471,670 @@ mod tests {
         drop(temp_dir);
     }
 
+    #[test]
+    fn test_large_recovery_is_compressed() {
+        let (storage, _temp) = create_test_storage();
+
+        let content: Vec<u8> = b"fn main() {\n    println!(\"hello\");\n}\n"
+            .iter()
+            .copied()
+            .cycle()
+            .take(COMPRESSION_THRESHOLD * 4)
+            .collect();
+        let id = "test-compressed";
+        let chunks = vec![RecoveryChunk::new(0, 0, content.clone())];
+        let metadata = storage
+            .save_recovery(id, chunks, None, Some("Untitled-1"), None, 0, content.len())
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(metadata.compression, RecoveryCompression::Zstd);
+        assert_eq!(metadata.content_size, content.len() as u64);
+
+        // The chunk file on disk is much smaller than the content
+        let on_disk = fs::metadata(storage.chunk_path(id, 0)).unwrap().len();
+        assert!(
+            on_disk * 10 < content.len() as u64,
+            "{} bytes on disk for {} bytes of content",
+            on_disk,
+            content.len()
+        );
+
+        // Reading decompresses transparently
+        let chunked_data = storage.read_chunked_content(id).unwrap().unwrap();
+        assert_eq!(chunked_data.chunks[0].content, content);
+
+        let entry = storage.load_entry(id).unwrap().unwrap();
+        assert_eq!(entry.metadata.compression, RecoveryCompression::Zstd);
+
+        // read_content decompresses the single-file layout too
+        let (_, content_path) = storage.recovery_paths(id);
+        fs::copy(storage.chunk_path(id, 0), &content_path).unwrap();
+        assert_eq!(storage.read_content(id).unwrap().unwrap(), content);
+    }
+
+    #[test]
+    fn test_small_recovery_is_not_compressed() {
+        let (storage, _temp) = create_test_storage();
+
+        let content = b"small buffer".to_vec();
+        let id = "test-uncompressed";
+        let chunks = vec![RecoveryChunk::new(0, 0, content.clone())];
+        let metadata = storage
+            .save_recovery(id, chunks, None, None, None, 0, content.len())
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(metadata.compression, RecoveryCompression::None);
+        assert_eq!(fs::read(storage.chunk_path(id, 0)).unwrap(), content);
+    }
+
+    #[test]
+    fn test_metadata_without_compression_field_loads_uncompressed() {
+        let (storage, _temp) = create_test_storage();
+
+        let content = b"written before compression existed".to_vec();
+        let id = "test-legacy";
+        let chunks = vec![RecoveryChunk::new(0, 0, content.clone())];
+        storage
+            .save_recovery(id, chunks, None, None, None, 0, content.len())
+            .unwrap();
+
+        // Strip the field, as in metadata written by older versions
+        let (meta_path, _) = storage.recovery_paths(id);
+        let mut meta: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&meta_path).unwrap()).unwrap();
+        meta.as_object_mut().unwrap().remove("compression");
+        fs::write(&meta_path, meta.to_string()).unwrap();
+
+        let metadata = storage.read_metadata(id).unwrap().unwrap();
+        assert_eq!(metadata.compression, RecoveryCompression::None);
+        let chunked_data = storage.read_chunked_content(id).unwrap().unwrap();
+        assert_eq!(chunked_data.chunks[0].content, content);
+    }
+
+    fn create_encrypted_storage(passphrase: &str) -> (RecoveryStorage, TempDir) {
+        let (mut storage, temp) = create_test_storage();
+        storage.enable_encryption(passphrase).unwrap();
+        (storage, temp)
+    }
+
+    fn reopen(temp: &TempDir, passphrase: Option<&str>) -> RecoveryStorage {
+        let mut storage = RecoveryStorage::with_dir(temp.path().to_path_buf());
+        if let Some(passphrase) = passphrase {
+            storage.enable_encryption(passphrase).unwrap();
+        }
+        storage
+    }
+
+    #[test]
+    fn test_encrypted_recovery_round_trip() {
+        let (storage, temp) = create_encrypted_storage("correct horse");
+        let content = b"API_KEY=super-secret-value\n".to_vec();
+        let chunks = vec![
+            RecoveryChunk::new(0, 0, content.clone()),
+            RecoveryChunk::new(100, 4, b"second".to_vec()),
+        ];
+        let metadata = storage
+            .save_recovery("secret", chunks, None, Some("secrets.env"), None, 0, 0)
+            .unwrap()
+            .unwrap();
+        assert!(metadata.is_encrypted());
+
+        // Nothing readable on disk
+        let on_disk = fs::read(storage.chunk_path("secret", 0)).unwrap();
+        assert!(!on_disk.windows(12).any(|w| w == b"super-secret"));
+
+        // A fresh storage with the same passphrase reads it back
+        let storage = reopen(&temp, Some("correct horse"));
+        let entry = storage.load_entry("secret").unwrap().unwrap();
+        assert_eq!(entry.metadata.buffer_name.as_deref(), Some("secrets.env"));
+        let data = storage.read_chunked_content("secret").unwrap().unwrap();
+        assert_eq!(data.chunks[0].content, content);
+        assert_eq!(data.chunks[1].content, b"second");
+        assert_eq!(data.chunks[1].offset, 100);
+    }
+
+    #[test]
+    fn test_encrypted_recovery_compressed_round_trip() {
+        let (storage, temp) = create_encrypted_storage("pass");
+        let content: Vec<u8> = b"let x = 1;\n".repeat(COMPRESSION_THRESHOLD / 8);
+        let metadata = storage
+            .save_recovery(
+                "big",
+                vec![RecoveryChunk::new(0, 0, content.clone())],
+                None,
+                None,
+                None,
+                0,
+                content.len(),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(metadata.compression, RecoveryCompression::Zstd);
+        assert!(fs::metadata(storage.chunk_path("big", 0)).unwrap().len() < content.len() as u64);
+
+        let storage = reopen(&temp, Some("pass"));
+        let data = storage.read_chunked_content("big").unwrap().unwrap();
+        assert_eq!(data.chunks[0].content, content);
+    }
+
+    #[test]
+    fn test_encrypted_recovery_wrong_key_fails_clearly() {
+        let (storage, temp) = create_encrypted_storage("right");
+        storage
+            .save_recovery(
+                "secret",
+                vec![RecoveryChunk::new(0, 0, b"hidden".to_vec())],
+                None,
+                None,
+                None,
+                0,
+                6,
+            )
+            .unwrap();
+
+        let storage = reopen(&temp, Some("wrong"));
+        for err in [
+            storage.load_entry("secret").unwrap_err(),
+            storage.read_chunked_content("secret").unwrap_err(),
+        ] {
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            assert!(err
+                .get_ref()
+                .is_some_and(|e| e.is::<crate::services::recovery::WrongRecoveryKey>()));
+        }
+
+        // Without any passphrase the entry is still listed but can't be read
+        let storage = reopen(&temp, None);
+        assert_eq!(storage.list_entries().unwrap().len(), 1);
+        let err = storage.read_chunked_content("secret").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+        assert!(err.get_ref().is_some_and(|e| e.is::<RecoveryKeyRequired>()));
+    }
+
+    #[test]
+    fn test_tampered_encrypted_chunk_is_rejected() {
+        let (storage, _temp) = create_encrypted_storage("pass");
+        storage
+            .save_recovery(
+                "secret",
+                vec![RecoveryChunk::new(0, 0, b"hidden".to_vec())],
+                None,
+                None,
+                None,
+                0,
+                6,
+            )
+            .unwrap();
+
+        let chunk_path = storage.chunk_path("secret", 0);
+        let mut data = fs::read(&chunk_path).unwrap();
+        data[0] ^= 0xff;
+        fs::write(&chunk_path, data).unwrap();
+
+        // The key check still passes, but the chunk fails authentication
+        assert!(storage.load_entry("secret").unwrap().is_some());
+        let err = storage.read_chunked_content("secret").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_encryption_salt_survives_cleanup() {
+        let (storage, temp) = create_encrypted_storage("pass");
+        storage.cleanup_all().unwrap();
+        storage
+            .save_recovery(
+                "later",
+                vec![RecoveryChunk::new(0, 0, b"after cleanup".to_vec())],
+                None,
+                None,
+                None,
+                0,
+                13,
+            )
+            .unwrap();
+
+        let storage = reopen(&temp, Some("pass"));
+        let data = storage.read_chunked_content("later").unwrap().unwrap();
+        assert_eq!(data.chunks[0].content, b"after cleanup");
+    }
+
+    fn hunk_spans(hunks: &[DiffHunk]) -> Vec<(usize, usize, usize, usize)> {
+        hunks
+            .iter()
+            .map(|h| (h.old_start, h.old_count, h.new_start, h.new_count))
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_full_entry_against_original() {
+        let (storage, temp) = create_test_storage();
+        let original = temp.path().join("notes.txt");
+        fs::write(&original, "one\ntwo\nthree\nfour\n").unwrap();
+
+        // Line 1 changed, line 2 removed, a line added at the end
+        let recovered = b"one\nTWO\nfour\nfive\n".to_vec();
+        let len = recovered.len();
+        storage
+            .save_recovery(
+                "full",
+                vec![RecoveryChunk::new(0, 0, recovered)],
+                Some(&original),
+                None,
+                None,
+                0,
+                len,
+            )
+            .unwrap();
+
+        let hunks = storage.diff_against_original("full").unwrap();
+        assert_eq!(hunk_spans(&hunks), vec![(1, 2, 1, 1), (4, 0, 3, 1)]);
+    }
+
+    #[test]
+    fn test_diff_chunked_entry_against_original() {
+        let (storage, temp) = create_test_storage();
+        let original = temp.path().join("big.txt");
+        let content = "aaaa\nbbbb\ncccc\ndddd\n";
+        fs::write(&original, content).unwrap();
+
+        // Replace "bbbb" and insert a new line before "dddd"
+        let chunks = vec![
+            RecoveryChunk::new(5, 4, b"BBBB".to_vec()),
+            RecoveryChunk::new(15, 0, b"new\n".to_vec()),
+        ];
+        storage
+            .save_recovery(
+                "chunked",
+                chunks,
+                Some(&original),
+                None,
+                None,
+                content.len(),
+                content.len() + 4,
+            )
+            .unwrap();
+
+        let hunks = storage.diff_against_original("chunked").unwrap();
+        assert_eq!(hunk_spans(&hunks), vec![(1, 1, 1, 1), (3, 0, 3, 1)]);
+    }
+
+    #[test]
+    fn test_diff_large_chunked_entry_with_distant_edits() {
+        let (storage, temp) = create_test_storage();
+        let original = temp.path().join("huge.txt");
+        let lines = 200_000;
+        let content: String = (0..lines).map(|i| format!("line {i:06}\n")).collect();
+        fs::write(&original, &content).unwrap();
+
+        // Every line is 12 bytes: change line 10 and insert before line 199990
+        let chunks = vec![
+            RecoveryChunk::new(10 * 12, 11, b"LINE 000010".to_vec()),
+            RecoveryChunk::new(199_990 * 12, 0, b"new\n".to_vec()),
+        ];
+        storage
+            .save_recovery(
+                "huge",
+                chunks,
+                Some(&original),
+                None,
+                None,
+                content.len(),
+                content.len() + 4,
+            )
+            .unwrap();
+
+        let hunks = storage.diff_against_original("huge").unwrap();
+        assert_eq!(
+            hunk_spans(&hunks),
+            vec![(10, 1, 10, 1), (199_990, 0, 199_990, 1)]
+        );
+    }
+
+    #[test]
+    fn test_diff_identical_is_empty() {
+        let (storage, temp) = create_test_storage();
+        let original = temp.path().join("same.txt");
+        fs::write(&original, "same\n").unwrap();
+        storage
+            .save_recovery(
+                "same",
+                vec![RecoveryChunk::new(0, 0, b"same\n".to_vec())],
+                Some(&original),
+                None,
+                None,
+                0,
+                5,
+            )
+            .unwrap();
+
+        assert!(storage.diff_against_original("same").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_diff_without_original_is_all_added() {
+        let (storage, temp) = create_test_storage();
+        let missing = temp.path().join("deleted.txt");
+        storage
+            .save_recovery(
+                "gone",
+                vec![RecoveryChunk::new(0, 0, b"a\nb\nc".to_vec())],
+                Some(&missing),
+                None,
+                None,
+                0,
+                5,
+            )
+            .unwrap();
+        storage
+            .save_recovery(
+                "unnamed",
+                vec![RecoveryChunk::new(0, 0, b"x\ny\n".to_vec())],
+                None,
+                Some("Untitled-1"),
+                None,
+                0,
+                4,
+            )
+            .unwrap();
+
+        let hunks = storage.diff_against_original("gone").unwrap();
+        assert_eq!(hunk_spans(&hunks), vec![(0, 0, 0, 3)]);
+        let hunks = storage.diff_against_original("unnamed").unwrap();
+        assert_eq!(hunk_spans(&hunks), vec![(0, 0, 0, 2)]);
+    }
+
+    #[test]
+    fn test_diff_chunked_entry_without_original_fails() {
+        let (storage, temp) = create_test_storage();
+        let missing = temp.path().join("missing.txt");
+        storage
+            .save_recovery(
+                "chunked",
+                vec![RecoveryChunk::new(0, 1, b"X".to_vec())],
+                Some(&missing),
+                None,
+                None,
+                10,
+                10,
+            )
+            .unwrap();
+
+        let err = storage.diff_against_original("chunked").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    /// Save a full entry holding `content` and return its single chunk's path
+    fn save_full_entry(storage: &RecoveryStorage, id: &str, content: &[u8]) -> PathBuf {
+        storage
+            .save_recovery(
+                id,
+                vec![RecoveryChunk::new(0, 0, content.to_vec())],
+                None,
+                Some(id),
+                None,
+                0,
+                content.len(),
+            )
+            .unwrap();
+        storage.chunk_path(id, 0)
+    }
+
+    #[test]
+    fn test_lenient_load_of_healthy_entry() {
+        let (storage, _temp) = create_test_storage();
+        save_full_entry(&storage, "ok", b"all good\n");
+
+        let salvaged = storage.load_entry_lenient("ok").unwrap().unwrap();
+        assert!(!salvaged.partial);
+        assert_eq!(salvaged.chunks_skipped, 0);
+        assert!(salvaged.problems.is_empty());
+        assert_eq!(salvaged.content, b"all good\n");
+        assert!(storage.load_entry_lenient("missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_lenient_load_truncated_full_content() {
+        let (storage, _temp) = create_test_storage();
+        let chunk_path = save_full_entry(&storage, "trunc", b"0123456789");
+        fs::write(&chunk_path, b"01234").unwrap();
+
+        let salvaged = storage.load_entry_lenient("trunc").unwrap().unwrap();
+        assert!(salvaged.partial);
+        assert_eq!(salvaged.content, b"01234");
+        assert_eq!(salvaged.chunks_skipped, 1);
+        assert!(salvaged.problems[0].contains("chunk 0 has 5 bytes, expected 10"));
+    }
+
+    #[test]
+    fn test_lenient_load_truncated_compressed_content() {
+        let (storage, _temp) = create_test_storage();
+        // zstd decodes in blocks of up to 128 KB, so span several with
+        // data that doesn't compress into the first one
+        let mut seed = 1u32;
+        let content: Vec<u8> = (0..COMPRESSION_THRESHOLD * 8)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                b'a' + (seed >> 16) as u8 % 16
+            })
+            .collect();
+        let chunk_path = save_full_entry(&storage, "zst", &content);
+        let data = fs::read(&chunk_path).unwrap();
+        fs::write(&chunk_path, &data[..data.len() / 2]).unwrap();
+
+        // The strict path fails outright
+        assert!(storage.read_chunked_content("zst").is_err());
+
+        let salvaged = storage.load_entry_lenient("zst").unwrap().unwrap();
+        assert!(salvaged.partial);
+        assert!(!salvaged.content.is_empty());
+        assert!(salvaged.content.len() < content.len());
+        assert_eq!(salvaged.content, content[..salvaged.content.len()]);
+        assert!(salvaged.problems[0].starts_with("chunk 0 is unreadable"));
+    }
+
+    #[test]
+    fn test_lenient_load_applies_chunks_before_first_bad_one() {
+        let (storage, temp) = create_test_storage();
+        let original = temp.path().join("orig.txt");
+        fs::write(&original, "aaaa\nbbbb\ncccc\n").unwrap();
+
+        let chunks = vec![
+            RecoveryChunk::new(0, 4, b"AAAA".to_vec()),
+            RecoveryChunk::new(5, 4, b"BBBB".to_vec()),
+            RecoveryChunk::new(10, 4, b"CCCC".to_vec()),
+        ];
+        storage
+            .save_recovery("chunked", chunks, Some(&original), None, None, 15, 15)
+            .unwrap();
+        fs::remove_file(storage.chunk_path("chunked", 1)).unwrap();
+
+        assert!(storage
+            .reconstruct_from_chunks("chunked", &original)
+            .is_err());
+
+        let salvaged = storage.load_entry_lenient("chunked").unwrap().unwrap();
+        assert!(salvaged.partial);
+        assert_eq!(salvaged.chunks_skipped, 2);
+        assert_eq!(salvaged.content, b"AAAA\nbbbb\ncccc\n");
+        assert!(salvaged.problems[0].starts_with("chunk 1 is unreadable"));
+        assert!(salvaged.metadata.is_some());
+    }
+
+    #[test]
+    fn test_lenient_load_truncated_metadata() {
+        let (storage, _temp) = create_test_storage();
+        save_full_entry(&storage, "meta", b"still here");
+        let (meta_path, _) = storage.recovery_paths("meta");
+        let json = fs::read_to_string(&meta_path).unwrap();
+        fs::write(&meta_path, &json[..json.len() / 2]).unwrap();
+
+        assert!(storage.load_entry("meta").is_err());
+
+        let salvaged = storage.load_entry_lenient("meta").unwrap().unwrap();
+        assert!(salvaged.partial);
+        assert!(salvaged.metadata.is_none());
+        assert_eq!(salvaged.content, b"still here");
+        assert!(salvaged.problems[0].starts_with("metadata is unreadable"));
+    }
+
+    #[test]
+    fn test_lenient_load_truncated_metadata_with_many_chunks_errors() {
+        let (storage, temp) = create_test_storage();
+        let original = temp.path().join("orig.txt");
+        fs::write(&original, "0123456789").unwrap();
+        let chunks = vec![
+            RecoveryChunk::new(0, 1, b"A".to_vec()),
+            RecoveryChunk::new(5, 1, b"B".to_vec()),
+        ];
+        storage
+            .save_recovery("many", chunks, Some(&original), None, None, 10, 10)
+            .unwrap();
+        let (meta_path, _) = storage.recovery_paths("many");
+        fs::write(&meta_path, "{\"original_path\": ").unwrap();
+
+        let err = storage.load_entry_lenient("many").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err
+            .to_string()
+            .contains("cannot tell how to apply 2 chunk files"));
+    }
+
+    #[test]
+    fn test_lenient_load_never_panics_on_bad_offsets() {
+        let (storage, temp) = create_test_storage();
+        let original = temp.path().join("orig.txt");
+        fs::write(&original, "short").unwrap();
+        let chunks = vec![
+            RecoveryChunk::new(3, 100, b"X".to_vec()),
+            RecoveryChunk::new(1, 0, b"Y".to_vec()),
+        ];
+        storage
+            .save_recovery("bad", chunks, Some(&original), None, None, 5, 5)
+            .unwrap();
+
+        // Offsets overlap and run past the end of the original
+        let salvaged = storage.load_entry_lenient("bad").unwrap().unwrap();
+        assert_eq!(salvaged.content, b"shoXY");
+    }
+
+    /// Save a small entry and backdate its `updated_at` by `age_secs`
+    fn save_aged_entry(storage: &RecoveryStorage, id: &str, content: &[u8], age_secs: u64) {
+        let chunks = vec![RecoveryChunk::new(0, 0, content.to_vec())];
+        storage
+            .save_recovery(id, chunks, None, Some(id), None, 0, content.len())
+            .unwrap();
+
+        let (meta_path, _) = storage.recovery_paths(id);
+        let mut meta: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&meta_path).unwrap()).unwrap();
+        let now = meta["updated_at"].as_u64().unwrap();
+        meta["updated_at"] = serde_json::json!(now - age_secs);
+        fs::write(&meta_path, meta.to_string()).unwrap();
+    }
+
+    fn surviving_ids(storage: &RecoveryStorage) -> Vec<String> {
+        let mut ids: Vec<String> = storage
+            .list_entries()
+            .unwrap()
+            .into_iter()
+            .map(|e| e.id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    #[test]
+    fn test_retention_max_entries() {
+        let (storage, _temp) = create_test_storage();
+        for age in 1..=5 {
+            save_aged_entry(&storage, &format!("entry-{age}"), b"content", age * DAY);
+        }
+
+        let summary = storage
+            .enforce_retention(3, Duration::from_secs(30 * DAY), u64::MAX)
+            .unwrap();
+
+        assert_eq!(summary.entries_removed, 2);
+        assert!(summary.bytes_reclaimed > 0);
+        assert_eq!(surviving_ids(&storage), ["entry-1", "entry-2", "entry-3"]);
+    }
+
+    #[test]
+    fn test_retention_max_age() {
+        let (storage, _temp) = create_test_storage();
+        for age in 1..=5 {
+            save_aged_entry(&storage, &format!("entry-{age}"), b"content", age * DAY);
+        }
+
+        let summary = storage
+            .enforce_retention(100, Duration::from_secs(2 * DAY + 60), u64::MAX)
+            .unwrap();
+
+        assert_eq!(summary.entries_removed, 3);
+        assert_eq!(surviving_ids(&storage), ["entry-1", "entry-2"]);
+    }
+
+    #[test]
+    fn test_retention_max_total_bytes() {
+        let (storage, _temp) = create_test_storage();
+        let content = vec![b'x'; 1000];
+        for age in 1..=4 {
+            save_aged_entry(&storage, &format!("entry-{age}"), &content, age * DAY);
+        }
+        let entry_size = storage.entry_disk_size("entry-1");
+        let total: u64 = (1..=4)
+            .map(|age| storage.entry_disk_size(&format!("entry-{age}")))
+            .sum();
+
+        // Room for two and a half entries: the two newest survive
+        let summary = storage
+            .enforce_retention(100, Duration::from_secs(30 * DAY), entry_size * 5 / 2)
+            .unwrap();
+
+        assert_eq!(summary.entries_removed, 2);
+        assert_eq!(surviving_ids(&storage), ["entry-1", "entry-2"]);
+        let remaining: u64 = ["entry-1", "entry-2"]
+            .iter()
+            .map(|id| storage.entry_disk_size(id))
+            .sum();
+        assert_eq!(summary.bytes_reclaimed, total - remaining);
+        assert!(storage.list_chunk_paths("entry-3").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_retention_keeps_session_lock() {
+        let (storage, _temp) = create_test_storage();
+        storage.create_session_lock().unwrap();
+        save_aged_entry(&storage, "entry-1", b"content", DAY);
+
+        let summary = storage
+            .enforce_retention(0, Duration::from_secs(30 * DAY), u64::MAX)
+            .unwrap();
+
+        assert_eq!(summary.entries_removed, 1);
+        assert!(storage.list_entries().unwrap().is_empty());
+        assert!(storage.read_session_lock().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_retention_within_limits_removes_nothing() {
+        let (storage, _temp) = create_test_storage();
+        for age in 1..=3 {
+            save_aged_entry(&storage, &format!("entry-{age}"), b"content", age * DAY);
+        }
+
+        let summary = storage
+            .enforce_retention(10, Duration::from_secs(30 * DAY), u64::MAX)
+            .unwrap();
+
+        assert_eq!(summary, RetentionSummary::default());
+        assert_eq!(surviving_ids(&storage).len(), 3);
+    }
+
     #[test]
     fn test_chunked_recovery_read_content() {
         let (storage, _temp) = create_test_storage();
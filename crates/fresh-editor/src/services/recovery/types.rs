@@ -18,6 +18,47 @@ use std::time::SystemTime;
 /// Maximum chunk size for chunked recovery (1 MB)
 pub const MAX_CHUNK_SIZE: usize = 1024 * 1024;
 
+/// Total chunk content size above which chunk files are compressed (64 KB)
+pub const COMPRESSION_THRESHOLD: usize = 64 * 1024;
+
+/// Compression applied to chunk files on disk
+///
+/// Recorded in the metadata so entries written before compression existed
+/// (which lack the field) still load as uncompressed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecoveryCompression {
+    /// Chunk files hold raw content
+    #[default]
+    None,
+    /// Chunk files are zstd frames
+    Zstd,
+}
+
+impl RecoveryCompression {
+    /// Pick the compression for chunks totalling `content_size` bytes
+    pub fn for_size(content_size: usize) -> Self {
+        if content_size > COMPRESSION_THRESHOLD {
+            Self::Zstd
+        } else {
+            Self::None
+        }
+    }
+}
+
+/// Encryption parameters of an encrypted recovery entry
+///
+/// Both fields are base64. The key check is the authentication tag of an
+/// empty message, which lets a passphrase be verified without touching the
+/// chunk files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecoveryEncryption {
+    /// Random nonce drawn for the save that wrote the chunk files
+    pub nonce: String,
+    /// Empty message sealed with the entry's key and nonce
+    pub key_check: String,
+}
+
 /// Metadata for a single chunk (stored in JSON, without binary content)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChunkMeta {
@@ -144,6 +185,14 @@ pub struct RecoveryMetadata {
     /// Original file size (0 for new buffers, needed for reconstruction)
     #[serde(default)]
     pub original_file_size: usize,
+
+    /// Compression of the chunk files (`content_size` is always uncompressed)
+    #[serde(default)]
+    pub compression: RecoveryCompression,
+
+    /// Set when the chunk files are encrypted (applied after compression)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<RecoveryEncryption>,
 }
 
 impl RecoveryMetadata {
@@ -176,6 +225,8 @@ impl RecoveryMetadata {
             format_version: Self::FORMAT_VERSION,
             chunk_count,
             original_file_size,
+            compression: RecoveryCompression::None,
+            encryption: None,
         }
     }
 
@@ -190,6 +241,11 @@ impl RecoveryMetadata {
         self.chunk_count = chunk_count;
     }
 
+    /// Whether the chunk files need a passphrase to read
+    pub fn is_encrypted(&self) -> bool {
+        self.encryption.is_some()
+    }
+
     /// Get a display name for this recovery entry
     pub fn display_name(&self) -> String {
         if let Some(ref path) = self.original_path {
@@ -214,10 +270,23 @@ impl RecoveryMetadata {
     }
 }
 
+/// Outcome of enforcing the recovery retention policy
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetentionSummary {
+    /// Number of recovery entries deleted
+    pub entries_removed: usize,
+    /// On-disk bytes freed by deleting them
+    pub bytes_reclaimed: u64,
+}
+
 /// Session information stored in the lock file
 ///
 /// This is used to detect crashes - if the lock file exists but
 /// the process is not running, we know the editor crashed.
+///
+/// A PID alone can't tell the editor apart from an unrelated process that
+/// was given the same PID later (typically after a reboot), so the lock also
+/// records the process start time and boot ID where the platform has them.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionInfo {
     /// Process ID of the editor
@@ -228,6 +297,15 @@ pub struct SessionInfo {
 
     /// Working directory
     pub working_dir: Option<PathBuf>,
+
+    /// Start time of the editor process, in platform-specific units
+    /// (None if unknown, e.g. lock files from older versions)
+    #[serde(default)]
+    pub process_start_time: Option<u64>,
+
+    /// Identifier of the boot the session ran in (None if unknown)
+    #[serde(default)]
+    pub boot_id: Option<String>,
 }
 
 impl SessionInfo {
@@ -237,17 +315,39 @@ impl SessionInfo {
             .duration_since(SystemTime::UNIX_EPOCH)
             .map(|d| d.as_secs())
             .unwrap_or(0);
+        let pid = std::process::id();
 
         Self {
-            pid: std::process::id(),
+            pid,
             started_at: now,
             working_dir: std::env::current_dir().ok(),
+            process_start_time: process_start_time(pid),
+            boot_id: boot_id(),
         }
     }
 
     /// Check if this session is still running
+    ///
+    /// The PID must be alive, and when the lock recorded a boot ID and
+    /// process start time, both must match the live process too. Anything
+    /// that can't be read on this platform is not compared.
     pub fn is_running(&self) -> bool {
-        is_process_running(self.pid)
+        if !is_process_running(self.pid) {
+            return false;
+        }
+        if let (Some(recorded), Some(current)) = (&self.boot_id, boot_id()) {
+            if *recorded != current {
+                return false;
+            }
+        }
+        if let (Some(recorded), Some(current)) =
+            (self.process_start_time, process_start_time(self.pid))
+        {
+            if recorded != current {
+                return false;
+            }
+        }
+        true
     }
 }
 
@@ -257,6 +357,21 @@ impl Default for SessionInfo {
     }
 }
 
+/// Content salvaged from a possibly damaged recovery entry
+#[derive(Debug, Clone)]
+pub struct SalvagedRecovery {
+    /// Metadata, if the metadata file could still be parsed
+    pub metadata: Option<RecoveryMetadata>,
+    /// The recovered buffer content (chunks already applied to the original)
+    pub content: Vec<u8>,
+    /// Whether anything was lost: `content` is then a best effort
+    pub partial: bool,
+    /// Chunks not fully applied, counting from the first unreadable one
+    pub chunks_skipped: usize,
+    /// What was wrong with the entry, for display to the user
+    pub problems: Vec<String>,
+}
+
 /// A recoverable buffer entry (in-memory representation)
 #[derive(Debug, Clone)]
 pub struct RecoveryEntry {
@@ -318,6 +433,15 @@ impl RecoveryEntry {
     }
 }
 
+/// How a buffer's content is written to a recovery entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecoveryFormat {
+    /// Only the modified regions, applied to the original file on recovery
+    Chunked,
+    /// The whole buffer content as a single chunk
+    Full,
+}
+
 /// Result of a recovery operation
 #[derive(Debug)]
 pub enum RecoveryResult {
@@ -381,6 +505,76 @@ fn is_process_running(_pid: u32) -> bool {
     false
 }
 
+/// Start time of a process, for telling a recycled PID from the original
+///
+/// Linux: clock ticks since boot (field 22 of `/proc/<pid>/stat`).
+#[cfg(target_os = "linux")]
+fn process_start_time(pid: u32) -> Option<u64> {
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // The command name is parenthesized and may itself contain spaces or
+    // parentheses, so count fields from the last ')' (field 2 ends there)
+    let after_comm = &stat[stat.rfind(')')? + 1..];
+    after_comm.split_whitespace().nth(19)?.parse().ok()
+}
+
+/// Start time of a process, for telling a recycled PID from the original
+///
+/// macOS: microseconds since the Unix epoch, from `proc_pidinfo`.
+#[cfg(target_os = "macos")]
+fn process_start_time(pid: u32) -> Option<u64> {
+    let mut info: libc::proc_bsdinfo = unsafe { std::mem::zeroed() };
+    let size = std::mem::size_of::<libc::proc_bsdinfo>() as libc::c_int;
+    let written = unsafe {
+        libc::proc_pidinfo(
+            pid as libc::c_int,
+            libc::PROC_PIDTBSDINFO,
+            0,
+            &mut info as *mut _ as *mut libc::c_void,
+            size,
+        )
+    };
+    if written != size {
+        return None;
+    }
+    Some(info.pbi_start_tvsec * 1_000_000 + info.pbi_start_tvusec)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn process_start_time(_pid: u32) -> Option<u64> {
+    None
+}
+
+/// Identifier of the current boot
+#[cfg(target_os = "linux")]
+fn boot_id() -> Option<String> {
+    std::fs::read_to_string("/proc/sys/kernel/random/boot_id")
+        .ok()
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+}
+
+/// Identifier of the current boot (the boot time, from `kern.boottime`)
+#[cfg(target_os = "macos")]
+fn boot_id() -> Option<String> {
+    let mut boot_time: libc::timeval = unsafe { std::mem::zeroed() };
+    let mut size = std::mem::size_of::<libc::timeval>();
+    let result = unsafe {
+        libc::sysctlbyname(
+            c"kern.boottime".as_ptr(),
+            &mut boot_time as *mut _ as *mut libc::c_void,
+            &mut size,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    (result == 0).then(|| format!("{}.{}", boot_time.tv_sec, boot_time.tv_usec))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn boot_id() -> Option<String> {
+    None
+}
+
 /// Generate a stable hash for a file path (used as recovery file ID)
 pub fn path_hash(path: &std::path::Path) -> String {
     use sha2::{Digest, Sha256};
@@ -496,6 +690,51 @@ mod tests {
         assert!(info.started_at > 0);
     }
 
+    #[test]
+    fn test_session_with_mismatched_start_time_is_not_running() {
+        let mut info = SessionInfo::new();
+        assert!(info.is_running());
+
+        // Same PID, but started at another time: a recycled PID
+        if let Some(start) = info.process_start_time {
+            info.process_start_time = Some(start + 1);
+            assert!(!info.is_running());
+        }
+    }
+
+    #[test]
+    fn test_session_from_another_boot_is_not_running() {
+        let mut info = SessionInfo::new();
+
+        if info.boot_id.is_some() {
+            info.boot_id = Some("some-earlier-boot".to_string());
+            assert!(!info.is_running());
+        }
+    }
+
+    #[test]
+    fn test_session_lock_without_identity_falls_back_to_pid() {
+        // Lock files written before start time and boot ID were recorded
+        let json = format!(
+            r#"{{"pid": {}, "started_at": 1, "working_dir": null}}"#,
+            std::process::id()
+        );
+        let info: SessionInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(info.process_start_time, None);
+        assert_eq!(info.boot_id, None);
+        #[cfg(any(unix, windows))]
+        assert!(info.is_running());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_linux_process_identity_is_available() {
+        let pid = std::process::id();
+        assert!(process_start_time(pid).is_some());
+        assert_eq!(process_start_time(pid), process_start_time(pid));
+        assert!(boot_id().is_some());
+    }
+
     #[test]
     fn test_is_process_running_self() {
         // Our own process should be running (on Unix and Windows)
@@ -0,0 +1,230 @@
+//! Background recovery writer
+//!
+//! Writing recovery files for a large buffer can take long enough to hitch
+//! the UI, so the editor hands save jobs to a worker thread instead. Jobs
+//! that pile up while the worker is busy are coalesced per buffer: only the
+//! latest content for an ID is written.
+
+use super::storage::RecoveryStorage;
+use super::types::RecoveryChunk;
+use std::io;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+
+/// One pending `RecoveryStorage::save_recovery` call
+#[derive(Debug, Clone)]
+pub struct SaveJob {
+    /// Recovery ID of the buffer
+    pub id: String,
+    /// The content chunks to save
+    pub chunks: Vec<RecoveryChunk>,
+    /// Path to the original file (None for new buffers)
+    pub original_path: Option<PathBuf>,
+    /// Display name for the buffer
+    pub buffer_name: Option<String>,
+    /// Number of lines in the buffer
+    pub line_count: Option<usize>,
+    /// Size of the original file (0 for new buffers)
+    pub original_file_size: usize,
+    /// Total size after applying all chunks
+    pub final_size: usize,
+}
+
+enum Message {
+    Save(SaveJob),
+    /// Write everything queued before this, then report the outcome
+    Flush(mpsc::Sender<io::Result<()>>),
+}
+
+/// Performs recovery saves on a worker thread
+///
+/// Dropping the writer finishes all queued saves before returning.
+#[derive(Debug)]
+pub struct RecoveryWriter {
+    sender: Option<mpsc::Sender<Message>>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl RecoveryWriter {
+    /// Start a worker that writes through `storage`
+    pub fn spawn(storage: RecoveryStorage) -> io::Result<Self> {
+        let (sender, receiver) = mpsc::channel();
+        let thread = thread::Builder::new()
+            .name("recovery-writer".to_string())
+            .spawn(move || run(&storage, &receiver))?;
+        Ok(Self {
+            sender: Some(sender),
+            thread: Some(thread),
+        })
+    }
+
+    /// Queue a save; it replaces any queued save of the same ID
+    pub fn enqueue(&self, job: SaveJob) -> io::Result<()> {
+        self.send(Message::Save(job))
+    }
+
+    /// Block until every save queued so far is on disk
+    ///
+    /// Returns the first error hit by a save since the previous flush.
+    pub fn flush(&self) -> io::Result<()> {
+        let (ack, done) = mpsc::channel();
+        self.send(Message::Flush(ack))?;
+        done.recv().map_err(|_| worker_gone())?
+    }
+
+    fn send(&self, message: Message) -> io::Result<()> {
+        self.sender
+            .as_ref()
+            .ok_or_else(worker_gone)?
+            .send(message)
+            .map_err(|_| worker_gone())
+    }
+}
+
+impl Drop for RecoveryWriter {
+    fn drop(&mut self) {
+        // Closing the channel lets the worker finish the queue and exit
+        drop(self.sender.take());
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                tracing::error!("Recovery writer thread panicked");
+            }
+        }
+    }
+}
+
+fn worker_gone() -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "recovery writer has stopped")
+}
+
+fn run(storage: &RecoveryStorage, receiver: &mpsc::Receiver<Message>) {
+    let mut failure = None;
+
+    while let Ok(first) = receiver.recv() {
+        // Take everything already queued so repeated saves of a buffer
+        // collapse into its latest content
+        let mut pending: Vec<SaveJob> = Vec::new();
+        for message in std::iter::once(first).chain(receiver.try_iter()) {
+            match message {
+                Message::Save(job) => match pending.iter_mut().find(|p| p.id == job.id) {
+                    Some(queued) => *queued = job,
+                    None => pending.push(job),
+                },
+                Message::Flush(ack) => {
+                    write_all(storage, pending.drain(..), &mut failure);
+                    // The flushing side may have given up waiting
+                    #[allow(clippy::let_underscore_must_use)]
+                    let _ = ack.send(failure.take().map_or(Ok(()), Err));
+                }
+            }
+        }
+        write_all(storage, pending.drain(..), &mut failure);
+    }
+}
+
+fn write_all(
+    storage: &RecoveryStorage,
+    jobs: impl Iterator<Item = SaveJob>,
+    failure: &mut Option<io::Error>,
+) {
+    for job in jobs {
+        let result = storage.save_recovery(
+            &job.id,
+            job.chunks,
+            job.original_path.as_deref(),
+            job.buffer_name.as_deref(),
+            job.line_count,
+            job.original_file_size,
+            job.final_size,
+        );
+        if let Err(e) = result {
+            tracing::warn!("Background recovery save of {} failed: {}", job.id, e);
+            failure.get_or_insert(e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn job(id: &str, content: &[u8]) -> SaveJob {
+        SaveJob {
+            id: id.to_string(),
+            chunks: vec![RecoveryChunk::new(0, 0, content.to_vec())],
+            original_path: None,
+            buffer_name: Some(id.to_string()),
+            line_count: None,
+            original_file_size: 0,
+            final_size: content.len(),
+        }
+    }
+
+    fn content_of(storage: &RecoveryStorage, id: &str) -> Vec<u8> {
+        storage.read_chunked_content(id).unwrap().unwrap().chunks[0]
+            .content
+            .clone()
+    }
+
+    #[test]
+    fn test_rapid_saves_land_latest_content() {
+        let temp = TempDir::new().unwrap();
+        let storage = RecoveryStorage::with_dir(temp.path().to_path_buf());
+        let writer = RecoveryWriter::spawn(storage.clone()).unwrap();
+
+        for i in 0..500 {
+            writer
+                .enqueue(job("buf", format!("version {i}").as_bytes()))
+                .unwrap();
+        }
+        writer.flush().unwrap();
+
+        assert_eq!(content_of(&storage, "buf"), b"version 499");
+        assert_eq!(storage.list_entries().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_flush_is_synchronous() {
+        let temp = TempDir::new().unwrap();
+        let storage = RecoveryStorage::with_dir(temp.path().to_path_buf());
+        let writer = RecoveryWriter::spawn(storage.clone()).unwrap();
+
+        for round in 0..20 {
+            let content = format!("round {round}");
+            writer.enqueue(job("a", content.as_bytes())).unwrap();
+            writer.enqueue(job("b", content.as_bytes())).unwrap();
+            writer.flush().unwrap();
+            // Visible as soon as flush returns, with no waiting
+            assert_eq!(content_of(&storage, "a"), content.as_bytes());
+            assert_eq!(content_of(&storage, "b"), content.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_drop_finishes_queued_saves() {
+        let temp = TempDir::new().unwrap();
+        let storage = RecoveryStorage::with_dir(temp.path().to_path_buf());
+        let writer = RecoveryWriter::spawn(storage.clone()).unwrap();
+
+        writer.enqueue(job("last", b"before shutdown")).unwrap();
+        drop(writer);
+
+        assert_eq!(content_of(&storage, "last"), b"before shutdown");
+    }
+
+    #[test]
+    fn test_flush_reports_failed_save() {
+        let temp = TempDir::new().unwrap();
+        // A file where the recovery directory should be
+        let blocked = temp.path().join("not-a-dir");
+        std::fs::write(&blocked, b"").unwrap();
+        let writer = RecoveryWriter::spawn(RecoveryStorage::with_dir(blocked)).unwrap();
+
+        writer.enqueue(job("x", b"data")).unwrap();
+        assert!(writer.flush().is_err());
+        // The error is reported once
+        assert!(writer.flush().is_ok());
+    }
+}
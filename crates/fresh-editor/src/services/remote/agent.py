@@ -9,9 +9,21 @@ import shutil
 import subprocess
 import threading
 import select
+import errno
+import gzip
+import zlib
+import re
+import ctypes
+import struct
+import tempfile
+import time
 
 CHUNK = 65536
+# Paths per streamed find batch
+FIND_BATCH = 200
 VERSION = 1
+# Optional protocol features, advertised in the ready message
+FEATURES = ["gzip", "stat_many", "find", "watch", "count_lines"]
 
 # Active background processes: id -> Popen
 procs = {}
@@ -19,13 +31,17 @@ procs = {}
 cancelled = set()
 # Lock for thread-safe access
 lock = threading.Lock()
+# Serializes writes from background threads (exec, watch) to stdout
+send_lock = threading.Lock()
 
 
 def send(id, **kw):
     """Send a JSON message to stdout."""
     msg = {"id": id, **kw}
-    sys.stdout.write(json.dumps(msg, separators=(",", ":")) + "\n")
-    sys.stdout.flush()
+    line = json.dumps(msg, separators=(",", ":")) + "\n"
+    with send_lock:
+        sys.stdout.write(line)
+        sys.stdout.flush()
 
 
 def b64(data):
@@ -48,35 +64,106 @@ def validate_path(p):
     return os.path.realpath(expanded)
 
 
+def validate_path_nofollow(p):
+    """Like validate_path, but leaves a trailing symlink unresolved.
+
+    Used by operations that act on the link itself rather than its target.
+    """
+    if not p:
+        raise ValueError("empty path")
+    expanded = os.path.expanduser(p)
+    if not os.path.isabs(expanded):
+        expanded = os.path.abspath(expanded)
+    parent, name = os.path.split(expanded.rstrip("/") or "/")
+    if not name:
+        return parent
+    return os.path.join(os.path.realpath(parent), name)
+
+
 # === File Operations ===
 
 
 def cmd_read(id, p):
-    """Read file contents, streaming in chunks for large files."""
+    """Read file contents, streaming in chunks for large files.
+
+    With "compress", each chunk is gzipped before encoding and marked "z".
+    Every chunk carries its uncompressed length "n", file offset "off" and the
+    CRC32 "crc" of its uncompressed bytes, so the client can verify it and
+    re-request just that range.
+
+    The file is opened before returning, so the read observes the file as of
+    this request even if later requests modify it; the chunks are then
+    streamed from a background thread so other requests (including other
+    reads) are not blocked behind a large file.
+    """
     path = validate_path(p["path"])
     off = p.get("off", 0)
     length = p.get("len")
+    compress = p.get("compress", False)
+
+    f = open(path, "rb")
+
+    def stream():
+        try:
+            with f:
+                if off:
+                    f.seek(off)
+                total = 0
+                while True:
+                    to_read = min(CHUNK, length - total) if length else CHUNK
+                    chunk = f.read(to_read)
+                    if not chunk:
+                        break
+                    meta = {"n": len(chunk), "off": off + total, "crc": zlib.crc32(chunk)}
+                    total += len(chunk)
+                    if compress:
+                        send(id, d={"data": b64(gzip.compress(chunk, 1)), "z": True, **meta})
+                    else:
+                        send(id, d={"data": b64(chunk), **meta})
+                    if length and total >= length:
+                        break
+            send(id, r={"size": total})
+        except Exception as e:
+            send(id, e=error_message(e))
+
+    threading.Thread(target=stream, daemon=True).start()
 
-    with open(path, "rb") as f:
-        if off:
-            f.seek(off)
-        total = 0
-        while True:
-            to_read = min(CHUNK, length - total) if length else CHUNK
-            chunk = f.read(to_read)
-            if not chunk:
-                break
-            total += len(chunk)
-            send(id, d={"data": b64(chunk)})
-            if length and total >= length:
-                break
-    send(id, r={"size": total})
+
+def cmd_count_lines(id, p):
+    """Count newlines in a file without sending its contents.
+
+    Like read, the file is scanned from a background thread so a large file
+    doesn't block other requests.
+    """
+    path = validate_path(p["path"])
+    f = open(path, "rb")
+
+    def count():
+        try:
+            newlines = 0
+            last = b""
+            with f:
+                while True:
+                    chunk = f.read(CHUNK)
+                    if not chunk:
+                        break
+                    newlines += chunk.count(b"\n")
+                    last = chunk[-1:]
+            send(id, r={"newlines": newlines, "trailing_newline": last == b"\n"})
+        except Exception as e:
+            send(id, e=error_message(e))
+
+    threading.Thread(target=count, daemon=True).start()
 
 
 def cmd_write(id, p):
     """Write file contents atomically."""
     path = validate_path(p["path"])
     data = unb64(p["data"])
+    if p.get("compress"):
+        data = gzip.decompress(data)
+    if "crc" in p and zlib.crc32(data) != p["crc"]:
+        raise ValueError(f"checksum mismatch: {len(data)} bytes for {path}")
 
     # Atomic write: write to temp, then rename
     tmp = f"{path}.fresh-{os.getpid()}"
@@ -105,63 +192,217 @@ def cmd_write(id, p):
     send(id, r={"size": len(data)})
 
 
+SUDO_REJECTED_MARKERS = (
+    "incorrect password",
+    "Sorry, try again",
+    "no password was provided",
+)
+
+
 def cmd_sudo_write(id, p):
     """Write file contents using sudo (for root-owned files).
 
     Uses sudo tee to write the file. Preserves original permissions and ownership.
+
+    With "password", sudo reads it from stdin (sudo -S) and the contents are
+    copied in place from a private temporary file, so the password never
+    mixes with the file data and can't end up in the file when sudo doesn't
+    ask for it. The password is never logged, and its buffer is wiped once
+    the sudo processes are done.
     """
     path = validate_path(p["path"])
     data = unb64(p["data"])
+    password = p.pop("password", None)
 
     # Get original metadata to preserve permissions
     mode = p.get("mode")
     uid = p.get("uid")
     gid = p.get("gid")
 
-    # Use sudo tee to write the file
-    proc = subprocess.Popen(
-        ["sudo", "tee", path],
-        stdin=subprocess.PIPE,
-        stdout=subprocess.DEVNULL,
-        stderr=subprocess.PIPE,
-    )
-    _, stderr = proc.communicate(data)
+    sudo = ["sudo"]
+    secret = None
+    data_dir = None
+    command = ["tee", path]
+    stdin = data
+    if password is not None:
+        secret = bytearray(password + "\n", "utf-8")
+        password = None
+        sudo = ["sudo", "-S", "-p", ""]
+        data_dir = tempfile.mkdtemp(prefix="fresh-sudo-")
+        source = os.path.join(data_dir, "data")
+        with open(source, "wb") as f:
+            f.write(data)
+        command = ["cp", "--", source, path]
+        stdin = secret
 
-    if proc.returncode != 0:
-        raise RuntimeError(f"sudo tee failed: {stderr.decode().strip()}")
+    try:
+        proc = subprocess.Popen(
+            sudo + command,
+            stdin=subprocess.PIPE,
+            stdout=subprocess.DEVNULL,
+            stderr=subprocess.PIPE,
+        )
+        _, stderr = proc.communicate(stdin)
+
+        if proc.returncode != 0:
+            message = stderr.decode().strip()
+            if secret is not None and any(m in message for m in SUDO_REJECTED_MARKERS):
+                raise RuntimeError(f"sudo password rejected: {message}")
+            raise RuntimeError(f"sudo {command[0]} failed: {message}")
 
-    # Restore permissions and ownership if provided
-    if mode is not None:
-        subprocess.run(["sudo", "chmod", f"{mode:o}", path], check=True,
-                       capture_output=True)
-    if uid is not None and gid is not None:
-        subprocess.run(["sudo", "chown", f"{uid}:{gid}", path], check=True,
-                       capture_output=True)
+        # Restore permissions and ownership if provided
+        if mode is not None:
+            subprocess.run(sudo + ["chmod", f"{mode:o}", path], check=True,
+                           capture_output=True, input=secret)
+        if uid is not None and gid is not None:
+            subprocess.run(sudo + ["chown", f"{uid}:{gid}", path], check=True,
+                           capture_output=True, input=secret)
+    finally:
+        if secret is not None:
+            secret[:] = bytes(len(secret))
+        if data_dir is not None:
+            shutil.rmtree(data_dir, ignore_errors=True)
 
     send(id, r={"size": len(data)})
 
 
+def stat_entry(p, follow):
+    """Build the metadata dict returned by stat and stat_many.
+
+    The trailing component is left unresolved so "link" reflects the path
+    itself; with follow, the remaining fields describe the link target.
+    """
+    path = validate_path_nofollow(p)
+    lst = os.lstat(path)
+    st = os.stat(path) if follow else lst
+    is_link = stat.S_ISLNK(lst.st_mode)
+
+    entry = {
+        "size": st.st_size,
+        "mtime": int(st.st_mtime),
+        "mode": st.st_mode,
+        "uid": st.st_uid,
+        "gid": st.st_gid,
+        "dir": stat.S_ISDIR(st.st_mode),
+        "file": stat.S_ISREG(st.st_mode),
+        "link": is_link,
+    }
+    if is_link:
+        entry["target"] = os.readlink(path)
+    return entry
+
+
 def cmd_stat(id, p):
     """Get file/directory metadata."""
-    path = validate_path(p["path"])
     follow = p.get("link", True)
+    send(id, r=stat_entry(p["path"], follow))
 
-    st = os.stat(path, follow_symlinks=follow)
-    is_link = stat.S_ISLNK(os.lstat(path).st_mode) if follow else False
-
-    send(
-        id,
-        r={
-            "size": st.st_size,
-            "mtime": int(st.st_mtime),
-            "mode": st.st_mode,
-            "uid": st.st_uid,
-            "gid": st.st_gid,
-            "dir": stat.S_ISDIR(st.st_mode),
-            "file": stat.S_ISREG(st.st_mode),
-            "link": is_link,
-        },
-    )
+
+def cmd_stat_many(id, p):
+    """Get metadata for many paths; each entry is metadata or an error."""
+    follow = p.get("link", True)
+    results = []
+
+    for path in p["paths"]:
+        try:
+            results.append(stat_entry(path, follow))
+        except Exception as e:
+            results.append({"error": error_message(e)})
+
+    send(id, r={"results": results})
+
+
+def glob_regex(pattern):
+    """Translate a glob ("**" crosses directories, "*" and "?" do not) to a regex."""
+    out = []
+    i = 0
+    while i < len(pattern):
+        if pattern.startswith("**/", i):
+            out.append("(?:.*/)?")
+            i += 3
+        elif pattern.startswith("**", i):
+            out.append(".*")
+            i += 2
+        elif pattern[i] == "*":
+            out.append("[^/]*")
+            i += 1
+        elif pattern[i] == "?":
+            out.append("[^/]")
+            i += 1
+        else:
+            out.append(re.escape(pattern[i]))
+            i += 1
+    return re.compile("".join(out) + r"\Z")
+
+
+def cmd_find(id, p):
+    """Walk a tree and stream relative paths of files matching a glob.
+
+    Symlinked directories are not followed (no loops) and .git is skipped.
+    With "git", the paths are the files git lists for the work tree instead:
+    tracked files plus untracked ones that aren't ignored, with ignored
+    directories never entered; this fails outside a work tree. The walk runs
+    on a background thread so other requests aren't blocked behind a large
+    tree, and stops once cancelled.
+    """
+    root = validate_path(p["root"])
+    regex = glob_regex(p["pattern"])
+    max_results = p.get("max", 0)
+    max_depth = p.get("depth", 0)
+
+    def candidates():
+        if p.get("git"):
+            out = subprocess.run(
+                ["git", "ls-files", "-z", "--cached", "--others", "--exclude-standard"],
+                cwd=root,
+                stdout=subprocess.PIPE,
+                stderr=subprocess.DEVNULL,
+                check=True,
+            ).stdout
+            # Untracked nested repositories are listed as "dir/"
+            paths = (path for path in out.split(b"\0") if path and not path.endswith(b"/"))
+            yield from (os.fsdecode(path) for path in paths)
+            return
+        for dirpath, dirnames, filenames in os.walk(root):
+            rel_dir = os.path.relpath(dirpath, root)
+            rel_dir = "" if rel_dir == "." else rel_dir.replace(os.sep, "/") + "/"
+            dirnames[:] = sorted(d for d in dirnames if d != ".git")
+            if max_depth and rel_dir.count("/") >= max_depth:
+                dirnames[:] = []
+            yield from (rel_dir + name for name in sorted(filenames))
+
+    def walk():
+        try:
+            batch = []
+            found = 0
+            truncated = False
+            for rel in candidates():
+                if id in cancelled:
+                    send(id, e="cancelled")
+                    return
+                if not regex.match(rel):
+                    continue
+                batch.append(rel)
+                found += 1
+                if len(batch) >= FIND_BATCH:
+                    send(id, d={"paths": batch})
+                    batch = []
+                if max_results and found >= max_results:
+                    truncated = True
+                    break
+
+            if batch:
+                send(id, d={"paths": batch})
+            send(id, r={"count": found, "truncated": truncated})
+        except subprocess.CalledProcessError:
+            send(id, e=f"not a git work tree: {root}")
+        except Exception as e:
+            send(id, e=error_message(e))
+        finally:
+            with lock:
+                cancelled.discard(id)
+
+    threading.Thread(target=walk, daemon=True).start()
 
 
 def cmd_ls(id, p):
@@ -203,8 +444,15 @@ def cmd_ls(id, p):
 
 
 def cmd_rm(id, p):
-    """Remove a file."""
-    os.unlink(validate_path(p["path"]))
+    """Remove a file.
+
+    Directories, even empty ones, fail with EISDIR. A symlink is removed
+    as a link and never followed.
+    """
+    path = validate_path_nofollow(p["path"])
+    if stat.S_ISDIR(os.lstat(path).st_mode):
+        raise IsADirectoryError(errno.EISDIR, os.strerror(errno.EISDIR), path)
+    os.unlink(path)
     send(id, r={})
 
 
@@ -214,6 +462,25 @@ def cmd_rmdir(id, p):
     send(id, r={})
 
 
+def cmd_remove(id, p):
+    """Remove a file or directory.
+
+    Directories are only removed recursively when "recursive" is set;
+    otherwise a non-empty directory fails with ENOTEMPTY. Symlinks are
+    removed as links and never followed.
+    """
+    path = validate_path_nofollow(p["path"])
+    st = os.lstat(path)
+    if stat.S_ISDIR(st.st_mode):
+        if p.get("recursive"):
+            shutil.rmtree(path)
+        else:
+            os.rmdir(path)
+    else:
+        os.unlink(path)
+    send(id, r={})
+
+
 def cmd_mkdir(id, p):
     """Create a directory."""
     path = validate_path(p["path"])
@@ -234,7 +501,7 @@ def cmd_mv(id, p):
 
 
 def cmd_cp(id, p):
-    """Copy a file."""
+    """Copy a file on this host, preserving mode and timestamps."""
     dst = validate_path(p["to"])
     shutil.copy2(validate_path(p["from"]), dst)
     send(id, r={"size": os.path.getsize(dst)})
@@ -245,6 +512,11 @@ def cmd_realpath(id, p):
     send(id, r={"path": validate_path(p["path"])})
 
 
+def cmd_readlink(id, p):
+    """Read the target of a symlink without resolving it."""
+    send(id, r={"target": os.readlink(validate_path_nofollow(p["path"]))})
+
+
 def cmd_chmod(id, p):
     """Change file permissions."""
     os.chmod(validate_path(p["path"]), p["mode"])
@@ -332,14 +604,204 @@ def cmd_exists(id, p):
         send(id, r={"exists": False})
 
 
+def cmd_statfs(id, p):
+    """Report free and total space on the filesystem holding path.
+
+    Also reports whether that filesystem is mounted read-only, and whether
+    the agent's user may write path itself when it exists.
+    """
+    target = validate_path(p["path"])
+    path = target
+    if not os.path.isdir(path):
+        path = os.path.dirname(path)
+    st = os.statvfs(path)
+    send(id, r={
+        "avail": st.f_bavail * st.f_frsize,
+        "total": st.f_blocks * st.f_frsize,
+        "readonly": bool(st.f_flag & os.ST_RDONLY),
+        "writable": not os.path.exists(target) or os.access(target, os.W_OK),
+    })
+
+
 def cmd_info(id, p):
     """Get system info (home directory, cwd, etc.)."""
     send(id, r={
         "home": os.path.expanduser("~"),
         "cwd": os.getcwd(),
+        "pid": os.getpid(),
     })
 
 
+def cmd_hello(id, p):
+    """Handshake: report protocol version and supported optional features."""
+    send(id, r={"v": VERSION, "features": FEATURES})
+
+
+def cmd_ping(id, p):
+    """Answer a keepalive ping."""
+    send(id, r={"pong": True})
+
+
+# === Watching ===
+
+IN_MODIFY = 0x002
+IN_ATTRIB = 0x004
+IN_CLOSE_WRITE = 0x008
+IN_MOVED_FROM = 0x040
+IN_MOVED_TO = 0x080
+IN_CREATE = 0x100
+IN_DELETE = 0x200
+IN_DELETE_SELF = 0x400
+IN_MOVE_SELF = 0x800
+IN_NONBLOCK = 0o4000
+IN_CLOEXEC = 0o2000000
+# Seconds between stat snapshots when inotify is unavailable
+POLL_INTERVAL = 0.5
+
+
+def inotify_watch(path):
+    """Return an inotify fd watching path's directory, or None if unavailable."""
+    try:
+        libc = ctypes.CDLL(None, use_errno=True)
+        fd = libc.inotify_init1(IN_NONBLOCK | IN_CLOEXEC)
+    except (OSError, AttributeError):
+        return None
+    if fd < 0:
+        return None
+    # Watch the directory so atomic saves (write temp + rename) are seen
+    target = path if os.path.isdir(path) else os.path.dirname(path)
+    mask = (
+        IN_MODIFY | IN_ATTRIB | IN_CLOSE_WRITE | IN_MOVED_FROM | IN_MOVED_TO
+        | IN_CREATE | IN_DELETE | IN_DELETE_SELF | IN_MOVE_SELF
+    )
+    if libc.inotify_add_watch(fd, target.encode(), mask) < 0:
+        os.close(fd)
+        return None
+    return fd
+
+
+def inotify_events(fd, path):
+    """Drain pending inotify events as (kind, path) pairs."""
+    watching_dir = os.path.isdir(path)
+    base = os.path.dirname(path) if not watching_dir else path
+    name = os.path.basename(path)
+    events = []
+    try:
+        buf = os.read(fd, 65536)
+    except BlockingIOError:
+        return events
+    off = 0
+    while off + 16 <= len(buf):
+        _wd, mask, _cookie, length = struct.unpack_from("iIII", buf, off)
+        entry = buf[off + 16:off + 16 + length].rstrip(b"\0").decode(errors="replace")
+        off += 16 + length
+        if not entry:
+            if mask & (IN_DELETE_SELF | IN_MOVE_SELF):
+                kind = "removed" if mask & IN_DELETE_SELF else "renamed"
+                events.append((kind, base))
+            continue
+        if not watching_dir and entry != name:
+            continue
+        if mask & IN_DELETE:
+            kind = "removed"
+        elif mask & IN_MOVED_FROM:
+            kind = "renamed"
+        elif mask & IN_CREATE:
+            kind = "created"
+        else:
+            kind = "modified"
+        events.append((kind, os.path.join(base, entry)))
+    return events
+
+
+def snapshot(path):
+    """Stat path (or its entries, for a directory) for polling comparisons."""
+    result = {}
+    try:
+        if os.path.isdir(path):
+            for entry in os.scandir(path):
+                try:
+                    st = entry.stat(follow_symlinks=False)
+                    result[entry.path] = (st.st_mtime_ns, st.st_size, st.st_ino)
+                except OSError:
+                    pass
+        else:
+            st = os.stat(path)
+            result[path] = (st.st_mtime_ns, st.st_size, st.st_ino)
+    except OSError:
+        pass
+    return result
+
+
+def snapshot_events(old, new):
+    """Diff two snapshots into (kind, path) pairs."""
+    events = []
+    for p, sig in new.items():
+        if p not in old:
+            events.append(("created", p))
+        elif old[p] != sig:
+            events.append(("modified", p))
+    for p in old:
+        if p not in new:
+            events.append(("removed", p))
+    return events
+
+
+def cmd_watch(id, p):
+    """Stream change events for a file or directory until cancelled.
+
+    Uses inotify where available and falls back to stat polling. An empty
+    batch is sent once the watch is in place. Events are debounced: after
+    the first change, further changes within "debounce" ms are coalesced
+    (last kind per path wins) into one batch.
+    """
+    path = validate_path(p["path"])
+    debounce = p.get("debounce", 100) / 1000.0
+    if not os.path.exists(path):
+        raise FileNotFoundError(path)
+
+    # Set up before acknowledging so no change after the ack is missed
+    fd = None if p.get("poll") else inotify_watch(path)
+    state = None if fd is not None else snapshot(path)
+    send(id, d={"events": []})
+
+    def run():
+        def collect():
+            nonlocal state
+            if fd is not None:
+                readable, _, _ = select.select([fd], [], [], 0.1)
+                return inotify_events(fd, path) if readable else []
+            time.sleep(POLL_INTERVAL)
+            new = snapshot(path)
+            events = snapshot_events(state, new)
+            state = new
+            return events
+
+        try:
+            while id not in cancelled:
+                events = collect()
+                if not events:
+                    continue
+                deadline = time.monotonic() + debounce
+                while time.monotonic() < deadline and id not in cancelled:
+                    events.extend(collect())
+                merged = {}
+                for kind, changed in events:
+                    merged.pop(changed, None)
+                    merged[changed] = kind
+                send(id, d={"events": [{"kind": k, "path": c} for c, k in merged.items()]})
+            send(id, e="cancelled")
+        except Exception as e:
+            send(id, e=str(e))
+        finally:
+            if fd is not None:
+                os.close(fd)
+            with lock:
+                cancelled.discard(id)
+
+    threading.Thread(target=run, daemon=True).start()
+
+
 # === Process Operations ===
 
 
@@ -445,25 +907,51 @@ METHODS = {
     "write": cmd_write,
     "sudo_write": cmd_sudo_write,
     "stat": cmd_stat,
+    "stat_many": cmd_stat_many,
+    "statfs": cmd_statfs,
     "ls": cmd_ls,
+    "find": cmd_find,
+    "count_lines": cmd_count_lines,
     "rm": cmd_rm,
     "rmdir": cmd_rmdir,
+    "remove": cmd_remove,
     "mkdir": cmd_mkdir,
     "mv": cmd_mv,
     "cp": cmd_cp,
     "realpath": cmd_realpath,
+    "readlink": cmd_readlink,
     "chmod": cmd_chmod,
     "append": cmd_append,
     "truncate": cmd_truncate,
     "patch": cmd_patch,
     "exists": cmd_exists,
     "info": cmd_info,
+    "ping": cmd_ping,
+    "hello": cmd_hello,
+    "watch": cmd_watch,
     "exec": cmd_exec,
     "kill": cmd_kill,
     "cancel": cmd_cancel,
 }
 
 
+def error_message(e):
+    """Format an exception with the prefix the client maps to an error kind."""
+    if isinstance(e, PermissionError):
+        return f"permission denied: {e}"
+    if isinstance(e, FileNotFoundError):
+        return f"not found: {e}"
+    if isinstance(e, IsADirectoryError):
+        return f"is a directory: {e}"
+    if isinstance(e, NotADirectoryError):
+        return f"not a directory: {e}"
+    if isinstance(e, OSError):
+        if e.errno == errno.ENOTEMPTY:
+            return f"directory not empty: {e}"
+        return f"os error: {e}"
+    return str(e)
+
+
 def handle_request(line):
     """Parse and handle a single request."""
     try:
@@ -482,24 +970,14 @@ def handle_request(line):
 
     try:
         METHODS[method](id, params)
-    except PermissionError as e:
-        send(id, e=f"permission denied: {e}")
-    except FileNotFoundError as e:
-        send(id, e=f"not found: {e}")
-    except IsADirectoryError as e:
-        send(id, e=f"is a directory: {e}")
-    except NotADirectoryError as e:
-        send(id, e=f"not a directory: {e}")
-    except OSError as e:
-        send(id, e=f"os error: {e}")
     except Exception as e:
-        send(id, e=str(e))
+        send(id, e=error_message(e))
 
 
 def main():
     """Main entry point."""
     # Send ready message
-    send(0, ok=True, v=VERSION)
+    send(0, ok=True, v=VERSION, caps=FEATURES)
 
     # Process requests from stdin
     for line in sys.stdin:
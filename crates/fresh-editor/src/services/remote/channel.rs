@@ -2,14 +2,20 @@
 //!
 //! Handles request/response multiplexing over SSH stdin/stdout.
 
-use crate::services::remote::protocol::{AgentRequest, AgentResponse};
+use crate::services::remote::connection::ReconnectPolicy;
+use crate::services::remote::protocol::{
+    hello_params, AgentRequest, AgentResponse, Capabilities, FEATURE_GZIP, PROTOCOL_VERSION,
+};
 use std::collections::HashMap;
+use std::future::Future;
 use std::io;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
 use tokio::sync::{mpsc, oneshot};
-use tracing::warn;
+use tracing::{info, warn};
+use zeroize::Zeroizing;
 
 /// Default capacity for the per-request streaming data channel.
 const DEFAULT_DATA_CHANNEL_CAPACITY: usize = 64;
@@ -40,6 +46,67 @@ pub enum ChannelError {
 
     #[error("Remote error: {0}")]
     Remote(String),
+
+    #[error("Connection lost")]
+    ConnectionLost,
+}
+
+/// Methods that are safe to replay after a reconnect because re-running
+/// them cannot change remote state.
+const IDEMPOTENT_METHODS: &[&str] = &[
+    "read",
+    "stat",
+    "stat_many",
+    "statfs",
+    "count_lines",
+    "ls",
+    "exists",
+    "realpath",
+    "readlink",
+    "info",
+];
+
+/// Reader/writer pair and ready message of a freshly started agent
+pub struct AgentTransport {
+    pub reader: tokio::io::BufReader<tokio::process::ChildStdout>,
+    pub writer: tokio::process::ChildStdin,
+    pub ready: AgentResponse,
+}
+
+/// Future returned by a [`Reconnector`]
+pub type ReconnectFuture = Pin<Box<dyn Future<Output = io::Result<AgentTransport>> + Send>>;
+
+/// Callback that starts a replacement agent after the connection drops
+pub type Reconnector = Box<dyn Fn() -> ReconnectFuture + Send + Sync>;
+
+type PendingMap = Arc<Mutex<HashMap<u64, PendingRequest>>>;
+
+/// Request line queued for the write task
+enum OutgoingLine {
+    Plain(String),
+    /// Holds a secret; the buffer is wiped once written
+    Secret(Zeroizing<String>),
+}
+
+impl OutgoingLine {
+    fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::Plain(line) => line.as_bytes(),
+            Self::Secret(line) => line.as_bytes(),
+        }
+    }
+}
+
+/// State tied to one agent process; replaced wholesale on reconnect so that
+/// tasks belonging to a dead agent cannot affect the new one.
+#[derive(Clone)]
+struct Connection {
+    /// Sender to the write task
+    write_tx: mpsc::Sender<OutgoingLine>,
+    /// Pending requests awaiting responses
+    pending: PendingMap,
+    /// Whether this connection is still alive
+    connected: Arc<AtomicBool>,
 }
 
 /// Pending request state
@@ -52,18 +119,29 @@ struct PendingRequest {
 
 /// Communication channel with the remote agent
 pub struct AgentChannel {
-    /// Sender to the write task
-    write_tx: mpsc::Sender<String>,
-    /// Pending requests awaiting responses
-    pending: Arc<Mutex<HashMap<u64, PendingRequest>>>,
+    /// Current agent connection
+    conn: Mutex<Connection>,
     /// Next request ID
     next_id: AtomicU64,
-    /// Whether the channel is connected
-    connected: Arc<std::sync::atomic::AtomicBool>,
     /// Runtime handle for blocking operations
     runtime_handle: tokio::runtime::Handle,
     /// Capacity for per-request streaming data channels
     data_channel_capacity: usize,
+    /// Capabilities negotiated with the agent
+    capabilities: Mutex<Capabilities>,
+    /// Whether the caller opted in to compressed read/write payloads
+    compression_requested: AtomicBool,
+    /// Total protocol bytes written to the agent
+    bytes_sent: Arc<AtomicU64>,
+    /// Total protocol bytes read from the agent
+    bytes_received: Arc<AtomicU64>,
+    /// How to restart the agent after the connection drops, if at all
+    reconnector: Option<(ReconnectPolicy, Reconnector)>,
+    /// Serializes reconnect attempts from concurrent requests
+    reconnect_lock: tokio::sync::Mutex<()>,
+    /// Test hook: number of upcoming read chunks to corrupt after decoding
+    #[cfg(test)]
+    test_corrupt_chunks: AtomicU64,
 }
 
 impl AgentChannel {
@@ -82,27 +160,68 @@ impl AgentChannel {
     /// Lower capacity makes channel overflow more likely if `try_send` is used,
     /// which is useful for stress-testing backpressure handling.
     pub fn with_capacity(
-        mut reader: tokio::io::BufReader<tokio::process::ChildStdout>,
-        mut writer: tokio::process::ChildStdin,
+        reader: tokio::io::BufReader<tokio::process::ChildStdout>,
+        writer: tokio::process::ChildStdin,
         data_channel_capacity: usize,
     ) -> Self {
-        let pending: Arc<Mutex<HashMap<u64, PendingRequest>>> =
-            Arc::new(Mutex::new(HashMap::new()));
-        let connected = Arc::new(std::sync::atomic::AtomicBool::new(true));
         // Capture the runtime handle for later use in blocking operations
         let runtime_handle = tokio::runtime::Handle::current();
 
+        let bytes_sent = Arc::new(AtomicU64::new(0));
+        let bytes_received = Arc::new(AtomicU64::new(0));
+        let conn = Self::start_io(reader, writer, &bytes_sent, &bytes_received);
+
+        Self {
+            conn: Mutex::new(conn),
+            next_id: AtomicU64::new(1),
+            runtime_handle,
+            data_channel_capacity,
+            capabilities: Mutex::new(Capabilities::default()),
+            compression_requested: AtomicBool::new(false),
+            bytes_sent,
+            bytes_received,
+            reconnector: None,
+            reconnect_lock: tokio::sync::Mutex::new(()),
+            #[cfg(test)]
+            test_corrupt_chunks: AtomicU64::new(0),
+        }
+    }
+
+    /// Enable automatic reconnection.
+    ///
+    /// When the agent goes away, the next request calls `reconnector` up to
+    /// `policy.max_attempts` times with exponential backoff. Idempotent
+    /// requests that were in flight are replayed; others fail with
+    /// [`ChannelError::ConnectionLost`].
+    pub fn with_reconnector(mut self, policy: ReconnectPolicy, reconnector: Reconnector) -> Self {
+        self.reconnector = Some((policy, reconnector));
+        self
+    }
+
+    /// Spawn the read and write tasks for one agent process
+    fn start_io(
+        mut reader: tokio::io::BufReader<tokio::process::ChildStdout>,
+        mut writer: tokio::process::ChildStdin,
+        bytes_sent: &Arc<AtomicU64>,
+        bytes_received: &Arc<AtomicU64>,
+    ) -> Connection {
+        let pending: PendingMap = Arc::new(Mutex::new(HashMap::new()));
+        let connected = Arc::new(AtomicBool::new(true));
+
         // Channel for outgoing requests
-        let (write_tx, mut write_rx) = mpsc::channel::<String>(64);
+        let (write_tx, mut write_rx) = mpsc::channel::<OutgoingLine>(64);
 
         // Spawn write task
         let connected_write = connected.clone();
+        let bytes_sent_write = bytes_sent.clone();
         tokio::spawn(async move {
             while let Some(msg) = write_rx.recv().await {
-                if writer.write_all(msg.as_bytes()).await.is_err() {
+                let bytes = msg.as_bytes();
+                if writer.write_all(bytes).await.is_err() {
                     connected_write.store(false, Ordering::SeqCst);
                     break;
                 }
+                bytes_sent_write.fetch_add(bytes.len() as u64, Ordering::Relaxed);
                 if writer.flush().await.is_err() {
                     connected_write.store(false, Ordering::SeqCst);
                     break;
@@ -113,6 +232,7 @@ impl AgentChannel {
         // Spawn read task
         let pending_read = pending.clone();
         let connected_read = connected.clone();
+        let bytes_received_read = bytes_received.clone();
         tokio::spawn(async move {
             let mut line = String::new();
             loop {
@@ -123,7 +243,8 @@ impl AgentChannel {
                         connected_read.store(false, Ordering::SeqCst);
                         break;
                     }
-                    Ok(_) => {
+                    Ok(n) => {
+                        bytes_received_read.fetch_add(n as u64, Ordering::Relaxed);
                         if let Ok(resp) = serde_json::from_str::<AgentResponse>(&line) {
                             Self::handle_response(&pending_read, resp).await;
                         }
@@ -150,13 +271,10 @@ impl AgentChannel {
             }
         });
 
-        Self {
+        Connection {
             write_tx,
             pending,
-            next_id: AtomicU64::new(1),
             connected,
-            runtime_handle,
-            data_channel_capacity,
         }
     }
 
@@ -165,10 +283,7 @@ impl AgentChannel {
     /// For streaming data, uses `send().await` to apply backpressure when the
     /// consumer is slower than the producer. This prevents silent data loss
     /// that occurred with `try_send` (#1059).
-    async fn handle_response(
-        pending: &Arc<Mutex<HashMap<u64, PendingRequest>>>,
-        resp: AgentResponse,
-    ) {
+    async fn handle_response(pending: &PendingMap, resp: AgentResponse) {
         // Send streaming data without holding the mutex (send().await may yield)
         if let Some(data) = resp.data {
             let data_tx = {
@@ -215,16 +330,223 @@ impl AgentChannel {
         }
     }
 
+    /// Runtime the channel's I/O tasks run on
+    pub fn runtime_handle(&self) -> &tokio::runtime::Handle {
+        &self.runtime_handle
+    }
+
     /// Check if the channel is connected
     pub fn is_connected(&self) -> bool {
-        self.connected.load(Ordering::SeqCst)
+        self.current().connected.load(Ordering::SeqCst)
+    }
+
+    fn current(&self) -> Connection {
+        self.conn.lock().unwrap().clone()
+    }
+
+    /// Restart the agent if the connection is down.
+    ///
+    /// Returns `ConnectionLost` when no reconnector is configured or every
+    /// attempt failed.
+    async fn reconnect(&self) -> Result<(), ChannelError> {
+        let Some((policy, reconnector)) = &self.reconnector else {
+            return Err(ChannelError::ConnectionLost);
+        };
+        let _guard = self.reconnect_lock.lock().await;
+        // Another request may have reconnected while we waited for the lock.
+        if self.is_connected() {
+            return Ok(());
+        }
+
+        let mut backoff = policy.initial_backoff;
+        for attempt in 1..=policy.max_attempts {
+            match reconnector().await {
+                Ok(transport) => {
+                    let conn = Self::start_io(
+                        transport.reader,
+                        transport.writer,
+                        &self.bytes_sent,
+                        &self.bytes_received,
+                    );
+                    *self.conn.lock().unwrap() = conn;
+                    if let Err(e) = self.handshake(&transport.ready).await {
+                        warn!("handshake after reconnect failed: {e}");
+                        self.set_capabilities(Capabilities::from_ready(&transport.ready));
+                    }
+                    info!("reconnected to agent (attempt {attempt})");
+                    return Ok(());
+                }
+                Err(e) => {
+                    warn!(
+                        "reconnect attempt {attempt}/{} failed: {e}",
+                        policy.max_attempts
+                    );
+                    if attempt < policy.max_attempts {
+                        tokio::time::sleep(backoff).await;
+                        backoff = backoff.saturating_mul(2);
+                    }
+                }
+            }
+        }
+        Err(ChannelError::ConnectionLost)
+    }
+
+    /// Send a keepalive ping and wait up to `timeout` for the pong.
+    ///
+    /// Never triggers a reconnect; returns false if the channel is down.
+    pub async fn ping(&self, timeout: std::time::Duration) -> bool {
+        if !self.is_connected() {
+            return false;
+        }
+        let Ok((id, _data_rx, result_rx)) = self
+            .request_streaming_with_id("ping", serde_json::json!({}))
+            .await
+        else {
+            return false;
+        };
+        match tokio::time::timeout(timeout, result_rx).await {
+            Ok(Ok(Ok(_))) => true,
+            Ok(_) => false,
+            Err(_) => {
+                // Forget the unanswered ping so its entry doesn't linger
+                self.current().pending.lock().unwrap().remove(&id);
+                false
+            }
+        }
+    }
+
+    /// Mark the connection as lost, failing every request still waiting on it.
+    ///
+    /// Used when the agent stops responding without the transport closing.
+    /// The next request reconnects if a reconnector is configured.
+    pub fn mark_lost(&self) {
+        let conn = self.current();
+        conn.connected.store(false, Ordering::SeqCst);
+        let mut pending = conn.pending.lock().unwrap();
+        for (id, req) in pending.drain() {
+            if req
+                .result_tx
+                .send(Err("connection lost".to_string()))
+                .is_err()
+            {
+                warn!("request {id}: receiver dropped while marking connection lost");
+            }
+        }
+    }
+
+    /// Whether a failed request should trigger a reconnect
+    fn lost_connection(&self) -> bool {
+        self.reconnector.is_some() && !self.is_connected()
+    }
+
+    /// Negotiate capabilities with a freshly started agent.
+    ///
+    /// Sends `hello` with our protocol version; agents that predate the
+    /// handshake reject it, in which case the features advertised in their
+    /// `ready` message are used.
+    pub async fn handshake(&self, ready: &AgentResponse) -> Result<Capabilities, ChannelError> {
+        // Bypasses reconnect logic: this also runs during reconnect
+        let (_id, _data_rx, result_rx) = self
+            .send_request("hello", hello_params(PROTOCOL_VERSION))
+            .await?;
+        let result = result_rx
+            .await
+            .map_err(|_| ChannelError::ChannelClosed)?
+            .map_err(ChannelError::Remote);
+        let caps = match result {
+            Ok(result) => serde_json::from_value(result)?,
+            Err(ChannelError::Remote(msg)) if msg.contains("unknown method") => {
+                Capabilities::from_ready(ready)
+            }
+            Err(e) => return Err(e),
+        };
+        self.set_capabilities(caps.clone());
+        Ok(caps)
+    }
+
+    /// Capabilities negotiated with the agent
+    pub fn capabilities(&self) -> Capabilities {
+        self.capabilities.lock().unwrap().clone()
+    }
+
+    /// Check if the agent handles an optional protocol feature
+    pub fn supports(&self, feature: &str) -> bool {
+        self.capabilities.lock().unwrap().supports(feature)
+    }
+
+    /// Replace the negotiated capabilities.
+    ///
+    /// Normally set by [`AgentChannel::handshake`]; exposed so tests can
+    /// simulate older agents.
+    #[doc(hidden)]
+    pub fn set_capabilities(&self, capabilities: Capabilities) {
+        *self.capabilities.lock().unwrap() = capabilities;
+    }
+
+    /// Opt in to (or out of) gzip-compressed read/write payloads.
+    ///
+    /// Has no effect on agents that did not advertise support, so it is
+    /// always safe to enable.
+    pub fn set_compression(&self, enabled: bool) {
+        self.compression_requested.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Whether read/write requests should ask for compressed payloads
+    pub fn compression_enabled(&self) -> bool {
+        self.compression_requested.load(Ordering::SeqCst) && self.supports(FEATURE_GZIP)
+    }
+
+    /// Test hook: corrupt the next `count` read chunks received on this
+    /// channel, to exercise checksum verification and retry.
+    #[cfg(test)]
+    pub(crate) fn corrupt_next_chunks(&self, count: u64) {
+        self.test_corrupt_chunks.store(count, Ordering::SeqCst);
+    }
+
+    /// Consume one pending test corruption, if any
+    #[cfg(test)]
+    pub(crate) fn take_chunk_corruption(&self) -> bool {
+        self.test_corrupt_chunks
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok()
+    }
+
+    /// Total protocol bytes written to the agent so far
+    pub fn bytes_sent(&self) -> u64 {
+        self.bytes_sent.load(Ordering::Relaxed)
+    }
+
+    /// Total protocol bytes read from the agent so far
+    pub fn bytes_received(&self) -> u64 {
+        self.bytes_received.load(Ordering::Relaxed)
     }
 
     /// Send a request and wait for the final result (ignoring streaming data)
+    ///
+    /// If the connection drops while the request is in flight and a
+    /// reconnector is configured, idempotent requests are replayed on the new
+    /// connection and other requests fail with `ConnectionLost`.
     pub async fn request(
         &self,
         method: &str,
         params: serde_json::Value,
+    ) -> Result<serde_json::Value, ChannelError> {
+        match self.request_once(method, params.clone()).await {
+            Err(_) if self.lost_connection() => {
+                self.reconnect().await?;
+                if !IDEMPOTENT_METHODS.contains(&method) {
+                    return Err(ChannelError::ConnectionLost);
+                }
+                self.request_once(method, params).await
+            }
+            result => result,
+        }
+    }
+
+    async fn request_once(
+        &self,
+        method: &str,
+        params: serde_json::Value,
     ) -> Result<serde_json::Value, ChannelError> {
         let (mut data_rx, result_rx) = self.request_streaming(method, params).await?;
 
@@ -249,11 +571,70 @@ impl AgentChannel {
             oneshot::Receiver<Result<serde_json::Value, String>>,
         ),
         ChannelError,
+    > {
+        let (_id, data_rx, result_rx) = self.request_streaming_with_id(method, params).await?;
+        Ok((data_rx, result_rx))
+    }
+
+    /// Send a request that may stream data, also returning its request ID
+    ///
+    /// The ID can be passed to [`AgentChannel::cancel`] to stop long-lived
+    /// requests such as watches.
+    pub async fn request_streaming_with_id(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<
+        (
+            u64,
+            mpsc::Receiver<serde_json::Value>,
+            oneshot::Receiver<Result<serde_json::Value, String>>,
+        ),
+        ChannelError,
     > {
         if !self.is_connected() {
-            return Err(ChannelError::ChannelClosed);
+            if self.reconnector.is_none() {
+                return Err(ChannelError::ChannelClosed);
+            }
+            // Nothing has been sent yet, so any request can go to a new agent.
+            self.reconnect().await?;
         }
+        self.send_request(method, params).await
+    }
 
+    /// Register and send a request on the current connection, without
+    /// reconnecting
+    async fn send_request(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<
+        (
+            u64,
+            mpsc::Receiver<serde_json::Value>,
+            oneshot::Receiver<Result<serde_json::Value, String>>,
+        ),
+        ChannelError,
+    > {
+        self.send_line(|id| {
+            OutgoingLine::Plain(AgentRequest::new(id, method, params).to_json_line())
+        })
+        .await
+    }
+
+    /// Register a request and send the line `build` makes for its ID
+    async fn send_line(
+        &self,
+        build: impl FnOnce(u64) -> OutgoingLine,
+    ) -> Result<
+        (
+            u64,
+            mpsc::Receiver<serde_json::Value>,
+            oneshot::Receiver<Result<serde_json::Value, String>>,
+        ),
+        ChannelError,
+    > {
+        let conn = self.current();
         let id = self.next_id.fetch_add(1, Ordering::SeqCst);
 
         // Create channels for response
@@ -262,18 +643,22 @@ impl AgentChannel {
 
         // Register pending request
         {
-            let mut pending = self.pending.lock().unwrap();
+            let mut pending = conn.pending.lock().unwrap();
             pending.insert(id, PendingRequest { data_tx, result_tx });
         }
+        // The read task marks the connection dead before draining pending
+        // requests, so if it is still alive here our entry will be drained.
+        if !conn.connected.load(Ordering::SeqCst) {
+            conn.pending.lock().unwrap().remove(&id);
+            return Err(ChannelError::ChannelClosed);
+        }
 
-        // Build and send request
-        let req = AgentRequest::new(id, method, params);
-        self.write_tx
-            .send(req.to_json_line())
+        conn.write_tx
+            .send(build(id))
             .await
             .map_err(|_| ChannelError::ChannelClosed)?;
 
-        Ok((data_rx, result_rx))
+        Ok((id, data_rx, result_rx))
     }
 
     /// Send a request synchronously (blocking)
@@ -287,17 +672,107 @@ impl AgentChannel {
         self.runtime_handle.block_on(self.request(method, params))
     }
 
+    /// Send a request whose serialized `params` hold a secret, and wait for
+    /// the final result
+    ///
+    /// The request line is built in a buffer that is wiped once written to
+    /// the agent. Like other non-idempotent requests, it is never replayed:
+    /// if the connection drops it fails with `ConnectionLost`.
+    pub async fn request_secret(
+        &self,
+        method: &str,
+        params: Zeroizing<String>,
+    ) -> Result<serde_json::Value, ChannelError> {
+        match self.request_secret_once(method, params).await {
+            Err(_) if self.lost_connection() => {
+                self.reconnect().await?;
+                Err(ChannelError::ConnectionLost)
+            }
+            result => result,
+        }
+    }
+
+    async fn request_secret_once(
+        &self,
+        method: &str,
+        params: Zeroizing<String>,
+    ) -> Result<serde_json::Value, ChannelError> {
+        if !self.is_connected() {
+            if self.reconnector.is_none() {
+                return Err(ChannelError::ChannelClosed);
+            }
+            self.reconnect().await?;
+        }
+        let method_json = serde_json::to_string(method)?;
+        let (_id, mut data_rx, result_rx) = self
+            .send_line(|id| {
+                let prefix = format!("{{\"id\":{id},\"m\":{method_json},\"p\":");
+                let mut line =
+                    Zeroizing::new(String::with_capacity(prefix.len() + params.len() + 2));
+                line.push_str(&prefix);
+                line.push_str(&params);
+                line.push_str("}\n");
+                OutgoingLine::Secret(line)
+            })
+            .await?;
+
+        while data_rx.recv().await.is_some() {}
+        result_rx
+            .await
+            .map_err(|_| ChannelError::ChannelClosed)?
+            .map_err(ChannelError::Remote)
+    }
+
     /// Send a request and collect all streaming data along with the final result
+    ///
+    /// Reconnects and replays like [`AgentChannel::request`].
     pub async fn request_with_data(
         &self,
         method: &str,
         params: serde_json::Value,
+    ) -> Result<(Vec<serde_json::Value>, serde_json::Value), ChannelError> {
+        self.request_with_data_observed(method, params, &mut |_| {})
+            .await
+    }
+
+    /// Like [`AgentChannel::request_with_data`], but calls `on_data` for each
+    /// chunk as it arrives.
+    ///
+    /// If the request is replayed after a reconnect, `on_data` sees the
+    /// replayed chunks too.
+    pub async fn request_with_data_observed(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+        on_data: &mut dyn FnMut(&serde_json::Value),
+    ) -> Result<(Vec<serde_json::Value>, serde_json::Value), ChannelError> {
+        match self
+            .request_with_data_once(method, params.clone(), on_data)
+            .await
+        {
+            Err(_) if self.lost_connection() => {
+                self.reconnect().await?;
+                if !IDEMPOTENT_METHODS.contains(&method) {
+                    return Err(ChannelError::ConnectionLost);
+                }
+                self.request_with_data_once(method, params, on_data).await
+            }
+            result => result,
+        }
+    }
+
+    async fn request_with_data_once(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+        on_data: &mut dyn FnMut(&serde_json::Value),
     ) -> Result<(Vec<serde_json::Value>, serde_json::Value), ChannelError> {
         let (mut data_rx, result_rx) = self.request_streaming(method, params).await?;
 
         // Collect all streaming data
         let mut data = Vec::new();
         while let Some(chunk) = data_rx.recv().await {
+            on_data(&chunk);
             data.push(chunk);
 
             // Test hook: simulate slow consumer for backpressure testing.
@@ -329,6 +804,20 @@ impl AgentChannel {
             .block_on(self.request_with_data(method, params))
     }
 
+    /// Send a request with streaming data and a per-chunk observer,
+    /// synchronously (blocking)
+    ///
+    /// This can be called from outside the Tokio runtime context.
+    pub fn request_with_data_observed_blocking(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+        on_data: &mut dyn FnMut(&serde_json::Value),
+    ) -> Result<(Vec<serde_json::Value>, serde_json::Value), ChannelError> {
+        self.runtime_handle
+            .block_on(self.request_with_data_observed(method, params, on_data))
+    }
+
     /// Cancel a request
     pub async fn cancel(&self, request_id: u64) -> Result<(), ChannelError> {
         use crate::services::remote::protocol::cancel_params;
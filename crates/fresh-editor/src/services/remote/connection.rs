@@ -2,13 +2,15 @@
 //!
 //! Handles spawning SSH process and bootstrapping the Python agent.
 
-use crate::services::remote::channel::AgentChannel;
+use crate::services::remote::channel::{AgentChannel, AgentTransport, ChannelError, Reconnector};
 use crate::services::remote::protocol::AgentResponse;
 use crate::services::remote::AGENT_SOURCE;
 use std::path::PathBuf;
 use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::process::{Child, Command};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
 
 /// Error type for SSH connection
 #[derive(Debug, thiserror::Error)]
@@ -29,6 +31,30 @@ pub enum SshError {
     AuthenticationFailed,
 }
 
+/// Default interval between keepalive pings
+pub const DEFAULT_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Consecutive unanswered pings after which the connection is declared lost
+const KEEPALIVE_MAX_MISSES: u32 = 3;
+
+/// How to re-establish a dropped agent connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    /// Number of respawn attempts before giving up (0 disables reconnect)
+    pub max_attempts: u32,
+    /// Delay after the first failed attempt; doubled after each failure
+    pub initial_backoff: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+        }
+    }
+}
+
 /// SSH connection parameters
 #[derive(Debug, Clone)]
 pub struct ConnectionParams {
@@ -36,6 +62,9 @@ pub struct ConnectionParams {
     pub host: String,
     pub port: Option<u16>,
     pub identity_file: Option<PathBuf>,
+    pub reconnect: ReconnectPolicy,
+    /// Interval between keepalive pings on an idle channel (`None` disables)
+    pub keepalive_interval: Option<Duration>,
 }
 
 impl ConnectionParams {
@@ -61,6 +90,8 @@ impl ConnectionParams {
             host: host.to_string(),
             port,
             identity_file: None,
+            reconnect: ReconnectPolicy::default(),
+            keepalive_interval: Some(DEFAULT_KEEPALIVE_INTERVAL),
         })
     }
 
@@ -76,118 +107,57 @@ impl ConnectionParams {
 
 /// Active SSH connection with bootstrapped agent
 pub struct SshConnection {
-    /// SSH child process
-    process: Child,
+    /// SSH child process (replaced when the channel reconnects)
+    process: Arc<Mutex<Child>>,
     /// Communication channel with agent (wrapped in Arc for sharing)
     channel: std::sync::Arc<AgentChannel>,
     /// Connection parameters
     params: ConnectionParams,
+    /// Background keepalive task, if enabled
+    keepalive: Option<tokio::task::JoinHandle<()>>,
 }
 
 impl SshConnection {
     /// Establish a new SSH connection and bootstrap the agent
     pub async fn connect(params: ConnectionParams) -> Result<Self, SshError> {
-        let mut cmd = Command::new("ssh");
-
-        // Don't check host key strictly for ease of use
-        cmd.arg("-o").arg("StrictHostKeyChecking=accept-new");
-        // Allow password prompts - SSH will use the terminal for this
-        // Note: We inherit stderr so SSH can prompt for password if needed
-
-        if let Some(port) = params.port {
-            cmd.arg("-p").arg(port.to_string());
-        }
-
-        if let Some(ref identity) = params.identity_file {
-            cmd.arg("-i").arg(identity);
-        }
-
-        cmd.arg(format!("{}@{}", params.user, params.host));
-
-        // Bootstrap the agent using Python itself to read the exact byte count.
-        // This avoids requiring bash or other shell utilities on the remote.
-        // Python reads exactly N bytes (the agent code), execs it, and the agent
-        // then continues reading from stdin for protocol messages.
-        //
-        // Note: SSH passes the remote command through a shell, so we need to
-        // properly quote the Python code. We use double quotes for the outer
-        // shell and avoid problematic characters in the Python code.
-        let agent_len = AGENT_SOURCE.len();
-        let bootstrap = format!(
-            "python3 -u -c \"import sys;exec(sys.stdin.read({}))\"",
-            agent_len
-        );
-        cmd.arg(bootstrap);
-
-        cmd.stdin(Stdio::piped());
-        cmd.stdout(Stdio::piped());
-        // Inherit stderr so SSH can prompt for password on the terminal
-        cmd.stderr(Stdio::inherit());
-
-        let mut child = cmd.spawn()?;
-
-        // Get handles
-        let mut stdin = child
-            .stdin
-            .take()
-            .ok_or_else(|| SshError::AgentStartFailed("failed to get stdin".to_string()))?;
-        let stdout = child
-            .stdout
-            .take()
-            .ok_or_else(|| SshError::AgentStartFailed("failed to get stdout".to_string()))?;
-        // Note: stderr is inherited so SSH can prompt for password on the terminal
-
-        // Send the agent code (exact byte count)
-        stdin.write_all(AGENT_SOURCE.as_bytes()).await?;
-        stdin.flush().await?;
-
-        // Create buffered reader for stdout
-        let mut reader = BufReader::new(stdout);
-
-        // Wait for ready message from agent
-        // No timeout needed - all failure modes (auth failure, network issues, etc.)
-        // result in SSH exiting and us getting EOF. User can Ctrl+C if needed.
-        let mut ready_line = String::new();
-        match reader.read_line(&mut ready_line).await {
-            Ok(0) => {
-                return Err(SshError::AgentStartFailed(
-                    "connection closed (check terminal for SSH errors)".to_string(),
-                ));
-            }
-            Ok(_) => {}
-            Err(e) => return Err(SshError::AgentStartFailed(format!("read error: {}", e))),
-        }
-
-        let ready: AgentResponse = serde_json::from_str(&ready_line).map_err(|e| {
-            SshError::AgentStartFailed(format!(
-                "invalid ready message '{}': {}",
-                ready_line.trim(),
-                e
-            ))
-        })?;
-
-        if !ready.is_ready() {
-            return Err(SshError::AgentStartFailed(
-                "agent did not send ready message".to_string(),
-            ));
-        }
+        let (child, reader, stdin, ready) = spawn_ssh_agent(&params, false).await?;
+        let process = Arc::new(Mutex::new(child));
 
-        // Check protocol version
-        let version = ready.version.unwrap_or(0);
-        if version != crate::services::remote::protocol::PROTOCOL_VERSION {
-            return Err(SshError::VersionMismatch {
-                expected: crate::services::remote::protocol::PROTOCOL_VERSION,
-                got: version,
+        // Create channel (takes ownership of stdin for writing)
+        let mut channel = AgentChannel::new(reader, stdin);
+        if params.reconnect.max_attempts > 0 {
+            let reconnect_params = params.clone();
+            let reconnect_process = process.clone();
+            let reconnector: Reconnector = Box::new(move || {
+                let params = reconnect_params.clone();
+                let process = reconnect_process.clone();
+                Box::pin(async move {
+                    // The terminal is in raw mode by now, so SSH must not prompt.
+                    let (child, reader, writer, ready) = spawn_ssh_agent(&params, true)
+                        .await
+                        .map_err(std::io::Error::other)?;
+                    let mut old = std::mem::replace(&mut *process.lock().unwrap(), child);
+                    kill_process(&mut old);
+                    Ok(AgentTransport {
+                        reader,
+                        writer,
+                        ready,
+                    })
+                })
             });
+            channel = channel.with_reconnector(params.reconnect, reconnector);
         }
-
-        // Create channel (takes ownership of stdin for writing)
-        let channel = std::sync::Arc::new(AgentChannel::new(reader, stdin));
+        channel.handshake(&ready).await.map_err(handshake_error)?;
+        let channel = std::sync::Arc::new(channel);
+        let keepalive = params
+            .keepalive_interval
+            .map(|interval| start_keepalive(&channel, interval));
 
         Ok(Self {
-            process: child,
+            process,
             channel,
             params,
+            keepalive,
         })
     }
 
@@ -214,35 +184,124 @@ impl SshConnection {
 
 impl Drop for SshConnection {
     fn drop(&mut self) {
-        // Best-effort kill of the SSH process during cleanup.
-        // If it fails (process already exited, permission error, etc.)
-        // there's nothing we can do in a Drop impl — the OS will clean
-        // up the zombie when our process exits.
-        match self.process.start_kill() {
-            Ok(()) => {}
-            Err(_) => {}
+        if let Some(keepalive) = &self.keepalive {
+            keepalive.abort();
+        }
+        if let Ok(mut process) = self.process.lock() {
+            kill_process(&mut process);
         }
     }
 }
 
-/// Spawn a local agent process for testing (no SSH)
+/// Ping the agent every `interval` so idle SSH sessions aren't dropped by the
+/// server's `ClientAliveInterval`.
 ///
-/// This is used by integration tests to test the full stack without SSH.
-/// Not intended for production use.
-#[doc(hidden)]
-pub async fn spawn_local_agent() -> Result<std::sync::Arc<AgentChannel>, SshError> {
-    use tokio::process::Command as TokioCommand;
+/// Pings are sent even while requests are in flight, so a request the agent
+/// never answers is noticed too. A ping stuck behind a slow request still
+/// counts as answered if anything else arrived from the agent meanwhile.
+/// After [`KEEPALIVE_MAX_MISSES`] consecutive pings go unanswered within
+/// `interval`, the channel is marked lost so callers see the failure (and
+/// the next request reconnects). The task ends when the channel is dropped.
+pub fn start_keepalive(
+    channel: &Arc<AgentChannel>,
+    interval: Duration,
+) -> tokio::task::JoinHandle<()> {
+    let channel = Arc::downgrade(channel);
+    tokio::spawn(async move {
+        let mut misses = 0;
+        loop {
+            tokio::time::sleep(interval).await;
+            let Some(channel) = channel.upgrade() else {
+                return;
+            };
+            if !channel.is_connected() {
+                misses = 0;
+                continue;
+            }
+            let received = channel.bytes_received();
+            if channel.ping(interval).await || channel.bytes_received() != received {
+                misses = 0;
+                continue;
+            }
+            misses += 1;
+            tracing::warn!("keepalive ping unanswered ({misses}/{KEEPALIVE_MAX_MISSES})");
+            if misses >= KEEPALIVE_MAX_MISSES {
+                tracing::warn!("agent stopped responding; marking connection lost");
+                channel.mark_lost();
+                misses = 0;
+            }
+        }
+    })
+}
 
-    let mut child = TokioCommand::new("python3")
-        .arg("-u")
-        .arg("-c")
-        .arg(AGENT_SOURCE)
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()?;
+/// Best-effort kill of an SSH process during cleanup.
+///
+/// If it fails (process already exited, permission error, etc.) there's
+/// nothing we can do — the OS will clean up the zombie when our process exits.
+fn kill_process(process: &mut Child) {
+    let _ = process.start_kill();
+}
 
-    let stdin = child
+fn handshake_error(e: ChannelError) -> SshError {
+    SshError::AgentStartFailed(format!("handshake failed: {}", e))
+}
+
+/// Spawn SSH, upload the agent and wait for its ready message.
+///
+/// With `batch_mode` set, SSH fails instead of prompting for a password;
+/// used for reconnects where the terminal belongs to the editor.
+async fn spawn_ssh_agent(
+    params: &ConnectionParams,
+    batch_mode: bool,
+) -> Result<(Child, BufReader<ChildStdout>, ChildStdin, AgentResponse), SshError> {
+    let mut cmd = Command::new("ssh");
+
+    // Don't check host key strictly for ease of use
+    cmd.arg("-o").arg("StrictHostKeyChecking=accept-new");
+    // Allow password prompts - SSH will use the terminal for this
+    // Note: We inherit stderr so SSH can prompt for password if needed
+    if batch_mode {
+        cmd.arg("-o").arg("BatchMode=yes");
+    }
+
+    if let Some(port) = params.port {
+        cmd.arg("-p").arg(port.to_string());
+    }
+
+    if let Some(ref identity) = params.identity_file {
+        cmd.arg("-i").arg(identity);
+    }
+
+    cmd.arg(format!("{}@{}", params.user, params.host));
+
+    // Bootstrap the agent using Python itself to read the exact byte count.
+    // This avoids requiring bash or other shell utilities on the remote.
+    // Python reads exactly N bytes (the agent code), execs it, and the agent
+    // then continues reading from stdin for protocol messages.
+    //
+    // Note: SSH passes the remote command through a shell, so we need to
+    // properly quote the Python code. We use double quotes for the outer
+    // shell and avoid problematic characters in the Python code.
+    let agent_len = AGENT_SOURCE.len();
+    let bootstrap = format!(
+        "python3 -u -c \"import sys;exec(sys.stdin.read({}))\"",
+        agent_len
+    );
+    cmd.arg(bootstrap);
+
+    cmd.stdin(Stdio::piped());
+    cmd.stdout(Stdio::piped());
+    // Inherit stderr so SSH can prompt for password on the terminal
+    cmd.stderr(if batch_mode {
+        Stdio::null()
+    } else {
+        Stdio::inherit()
+    });
+
+    let mut child = cmd.spawn()?;
+
+    // Get handles
+    let mut stdin = child
         .stdin
         .take()
         .ok_or_else(|| SshError::AgentStartFailed("failed to get stdin".to_string()))?;
@@ -251,14 +310,34 @@ pub async fn spawn_local_agent() -> Result<std::sync::Arc<AgentChannel>, SshErro
         .take()
         .ok_or_else(|| SshError::AgentStartFailed("failed to get stdout".to_string()))?;
 
+    // Send the agent code (exact byte count)
+    stdin.write_all(AGENT_SOURCE.as_bytes()).await?;
+    stdin.flush().await?;
+
+    // Create buffered reader for stdout
     let mut reader = BufReader::new(stdout);
 
-    // Wait for ready message
+    // Wait for ready message from agent
+    // No timeout needed - all failure modes (auth failure, network issues, etc.)
+    // result in SSH exiting and us getting EOF. User can Ctrl+C if needed.
     let mut ready_line = String::new();
-    reader.read_line(&mut ready_line).await?;
+    match reader.read_line(&mut ready_line).await {
+        Ok(0) => {
+            return Err(SshError::AgentStartFailed(
+                "connection closed (check terminal for SSH errors)".to_string(),
+            ));
+        }
+        Ok(_) => {}
+        Err(e) => return Err(SshError::AgentStartFailed(format!("read error: {}", e))),
+    }
 
-    let ready: AgentResponse = serde_json::from_str(&ready_line)
-        .map_err(|e| SshError::AgentStartFailed(format!("invalid ready message: {}", e)))?;
+    let ready: AgentResponse = serde_json::from_str(&ready_line).map_err(|e| {
+        SshError::AgentStartFailed(format!(
+            "invalid ready message '{}': {}",
+            ready_line.trim(),
+            e
+        ))
+    })?;
 
     if !ready.is_ready() {
         return Err(SshError::AgentStartFailed(
@@ -266,17 +345,20 @@ pub async fn spawn_local_agent() -> Result<std::sync::Arc<AgentChannel>, SshErro
         ));
     }
 
-    Ok(std::sync::Arc::new(AgentChannel::new(reader, stdin)))
+    // Check protocol version
+    let version = ready.version.unwrap_or(0);
+    if version != crate::services::remote::protocol::PROTOCOL_VERSION {
+        return Err(SshError::VersionMismatch {
+            expected: crate::services::remote::protocol::PROTOCOL_VERSION,
+            got: version,
+        });
+    }
+
+    Ok((child, reader, stdin, ready))
 }
 
-/// Spawn a local Python agent with a custom data channel capacity.
-///
-/// Same as `spawn_local_agent` but allows overriding the channel capacity
-/// for stress-testing backpressure handling.
-#[doc(hidden)]
-pub async fn spawn_local_agent_with_capacity(
-    data_channel_capacity: usize,
-) -> Result<std::sync::Arc<AgentChannel>, SshError> {
+/// Spawn a local Python agent and wait for its ready message
+async fn spawn_local_process() -> Result<AgentTransport, SshError> {
     use tokio::process::Command as TokioCommand;
 
     let mut child = TokioCommand::new("python3")
@@ -312,11 +394,51 @@ pub async fn spawn_local_agent_with_capacity(
         ));
     }
 
-    Ok(std::sync::Arc::new(AgentChannel::with_capacity(
+    Ok(AgentTransport {
         reader,
-        stdin,
-        data_channel_capacity,
-    )))
+        writer: stdin,
+        ready,
+    })
+}
+
+/// Reconnector that starts a fresh local agent
+fn local_reconnector() -> Reconnector {
+    Box::new(|| Box::pin(async { spawn_local_process().await.map_err(std::io::Error::other) }))
+}
+
+/// Spawn a local agent process for testing (no SSH)
+///
+/// This is used by integration tests to test the full stack without SSH.
+/// Not intended for production use. The channel respawns the agent with the
+/// default [`ReconnectPolicy`] if it dies.
+#[doc(hidden)]
+pub async fn spawn_local_agent() -> Result<std::sync::Arc<AgentChannel>, SshError> {
+    let transport = spawn_local_process().await?;
+    let channel = AgentChannel::new(transport.reader, transport.writer)
+        .with_reconnector(ReconnectPolicy::default(), local_reconnector());
+    channel
+        .handshake(&transport.ready)
+        .await
+        .map_err(handshake_error)?;
+    Ok(std::sync::Arc::new(channel))
+}
+
+/// Spawn a local Python agent with a custom data channel capacity.
+///
+/// Same as `spawn_local_agent` but allows overriding the channel capacity
+/// for stress-testing backpressure handling.
+#[doc(hidden)]
+pub async fn spawn_local_agent_with_capacity(
+    data_channel_capacity: usize,
+) -> Result<std::sync::Arc<AgentChannel>, SshError> {
+    let transport = spawn_local_process().await?;
+    let channel =
+        AgentChannel::with_capacity(transport.reader, transport.writer, data_channel_capacity);
+    channel
+        .handshake(&transport.ready)
+        .await
+        .map_err(handshake_error)?;
+    Ok(std::sync::Arc::new(channel))
 }
 
 #[cfg(test)]
@@ -347,6 +469,8 @@ mod tests {
             host: "example.com".to_string(),
             port: None,
             identity_file: None,
+            reconnect: ReconnectPolicy::default(),
+            keepalive_interval: None,
         };
         assert_eq!(params.to_string(), "alice@example.com");
 
@@ -355,6 +479,8 @@ mod tests {
             host: "server.local".to_string(),
             port: Some(2222),
             identity_file: None,
+            reconnect: ReconnectPolicy::default(),
+            keepalive_interval: None,
         };
         assert_eq!(params.to_string(), "bob@server.local:2222");
     }
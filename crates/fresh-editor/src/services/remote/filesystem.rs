@@ -3,17 +3,108 @@
 //! Implements the FileSystem trait for remote operations via SSH agent.
 
 use crate::model::filesystem::{
-    DirEntry, EntryType, FileMetadata, FilePermissions, FileReader, FileSystem, FileWriter, WriteOp,
+    DirEntry, EntryType, FileMetadata, FilePermissions, FileReader, FileSystem, FileWatch,
+    FileWriter, WriteOp,
 };
 use crate::services::remote::channel::{AgentChannel, ChannelError};
 use crate::services::remote::protocol::{
-    append_params, decode_base64, ls_params, patch_params, read_params, stat_params,
-    sudo_write_params, truncate_params, write_params, PatchOp, RemoteDirEntry, RemoteMetadata,
+    append_params, checksum, count_lines_params, decode_base64, find_params, git_files_params,
+    gzip_decompress, ls_params, patch_params, read_params, readlink_params, remove_params,
+    stat_many_params, stat_params, statfs_params, sudo_write_params, sudo_write_password_params,
+    truncate_params, watch_params, write_params, LineCount, PatchOp, RemoteDirEntry,
+    RemoteMetadata, FEATURE_COUNT_LINES, FEATURE_FIND, FEATURE_STAT_MANY, FEATURE_WATCH,
+    FIND_MAX_DEPTH, SUDO_PASSWORD_REJECTED,
 };
 use std::io::{self, Cursor, Read, Seek, Write};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, UNIX_EPOCH};
+use tokio::sync::{mpsc, oneshot};
+use zeroize::Zeroizing;
+
+/// Times a chunk that fails its checksum is re-read before giving up
+const CHUNK_RETRIES: usize = 3;
+
+/// Debounce window the agent applies to bursts of watch events
+const WATCH_DEBOUNCE_MS: u64 = 100;
+
+/// How often a blocking find checks whether it was cancelled
+const FIND_CANCEL_POLL: Duration = Duration::from_millis(50);
+
+/// Kind of change reported by [`RemoteFileSystem::watch`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileChangeKind {
+    Created,
+    Modified,
+    Removed,
+    Renamed,
+}
+
+/// A change to a watched path on the remote host
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChangeEvent {
+    pub path: PathBuf,
+    pub kind: FileChangeKind,
+}
+
+impl FileChangeEvent {
+    fn from_json(value: &serde_json::Value) -> Option<Self> {
+        let kind = match value.get("kind")?.as_str()? {
+            "created" => FileChangeKind::Created,
+            "modified" => FileChangeKind::Modified,
+            "removed" => FileChangeKind::Removed,
+            "renamed" => FileChangeKind::Renamed,
+            _ => return None,
+        };
+        let path = PathBuf::from(value.get("path")?.as_str()?);
+        Some(Self { path, kind })
+    }
+}
+
+/// A password held only as long as needed
+///
+/// The buffer is zeroized when dropped and `Debug` never prints it.
+pub struct SecretString(Zeroizing<String>);
+
+impl SecretString {
+    pub fn new(secret: String) -> Self {
+        Self(Zeroizing::new(secret))
+    }
+
+    /// Borrow the secret; avoid copying it into long-lived values
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        Self::new(secret)
+    }
+}
+
+impl std::fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SecretString(<redacted>)")
+    }
+}
+
+/// Error returned by [`RemoteFileSystem::write_file_sudo`] when sudo refuses
+/// the password
+///
+/// Carried inside an `io::Error` of kind `PermissionDenied`; check for it
+/// with `err.get_ref().is_some_and(|e| e.is::<SudoPasswordRejected>())`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SudoPasswordRejected;
+
+impl std::fmt::Display for SudoPasswordRejected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("sudo rejected the password")
+    }
+}
+
+impl std::error::Error for SudoPasswordRejected {}
 
 /// Remote filesystem that communicates with the Python agent
 pub struct RemoteFileSystem {
@@ -41,12 +132,452 @@ impl RemoteFileSystem {
         self.channel.is_connected()
     }
 
+    /// Remove a file or directory on the remote host.
+    ///
+    /// Without `recursive`, directories must be empty; a non-empty directory
+    /// fails with `DirectoryNotEmpty`. Symlinks are removed, not followed.
+    pub fn remove(&self, path: &Path, recursive: bool) -> io::Result<()> {
+        let path_str = path.to_string_lossy();
+        self.channel
+            .request_blocking("remove", remove_params(&path_str, recursive))
+            .map_err(Self::to_io_error)?;
+        Ok(())
+    }
+
+    /// Fail with `Unsupported` if the agent did not negotiate `feature`
+    fn require(&self, feature: &str) -> io::Result<()> {
+        if self.channel.supports(feature) {
+            Ok(())
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("remote agent does not support {}", feature),
+            ))
+        }
+    }
+
+    /// Decode the streamed `read` chunks of `path` into file content.
+    ///
+    /// Each chunk is verified against its CRC32 before being appended; a
+    /// corrupted chunk is re-requested up to [`CHUNK_RETRIES`] times.
+    async fn collect_chunks(
+        &self,
+        path: &Path,
+        chunks: Vec<serde_json::Value>,
+    ) -> io::Result<Vec<u8>> {
+        let mut content = Vec::new();
+        for chunk in chunks {
+            if chunk.get("data").is_some() {
+                content.extend(self.verified_chunk(path, &chunk).await?);
+            }
+        }
+        Ok(content)
+    }
+
+    /// Decode a single chunk. Chunks marked `"z": true` were gzipped by the
+    /// agent and are decompressed.
+    fn decode_chunk(&self, chunk: &serde_json::Value) -> io::Result<Vec<u8>> {
+        let b64 = chunk.get("data").and_then(|v| v.as_str()).unwrap_or("");
+        let decoded =
+            decode_base64(b64).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let bytes = if chunk.get("z").and_then(|v| v.as_bool()).unwrap_or(false) {
+            gzip_decompress(&decoded)?
+        } else {
+            decoded
+        };
+        #[cfg(test)]
+        let bytes = {
+            let mut bytes = bytes;
+            if self.channel.take_chunk_corruption() {
+                if let Some(first) = bytes.first_mut() {
+                    *first ^= 0xff;
+                }
+            }
+            bytes
+        };
+        Ok(bytes)
+    }
+
+    /// Decode a chunk and check it against its CRC32, re-reading its range
+    /// from the agent on mismatch.
+    async fn verified_chunk(&self, path: &Path, chunk: &serde_json::Value) -> io::Result<Vec<u8>> {
+        let mut bytes = self.decode_chunk(chunk)?;
+        let Some(mut expected) = chunk.get("crc").and_then(|v| v.as_u64()) else {
+            return Ok(bytes);
+        };
+        let offset = chunk.get("off").and_then(|v| v.as_u64()).unwrap_or(0);
+        let len = chunk
+            .get("n")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(bytes.len() as u64) as usize;
+
+        for attempt in 0..=CHUNK_RETRIES {
+            if u64::from(checksum(&bytes)) == expected {
+                return Ok(bytes);
+            }
+            if attempt == CHUNK_RETRIES {
+                break;
+            }
+            tracing::warn!(
+                "checksum mismatch reading {} at offset {}, retrying",
+                path.display(),
+                offset
+            );
+            let path_str = path.to_string_lossy();
+            let compress = self.channel.compression_enabled();
+            let (chunks, _result) = self
+                .channel
+                .request_with_data(
+                    "read",
+                    read_params(&path_str, Some(offset), Some(len), compress),
+                )
+                .await
+                .map_err(Self::to_io_error)?;
+            let retry = chunks.first().ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!(
+                        "{} shrank while re-reading offset {}",
+                        path.display(),
+                        offset
+                    ),
+                )
+            })?;
+            bytes = self.decode_chunk(retry)?;
+            expected = retry
+                .get("crc")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(expected);
+        }
+
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "checksum mismatch reading {} at offset {} ({} bytes) after {} retries",
+                path.display(),
+                offset,
+                len,
+                CHUNK_RETRIES
+            ),
+        ))
+    }
+
+    /// Find files under `root` whose relative path matches a glob pattern.
+    ///
+    /// The tree is walked on the remote host in a single request; `**`
+    /// matches any number of directories, `*` and `?` stay within one path
+    /// component. `.git` directories and symlinked directories are skipped.
+    /// Returns at most `max_results` paths (0 for unlimited), relative to
+    /// `root`. Fails with `Unsupported` on agents without server-side find.
+    pub fn find(&self, root: &Path, pattern: &str, max_results: usize) -> io::Result<Vec<PathBuf>> {
+        self.require(FEATURE_FIND)?;
+        let root_str = root.to_string_lossy();
+        let mut paths = Vec::new();
+        self.channel
+            .request_with_data_observed_blocking(
+                "find",
+                find_params(&root_str, pattern, max_results, FIND_MAX_DEPTH),
+                &mut |chunk| {
+                    let Some(batch) = chunk.get("paths").and_then(|v| v.as_array()) else {
+                        return;
+                    };
+                    for path in batch.iter().filter_map(|v| v.as_str()) {
+                        if max_results != 0 && paths.len() >= max_results {
+                            return;
+                        }
+                        paths.push(PathBuf::from(path));
+                    }
+                },
+            )
+            .map_err(Self::to_io_error)?;
+        Ok(paths)
+    }
+
+    /// Like [`find`](Self::find), but delivers paths in batches as the agent
+    /// finds them.
+    ///
+    /// The receiver closes after the last batch; a failed walk ends with an
+    /// error. Dropping the receiver cancels the walk on the agent.
+    pub fn find_streaming(
+        &self,
+        root: &Path,
+        pattern: &str,
+        max_results: usize,
+    ) -> io::Result<mpsc::UnboundedReceiver<io::Result<Vec<PathBuf>>>> {
+        let root_str = root.to_string_lossy();
+        self.stream_find(find_params(&root_str, pattern, max_results, FIND_MAX_DEPTH))
+    }
+
+    /// Send a find request with `params`, streaming its batches of paths
+    fn stream_find(
+        &self,
+        params: serde_json::Value,
+    ) -> io::Result<mpsc::UnboundedReceiver<io::Result<Vec<PathBuf>>>> {
+        self.require(FEATURE_FIND)?;
+        let handle = self.channel.runtime_handle();
+        let (id, mut data_rx, result_rx) = handle
+            .block_on(self.channel.request_streaming_with_id("find", params))
+            .map_err(Self::to_io_error)?;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let channel = self.channel.clone();
+        handle.spawn(async move {
+            loop {
+                tokio::select! {
+                    data = data_rx.recv() => {
+                        let Some(data) = data else { break };
+                        let paths = data
+                            .get("paths")
+                            .and_then(|v| v.as_array())
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|v| v.as_str())
+                            .map(PathBuf::from)
+                            .collect();
+                        // A send error means the receiver is gone;
+                        // `tx.closed()` fires on the next iteration.
+                        #[allow(clippy::let_underscore_must_use)]
+                        let _ = tx.send(Ok(paths));
+                    }
+                    _ = tx.closed() => {
+                        // Receiver dropped: stop the walk on the agent
+                        if let Err(e) = channel.cancel(id).await {
+                            tracing::debug!("failed to cancel find {id}: {e}");
+                        }
+                        while data_rx.recv().await.is_some() {}
+                        return;
+                    }
+                }
+            }
+            let result = result_rx
+                .await
+                .map_err(|_| ChannelError::ChannelClosed)
+                .and_then(|r| r.map_err(ChannelError::Remote));
+            if let Err(e) = result {
+                // Nobody to report to if the receiver is gone
+                #[allow(clippy::let_underscore_must_use)]
+                let _ = tx.send(Err(Self::to_io_error(e)));
+            }
+        });
+        Ok(rx)
+    }
+
+    /// Gather the batches of a streamed find, giving up once `cancelled` is set
+    fn collect_found(
+        &self,
+        mut batches: mpsc::UnboundedReceiver<io::Result<Vec<PathBuf>>>,
+        cancelled: &AtomicBool,
+    ) -> io::Result<Vec<PathBuf>> {
+        let handle = self.channel.runtime_handle();
+        let mut paths = Vec::new();
+        loop {
+            if cancelled.load(Ordering::Relaxed) {
+                // Dropping `batches` cancels the walk on the agent
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "find cancelled"));
+            }
+            // The timer is created inside the runtime, so this also works
+            // from threads outside it
+            match handle
+                .block_on(async { tokio::time::timeout(FIND_CANCEL_POLL, batches.recv()).await })
+            {
+                Ok(Some(batch)) => paths.extend(batch?),
+                Ok(None) => return Ok(paths),
+                // Still walking; check for cancellation again
+                Err(_) => {}
+            }
+        }
+    }
+
+    /// Write a file as root with `sudo`, authenticating with `password`.
+    ///
+    /// For the "permission denied, try with sudo" flow when saving system
+    /// files. The password is piped to `sudo -S` on the remote host, is
+    /// never logged, and the request buffers holding it are wiped after use.
+    /// An existing file keeps its owner and permissions. Fails with a
+    /// [`SudoPasswordRejected`] error if sudo refuses the password.
+    pub fn write_file_sudo(
+        &self,
+        path: &Path,
+        content: &[u8],
+        password: &SecretString,
+    ) -> io::Result<()> {
+        let params =
+            sudo_write_password_params(&path.to_string_lossy(), content, password.expose_secret());
+        let result = self
+            .channel
+            .runtime_handle()
+            .block_on(self.channel.request_secret("sudo_write", params));
+        match result {
+            Ok(_) => Ok(()),
+            Err(ChannelError::Remote(msg)) if msg.starts_with(SUDO_PASSWORD_REJECTED) => Err(
+                io::Error::new(io::ErrorKind::PermissionDenied, SudoPasswordRejected),
+            ),
+            Err(e) => Err(Self::to_io_error(e)),
+        }
+    }
+
+    /// Count the newlines in a file on the remote host.
+    ///
+    /// The file is scanned by the agent, so only the count crosses the
+    /// connection. Fails with `Unsupported` on agents without server-side
+    /// line counting.
+    pub fn count_lines(&self, path: &Path) -> io::Result<LineCount> {
+        self.require(FEATURE_COUNT_LINES)?;
+        let result = self
+            .channel
+            .request_blocking("count_lines", count_lines_params(&path.to_string_lossy()))
+            .map_err(Self::to_io_error)?;
+
+        serde_json::from_value(result).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Watch a file or directory for changes made on the remote host.
+    ///
+    /// Events arrive debounced: a burst of changes within a short window is
+    /// delivered together, one event per path. Returns once the agent is
+    /// watching, so any later change is reported. Dropping the receiver cancels
+    /// the watch on the agent. The receiver closes if the watch ends, e.g.
+    /// because the connection dropped.
+    pub fn watch(&self, path: &Path) -> io::Result<mpsc::UnboundedReceiver<FileChangeEvent>> {
+        self.require(FEATURE_WATCH)?;
+        // Surface a missing path here rather than as a silently closed stream
+        self.metadata(path)?;
+
+        let path_str = path.to_string_lossy();
+        let handle = self.channel.runtime_handle();
+        let (id, mut data_rx, result_rx) = handle
+            .block_on(self.channel.request_streaming_with_id(
+                "watch",
+                watch_params(&path_str, WATCH_DEBOUNCE_MS, false),
+            ))
+            .map_err(Self::to_io_error)?;
+        // The agent acknowledges with an empty batch once the watch is in place
+        if handle.block_on(data_rx.recv()).is_none() {
+            let err = handle
+                .block_on(result_rx)
+                .map_err(|_| ChannelError::ChannelClosed)
+                .and_then(|r| r.map_err(ChannelError::Remote))
+                .err()
+                .unwrap_or(ChannelError::ChannelClosed);
+            return Err(Self::to_io_error(err));
+        }
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let channel = self.channel.clone();
+        handle.spawn(async move {
+            loop {
+                tokio::select! {
+                    data = data_rx.recv() => {
+                        let Some(data) = data else { return };
+                        let events = data.get("events").and_then(|v| v.as_array());
+                        for event in events.into_iter().flatten() {
+                            if let Some(event) = FileChangeEvent::from_json(event) {
+                                // A send error means the receiver is gone;
+                                // `tx.closed()` fires on the next iteration.
+                                if tx.send(event).is_err() {
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                    _ = tx.closed() => break,
+                }
+            }
+            // Receiver dropped: stop the agent-side watcher
+            if let Err(e) = channel.cancel(id).await {
+                tracing::debug!("failed to cancel watch {id}: {e}");
+            }
+            // Keep draining until the agent acknowledges; the data stream
+            // only ends once the final result has been delivered.
+            while data_rx.recv().await.is_some() {}
+            drop(result_rx);
+        });
+        Ok(rx)
+    }
+
+    /// Read a whole file without blocking the calling task.
+    ///
+    /// Requests are multiplexed by ID over the single agent channel and the
+    /// agent streams each read from its own thread, so many reads can be in
+    /// flight at once (e.g. with `tokio::join!`). Shadows the blocking
+    /// default from `FileSystemExt`.
+    pub async fn read_file_async(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let path_str = path.to_string_lossy();
+        let compress = self.channel.compression_enabled();
+        let (data_chunks, _result) = self
+            .channel
+            .request_with_data("read", read_params(&path_str, None, None, compress))
+            .await
+            .map_err(Self::to_io_error)?;
+
+        self.collect_chunks(path, data_chunks).await
+    }
+
+    /// Read a whole file, reporting progress as chunks arrive.
+    ///
+    /// `progress` receives the bytes transferred so far and the total size
+    /// when the initial `stat` succeeded. It is called once before the
+    /// transfer starts (so even empty files report), once per chunk, and
+    /// exactly once more when the read completes.
+    pub fn read_file_with_progress(
+        &self,
+        path: &Path,
+        progress: &mut dyn FnMut(u64, Option<u64>),
+    ) -> io::Result<Vec<u8>> {
+        let total = self.metadata(path).ok().map(|m| m.size);
+        progress(0, total);
+
+        let path_str = path.to_string_lossy();
+        let compress = self.channel.compression_enabled();
+        let mut transferred = 0u64;
+        let (data_chunks, _result) = self
+            .channel
+            .request_with_data_observed_blocking(
+                "read",
+                read_params(&path_str, None, None, compress),
+                &mut |chunk| {
+                    let n = chunk.get("n").and_then(|v| v.as_u64()).unwrap_or(0);
+                    // A replay after reconnect resends chunks; never go backwards
+                    // or past the known size.
+                    transferred += n;
+                    progress(total.map_or(transferred, |t| transferred.min(t)), total);
+                },
+            )
+            .map_err(Self::to_io_error)?;
+
+        let content = self
+            .channel
+            .runtime_handle()
+            .block_on(self.collect_chunks(path, data_chunks))?;
+        progress(content.len() as u64, total);
+        Ok(content)
+    }
+
+    /// Write a whole file, reporting progress.
+    ///
+    /// The agent receives the data in a single message, so `progress` is
+    /// called once before sending and once when the write completes.
+    pub fn write_file_with_progress(
+        &self,
+        path: &Path,
+        data: &[u8],
+        progress: &mut dyn FnMut(u64, Option<u64>),
+    ) -> io::Result<()> {
+        let total = Some(data.len() as u64);
+        progress(0, total);
+        self.write_file(path, data)?;
+        progress(data.len() as u64, total);
+        Ok(())
+    }
+
     /// Convert a ChannelError to io::Error
     fn to_io_error(e: ChannelError) -> io::Error {
         match e {
             ChannelError::Io(e) => e,
             ChannelError::Remote(msg) => {
-                let kind = if msg.contains("not found") || msg.contains("No such file") {
+                let kind = if msg.contains("directory not empty") {
+                    io::ErrorKind::DirectoryNotEmpty
+                } else if msg.contains("not found") || msg.contains("No such file") {
                     io::ErrorKind::NotFound
                 } else if msg.contains("permission denied") {
                     io::ErrorKind::PermissionDenied
@@ -59,6 +590,9 @@ impl RemoteFileSystem {
                 };
                 io::Error::new(kind, msg)
             }
+            ChannelError::ConnectionLost => {
+                io::Error::new(io::ErrorKind::ConnectionAborted, "connection to agent lost")
+            }
             e => io::Error::new(io::ErrorKind::Other, e.to_string()),
         }
     }
@@ -132,41 +666,26 @@ impl RemoteFileSystem {
 
 impl FileSystem for RemoteFileSystem {
     fn read_file(&self, path: &Path) -> io::Result<Vec<u8>> {
-        let path_str = path.to_string_lossy();
-        let (data_chunks, _result) = self
-            .channel
-            .request_with_data_blocking("read", read_params(&path_str, None, None))
-            .map_err(Self::to_io_error)?;
-
-        // Collect all streaming data chunks
-        let mut content = Vec::new();
-        for chunk in data_chunks {
-            if let Some(b64) = chunk.get("data").and_then(|v| v.as_str()) {
-                let decoded = decode_base64(b64)
-                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
-                content.extend(decoded);
-            }
-        }
-
-        Ok(content)
+        self.channel
+            .runtime_handle()
+            .block_on(self.read_file_async(path))
     }
 
     fn read_range(&self, path: &Path, offset: u64, len: usize) -> io::Result<Vec<u8>> {
         let path_str = path.to_string_lossy();
-        let (data_chunks, result) = self
-            .channel
-            .request_with_data_blocking("read", read_params(&path_str, Some(offset), Some(len)))
-            .map_err(Self::to_io_error)?;
-
-        // Collect all streaming data chunks
-        let mut content = Vec::new();
-        for chunk in data_chunks {
-            if let Some(b64) = chunk.get("data").and_then(|v| v.as_str()) {
-                let decoded = decode_base64(b64)
-                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
-                content.extend(decoded);
-            }
-        }
+        let compress = self.channel.compression_enabled();
+        let (content, result) = self.channel.runtime_handle().block_on(async {
+            let (data_chunks, result) = self
+                .channel
+                .request_with_data(
+                    "read",
+                    read_params(&path_str, Some(offset), Some(len), compress),
+                )
+                .await
+                .map_err(Self::to_io_error)?;
+            let content = self.collect_chunks(path, data_chunks).await?;
+            Ok::<_, io::Error>((content, result))
+        })?;
 
         // Get the size reported by the agent (how many bytes it actually read from the file)
         let agent_reported_size = result
@@ -195,11 +714,9 @@ impl FileSystem for RemoteFileSystem {
     }
 
     fn write_file(&self, path: &Path, data: &[u8]) -> io::Result<()> {
-        let path_str = path.to_string_lossy();
-        self.channel
-            .request_blocking("write", write_params(&path_str, data))
-            .map_err(Self::to_io_error)?;
-        Ok(())
+        // The agent writes to a temp file and renames it over the destination,
+        // re-applying the destination's mode first; new files get the umask.
+        write_verified(&self.channel, &path.to_string_lossy(), data)
     }
 
     fn create_file(&self, path: &Path) -> io::Result<Box<dyn FileWriter>> {
@@ -304,6 +821,10 @@ impl FileSystem for RemoteFileSystem {
         Ok(())
     }
 
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()> {
+        self.remove(path, true)
+    }
+
     fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
         let path_str = path.to_string_lossy();
         let result = self
@@ -321,6 +842,58 @@ impl FileSystem for RemoteFileSystem {
         Ok(Self::convert_metadata(&rm, &name))
     }
 
+    fn stat_many(&self, paths: &[PathBuf]) -> Vec<io::Result<FileMetadata>> {
+        if !self.channel.supports(FEATURE_STAT_MANY) {
+            return paths.iter().map(|p| self.metadata(p)).collect();
+        }
+        let path_strs: Vec<String> = paths
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect();
+        let path_refs: Vec<&str> = path_strs.iter().map(String::as_str).collect();
+
+        let entries = match self
+            .channel
+            .request_blocking("stat_many", stat_many_params(&path_refs, true))
+        {
+            Ok(mut result) => match result.get_mut("results").map(serde_json::Value::take) {
+                Some(serde_json::Value::Array(entries)) if entries.len() == paths.len() => entries,
+                _ => {
+                    let msg = "malformed stat_many response";
+                    return paths
+                        .iter()
+                        .map(|_| Err(io::Error::new(io::ErrorKind::InvalidData, msg)))
+                        .collect();
+                }
+            },
+            Err(e) => {
+                // The whole batch failed; report the same error for every path
+                let e = Self::to_io_error(e);
+                return paths
+                    .iter()
+                    .map(|_| Err(io::Error::new(e.kind(), e.to_string())))
+                    .collect();
+            }
+        };
+
+        paths
+            .iter()
+            .zip(entries)
+            .map(|(path, entry)| {
+                if let Some(msg) = entry.get("error").and_then(|v| v.as_str()) {
+                    return Err(Self::to_io_error(ChannelError::Remote(msg.to_string())));
+                }
+                let rm: RemoteMetadata = serde_json::from_value(entry)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                let name = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                Ok(Self::convert_metadata(&rm, &name))
+            })
+            .collect()
+    }
+
     fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
         let path_str = path.to_string_lossy();
         let result = self
@@ -335,7 +908,53 @@ impl FileSystem for RemoteFileSystem {
             .file_name()
             .map(|n| n.to_string_lossy().to_string())
             .unwrap_or_default();
-        Ok(Self::convert_metadata(&rm, &name))
+        let meta = Self::convert_metadata(&rm, &name);
+        if rm.link {
+            Ok(meta.with_symlink(rm.target.map(PathBuf::from)))
+        } else {
+            Ok(meta)
+        }
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        let result = self
+            .channel
+            .request_blocking("readlink", readlink_params(&path.to_string_lossy()))
+            .map_err(Self::to_io_error)?;
+
+        let target = result
+            .get("target")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing target"))?;
+        Ok(PathBuf::from(target))
+    }
+
+    fn available_space(&self, path: &Path) -> io::Result<u64> {
+        let result = self
+            .channel
+            .request_blocking("statfs", statfs_params(&path.to_string_lossy()))
+            .map_err(Self::to_io_error)?;
+
+        result
+            .get("avail")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing avail"))
+    }
+
+    fn is_writable(&self, path: &Path) -> bool {
+        if self.metadata(path).is_ok_and(|meta| meta.is_readonly) {
+            return false;
+        }
+        // statvfs catches read-only mounts, and access() files owned by others.
+        // Agents that don't report these leave the answer to the save.
+        let Ok(result) = self
+            .channel
+            .request_blocking("statfs", statfs_params(&path.to_string_lossy()))
+        else {
+            return true;
+        };
+        let flag = |key: &str| result.get(key).and_then(|v| v.as_bool());
+        flag("readonly") != Some(true) && flag("writable") != Some(false)
     }
 
     fn is_dir(&self, path: &Path) -> io::Result<bool> {
@@ -394,6 +1013,27 @@ impl FileSystem for RemoteFileSystem {
         Ok(entries.iter().map(Self::convert_dir_entry).collect())
     }
 
+    fn find_files(
+        &self,
+        root: &Path,
+        pattern: &str,
+        max_results: usize,
+        cancelled: &AtomicBool,
+    ) -> io::Result<Vec<PathBuf>> {
+        let batches = self.find_streaming(root, pattern, max_results)?;
+        self.collect_found(batches, cancelled)
+    }
+
+    fn git_files(
+        &self,
+        root: &Path,
+        max_results: usize,
+        cancelled: &AtomicBool,
+    ) -> io::Result<Vec<PathBuf>> {
+        let batches = self.stream_find(git_files_params(&root.to_string_lossy(), max_results))?;
+        self.collect_found(batches, cancelled)
+    }
+
     fn create_dir(&self, path: &Path) -> io::Result<()> {
         let params = serde_json::json!({"path": path.to_string_lossy()});
         self.channel
@@ -433,6 +1073,31 @@ impl FileSystem for RemoteFileSystem {
         0
     }
 
+    fn watch_changes(
+        &self,
+        path: &Path,
+        on_change: Box<dyn Fn() + Send + Sync>,
+    ) -> io::Result<FileWatch> {
+        let mut events = self.watch(path)?;
+        // Dropping the sender ends the forwarding task, and with it the
+        // agent-side watch
+        let (stop_tx, mut stop_rx) = oneshot::channel::<()>();
+        self.channel.runtime_handle().spawn(async move {
+            loop {
+                tokio::select! {
+                    event = events.recv() => {
+                        if event.is_none() {
+                            return;
+                        }
+                        on_change();
+                    }
+                    _ = &mut stop_rx => return,
+                }
+            }
+        });
+        Ok(FileWatch::new(stop_tx))
+    }
+
     fn remote_connection_info(&self) -> Option<&str> {
         Some(&self.connection_string)
     }
@@ -545,11 +1210,26 @@ impl Write for RemoteFileWriter {
 
 impl FileWriter for RemoteFileWriter {
     fn sync_all(&self) -> io::Result<()> {
-        let path_str = self.path.to_string_lossy();
-        self.channel
-            .request_blocking("write", write_params(&path_str, &self.buffer))
-            .map_err(RemoteFileSystem::to_io_error)?;
-        Ok(())
+        write_verified(&self.channel, &self.path.to_string_lossy(), &self.buffer)
+    }
+}
+
+/// Write a whole file, resending if the agent reports the payload failed
+/// its checksum.
+fn write_verified(channel: &AgentChannel, path: &str, data: &[u8]) -> io::Result<()> {
+    let mut attempt = 0;
+    loop {
+        let compress = channel.compression_enabled();
+        match channel.request_blocking("write", write_params(path, data, compress)) {
+            Ok(_) => return Ok(()),
+            Err(ChannelError::Remote(msg))
+                if msg.contains("checksum mismatch") && attempt < CHUNK_RETRIES =>
+            {
+                tracing::warn!("checksum mismatch writing {}, retrying", path);
+                attempt += 1;
+            }
+            Err(e) => return Err(RemoteFileSystem::to_io_error(e)),
+        }
     }
 }
 
@@ -609,6 +1289,7 @@ mod tests {
             dir: false,
             file: true,
             link: false,
+            target: None,
         };
 
         let meta = RemoteFileSystem::convert_metadata(&rm, "test.txt");
@@ -639,4 +1320,41 @@ mod tests {
         assert_eq!(entry.entry_type, EntryType::File);
         assert!(!entry.is_symlink());
     }
+
+    #[test]
+    fn test_corrupted_chunks_are_reread() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let Ok(channel) = rt.block_on(crate::services::remote::spawn_local_agent()) else {
+            eprintln!("Skipping test: could not spawn local agent");
+            return;
+        };
+        let fs = RemoteFileSystem::new(channel.clone(), "test@localhost".to_string());
+
+        // Several agent chunks, so a corrupted chunk sits at a non-zero offset
+        let path = temp_dir.path().join("checked.bin");
+        let content: Vec<u8> = (0..300_000u32).map(|i| (i % 253) as u8).collect();
+        std::fs::write(&path, &content).unwrap();
+
+        // A couple of bad chunks are repaired by re-reading them
+        channel.corrupt_next_chunks(2);
+        assert_eq!(fs.read_file(&path).unwrap(), content);
+        channel.corrupt_next_chunks(2);
+        assert_eq!(
+            fs.read_range(&path, 70_000, 100_000).unwrap(),
+            &content[70_000..170_000]
+        );
+
+        // Persistent corruption fails with the offending offset
+        channel.corrupt_next_chunks(u64::MAX);
+        let err = fs.read_range(&path, 131_072, 1000).unwrap_err();
+        channel.corrupt_next_chunks(0);
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(
+            err.to_string().contains("offset 131072"),
+            "Error should name the offset: {}",
+            err
+        );
+        assert_eq!(fs.read_file(&path).unwrap(), content);
+    }
 }
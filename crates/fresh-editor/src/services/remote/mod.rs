@@ -9,22 +9,29 @@ mod filesystem;
 mod protocol;
 mod spawner;
 
-pub use channel::AgentChannel;
 /// Test-only global: microseconds to sleep per chunk in the consumer loop.
 /// Defaults to 0 (no delay). Set non-zero from tests to simulate slow consumers.
 #[doc(hidden)]
 pub use channel::TEST_RECV_DELAY_US;
+pub use channel::{AgentChannel, AgentTransport, ReconnectFuture, Reconnector};
 /// Re-export for integration tests - spawns a local agent without SSH
 #[doc(hidden)]
 pub use connection::spawn_local_agent;
 /// Like `spawn_local_agent` but with a custom data channel capacity.
 #[doc(hidden)]
 pub use connection::spawn_local_agent_with_capacity;
-pub use connection::{ConnectionParams, SshConnection};
-pub use filesystem::RemoteFileSystem;
+pub use connection::{
+    start_keepalive, ConnectionParams, ReconnectPolicy, SshConnection, DEFAULT_KEEPALIVE_INTERVAL,
+};
+pub use filesystem::{
+    FileChangeEvent, FileChangeKind, RemoteFileSystem, SecretString, SudoPasswordRejected,
+};
 pub use protocol::{
-    decode_base64, encode_base64, ls_params, read_params, stat_params, sudo_write_params,
-    write_params, AgentRequest, AgentResponse,
+    count_lines_params, decode_base64, encode_base64, find_params, gzip_compress, gzip_decompress,
+    hello_params, ls_params, read_params, readlink_params, remove_params, stat_many_params,
+    stat_params, statfs_params, sudo_write_params, sudo_write_password_params, watch_params,
+    write_params, AgentRequest, AgentResponse, Capabilities, LineCount, FEATURE_COUNT_LINES,
+    FEATURE_FIND, FEATURE_GZIP, FEATURE_STAT_MANY, FEATURE_WATCH,
 };
 pub use spawner::{
     LocalProcessSpawner, ProcessSpawner, RemoteProcessSpawner, SpawnError, SpawnResult,
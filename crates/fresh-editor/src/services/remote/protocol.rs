@@ -5,10 +5,28 @@
 
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use serde::{Deserialize, Serialize};
+use std::io::{self, Read, Write};
+use zeroize::Zeroizing;
 
 /// Protocol version
 pub const PROTOCOL_VERSION: u32 = 1;
 
+/// Capability advertised by agents that accept the `compress` flag on
+/// read/write requests.
+pub const FEATURE_GZIP: &str = "gzip";
+
+/// Capability advertised by agents that handle `stat_many` batches.
+pub const FEATURE_STAT_MANY: &str = "stat_many";
+
+/// Capability advertised by agents that handle server-side `find`.
+pub const FEATURE_FIND: &str = "find";
+
+/// Capability advertised by agents that handle `watch` streams.
+pub const FEATURE_WATCH: &str = "watch";
+
+/// Capability advertised by agents that handle server-side `count_lines`.
+pub const FEATURE_COUNT_LINES: &str = "count_lines";
+
 /// Request sent to the agent
 #[derive(Debug, Clone, Serialize)]
 pub struct AgentRequest {
@@ -50,6 +68,9 @@ pub struct AgentResponse {
     pub ok: Option<bool>,
     #[serde(rename = "v")]
     pub version: Option<u32>,
+    /// Optional protocol features advertised in the ready message.
+    /// Absent on older agents, which support none of them.
+    pub caps: Option<Vec<String>>,
 }
 
 impl AgentResponse {
@@ -67,6 +88,40 @@ impl AgentResponse {
     pub fn is_final(&self) -> bool {
         self.result.is_some() || self.error.is_some()
     }
+
+    /// Check if the ready message advertises an optional feature
+    pub fn supports(&self, feature: &str) -> bool {
+        self.caps
+            .as_ref()
+            .is_some_and(|caps| caps.iter().any(|c| c == feature))
+    }
+}
+
+/// Capabilities negotiated with the agent via the `hello` handshake
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct Capabilities {
+    /// Agent protocol version
+    #[serde(rename = "v")]
+    pub version: u32,
+    /// Optional protocol features the agent handles
+    #[serde(default)]
+    pub features: Vec<String>,
+}
+
+impl Capabilities {
+    /// Capabilities advertised in the ready message, for agents that
+    /// predate the `hello` handshake
+    pub fn from_ready(ready: &AgentResponse) -> Self {
+        Self {
+            version: ready.version.unwrap_or(0),
+            features: ready.caps.clone().unwrap_or_default(),
+        }
+    }
+
+    /// Check if the agent handles an optional feature
+    pub fn supports(&self, feature: &str) -> bool {
+        self.features.iter().any(|f| f == feature)
+    }
 }
 
 /// Directory entry returned by `ls` command
@@ -108,6 +163,26 @@ pub struct RemoteMetadata {
     pub file: bool,
     #[serde(default)]
     pub link: bool,
+    /// Symlink target, present when `link` is set
+    #[serde(default)]
+    pub target: Option<String>,
+}
+
+/// Newline count of a file, computed on the remote host
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct LineCount {
+    /// Number of `\n` bytes in the file
+    pub newlines: usize,
+    /// Whether the file ends with `\n` (false for an empty file)
+    pub trailing_newline: bool,
+}
+
+impl LineCount {
+    /// Number of lines as the editor counts them: the text after the last
+    /// newline is a line too, even when empty.
+    pub fn lines(&self) -> usize {
+        self.newlines + 1
+    }
 }
 
 /// Process execution result
@@ -137,8 +212,34 @@ pub fn decode_base64(s: &str) -> Result<Vec<u8>, base64::DecodeError> {
     BASE64.decode(s)
 }
 
+/// Gzip-compress a payload for the data channel
+pub fn gzip_compress(data: &[u8]) -> Vec<u8> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+    // Writing into a Vec cannot fail
+    encoder
+        .write_all(data)
+        .and_then(|()| encoder.finish())
+        .expect("in-memory gzip compression failed")
+}
+
+/// Decompress a gzip payload received over the data channel
+pub fn gzip_decompress(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    flate2::read::GzDecoder::new(data).read_to_end(&mut out)?;
+    Ok(out)
+}
+
 /// Build params for read request
-pub fn read_params(path: &str, offset: Option<u64>, len: Option<usize>) -> serde_json::Value {
+///
+/// With `compress`, the agent gzips each chunk before base64-encoding it and
+/// marks the chunk with `"z": true`. Only send it to agents advertising
+/// [`FEATURE_GZIP`]; older agents ignore the flag and send raw chunks.
+pub fn read_params(
+    path: &str,
+    offset: Option<u64>,
+    len: Option<usize>,
+    compress: bool,
+) -> serde_json::Value {
     let mut params = serde_json::json!({"path": path});
     if let Some(off) = offset {
         params["off"] = serde_json::json!(off);
@@ -146,15 +247,70 @@ pub fn read_params(path: &str, offset: Option<u64>, len: Option<usize>) -> serde
     if let Some(l) = len {
         params["len"] = serde_json::json!(l);
     }
+    if compress {
+        params["compress"] = serde_json::json!(true);
+    }
     params
 }
 
 /// Build params for write request
-pub fn write_params(path: &str, data: &[u8]) -> serde_json::Value {
-    serde_json::json!({
+///
+/// With `compress`, the payload is gzipped before base64-encoding. Only use
+/// it with agents advertising [`FEATURE_GZIP`]. The `crc` of the uncompressed
+/// payload lets the agent reject data corrupted in transit.
+pub fn write_params(path: &str, data: &[u8], compress: bool) -> serde_json::Value {
+    if compress {
+        serde_json::json!({
+            "path": path,
+            "data": encode_base64(&gzip_compress(data)),
+            "compress": true,
+            "crc": checksum(data)
+        })
+    } else {
+        serde_json::json!({
+            "path": path,
+            "data": encode_base64(data),
+            "crc": checksum(data)
+        })
+    }
+}
+
+/// CRC32 of a transferred payload, matching Python's `zlib.crc32`
+pub fn checksum(data: &[u8]) -> u32 {
+    let mut crc = flate2::Crc::new();
+    crc.update(data);
+    crc.sum()
+}
+
+/// Prefix of the agent error returned when sudo refuses the password
+pub const SUDO_PASSWORD_REJECTED: &str = "sudo password rejected";
+
+/// Build serialized params for sudo_write request authenticated with a
+/// password
+///
+/// The params are built in a buffer that is wiped on drop, so no copy of
+/// the password outlives the request; send them with
+/// [`AgentChannel::request_secret`](super::AgentChannel::request_secret).
+/// The agent pipes the password to `sudo -S` and keeps the existing file's
+/// ownership and permissions (the copy writes in place).
+pub fn sudo_write_password_params(path: &str, data: &[u8], password: &str) -> Zeroizing<String> {
+    let body = serde_json::json!({
         "path": path,
         "data": encode_base64(data)
     })
+    .to_string();
+    // Escaping at most turns each byte into `\u00XX`; reserving that up front
+    // keeps the buffer from reallocating and leaving an unwiped copy behind
+    let mut escaped = Zeroizing::new(Vec::with_capacity(password.len() * 6 + 2));
+    serde_json::to_writer(&mut *escaped, password).expect("a string always serializes");
+    let escaped = std::str::from_utf8(&escaped).expect("serialized JSON is UTF-8");
+
+    let mut params = Zeroizing::new(String::with_capacity(body.len() + escaped.len() + 12));
+    params.push_str(&body[..body.len() - 1]);
+    params.push_str(",\"password\":");
+    params.push_str(escaped);
+    params.push('}');
+    params
 }
 
 /// Build params for sudo_write request (write file as root)
@@ -174,6 +330,11 @@ pub fn sudo_write_params(
     })
 }
 
+/// Build params for the hello handshake request
+pub fn hello_params(client_version: u32) -> serde_json::Value {
+    serde_json::json!({"client_version": client_version})
+}
+
 /// Build params for stat request
 pub fn stat_params(path: &str, follow_symlinks: bool) -> serde_json::Value {
     serde_json::json!({
@@ -182,11 +343,79 @@ pub fn stat_params(path: &str, follow_symlinks: bool) -> serde_json::Value {
     })
 }
 
+/// Build params for readlink request
+pub fn readlink_params(path: &str) -> serde_json::Value {
+    serde_json::json!({
+        "path": path
+    })
+}
+
+/// Build params for statfs request
+pub fn statfs_params(path: &str) -> serde_json::Value {
+    serde_json::json!({
+        "path": path
+    })
+}
+
+/// Build params for stat_many request
+pub fn stat_many_params(paths: &[&str], follow_symlinks: bool) -> serde_json::Value {
+    serde_json::json!({
+        "paths": paths,
+        "link": follow_symlinks
+    })
+}
+
 /// Build params for ls request
 pub fn ls_params(path: &str) -> serde_json::Value {
     serde_json::json!({"path": path})
 }
 
+/// Build params for watch request
+///
+/// With `force_poll`, the agent uses stat polling even if inotify works.
+pub fn watch_params(path: &str, debounce_ms: u64, force_poll: bool) -> serde_json::Value {
+    serde_json::json!({
+        "path": path,
+        "debounce": debounce_ms,
+        "poll": force_poll
+    })
+}
+
+/// Default directory depth limit for find requests
+pub const FIND_MAX_DEPTH: usize = 32;
+
+/// Build params for count_lines request
+pub fn count_lines_params(path: &str) -> serde_json::Value {
+    serde_json::json!({
+        "path": path
+    })
+}
+
+/// Build params for find request
+///
+/// `max_results` and `max_depth` of 0 mean unlimited.
+pub fn find_params(
+    root: &str,
+    pattern: &str,
+    max_results: usize,
+    max_depth: usize,
+) -> serde_json::Value {
+    serde_json::json!({
+        "root": root,
+        "pattern": pattern,
+        "max": max_results,
+        "depth": max_depth
+    })
+}
+
+/// Build params for a find request that lists the files git shows for the
+/// work tree at `root` instead of walking it
+pub fn git_files_params(root: &str, max_results: usize) -> serde_json::Value {
+    let mut params = find_params(root, "**", max_results, 0);
+    params["git"] = serde_json::Value::Bool(true);
+    params
+}
+
 /// Build params for exec request
 ///
 /// NOTE: Used by RemoteProcessSpawner, appears unused until spawner integration.
@@ -222,6 +451,17 @@ pub fn truncate_params(path: &str, len: u64) -> serde_json::Value {
     })
 }
 
+/// Build params for remove request
+///
+/// With `recursive`, directories are removed along with their contents.
+/// Without it, only files and empty directories can be removed.
+pub fn remove_params(path: &str, recursive: bool) -> serde_json::Value {
+    serde_json::json!({
+        "path": path,
+        "recursive": recursive
+    })
+}
+
 /// A single operation in a patch recipe
 #[derive(Debug, Clone, Serialize)]
 #[serde(untagged)]
@@ -319,6 +559,43 @@ mod tests {
         assert_eq!(data.as_slice(), decoded.as_slice());
     }
 
+    #[test]
+    fn test_gzip_roundtrip() {
+        let data = b"abcabcabc".repeat(1000);
+        let compressed = gzip_compress(&data);
+        assert!(compressed.len() < data.len());
+        assert_eq!(gzip_decompress(&compressed).unwrap(), data);
+        assert!(gzip_decompress(b"not gzip").is_err());
+    }
+
+    #[test]
+    fn test_ready_capabilities() {
+        let old = r#"{"id":0,"ok":true,"v":1}"#;
+        let resp: AgentResponse = serde_json::from_str(old).unwrap();
+        assert!(!resp.supports(FEATURE_GZIP));
+
+        let new = r#"{"id":0,"ok":true,"v":1,"caps":["gzip"]}"#;
+        let resp: AgentResponse = serde_json::from_str(new).unwrap();
+        assert!(resp.supports(FEATURE_GZIP));
+        assert!(!resp.supports("find"));
+    }
+
+    #[test]
+    fn test_hello_capabilities() {
+        let caps: Capabilities =
+            serde_json::from_str(r#"{"v":1,"features":["gzip","find"]}"#).unwrap();
+        assert_eq!(caps.version, 1);
+        assert!(caps.supports(FEATURE_FIND));
+        assert!(!caps.supports(FEATURE_WATCH));
+
+        // Agents without `hello` fall back to the ready message
+        let ready: AgentResponse =
+            serde_json::from_str(r#"{"id":0,"ok":true,"v":1,"caps":["gzip"]}"#).unwrap();
+        let caps = Capabilities::from_ready(&ready);
+        assert_eq!(caps.features, vec!["gzip".to_string()]);
+        assert!(!caps.supports(FEATURE_STAT_MANY));
+    }
+
     #[test]
     fn test_patch_op_copy_serialization() {
         let op = PatchOp::copy(100, 500);
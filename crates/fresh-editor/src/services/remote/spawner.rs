@@ -28,7 +28,12 @@ pub struct SpawnResult {
 #[derive(Debug, thiserror::Error)]
 pub enum SpawnError {
     #[error("Channel error: {0}")]
-    Channel(#[from] ChannelError),
+    Channel(ChannelError),
+
+    /// The connection dropped while the request was in flight; the agent may
+    /// or may not have acted on it, so the caller decides whether to retry.
+    #[error("Connection lost")]
+    ConnectionLost,
 
     #[error("Process error: {0}")]
     Process(String),
@@ -37,6 +42,15 @@ pub enum SpawnError {
     Decode(String),
 }
 
+impl From<ChannelError> for SpawnError {
+    fn from(e: ChannelError) -> Self {
+        match e {
+            ChannelError::ConnectionLost => SpawnError::ConnectionLost,
+            e => SpawnError::Channel(e),
+        }
+    }
+}
+
 /// Trait for spawning processes (local or remote)
 ///
 /// NOTE: Unused until process spawner is integrated with Editor.
@@ -133,7 +147,13 @@ impl ProcessSpawner for RemoteProcessSpawner {
         let result = result_rx
             .await
             .map_err(|_| SpawnError::Channel(ChannelError::ChannelClosed))?
-            .map_err(|e| SpawnError::Process(e))?;
+            .map_err(|e| {
+                if self.channel.is_connected() {
+                    SpawnError::Process(e)
+                } else {
+                    SpawnError::ConnectionLost
+                }
+            })?;
 
         let exit_code = result
             .get("code")
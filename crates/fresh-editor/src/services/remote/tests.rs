@@ -88,22 +88,61 @@ fn test_base64_empty() {
 
 #[test]
 fn test_read_params() {
-    let params = read_params("/path/to/file", None, None);
+    let params = read_params("/path/to/file", None, None, false);
     assert_eq!(params["path"], "/path/to/file");
     assert!(params.get("off").is_none());
     assert!(params.get("len").is_none());
+    // Uncompressed requests must look exactly like they did for older agents
+    assert!(params.get("compress").is_none());
 
-    let params = read_params("/path/to/file", Some(100), Some(50));
+    let params = read_params("/path/to/file", Some(100), Some(50), false);
     assert_eq!(params["path"], "/path/to/file");
     assert_eq!(params["off"], 100);
     assert_eq!(params["len"], 50);
+
+    let params = read_params("/path/to/file", None, None, true);
+    assert_eq!(params["compress"], true);
 }
 
 #[test]
 fn test_write_params() {
-    let params = write_params("/path/to/file", b"Hello");
+    let params = write_params("/path/to/file", b"Hello", false);
     assert_eq!(params["path"], "/path/to/file");
     assert_eq!(params["data"], encode_base64(b"Hello"));
+    assert!(params.get("compress").is_none());
+
+    let params = write_params("/path/to/file", b"Hello", true);
+    assert_eq!(params["compress"], true);
+    let compressed = decode_base64(params["data"].as_str().unwrap()).unwrap();
+    assert_eq!(gzip_decompress(&compressed).unwrap(), b"Hello");
+    // The checksum covers the uncompressed payload in both forms
+    assert_eq!(params["crc"], checksum(b"Hello"));
+}
+
+#[test]
+fn test_sudo_write_password_params() {
+    let params = sudo_write_password_params("/etc/hosts", b"127.0.0.1 localhost\n", "hun\"ter2");
+    let params: serde_json::Value = serde_json::from_str(&params).unwrap();
+    assert_eq!(params["path"], "/etc/hosts");
+    assert_eq!(params["data"], encode_base64(b"127.0.0.1 localhost\n"));
+    assert_eq!(params["password"], "hun\"ter2");
+    // Ownership and permissions are left to the in-place write
+    assert!(params.get("mode").is_none());
+    assert!(params.get("uid").is_none());
+}
+
+#[test]
+fn test_secret_string_debug_is_redacted() {
+    let secret = super::SecretString::from("hunter2".to_string());
+    assert_eq!(secret.expose_secret(), "hunter2");
+    assert!(!format!("{:?}", secret).contains("hunter2"));
+}
+
+#[test]
+fn test_checksum_is_crc32() {
+    // Standard CRC-32 check value, as produced by Python's zlib.crc32
+    assert_eq!(checksum(b"123456789"), 0xCBF4_3926);
+    assert_eq!(checksum(b""), 0);
 }
 
 #[test]
@@ -116,6 +155,63 @@ fn test_stat_params() {
     assert_eq!(params["link"], false);
 }
 
+#[test]
+fn test_readlink_params() {
+    let params = readlink_params("/path/to/link");
+    assert_eq!(params["path"], "/path/to/link");
+}
+
+#[test]
+fn test_statfs_params() {
+    let params = statfs_params("/path/to/file");
+    assert_eq!(params["path"], "/path/to/file");
+}
+
+#[test]
+fn test_count_lines_params() {
+    let params = count_lines_params("/path/to/file");
+    assert_eq!(params["path"], "/path/to/file");
+}
+
+#[test]
+fn test_line_count_lines() {
+    let count: LineCount =
+        serde_json::from_value(serde_json::json!({"newlines": 2, "trailing_newline": true}))
+            .unwrap();
+    assert_eq!(count.newlines, 2);
+    assert!(count.trailing_newline);
+    assert_eq!(count.lines(), 3);
+}
+
+#[test]
+fn test_stat_many_params() {
+    let params = stat_many_params(&["/a", "/b/c"], true);
+    assert_eq!(params["paths"], serde_json::json!(["/a", "/b/c"]));
+    assert_eq!(params["link"], true);
+}
+
+#[test]
+fn test_find_params() {
+    let params = find_params("/project", "**/*.rs", 100, FIND_MAX_DEPTH);
+    assert_eq!(params["root"], "/project");
+    assert_eq!(params["pattern"], "**/*.rs");
+    assert_eq!(params["max"], 100);
+    assert_eq!(params["depth"], FIND_MAX_DEPTH);
+
+    let params = git_files_params("/project", 100);
+    assert_eq!(params["git"], true);
+    assert_eq!(params["pattern"], "**");
+    assert_eq!(params["depth"], 0);
+}
+
+#[test]
+fn test_watch_params() {
+    let params = watch_params("/etc/hosts", 100, false);
+    assert_eq!(params["path"], "/etc/hosts");
+    assert_eq!(params["debounce"], 100);
+    assert_eq!(params["poll"], false);
+}
+
 #[test]
 fn test_exec_params() {
     let params = exec_params(
@@ -132,6 +228,16 @@ fn test_exec_params() {
     assert!(params.get("cwd").is_none());
 }
 
+#[test]
+fn test_remove_params() {
+    let params = remove_params("/path/to/dir", true);
+    assert_eq!(params["path"], "/path/to/dir");
+    assert_eq!(params["recursive"], true);
+
+    let params = remove_params("/path/to/file", false);
+    assert_eq!(params["recursive"], false);
+}
+
 #[test]
 fn test_cancel_params() {
     let params = cancel_params(42);
@@ -212,6 +318,8 @@ fn test_connection_params_to_string() {
         host: "server.com".to_string(),
         port: None,
         identity_file: None,
+        reconnect: Default::default(),
+        keepalive_interval: None,
     };
     assert_eq!(params.to_string(), "alice@server.com");
 
@@ -220,6 +328,8 @@ fn test_connection_params_to_string() {
         host: "example.org".to_string(),
         port: Some(2222),
         identity_file: None,
+        reconnect: Default::default(),
+        keepalive_interval: None,
     };
     assert_eq!(params.to_string(), "bob@example.org:2222");
 }
@@ -64,6 +64,14 @@ pub struct BufferSettings {
     /// Used for visual display of tab characters and indent calculations.
     /// Set based on language config; can be changed per-buffer by user
     pub tab_size: usize,
+
+    /// Per-buffer override for trimming trailing whitespace on save.
+    /// None follows `editor.trim_trailing_whitespace_on_save`
+    pub trim_trailing_whitespace_on_save: Option<bool>,
+
+    /// Per-buffer override for ensuring a final newline on save.
+    /// None follows `editor.ensure_final_newline_on_save`
+    pub ensure_final_newline_on_save: Option<bool>,
 }
 
 impl Default for BufferSettings {
@@ -72,6 +80,8 @@ impl Default for BufferSettings {
             show_whitespace_tabs: true,
             use_tabs: false,
             tab_size: 4,
+            trim_trailing_whitespace_on_save: None,
+            ensure_final_newline_on_save: None,
         }
     }
 }
@@ -409,9 +419,8 @@ impl EditorState {
         // Insert text into buffer
         self.buffer.insert(position, text);
 
-        // Invalidate highlight cache for edited range
-        self.highlighter
-            .invalidate_range(position..position + text.len());
+        // Re-highlight the edited lines on the next render
+        self.highlighter.invalidate_edit(position, 0, text.len());
 
         // Note: reference_highlight_overlay uses markers that auto-adjust,
         // so no manual invalidation needed
@@ -458,8 +467,8 @@ impl EditorState {
         // Delete from buffer
         self.buffer.delete(range.clone());
 
-        // Invalidate highlight cache for edited range
-        self.highlighter.invalidate_range(range.clone());
+        // Re-highlight the edited lines on the next render
+        self.highlighter.invalidate_edit(range.start, len, 0);
 
         // Note: reference_highlight_overlay uses markers that auto-adjust,
         // so no manual invalidation needed
@@ -1410,8 +1419,8 @@ mod tests {
                 new_position: 2,
                 old_anchor: None,
                 new_anchor: None,
-                old_sticky_column: 0,
-                new_sticky_column: 0,
+                old_sticky_column: None,
+                new_sticky_column: None,
             },
         );
 
@@ -1,7 +1,8 @@
 //! Bracket matching highlight using the overlay system
 //!
 //! This module manages bracket pair highlighting through overlays.
-//! When the cursor is on a bracket, the matching bracket is highlighted.
+//! When the cursor is on or just after a bracket, the matching bracket is
+//! highlighted.
 //! Optional rainbow colors can be applied based on nesting depth.
 
 use crate::model::buffer::Buffer;
@@ -52,6 +53,15 @@ fn get_bracket_pair(ch: char) -> Option<(char, char, bool)> {
     None
 }
 
+/// The bracket pair info for the byte at `pos`, if it is a bracket
+fn bracket_at(buffer: &Buffer, pos: usize) -> Option<(char, char, bool)> {
+    if pos >= buffer.len() {
+        return None;
+    }
+    let bytes = buffer.slice_bytes(pos..pos + 1);
+    bytes.first().and_then(|&b| get_bracket_pair(b as char))
+}
+
 /// Manager for bracket highlight overlays
 pub struct BracketHighlightOverlay {
     /// Whether bracket highlighting is enabled
@@ -78,6 +88,25 @@ impl BracketHighlightOverlay {
         }
     }
 
+    /// Apply the `highlight_matching_brackets` and `rainbow_brackets` settings
+    ///
+    /// Highlights are cleared when disabled and recomputed on the next update
+    /// when either setting changes.
+    pub fn configure(
+        &mut self,
+        enabled: bool,
+        rainbow_enabled: bool,
+        overlays: &mut OverlayManager,
+        marker_list: &mut MarkerList,
+    ) {
+        if self.enabled == enabled && self.rainbow_enabled == rainbow_enabled {
+            return;
+        }
+        self.enabled = enabled;
+        self.rainbow_enabled = rainbow_enabled;
+        self.clear(overlays, marker_list);
+    }
+
     /// Update bracket highlights based on cursor position
     ///
     /// Returns true if overlays were updated
@@ -102,35 +131,26 @@ impl BracketHighlightOverlay {
         let ns = bracket_highlight_namespace();
         overlays.clear_namespace(&ns, marker_list);
 
-        // Check if cursor is on a bracket
-        let buf_len = buffer.len();
-        if cursor_position >= buf_len {
-            return true;
-        }
-
-        let bytes = buffer.slice_bytes(cursor_position..cursor_position + 1);
-        if bytes.is_empty() {
-            return true;
-        }
-
-        let ch = bytes[0] as char;
-
-        // Get bracket pair info
-        let (opening, closing, forward) = match get_bracket_pair(ch) {
-            Some(pair) => pair,
-            None => return true, // Not on a bracket
+        // Prefer a bracket under the cursor, then one just before it
+        let Some((bracket_pos, (opening, closing, forward))) =
+            [Some(cursor_position), cursor_position.checked_sub(1)]
+                .into_iter()
+                .flatten()
+                .find_map(|pos| bracket_at(buffer, pos).map(|pair| (pos, pair)))
+        else {
+            return true; // Not next to a bracket
         };
 
-        // Calculate nesting depth at cursor position for rainbow colors
+        // Calculate nesting depth at the bracket for rainbow colors
         let depth = if self.rainbow_enabled {
-            self.calculate_nesting_depth(buffer, cursor_position, opening, closing, forward)
+            self.calculate_nesting_depth(buffer, bracket_pos, opening, closing, forward)
         } else {
             0
         };
 
         // Find matching bracket
         let matching_pos =
-            self.find_matching_bracket(buffer, cursor_position, opening, closing, forward);
+            self.find_matching_bracket(buffer, bracket_pos, opening, closing, forward);
 
         // Determine color based on depth
         let color = if self.rainbow_enabled && !self.rainbow_colors.is_empty() {
@@ -139,11 +159,11 @@ impl BracketHighlightOverlay {
             self.match_color
         };
 
-        // Create overlay for the bracket at cursor
+        // Create overlay for the bracket next to the cursor
         let cursor_face = OverlayFace::Foreground { color };
         let cursor_overlay = Overlay::with_namespace(
             marker_list,
-            cursor_position..cursor_position + 1,
+            bracket_pos..bracket_pos + 1,
             cursor_face,
             ns.clone(),
         )
@@ -19,6 +19,7 @@
 //! - Concealment happens transparently at the editor level
 //! - Wrapping operates on the concealed (shorter) lines
 
+use crate::model::buffer::Buffer;
 use crate::model::marker::{MarkerId, MarkerList};
 use fresh_core::overlay::OverlayNamespace;
 use std::ops::Range;
@@ -186,6 +187,31 @@ impl ConcealManager {
         None
     }
 
+    /// Count the line breaks hidden by conceal ranges within a byte range.
+    ///
+    /// Each one joins two source lines into a single row (e.g. a folded
+    /// section), so rendering needs that many extra source lines to fill
+    /// the viewport.
+    pub fn concealed_line_breaks(
+        &self,
+        buffer: &Buffer,
+        range: Range<usize>,
+        marker_list: &MarkerList,
+    ) -> usize {
+        self.ranges
+            .iter()
+            .map(|r| r.range(marker_list))
+            .filter(|r| r.start < range.end && range.start < r.end)
+            .map(|r| {
+                buffer
+                    .slice_bytes(r.start.max(range.start)..r.end.min(range.end))
+                    .iter()
+                    .filter(|&&b| b == b'\n')
+                    .count()
+            })
+            .sum()
+    }
+
     /// Returns true if there are no conceal ranges
     pub fn is_empty(&self) -> bool {
         self.ranges.is_empty()
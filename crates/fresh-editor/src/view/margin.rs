@@ -425,6 +425,24 @@ impl MarginManager {
         }
     }
 
+    /// Markers and byte positions of the line indicators in a namespace,
+    /// ordered by position
+    pub fn line_indicator_positions(&self, namespace: &str) -> Vec<(MarkerId, usize)> {
+        let mut positions: Vec<(MarkerId, usize)> = self
+            .line_indicators
+            .iter()
+            .filter(|(_, indicators)| indicators.contains_key(namespace))
+            .filter_map(|(&marker_id, _)| {
+                let marker_id = MarkerId(marker_id);
+                self.indicator_markers
+                    .get_position(marker_id)
+                    .map(|position| (marker_id, position))
+            })
+            .collect();
+        positions.sort_by_key(|&(_, position)| position);
+        positions
+    }
+
     /// Get the line indicator for a specific line number
     ///
     /// This looks up all indicators whose markers resolve to the given line.
@@ -1003,4 +1021,35 @@ mod tests {
         assert!(manager.get_line_indicator(7, byte_to_line).is_some());
         assert!(manager.get_line_indicator(9, byte_to_line).is_some());
     }
+
+    #[test]
+    fn test_line_indicator_positions() {
+        let mut manager = MarginManager::new();
+
+        let bookmark = manager.set_line_indicator(
+            line_to_byte(7),
+            "bookmarks".to_string(),
+            LineIndicator::new("◆", Color::Blue, 15),
+        );
+        manager.set_line_indicator(
+            line_to_byte(2),
+            "bookmarks".to_string(),
+            LineIndicator::new("◆", Color::Blue, 15),
+        );
+        manager.set_line_indicator(
+            line_to_byte(4),
+            "git-gutter".to_string(),
+            LineIndicator::new("│", Color::Green, 10),
+        );
+        manager.adjust_for_insert(0, 10);
+
+        // Only the namespace's indicators, by position, after the edit
+        let positions = manager.line_indicator_positions("bookmarks");
+        let lines: Vec<usize> = positions
+            .iter()
+            .map(|&(_, pos)| byte_to_line(pos))
+            .collect();
+        assert_eq!(lines, vec![3, 8]);
+        assert_eq!(positions[1].0, bookmark);
+    }
 }
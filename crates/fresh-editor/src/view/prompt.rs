@@ -19,14 +19,25 @@ pub enum PromptType {
     ReloadWithEncoding,
     /// Switch to a different project folder (change working directory)
     SwitchProject,
+    /// Name under which to save the current workspace as a session
+    SaveWorkspaceAs,
+    /// Pick a named workspace session to switch to
+    SwitchWorkspace,
+    /// Confirm discarding modified buffers before switching workspace
+    ConfirmSwitchWorkspace { name: String },
     /// Save current buffer to a new file
     SaveFileAs,
     /// Search for text in buffer
     Search,
+    /// Search as you type, jumping to the nearest match (Enter cycles matches)
+    IncrementalSearch,
     /// Search for text in buffer (for replace operation - will prompt for replacement after)
     ReplaceSearch,
-    /// Replace text in buffer
-    Replace { search: String },
+    /// Replace text in buffer, within `range` when replacing in a selection
+    Replace {
+        search: String,
+        range: Option<std::ops::Range<usize>>,
+    },
     /// Search for text in buffer (for query-replace - will prompt for replacement after)
     QueryReplaceSearch,
     /// Query replace text in buffer - prompt for replacement text
@@ -38,6 +49,8 @@ pub enum PromptType {
     /// Quick Open - unified prompt with prefix-based provider routing
     /// Supports file finding (default), commands (>), buffers (#), goto line (:)
     QuickOpen,
+    /// Search the text of every file in the project, listing matches
+    SearchInProject,
     /// Go to a specific line number
     GotoLine,
     /// Choose an ANSI background file
@@ -59,10 +72,16 @@ pub enum PromptType {
     RecordMacro,
     /// Play a macro - prompts for register (0-9)
     PlayMacro,
+    /// Play the last macro repeatedly - prompts for the repeat count
+    RepeatLastMacro,
     /// Set a bookmark - prompts for register (0-9)
     SetBookmark,
     /// Jump to a bookmark - prompts for register (0-9)
     JumpToBookmark,
+    /// Copy into a named clipboard register - prompts for register (a-z or ")
+    CopyToRegister,
+    /// Paste from a named clipboard register - prompts for register (a-z or ")
+    PasteFromRegister,
     /// Set compose width (empty clears to viewport)
     SetComposeWidth,
     /// Add a vertical ruler at a column position
@@ -73,8 +92,12 @@ pub enum PromptType {
     SetTabSize,
     /// Set line ending format for current buffer
     SetLineEnding,
+    /// Rewrite every line ending in the current buffer to one format
+    NormalizeLineEndings,
     /// Set text encoding format for current buffer
     SetEncoding,
+    /// Convert the current buffer to an encoding and save it
+    SaveWithEncoding,
     /// Set language/syntax highlighting for current buffer
     SetLanguage,
     /// Stop a running LSP server (select from list)
@@ -94,6 +117,11 @@ pub enum PromptType {
     ConfirmRevert,
     /// Confirm saving over a file that changed on disk
     ConfirmSaveConflict,
+    /// Keep a modified buffer, load its file that changed on disk, or
+    /// compare the two
+    ConfirmFileConflict {
+        buffer_id: crate::model::event::BufferId,
+    },
     /// Confirm saving with sudo after permission denied
     ConfirmSudoSave {
         info: crate::model::buffer::SudoSaveRequired,
@@ -107,6 +135,9 @@ pub enum PromptType {
     },
     /// Confirm quitting with modified buffers
     ConfirmQuitWithModified,
+    /// Recover or discard crash-recovered changes for a file restored from
+    /// the workspace
+    ConfirmRestoreRecovery { recovery_id: String },
     /// File Explorer rename operation
     /// Stores the original path and name for the file/directory being renamed
     FileExplorerRename {
@@ -130,6 +161,12 @@ pub enum PromptType {
     /// If replace is true, replace the input with the output
     /// If replace is false, output goes to a new buffer
     ShellCommand { replace: bool },
+    /// Align the selected lines on a delimiter
+    Align,
+    /// Pick a clipboard history entry to paste (select from list)
+    PasteFromHistory,
+    /// Path of a file to compare the active buffer with
+    CompareWithFile,
     /// Async prompt from plugin (for editor.prompt() API)
     /// The result is returned via callback resolution
     AsyncPrompt,
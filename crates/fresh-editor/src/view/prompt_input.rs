@@ -3,7 +3,7 @@
 //! Implements the InputHandler trait for Prompt, handling text editing,
 //! cursor movement, and suggestion navigation.
 
-use super::prompt::Prompt;
+use super::prompt::{Prompt, PromptType};
 use crate::input::handler::{DeferredAction, InputContext, InputHandler, InputResult};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
@@ -14,6 +14,10 @@ impl InputHandler for Prompt {
         let shift = event.modifiers.contains(KeyModifiers::SHIFT);
 
         match event.code {
+            // Shift+Enter steps back through incremental search matches via keybindings
+            KeyCode::Enter if shift && self.prompt_type == PromptType::IncrementalSearch => {
+                InputResult::Ignored
+            }
             // Confirmation and cancellation
             KeyCode::Enter => {
                 ctx.defer(DeferredAction::ConfirmPrompt);
@@ -138,13 +142,14 @@ impl InputHandler for Prompt {
                     if let Some(selected) = self.selected_suggestion {
                         let new_selected = if selected == 0 { 0 } else { selected - 1 };
                         self.selected_suggestion = Some(new_selected);
-                        // For non-plugin prompts (except QuickOpen), or plugin prompts
+                        // For non-plugin prompts (except QuickOpen and project search), or plugin prompts
                         // with sync_input_on_navigate, update input to match selected suggestion
                         let should_sync = self.sync_input_on_navigate
                             || !matches!(
                                 self.prompt_type,
                                 crate::view::prompt::PromptType::Plugin { .. }
                                     | crate::view::prompt::PromptType::QuickOpen
+                                    | crate::view::prompt::PromptType::SearchInProject
                             );
                         if should_sync {
                             if let Some(suggestion) = self.suggestions.get(new_selected) {
@@ -182,13 +187,14 @@ impl InputHandler for Prompt {
                     if let Some(selected) = self.selected_suggestion {
                         let new_selected = (selected + 1).min(self.suggestions.len() - 1);
                         self.selected_suggestion = Some(new_selected);
-                        // For non-plugin prompts (except QuickOpen), or plugin prompts
+                        // For non-plugin prompts (except QuickOpen and project search), or plugin prompts
                         // with sync_input_on_navigate, update input to match selected suggestion
                         let should_sync = self.sync_input_on_navigate
                             || !matches!(
                                 self.prompt_type,
                                 crate::view::prompt::PromptType::Plugin { .. }
                                     | crate::view::prompt::PromptType::QuickOpen
+                                    | crate::view::prompt::PromptType::SearchInProject
                             );
                         if should_sync {
                             if let Some(suggestion) = self.suggestions.get(new_selected) {
@@ -9,6 +9,7 @@ use crate::model::cursor::SelectionMode;
 use crate::model::event::{BufferId, EventLog, LeafId, SplitDirection};
 use crate::primitives::ansi::AnsiParser;
 use crate::primitives::ansi_background::AnsiBackground;
+use crate::primitives::block_selection::{block_line_range, block_rect};
 use crate::primitives::display_width::char_width;
 use crate::state::{EditorState, ViewMode};
 use crate::view::split::SplitManager;
@@ -33,6 +34,23 @@ use std::ops::Range;
 /// memory usage reasonable (~80KB per ViewLine instead of hundreds of MB).
 const MAX_SAFE_LINE_WIDTH: usize = 10_000;
 
+/// Byte ranges of a line (without its line ending) that deserve a whitespace
+/// warning: trailing spaces and tabs, and leading indentation that mixes them
+fn whitespace_warning_ranges(text: &[u8]) -> Vec<Range<usize>> {
+    let is_blank = |b: &u8| *b == b' ' || *b == b'\t';
+    let mut ranges = Vec::new();
+    let indent_end = text.iter().take_while(|b| is_blank(b)).count();
+    let indent = &text[..indent_end];
+    if indent_end < text.len() && indent.contains(&b' ') && indent.contains(&b'\t') {
+        ranges.push(0..indent_end);
+    }
+    let content_end = text.len() - text.iter().rev().take_while(|b| is_blank(b)).count();
+    if content_end < text.len() {
+        ranges.push(content_end..text.len());
+    }
+    ranges
+}
+
 /// Compute character-level diff between two strings, returning ranges of changed characters.
 /// Returns a tuple of (old_changed_ranges, new_changed_ranges) where each range indicates
 /// character indices that differ between the strings.
@@ -314,7 +332,6 @@ struct ComposeLayout {
 
 struct SelectionContext {
     ranges: Vec<Range<usize>>,
-    block_rects: Vec<(usize, usize, usize, usize)>,
     cursor_positions: Vec<usize>,
     primary_cursor_position: usize,
 }
@@ -324,7 +341,8 @@ struct DecorationContext {
     semantic_token_spans: Vec<crate::primitives::highlighter::HighlightSpan>,
     viewport_overlays: Vec<(crate::view::overlay::Overlay, Range<usize>)>,
     virtual_text_lookup: HashMap<usize, Vec<crate::view::virtual_text::VirtualText>>,
-    diagnostic_lines: HashSet<usize>,
+    /// Gutter marker color of each line with a diagnostic, by severity
+    diagnostic_lines: HashMap<usize, Color>,
     /// Line indicators indexed by line number (highest priority indicator per line)
     line_indicators: BTreeMap<usize, crate::view::margin::LineIndicator>,
 }
@@ -365,6 +383,25 @@ struct SplitLayout {
     horizontal_scrollbar_rect: Rect,
 }
 
+/// What a vertical scrollbar marker points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum ScrollbarMarkerKind {
+    /// A line modified since the last save
+    Change,
+    /// A line containing a search match
+    SearchMatch,
+}
+
+/// A marker drawn on one row of the vertical scrollbar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ScrollbarMarker {
+    /// Row within the scrollbar
+    row: usize,
+    kind: ScrollbarMarkerKind,
+    /// Start of the first marked line on this row
+    line_start: usize,
+}
+
 struct ViewPreferences {
     view_mode: ViewMode,
     compose_width: Option<u16>,
@@ -429,9 +466,11 @@ struct LeftMarginContext<'a> {
     state: &'a EditorState,
     theme: &'a crate::view::theme::Theme,
     is_continuation: bool,
+    /// Whether this row continues a soft-wrapped line (shows a wrap indicator)
+    is_wrapped: bool,
     current_source_line_num: usize,
     estimated_lines: usize,
-    diagnostic_lines: &'a HashSet<usize>,
+    diagnostic_lines: &'a HashMap<usize, Color>,
     /// Pre-computed line indicators (line_num -> indicator)
     line_indicators: &'a BTreeMap<usize, crate::view::margin::LineIndicator>,
     /// Line number where the primary cursor is located (for relative line numbers)
@@ -461,13 +500,13 @@ fn render_left_margin(
             Style::default(),
             None,
         );
-    } else if ctx.diagnostic_lines.contains(&ctx.current_source_line_num) {
+    } else if let Some(&color) = ctx.diagnostic_lines.get(&ctx.current_source_line_num) {
         // Diagnostic indicators have highest priority
         push_span_with_map(
             line_spans,
             line_view_map,
             "●".to_string(),
-            Style::default().fg(ratatui::style::Color::Red),
+            Style::default().fg(color),
             None,
         );
     } else if let Some(indicator) = ctx.line_indicators.get(&ctx.current_source_line_num) {
@@ -490,14 +529,19 @@ fn render_left_margin(
         );
     }
 
-    // Render line number (right-aligned) or blank for continuations
+    // Render line number (right-aligned), a wrap indicator for wrapped
+    // continuations, or blank for other non-source rows
     if ctx.is_continuation {
-        // For wrapped continuation lines, render blank space
-        let blank = " ".repeat(ctx.state.margins.left_config.width);
+        let marker = if ctx.is_wrapped { "↪" } else { "" };
+        let text = format!(
+            "{:>width$}",
+            marker,
+            width = ctx.state.margins.left_config.width
+        );
         push_span_with_map(
             line_spans,
             line_view_map,
-            blank,
+            text,
             Style::default().fg(ctx.theme.line_number_fg),
             None,
         );
@@ -740,6 +784,10 @@ impl SplitRenderer {
     /// * `line_wrap` - Whether line wrapping is enabled
     /// * `estimated_line_length` - Estimated average line length for large file line estimation
     /// * `hide_cursor` - Whether to hide the hardware cursor (e.g., when menu is open)
+    /// * `show_indent_guides` - Whether to draw guides at each indentation level
+    /// * `show_whitespace_warnings` - Whether to highlight trailing whitespace and mixed indentation
+    /// * `show_whitespace` - Whether to draw glyphs for spaces, tabs and line endings
+    /// * `search_matches` - Buffer with an active search and its match positions, marked on the scrollbar
     ///
     /// # Returns
     /// * Vec of (split_id, buffer_id, content_rect, scrollbar_rect, thumb_start, thumb_end) for mouse handling
@@ -777,6 +825,10 @@ impl SplitRenderer {
         session_mode: bool,
         show_vertical_scrollbar: bool,
         show_horizontal_scrollbar: bool,
+        show_indent_guides: bool,
+        show_whitespace_warnings: bool,
+        show_whitespace: bool,
+        search_matches: Option<(BufferId, &[usize])>,
     ) -> (
         Vec<(LeafId, BufferId, Rect, Rect, usize, usize)>,
         HashMap<LeafId, crate::view::ui::tabs::TabLayout>, // tab layouts per split
@@ -784,6 +836,7 @@ impl SplitRenderer {
         Vec<(LeafId, u16, u16, u16)>,                      // maximize split button areas
         HashMap<LeafId, Vec<ViewLineMapping>>,             // view line mappings for mouse clicks
         Vec<(LeafId, BufferId, Rect, usize, usize, usize)>, // horizontal scrollbar areas (rect + max_content_width + thumb_start + thumb_end)
+        HashMap<LeafId, Vec<(usize, usize)>>, // scrollbar markers (row, line start byte)
     ) {
         let _span = tracing::trace_span!("render_content").entered();
 
@@ -800,6 +853,7 @@ impl SplitRenderer {
         let mut close_split_areas = Vec::new();
         let mut maximize_split_areas = Vec::new();
         let mut view_line_mappings: HashMap<LeafId, Vec<ViewLineMapping>> = HashMap::new();
+        let mut scrollbar_markers: HashMap<LeafId, Vec<(usize, usize)>> = HashMap::new();
 
         // Render each split
         for (split_id, buffer_id, split_area) in visible_buffers {
@@ -1032,6 +1086,9 @@ impl SplitRenderer {
                     session_mode,
                     &view_prefs.rulers,
                     view_prefs.show_line_numbers,
+                    show_indent_guides,
+                    show_whitespace_warnings,
+                    show_whitespace,
                 );
 
                 // Store view line mappings for mouse click handling
@@ -1064,6 +1121,25 @@ impl SplitRenderer {
                     (0, 0)
                 };
 
+                // Mark changed lines and search matches on the scrollbar
+                if show_vertical_scrollbar && buffer_len <= large_file_threshold_bytes as usize {
+                    let matches = search_matches
+                        .filter(|(id, _)| *id == buffer_id)
+                        .map_or(&[][..], |(_, matches)| matches);
+                    let markers = Self::scrollbar_markers(
+                        state,
+                        &viewport,
+                        matches,
+                        total_lines,
+                        layout.scrollbar_rect.height as usize,
+                    );
+                    Self::render_scrollbar_markers(frame, &markers, layout.scrollbar_rect, theme);
+                    scrollbar_markers.insert(
+                        split_id,
+                        markers.iter().map(|m| (m.row, m.line_start)).collect(),
+                    );
+                }
+
                 // Compute the actual max line length for horizontal scrollbar
                 let max_content_width = if show_horizontal_scrollbar && !viewport.line_wrap_enabled
                 {
@@ -1142,6 +1218,7 @@ impl SplitRenderer {
             maximize_split_areas,
             view_line_mappings,
             horizontal_scrollbar_areas,
+            scrollbar_markers,
         )
     }
 
@@ -2068,7 +2145,7 @@ impl SplitRenderer {
         viewport: &crate::view::viewport::Viewport,
         buffer_len: usize,
     ) -> (usize, usize) {
-        use crate::primitives::line_wrapping::{wrap_line, WrapConfig};
+        use crate::primitives::line_wrapping::WrapConfig;
 
         if buffer_len == 0 {
             return (1, 0);
@@ -2102,18 +2179,10 @@ impl SplitRenderer {
                 found_top = true;
             }
 
-            // Get line content
-            let line_content = if let Some(bytes) = state.buffer.get_line(line_idx) {
-                String::from_utf8_lossy(&bytes)
-                    .trim_end_matches('\n')
-                    .trim_end_matches('\r')
-                    .to_string()
-            } else {
+            let Some(visual_rows_in_line) = Self::wrapped_row_count(state, line_idx, &wrap_config)
+            else {
                 break;
             };
-
-            let segments = wrap_line(&line_content, &wrap_config);
-            let visual_rows_in_line = segments.len().max(1);
             total_visual_rows += visual_rows_in_line;
         }
 
@@ -2128,6 +2197,132 @@ impl SplitRenderer {
         (total_visual_rows, top_visual_row)
     }
 
+    /// Number of visual rows a line takes when wrapped, or None past the end
+    fn wrapped_row_count(
+        state: &EditorState,
+        line_idx: usize,
+        wrap_config: &crate::primitives::line_wrapping::WrapConfig,
+    ) -> Option<usize> {
+        let bytes = state.buffer.get_line(line_idx)?;
+        let line_content = String::from_utf8_lossy(&bytes);
+        let line_content = line_content.trim_end_matches('\n').trim_end_matches('\r');
+        let segments = crate::primitives::line_wrapping::wrap_line(line_content, wrap_config);
+        Some(segments.len().max(1))
+    }
+
+    /// Scrollbar markers for lines modified since the last save and lines
+    /// with search matches
+    ///
+    /// Lines are placed in the same units as the thumb: logical lines, or
+    /// visual rows when wrapping is on. A row shows the first marked line
+    /// landing on it, and search matches win over changes.
+    fn scrollbar_markers(
+        state: &EditorState,
+        viewport: &crate::view::viewport::Viewport,
+        search_matches: &[usize],
+        total_lines: usize,
+        height: usize,
+    ) -> Vec<ScrollbarMarker> {
+        if height == 0 || state.buffer.line_count().is_none() {
+            return Vec::new();
+        }
+
+        let mut lines: Vec<(usize, ScrollbarMarkerKind)> = Vec::new();
+        if state.buffer.is_modified() {
+            let diff = state.buffer.diff_since_saved();
+            for range in diff.line_ranges.unwrap_or_default() {
+                let end = range.end.max(range.start + 1);
+                lines.extend((range.start..end).map(|line| (line, ScrollbarMarkerKind::Change)));
+            }
+        }
+        // Matches can be stale after an edit until the next search
+        let buffer_len = state.buffer.len();
+        lines.extend(
+            search_matches
+                .iter()
+                .filter(|&&pos| pos < buffer_len)
+                .map(|&pos| {
+                    (
+                        state.buffer.get_line_number(pos),
+                        ScrollbarMarkerKind::SearchMatch,
+                    )
+                }),
+        );
+        if lines.is_empty() {
+            return Vec::new();
+        }
+        lines.sort_unstable();
+        lines.dedup();
+
+        // Position of each marked line in scrollbar units
+        let positions: Vec<usize> = if viewport.line_wrap_enabled {
+            let gutter_width = viewport.gutter_width(&state.buffer);
+            let wrap_config = crate::primitives::line_wrapping::WrapConfig::new(
+                viewport.width as usize,
+                gutter_width,
+                true,
+            );
+            let mut row = 0;
+            let mut next_line = 0;
+            lines
+                .iter()
+                .map(|&(line, _)| {
+                    while next_line < line {
+                        row += Self::wrapped_row_count(state, next_line, &wrap_config).unwrap_or(1);
+                        next_line += 1;
+                    }
+                    row
+                })
+                .collect()
+        } else {
+            lines.iter().map(|&(line, _)| line).collect()
+        };
+
+        let total = total_lines.max(1);
+        let mut markers: Vec<ScrollbarMarker> = Vec::new();
+        for (&(line, kind), &position) in lines.iter().zip(&positions) {
+            let row = (position * height / total).min(height - 1);
+            let line_start = state.buffer.line_start_offset(line).unwrap_or(0);
+            match markers.iter_mut().find(|m| m.row == row) {
+                Some(marker) => {
+                    if kind == ScrollbarMarkerKind::SearchMatch
+                        && marker.kind == ScrollbarMarkerKind::Change
+                    {
+                        *marker = ScrollbarMarker {
+                            row,
+                            kind,
+                            line_start,
+                        };
+                    }
+                }
+                None => markers.push(ScrollbarMarker {
+                    row,
+                    kind,
+                    line_start,
+                }),
+            }
+        }
+        markers
+    }
+
+    /// Draw scrollbar markers over the track and thumb, keeping their background
+    fn render_scrollbar_markers(
+        frame: &mut Frame,
+        markers: &[ScrollbarMarker],
+        scrollbar_rect: Rect,
+        theme: &crate::view::theme::Theme,
+    ) {
+        for marker in markers {
+            let (symbol, color) = match marker.kind {
+                ScrollbarMarkerKind::Change => ("▐", theme.diff_modify_bg),
+                ScrollbarMarkerKind::SearchMatch => ("▬", theme.search_match_bg),
+            };
+            let cell =
+                &mut frame.buffer_mut()[(scrollbar_rect.x, scrollbar_rect.y + marker.row as u16)];
+            cell.set_symbol(symbol).set_fg(color);
+        }
+    }
+
     /// Render a scrollbar for a split
     /// Returns (thumb_start, thumb_end) positions for mouse hit testing
     #[allow(clippy::too_many_arguments)]
@@ -2376,6 +2571,19 @@ impl SplitRenderer {
         let is_binary = state.buffer.is_binary();
         let line_ending = state.buffer.line_ending();
 
+        // Conceals only apply in Compose mode; there they can hide whole lines
+        let is_compose = matches!(view_mode, ViewMode::Compose);
+        let visible_count = if is_compose {
+            Self::source_lines_to_fill(
+                state,
+                viewport.top_byte,
+                visible_count,
+                estimated_line_length,
+            )
+        } else {
+            visible_count
+        };
+
         // Build base token stream from source
         let base_tokens = Self::build_base_tokens(
             &mut state.buffer,
@@ -2392,7 +2600,6 @@ impl SplitRenderer {
 
         // Apply soft breaks — marker-based line wrapping that survives edits without flicker.
         // Only apply in Compose mode; Source mode shows the raw unwrapped text.
-        let is_compose = matches!(view_mode, ViewMode::Compose);
         if is_compose && !state.soft_breaks.is_empty() {
             let viewport_end = tokens
                 .iter()
@@ -2437,7 +2644,12 @@ impl SplitRenderer {
         } else {
             MAX_SAFE_LINE_WIDTH
         };
-        tokens = Self::apply_wrapping_transform(tokens, effective_width, gutter_width);
+        tokens = Self::apply_wrapping_transform(
+            tokens,
+            effective_width,
+            gutter_width,
+            state.buffer_settings.tab_size,
+        );
 
         // Convert tokens to display lines using the view pipeline
         // Each ViewLine preserves LineStart info for correct line number rendering
@@ -3128,6 +3340,45 @@ impl SplitRenderer {
         b < 0x20 || b == 0x7F
     }
 
+    /// Number of source lines to read from `top_byte` so that `visible_count`
+    /// rows remain once conceals have joined lines together (e.g. a folded
+    /// markdown section collapses into its heading row).
+    pub fn source_lines_to_fill(
+        state: &mut EditorState,
+        top_byte: usize,
+        visible_count: usize,
+        estimated_line_length: usize,
+    ) -> usize {
+        // Bounds the work when folds keep revealing further folds
+        const MAX_PASSES: usize = 8;
+
+        if state.conceals.is_empty() {
+            return visible_count;
+        }
+        let mut line_count = visible_count;
+        for _ in 0..MAX_PASSES {
+            let mut end = top_byte;
+            let mut iter = state.buffer.line_iterator(top_byte, estimated_line_length);
+            for _ in 0..line_count {
+                match iter.next_line() {
+                    Some((line_start, content)) => end = line_start + content.len(),
+                    None => break,
+                }
+            }
+            let hidden = state.conceals.concealed_line_breaks(
+                &state.buffer,
+                top_byte..end,
+                &state.marker_list,
+            );
+            // Lines read in this pass may hide more breaks; stop once they don't
+            if visible_count + hidden <= line_count {
+                break;
+            }
+            line_count = visible_count + hidden;
+        }
+        line_count
+    }
+
     /// Public wrapper for building base tokens - used by render.rs for the view_transform_request hook
     pub fn build_base_tokens_for_hook(
         buffer: &mut Buffer,
@@ -3151,6 +3402,7 @@ impl SplitRenderer {
         tokens: Vec<fresh_core::api::ViewTokenWire>,
         content_width: usize,
         gutter_width: usize,
+        tab_size: usize,
     ) -> Vec<fresh_core::api::ViewTokenWire> {
         use crate::primitives::visual_layout::visual_width;
         use fresh_core::api::{ViewTokenWire, ViewTokenWireKind};
@@ -3170,7 +3422,7 @@ impl SplitRenderer {
                 }
                 ViewTokenWireKind::Text(text) => {
                     // Use visual_width which properly handles tabs and ANSI codes
-                    let text_visual_width = visual_width(text, current_line_width);
+                    let text_visual_width = visual_width(text, current_line_width, tab_size);
 
                     // If this token would exceed line width, insert Break before it
                     if current_line_width > 0
@@ -3185,7 +3437,7 @@ impl SplitRenderer {
                     }
 
                     // Recalculate visual width after potential line break (tabs depend on column)
-                    let text_visual_width = visual_width(text, current_line_width);
+                    let text_visual_width = visual_width(text, current_line_width, tab_size);
 
                     // If visible text is longer than line width, we need to split
                     // However, we don't split tokens containing ANSI codes to avoid
@@ -3222,13 +3474,15 @@ impl SplitRenderer {
 
                             for &(_byte_offset, grapheme) in &graphemes[grapheme_idx..] {
                                 let g_width = if grapheme == "\t" {
-                                    crate::primitives::visual_layout::tab_expansion_width(col)
+                                    crate::primitives::visual_layout::tab_expansion_width(
+                                        col, tab_size,
+                                    )
                                 } else {
                                     crate::primitives::display_width::str_width(grapheme)
                                 };
 
                                 if chunk_visual_width + g_width > remaining_width
-                                    && chunk_grapheme_count > 0
+                                    && (chunk_grapheme_count > 0 || current_line_width > 0)
                                 {
                                     break;
                                 }
@@ -3238,6 +3492,18 @@ impl SplitRenderer {
                                 col += g_width;
                             }
 
+                            if chunk_grapheme_count == 0 && current_line_width > 0 {
+                                // A wide grapheme doesn't fit the rest of this row;
+                                // move it whole to the next row instead of clipping it
+                                wrapped.push(ViewTokenWire {
+                                    source_offset: None,
+                                    kind: ViewTokenWireKind::Break,
+                                    style: None,
+                                });
+                                current_line_width = 0;
+                                continue;
+                            }
+
                             if chunk_grapheme_count == 0 {
                                 // Single grapheme is wider than available width, force it
                                 chunk_grapheme_count = 1;
@@ -3245,6 +3511,7 @@ impl SplitRenderer {
                                 chunk_visual_width = if grapheme == "\t" {
                                     crate::primitives::visual_layout::tab_expansion_width(
                                         current_line_width,
+                                        tab_size,
                                     )
                                 } else {
                                     crate::primitives::display_width::str_width(grapheme)
@@ -3460,11 +3727,11 @@ impl SplitRenderer {
         state: &EditorState,
         cursors: &crate::model::cursor::Cursors,
     ) -> SelectionContext {
-        let ranges: Vec<Range<usize>> = cursors
+        let mut ranges: Vec<Range<usize>> = cursors
             .iter()
             .filter_map(|(_, cursor)| {
                 // Don't include normal selection for cursors in block selection mode
-                // Block selections are rendered separately via block_rects
+                // Block selections are added below as one range per line
                 if cursor.selection_mode == SelectionMode::Block {
                     None
                 } else {
@@ -3473,31 +3740,18 @@ impl SplitRenderer {
             })
             .collect();
 
-        let block_rects: Vec<(usize, usize, usize, usize)> = cursors
-            .iter()
-            .filter_map(|(_, cursor)| {
-                if cursor.selection_mode == SelectionMode::Block {
-                    if let Some(anchor) = cursor.block_anchor {
-                        // Convert cursor position to 2D coords
-                        let cur_line = state.buffer.get_line_number(cursor.position);
-                        let cur_line_start = state.buffer.line_start_offset(cur_line).unwrap_or(0);
-                        let cur_col = cursor.position.saturating_sub(cur_line_start);
-
-                        // Return normalized rectangle (min values first)
-                        Some((
-                            anchor.line.min(cur_line),
-                            anchor.column.min(cur_col),
-                            anchor.line.max(cur_line),
-                            anchor.column.max(cur_col),
-                        ))
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            })
-            .collect();
+        // Block selections cover the same visual columns on every line,
+        // which map to different byte ranges around tabs and wide characters
+        let tab_size = state.buffer_settings.tab_size;
+        for (_, cursor) in cursors.iter() {
+            if let Some((start_line, start_col, end_line, end_col)) =
+                block_rect(&state.buffer, cursor, tab_size)
+            {
+                ranges.extend((start_line..=end_line).map(|line| {
+                    block_line_range(&state.buffer, line, start_col, end_col, tab_size)
+                }));
+            }
+        }
 
         let cursor_positions: Vec<usize> = if state.show_cursors {
             cursors.iter().map(|(_, cursor)| cursor.position).collect()
@@ -3507,7 +3761,6 @@ impl SplitRenderer {
 
         SelectionContext {
             ranges,
-            block_rects,
             cursor_positions,
             primary_cursor_position: cursors.primary().position,
         }
@@ -3594,14 +3847,25 @@ impl SplitRenderer {
 
         // Use the lsp-diagnostic namespace to identify diagnostic overlays
         let diagnostic_ns = crate::services::lsp::diagnostics::lsp_diagnostic_namespace();
-        let diagnostic_lines: HashSet<usize> = viewport_overlays
-            .iter()
-            .filter_map(|(overlay, range)| {
-                if overlay.namespace.as_ref() == Some(&diagnostic_ns) {
-                    return Some(state.buffer.get_line_number(range.start));
-                }
-                None
-            })
+        // A line's marker takes the color of its most severe diagnostic
+        let mut diagnostic_lines: HashMap<usize, (i32, Color)> = HashMap::new();
+        for (overlay, range) in &viewport_overlays {
+            if overlay.namespace.as_ref() != Some(&diagnostic_ns) {
+                continue;
+            }
+            let line = state.buffer.get_line_number(range.start);
+            let color =
+                crate::services::lsp::diagnostics::diagnostic_gutter_color(overlay.priority, theme);
+            let marker = diagnostic_lines
+                .entry(line)
+                .or_insert((overlay.priority, color));
+            if overlay.priority > marker.0 {
+                *marker = (overlay.priority, color);
+            }
+        }
+        let diagnostic_lines: HashMap<usize, Color> = diagnostic_lines
+            .into_iter()
+            .map(|(line, (_, color))| (line, color))
             .collect();
 
         let virtual_text_lookup: HashMap<usize, Vec<crate::view::virtual_text::VirtualText>> =
@@ -3674,7 +3938,6 @@ impl SplitRenderer {
         } = input;
 
         let selection_ranges = &selection.ranges;
-        let block_selections = &selection.block_rects;
         let cursor_positions = &selection.cursor_positions;
         let primary_cursor_position = selection.primary_cursor_position;
 
@@ -3793,6 +4056,9 @@ impl SplitRenderer {
                     state,
                     theme,
                     is_continuation,
+                    // Binary views wrap raw byte runs, not source text
+                    is_wrapped: current_view_line.line_start.is_continuation()
+                        && !state.buffer.is_binary(),
                     current_source_line_num,
                     estimated_lines,
                     diagnostic_lines,
@@ -3806,7 +4072,6 @@ impl SplitRenderer {
             );
 
             // Check if this line has any selected text
-            let mut byte_index = 0; // Byte offset in line_content string
             let mut display_char_idx = 0usize; // Character index in text (for char_source_bytes)
             let mut col_offset = 0usize; // Visual column position
 
@@ -3884,7 +4149,6 @@ impl SplitRenderer {
                                     have_cursor = true;
                                 }
                             }
-                            byte_index += ch.len_utf8();
                             display_char_idx += 1;
                             // Note: col_offset not incremented - ANSI chars have 0 visual width
                             continue;
@@ -3896,7 +4160,7 @@ impl SplitRenderer {
                 };
 
                 // Performance: skip expensive style calculations for characters beyond visible range
-                // Use visible_char_count (not byte_index) since ANSI codes don't take up visible space
+                // Use visible_char_count since ANSI codes don't take up visible space
                 if visible_char_count > max_chars_to_process {
                     // Fast path: skip remaining characters without processing
                     // This is critical for performance with very long lines (e.g., 100KB single line)
@@ -3927,16 +4191,6 @@ impl SplitRenderer {
                         .unwrap_or(false);
 
                     // Check if this character is in any selection range (but not at cursor position)
-                    // Also check for block/rectangular selections
-                    let is_in_block_selection = block_selections.iter().any(
-                        |(start_line, start_col, end_line, end_col)| {
-                            current_source_line_num >= *start_line
-                                && current_source_line_num <= *end_line
-                                && byte_index >= *start_col
-                                && byte_index <= *end_col
-                        },
-                    );
-
                     // For primary cursor in active split, terminal hardware cursor provides
                     // visual indication, so we can still show selection background.
                     // Only exclude secondary cursors from selection (they use REVERSED styling).
@@ -3946,9 +4200,9 @@ impl SplitRenderer {
                     let exclude_from_selection = is_cursor && !(is_active && is_primary_cursor);
 
                     let is_selected = !exclude_from_selection
-                        && (byte_pos.is_some_and(|bp| {
+                        && byte_pos.is_some_and(|bp| {
                             selection_ranges.iter().any(|range| range.contains(&bp))
-                        }) || is_in_block_selection);
+                        });
 
                     // Compute character style using helper function
                     // char_styles is indexed by character position, not visual column
@@ -4123,7 +4377,6 @@ impl SplitRenderer {
                     }
                 }
 
-                byte_index += ch.len_utf8();
                 display_char_idx += 1; // Increment character index for next lookup
                                        // col_offset tracks visual column position (for indexing into visual_to_char)
                                        // visual_to_char has one entry per visual column, not per character
@@ -4468,11 +4721,8 @@ impl SplitRenderer {
 
                 if state.margins.left_config.enabled {
                     // Indicator column: check for diagnostic markers on this implicit line
-                    if decorations.diagnostic_lines.contains(&implicit_line_num) {
-                        implicit_line_spans.push(Span::styled(
-                            "●",
-                            Style::default().fg(ratatui::style::Color::Red),
-                        ));
+                    if let Some(&color) = decorations.diagnostic_lines.get(&implicit_line_num) {
+                        implicit_line_spans.push(Span::styled("●", Style::default().fg(color)));
                     } else {
                         implicit_line_spans.push(Span::styled(" ", Style::default()));
                     }
@@ -4692,9 +4942,18 @@ impl SplitRenderer {
             false
         };
 
+        // Go to Line asked for the cursor to be centered; measure on view
+        // lines so wrapped and injected lines (e.g. compose mode) count.
+        let centered = if viewport.center_cursor && !sync_scrolled {
+            viewport.center_cursor = false;
+            viewport.center_on_view_line(&view_data.lines, cursors.primary().position)
+        } else {
+            false
+        };
+
         // If the sync adjustment changed top_byte, rebuild view_data before
         // ensure_visible_in_layout runs (so it sees the correct view lines).
-        let (view_data, view_transform_for_rebuild) = if sync_scrolled {
+        let (view_data, view_transform_for_rebuild) = if sync_scrolled || centered {
             viewport.top_view_line_offset = 0;
             let rebuilt = Self::build_view_data(
                 state,
@@ -4707,7 +4966,9 @@ impl SplitRenderer {
                 gutter_width,
                 &view_mode,
             );
-            viewport.scroll_to_end_of_view(&rebuilt.lines);
+            if sync_scrolled {
+                viewport.scroll_to_end_of_view(&rebuilt.lines);
+            }
             (rebuilt, None)
         } else {
             (view_data, Some(view_transform_for_rebuild))
@@ -4876,6 +5137,9 @@ impl SplitRenderer {
         hide_cursor: bool,
         rulers: &[usize],
         compose_column_guides: Option<Vec<u16>>,
+        show_indent_guides: bool,
+        show_whitespace_warnings: bool,
+        show_whitespace: bool,
     ) {
         let render_area = layout_output.render_area;
         let effective_editor_bg = layout_output.effective_editor_bg;
@@ -4948,6 +5212,48 @@ impl SplitRenderer {
             );
         }
 
+        if show_whitespace_warnings {
+            Self::render_whitespace_warnings(
+                frame,
+                state,
+                &layout_output.view_line_mappings,
+                theme.diagnostic_warning_bg,
+                render_area,
+                gutter_width,
+            );
+        }
+
+        if show_whitespace {
+            let glyph_style = Style::default()
+                .fg(theme.line_number_fg)
+                .add_modifier(Modifier::DIM);
+            Self::render_whitespace_glyphs(
+                frame,
+                state,
+                &layout_output.view_line_mappings,
+                glyph_style,
+                render_area,
+                gutter_width,
+                matches!(layout_output.view_mode, ViewMode::Compose),
+            );
+        }
+
+        // Render indent guides (compose mode reflows and conceals source text)
+        if show_indent_guides && !matches!(layout_output.view_mode, ViewMode::Compose) {
+            let guide_style = Style::default()
+                .fg(theme.line_number_fg)
+                .add_modifier(Modifier::DIM);
+            Self::render_indent_guides(
+                frame,
+                state,
+                &layout_output.view_line_mappings,
+                guide_style,
+                render_area,
+                gutter_width,
+                layout_output.left_column,
+            );
+        }
+
         // Render compose column guides
         if let Some(guides) = compose_column_guides {
             let guide_style = Style::default()
@@ -5004,6 +5310,9 @@ impl SplitRenderer {
         session_mode: bool,
         rulers: &[usize],
         show_line_numbers: bool,
+        show_indent_guides: bool,
+        show_whitespace_warnings: bool,
+        show_whitespace: bool,
     ) -> Vec<ViewLineMapping> {
         let layout_output = Self::compute_buffer_layout(
             state,
@@ -5040,6 +5349,9 @@ impl SplitRenderer {
             hide_cursor,
             rulers,
             compose_column_guides,
+            show_indent_guides,
+            show_whitespace_warnings,
+            show_whitespace,
         );
 
         view_line_mappings
@@ -5078,6 +5390,232 @@ impl SplitRenderer {
         }
     }
 
+    /// Render indent guides: a `│` at every indentation level inside the
+    /// leading whitespace of each indented line.
+    ///
+    /// Levels are `tab_size` visual columns apart and tabs advance to the next
+    /// tab stop, so guides line up whether a file indents with tabs or spaces.
+    /// Only the first visual row of a wrapped line gets guides, and cells that
+    /// already show something (such as a tab indicator) are left alone.
+    fn render_indent_guides(
+        frame: &mut Frame,
+        state: &EditorState,
+        view_line_mappings: &[ViewLineMapping],
+        style: Style,
+        render_area: Rect,
+        gutter_width: usize,
+        left_column: usize,
+    ) {
+        let tab_size = state.buffer_settings.tab_size.max(1);
+        let content_x = render_area.x as usize + gutter_width;
+        let area_right = (render_area.x + render_area.width) as usize;
+        let mut prev_line = None;
+        for (row, mapping) in view_line_mappings
+            .iter()
+            .take(render_area.height as usize)
+            .enumerate()
+        {
+            let Some(byte) = mapping.char_source_bytes.iter().flatten().next() else {
+                continue;
+            };
+            let line = state.buffer.get_line_number(*byte);
+            if prev_line.replace(line) == Some(line) {
+                continue;
+            }
+            let text = state.buffer.get_line(line).unwrap_or_default();
+            let mut indent_width = 0;
+            let mut has_text = false;
+            for &b in &text {
+                match b {
+                    b' ' => indent_width += 1,
+                    b'\t' => indent_width += tab_size - indent_width % tab_size,
+                    b'\n' | b'\r' => break,
+                    _ => {
+                        has_text = true;
+                        break;
+                    }
+                }
+            }
+            // Whitespace-only lines have no indentation level of their own
+            if !has_text {
+                continue;
+            }
+            let y = render_area.y + row as u16;
+            for col in (0..indent_width).step_by(tab_size) {
+                let Some(scrolled_col) = col.checked_sub(left_column) else {
+                    continue;
+                };
+                let x = content_x + scrolled_col;
+                if x >= area_right {
+                    break;
+                }
+                let cell = &mut frame.buffer_mut()[(x as u16, y)];
+                if cell.symbol() != " " {
+                    continue;
+                }
+                cell.set_symbol("│").set_style(style);
+            }
+        }
+    }
+
+    /// Tint trailing whitespace and mixed-indentation cells with `color`.
+    ///
+    /// Works from the rendered rows, so text hidden by conceals is never
+    /// tinted, and only cells that display as whitespace (or a tab indicator)
+    /// are touched.
+    fn render_whitespace_warnings(
+        frame: &mut Frame,
+        state: &EditorState,
+        view_line_mappings: &[ViewLineMapping],
+        color: Color,
+        render_area: Rect,
+        gutter_width: usize,
+    ) {
+        let content_x = render_area.x as usize + gutter_width;
+        let area_right = (render_area.x + render_area.width) as usize;
+        for (row, mapping) in view_line_mappings
+            .iter()
+            .take(render_area.height as usize)
+            .enumerate()
+        {
+            let Some(&first_byte) = mapping.char_source_bytes.iter().flatten().next() else {
+                continue;
+            };
+            let line = state.buffer.get_line_number(first_byte);
+            let line_start = state.buffer.line_start_offset(line).unwrap_or(0);
+            let mut text = state.buffer.get_line(line).unwrap_or_default();
+            while matches!(text.last(), Some(b'\n' | b'\r')) {
+                text.pop();
+            }
+            let ranges = whitespace_warning_ranges(&text);
+            if ranges.is_empty() {
+                continue;
+            }
+
+            let y = render_area.y + row as u16;
+            for (col, byte) in mapping.char_source_bytes.iter().enumerate() {
+                let Some(offset) = byte.and_then(|b| b.checked_sub(line_start)) else {
+                    continue;
+                };
+                if !ranges.iter().any(|range| range.contains(&offset)) {
+                    continue;
+                }
+                let x = content_x + col;
+                if x >= area_right {
+                    break;
+                }
+                let cell = &mut frame.buffer_mut()[(x as u16, y)];
+                if matches!(cell.symbol(), " " | "→") {
+                    cell.set_bg(color);
+                }
+            }
+        }
+    }
+
+    /// Draw `·` over spaces, `→` at the start of each tab, and `↵` (LF) or
+    /// `¶` (CRLF) after the last row of each line
+    ///
+    /// Only blank cells are replaced and the cell background is kept, so the
+    /// buffer, cursor placement and selection are unaffected. In compose mode
+    /// concealed text keeps its replacement.
+    #[allow(clippy::too_many_arguments)]
+    fn render_whitespace_glyphs(
+        frame: &mut Frame,
+        state: &EditorState,
+        view_line_mappings: &[ViewLineMapping],
+        style: Style,
+        render_area: Rect,
+        gutter_width: usize,
+        is_compose: bool,
+    ) {
+        let content_x = render_area.x as usize + gutter_width;
+        let area_right = (render_area.x + render_area.width) as usize;
+        let mappings =
+            &view_line_mappings[..view_line_mappings.len().min(render_area.height as usize)];
+
+        let concealed: Vec<Range<usize>> = match (
+            is_compose,
+            mappings
+                .iter()
+                .find_map(|m| m.char_source_bytes.iter().flatten().next()),
+            mappings.last(),
+        ) {
+            (true, Some(&start), Some(last)) => state
+                .conceals
+                .query_viewport(start, last.line_end_byte + 1, &state.marker_list)
+                .into_iter()
+                .map(|(range, _)| range)
+                .collect(),
+            _ => Vec::new(),
+        };
+        let is_concealed = |byte: usize| concealed.iter().any(|range| range.contains(&byte));
+
+        let mut prev_line_end = None;
+        for (row, mapping) in mappings.iter().enumerate() {
+            // Virtual rows repeat the previous row's line end
+            let inherited = prev_line_end == Some(mapping.line_end_byte);
+            prev_line_end = Some(mapping.line_end_byte);
+
+            let first_byte = mapping
+                .char_source_bytes
+                .iter()
+                .flatten()
+                .next()
+                .copied()
+                .unwrap_or(mapping.line_end_byte);
+            let line = state.buffer.get_line_number(first_byte);
+            let line_start = state.buffer.line_start_offset(line).unwrap_or(0);
+            let text = state.buffer.get_line(line).unwrap_or_default();
+            let byte_at = |pos: usize| {
+                pos.checked_sub(line_start)
+                    .and_then(|i| text.get(i))
+                    .copied()
+            };
+
+            let y = render_area.y + row as u16;
+            let mut last_col = None;
+            let mut prev_byte = None;
+            for (col, byte) in mapping.char_source_bytes.iter().enumerate() {
+                let Some(byte) = *byte else {
+                    prev_byte = None;
+                    continue;
+                };
+                last_col = Some(col);
+                let x = content_x + col;
+                let starts_char = prev_byte != Some(byte);
+                prev_byte = Some(byte);
+                if x >= area_right || is_concealed(byte) {
+                    continue;
+                }
+                let glyph = match byte_at(byte) {
+                    Some(b' ') => "·",
+                    Some(b'\t') if starts_char => "→",
+                    _ => continue,
+                };
+                let cell = &mut frame.buffer_mut()[(x as u16, y)];
+                if cell.symbol() == " " {
+                    cell.set_symbol(glyph).set_style(style);
+                }
+            }
+
+            if inherited || (last_col.is_none() && mapping.line_end_byte != line_start) {
+                continue;
+            }
+            let glyph = match byte_at(mapping.line_end_byte) {
+                Some(b'\n') => "↵",
+                Some(b'\r') => "¶",
+                _ => continue,
+            };
+            let x = content_x + last_col.map_or(0, |col| col + 1);
+            if x < area_right {
+                let cell = &mut frame.buffer_mut()[(x as u16, y)];
+                if cell.symbol() == " " {
+                    cell.set_symbol(glyph).set_style(style);
+                }
+            }
+        }
+    }
+
     /// Render vertical rulers as a subtle background color tint.
     /// Unlike `render_column_guides` which draws │ characters (for compose guides),
     /// this preserves the existing text content and only adjusts the background color.
@@ -5327,6 +5865,16 @@ mod tests {
     use crate::view::theme::Theme;
     use crate::view::viewport::Viewport;
 
+    #[test]
+    fn test_whitespace_warning_ranges() {
+        assert!(whitespace_warning_ranges(b"    clean").is_empty());
+        assert_eq!(whitespace_warning_ranges(b"code  "), vec![4..6]);
+        assert_eq!(whitespace_warning_ranges(b"  \tmixed"), vec![0..3]);
+        assert_eq!(whitespace_warning_ranges(b"\t x\t"), vec![0..2, 3..4]);
+        // A whitespace-only line is all trailing whitespace
+        assert_eq!(whitespace_warning_ranges(b" \t "), vec![0..3]);
+    }
+
     fn render_output_for(
         content: &str,
         cursor_pos: usize,
@@ -6427,7 +6975,7 @@ mod tests {
         ];
 
         // Apply wrapping with MAX_SAFE_LINE_WIDTH (simulating line_wrap disabled)
-        let wrapped = SplitRenderer::apply_wrapping_transform(tokens, MAX_SAFE_LINE_WIDTH, 0);
+        let wrapped = SplitRenderer::apply_wrapping_transform(tokens, MAX_SAFE_LINE_WIDTH, 0, 4);
 
         // Count Break tokens - should have at least 2 breaks for 25K chars at 10K width
         let break_count = wrapped
@@ -6477,7 +7025,7 @@ mod tests {
         ];
 
         // Apply wrapping with MAX_SAFE_LINE_WIDTH (simulating line_wrap disabled)
-        let wrapped = SplitRenderer::apply_wrapping_transform(tokens, MAX_SAFE_LINE_WIDTH, 0);
+        let wrapped = SplitRenderer::apply_wrapping_transform(tokens, MAX_SAFE_LINE_WIDTH, 0, 4);
 
         // Should have no Break tokens for short lines
         let break_count = wrapped
@@ -6534,7 +7082,7 @@ mod tests {
         ];
 
         // Apply safety wrapping (simulating line_wrap=false with MAX_SAFE_LINE_WIDTH)
-        let wrapped = SplitRenderer::apply_wrapping_transform(tokens, MAX_SAFE_LINE_WIDTH, 0);
+        let wrapped = SplitRenderer::apply_wrapping_transform(tokens, MAX_SAFE_LINE_WIDTH, 0, 4);
 
         // Convert to ViewLines
         let view_lines: Vec<_> = ViewLineIterator::new(&wrapped, false, false, 4, false).collect();
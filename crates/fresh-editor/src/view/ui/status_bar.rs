@@ -625,8 +625,14 @@ impl StatusBarRenderer {
         // Order: [Line ending] [Language] [LSP indicator] [warning badge] [update] [Palette]
         // Note: Remote indicator is now on the left side, before the filename
 
-        // Line ending indicator (clickable to change format)
-        let line_ending_text = format!(" {} ", state.buffer.line_ending().display_name());
+        // Line ending indicator (clickable to change format); files with mixed
+        // line endings show the dominant format they will be saved with
+        let line_ending_name = state.buffer.line_ending().display_name();
+        let line_ending_text = if state.buffer.has_mixed_line_endings() {
+            format!(" Mixed ({line_ending_name}) ")
+        } else {
+            format!(" {line_ending_name} ")
+        };
         let line_ending_width = str_width(&line_ending_text);
 
         // Encoding indicator (clickable to change encoding)
@@ -224,9 +224,20 @@ pub fn calculate_tab_widths(
         } else {
             ""
         };
+        let deleted_indicator = if buffer_metadata.get(id).is_some_and(|m| m.deleted_on_disk) {
+            " [deleted]"
+        } else {
+            ""
+        };
+        let read_only_indicator = if buffer_metadata.get(id).is_some_and(|m| m.write_protected) {
+            " [RO]"
+        } else {
+            ""
+        };
 
-        // Same format as render_for_split: " {name}{modified}{binary_indicator} " + "× "
-        let tab_name_text = format!(" {name}{modified}{binary_indicator} ");
+        // Same format as render_for_split: " {name}{modified}{indicators} " + "× "
+        let tab_name_text =
+            format!(" {name}{modified}{binary_indicator}{read_only_indicator}{deleted_indicator} ");
         let close_text = "× ";
         let tab_width = str_width(&tab_name_text) + str_width(close_text);
 
@@ -338,6 +349,17 @@ impl TabsRenderer {
             } else {
                 ""
             };
+            let deleted_indicator = if buffer_metadata.get(id).is_some_and(|m| m.deleted_on_disk) {
+                " [deleted]"
+            } else {
+                ""
+            };
+            let read_only_indicator = if buffer_metadata.get(id).is_some_and(|m| m.write_protected)
+            {
+                " [RO]"
+            } else {
+                ""
+            };
 
             let is_active = *id == active_buffer;
 
@@ -379,8 +401,10 @@ impl TabsRenderer {
                 base_style
             };
 
-            // Build tab content: " {name}{modified}{binary_indicator} "
-            let tab_name_text = format!(" {name}{modified}{binary_indicator} ");
+            // Build tab content: " {name}{modified}{indicators} "
+            let tab_name_text = format!(
+                " {name}{modified}{binary_indicator}{read_only_indicator}{deleted_indicator} "
+            );
             let tab_name_width = str_width(&tab_name_text);
 
             // Close button: "× "
@@ -59,6 +59,12 @@ pub struct Viewport {
     /// active split is at the end of the document.  Consumed (cleared) during
     /// rendering after the adjustment is applied.
     pub sync_scroll_to_end: bool,
+
+    /// When true, the next render pass scrolls so the primary cursor's view
+    /// line sits in the middle of the viewport.  Set by Go to Line after it
+    /// has placed `top_byte` roughly; rendering refines it on view lines so
+    /// wrapped and virtual lines count as rows.  Consumed during rendering.
+    pub center_cursor: bool,
 }
 
 impl Viewport {
@@ -78,6 +84,7 @@ impl Viewport {
             skip_ensure_visible: false,
             max_line_length_seen: 0,
             sync_scroll_to_end: false,
+            center_cursor: false,
         }
     }
 
@@ -886,6 +893,29 @@ impl Viewport {
         true
     }
 
+    /// Scroll so the view line holding `cursor_byte` is centered
+    ///
+    /// Returns true if `top_byte` changed and the view lines need rebuilding.
+    pub fn center_on_view_line(&mut self, view_lines: &[ViewLine], cursor_byte: usize) -> bool {
+        let viewport_height = self.visible_line_count();
+        if view_lines.is_empty() || viewport_height == 0 {
+            return false;
+        }
+        let cursor_view_line = self.find_view_line_for_byte(view_lines, cursor_byte);
+        let new_offset = cursor_view_line.saturating_sub(viewport_height / 2);
+        if new_offset == self.top_view_line_offset {
+            return false;
+        }
+        self.top_view_line_offset = new_offset;
+        match self.get_source_byte_for_view_line(view_lines, new_offset) {
+            Some(new_top_byte) if new_top_byte != self.top_byte => {
+                self.top_byte = new_top_byte;
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Mark viewport as needing synchronization with cursor positions
     /// This defers the actual viewport update until sync_with_cursor is called
     pub fn mark_needs_sync(&mut self) {
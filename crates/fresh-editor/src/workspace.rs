@@ -18,6 +18,10 @@
 //!
 //! The encoding is fully reversible using `decode_filename_to_path()`.
 //!
+//! Named sessions saved with "Save Workspace As" live next to it in
+//! `workspaces/named/{encoded_path}/{encoded_name}.json`, one directory per
+//! working directory.
+//!
 //! ## Crash Resistance
 //!
 //! Uses atomic writes: write to temp file, then rename.
@@ -43,6 +47,10 @@ pub struct Workspace {
     /// Schema version for future migrations
     pub version: u32,
 
+    /// Session name (None for the implicit per-directory workspace)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
     /// Working directory this workspace belongs to (for validation)
     pub working_dir: PathBuf,
 
@@ -74,6 +82,10 @@ pub struct Workspace {
     #[serde(default)]
     pub bookmarks: HashMap<char, SerializedBookmark>,
 
+    /// Line bookmarks shown in the gutter (file position of each bookmarked line)
+    #[serde(default)]
+    pub line_bookmarks: Vec<SerializedBookmark>,
+
     /// Open terminal workspaces (for restoration)
     #[serde(default)]
     pub terminals: Vec<SerializedTerminalWorkspace>,
@@ -190,9 +202,9 @@ pub struct SerializedCursor {
     /// Selection anchor as byte offset (if selection active)
     #[serde(default)]
     pub anchor: Option<usize>,
-    /// Sticky column for vertical movement (character column)
+    /// Sticky column for vertical movement (character column), if set
     #[serde(default)]
-    pub sticky_column: usize,
+    pub sticky_column: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -290,6 +302,8 @@ pub struct SearchOptions {
     #[serde(default)]
     pub use_regex: bool,
     #[serde(default)]
+    pub smart_case: bool,
+    #[serde(default)]
     pub confirm_each: bool,
 }
 
@@ -563,6 +577,32 @@ pub fn get_workspace_path(working_dir: &Path) -> io::Result<PathBuf> {
     Ok(get_workspaces_dir()?.join(filename))
 }
 
+/// Get the directory holding named sessions for a working directory
+pub fn get_named_workspaces_dir(working_dir: &Path) -> io::Result<PathBuf> {
+    let canonical = working_dir
+        .canonicalize()
+        .unwrap_or_else(|_| working_dir.to_path_buf());
+    Ok(get_workspaces_dir()?
+        .join("named")
+        .join(encode_path_for_filename(&canonical)))
+}
+
+/// Get the file path of a named session for a working directory
+pub fn get_named_workspace_path(working_dir: &Path, name: &str) -> io::Result<PathBuf> {
+    let filename = format!("{}.json", encode_path_for_filename(Path::new(name)));
+    Ok(get_named_workspaces_dir(working_dir)?.join(filename))
+}
+
+/// Summary of a saved named session, for listing without restoring it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceSummary {
+    pub name: String,
+    /// Number of distinct files open across all splits
+    pub open_files: usize,
+    /// Timestamp when the session was saved (Unix epoch seconds)
+    pub saved_at: u64,
+}
+
 /// Workspace error types
 #[derive(Debug)]
 pub enum WorkspaceError {
@@ -629,7 +669,79 @@ impl From<serde_json::Error> for WorkspaceError {
 impl Workspace {
     /// Load workspace for a working directory (if exists)
     pub fn load(working_dir: &Path) -> Result<Option<Workspace>, WorkspaceError> {
-        let path = get_workspace_path(working_dir)?;
+        Self::load_from(&get_workspace_path(working_dir)?, working_dir)
+    }
+
+    /// Load a named session for a working directory (if exists)
+    pub fn load_named(working_dir: &Path, name: &str) -> Result<Option<Workspace>, WorkspaceError> {
+        Self::load_from(&get_named_workspace_path(working_dir, name)?, working_dir)
+    }
+
+    /// List the named sessions saved for a working directory, most recent first
+    ///
+    /// Files that fail to parse are skipped.
+    pub fn list_named(working_dir: &Path) -> Result<Vec<WorkspaceSummary>, WorkspaceError> {
+        let dir = get_named_workspaces_dir(working_dir)?;
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut summaries = Vec::new();
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let workspace: Workspace = match std::fs::read_to_string(&path)
+                .map_err(WorkspaceError::from)
+                .and_then(|content| Ok(serde_json::from_str(&content)?))
+            {
+                Ok(workspace) => workspace,
+                Err(e) => {
+                    tracing::warn!("Skipping unreadable workspace {:?}: {}", path, e);
+                    continue;
+                }
+            };
+            let Some(name) = workspace.name.clone() else {
+                continue;
+            };
+            summaries.push(WorkspaceSummary {
+                name,
+                open_files: workspace.open_file_count(),
+                saved_at: workspace.saved_at,
+            });
+        }
+
+        summaries.sort_by(|a, b| b.saved_at.cmp(&a.saved_at).then(a.name.cmp(&b.name)));
+        Ok(summaries)
+    }
+
+    /// Number of distinct files open across all splits, including external files
+    pub fn open_file_count(&self) -> usize {
+        let mut paths: Vec<&PathBuf> = Vec::new();
+        for state in self.split_states.values() {
+            let files: Vec<&PathBuf> = if state.open_tabs.is_empty() {
+                state.open_files.iter().collect()
+            } else {
+                state
+                    .open_tabs
+                    .iter()
+                    .filter_map(|tab| match tab {
+                        SerializedTabRef::File(path) => Some(path),
+                        SerializedTabRef::Terminal(_) => None,
+                    })
+                    .collect()
+            };
+            for path in files {
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+        paths.len() + self.external_files.len()
+    }
+
+    fn load_from(path: &Path, working_dir: &Path) -> Result<Option<Workspace>, WorkspaceError> {
         tracing::debug!("Looking for workspace at {:?}", path);
 
         if !path.exists() {
@@ -638,7 +750,7 @@ impl Workspace {
         }
 
         tracing::debug!("Loading workspace from {:?}", path);
-        let content = std::fs::read_to_string(&path)?;
+        let content = std::fs::read_to_string(path)?;
         let workspace: Workspace = serde_json::from_str(&content)?;
 
         tracing::debug!(
@@ -688,8 +800,14 @@ impl Workspace {
     /// 1. Write to a temporary file in the same directory
     /// 2. Sync to disk (fsync)
     /// 3. Atomically rename to the final path
+    ///
+    /// Named sessions are written to their own file and never replace the
+    /// implicit workspace.
     pub fn save(&self) -> Result<(), WorkspaceError> {
-        let path = get_workspace_path(&self.working_dir)?;
+        let path = match &self.name {
+            Some(name) => get_named_workspace_path(&self.working_dir, name)?,
+            None => get_workspace_path(&self.working_dir)?,
+        };
         tracing::debug!("Saving workspace to {:?}", path);
 
         // Ensure directory exists
@@ -731,6 +849,7 @@ impl Workspace {
     pub fn new(working_dir: PathBuf) -> Self {
         Self {
             version: WORKSPACE_VERSION,
+            name: None,
             working_dir,
             split_layout: SerializedSplitNode::Leaf {
                 file_path: None,
@@ -744,6 +863,7 @@ impl Workspace {
             histories: WorkspaceHistories::default(),
             search_options: SearchOptions::default(),
             bookmarks: HashMap::new(),
+            line_bookmarks: Vec::new(),
             terminals: Vec::new(),
             external_files: Vec::new(),
             saved_at: SystemTime::now()
@@ -890,12 +1010,12 @@ mod tests {
             cursor: SerializedCursor {
                 position: 1234,
                 anchor: Some(1000),
-                sticky_column: 15,
+                sticky_column: Some(15),
             },
             additional_cursors: vec![SerializedCursor {
                 position: 5000,
                 anchor: None,
-                sticky_column: 0,
+                sticky_column: None,
             }],
             scroll: SerializedScroll {
                 top_byte: 500,
@@ -912,7 +1032,7 @@ mod tests {
 
         assert_eq!(restored.cursor.position, 1234);
         assert_eq!(restored.cursor.anchor, Some(1000));
-        assert_eq!(restored.cursor.sticky_column, 15);
+        assert_eq!(restored.cursor.sticky_column, Some(15));
         assert_eq!(restored.additional_cursors.len(), 1);
         assert_eq!(restored.scroll.top_byte, 500);
         assert_eq!(restored.scroll.left_column, 10);
@@ -953,6 +1073,7 @@ mod tests {
             case_sensitive: true,
             whole_word: true,
             use_regex: false,
+            smart_case: true,
             confirm_each: true,
         };
 
@@ -962,6 +1083,7 @@ mod tests {
         assert!(restored.case_sensitive);
         assert!(restored.whole_word);
         assert!(!restored.use_regex);
+        assert!(restored.smart_case);
         assert!(restored.confirm_each);
     }
 
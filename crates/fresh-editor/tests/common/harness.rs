@@ -152,6 +152,9 @@ pub struct HarnessOptions {
     /// Preserve the keybinding map from the config (don't force "default").
     /// Set this when testing a specific keymap like emacs.
     pub preserve_keybinding_map: bool,
+    /// Give each typed character its own undo step instead of grouping
+    /// typing by the config's `undo_group_timeout_ms`.
+    pub per_character_undo: bool,
     /// Use full grammar registry with syntax highlighting support.
     /// Defaults to false (uses empty registry for fast test startup).
     /// Set to true only for tests that need syntax highlighting or shebang detection.
@@ -172,6 +175,7 @@ impl HarnessOptions {
             slow_fs_config: None,
             filesystem: None,
             preserve_keybinding_map: false,
+            per_character_undo: false,
             use_full_grammar_registry: false,
         }
     }
@@ -239,6 +243,13 @@ impl HarnessOptions {
         self
     }
 
+    /// Undo each typed character on its own.
+    /// Use this for tests that step through typing one undo at a time.
+    pub fn without_undo_grouping(mut self) -> Self {
+        self.per_character_undo = true;
+        self
+    }
+
     /// Use full grammar registry with syntax highlighting support.
     /// By default, tests use an empty registry for fast startup.
     /// Only enable this for tests that need syntax highlighting or shebang detection.
@@ -444,6 +455,10 @@ impl EditorTestHarness {
         }
         config.check_for_updates = false; // Disable update checking in tests
 
+        if options.per_character_undo {
+            config.editor.undo_group_timeout_ms = 0;
+        }
+
         // Initialize i18n with the config's locale before creating the editor
         // This ensures menu defaults are created with the correct translations
         fresh::i18n::init_with_config(config.locale.as_option());
@@ -816,6 +831,17 @@ impl EditorTestHarness {
         Ok(())
     }
 
+    /// Simulate a double-click at specific coordinates
+    ///
+    /// Waits past the double-click window first so an earlier click can't
+    /// turn this into a triple-click.
+    pub fn mouse_double_click(&mut self, col: u16, row: u16) -> anyhow::Result<()> {
+        let window = self.config().editor.double_click_time_ms;
+        self.advance_time(std::time::Duration::from_millis(window * 2));
+        self.mouse_click(col, row)?;
+        self.mouse_click(col, row)
+    }
+
     /// Simulate a shift+click at specific coordinates (for extending selection)
     pub fn mouse_shift_click(&mut self, col: u16, row: u16) -> anyhow::Result<()> {
         let mouse_event = MouseEvent {
@@ -884,13 +910,31 @@ impl EditorTestHarness {
         start_row: u16,
         end_col: u16,
         end_row: u16,
+    ) -> anyhow::Result<()> {
+        self.mouse_drag_with_modifiers(
+            start_col,
+            start_row,
+            end_col,
+            end_row,
+            KeyModifiers::empty(),
+        )
+    }
+
+    /// Simulate a mouse drag with modifier keys held (e.g. Alt for block selection)
+    pub fn mouse_drag_with_modifiers(
+        &mut self,
+        start_col: u16,
+        start_row: u16,
+        end_col: u16,
+        end_row: u16,
+        modifiers: KeyModifiers,
     ) -> anyhow::Result<()> {
         // Send initial press
         let mouse_down = MouseEvent {
             kind: MouseEventKind::Down(MouseButton::Left),
             column: start_col,
             row: start_row,
-            modifiers: KeyModifiers::empty(),
+            modifiers,
         };
         self.send_mouse(mouse_down)?;
 
@@ -907,7 +951,7 @@ impl EditorTestHarness {
                 kind: MouseEventKind::Drag(MouseButton::Left),
                 column: col as u16,
                 row: row as u16,
-                modifiers: KeyModifiers::empty(),
+                modifiers,
             };
             self.send_mouse(mouse_drag_event)?;
         }
@@ -917,7 +961,7 @@ impl EditorTestHarness {
             kind: MouseEventKind::Up(MouseButton::Left),
             column: end_col,
             row: end_row,
-            modifiers: KeyModifiers::empty(),
+            modifiers,
         };
         self.send_mouse(mouse_up)?;
         self.render()?;
@@ -1256,14 +1300,17 @@ impl EditorTestHarness {
 
         for y in 0..height {
             let mut row_text = String::new();
+            // Screen column of the cell each byte of `row_text` came from
+            let mut byte_cols = Vec::new();
             for x in 0..width {
                 let pos = buffer.index_of(x, y);
                 if let Some(cell) = buffer.content.get(pos) {
                     row_text.push_str(cell.symbol());
+                    byte_cols.resize(row_text.len(), x);
                 }
             }
-            if let Some(col) = row_text.find(text) {
-                return Some((col as u16, y));
+            if let Some(byte) = row_text.find(text) {
+                return Some((byte_cols[byte], y));
             }
         }
         None
@@ -1570,13 +1617,14 @@ impl EditorTestHarness {
                 self.shadow_cursor = redo_cursor;
             }
             KeyCode::Left => {
-                if self.shadow_cursor > 0 {
-                    self.shadow_cursor -= 1;
+                // Step over a whole character so the cursor stays on a char boundary
+                if let Some(c) = self.shadow_string[..self.shadow_cursor].chars().next_back() {
+                    self.shadow_cursor -= c.len_utf8();
                 }
             }
             KeyCode::Right => {
-                if self.shadow_cursor < self.shadow_string.len() {
-                    self.shadow_cursor += 1;
+                if let Some(c) = self.shadow_string[self.shadow_cursor..].chars().next() {
+                    self.shadow_cursor += c.len_utf8();
                 }
             }
             KeyCode::Home => {
@@ -1892,6 +1940,8 @@ impl EditorTestHarness {
         let _ = self.editor.process_async_messages();
         // Check debounced completion trigger timer (quick suggestions)
         self.editor.check_completion_trigger_timer();
+        // Start a debounced Quick Open file query
+        self.editor.check_file_suggestions_timer();
         self.render()?;
         Ok(())
     }
@@ -0,0 +1,97 @@
+//! Tests for the Align command
+//!
+//! Tests that:
+//! - Assignments line up on `=`, measured in visual columns
+//! - Markdown table rows line up on their first `|`
+//! - Aligning is a single undo step
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::primitives::display_width::str_width;
+use tempfile::TempDir;
+
+/// Select the whole file and align it on `delimiter`
+fn align_all(harness: &mut EditorTestHarness, delimiter: &str) {
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.type_text("Align").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.type_text(delimiter).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Open `content` as a file named `name` in a new harness
+fn open(name: &str, content: &str) -> (TempDir, EditorTestHarness) {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join(name);
+    std::fs::write(&file_path, content).unwrap();
+    let mut harness = EditorTestHarness::create(80, 24, HarnessOptions::new()).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    (temp_dir, harness)
+}
+
+/// Visual columns of each occurrence of `delimiter` on `line`
+fn delimiter_columns(line: &str, delimiter: &str) -> Vec<usize> {
+    line.match_indices(delimiter)
+        .map(|(at, _)| str_width(&line[..at]))
+        .collect()
+}
+
+/// Test aligning an assignment block on `=`
+#[test]
+fn test_align_assignments() {
+    let original = "x = 1\nlonger_name = 2\n名前 = 3\n# no delimiter\n";
+    let (_temp_dir, mut harness) = open("config.txt", original);
+
+    align_all(&mut harness, "=");
+
+    let content = harness.get_buffer_content().unwrap();
+    assert_eq!(
+        content,
+        "x           = 1\nlonger_name = 2\n名前        = 3\n# no delimiter\n"
+    );
+    let columns: Vec<Vec<usize>> = content
+        .lines()
+        .filter(|line| line.contains('='))
+        .map(|line| delimiter_columns(line, "="))
+        .collect();
+    assert!(columns.iter().all(|c| *c == vec![12]), "{:?}", columns);
+
+    // The alignment is a single undo step
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), original);
+}
+
+/// Test aligning a markdown table on `|`
+#[test]
+fn test_align_markdown_table() {
+    let (_temp_dir, mut harness) = open("table.md", "Name | Qty\n---|---\nÄpfel | 3\n梨 | 10\n");
+
+    align_all(&mut harness, "|");
+
+    let content = harness.get_buffer_content().unwrap();
+    assert_eq!(content, "Name  | Qty\n---   |---\nÄpfel | 3\n梨    | 10\n");
+    let columns: Vec<usize> = content
+        .lines()
+        .map(|line| delimiter_columns(line, "|")[0])
+        .collect();
+    assert!(
+        columns.iter().all(|&c| c == 6),
+        "Table rows should line up: {:?}",
+        columns
+    );
+}
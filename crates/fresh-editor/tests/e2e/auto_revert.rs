@@ -466,3 +466,123 @@ fn test_auto_revert_with_temp_rename_save() {
         harness.assert_buffer_content(&new_content);
     }
 }
+
+/// Open `content` in a buffer, type an unsaved edit, then change the file
+/// externally and wait for the conflict prompt
+fn setup_external_change_conflict(harness: &mut EditorTestHarness, file_path: &Path) {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    write_and_sync(file_path, "Original content");
+    harness.open_file(file_path).unwrap();
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(" - local edit").unwrap();
+
+    harness.sleep(FILE_CHANGE_DELAY);
+    write_and_sync(file_path, "External change");
+
+    harness
+        .wait_until(|h| {
+            h.screen_to_string()
+                .contains("changed on disk. (k)eep mine")
+        })
+        .expect("External change to a modified buffer should prompt");
+    harness.assert_buffer_content("Original content - local edit");
+}
+
+/// Test that an external change to a modified buffer prompts, and that
+/// loading the file replaces the buffer's content
+#[test]
+#[cfg_attr(target_os = "macos", ignore)] // FSEvents coalescing can cause flaky timing
+fn test_external_change_to_modified_buffer_prompts_and_loads() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let file_path = harness.project_dir().unwrap().join("conflict_load.txt");
+    setup_external_change_conflict(&mut harness, &file_path);
+
+    harness.type_text("L").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    harness.assert_buffer_content("External change");
+    assert!(!harness.editor().active_state().buffer.is_modified());
+}
+
+/// Test that keeping the buffer leaves the edit in place without asking
+/// again, and that the diff choice compares the disk file with the buffer
+#[test]
+#[cfg_attr(target_os = "macos", ignore)] // FSEvents coalescing can cause flaky timing
+fn test_external_change_to_modified_buffer_keep_and_diff() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+
+    let kept_path = project_dir.join("conflict_keep.txt");
+    setup_external_change_conflict(&mut harness, &kept_path);
+    harness.type_text("k").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("Original content - local edit");
+
+    // The same change is not reported twice
+    for _ in 0..5 {
+        harness.process_async_and_render().unwrap();
+        harness.sleep(Duration::from_millis(500));
+    }
+    assert!(!harness.editor().is_prompting());
+
+    let diff_path = project_dir.join("conflict_diff.txt");
+    setup_external_change_conflict(&mut harness, &diff_path);
+    harness.type_text("d").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("On disk");
+    harness.assert_screen_contains("External change");
+    harness.assert_screen_contains("Original content - local edit");
+}
+
+/// Test that a buffer whose file is deleted on disk, along with its
+/// directory, is flagged as deleted, and that saving recreates both
+#[test]
+#[cfg_attr(target_os = "macos", ignore)] // FSEvents coalescing can cause flaky timing
+fn test_deleted_file_is_flagged_and_recreated_on_save() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let dir = harness.project_dir().unwrap().join("notes");
+    fs::create_dir(&dir).unwrap();
+    let file_path = dir.join("deleted.txt");
+    write_and_sync(&file_path, "Original content");
+    harness.open_file(&file_path).unwrap();
+    harness.assert_screen_not_contains("[deleted]");
+
+    harness.sleep(FILE_CHANGE_DELAY);
+    fs::remove_dir_all(&dir).unwrap();
+
+    harness
+        .wait_until(|h| h.screen_to_string().contains("[deleted]"))
+        .expect("Deleting the file should flag its buffer");
+    harness.assert_buffer_content("Original content");
+
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(" - kept").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&file_path).unwrap(),
+        "Original content - kept"
+    );
+    harness.assert_screen_not_contains("[deleted]");
+}
@@ -542,3 +542,102 @@ fn test_block_selection_copy_copies_rectangular_region() {
         "Block selection copy should produce exactly the rectangular region"
     );
 }
+
+/// Test Alt+drag creates a zero-width block that puts a caret on every row at
+/// the same visual column, even across tabs and wide characters
+#[test]
+fn test_alt_drag_block_then_type_prefix() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    // "x" sits at visual column 4 on every line: after a tab, after two
+    // double-width characters, and after four ASCII characters
+    let _fixture = harness
+        .load_buffer_from_text("ab\tx\n日本xy\nabcdx\n")
+        .unwrap();
+    harness.render().unwrap();
+
+    let (content_first_row, _) = harness.content_area_rows();
+    let row = content_first_row as u16;
+    let gutter_width = 8;
+
+    harness
+        .mouse_drag_with_modifiers(
+            gutter_width + 4,
+            row,
+            gutter_width + 4,
+            row + 2,
+            KeyModifiers::ALT,
+        )
+        .unwrap();
+
+    harness.type_text("|").unwrap();
+    harness.assert_buffer_content("ab\t|x\n日本|xy\nabcd|x\n");
+}
+
+/// Test typing into a block whose column is past the end of a short line
+/// pads that line with spaces, so every row gets the text at the same column
+#[test]
+fn test_alt_drag_block_pads_short_line_when_typing() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    let _fixture = harness.load_buffer_from_text("abcdx\nab\nabcdx\n").unwrap();
+    harness.render().unwrap();
+
+    let (content_first_row, _) = harness.content_area_rows();
+    let row = content_first_row as u16;
+    let gutter_width = 8;
+
+    harness
+        .mouse_drag_with_modifiers(
+            gutter_width + 4,
+            row,
+            gutter_width + 4,
+            row + 2,
+            KeyModifiers::ALT,
+        )
+        .unwrap();
+
+    harness.type_text("|").unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "abcd|x\nab  |\nabcd|x\n"
+    );
+
+    // The padding is undone together with the typed text
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "abcdx\nab\nabcdx\n");
+}
+
+/// Test Alt+drag past the end of a short line keeps the block's right edge
+/// in virtual space, so longer lines still get the full width
+#[test]
+fn test_alt_drag_block_past_short_line() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.editor_mut().set_clipboard_for_test("".to_string());
+
+    let _fixture = harness.load_buffer_from_text("abcdef\nab\n").unwrap();
+    harness.render().unwrap();
+
+    let (content_first_row, _) = harness.content_area_rows();
+    let row = content_first_row as u16;
+    let gutter_width = 8;
+
+    // Drag from column 1 of the first line to column 5 of the short line
+    harness
+        .mouse_drag_with_modifiers(
+            gutter_width + 1,
+            row,
+            gutter_width + 5,
+            row + 1,
+            KeyModifiers::ALT,
+        )
+        .unwrap();
+    assert!(harness.has_selection(), "Alt+drag should select a block");
+
+    harness
+        .send_key(KeyCode::Char('c'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.editor_mut().clipboard_content_for_test(), "bcde\nb");
+}
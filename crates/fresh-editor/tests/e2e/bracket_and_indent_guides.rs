@@ -0,0 +1,108 @@
+//! E2E tests for matching-bracket highlighting and indent guides.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use ratatui::style::Color;
+
+/// Gutter width for a small buffer: 1 (indicator) + 4 (digits) + 3 (" │ ") = 8
+const SMALL_BUFFER_GUTTER: u16 = 8;
+
+/// Color of an outermost bracket pair with rainbow brackets on
+const DEPTH_0_BRACKET: Color = Color::Rgb(255, 215, 0);
+
+fn bracket_fg(harness: &EditorTestHarness, col: u16, row: u16) -> bool {
+    harness
+        .get_cell_style(SMALL_BUFFER_GUTTER + col, row)
+        .is_some_and(|style| style.fg == Some(DEPTH_0_BRACKET))
+}
+
+#[test]
+fn test_bracket_before_cursor_highlights_its_match() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let _fixture = harness.load_buffer_from_text("foo(bar)\n").unwrap();
+
+    // Cursor on the "b", just after the "("
+    for _ in 0..4 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::NONE)
+            .unwrap();
+    }
+    harness.render().unwrap();
+
+    let row = harness.content_area_rows().0 as u16;
+    assert!(bracket_fg(&harness, 3, row), "'(' should be highlighted");
+    assert!(bracket_fg(&harness, 7, row), "')' should be highlighted");
+    assert!(
+        !bracket_fg(&harness, 5, row),
+        "text should not be highlighted"
+    );
+
+    // Away from any bracket, nothing is highlighted
+    harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    assert!(!bracket_fg(&harness, 3, row));
+    assert!(!bracket_fg(&harness, 7, row));
+}
+
+#[test]
+fn test_bracket_highlight_can_be_disabled() {
+    let mut config = Config::default();
+    config.editor.highlight_matching_brackets = false;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    let _fixture = harness.load_buffer_from_text("(a)\n").unwrap();
+    harness.render().unwrap();
+
+    let row = harness.content_area_rows().0 as u16;
+    assert!(!bracket_fg(&harness, 0, row));
+    assert!(!bracket_fg(&harness, 2, row));
+}
+
+#[test]
+fn test_indent_guides_at_each_level() {
+    let mut config = Config::default();
+    config.editor.show_indent_guides = true;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    let _fixture = harness
+        .load_buffer_from_text("fn main() {\n    if x {\n        y();\n\n    }\n}\n")
+        .unwrap();
+    harness.render().unwrap();
+
+    let first = harness.content_area_rows().0 as u16;
+    let guides = |row: u16| -> Vec<u16> {
+        (0..12)
+            .filter(|&col| {
+                harness
+                    .get_cell(SMALL_BUFFER_GUTTER + col, first + row)
+                    .as_deref()
+                    == Some("│")
+            })
+            .collect()
+    };
+
+    assert_eq!(guides(0), Vec::<u16>::new(), "unindented line");
+    assert_eq!(guides(1), vec![0], "one level");
+    assert_eq!(guides(2), vec![0, 4], "two levels");
+    assert_eq!(guides(3), Vec::<u16>::new(), "blank line");
+    assert_eq!(guides(4), vec![0], "closing brace");
+
+    // The code itself is untouched
+    let code: String = (8..12)
+        .filter_map(|col| harness.get_cell(SMALL_BUFFER_GUTTER + col, first + 2))
+        .collect();
+    assert_eq!(code, "y();");
+}
+
+#[test]
+fn test_indent_guides_off_by_default() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let _fixture = harness.load_buffer_from_text("a\n    b\n").unwrap();
+    harness.render().unwrap();
+
+    let row = harness.content_area_rows().0 as u16 + 1;
+    assert_eq!(
+        harness.get_cell(SMALL_BUFFER_GUTTER, row).as_deref(),
+        Some(" ")
+    );
+}
@@ -0,0 +1,67 @@
+//! Tests for Paste from History
+//!
+//! Tests that every copy enters the clipboard history, and that an older
+//! entry picked from the history picker is pasted at the cursor.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Select the word starting `offset` characters into the line and copy it
+fn copy_word(harness: &mut EditorTestHarness, offset: usize, len: usize) {
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    for _ in 0..offset {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::NONE)
+            .unwrap();
+    }
+    for _ in 0..len {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::SHIFT)
+            .unwrap();
+    }
+    harness
+        .send_key(KeyCode::Char('c'), KeyModifiers::CONTROL)
+        .unwrap();
+}
+
+#[test]
+fn test_paste_second_oldest_from_history() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    // Keep the system clipboard out of the test
+    harness.editor_mut().set_clipboard_for_test(String::new());
+    harness.type_text("alpha beta gamma delta").unwrap();
+
+    copy_word(&mut harness, 0, 5);
+    copy_word(&mut harness, 6, 4);
+    copy_word(&mut harness, 11, 5);
+    copy_word(&mut harness, 17, 5);
+
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.type_text("Paste from History").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    // Newest first: delta, gamma, beta, alpha
+    harness.assert_screen_contains("Paste from history:");
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "alpha beta gamma deltabeta"
+    );
+    assert_eq!(
+        harness.cursor_position(),
+        "alpha beta gamma deltabeta".len()
+    );
+}
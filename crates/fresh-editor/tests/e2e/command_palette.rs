@@ -745,6 +745,31 @@ fn test_command_palette_shortcuts_with_filtering() {
     // So we just check that the command appears
 }
 
+/// Test that typing a key combination finds the commands bound to it
+#[test]
+fn test_command_palette_filter_by_shortcut() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let mut harness = EditorTestHarness::new(120, 30).unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+
+    // Emacs-style notation for Ctrl+S
+    harness.type_text("C-s").unwrap();
+    harness.render().unwrap();
+
+    let screen = harness.screen_to_string();
+    let first_suggestion = screen
+        .lines()
+        .find(|line| line.contains("Ctrl+S") || line.contains("⌃S"))
+        .expect("Should list the command bound to Ctrl+S");
+    assert!(
+        first_suggestion.contains("Save File"),
+        "Ctrl+S should find Save File, got: {first_suggestion}"
+    );
+}
+
 /// Test that shortcuts are displayed in a column format in the command palette
 #[test]
 fn test_command_palette_shortcuts_alignment() {
@@ -972,3 +997,104 @@ fn test_command_palette_select_cursor_style() {
         .wait_for_screen_contains("Cursor style changed")
         .unwrap();
 }
+
+/// Test that a `:line:col` suffix in Quick Open file mode opens the file
+/// at that 1-based position
+#[test]
+fn test_quick_open_file_with_line_and_column() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_dir = temp_dir.path().to_path_buf();
+    std::fs::write(project_dir.join("target.txt"), "first\nsecond\nthird\n").unwrap();
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        24,
+        fresh::config::Config::default(),
+        project_dir,
+    )
+    .unwrap();
+
+    // Ctrl+P starts in command mode; drop the ">" to search files
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("target.txt:3:2").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("target.txt");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_buffer_content("first\nsecond\nthird\n");
+    // Line 3, column 2 is the "h" of "third"
+    assert_eq!(harness.cursor_position(), "first\nsecond\n".len() + 1);
+}
+
+/// Test that typing quickly in Quick Open file mode only ever shows the
+/// results of the latest query, even while slow scans for earlier queries
+/// are still running
+#[test]
+fn test_quick_open_file_results_follow_latest_query() {
+    use crate::common::harness::HarnessOptions;
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use fresh::services::fs::SlowFsConfig;
+    use std::time::Duration;
+
+    let mut harness = EditorTestHarness::create(
+        100,
+        24,
+        HarnessOptions::new()
+            .with_project_root()
+            .with_slow_fs(SlowFsConfig::uniform(Duration::from_millis(20))),
+    )
+    .unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    for path in ["a/alpha.txt", "b/beta.txt", "c/gamma.txt"] {
+        let path = project_dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, "").unwrap();
+    }
+
+    // Ctrl+P starts in command mode; drop the ">" to search files
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("alpha").unwrap();
+    // Let the scan for "alpha" start, then replace the query
+    harness.advance_time(Duration::from_millis(60));
+    harness.process_async_and_render().unwrap();
+    for _ in 0.."alpha".len() {
+        harness
+            .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+            .unwrap();
+    }
+    harness.type_text("bet").unwrap();
+    for _ in 0.."bet".len() {
+        harness
+            .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+            .unwrap();
+    }
+    harness.type_text("gamma").unwrap();
+
+    harness
+        .wait_until(|h| h.screen_to_string().contains("c/gamma.txt"))
+        .unwrap();
+    harness.assert_screen_not_contains("alpha.txt");
+    harness.assert_screen_not_contains("beta.txt");
+
+    // The superseded queries never start a scan of their own later
+    harness.advance_time(Duration::from_millis(300));
+    harness.process_async_and_render().unwrap();
+    harness.assert_screen_contains("c/gamma.txt");
+    harness.assert_screen_not_contains("alpha.txt");
+    harness.assert_screen_not_contains("beta.txt");
+}
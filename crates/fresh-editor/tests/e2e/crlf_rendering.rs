@@ -776,3 +776,48 @@ fn test_set_line_ending_converts_on_save_crlf_to_lf() {
         "All line endings should be converted to LF"
     );
 }
+
+/// Test that interleaved LF and CRLF endings are reported as mixed and that
+/// "Normalize Line Endings" saves them in a single format
+#[test]
+fn test_normalize_mixed_line_endings() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("mixed_normalize.txt");
+
+    // CRLF is the dominant format
+    std::fs::write(&file_path, "Line 1\r\nLine 2\nLine 3\r\nLine 4\r\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Mixed (CRLF)");
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Normalize Line Endings").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.assert_screen_contains("Normalize line endings to:");
+
+    // The dominant format is preselected
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt_closed().unwrap();
+    harness.assert_screen_not_contains("Mixed");
+
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_until(|h| !h.editor().active_state().buffer.is_modified())
+        .unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(&file_path).unwrap(),
+        "Line 1\r\nLine 2\r\nLine 3\r\nLine 4\r\n"
+    );
+}
@@ -0,0 +1,149 @@
+//! E2E tests for diagnostics providers and pushed diagnostics
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::services::diagnostics::DiagnosticsProvider;
+use lsp_types::{Diagnostic, DiagnosticSeverity, Position, Range};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Reports `FIXME` as an error and `TODO` as a warning on the line they're on
+struct MarkerLinter;
+
+impl DiagnosticsProvider for MarkerLinter {
+    fn name(&self) -> &str {
+        "markers"
+    }
+
+    fn check(&self, _path: &Path, contents: &str) -> Vec<Diagnostic> {
+        contents
+            .lines()
+            .enumerate()
+            .filter_map(|(line, text)| {
+                let (column, severity) = match (text.find("FIXME"), text.find("TODO")) {
+                    (Some(column), _) => (column, DiagnosticSeverity::ERROR),
+                    (None, Some(column)) => (column, DiagnosticSeverity::WARNING),
+                    (None, None) => return None,
+                };
+                Some(diagnostic(line as u32, column as u32, severity, text))
+            })
+            .collect()
+    }
+}
+
+fn diagnostic(line: u32, column: u32, severity: DiagnosticSeverity, message: &str) -> Diagnostic {
+    Diagnostic {
+        range: Range {
+            start: Position {
+                line,
+                character: column,
+            },
+            end: Position {
+                line,
+                character: column + 4,
+            },
+        },
+        severity: Some(severity),
+        message: message.to_string(),
+        ..Default::default()
+    }
+}
+
+/// Buffer lines (0-based) with a diagnostic marker in the gutter, with the
+/// marker's color
+fn gutter_markers(harness: &EditorTestHarness) -> Vec<(usize, Option<ratatui::style::Color>)> {
+    let (first_row, last_row) = harness.content_area_rows();
+    (first_row..=last_row)
+        .filter(|&row| harness.get_cell(0, row as u16).as_deref() == Some("●"))
+        .map(|row| {
+            let color = harness.get_cell_style(0, row as u16).and_then(|s| s.fg);
+            (row - first_row, color)
+        })
+        .collect()
+}
+
+fn cursor_line(harness: &EditorTestHarness) -> usize {
+    let position = harness.editor().active_cursors().primary().position;
+    harness
+        .editor()
+        .active_state()
+        .buffer
+        .get_line_number(position)
+}
+
+#[test]
+fn test_provider_diagnostics_mark_gutter_and_cycle() {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24).unwrap();
+    let file_path = harness.project_dir().unwrap().join("notes.txt");
+    std::fs::write(&file_path, "fine\n// TODO tidy\nfine\n// FIXME broken\n").unwrap();
+    harness
+        .editor_mut()
+        .register_diagnostics_provider(Arc::new(MarkerLinter));
+
+    harness.open_file(&file_path).unwrap();
+    harness
+        .wait_until(|h| gutter_markers(h).len() == 2)
+        .unwrap();
+
+    let theme = harness.editor().theme();
+    let expected = vec![
+        (1, Some(theme.diagnostic_warning_fg)),
+        (3, Some(theme.diagnostic_error_fg)),
+    ];
+    assert_eq!(gutter_markers(&harness), expected);
+
+    // Next Diagnostic visits both lines, then wraps to the first
+    for expected in [1, 3, 1] {
+        harness.send_key(KeyCode::F(8), KeyModifiers::NONE).unwrap();
+        assert_eq!(cursor_line(&harness), expected);
+    }
+    // Previous Diagnostic wraps back to the last
+    harness
+        .send_key(KeyCode::F(8), KeyModifiers::SHIFT)
+        .unwrap();
+    assert_eq!(cursor_line(&harness), 3);
+
+    // Markers move with the text they're on
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    let lines: Vec<usize> = gutter_markers(&harness).iter().map(|m| m.0).collect();
+    assert_eq!(lines, vec![2, 4]);
+}
+
+#[test]
+fn test_pushed_diagnostics_are_kept_per_source() {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24).unwrap();
+    let file_path = harness.project_dir().unwrap().join("main.txt");
+    std::fs::write(&file_path, "one\ntwo\nthree\n").unwrap();
+    harness.open_file(&file_path).unwrap();
+
+    harness.editor_mut().set_diagnostics(
+        &file_path,
+        "spell",
+        vec![diagnostic(0, 0, DiagnosticSeverity::HINT, "spelling")],
+    );
+    harness.editor_mut().set_diagnostics(
+        &file_path,
+        "lint",
+        vec![diagnostic(2, 0, DiagnosticSeverity::ERROR, "lint")],
+    );
+    harness.render().unwrap();
+    let lines: Vec<usize> = gutter_markers(&harness).iter().map(|m| m.0).collect();
+    assert_eq!(lines, vec![0, 2]);
+
+    // Clearing one source leaves the other's diagnostics
+    harness
+        .editor_mut()
+        .set_diagnostics(&file_path, "lint", Vec::new());
+    harness.render().unwrap();
+    let lines: Vec<usize> = gutter_markers(&harness).iter().map(|m| m.0).collect();
+    assert_eq!(lines, vec![0]);
+    let stored = harness.editor().get_stored_diagnostics();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored.values().next().unwrap()[0].message, "spelling");
+}
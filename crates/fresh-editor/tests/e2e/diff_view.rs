@@ -0,0 +1,205 @@
+//! Tests for side-by-side diff views (Compare with File / Compare with Saved)
+//!
+//! Tests that:
+//! - Added, removed and changed lines become separate hunks aligned row by row
+//! - n/p step between changes and stop at the last one
+//! - Compare with Saved diffs unsaved edits against the file on disk
+//! - The view realigns after the buffer is edited
+//! - Closing the view frees the hidden buffer holding the other text
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::model::composite_buffer::RowType;
+
+/// Helper to run a command from the command palette
+fn run_command(harness: &mut EditorTestHarness, command_name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.type_text(command_name).unwrap();
+    harness.render().unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Row type and the old/new line shown on each row of the active diff view
+fn diff_rows(harness: &EditorTestHarness) -> Vec<(RowType, Option<usize>, Option<usize>)> {
+    let editor = harness.editor();
+    let composite = editor
+        .get_composite(editor.active_buffer())
+        .expect("a diff view should be active");
+    composite
+        .alignment
+        .rows
+        .iter()
+        .map(|row| {
+            let line = |pane: usize| row.get_pane_line(pane).map(|line_ref| line_ref.line);
+            (row.row_type, line(0), line(1))
+        })
+        .collect()
+}
+
+/// Row of the cursor in the active diff view
+fn diff_cursor_row(harness: &mut EditorTestHarness) -> usize {
+    let split_id = harness.editor().get_active_split();
+    let buffer_id = harness.editor().active_buffer();
+    harness
+        .editor_mut()
+        .get_composite_view_state(split_id, buffer_id)
+        .unwrap()
+        .cursor_row
+}
+
+/// Press `key` in the diff view and return the cursor row
+fn press(harness: &mut EditorTestHarness, key: char) -> usize {
+    harness
+        .send_key(KeyCode::Char(key), KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    diff_cursor_row(harness)
+}
+
+#[test]
+fn test_compare_with_file_aligns_hunks_and_navigates_changes() {
+    let mut harness = EditorTestHarness::with_temp_project(120, 30).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    std::fs::write(
+        project_dir.join("old.txt"),
+        "alpha\nbeta\ngamma\ndelta\nepsilon\nzeta\neta\ntheta\niota\n",
+    )
+    .unwrap();
+    let new_path = project_dir.join("new.txt");
+    std::fs::write(
+        &new_path,
+        "alpha\nbeta\ngamma ray\ndelta\nzeta\neta\ninserted\ntheta\niota\n",
+    )
+    .unwrap();
+    harness.open_file(&new_path).unwrap();
+
+    run_command(&mut harness, "Compare with File");
+    harness.type_text("old.txt").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("3 change(s)");
+
+    let rows = diff_rows(&harness);
+    assert_eq!(
+        rows[..13],
+        [
+            (RowType::Context, Some(0), Some(0)),
+            (RowType::Context, Some(1), Some(1)),
+            (RowType::HunkHeader, None, None),
+            (RowType::Modification, Some(2), Some(2)),
+            (RowType::Context, Some(3), Some(3)),
+            (RowType::HunkHeader, None, None),
+            (RowType::Deletion, Some(4), None),
+            (RowType::Context, Some(5), Some(4)),
+            (RowType::Context, Some(6), Some(5)),
+            (RowType::HunkHeader, None, None),
+            (RowType::Addition, None, Some(6)),
+            (RowType::Context, Some(7), Some(7)),
+            (RowType::Context, Some(8), Some(8)),
+        ]
+    );
+    harness.assert_screen_contains("gamma ray");
+    harness.assert_screen_contains("epsilon");
+    harness.assert_screen_contains("inserted");
+
+    // Next change visits each hunk in order, then stops at the last one
+    assert_eq!(press(&mut harness, 'n'), 2);
+    harness.assert_screen_contains("Change 1 of 3");
+    assert_eq!(press(&mut harness, 'n'), 5);
+    harness.assert_screen_contains("Change 2 of 3");
+    assert_eq!(press(&mut harness, 'n'), 9);
+    harness.assert_screen_contains("Change 3 of 3");
+    assert_eq!(press(&mut harness, 'n'), 9);
+    harness.assert_screen_contains("No more changes");
+
+    assert_eq!(press(&mut harness, 'p'), 5);
+    harness.assert_screen_contains("Change 2 of 3");
+}
+
+#[test]
+fn test_compare_with_saved_shows_unsaved_edit() {
+    let mut harness = EditorTestHarness::with_temp_project(120, 30).unwrap();
+    let path = harness.project_dir().unwrap().join("notes.txt");
+    std::fs::write(&path, "first\nsecond\nthird\n").unwrap();
+    harness.open_file(&path).unwrap();
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.type_text("my ").unwrap();
+
+    run_command(&mut harness, "Compare with Saved");
+    harness.assert_screen_contains("1 change(s)");
+    harness.assert_screen_contains("my second");
+
+    let rows = diff_rows(&harness);
+    assert_eq!(
+        rows[..4],
+        [
+            (RowType::Context, Some(0), Some(0)),
+            (RowType::HunkHeader, None, None),
+            (RowType::Modification, Some(1), Some(1)),
+            (RowType::Context, Some(2), Some(2)),
+        ]
+    );
+}
+
+#[test]
+fn test_diff_view_follows_edits_to_the_buffer() {
+    let mut harness = EditorTestHarness::with_temp_project(120, 30).unwrap();
+    let path = harness.project_dir().unwrap().join("notes.txt");
+    std::fs::write(&path, "first\nsecond\nthird\n").unwrap();
+    harness.open_file(&path).unwrap();
+
+    run_command(&mut harness, "Compare with Saved");
+    harness.assert_screen_contains("No differences");
+    let diff_id = harness.editor().active_buffer();
+
+    // Edit the file in its own tab, then come back to the diff
+    harness.open_file(&path).unwrap();
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("fourth\n").unwrap();
+    harness.editor_mut().switch_buffer(diff_id);
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("fourth");
+    let rows = diff_rows(&harness);
+    assert_eq!(
+        rows[..5],
+        [
+            (RowType::Context, Some(0), Some(0)),
+            (RowType::Context, Some(1), Some(1)),
+            (RowType::Context, Some(2), Some(2)),
+            (RowType::HunkHeader, None, None),
+            (RowType::Addition, None, Some(3)),
+        ]
+    );
+    assert_eq!(press(&mut harness, 'n'), 3);
+}
+
+#[test]
+fn test_closing_diff_view_frees_its_hidden_buffer() {
+    let mut harness = EditorTestHarness::with_temp_project(120, 30).unwrap();
+    let path = harness.project_dir().unwrap().join("notes.txt");
+    std::fs::write(&path, "first\nsecond\n").unwrap();
+    harness.open_file(&path).unwrap();
+    harness.type_text("new ").unwrap();
+
+    run_command(&mut harness, "Compare with Saved");
+    let diff_id = harness.editor().active_buffer();
+    let saved_id = harness.editor().get_composite(diff_id).unwrap().sources[0].buffer_id;
+    assert!(harness.editor().get_buffer_content(saved_id).is_some());
+
+    run_command(&mut harness, "Close Buffer");
+    assert!(harness.editor().get_composite(diff_id).is_none());
+    assert!(harness.editor().get_buffer_content(saved_id).is_none());
+    harness.assert_screen_contains("new first");
+}
@@ -208,3 +208,51 @@ fn test_duplicate_line_undo() {
         "Undo should restore original content"
     );
 }
+
+/// Duplicating a multi-line selection keeps the selection, moved onto the copy
+#[test]
+fn test_duplicate_selection_moves_selection_to_copy() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.type_text("ünï\nçödé\nend").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    // Select from after "ü" to after "ç" on the next line
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+    let original = "ünï\nçödé\n".len();
+    assert_eq!(
+        harness.get_selection_range(),
+        Some("ü".len().."ünï\nç".len())
+    );
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains(">command"))
+        .unwrap();
+    harness.type_text("duplicate line").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_buffer_content("ünï\nçödé\nünï\nçödé\nend");
+    assert_eq!(
+        harness.get_selection_range(),
+        Some(original + "ü".len()..original + "ünï\nç".len())
+    );
+    assert_eq!(harness.cursor_position(), original + "ünï\nç".len());
+
+    // One undo step removes the copy
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("ünï\nçödé\nend");
+}
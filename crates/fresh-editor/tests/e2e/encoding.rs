@@ -767,6 +767,79 @@ fn test_utf8_bom_hidden_but_preserved() {
     );
 }
 
+/// Test that UTF-16 LE detected without a BOM is not given one on save
+#[test]
+fn test_utf16_le_without_bom_saved_without_bom() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("utf16_no_bom.txt");
+
+    let encode = |text: &str| -> Vec<u8> {
+        text.encode_utf16()
+            .flat_map(|unit| unit.to_le_bytes())
+            .collect()
+    };
+    std::fs::write(&file_path, encode("Hello\n")).unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("UTF-16 LE");
+    assert!(!harness.editor().active_state().buffer.has_bom());
+
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.type_text(" World").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_until(|h| !h.editor().active_state().buffer.is_modified())
+        .unwrap();
+
+    let saved = std::fs::read(&file_path).unwrap();
+    assert_eq!(saved, encode("Hello World\n"), "No BOM should be added");
+}
+
+/// Test adding and removing the BOM with the command palette
+#[test]
+fn test_toggle_bom_command() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("toggle_bom.txt");
+
+    let mut content = UTF8_BOM.to_vec();
+    content.extend_from_slice(b"Hello\n");
+    std::fs::write(&file_path, &content).unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    let toggle_and_save = |harness: &mut EditorTestHarness| {
+        harness
+            .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.type_text("Toggle Byte Order").unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+        harness.render().unwrap();
+        harness
+            .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness
+            .wait_until(|h| !h.editor().active_state().buffer.is_modified())
+            .unwrap();
+        std::fs::read(&file_path).unwrap()
+    };
+
+    // Removing the BOM leaves plain UTF-8
+    assert_eq!(toggle_and_save(&mut harness), b"Hello\n");
+    harness.assert_buffer_content("Hello\n");
+
+    // Adding it back writes the BOM again, still outside the text
+    assert_eq!(toggle_and_save(&mut harness), content);
+    harness.assert_buffer_content("Hello\n");
+}
+
 /// Test handling of empty file
 #[test]
 fn test_empty_file_defaults_to_utf8() {
@@ -901,6 +974,83 @@ fn test_latin1_special_chars_display() {
     harness.assert_screen_contains("Café");
 }
 
+/// Test that an edited Latin-1 file is saved back as Latin-1
+#[test]
+fn test_latin1_round_trip_after_edit() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("latin1_round_trip.txt");
+
+    // "Café crème" in Latin-1
+    let latin1_bytes: &[u8] = &[
+        0x43, 0x61, 0x66, 0xE9, 0x20, 0x63, 0x72, 0xE8, 0x6D, 0x65, 0x0A,
+    ];
+    std::fs::write(&file_path, latin1_bytes).unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    harness.assert_buffer_content("Café crème\n");
+
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.type_text(" à la française").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_until(|h| !h.editor().active_state().buffer.is_modified())
+        .unwrap();
+
+    // Every character is re-encoded as a single Latin-1 byte
+    let expected: Vec<u8> = "Café crème à la française\n"
+        .chars()
+        .map(|c| c as u32 as u8)
+        .collect();
+    assert_eq!(std::fs::read(&file_path).unwrap(), expected);
+}
+
+/// Test converting a Latin-1 file to UTF-8 with "Save with Encoding..."
+#[test]
+fn test_save_with_encoding_command() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("latin1_convert.txt");
+
+    // "Café crème" in Latin-1
+    let latin1_bytes: &[u8] = &[
+        0x43, 0x61, 0x66, 0xE9, 0x20, 0x63, 0x72, 0xE8, 0x6D, 0x65, 0x0A,
+    ];
+    std::fs::write(&file_path, latin1_bytes).unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Save with Encoding").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Save with encoding:");
+
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("UTF-8").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_until(|h| !h.editor().active_state().buffer.is_modified())
+        .unwrap();
+
+    assert_eq!(
+        std::fs::read(&file_path).unwrap(),
+        "Café crème\n".as_bytes()
+    );
+}
+
 /// Test encoding display in status bar
 /// Note: UTF-8 and ASCII are hidden from status bar (as they're the expected defaults)
 /// This test verifies encoding is shown for non-default encodings like UTF-16
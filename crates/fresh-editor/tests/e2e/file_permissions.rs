@@ -2,6 +2,7 @@
 
 use crate::common::harness::EditorTestHarness;
 use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
 use std::fs::Permissions;
 use tempfile::TempDir;
 
@@ -397,3 +398,73 @@ fn test_save_preserves_various_permission_modes() {
         );
     }
 }
+
+/// Test that backup_on_save keeps the previous version in `file~` with the
+/// original's permissions, and a second save backs up the first save's result
+#[test]
+#[cfg(unix)]
+fn test_backup_on_save_keeps_previous_version() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.txt");
+    let backup_path = temp_dir.path().join("test.txt~");
+
+    std::fs::write(&file_path, "original").unwrap();
+    std::fs::set_permissions(&file_path, Permissions::from_mode(0o640)).unwrap();
+
+    let mut config = Config::default();
+    config.editor.backup_on_save = true;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    harness.type_text("first ").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(std::fs::read_to_string(&backup_path).unwrap(), "original");
+    assert_eq!(
+        std::fs::read_to_string(&file_path).unwrap(),
+        "first original"
+    );
+    let mode =
+        |path: &std::path::Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+    assert_eq!(mode(&file_path), 0o640);
+    assert_eq!(mode(&backup_path), 0o640);
+
+    harness.type_text("second ").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(&backup_path).unwrap(),
+        "first original"
+    );
+    assert_eq!(
+        std::fs::read_to_string(&file_path).unwrap(),
+        "first second original"
+    );
+}
+
+/// Test that no backup is written unless backup_on_save is enabled
+#[test]
+fn test_no_backup_by_default() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.txt");
+    std::fs::write(&file_path, "original").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.type_text("x").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "xoriginal");
+    assert!(!temp_dir.path().join("test.txt~").exists());
+}
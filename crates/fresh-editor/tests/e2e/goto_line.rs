@@ -0,0 +1,139 @@
+// End-to-end tests for the Go to Line prompt: absolute, relative and
+// percentage targets
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tempfile::TempDir;
+
+/// Open a file of `count` lines named "Line 1".."Line N" (no trailing newline)
+fn harness_with_lines(count: usize) -> (EditorTestHarness, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("lines.txt");
+    let content: Vec<String> = (1..=count).map(|i| format!("Line {i}")).collect();
+    std::fs::write(&file_path, content.join("\n")).unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    (harness, temp_dir)
+}
+
+fn goto_line(harness: &mut EditorTestHarness, input: &str) {
+    harness
+        .send_key(KeyCode::Char('g'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(input).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// 1-based line of the primary cursor
+fn cursor_line(harness: &EditorTestHarness) -> usize {
+    let position = harness.cursor_position();
+    harness
+        .editor()
+        .active_state()
+        .buffer
+        .get_line_number(position)
+        + 1
+}
+
+/// The editor's status message, which the status bar may cut short
+fn status_message(harness: &EditorTestHarness) -> String {
+    harness
+        .editor()
+        .get_status_message()
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Byte offset of the start of a 1-based line in the "Line N" fixture
+fn line_start(line: usize) -> usize {
+    (1..line).map(|i| format!("Line {i}\n").len()).sum()
+}
+
+#[test]
+fn test_goto_absolute_line_centers_it() {
+    let (mut harness, _temp_dir) = harness_with_lines(200);
+
+    goto_line(&mut harness, "100");
+
+    assert_eq!(harness.cursor_position(), line_start(100));
+    assert!(status_message(&harness).contains("Jumped to line 100"));
+
+    // The target line sits in the middle of the viewport
+    let (row_start, row_end) = harness.content_area_rows();
+    let (_, cursor_row) = harness.screen_cursor_position();
+    let middle = (row_start + row_end) / 2;
+    assert!(
+        (cursor_row as usize).abs_diff(middle) <= 1,
+        "cursor row {cursor_row} should be near the middle row {middle}"
+    );
+}
+
+#[test]
+fn test_goto_relative_line_keeps_column() {
+    let (mut harness, _temp_dir) = harness_with_lines(200);
+
+    goto_line(&mut harness, "50");
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.cursor_position(), line_start(50) + "Line 50".len());
+
+    goto_line(&mut harness, "+20");
+    assert_eq!(cursor_line(&harness), 70);
+    assert_eq!(harness.cursor_position(), line_start(70) + "Line 70".len());
+
+    goto_line(&mut harness, "-5");
+    assert_eq!(cursor_line(&harness), 65);
+    assert_eq!(harness.cursor_position(), line_start(65) + "Line 65".len());
+
+    // A shorter line clamps the column, and the goal column comes back
+    goto_line(&mut harness, "-60");
+    assert_eq!(harness.cursor_position(), line_start(5) + "Line 5".len());
+    goto_line(&mut harness, "+95");
+    assert_eq!(harness.cursor_position(), line_start(100) + "Line 10".len());
+}
+
+#[test]
+fn test_goto_percentage() {
+    let (mut harness, _temp_dir) = harness_with_lines(200);
+
+    goto_line(&mut harness, "50%");
+    assert_eq!(harness.cursor_position(), line_start(100));
+
+    goto_line(&mut harness, "0%");
+    assert_eq!(harness.cursor_position(), 0);
+
+    goto_line(&mut harness, "100%");
+    assert_eq!(harness.cursor_position(), line_start(200));
+}
+
+#[test]
+fn test_goto_out_of_range_clamps() {
+    let (mut harness, _temp_dir) = harness_with_lines(50);
+
+    goto_line(&mut harness, "5000");
+    assert_eq!(harness.cursor_position(), line_start(50));
+    harness.assert_screen_contains("Line 50");
+
+    goto_line(&mut harness, "-5000");
+    assert_eq!(harness.cursor_position(), 0);
+
+    goto_line(&mut harness, "+5000");
+    assert_eq!(cursor_line(&harness), 50);
+
+    goto_line(&mut harness, "250%");
+    assert_eq!(harness.cursor_position(), line_start(50));
+}
+
+#[test]
+fn test_goto_invalid_input_does_not_move() {
+    let (mut harness, _temp_dir) = harness_with_lines(50);
+
+    goto_line(&mut harness, "10");
+    goto_line(&mut harness, "ten");
+    assert_eq!(harness.cursor_position(), line_start(10));
+    assert!(status_message(&harness).contains("Invalid line number"));
+}
@@ -391,11 +391,11 @@ fn test_tab_preserves_selection() {
     );
 
     let selection = cursor.selection_range().unwrap();
-    // Selection should start at beginning (position 0 + 4 spaces = 4)
-    // and end at the end of file (original 20 chars + 12 spaces = 32)
+    // Selection covers the whole lines: from the start of the first line
+    // to the end of file (original 20 chars + 12 spaces = 32)
     assert_eq!(
-        selection.start, 4,
-        "Selection should start after first indent"
+        selection.start, 0,
+        "Selection should start at the beginning of the first line"
     );
     assert_eq!(
         selection.end, 32,
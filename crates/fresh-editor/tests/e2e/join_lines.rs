@@ -0,0 +1,97 @@
+//! Tests for Join Lines
+//!
+//! Tests that:
+//! - Line breaks and indentation collapse to a single space
+//! - No space is added inside brackets
+//! - A repeated comment token is dropped from joined comment lines
+//! - Joining on the last line changes nothing
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use tempfile::TempDir;
+
+/// Helper to run a command from the command palette
+fn run_command(harness: &mut EditorTestHarness, command_name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.type_text(command_name).unwrap();
+    harness.render().unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Open `content` as a file named `name` in a new harness
+fn open(name: &str, content: &str) -> (TempDir, EditorTestHarness) {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join(name);
+    std::fs::write(&file_path, content).unwrap();
+    let mut harness = EditorTestHarness::create(80, 24, HarnessOptions::new()).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    (temp_dir, harness)
+}
+
+/// Test joining a selection of indented lines, including bracket edge cases
+#[test]
+fn test_join_selected_indented_lines() {
+    let original = "fn f() {\n    call(\n        first,\n        second\n    );\n}\n";
+    let (_temp_dir, mut harness) = open("test.rs", original);
+
+    // Select the four lines of the call
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    for _ in 0..4 {
+        harness
+            .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+            .unwrap();
+    }
+    run_command(&mut harness, "Join Lines");
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "fn f() {\n    call(first, second);\n}\n"
+    );
+    assert_eq!(harness.get_selected_text(), "    call(first, second);");
+
+    // The join is a single undo step
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), original);
+}
+
+/// Test that joining without a selection merges the next line and drops its comment token
+#[test]
+fn test_join_comment_lines() {
+    let (_temp_dir, mut harness) = open("test.rs", "// one\n    // two\nlet x = 1;\n");
+
+    run_command(&mut harness, "Join Lines");
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "// one two\nlet x = 1;\n"
+    );
+    // The cursor is left at the join point
+    assert_eq!(harness.cursor_position(), 6);
+}
+
+/// Test joining onto the final line, and that joining on the final line is a no-op
+#[test]
+fn test_join_final_line() {
+    let (_temp_dir, mut harness) = open("test.txt", "first\n   last");
+
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    run_command(&mut harness, "Join Lines");
+    assert_eq!(harness.get_buffer_content().unwrap(), "first\n   last");
+
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    run_command(&mut harness, "Join Lines");
+    assert_eq!(harness.get_buffer_content().unwrap(), "first last");
+}
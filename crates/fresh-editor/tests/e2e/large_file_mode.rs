@@ -1,6 +1,9 @@
 use crate::common::fixtures::TestFixture;
-use crate::common::harness::EditorTestHarness;
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
 use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::model::buffer::LOAD_CHUNK_SIZE;
+use fresh::services::fs::SlowFsConfig;
+use std::sync::atomic::Ordering;
 
 /// Test cursor positioning when moving down in large file mode
 /// This test catches a bug where cursor movement with Down arrow key
@@ -671,3 +674,52 @@ fn test_large_file_edits_beginning_middle_end() {
         );
     }
 }
+
+/// Test that opening and scrolling a large file only reads the chunks around
+/// the viewport instead of the whole file
+#[test]
+fn test_large_file_scrolling_reads_only_viewport_chunks() {
+    let big_txt_path = TestFixture::big_txt_for_test("viewport_chunks").unwrap();
+    let file_size = std::fs::metadata(&big_txt_path).unwrap().len() as usize;
+
+    let mut harness = EditorTestHarness::create(
+        80,
+        24,
+        HarnessOptions::new().with_slow_fs(SlowFsConfig::none()),
+    )
+    .unwrap();
+    let metrics = harness.fs_metrics().unwrap().clone();
+
+    harness.open_file(&big_txt_path).unwrap();
+    harness.render().unwrap();
+    let after_open = metrics.bytes_read.load(Ordering::SeqCst);
+    assert!(
+        after_open <= LOAD_CHUNK_SIZE * 2,
+        "Opening read {} bytes of a {} byte file",
+        after_open,
+        file_size
+    );
+
+    // Paging through the first screens stays within the loaded chunk
+    for _ in 0..10 {
+        harness
+            .send_key(KeyCode::PageDown, KeyModifiers::NONE)
+            .unwrap();
+    }
+    harness.render().unwrap();
+    assert_eq!(metrics.bytes_read.load(Ordering::SeqCst), after_open);
+
+    // Jumping to the end loads only the chunks near the end
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    let total = metrics.bytes_read.load(Ordering::SeqCst);
+    assert!(
+        total <= LOAD_CHUNK_SIZE * 4,
+        "Scrolling read {} bytes of a {} byte file",
+        total,
+        file_size
+    );
+    assert!(!harness.editor().active_state().buffer.is_modified());
+}
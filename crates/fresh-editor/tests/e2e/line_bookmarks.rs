@@ -0,0 +1,172 @@
+//! Tests for line bookmarks
+//!
+//! Tests that:
+//! - Toggling a line bookmark shows a gutter marker and toggling again removes it
+//! - Next/Previous Line Bookmark cycle through bookmarks across open files
+//! - Line bookmarks follow their lines across edits and persist with the workspace
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+
+/// Helper to run a command from the command palette
+fn run_command(harness: &mut EditorTestHarness, command_name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.type_text(command_name).unwrap();
+    harness.render().unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Write a file whose lines read "<prefix> line N"
+fn write_numbered_file(dir: &std::path::Path, name: &str, prefix: &str) -> std::path::PathBuf {
+    let content: String = (1..=10).map(|i| format!("{prefix} line {i}\n")).collect();
+    let path = dir.join(name);
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+/// Jump to a 1-based line with Go to Line
+fn goto_line(harness: &mut EditorTestHarness, line: usize) {
+    harness
+        .send_key(KeyCode::Char('g'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(&line.to_string()).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+}
+
+/// Byte offset of `text` in the active buffer
+fn offset_of(harness: &EditorTestHarness, text: &str) -> usize {
+    harness.get_buffer_content().unwrap().find(text).unwrap()
+}
+
+/// Toggling adds a gutter marker on the current line and removes it again
+#[test]
+fn test_toggle_line_bookmark() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let file = write_numbered_file(temp_dir.path(), "a.txt", "a");
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file).unwrap();
+
+    goto_line(&mut harness, 3);
+    run_command(&mut harness, "Toggle Line Bookmark");
+    harness.assert_screen_contains("◆");
+    assert!(harness
+        .editor()
+        .get_status_message()
+        .is_some_and(|status| status.contains("Bookmarked line 3")));
+
+    run_command(&mut harness, "Toggle Line Bookmark");
+    harness.assert_screen_not_contains("◆");
+    assert!(harness
+        .editor()
+        .get_status_message()
+        .is_some_and(|status| status.contains("Removed bookmark from line 3")));
+
+    run_command(&mut harness, "Next Line Bookmark");
+    assert!(harness
+        .editor()
+        .get_status_message()
+        .is_some_and(|status| status.contains("No line bookmarks")));
+}
+
+/// Next/Previous visit bookmarks in tab order, then line order, and wrap
+#[test]
+fn test_line_bookmarks_across_files() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let a = write_numbered_file(temp_dir.path(), "a.txt", "a");
+    let b = write_numbered_file(temp_dir.path(), "b.txt", "b");
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.open_file(&a).unwrap();
+    goto_line(&mut harness, 3);
+    run_command(&mut harness, "Toggle Line Bookmark");
+    goto_line(&mut harness, 7);
+    run_command(&mut harness, "Toggle Line Bookmark");
+    harness.open_file(&b).unwrap();
+    goto_line(&mut harness, 5);
+    run_command(&mut harness, "Toggle Line Bookmark");
+    goto_line(&mut harness, 1);
+
+    run_command(&mut harness, "Next Line Bookmark");
+    assert_eq!(harness.cursor_position(), offset_of(&harness, "b line 5"));
+
+    run_command(&mut harness, "Next Line Bookmark");
+    assert_eq!(harness.cursor_position(), offset_of(&harness, "a line 3"));
+
+    run_command(&mut harness, "Next Line Bookmark");
+    assert_eq!(harness.cursor_position(), offset_of(&harness, "a line 7"));
+
+    run_command(&mut harness, "Previous Line Bookmark");
+    assert_eq!(harness.cursor_position(), offset_of(&harness, "a line 3"));
+
+    run_command(&mut harness, "Previous Line Bookmark");
+    assert_eq!(harness.cursor_position(), offset_of(&harness, "b line 5"));
+}
+
+/// Bookmarks stay on their lines when text is inserted above them, and are
+/// restored with the workspace
+#[test]
+fn test_line_bookmarks_persist_with_workspace() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+    let file = write_numbered_file(&project_dir, "a.txt", "a");
+
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+        harness.open_file(&file).unwrap();
+
+        goto_line(&mut harness, 4);
+        run_command(&mut harness, "Toggle Line Bookmark");
+        goto_line(&mut harness, 8);
+        run_command(&mut harness, "Toggle Line Bookmark");
+
+        harness
+            .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+            .unwrap();
+        harness.type_text("new 1\nnew 2\n").unwrap();
+        harness
+            .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+            .unwrap();
+
+        run_command(&mut harness, "Next Line Bookmark");
+        assert_eq!(harness.cursor_position(), offset_of(&harness, "a line 4"));
+
+        harness.editor_mut().save_workspace().unwrap();
+    }
+
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+        assert!(harness.editor_mut().try_restore_workspace().unwrap());
+        harness.render().unwrap();
+
+        harness
+            .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+            .unwrap();
+        run_command(&mut harness, "Next Line Bookmark");
+        assert_eq!(harness.cursor_position(), offset_of(&harness, "a line 4"));
+
+        run_command(&mut harness, "Next Line Bookmark");
+        assert_eq!(harness.cursor_position(), offset_of(&harness, "a line 8"));
+    }
+}
@@ -2156,3 +2156,64 @@ fn test_add_cursor_below_with_line_wrap_enabled() {
         "Should have 2 cursors after add cursor below"
     );
 }
+
+/// Test that a long source line spans several rows marked with a wrap
+/// indicator, and that Down moves one visual row within it
+#[test]
+fn test_wrapped_continuation_indicator_and_down_moves_one_row() {
+    const GUTTER_WIDTH: u16 = 8;
+    let mut harness = EditorTestHarness::new(60, 24).unwrap();
+
+    let long_text = "0123456789".repeat(12);
+    harness.type_text(&long_text).unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+
+    let first_row = harness.content_area_rows().0 as u16;
+    let gutter = |row: u16| {
+        let text = harness.get_screen_row(row as usize);
+        text[..text.find('│').unwrap()].to_string()
+    };
+    assert_eq!(gutter(first_row).trim(), "1");
+    // 120 characters need three rows of ~51 columns
+    assert_eq!(gutter(first_row + 1).trim(), "↪");
+    assert_eq!(gutter(first_row + 2).trim(), "↪");
+
+    // Down lands at the start of the next visual row, still on line 1
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    let (x, y) = harness.screen_cursor_position();
+    assert_eq!((x, y), (GUTTER_WIDTH, first_row + 1));
+    let pos = harness.cursor_position();
+    assert!(pos > 0 && pos < long_text.len());
+    assert_eq!(
+        harness.get_cell(x, y).as_deref(),
+        Some(&long_text[pos..pos + 1])
+    );
+
+    // Up returns to the start of the line
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.cursor_position(), 0);
+}
+
+/// Test that wide characters wrap whole rather than being split at the edge
+#[test]
+fn test_wrapped_line_with_wide_chars_all_visible() {
+    let mut harness = EditorTestHarness::new(60, 24).unwrap();
+
+    // 40 double-width characters are 80 columns wide
+    let wide_text = "日".repeat(40);
+    harness.type_text(&wide_text).unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+
+    let first_row = harness.content_area_rows().0 as u16;
+    let row_count = |row: u16| harness.get_screen_row(row as usize).matches('日').count();
+    assert!(row_count(first_row) > 0);
+    assert!(row_count(first_row + 1) > 0);
+    assert_eq!(row_count(first_row) + row_count(first_row + 1), 40);
+}
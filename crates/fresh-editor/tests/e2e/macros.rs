@@ -432,3 +432,82 @@ fn test_macro_playback_is_undoable() {
         screen_after_undo
     );
 }
+
+/// Test that "Repeat Last Macro" replays a macro the given number of times,
+/// with each replay undone as one step
+#[test]
+fn test_repeat_last_macro() {
+    use crate::common::harness::HarnessOptions;
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("list.txt");
+    std::fs::write(&file_path, "one\ntwo\nthree\nfour\nfive").unwrap();
+    let mut harness = EditorTestHarness::create(100, 24, HarnessOptions::new()).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    // Start recording macro on register 0
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("Record Macro").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.type_text("0").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    // Go to line start, type a prefix, move down
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness.type_text("- ").unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+
+    // Stop recording with F5
+    harness.send_key(KeyCode::F(5), KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "- one\ntwo\nthree\nfour\nfive"
+    );
+
+    // Replay it three times
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("Repeat Last Macro").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Repeat last macro how many times:");
+    harness.type_text("3").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "- one\n- two\n- three\n- four\nfive"
+    );
+    assert!(harness
+        .editor()
+        .get_status_message()
+        .is_some_and(|status| status.contains("3 times")));
+
+    // Undo removes only the last replay
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "- one\n- two\n- three\nfour\nfive"
+    );
+}
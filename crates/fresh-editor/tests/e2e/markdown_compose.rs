@@ -2016,35 +2016,27 @@ fn test_compose_mode_link_auto_expose() {
     );
 }
 
-/// Test that table rows with cells wider than the allocated column width
-/// wrap onto extra visual lines rather than being truncated.
+/// Test rendering and auto-expose of footnotes in compose mode.
 ///
-/// Verifies:
-/// 1. Each visual line of a wrapped table row has exactly the expected
-///    number of column separator characters (│) — no doubled `││`.
-/// 2. Every visual line of a wrapped row starts with a leading `│`.
-/// 3. The separator row (├─┼─┤) remains a single visual line.
+/// With the cursor elsewhere, the `[^1]` reference renders as a superscript
+/// `¹` and the `[^1]: ` label of the definition as `¹ `, with the definition
+/// styled apart from body text. With the cursor inside the reference, the raw
+/// `[^1]` is exposed.
 #[test]
-fn test_compose_mode_table_cell_wrapping() {
+fn test_compose_mode_footnote_auto_expose() {
     use crate::common::harness::{copy_plugin, copy_plugin_lib};
     use crate::common::tracing::init_tracing_from_env;
     use crossterm::event::{KeyCode, KeyModifiers};
-    use std::time::Duration;
+    use ratatui::style::Modifier;
 
     init_tracing_from_env();
 
-    // Use a narrow viewport (60 cols) to force wrapping on a table
-    // with a wide second column.
     let md_content = "\
-# Table Wrap Test
+# Test
 
-| Category | Features |
-|---|---|
-| Editing | undo/redo, multi-cursor, block selection, smart indent |
-| Language Server (LSP) | go to definition, references, hover, code actions, rename, diagnostics, autocompletion |
-| Productivity | command palette, menu bar, keyboard macros, git log, diagnostics panel |
+A claim[^1] here.
 
-End.
+[^1]: The source.
 ";
 
     let temp_dir = tempfile::TempDir::new().unwrap();
@@ -2056,17 +2048,16 @@ End.
     copy_plugin(&plugins_dir, "markdown_compose");
     copy_plugin_lib(&plugins_dir);
 
-    let md_path = project_root.join("table_wrap_test.md");
-    std::fs::write(&md_path, &md_content).unwrap();
+    let md_path = project_root.join("footnote_test.md");
+    std::fs::write(&md_path, md_content).unwrap();
 
-    // Use a narrow width (60 cols) to force column compression and wrapping.
     let mut harness =
-        EditorTestHarness::with_config_and_working_dir(60, 30, Default::default(), project_root)
+        EditorTestHarness::with_config_and_working_dir(80, 40, Default::default(), project_root)
             .unwrap();
 
     harness.open_file(&md_path).unwrap();
     harness.render().unwrap();
-    harness.assert_screen_contains("table_wrap_test.md");
+    harness.assert_screen_contains("footnote_test.md");
 
     // Enable compose mode
     harness
@@ -2080,122 +2071,94 @@ End.
         .unwrap();
     harness.wait_for_prompt_closed().unwrap();
 
-    // Wait for compose mode to fully activate with table conceals.
-    // First wait for box-drawing to appear, then wait for screen stability
-    // so all data rows have their conceals applied too.
+    // row 0=menu, 1=tabs, 2="# Test", 3=blank, 4=reference line, 5=blank, 6=definition
+    let ref_row: u16 = 4;
+    let def_row: u16 = 6;
+
+    // ── 1. Cursor on heading — reference and definition label concealed ──
     harness
-        .wait_until_stable(|h| {
-            let s = h.screen_to_string();
-            s.contains("│") && s.contains("─")
-        })
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
         .unwrap();
-    let mut prev = String::new();
     harness
-        .wait_until_stable(|h| {
-            let s = h.screen_to_string();
-            let stable = s == prev;
-            prev = s;
-            stable
-        })
+        .wait_until_stable(|h| !h.screen_to_string().contains("[^1]"))
         .unwrap();
-    let screen = harness.screen_to_string();
-
-    // Dump rendered output for debugging
-    eprintln!("=== Rendered table wrapping output ===");
-    for (i, line) in screen.lines().enumerate() {
-        eprintln!("{:3}: {}", i, line);
-    }
-    eprintln!("=== End rendered output ===");
-
-    // Collect all visual lines that contain box-drawing column separators.
-    // This includes data rows, wrapped continuation lines, and separator rows.
-    let table_lines: Vec<(usize, &str)> = screen
-        .lines()
-        .enumerate()
-        .filter(|(_, l)| {
-            let t = l.trim();
-            t.contains('│') || t.contains('┼') || t.contains('├')
-        })
-        .collect();
 
+    let off_row = harness.screen_row_text(ref_row);
     assert!(
-        table_lines.len() >= 5,
-        "Expected at least 5 visual table lines (header + separator + data rows with wrapping). \
-         Found {}.\nScreen:\n{}",
-        table_lines.len(),
-        screen,
+        off_row.contains("A claim\u{00B9} here."),
+        "Reference should render as a superscript marker. Got: '{}'",
+        off_row,
+    );
+    let def_text = harness.screen_row_text(def_row);
+    assert!(
+        def_text.contains("\u{00B9} The source."),
+        "Definition label should be concealed. Got: '{}'",
+        def_text,
     );
 
-    // For a 2-column table, each visual line should have exactly 3 column
-    // separator characters: leading │, middle │, trailing │.
-    // (Separator rows use ├/┼/┤ instead, also exactly 3.)
-    let pipe_chars = ['│', '┼', '├', '┤'];
-    let mut failures = Vec::new();
-
-    for (line_num, line_text) in &table_lines {
-        let pipe_count: usize = line_text.chars().filter(|c| pipe_chars.contains(c)).count();
-
-        if pipe_count != 3 {
-            failures.push(format!(
-                "  line {}: {} separators (expected 3): {:?}",
-                line_num,
-                pipe_count,
-                line_text.trim()
-            ));
-        }
+    // The definition is styled apart from body text
+    let (col, row) = harness.find_text_on_screen("The source.").unwrap();
+    let def_style = harness.get_cell_style(col, row).unwrap();
+    assert!(
+        def_style.add_modifier.contains(Modifier::ITALIC),
+        "Footnote definition should be italic. Got: {:?}",
+        def_style,
+    );
+    let (col, row) = harness.find_text_on_screen("A claim").unwrap();
+    let body_style = harness.get_cell_style(col, row).unwrap();
+    assert!(!body_style.add_modifier.contains(Modifier::ITALIC));
 
-        // Every table visual line must start with a box-drawing character
-        // (after trimming leading whitespace for centered layout).
-        let trimmed = line_text.trim_start();
-        let first_char = trimmed.chars().next().unwrap_or(' ');
-        if !pipe_chars.contains(&first_char) {
-            failures.push(format!(
-                "  line {}: missing leading separator (starts with {:?}): {:?}",
-                line_num,
-                first_char,
-                line_text.trim()
-            ));
-        }
-    }
+    // ── 2. Cursor inside the reference — raw syntax exposed ──────────────
+    harness
+        .send_key_repeat(KeyCode::Down, KeyModifiers::NONE, 2)
+        .unwrap();
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key_repeat(KeyCode::Right, KeyModifiers::NONE, "A claim[".len())
+        .unwrap();
+    harness
+        .wait_until_stable(|h| h.screen_to_string().contains("[^1]"))
+        .unwrap();
 
+    let on_row = harness.screen_row_text(ref_row);
     assert!(
-        failures.is_empty(),
-        "Table wrapping produced incorrect column separators:\n{}\n\nFull table lines:\n{}",
-        failures.join("\n"),
-        table_lines
-            .iter()
-            .map(|(n, l)| format!("  {:3}: {}", n, l))
-            .collect::<Vec<_>>()
-            .join("\n"),
+        on_row.contains("A claim[^1] here."),
+        "With the cursor inside the reference, it should be exposed. Got: '{}'",
+        on_row,
+    );
+    // Exposing is span-level: the definition stays concealed
+    let def_text = harness.screen_row_text(def_row);
+    assert!(
+        !def_text.contains("[^1]:"),
+        "Definition should stay concealed. Got: '{}'",
+        def_text,
     );
 }
 
-/// Regression test: pressing Down arrow past the end of a table should
-/// advance the cursor to the next line below the table, NOT jump it
-/// back to the beginning of the document.
+/// Test nested list rendering in compose mode.
+///
+/// With the cursor off the list, each nesting level is indented by two more
+/// columns than its parent and drawn with its own bullet glyph (•, ◦, ▪),
+/// ordered items keep their numbers, and a wrapped item's continuation line
+/// hangs under the item text rather than the bullet.
 #[test]
-fn test_compose_mode_table_cursor_down_past_end() {
+fn test_compose_mode_nested_list_indentation() {
     use crate::common::harness::{copy_plugin, copy_plugin_lib};
     use crate::common::tracing::init_tracing_from_env;
     use crossterm::event::{KeyCode, KeyModifiers};
 
     init_tracing_from_env();
 
-    // Use a table with long cells that force wrapping in a narrow viewport
+    // Four-space nesting renders as two columns per level
     let md_content = "\
-# Cursor Test
-
-Some intro text.
-
-| Category | Features |
-|---|---|
-| Editing | undo/redo, multi-cursor, block selection, smart indent |
-| Language Server (LSP) | go to definition, references, hover, code actions, rename, diagnostics, autocompletion |
-| Productivity | command palette, menu bar, keyboard macros, git log, diagnostics panel |
-
-After the table.
-
-End of document.
+# Lists
+
+- Top level
+    - Second level
+        - Third level item with enough words in it to wrap past the end of the row
+- Back to top
+    1. First
+    2. Second
 ";
 
     let temp_dir = tempfile::TempDir::new().unwrap();
@@ -2207,10 +2170,9 @@ End of document.
     copy_plugin(&plugins_dir, "markdown_compose");
     copy_plugin_lib(&plugins_dir);
 
-    let md_path = project_root.join("cursor_test.md");
-    std::fs::write(&md_path, &md_content).unwrap();
+    let md_path = project_root.join("nested_list_test.md");
+    std::fs::write(&md_path, md_content).unwrap();
 
-    // Narrow viewport to force table cell wrapping
     let mut harness =
         EditorTestHarness::with_config_and_working_dir(60, 30, Default::default(), project_root)
             .unwrap();
@@ -2230,104 +2192,71 @@ End of document.
         .unwrap();
     harness.wait_for_prompt_closed().unwrap();
 
-    // Wait for table conceals with wrapping.
+    // Keep the cursor on the heading so every list line is concealed
     harness
-        .wait_until_stable(|h| {
-            let s = h.screen_to_string();
-            s.contains("│") && s.contains("─")
-        })
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_until_stable(|h| h.screen_to_string().contains('\u{25AA}'))
         .unwrap();
 
-    let content = harness.get_buffer_content().unwrap();
-    let table_start_byte = content.find("| Category").unwrap();
-    let after_table_byte = content.find("After the table").unwrap();
-
-    // Cursor starts at byte 0 (top of file after opening).
-    // Press Down repeatedly through heading, intro, table (with wrapped rows), and past end.
-    // The table has wrapped rows creating extra visual lines.
-    // We press Down enough times to get well past the table.
-    for i in 0..18 {
-        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
-        harness.render().unwrap();
-
-        let pos = harness.cursor_position();
-        let screen_pos = harness.screen_cursor_position();
-        eprintln!(
-            "Down press {}: cursor_byte={}, screen=({},{})",
-            i + 1,
-            pos,
-            screen_pos.0,
-            screen_pos.1
-        );
-    }
-
-    let final_cursor = harness.cursor_position();
-    let final_screen = harness.screen_cursor_position();
-
-    eprintln!(
-        "Final cursor: byte={}, screen=({},{})",
-        final_cursor, final_screen.0, final_screen.1
-    );
-
-    // The cursor should NOT have jumped back to the beginning (byte 0)
-    assert!(
-        final_cursor > table_start_byte,
-        "Cursor jumped back to byte {} which is before the table start at {}! \
-         Pressing Down past a table should not jump to the beginning of the document.",
-        final_cursor,
-        table_start_byte,
-    );
+    // Column of the first occurrence of `needle` on its screen row
+    let column_of = |h: &EditorTestHarness, needle: &str| -> (u16, usize) {
+        let (_, row) = h.find_text_on_screen(needle).unwrap();
+        let text = h.screen_row_text(row);
+        let byte = text.find(needle).unwrap();
+        (row, text[..byte].chars().count())
+    };
 
-    // The cursor should be at or past the "After the table" line
+    let (top_row, top_col) = column_of(&harness, "\u{2022} Top level");
+    let (_, second_col) = column_of(&harness, "\u{25E6} Second level");
+    let (third_row, third_col) = column_of(&harness, "\u{25AA} Third level");
+    let (_, back_col) = column_of(&harness, "\u{2022} Back to top");
+
+    assert_eq!(second_col, top_col + 2, "second level indents two columns");
+    assert_eq!(third_col, top_col + 4, "third level indents two more");
+    assert_eq!(back_col, top_col, "returning to the top level");
+
+    // Ordered items keep their numbers, indented as one level deep
+    let (_, first_col) = column_of(&harness, "1. First");
+    let (_, second_num_col) = column_of(&harness, "2. Second");
+    assert_eq!(first_col, top_col + 2);
+    assert_eq!(second_num_col, top_col + 2);
+
+    // The long third-level item wraps, and its continuation hangs under the
+    // item text (glyph + space), not under the bullet
+    let continuation = harness.screen_row_text(third_row + 1);
+    let continuation_col = continuation.chars().take_while(|c| *c == ' ').count();
     assert!(
-        final_cursor >= after_table_byte,
-        "After pressing Down through the table, cursor (byte {}) should be at or past \
-         'After the table' (byte {}). Instead it's still inside the table.",
-        final_cursor,
-        after_table_byte,
+        !continuation.trim().is_empty() && continuation_col == third_col + 2,
+        "continuation should start at column {}, got row '{}'",
+        third_col + 2,
+        continuation,
     );
+    assert!(third_row > top_row);
 }
 
-/// Regression test: pressing Down through a document with tables (in compose mode)
-/// and then Up all the way back must produce monotonically increasing then
-/// monotonically decreasing cursor byte positions.  A jump to byte 0 mid-sequence
-/// is the specific bug being guarded against.
+/// Test image rendering in compose mode.
+///
+/// With the cursor elsewhere, `![alt](url)` renders as an image placeholder
+/// followed by the alt text, styled like a link; an image without alt text
+/// falls back to the file name. Putting the cursor inside the image exposes
+/// the raw syntax.
 #[test]
-fn test_compose_mode_cursor_monotonic_through_tables() {
+fn test_compose_mode_image_placeholder() {
     use crate::common::harness::{copy_plugin, copy_plugin_lib};
+    use crate::common::tracing::init_tracing_from_env;
     use crossterm::event::{KeyCode, KeyModifiers};
+    use ratatui::style::Modifier;
 
-    // Synthetic document with the features that trigger the bug: tables with
-    // long cells, bold/italic, links, and code blocks — but small enough to
-    // traverse quickly.
-    let md_content = "\
-# Monotonic Cursor Test
-
-Some **bold text** and *italic text* here.
-
-| Feature | Description | Status |
-|---------|-------------|--------|
-| **Tables** | Box-drawing conceals | Done |
-| *Links* | [Click here](https://example.com) | Done |
-| Code | `inline code` rendering | Done |
-
-Middle paragraph with [a link](https://example.com) and **emphasis**.
-
-| Name | Value | Notes |
-|------|-------|-------|
-| Alpha | 100 | First row |
-| Beta | 200 | Second row |
-| Gamma | 300 | Third row |
+    init_tracing_from_env();
 
-```rust
-fn example() {
-    println!(\"hello\");
-}
-```
+    let md_content = "\
+# Test
 
-Final paragraph with **more bold** and *more italic*.
+See ![Diagram](docs/diagram.png) here.
 
-End.
+Also ![](img/cat.png?raw=1) there.
 ";
 
     let temp_dir = tempfile::TempDir::new().unwrap();
@@ -2339,15 +2268,16 @@ End.
     copy_plugin(&plugins_dir, "markdown_compose");
     copy_plugin_lib(&plugins_dir);
 
-    let md_path = project_root.join("monotonic_test.md");
-    std::fs::write(&md_path, &md_content).unwrap();
+    let md_path = project_root.join("image_test.md");
+    std::fs::write(&md_path, md_content).unwrap();
 
     let mut harness =
-        EditorTestHarness::with_config_and_working_dir(60, 30, Default::default(), project_root)
+        EditorTestHarness::with_config_and_working_dir(80, 40, Default::default(), project_root)
             .unwrap();
 
     harness.open_file(&md_path).unwrap();
     harness.render().unwrap();
+    harness.assert_screen_contains("image_test.md");
 
     // Enable compose mode
     harness
@@ -2361,77 +2291,98 @@ End.
         .unwrap();
     harness.wait_for_prompt_closed().unwrap();
 
-    // Wait for compose mode conceals to activate.
+    // row 0=menu, 1=tabs, 2="# Test", 3=blank, 4=first image, 5=blank, 6=second image
+    let image_row: u16 = 4;
+    let bare_row: u16 = 6;
+
+    // The placeholder depends on whether the terminal draws emoji two cells
+    // wide, and a wide glyph's trailing cell may read as a space
+    let has_placeholder = |row: &str| row.contains('\u{1F5BC}') || row.contains("[img] ");
+
+    // ── 1. Cursor on heading — images concealed ──────────────────────────
     harness
-        .wait_until_stable(|h| {
-            let s = h.screen_to_string();
-            s.contains("│") || s.contains("─") || !s.contains("**")
-        })
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_until_stable(|h| !h.screen_to_string().contains("!["))
         .unwrap();
 
-    // --- Move Down through entire document ---
-    let total_downs = 60;
-    let mut prev_pos = harness.cursor_position();
-    let mut down_positions = vec![prev_pos];
+    let off_row = harness.screen_row_text(image_row);
+    assert!(
+        has_placeholder(&off_row) && off_row.contains("Diagram here."),
+        "Image should render as placeholder and alt text. Got: '{}'",
+        off_row,
+    );
+    assert!(
+        !off_row.contains("docs/diagram.png"),
+        "Image URL should be concealed. Got: '{}'",
+        off_row,
+    );
 
-    for i in 0..total_downs {
-        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
-        harness.render().unwrap();
-
-        let pos = harness.cursor_position();
-        assert!(
-            pos >= prev_pos,
-            "Down press {}: cursor went backwards! {} -> {} (positions so far: {:?})",
-            i + 1,
-            prev_pos,
-            pos,
-            down_positions,
-        );
-        down_positions.push(pos);
-        prev_pos = pos;
-    }
+    // Without alt text the file name stands in
+    let bare = harness.screen_row_text(bare_row);
+    assert!(
+        has_placeholder(&bare) && bare.contains("cat.png there.") && !bare.contains("img/"),
+        "Image without alt text should show its file name. Got: '{}'",
+        bare,
+    );
 
-    // --- Move Up through entire document ---
-    let total_ups = 60;
-    prev_pos = harness.cursor_position();
-    let mut up_positions = vec![prev_pos];
+    // The alt text carries link styling
+    let (col, row) = harness.find_text_on_screen("Diagram").unwrap();
+    let alt_style = harness.get_cell_style(col, row).unwrap();
+    assert!(
+        alt_style.add_modifier.contains(Modifier::UNDERLINED),
+        "Image alt text should be underlined like a link. Got: {:?}",
+        alt_style,
+    );
 
-    for i in 0..total_ups {
-        harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
-        harness.render().unwrap();
+    // ── 2. Cursor inside the image — raw syntax exposed ──────────────────
+    harness
+        .send_key_repeat(KeyCode::Down, KeyModifiers::NONE, 2)
+        .unwrap();
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key_repeat(KeyCode::Right, KeyModifiers::NONE, "See ![".len())
+        .unwrap();
+    harness
+        .wait_until_stable(|h| h.screen_to_string().contains("![Diagram]"))
+        .unwrap();
 
-        let pos = harness.cursor_position();
-        assert!(
-            pos <= prev_pos,
-            "Up press {}: cursor went forwards! {} -> {} (positions so far: {:?})",
-            i + 1,
-            prev_pos,
-            pos,
-            up_positions,
-        );
-        up_positions.push(pos);
-        prev_pos = pos;
-    }
+    let on_row = harness.screen_row_text(image_row);
+    assert!(
+        on_row.contains("See ![Diagram](docs/diagram.png) here."),
+        "With the cursor inside the image, it should be exposed. Got: '{}'",
+        on_row,
+    );
+    // Exposing is span-level: the other image stays concealed
+    let bare = harness.screen_row_text(bare_row);
+    assert!(
+        !bare.contains("!["),
+        "Other image should stay concealed. Got: '{}'",
+        bare,
+    );
 }
 
-/// Regression test: cursor on a numbered list item should maintain its visual
-/// column when moving up.  When on the first character of "2.", pressing Up
-/// should land near the start of the line above (visual column 0), not jump
-/// to the end of that line.
+/// Test fenced code blocks in compose mode.
+///
+/// With the cursor elsewhere, the ``` fences are concealed and the block's
+/// contents are highlighted for the fence language rather than drawn in the
+/// markdown grammar's single raw-code color. Moving the cursor onto a fence
+/// line exposes the raw markers.
 #[test]
-fn test_compose_mode_cursor_column_sticky_on_list() {
+fn test_compose_mode_code_block_highlighting() {
     use crate::common::harness::{copy_plugin, copy_plugin_lib};
+    use crate::common::tracing::init_tracing_from_env;
     use crossterm::event::{KeyCode, KeyModifiers};
-    use std::time::Duration;
 
-    let md_content = "\
-# List Test
-
-1. **Reproduce Before Fixing**: Always include a test case that reproduces the bug (fails) without the fix, and passes with the fix. This ensures the issue is verified and prevents future regressions.
+    init_tracing_from_env();
 
-2. **E2E Tests for New Flows**: Any new user flow or feature must include an end-to-end (e2e) test. E2E tests send keyboard/mouse events and examines the final rendered output, do not examine internal state.
+    let md_content = "\
+# Code
 
-3. **No timeouts or time-sensitive tests**: Use semantic waiting instead of fixed timers to ensure test stability.
+```rust
+fn main() {}
+```
 ";
 
     let temp_dir = tempfile::TempDir::new().unwrap();
@@ -2443,16 +2394,16 @@ fn test_compose_mode_cursor_column_sticky_on_list() {
     copy_plugin(&plugins_dir, "markdown_compose");
     copy_plugin_lib(&plugins_dir);
 
-    let md_path = project_root.join("list_test.md");
-    std::fs::write(&md_path, &md_content).unwrap();
+    let md_path = project_root.join("code_block_test.md");
+    std::fs::write(&md_path, md_content).unwrap();
 
-    // Narrow viewport to force wrapping of the long list items
     let mut harness =
-        EditorTestHarness::with_config_and_working_dir(40, 20, Default::default(), project_root)
+        EditorTestHarness::with_config_and_working_dir(80, 40, Default::default(), project_root)
             .unwrap();
 
     harness.open_file(&md_path).unwrap();
     harness.render().unwrap();
+    harness.assert_screen_contains("code_block_test.md");
 
     // Enable compose mode
     harness
@@ -2466,90 +2417,90 @@ fn test_compose_mode_cursor_column_sticky_on_list() {
         .unwrap();
     harness.wait_for_prompt_closed().unwrap();
 
-    // Wait for compose mode to settle — emphasis markers should be concealed
+    // row 0=menu, 1=tabs, 2="# Code", 3=blank, 4=opening fence, 5=code, 6=closing fence
+    let fence_row: u16 = 4;
+
+    // Foreground of the first cell showing `needle`
+    let fg_of = |h: &EditorTestHarness, needle: &str| {
+        h.find_text_on_screen(needle)
+            .and_then(|(col, row)| h.get_cell_style(col, row))
+            .and_then(|style| style.fg)
+    };
+
+    // ── 1. Cursor on heading — fences concealed, code highlighted ────────
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
     harness
         .wait_until_stable(|h| {
-            let s = h.screen_to_string();
-            s.contains("Reproduce") && !s.contains("**")
+            !h.screen_to_string().contains("```") && fg_of(h, "fn ") != fg_of(h, "main")
         })
         .unwrap();
 
-    // Navigate to the start of "2." — use Home to go to line start first,
-    // then Down to reach it
-    let content = harness.get_buffer_content().unwrap();
-    let item2_byte = content.find("2. **E2E").unwrap();
-
-    // Move cursor to the "2." line by pressing Down until we're there
-    for _ in 0..20 {
-        let pos = harness.cursor_position();
-        if pos >= item2_byte {
-            break;
-        }
-        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
-        harness.render().unwrap();
-    }
-
-    // Press Home to go to column 0 of the "2." line
-    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
-    harness.render().unwrap();
-
-    let pos_on_item2 = harness.cursor_position();
-    let screen_before = harness.screen_cursor_position();
-    let visual_col_before = screen_before.0;
-
-    // Now press Up — cursor should land near the same visual column on the
-    // line above, NOT at the end of the line above
-    harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
-    harness.render().unwrap();
+    let screen = harness.screen_to_string();
+    assert!(
+        screen.contains("fn main() {}"),
+        "Code should be visible. Screen:\n{}",
+        screen,
+    );
+    assert!(
+        !screen.contains("```"),
+        "Fences should be concealed. Screen:\n{}",
+        screen,
+    );
 
-    let pos_after_up = harness.cursor_position();
-    let screen_after = harness.screen_cursor_position();
-    let visual_col_after = screen_after.0;
+    // The keyword is styled apart from the function name
+    let keyword_fg = fg_of(&harness, "fn ");
+    assert!(keyword_fg.is_some());
+    assert_ne!(
+        keyword_fg,
+        fg_of(&harness, "main"),
+        "Keyword should be highlighted for the fence language"
+    );
 
-    // The visual column should stay near column 0 (within a small tolerance
-    // for gutter width differences), definitely not jump to the end
-    let col_distance = if visual_col_after > visual_col_before {
-        visual_col_after - visual_col_before
-    } else {
-        visual_col_before - visual_col_after
-    };
+    // ── 2. Cursor on the opening fence — raw markers exposed ─────────────
+    harness
+        .send_key_repeat(KeyCode::Down, KeyModifiers::NONE, 2)
+        .unwrap();
+    harness
+        .wait_until_stable(|h| h.screen_to_string().contains("```rust"))
+        .unwrap();
 
+    let on_row = harness.screen_row_text(fence_row);
     assert!(
-        col_distance <= 3,
-        "Pressing Up from start of '2.' line (visual col {}) should land near \
-         the same column on the line above, but jumped to visual col {} \
-         (distance {}). cursor byte: {} -> {}",
-        visual_col_before,
-        visual_col_after,
-        col_distance,
-        pos_on_item2,
-        pos_after_up,
+        on_row.contains("```rust"),
+        "With the cursor on the fence, it should be exposed. Got: '{}'",
+        on_row,
     );
 }
 
-/// Regression test: visual column should remain sticky at column 0 when
-/// navigating up and down through wrapped numbered list items.
-/// Start at column 0, press Down through all items, then Up back to the top.
-/// The visual column should stay at the gutter-adjusted column 0 throughout.
+/// Test folding a section in compose mode.
+///
+/// "Toggle Fold Section" on an H2 hides everything down to the next H2
+/// behind a line-count marker on the heading row, Down moves straight to the
+/// next heading, and toggling again restores the section.
 #[test]
-fn test_compose_mode_cursor_column_zero_sticky_through_wrapped_list() {
+fn test_compose_mode_fold_section() {
     use crate::common::harness::{copy_plugin, copy_plugin_lib};
+    use crate::common::tracing::init_tracing_from_env;
     use crossterm::event::{KeyCode, KeyModifiers};
-    use std::time::Duration;
 
-    // Numbered list with items long enough to wrap at 50 columns
+    init_tracing_from_env();
+
     let md_content = "\
-# Wrapped List
+# Title
 
-1. First item with enough text to wrap across multiple visual lines in a narrow viewport.
+## Section A
 
-2. Second item also has enough text that it will definitely wrap when rendered in compose mode.
+Alpha body line.
 
-3. Third item continues the pattern of long text that wraps across multiple lines in the viewport.
+### Alpha detail
 
-4. Fourth item is similarly long to ensure consistent wrapping behavior throughout the list.
+More alpha.
 
-5. Fifth and final item rounds out the list with more wrapping text for thorough coverage.
+## Section B
+
+Beta body.
 ";
 
     let temp_dir = tempfile::TempDir::new().unwrap();
@@ -2561,150 +2512,117 @@ fn test_compose_mode_cursor_column_zero_sticky_through_wrapped_list() {
     copy_plugin(&plugins_dir, "markdown_compose");
     copy_plugin_lib(&plugins_dir);
 
-    let md_path = project_root.join("wrapped_list.md");
-    std::fs::write(&md_path, &md_content).unwrap();
+    let md_path = project_root.join("fold_test.md");
+    std::fs::write(&md_path, md_content).unwrap();
 
-    // Narrow viewport to force wrapping
     let mut harness =
-        EditorTestHarness::with_config_and_working_dir(50, 30, Default::default(), project_root)
+        EditorTestHarness::with_config_and_working_dir(80, 40, Default::default(), project_root)
             .unwrap();
 
     harness.open_file(&md_path).unwrap();
     harness.render().unwrap();
 
-    // Enable compose mode
+    let run_command = |h: &mut EditorTestHarness, name: &str| {
+        h.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        h.wait_for_prompt().unwrap();
+        h.type_text(name).unwrap();
+        h.wait_for_screen_contains(name).unwrap();
+        h.send_key(KeyCode::Enter, KeyModifiers::NONE).unwrap();
+        h.wait_for_prompt_closed().unwrap();
+    };
+
+    run_command(&mut harness, "Toggle Compose");
+
+    // Put the cursor on "## Section A" and fold it
     harness
-        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
         .unwrap();
-    harness.wait_for_prompt().unwrap();
-    harness.type_text("Toggle Compose").unwrap();
-    harness.wait_for_screen_contains("Toggle Compose").unwrap();
     harness
-        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .send_key_repeat(KeyCode::Down, KeyModifiers::NONE, 2)
         .unwrap();
-    harness.wait_for_prompt_closed().unwrap();
-
-    // Wait for compose mode to fully settle — list content visible
     harness
-        .wait_until_stable(|h| h.screen_to_string().contains("First item"))
+        .wait_for_screen_contains("Alpha body line.")
         .unwrap();
+    run_command(&mut harness, "Toggle Fold Section");
 
-    // Press Home to ensure we start at column 0
-    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
-    harness.render().unwrap();
-
-    let start_col = harness.screen_cursor_position().0;
-    let gutter_col = start_col; // this is column 0 adjusted for gutter
-
-    // --- Move Down through the entire list ---
-    // On wrapped continuation lines, the cursor may land a few columns
-    // from 0 due to indentation padding.  The important properties are:
-    //  1. The cursor stays near column 0 (not jumping to the end)
-    //  2. The round trip (down then up) returns to column 0
-    let total_presses = 25; // enough to traverse all 5 items + wrapping
-    let max_drift = 10; // allow for indentation on wrapped continuation lines
-
-    for i in 0..total_presses {
-        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
-        // Wait for screen to stabilize after the plugin chain
-        // (cursor_moved → refreshLines → lines_changed → soft-breaks).
-        let mut prev = String::new();
-        harness
-            .wait_until_stable(|h| {
-                let s = h.screen_to_string();
-                let stable = s == prev;
-                prev = s;
-                stable
-            })
-            .unwrap();
+    harness
+        .wait_until_stable(|h| !h.screen_to_string().contains("Alpha"))
+        .unwrap();
 
-        let screen = harness.screen_cursor_position();
-        let col = screen.0;
-        assert!(
-            col <= gutter_col + max_drift,
-            "Down press {}: visual column {} drifted too far from start column {} \
-             (screen row {})",
-            i + 1,
-            col,
-            gutter_col,
-            screen.1,
-        );
-    }
+    // The body, including the nested H3, is hidden; the next H2 remains
+    let screen = harness.screen_to_string();
+    assert!(
+        !screen.contains("Alpha body line.") && !screen.contains("More alpha."),
+        "Folded section body should be hidden. Screen:\n{}",
+        screen,
+    );
+    let (_, heading_row) = harness.find_text_on_screen("Section A").unwrap();
+    let heading = harness.screen_row_text(heading_row);
+    assert!(
+        heading.contains("\u{27E9} 7 lines hidden"),
+        "Heading row should show the fold marker. Got: '{}'",
+        heading,
+    );
+    let (_, next_row) = harness.find_text_on_screen("Section B").unwrap();
+    assert_eq!(
+        next_row,
+        heading_row + 1,
+        "Next heading should follow the folded one. Screen:\n{}",
+        screen,
+    );
+    harness.assert_screen_contains("Beta body.");
 
-    // --- Move Up back to the top ---
-    for i in 0..total_presses {
-        harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
-        let mut prev = String::new();
-        harness
-            .wait_until_stable(|h| {
-                let s = h.screen_to_string();
-                let stable = s == prev;
-                prev = s;
-                stable
-            })
-            .unwrap();
+    // Down skips the folded body and lands on the next heading
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    let (_, cursor_row) = harness.screen_cursor_position();
+    assert_eq!(cursor_row, next_row, "Down should move to the next heading");
 
-        let screen = harness.screen_cursor_position();
-        let col = screen.0;
-        assert!(
-            col <= gutter_col + max_drift,
-            "Up press {}: visual column {} drifted too far from start column {} \
-             (screen row {})",
-            i + 1,
-            col,
-            gutter_col,
-            screen.1,
-        );
-    }
+    // Back up onto the folded heading and unfold
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+    run_command(&mut harness, "Toggle Fold Section");
+    harness
+        .wait_until_stable(|h| h.screen_to_string().contains("Alpha body line."))
+        .unwrap();
 
-    // After the full round trip, cursor should be near column 0.
-    // Note: sticky_column=0 is treated as "unset" in the cursor model,
-    // so column 0 cannot be perfectly sticky.  Allow same tolerance.
-    let final_col = harness.screen_cursor_position().0;
+    let screen = harness.screen_to_string();
     assert!(
-        final_col <= gutter_col + max_drift,
-        "After full down+up round trip, cursor column {} drifted too far from start {}",
-        final_col,
-        gutter_col,
+        screen.contains("More alpha.") && !screen.contains("lines hidden"),
+        "Unfolding should restore the section. Screen:\n{}",
+        screen,
     );
 }
 
-/// Regression test: table rows with emphasis markup should not overflow when
-/// cursor enters them.
+/// Test that table rows with cells wider than the allocated column width
+/// wrap onto extra visual lines rather than being truncated.
 ///
-/// When the cursor moves into a table row containing `**bold**`, the auto-expose
-/// feature reveals the `**` markers. Without the fix, the cell width was computed
-/// from the concealed text (shorter), so the revealed markers overflow the
-/// allocated column width, causing the row to wrap onto a second visual line.
-///
-/// The fix sizes columns based on raw (unconcealed) text so revealed markers
-/// always fit, and skips emphasis conceals for the entire row when the cursor
-/// is on it.
+/// Verifies:
+/// 1. Each visual line of a wrapped table row has exactly the expected
+///    number of column separator characters (│) — no doubled `││`.
+/// 2. Every visual line of a wrapped row starts with a leading `│`.
+/// 3. The separator row (├─┼─┤) remains a single visual line.
 #[test]
-fn test_compose_mode_table_emphasis_auto_expose_no_overflow() {
+fn test_compose_mode_table_cell_wrapping() {
     use crate::common::harness::{copy_plugin, copy_plugin_lib};
     use crate::common::tracing::init_tracing_from_env;
     use crossterm::event::{KeyCode, KeyModifiers};
+    use std::time::Duration;
 
     init_tracing_from_env();
 
-    // Table sized so that:
-    //  - Concealed row fits in 60-col viewport (57 chars)
-    //  - Revealed row (with **) overflows (61 chars) → wraps without fix
-    //
-    // Row: | **Search & Replace** | Find and replace text in the docs! |
-    //  concealed cells: " Search & Replace " (18) + " Find and replace text in the docs! " (36)
-    //  → total concealed row = 18 + 36 + 3 pipes = 57 chars
-    //  → total revealed row  = 22 + 36 + 3 pipes = 61 chars
+    // Use a narrow viewport (60 cols) to force wrapping on a table
+    // with a wide second column.
     let md_content = "\
-# Test
+# Table Wrap Test
 
-| Feature | Description |
+| Category | Features |
 |---|---|
-| **Search & Replace** | Find and replace text in the docs! |
-| Normal item | Plain text cell |
+| Editing | undo/redo, multi-cursor, block selection, smart indent |
+| Language Server (LSP) | go to definition, references, hover, code actions, rename, diagnostics, autocompletion |
+| Productivity | command palette, menu bar, keyboard macros, git log, diagnostics panel |
 
-Done.
+End.
 ";
 
     let temp_dir = tempfile::TempDir::new().unwrap();
@@ -2716,16 +2634,17 @@ Done.
     copy_plugin(&plugins_dir, "markdown_compose");
     copy_plugin_lib(&plugins_dir);
 
-    let md_path = project_root.join("table_auto_expose.md");
+    let md_path = project_root.join("table_wrap_test.md");
     std::fs::write(&md_path, &md_content).unwrap();
 
-    // Viewport 60 cols: concealed row (57) fits, revealed row (61) overflows
+    // Use a narrow width (60 cols) to force column compression and wrapping.
     let mut harness =
-        EditorTestHarness::with_config_and_working_dir(60, 20, Default::default(), project_root)
+        EditorTestHarness::with_config_and_working_dir(60, 30, Default::default(), project_root)
             .unwrap();
 
     harness.open_file(&md_path).unwrap();
     harness.render().unwrap();
+    harness.assert_screen_contains("table_wrap_test.md");
 
     // Enable compose mode
     harness
@@ -2739,116 +2658,123 @@ Done.
         .unwrap();
     harness.wait_for_prompt_closed().unwrap();
 
-    // Wait for compose mode table to be fully rendered with box-drawing
-    // and emphasis concealed.
+    // Wait for compose mode to fully activate with table conceals.
+    // First wait for box-drawing to appear, then wait for screen stability
+    // so all data rows have their conceals applied too.
     harness
         .wait_until_stable(|h| {
             let s = h.screen_to_string();
-            s.lines().any(|l| l.contains("│") && l.contains("Search"))
-                && !s.lines().any(|l| l.contains("**"))
+            s.contains("│") && s.contains("─")
+        })
+        .unwrap();
+    let mut prev = String::new();
+    harness
+        .wait_until_stable(|h| {
+            let s = h.screen_to_string();
+            let stable = s == prev;
+            prev = s;
+            stable
         })
         .unwrap();
+    let screen = harness.screen_to_string();
 
-    // Count how many screen lines contain "Search" — should be exactly 1
-    // (the row is not wrapping)
-    let screen_before = harness.screen_to_string();
-    let search_lines_before: Vec<_> = screen_before
+    // Dump rendered output for debugging
+    eprintln!("=== Rendered table wrapping output ===");
+    for (i, line) in screen.lines().enumerate() {
+        eprintln!("{:3}: {}", i, line);
+    }
+    eprintln!("=== End rendered output ===");
+
+    // Collect all visual lines that contain box-drawing column separators.
+    // This includes data rows, wrapped continuation lines, and separator rows.
+    let table_lines: Vec<(usize, &str)> = screen
         .lines()
-        .filter(|l| l.contains("Search"))
+        .enumerate()
+        .filter(|(_, l)| {
+            let t = l.trim();
+            t.contains('│') || t.contains('┼') || t.contains('├')
+        })
         .collect();
-    assert_eq!(
-        search_lines_before.len(),
-        1,
-        "Before cursor: 'Search' row should be on exactly 1 screen line.\nScreen:\n{}",
-        screen_before,
+
+    assert!(
+        table_lines.len() >= 5,
+        "Expected at least 5 visual table lines (header + separator + data rows with wrapping). \
+         Found {}.\nScreen:\n{}",
+        table_lines.len(),
+        screen,
     );
 
-    // Navigate cursor into the emphasis span on the "Search & Replace" row.
-    // First go to the top, then down to the data row, then right into the span.
-    harness
-        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
-        .unwrap();
-    harness.render().unwrap();
+    // For a 2-column table, each visual line should have exactly 3 column
+    // separator characters: leading │, middle │, trailing │.
+    // (Separator rows use ├/┼/┤ instead, also exactly 3.)
+    let pipe_chars = ['│', '┼', '├', '┤'];
+    let mut failures = Vec::new();
 
-    // Move down: heading(1) → blank(2) → header(3) → separator(4) → data row(5)
-    for _ in 0..4 {
-        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
-        harness.render().unwrap();
-    }
-    // Move right into the bold span (past the leading pipe + space + **)
-    for _ in 0..4 {
-        harness
-            .send_key(KeyCode::Right, KeyModifiers::NONE)
-            .unwrap();
-        harness.render().unwrap();
-    }
+    for (line_num, line_text) in &table_lines {
+        let pipe_count: usize = line_text.chars().filter(|c| pipe_chars.contains(c)).count();
 
-    // Wait for emphasis markers to be auto-exposed on the cursor row.
-    harness
-        .wait_until_stable(|h| {
-            let s = h.screen_to_string();
-            s.lines().any(|l| l.contains("**Search"))
-        })
-        .unwrap();
+        if pipe_count != 3 {
+            failures.push(format!(
+                "  line {}: {} separators (expected 3): {:?}",
+                line_num,
+                pipe_count,
+                line_text.trim()
+            ));
+        }
 
-    let screen_after = harness.screen_to_string();
+        // Every table visual line must start with a box-drawing character
+        // (after trimming leading whitespace for centered layout).
+        let trimmed = line_text.trim_start();
+        let first_char = trimmed.chars().next().unwrap_or(' ');
+        if !pipe_chars.contains(&first_char) {
+            failures.push(format!(
+                "  line {}: missing leading separator (starts with {:?}): {:?}",
+                line_num,
+                first_char,
+                line_text.trim()
+            ));
+        }
+    }
 
-    // The cursor row shows raw text without padding/truncation, so it may
-    // overflow the viewport width (raw text is wider than allocated columns).
-    // This is expected — cursor positioning requires the raw bytes to be
-    // present (segment conceals break cursor mapping). Verify the raw text
-    // and pipe replacements are visible.
-    // TODO: Ideally the cursor row should fit within the viewport (with
-    // proper padding/truncation) while still allowing correct cursor
-    // positioning. This overflow is a workaround for the segment-conceal
-    // cursor mapping bug.
-    let search_line = screen_after
-        .lines()
-        .find(|l| l.contains("Search"))
-        .expect("Should find line with 'Search'");
     assert!(
-        search_line.contains("│"),
-        "Cursor row should still have pipe replacements (│).\n\
-         Got: '{}'\nScreen:\n{}",
-        search_line,
-        screen_after,
+        failures.is_empty(),
+        "Table wrapping produced incorrect column separators:\n{}\n\nFull table lines:\n{}",
+        failures.join("\n"),
+        table_lines
+            .iter()
+            .map(|(n, l)| format!("  {:3}: {}", n, l))
+            .collect::<Vec<_>>()
+            .join("\n"),
     );
 }
 
-/// Regression test: cursor rendering while navigating through table rows
-/// with emphasis markup.
-///
-/// Uses a 6-row data table where every row has emphasis. Moves the cursor
-/// down through every row, then back up, and at each position asserts that
-/// ONLY the cursor's row has its emphasis markers auto-exposed while all
-/// other rows keep them concealed.
+/// Regression test: pressing Down arrow past the end of a table should
+/// advance the cursor to the next line below the table, NOT jump it
+/// back to the beginning of the document.
 #[test]
-fn test_compose_mode_table_cursor_render_through_emphasis() {
+fn test_compose_mode_table_cursor_down_past_end() {
     use crate::common::harness::{copy_plugin, copy_plugin_lib};
     use crate::common::tracing::init_tracing_from_env;
     use crossterm::event::{KeyCode, KeyModifiers};
 
     init_tracing_from_env();
 
-    // 6 data rows, each with bold emphasis in the first column.
-    // Row labels are unique so we can identify them on screen.
+    // Use a table with long cells that force wrapping in a narrow viewport
     let md_content = "\
-# Table
+# Cursor Test
 
-| Feature | Status |
+Some intro text.
+
+| Category | Features |
 |---|---|
-| **Alpha** | *Done* |
-| **Bravo** | *Active* |
-| **Charlie** | *Pending* |
-| **Delta** | *Review* |
-| **Echo** | *Blocked* |
-| **Foxtrot** | *Shipped* |
+| Editing | undo/redo, multi-cursor, block selection, smart indent |
+| Language Server (LSP) | go to definition, references, hover, code actions, rename, diagnostics, autocompletion |
+| Productivity | command palette, menu bar, keyboard macros, git log, diagnostics panel |
 
-End.
-";
+After the table.
 
-    // The row labels without emphasis markers (concealed form)
-    let labels = ["Alpha", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot"];
+End of document.
+";
 
     let temp_dir = tempfile::TempDir::new().unwrap();
     let project_root = temp_dir.path().join("project");
@@ -2859,11 +2785,12 @@ End.
     copy_plugin(&plugins_dir, "markdown_compose");
     copy_plugin_lib(&plugins_dir);
 
-    let md_path = project_root.join("table_cursor_render.md");
+    let md_path = project_root.join("cursor_test.md");
     std::fs::write(&md_path, &md_content).unwrap();
 
+    // Narrow viewport to force table cell wrapping
     let mut harness =
-        EditorTestHarness::with_config_and_working_dir(80, 30, Default::default(), project_root)
+        EditorTestHarness::with_config_and_working_dir(60, 30, Default::default(), project_root)
             .unwrap();
 
     harness.open_file(&md_path).unwrap();
@@ -2881,185 +2808,104 @@ End.
         .unwrap();
     harness.wait_for_prompt_closed().unwrap();
 
-    // Wait for compose mode to fully activate: table box-drawing visible and
-    // all emphasis concealed.
+    // Wait for table conceals with wrapping.
     harness
         .wait_until_stable(|h| {
             let s = h.screen_to_string();
-            s.lines().any(|l| l.contains("│") && l.contains("Alpha"))
-                && !s.lines().any(|l| l.contains("**"))
+            s.contains("│") && s.contains("─")
         })
         .unwrap();
 
-    /// Assert that exactly the row for `exposed_label` (if Some) has visible
-    /// `**` markers, and all other rows have them concealed.
-    fn assert_only_row_exposed(
-        screen: &str,
-        labels: &[&str],
-        exposed_label: Option<&str>,
-        context: &str,
-    ) {
-        for &label in labels {
-            let should_expose = exposed_label == Some(label);
-            let marker = format!("**{}**", label);
-            let has_marker = screen.lines().any(|l| l.contains(&marker));
-
-            if should_expose {
-                assert!(
-                    has_marker,
-                    "{}: cursor row '{}' should show '{}' but markers are concealed.\nScreen:\n{}",
-                    context, label, marker, screen,
-                );
-            } else {
-                // The label text itself should be present (concealed form)
-                // but the ** markers should not surround it.
-                assert!(
-                    !has_marker,
-                    "{}: non-cursor row '{}' should have ** concealed but found '{}'.\nScreen:\n{}",
-                    context, label, marker, screen,
-                );
-            }
-        }
-    }
+    let content = harness.get_buffer_content().unwrap();
+    let table_start_byte = content.find("| Category").unwrap();
+    let after_table_byte = content.find("After the table").unwrap();
 
-    /// Wait until the screen shows `**{label}**` for the expected row AND
-    /// all other rows have their emphasis concealed. Waiting for the full
-    /// stable state prevents races where the plugin is still re-processing
-    /// other rows.
-    /// Advances logical time to allow debounce/plugin processing to complete.
-    fn wait_for_only_row_exposed(
-        harness: &mut EditorTestHarness,
-        labels: &[&str],
-        exposed_label: &str,
-    ) {
-        let wait_sleep = std::time::Duration::from_millis(50);
-        loop {
-            harness.process_async_and_render().unwrap();
-            let s = harness.screen_to_string();
-            // Check: exposed_label has markers, all others do not
-            let target_ok = s
-                .lines()
-                .any(|l| l.contains(&format!("**{}**", exposed_label)));
-            let others_ok = labels.iter().all(|&l| {
-                l == exposed_label || !s.lines().any(|line| line.contains(&format!("**{}**", l)))
-            });
-            if target_ok && others_ok {
-                return;
-            }
-            std::thread::sleep(wait_sleep);
-            harness.advance_time(wait_sleep);
-        }
-    }
-
-    /// Wait until no rows in the table have exposed emphasis markers.
-    /// Advances logical time to allow debounce/plugin processing to complete.
-    fn wait_for_all_concealed(harness: &mut EditorTestHarness, labels: &[&str]) {
-        let wait_sleep = std::time::Duration::from_millis(50);
-        loop {
-            harness.process_async_and_render().unwrap();
-            let s = harness.screen_to_string();
-            let any_exposed = labels
-                .iter()
-                .any(|l| s.lines().any(|line| line.contains(&format!("**{}**", l))));
-            if !any_exposed {
-                return;
-            }
-            std::thread::sleep(wait_sleep);
-            harness.advance_time(wait_sleep);
-        }
-    }
-
-    // Navigate: Ctrl+Home → Down×4 to reach first data row (Alpha)
-    harness
-        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
-        .unwrap();
-    harness.render().unwrap();
-    for _ in 0..4 {
+    // Cursor starts at byte 0 (top of file after opening).
+    // Press Down repeatedly through heading, intro, table (with wrapped rows), and past end.
+    // The table has wrapped rows creating extra visual lines.
+    // We press Down enough times to get well past the table.
+    for i in 0..18 {
         harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
         harness.render().unwrap();
-    }
-    wait_for_only_row_exposed(&mut harness, &labels, labels[0]);
 
-    // --- Move DOWN through all 6 rows ---
-    for i in 0..labels.len() {
-        let screen = harness.screen_to_string();
-        assert_only_row_exposed(
-            &screen,
-            &labels,
-            Some(labels[i]),
-            &format!("Down pass, row {} ({})", i, labels[i]),
+        let pos = harness.cursor_position();
+        let screen_pos = harness.screen_cursor_position();
+        eprintln!(
+            "Down press {}: cursor_byte={}, screen=({},{})",
+            i + 1,
+            pos,
+            screen_pos.0,
+            screen_pos.1
         );
-
-        // Move down to next row (skip on the last iteration)
-        if i + 1 < labels.len() {
-            harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
-            wait_for_only_row_exposed(&mut harness, &labels, labels[i + 1]);
-        }
     }
 
-    // --- Move UP back through all 6 rows ---
-    for i in (0..labels.len()).rev() {
-        let screen = harness.screen_to_string();
-        assert_only_row_exposed(
-            &screen,
-            &labels,
-            Some(labels[i]),
-            &format!("Up pass, row {} ({})", i, labels[i]),
-        );
+    let final_cursor = harness.cursor_position();
+    let final_screen = harness.screen_cursor_position();
 
-        // Move up to previous row (skip on the last iteration)
-        if i > 0 {
-            harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
-            wait_for_only_row_exposed(&mut harness, &labels, labels[i - 1]);
-        }
-    }
+    eprintln!(
+        "Final cursor: byte={}, screen=({},{})",
+        final_cursor, final_screen.0, final_screen.1
+    );
 
-    // --- Move up past the table: all rows should be concealed ---
-    // From Alpha row, move up past separator and header to above the table
-    for _ in 0..3 {
-        harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
-        harness.render().unwrap();
-    }
-    wait_for_all_concealed(&mut harness, &labels);
+    // The cursor should NOT have jumped back to the beginning (byte 0)
+    assert!(
+        final_cursor > table_start_byte,
+        "Cursor jumped back to byte {} which is before the table start at {}! \
+         Pressing Down past a table should not jump to the beginning of the document.",
+        final_cursor,
+        table_start_byte,
+    );
 
-    let screen = harness.screen_to_string();
-    assert_only_row_exposed(&screen, &labels, None, "Cursor above table");
+    // The cursor should be at or past the "After the table" line
+    assert!(
+        final_cursor >= after_table_byte,
+        "After pressing Down through the table, cursor (byte {}) should be at or past \
+         'After the table' (byte {}). Instead it's still inside the table.",
+        final_cursor,
+        after_table_byte,
+    );
 }
 
-/// Test cursor rendering while pressing Right arrow through an entire table
-/// that has emphasis causing cell wrapping on some rows.
-///
-/// Uses a 5-row table with various text lengths — some rows have bold emphasis
-/// wide enough to trigger multi-line cell wrapping when revealed. Presses
-/// Right through every source byte and asserts that:
-/// 1. Exactly one cursor is visible on screen at each step
-/// 2. The screen cursor position (row, col) is lexicographically non-decreasing
-///    — col increases within a visual line, row increases at wraps/newlines
+/// Regression test: pressing Down through a document with tables (in compose mode)
+/// and then Up all the way back must produce monotonically increasing then
+/// monotonically decreasing cursor byte positions.  A jump to byte 0 mid-sequence
+/// is the specific bug being guarded against.
 #[test]
-fn test_compose_mode_table_cursor_right_through_emphasis_wrap() {
+fn test_compose_mode_cursor_monotonic_through_tables() {
     use crate::common::harness::{copy_plugin, copy_plugin_lib};
-    use crate::common::tracing::init_tracing_from_env;
     use crossterm::event::{KeyCode, KeyModifiers};
-    use std::time::Duration;
-
-    init_tracing_from_env();
 
-    // Table modeled after the real README — emphasis in the first column
-    // that is long enough to cause multi-line cell wrapping when the **
-    // markers are revealed. The "Language Server Protocol (LSP)" cell is
-    // the key trigger row.
+    // Synthetic document with the features that trigger the bug: tables with
+    // long cells, bold/italic, links, and code blocks — but small enough to
+    // traverse quickly.
     let md_content = "\
-# Test
+# Monotonic Cursor Test
 
-| Feature | Description |
-|---|---|
-| File Management | open, save, close, tabs |
-| Editing | undo, redo, multi-cursor |
-| **Language Server Protocol (LSP)** | go to definition, hover, rename |
-| Productivity | command palette, macros |
-| **Bold** | Short |
+Some **bold text** and *italic text* here.
 
-Done.
+| Feature | Description | Status |
+|---------|-------------|--------|
+| **Tables** | Box-drawing conceals | Done |
+| *Links* | [Click here](https://example.com) | Done |
+| Code | `inline code` rendering | Done |
+
+Middle paragraph with [a link](https://example.com) and **emphasis**.
+
+| Name | Value | Notes |
+|------|-------|-------|
+| Alpha | 100 | First row |
+| Beta | 200 | Second row |
+| Gamma | 300 | Third row |
+
+```rust
+fn example() {
+    println!(\"hello\");
+}
+```
+
+Final paragraph with **more bold** and *more italic*.
+
+End.
 ";
 
     let temp_dir = tempfile::TempDir::new().unwrap();
@@ -3071,14 +2917,11 @@ Done.
     copy_plugin(&plugins_dir, "markdown_compose");
     copy_plugin_lib(&plugins_dir);
 
-    let md_path = project_root.join("table_right_wrap.md");
+    let md_path = project_root.join("monotonic_test.md");
     std::fs::write(&md_path, &md_content).unwrap();
 
-    // Narrow viewport to force the LSP row to wrap when emphasis revealed.
-    // Raw cell: "**Language Server Protocol (LSP)**" = 34 chars
-    // At viewport 55, allocated column width will be smaller → cell wraps.
     let mut harness =
-        EditorTestHarness::with_config_and_working_dir(55, 25, Default::default(), project_root)
+        EditorTestHarness::with_config_and_working_dir(60, 30, Default::default(), project_root)
             .unwrap();
 
     harness.open_file(&md_path).unwrap();
@@ -3096,170 +2939,196 @@ Done.
         .unwrap();
     harness.wait_for_prompt_closed().unwrap();
 
-    // Wait for compose mode to fully settle — table with box-drawing visible
+    // Wait for compose mode conceals to activate.
     harness
         .wait_until_stable(|h| {
             let s = h.screen_to_string();
-            s.lines().any(|l| l.contains("│") && l.contains("Editing"))
+            s.contains("│") || s.contains("─") || !s.contains("**")
         })
         .unwrap();
 
-    // Navigate to start of first data row:
-    // Ctrl+Home, Down×4 (heading, blank, header, separator)
-    harness
-        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
-        .unwrap();
-    harness.render().unwrap();
-    for _ in 0..4 {
+    // --- Move Down through entire document ---
+    let total_downs = 60;
+    let mut prev_pos = harness.cursor_position();
+    let mut down_positions = vec![prev_pos];
+
+    for i in 0..total_downs {
         harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
         harness.render().unwrap();
-    }
-    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
-    for _ in 0..3 {
-        harness.process_async_and_render().unwrap();
-        std::thread::sleep(Duration::from_millis(50));
-        harness.advance_time(Duration::from_millis(50));
-    }
 
-    // Find byte range of all 5 data rows to know when we've passed the table.
-    let content = harness.get_buffer_content().unwrap();
-    let table_end_marker = "| **Bold** | Short |";
-    let last_row_start = content.find(table_end_marker).unwrap();
-    let table_end = last_row_start + table_end_marker.len() + 1; // +1 for newline
+        let pos = harness.cursor_position();
+        assert!(
+            pos >= prev_pos,
+            "Down press {}: cursor went backwards! {} -> {} (positions so far: {:?})",
+            i + 1,
+            prev_pos,
+            pos,
+            down_positions,
+        );
+        down_positions.push(pos);
+        prev_pos = pos;
+    }
 
-    let mut prev_byte: usize = 0;
-    let mut prev_row: i32 = -1;
-    let mut prev_col: i32 = -1;
-    let mut stuck_count: usize = 0; // consecutive steps with same screen position
-    let mut failures: Vec<String> = Vec::new();
-    let mut step = 0;
+    // --- Move Up through entire document ---
+    let total_ups = 60;
+    prev_pos = harness.cursor_position();
+    let mut up_positions = vec![prev_pos];
 
-    // Press Right through the entire table, checking cursor at each step.
-    // Max steps prevents infinite loop if cursor gets stuck.
-    let max_steps = 500;
-    loop {
-        // Process async to let conceals update after cursor move
-        harness.process_async_and_render().unwrap();
-        std::thread::sleep(Duration::from_millis(30));
-        harness.advance_time(Duration::from_millis(30));
-        harness.process_async_and_render().unwrap();
+    for i in 0..total_ups {
+        harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+        harness.render().unwrap();
 
-        let byte = harness.cursor_position();
-        if byte >= table_end || step >= max_steps {
-            break; // Cursor has left the table or max steps reached
-        }
+        let pos = harness.cursor_position();
+        assert!(
+            pos <= prev_pos,
+            "Up press {}: cursor went forwards! {} -> {} (positions so far: {:?})",
+            i + 1,
+            prev_pos,
+            pos,
+            up_positions,
+        );
+        up_positions.push(pos);
+        prev_pos = pos;
+    }
+}
 
-        let (col, row) = harness.screen_cursor_position();
-        let cursors = harness.find_all_cursors();
+/// Regression test: cursor on a numbered list item should maintain its visual
+/// column when moving up.  When on the first character of "2.", pressing Up
+/// should land near the start of the line above (visual column 0), not jump
+/// to the end of that line.
+#[test]
+fn test_compose_mode_cursor_column_sticky_on_list() {
+    use crate::common::harness::{copy_plugin, copy_plugin_lib};
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use std::time::Duration;
 
-        // Check: exactly 1 cursor visible on screen
-        if cursors.len() != 1 {
-            failures.push(format!(
-                "Step {}: expected 1 cursor, found {} at byte {} (screen {},{}).\n  Cursors: {:?}",
-                step,
-                cursors.len(),
-                byte,
-                col,
-                row,
-                cursors,
-            ));
-        }
+    let md_content = "\
+# List Test
 
-        // Check: cursor byte position monotonically non-decreasing.
-        if step > 0 && byte < prev_byte {
-            failures.push(format!(
-                "Step {}: cursor byte went backward {} → {} (screen {},{} → {},{})",
-                step, prev_byte, byte, prev_col, prev_row, col, row,
-            ));
-        }
+1. **Reproduce Before Fixing**: Always include a test case that reproduces the bug (fails) without the fix, and passes with the fix. This ensures the issue is verified and prevents future regressions.
 
-        // Check: within the same screen row, column must not decrease,
-        // UNLESS it resets to column 0 which indicates a line wrap (the raw
-        // text is wider than the viewport when emphasis markers are revealed).
-        if prev_row >= 0 && (row as i32) == prev_row && (col as i32) < prev_col && col != 0 {
-            failures.push(format!(
-                "Step {}: column went backward on same row ({},{}) → ({},{}) at byte {}",
-                step, prev_col, prev_row, col, row, byte,
-            ));
-        }
+2. **E2E Tests for New Flows**: Any new user flow or feature must include an end-to-end (e2e) test. E2E tests send keyboard/mouse events and examines the final rendered output, do not examine internal state.
 
-        // Check: cursor screen position must actually advance — it should
-        // not stay stuck at the same (row, col) for many consecutive steps.
-        // Conceals can map a few adjacent source bytes to the same visual
-        // position, but more than 3 consecutive stuck steps means the
-        // cursor is not rendering in the correct position.
-        if (row as i32) == prev_row && (col as i32) == prev_col {
-            stuck_count += 1;
-            if stuck_count > 3 {
-                failures.push(format!(
-                    "Step {}: cursor stuck at ({},{}) for {} consecutive steps \
-                     (byte {} → {}). Cursor not rendering correctly.",
-                    step,
-                    col,
-                    row,
-                    stuck_count + 1,
-                    prev_byte,
-                    byte,
-                ));
-            }
-        } else {
-            stuck_count = 0;
-        }
+3. **No timeouts or time-sensitive tests**: Use semantic waiting instead of fixed timers to ensure test stability.
+";
 
-        prev_byte = byte;
-        prev_row = row as i32;
-        prev_col = col as i32;
-        step += 1;
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project");
+    std::fs::create_dir(&project_root).unwrap();
 
-        // Move right
-        harness
-            .send_key(KeyCode::Right, KeyModifiers::NONE)
+    let plugins_dir = project_root.join("plugins");
+    std::fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin(&plugins_dir, "markdown_compose");
+    copy_plugin_lib(&plugins_dir);
+
+    let md_path = project_root.join("list_test.md");
+    std::fs::write(&md_path, &md_content).unwrap();
+
+    // Narrow viewport to force wrapping of the long list items
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(40, 20, Default::default(), project_root)
             .unwrap();
-        harness.render().unwrap();
-    }
 
-    assert!(
-        step > 50,
-        "Expected to traverse >50 steps through the table, but only got {}",
-        step,
-    );
+    harness.open_file(&md_path).unwrap();
+    harness.render().unwrap();
 
-    if !failures.is_empty() {
-        eprintln!("=== {} failures out of {} steps ===", failures.len(), step);
-        for f in &failures {
-            eprintln!("{}", f);
+    // Enable compose mode
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("Toggle Compose").unwrap();
+    harness.wait_for_screen_contains("Toggle Compose").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt_closed().unwrap();
+
+    // Wait for compose mode to settle — emphasis markers should be concealed
+    harness
+        .wait_until_stable(|h| {
+            let s = h.screen_to_string();
+            s.contains("Reproduce") && !s.contains("**")
+        })
+        .unwrap();
+
+    // Navigate to the start of "2." — use Home to go to line start first,
+    // then Down to reach it
+    let content = harness.get_buffer_content().unwrap();
+    let item2_byte = content.find("2. **E2E").unwrap();
+
+    // Move cursor to the "2." line by pressing Down until we're there
+    for _ in 0..20 {
+        let pos = harness.cursor_position();
+        if pos >= item2_byte {
+            break;
         }
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+        harness.render().unwrap();
     }
+
+    // Press Home to go to column 0 of the "2." line
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+
+    let pos_on_item2 = harness.cursor_position();
+    let screen_before = harness.screen_cursor_position();
+    let visual_col_before = screen_before.0;
+
+    // Now press Up — cursor should land near the same visual column on the
+    // line above, NOT at the end of the line above
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+
+    let pos_after_up = harness.cursor_position();
+    let screen_after = harness.screen_cursor_position();
+    let visual_col_after = screen_after.0;
+
+    // The visual column should stay near column 0 (within a small tolerance
+    // for gutter width differences), definitely not jump to the end
+    let col_distance = if visual_col_after > visual_col_before {
+        visual_col_after - visual_col_before
+    } else {
+        visual_col_before - visual_col_after
+    };
+
     assert!(
-        failures.is_empty(),
-        "Cursor position errors at {} of {} steps:\n{}",
-        failures.len(),
-        step,
-        failures.join("\n"),
+        col_distance <= 3,
+        "Pressing Up from start of '2.' line (visual col {}) should land near \
+         the same column on the line above, but jumped to visual col {} \
+         (distance {}). cursor byte: {} -> {}",
+        visual_col_before,
+        visual_col_after,
+        col_distance,
+        pos_on_item2,
+        pos_after_up,
     );
 }
 
-/// Test that mouse clicks position the cursor at the correct visual column
-/// when compose width is narrower than the window width.
-///
-/// With composeWidth < viewport, content is centered with left padding.
-/// A mouse click on a visual column should place the cursor at the
-/// corresponding buffer position, not at an offset position.
+/// Regression test: visual column should remain sticky at column 0 when
+/// navigating up and down through wrapped numbered list items.
+/// Start at column 0, press Down through all items, then Up back to the top.
+/// The cursor should sit at the leftmost column of every row throughout:
+/// column 0, or the first text column of a wrapped continuation row.
 #[test]
-fn test_compose_mode_mouse_click_with_narrow_compose_width() {
+fn test_compose_mode_cursor_column_zero_sticky_through_wrapped_list() {
     use crate::common::harness::{copy_plugin, copy_plugin_lib};
-    use crate::common::tracing::init_tracing_from_env;
     use crossterm::event::{KeyCode, KeyModifiers};
     use std::time::Duration;
 
-    init_tracing_from_env();
-
+    // Numbered list with items long enough to wrap at 50 columns
     let md_content = "\
-# Hello World
+# Wrapped List
 
-This is a test paragraph with some text.
+1. First item with enough text to wrap across multiple visual lines in a narrow viewport.
 
-Another line here.
+2. Second item also has enough text that it will definitely wrap when rendered in compose mode.
+
+3. Third item continues the pattern of long text that wraps across multiple lines in the viewport.
+
+4. Fourth item is similarly long to ensure consistent wrapping behavior throughout the list.
+
+5. Fifth and final item rounds out the list with more wrapping text for thorough coverage.
 ";
 
     let temp_dir = tempfile::TempDir::new().unwrap();
@@ -3271,12 +3140,12 @@ Another line here.
     copy_plugin(&plugins_dir, "markdown_compose");
     copy_plugin_lib(&plugins_dir);
 
-    let md_path = project_root.join("mouse_click.md");
+    let md_path = project_root.join("wrapped_list.md");
     std::fs::write(&md_path, &md_content).unwrap();
 
-    // Wide viewport (80) so we can set a narrower compose width
+    // Narrow viewport to force wrapping
     let mut harness =
-        EditorTestHarness::with_config_and_working_dir(80, 25, Default::default(), project_root)
+        EditorTestHarness::with_config_and_working_dir(50, 30, Default::default(), project_root)
             .unwrap();
 
     harness.open_file(&md_path).unwrap();
@@ -3294,107 +3163,126 @@ Another line here.
         .unwrap();
     harness.wait_for_prompt_closed().unwrap();
 
-    // Set compose width to 50 (narrower than 80-col viewport)
-    harness
-        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
-        .unwrap();
-    harness.wait_for_prompt().unwrap();
-    harness.type_text("Set Compose Width").unwrap();
-    harness
-        .wait_for_screen_contains("Set Compose Width")
-        .unwrap();
-    harness
-        .send_key(KeyCode::Enter, KeyModifiers::NONE)
-        .unwrap();
-    harness.wait_for_prompt().unwrap();
-    harness.type_text("50").unwrap();
+    // Wait for compose mode to fully settle — list content visible
     harness
-        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .wait_until_stable(|h| h.screen_to_string().contains("First item"))
         .unwrap();
-    harness.wait_for_prompt_closed().unwrap();
 
-    // Wait for compose mode to settle with new width — text should be visible
-    harness
-        .wait_until_stable(|h| h.screen_to_string().contains("This is a test"))
-        .unwrap();
+    // Press Home to ensure we start at column 0
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
 
-    // The text line "This is a test paragraph with some text." should be
-    // visible and centered. Find its screen row.
-    let screen = harness.screen_to_string();
-    let target_text = "This is a test";
-    let target_row = screen
-        .lines()
-        .enumerate()
-        .find(|(_, l)| l.contains(target_text))
-        .map(|(i, _)| i as u16)
-        .expect(&format!(
-            "Should find '{}' on screen.\nScreen:\n{}",
-            target_text, screen,
-        ));
-
-    // Find the column offset where "This" starts on screen
-    let target_line = screen.lines().nth(target_row as usize).unwrap();
-    let text_col_start = target_line.find("This").unwrap() as u16;
-
-    // Click at various offsets within the text and verify cursor column
-    let test_offsets: Vec<u16> = vec![0, 5, 10, 20, 30];
-
-    for &offset in &test_offsets {
-        let click_col = text_col_start + offset;
-        harness.mouse_click(click_col, target_row).unwrap();
+    let start_col = harness.screen_cursor_position().0;
+    let gutter_col = start_col; // this is column 0 adjusted for gutter
 
-        for _ in 0..3 {
-            harness.process_async_and_render().unwrap();
-            std::thread::sleep(Duration::from_millis(30));
-            harness.advance_time(Duration::from_millis(30));
+    // The leftmost column the cursor can reach on a screen row.  A wrapped
+    // continuation row starts with hanging-indent padding that has no source
+    // bytes, so column 0 there resolves to the first text column.
+    let leftmost_col = |h: &EditorTestHarness, row: u16| -> u16 {
+        let screen = h.screen_to_string();
+        let line = screen.lines().nth(row as usize).unwrap_or("");
+        let mut rest = line.chars().skip(gutter_col as usize);
+        let indent = rest.clone().take_while(|c| *c == ' ').count();
+        match rest.nth(indent) {
+            Some(c) if c.is_alphanumeric() => gutter_col + indent as u16,
+            _ => gutter_col, // blank row (or only the scrollbar)
         }
+    };
 
-        let (cursor_col, cursor_row) = harness.screen_cursor_position();
+    // --- Move Down through the entire list ---
+    let total_presses = 25; // enough to traverse all 5 items + wrapping
+
+    for i in 0..total_presses {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+        // Wait for screen to stabilize after the plugin chain
+        // (cursor_moved → refreshLines → lines_changed → soft-breaks).
+        let mut prev = String::new();
+        harness
+            .wait_until_stable(|h| {
+                let s = h.screen_to_string();
+                let stable = s == prev;
+                prev = s;
+                stable
+            })
+            .unwrap();
 
+        let (col, row) = harness.screen_cursor_position();
         assert_eq!(
-            cursor_row,
-            target_row,
-            "After clicking at column {} on row {}, cursor should be on the same row. \
-             Got row {}.\nScreen:\n{}",
-            click_col,
-            target_row,
-            cursor_row,
-            harness.screen_to_string(),
+            col,
+            leftmost_col(&harness, row),
+            "Down press {}: cursor should stay at the leftmost column (screen row {})",
+            i + 1,
+            row,
         );
+    }
+
+    // --- Move Up back to the top ---
+    for i in 0..total_presses {
+        harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+        let mut prev = String::new();
+        harness
+            .wait_until_stable(|h| {
+                let s = h.screen_to_string();
+                let stable = s == prev;
+                prev = s;
+                stable
+            })
+            .unwrap();
 
+        let (col, row) = harness.screen_cursor_position();
         assert_eq!(
-            cursor_col,
-            click_col,
-            "After clicking at column {} on row {}, cursor should be at the same column. \
-             Got column {}.\nScreen:\n{}",
-            click_col,
-            target_row,
-            cursor_col,
-            harness.screen_to_string(),
+            col,
+            leftmost_col(&harness, row),
+            "Up press {}: cursor should stay at the leftmost column (screen row {})",
+            i + 1,
+            row,
         );
     }
+
+    // After the full round trip, cursor should be back at column 0.
+    let final_col = harness.screen_cursor_position().0;
+    assert_eq!(
+        final_col, gutter_col,
+        "After full down+up round trip, cursor should be back at column 0"
+    );
 }
 
-/// Test that mouse-clicking on emphasis text auto-exposes the markers,
-/// the same way keyboard navigation does.
+/// Regression test: table rows with emphasis markup should not overflow when
+/// cursor enters them.
 ///
-/// In compose mode, emphasis markers (`**`) are concealed. When the cursor
-/// moves into an emphasis span via keyboard, the markers are revealed.
-/// Mouse clicks should trigger the same behavior.
+/// When the cursor moves into a table row containing `**bold**`, the auto-expose
+/// feature reveals the `**` markers. Without the fix, the cell width was computed
+/// from the concealed text (shorter), so the revealed markers overflow the
+/// allocated column width, causing the row to wrap onto a second visual line.
+///
+/// The fix sizes columns based on raw (unconcealed) text so revealed markers
+/// always fit, and skips emphasis conceals for the entire row when the cursor
+/// is on it.
 #[test]
-fn test_compose_mode_mouse_click_auto_exposes_emphasis() {
+fn test_compose_mode_table_emphasis_auto_expose_no_overflow() {
     use crate::common::harness::{copy_plugin, copy_plugin_lib};
     use crate::common::tracing::init_tracing_from_env;
     use crossterm::event::{KeyCode, KeyModifiers};
 
     init_tracing_from_env();
 
+    // Table sized so that:
+    //  - Concealed row fits in 60-col viewport (57 chars)
+    //  - Revealed row (with **) overflows (61 chars) → wraps without fix
+    //
+    // Row: | **Search & Replace** | Find and replace text in the docs! |
+    //  concealed cells: " Search & Replace " (18) + " Find and replace text in the docs! " (36)
+    //  → total concealed row = 18 + 36 + 3 pipes = 57 chars
+    //  → total revealed row  = 22 + 36 + 3 pipes = 61 chars
     let md_content = "\
-# Title
+# Test
 
-Some **bold text** here.
+| Feature | Description |
+|---|---|
+| **Search & Replace** | Find and replace text in the docs! |
+| Normal item | Plain text cell |
 
-End.
+Done.
 ";
 
     let temp_dir = tempfile::TempDir::new().unwrap();
@@ -3406,19 +3294,16 @@ End.
     copy_plugin(&plugins_dir, "markdown_compose");
     copy_plugin_lib(&plugins_dir);
 
-    let md_path = project_root.join("mouse_expose.md");
+    let md_path = project_root.join("table_auto_expose.md");
     std::fs::write(&md_path, &md_content).unwrap();
 
+    // Viewport 60 cols: concealed row (57) fits, revealed row (61) overflows
     let mut harness =
-        EditorTestHarness::with_config_and_working_dir(80, 20, Default::default(), project_root)
+        EditorTestHarness::with_config_and_working_dir(60, 20, Default::default(), project_root)
             .unwrap();
 
     harness.open_file(&md_path).unwrap();
     harness.render().unwrap();
-    eprintln!(
-        "[DIAG] after open+render, screen:\n{}",
-        harness.screen_to_string()
-    );
 
     // Enable compose mode
     harness
@@ -3431,128 +3316,1642 @@ End.
         .send_key(KeyCode::Enter, KeyModifiers::NONE)
         .unwrap();
     harness.wait_for_prompt_closed().unwrap();
-    eprintln!(
-        "[DIAG] after Toggle Compose, screen:\n{}",
-        harness.screen_to_string()
-    );
 
-    // Wait for compose mode to activate: emphasis should be concealed.
-    let init_iter = std::sync::atomic::AtomicUsize::new(0);
+    // Wait for compose mode table to be fully rendered with box-drawing
+    // and emphasis concealed.
     harness
         .wait_until_stable(|h| {
             let s = h.screen_to_string();
-            let i = init_iter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-            let has_bold = s.contains("bold text");
-            let has_stars = s.contains("**");
-            eprintln!("[DIAG] init wait iter={i} has_bold={has_bold} has_stars={has_stars}");
-            eprintln!("[DIAG] screen:\n{s}");
-            has_bold && !has_stars
+            s.lines().any(|l| l.contains("│") && l.contains("Search"))
+                && !s.lines().any(|l| l.contains("**"))
         })
         .unwrap();
-    eprintln!(
-        "[DIAG] compose init done after {} iters",
-        init_iter.load(std::sync::atomic::Ordering::Relaxed)
-    );
 
-    // Find the screen position of "bold" to click on it
+    // Count how many screen lines contain "Search" — should be exactly 1
+    // (the row is not wrapping)
     let screen_before = harness.screen_to_string();
-    let bold_row = screen_before
+    let search_lines_before: Vec<_> = screen_before
         .lines()
-        .enumerate()
-        .find(|(_, l)| l.contains("bold text"))
-        .map(|(i, _)| i as u16)
-        .expect("Should find 'bold text' on screen");
-    let bold_line = screen_before.lines().nth(bold_row as usize).unwrap();
-    let bold_col = bold_line.find("bold").unwrap() as u16;
-    eprintln!("[DIAG] clicking at col={}, row={}", bold_col + 2, bold_row);
-
-    // Click on "bold text"
-    harness.mouse_click(bold_col + 2, bold_row).unwrap();
-    eprintln!(
-        "[DIAG] after mouse_click, screen:\n{}",
-        harness.screen_to_string()
+        .filter(|l| l.contains("Search"))
+        .collect();
+    assert_eq!(
+        search_lines_before.len(),
+        1,
+        "Before cursor: 'Search' row should be on exactly 1 screen line.\nScreen:\n{}",
+        screen_before,
     );
 
-    // Wait for emphasis markers to be auto-exposed by mouse click.
-    let expose_iter = std::sync::atomic::AtomicUsize::new(0);
+    // Navigate cursor into the emphasis span on the "Search & Replace" row.
+    // First go to the top, then down to the data row, then right into the span.
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+
+    // Move down: heading(1) → blank(2) → header(3) → separator(4) → data row(5)
+    for _ in 0..4 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+        harness.render().unwrap();
+    }
+    // Move right into the bold span (past the leading pipe + space + **)
+    for _ in 0..4 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::NONE)
+            .unwrap();
+        harness.render().unwrap();
+    }
+
+    // Wait for emphasis markers to be auto-exposed on the cursor row.
     harness
         .wait_until_stable(|h| {
             let s = h.screen_to_string();
-            let i = expose_iter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-            let has_exposed = s.contains("**bold text**");
-            let has_stars = s.contains("**");
-            let has_bold = s.contains("bold text");
-            if i < 5 || i % 50 == 0 {
-                eprintln!("[DIAG] expose wait iter={i} has_exposed={has_exposed} has_stars={has_stars} has_bold={has_bold}");
-                if i % 50 == 0 {
-                    eprintln!("[DIAG] screen:\n{s}");
-                }
-            }
-            has_exposed
+            s.lines().any(|l| l.contains("**Search"))
         })
         .unwrap();
-    eprintln!(
-        "[DIAG] expose done after {} iters",
-        expose_iter.load(std::sync::atomic::Ordering::Relaxed)
-    );
 
     let screen_after = harness.screen_to_string();
 
-    // The emphasis markers should now be visible (auto-exposed by mouse click)
+    // The cursor row shows raw text without padding/truncation, so it may
+    // overflow the viewport width (raw text is wider than allocated columns).
+    // This is expected — cursor positioning requires the raw bytes to be
+    // present (segment conceals break cursor mapping). Verify the raw text
+    // and pipe replacements are visible.
+    // TODO: Ideally the cursor row should fit within the viewport (with
+    // proper padding/truncation) while still allowing correct cursor
+    // positioning. This overflow is a workaround for the segment-conceal
+    // cursor mapping bug.
+    let search_line = screen_after
+        .lines()
+        .find(|l| l.contains("Search"))
+        .expect("Should find line with 'Search'");
     assert!(
-        screen_after.contains("**bold text**"),
-        "After clicking on emphasis text, markers should be auto-exposed.\nScreen:\n{}",
+        search_line.contains("│"),
+        "Cursor row should still have pipe replacements (│).\n\
+         Got: '{}'\nScreen:\n{}",
+        search_line,
         screen_after,
     );
 }
 
-/// Test setting compose width via command palette, verifying that a long line
-/// wraps at different lengths when the width is changed.
+/// Regression test: cursor rendering while navigating through table rows
+/// with emphasis markup.
+///
+/// Uses a 6-row data table where every row has emphasis. Moves the cursor
+/// down through every row, then back up, and at each position asserts that
+/// ONLY the cursor's row has its emphasis markers auto-exposed while all
+/// other rows keep them concealed.
 #[test]
-fn test_compose_mode_set_width_via_command_palette() {
+fn test_compose_mode_table_cursor_render_through_emphasis() {
     use crate::common::harness::{copy_plugin, copy_plugin_lib};
     use crate::common::tracing::init_tracing_from_env;
     use crossterm::event::{KeyCode, KeyModifiers};
-    use std::time::Duration;
 
     init_tracing_from_env();
 
-    // 200 x's — long enough to wrap at any compose width we test
-    let long_line = "x".repeat(200);
-    let md_content = format!("# Width Test\n\n{}\n", long_line);
+    // 6 data rows, each with bold emphasis in the first column.
+    // Row labels are unique so we can identify them on screen.
+    let md_content = "\
+# Table
 
-    let temp_dir = tempfile::TempDir::new().unwrap();
-    let project_root = temp_dir.path().join("project");
-    std::fs::create_dir(&project_root).unwrap();
+| Feature | Status |
+|---|---|
+| **Alpha** | *Done* |
+| **Bravo** | *Active* |
+| **Charlie** | *Pending* |
+| **Delta** | *Review* |
+| **Echo** | *Blocked* |
+| **Foxtrot** | *Shipped* |
+
+End.
+";
+
+    // The row labels without emphasis markers (concealed form)
+    let labels = ["Alpha", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot"];
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project");
+    std::fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    std::fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin(&plugins_dir, "markdown_compose");
+    copy_plugin_lib(&plugins_dir);
+
+    let md_path = project_root.join("table_cursor_render.md");
+    std::fs::write(&md_path, &md_content).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(80, 30, Default::default(), project_root)
+            .unwrap();
+
+    harness.open_file(&md_path).unwrap();
+    harness.render().unwrap();
+
+    // Enable compose mode
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("Toggle Compose").unwrap();
+    harness.wait_for_screen_contains("Toggle Compose").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt_closed().unwrap();
+
+    // Wait for compose mode to fully activate: table box-drawing visible and
+    // all emphasis concealed.
+    harness
+        .wait_until_stable(|h| {
+            let s = h.screen_to_string();
+            s.lines().any(|l| l.contains("│") && l.contains("Alpha"))
+                && !s.lines().any(|l| l.contains("**"))
+        })
+        .unwrap();
+
+    /// Assert that exactly the row for `exposed_label` (if Some) has visible
+    /// `**` markers, and all other rows have them concealed.
+    fn assert_only_row_exposed(
+        screen: &str,
+        labels: &[&str],
+        exposed_label: Option<&str>,
+        context: &str,
+    ) {
+        for &label in labels {
+            let should_expose = exposed_label == Some(label);
+            let marker = format!("**{}**", label);
+            let has_marker = screen.lines().any(|l| l.contains(&marker));
+
+            if should_expose {
+                assert!(
+                    has_marker,
+                    "{}: cursor row '{}' should show '{}' but markers are concealed.\nScreen:\n{}",
+                    context, label, marker, screen,
+                );
+            } else {
+                // The label text itself should be present (concealed form)
+                // but the ** markers should not surround it.
+                assert!(
+                    !has_marker,
+                    "{}: non-cursor row '{}' should have ** concealed but found '{}'.\nScreen:\n{}",
+                    context, label, marker, screen,
+                );
+            }
+        }
+    }
+
+    /// Wait until the screen shows `**{label}**` for the expected row AND
+    /// all other rows have their emphasis concealed. Waiting for the full
+    /// stable state prevents races where the plugin is still re-processing
+    /// other rows.
+    /// Advances logical time to allow debounce/plugin processing to complete.
+    fn wait_for_only_row_exposed(
+        harness: &mut EditorTestHarness,
+        labels: &[&str],
+        exposed_label: &str,
+    ) {
+        let wait_sleep = std::time::Duration::from_millis(50);
+        loop {
+            harness.process_async_and_render().unwrap();
+            let s = harness.screen_to_string();
+            // Check: exposed_label has markers, all others do not
+            let target_ok = s
+                .lines()
+                .any(|l| l.contains(&format!("**{}**", exposed_label)));
+            let others_ok = labels.iter().all(|&l| {
+                l == exposed_label || !s.lines().any(|line| line.contains(&format!("**{}**", l)))
+            });
+            if target_ok && others_ok {
+                return;
+            }
+            std::thread::sleep(wait_sleep);
+            harness.advance_time(wait_sleep);
+        }
+    }
+
+    /// Wait until no rows in the table have exposed emphasis markers.
+    /// Advances logical time to allow debounce/plugin processing to complete.
+    fn wait_for_all_concealed(harness: &mut EditorTestHarness, labels: &[&str]) {
+        let wait_sleep = std::time::Duration::from_millis(50);
+        loop {
+            harness.process_async_and_render().unwrap();
+            let s = harness.screen_to_string();
+            let any_exposed = labels
+                .iter()
+                .any(|l| s.lines().any(|line| line.contains(&format!("**{}**", l))));
+            if !any_exposed {
+                return;
+            }
+            std::thread::sleep(wait_sleep);
+            harness.advance_time(wait_sleep);
+        }
+    }
+
+    // Navigate: Ctrl+Home → Down×4 to reach first data row (Alpha)
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    for _ in 0..4 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+        harness.render().unwrap();
+    }
+    wait_for_only_row_exposed(&mut harness, &labels, labels[0]);
+
+    // --- Move DOWN through all 6 rows ---
+    for i in 0..labels.len() {
+        let screen = harness.screen_to_string();
+        assert_only_row_exposed(
+            &screen,
+            &labels,
+            Some(labels[i]),
+            &format!("Down pass, row {} ({})", i, labels[i]),
+        );
+
+        // Move down to next row (skip on the last iteration)
+        if i + 1 < labels.len() {
+            harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+            wait_for_only_row_exposed(&mut harness, &labels, labels[i + 1]);
+        }
+    }
+
+    // --- Move UP back through all 6 rows ---
+    for i in (0..labels.len()).rev() {
+        let screen = harness.screen_to_string();
+        assert_only_row_exposed(
+            &screen,
+            &labels,
+            Some(labels[i]),
+            &format!("Up pass, row {} ({})", i, labels[i]),
+        );
+
+        // Move up to previous row (skip on the last iteration)
+        if i > 0 {
+            harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+            wait_for_only_row_exposed(&mut harness, &labels, labels[i - 1]);
+        }
+    }
+
+    // --- Move up past the table: all rows should be concealed ---
+    // From Alpha row, move up past separator and header to above the table
+    for _ in 0..3 {
+        harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+        harness.render().unwrap();
+    }
+    wait_for_all_concealed(&mut harness, &labels);
+
+    let screen = harness.screen_to_string();
+    assert_only_row_exposed(&screen, &labels, None, "Cursor above table");
+}
+
+/// Test cursor rendering while pressing Right arrow through an entire table
+/// that has emphasis causing cell wrapping on some rows.
+///
+/// Uses a 5-row table with various text lengths — some rows have bold emphasis
+/// wide enough to trigger multi-line cell wrapping when revealed. Presses
+/// Right through every source byte and asserts that:
+/// 1. Exactly one cursor is visible on screen at each step
+/// 2. The screen cursor position (row, col) is lexicographically non-decreasing
+///    — col increases within a visual line, row increases at wraps/newlines
+#[test]
+fn test_compose_mode_table_cursor_right_through_emphasis_wrap() {
+    use crate::common::harness::{copy_plugin, copy_plugin_lib};
+    use crate::common::tracing::init_tracing_from_env;
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use std::time::Duration;
+
+    init_tracing_from_env();
+
+    // Table modeled after the real README — emphasis in the first column
+    // that is long enough to cause multi-line cell wrapping when the **
+    // markers are revealed. The "Language Server Protocol (LSP)" cell is
+    // the key trigger row.
+    let md_content = "\
+# Test
+
+| Feature | Description |
+|---|---|
+| File Management | open, save, close, tabs |
+| Editing | undo, redo, multi-cursor |
+| **Language Server Protocol (LSP)** | go to definition, hover, rename |
+| Productivity | command palette, macros |
+| **Bold** | Short |
+
+Done.
+";
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project");
+    std::fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    std::fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin(&plugins_dir, "markdown_compose");
+    copy_plugin_lib(&plugins_dir);
+
+    let md_path = project_root.join("table_right_wrap.md");
+    std::fs::write(&md_path, &md_content).unwrap();
+
+    // Narrow viewport to force the LSP row to wrap when emphasis revealed.
+    // Raw cell: "**Language Server Protocol (LSP)**" = 34 chars
+    // At viewport 55, allocated column width will be smaller → cell wraps.
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(55, 25, Default::default(), project_root)
+            .unwrap();
+
+    harness.open_file(&md_path).unwrap();
+    harness.render().unwrap();
+
+    // Enable compose mode
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("Toggle Compose").unwrap();
+    harness.wait_for_screen_contains("Toggle Compose").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt_closed().unwrap();
+
+    // Wait for compose mode to fully settle — table with box-drawing visible
+    harness
+        .wait_until_stable(|h| {
+            let s = h.screen_to_string();
+            s.lines().any(|l| l.contains("│") && l.contains("Editing"))
+        })
+        .unwrap();
+
+    // Navigate to start of first data row:
+    // Ctrl+Home, Down×4 (heading, blank, header, separator)
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    for _ in 0..4 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+        harness.render().unwrap();
+    }
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    for _ in 0..3 {
+        harness.process_async_and_render().unwrap();
+        std::thread::sleep(Duration::from_millis(50));
+        harness.advance_time(Duration::from_millis(50));
+    }
+
+    // Find byte range of all 5 data rows to know when we've passed the table.
+    let content = harness.get_buffer_content().unwrap();
+    let table_end_marker = "| **Bold** | Short |";
+    let last_row_start = content.find(table_end_marker).unwrap();
+    let table_end = last_row_start + table_end_marker.len() + 1; // +1 for newline
+
+    let mut prev_byte: usize = 0;
+    let mut prev_row: i32 = -1;
+    let mut prev_col: i32 = -1;
+    let mut stuck_count: usize = 0; // consecutive steps with same screen position
+    let mut failures: Vec<String> = Vec::new();
+    let mut step = 0;
+
+    // Press Right through the entire table, checking cursor at each step.
+    // Max steps prevents infinite loop if cursor gets stuck.
+    let max_steps = 500;
+    loop {
+        // Process async to let conceals update after cursor move
+        harness.process_async_and_render().unwrap();
+        std::thread::sleep(Duration::from_millis(30));
+        harness.advance_time(Duration::from_millis(30));
+        harness.process_async_and_render().unwrap();
+
+        let byte = harness.cursor_position();
+        if byte >= table_end || step >= max_steps {
+            break; // Cursor has left the table or max steps reached
+        }
+
+        let (col, row) = harness.screen_cursor_position();
+        let cursors = harness.find_all_cursors();
+
+        // Check: exactly 1 cursor visible on screen
+        if cursors.len() != 1 {
+            failures.push(format!(
+                "Step {}: expected 1 cursor, found {} at byte {} (screen {},{}).\n  Cursors: {:?}",
+                step,
+                cursors.len(),
+                byte,
+                col,
+                row,
+                cursors,
+            ));
+        }
+
+        // Check: cursor byte position monotonically non-decreasing.
+        if step > 0 && byte < prev_byte {
+            failures.push(format!(
+                "Step {}: cursor byte went backward {} → {} (screen {},{} → {},{})",
+                step, prev_byte, byte, prev_col, prev_row, col, row,
+            ));
+        }
+
+        // Check: within the same screen row, column must not decrease,
+        // UNLESS it resets to column 0 which indicates a line wrap (the raw
+        // text is wider than the viewport when emphasis markers are revealed).
+        if prev_row >= 0 && (row as i32) == prev_row && (col as i32) < prev_col && col != 0 {
+            failures.push(format!(
+                "Step {}: column went backward on same row ({},{}) → ({},{}) at byte {}",
+                step, prev_col, prev_row, col, row, byte,
+            ));
+        }
+
+        // Check: cursor screen position must actually advance — it should
+        // not stay stuck at the same (row, col) for many consecutive steps.
+        // Conceals can map a few adjacent source bytes to the same visual
+        // position, but more than 3 consecutive stuck steps means the
+        // cursor is not rendering in the correct position.
+        if (row as i32) == prev_row && (col as i32) == prev_col {
+            stuck_count += 1;
+            if stuck_count > 3 {
+                failures.push(format!(
+                    "Step {}: cursor stuck at ({},{}) for {} consecutive steps \
+                     (byte {} → {}). Cursor not rendering correctly.",
+                    step,
+                    col,
+                    row,
+                    stuck_count + 1,
+                    prev_byte,
+                    byte,
+                ));
+            }
+        } else {
+            stuck_count = 0;
+        }
+
+        prev_byte = byte;
+        prev_row = row as i32;
+        prev_col = col as i32;
+        step += 1;
+
+        // Move right
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::NONE)
+            .unwrap();
+        harness.render().unwrap();
+    }
+
+    assert!(
+        step > 50,
+        "Expected to traverse >50 steps through the table, but only got {}",
+        step,
+    );
+
+    if !failures.is_empty() {
+        eprintln!("=== {} failures out of {} steps ===", failures.len(), step);
+        for f in &failures {
+            eprintln!("{}", f);
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "Cursor position errors at {} of {} steps:\n{}",
+        failures.len(),
+        step,
+        failures.join("\n"),
+    );
+}
+
+/// Test that mouse clicks position the cursor at the correct visual column
+/// when compose width is narrower than the window width.
+///
+/// With composeWidth < viewport, content is centered with left padding.
+/// A mouse click on a visual column should place the cursor at the
+/// corresponding buffer position, not at an offset position.
+#[test]
+fn test_compose_mode_mouse_click_with_narrow_compose_width() {
+    use crate::common::harness::{copy_plugin, copy_plugin_lib};
+    use crate::common::tracing::init_tracing_from_env;
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use std::time::Duration;
+
+    init_tracing_from_env();
+
+    let md_content = "\
+# Hello World
+
+This is a test paragraph with some text.
+
+Another line here.
+";
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project");
+    std::fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    std::fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin(&plugins_dir, "markdown_compose");
+    copy_plugin_lib(&plugins_dir);
+
+    let md_path = project_root.join("mouse_click.md");
+    std::fs::write(&md_path, &md_content).unwrap();
+
+    // Wide viewport (80) so we can set a narrower compose width
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(80, 25, Default::default(), project_root)
+            .unwrap();
+
+    harness.open_file(&md_path).unwrap();
+    harness.render().unwrap();
+
+    // Enable compose mode
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("Toggle Compose").unwrap();
+    harness.wait_for_screen_contains("Toggle Compose").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt_closed().unwrap();
+
+    // Set compose width to 50 (narrower than 80-col viewport)
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("Set Compose Width").unwrap();
+    harness
+        .wait_for_screen_contains("Set Compose Width")
+        .unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("50").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt_closed().unwrap();
+
+    // Wait for compose mode to settle with new width — text should be visible
+    harness
+        .wait_until_stable(|h| h.screen_to_string().contains("This is a test"))
+        .unwrap();
+
+    // The text line "This is a test paragraph with some text." should be
+    // visible and centered. Find its screen row.
+    let screen = harness.screen_to_string();
+    let target_text = "This is a test";
+    let target_row = screen
+        .lines()
+        .enumerate()
+        .find(|(_, l)| l.contains(target_text))
+        .map(|(i, _)| i as u16)
+        .expect(&format!(
+            "Should find '{}' on screen.\nScreen:\n{}",
+            target_text, screen,
+        ));
+
+    // Find the column offset where "This" starts on screen
+    let target_line = screen.lines().nth(target_row as usize).unwrap();
+    let text_col_start = target_line.find("This").unwrap() as u16;
+
+    // Click at various offsets within the text and verify cursor column
+    let test_offsets: Vec<u16> = vec![0, 5, 10, 20, 30];
+
+    for &offset in &test_offsets {
+        let click_col = text_col_start + offset;
+        harness.mouse_click(click_col, target_row).unwrap();
+
+        for _ in 0..3 {
+            harness.process_async_and_render().unwrap();
+            std::thread::sleep(Duration::from_millis(30));
+            harness.advance_time(Duration::from_millis(30));
+        }
+
+        let (cursor_col, cursor_row) = harness.screen_cursor_position();
+
+        assert_eq!(
+            cursor_row,
+            target_row,
+            "After clicking at column {} on row {}, cursor should be on the same row. \
+             Got row {}.\nScreen:\n{}",
+            click_col,
+            target_row,
+            cursor_row,
+            harness.screen_to_string(),
+        );
+
+        assert_eq!(
+            cursor_col,
+            click_col,
+            "After clicking at column {} on row {}, cursor should be at the same column. \
+             Got column {}.\nScreen:\n{}",
+            click_col,
+            target_row,
+            cursor_col,
+            harness.screen_to_string(),
+        );
+    }
+}
+
+/// Test that mouse-clicking on emphasis text auto-exposes the markers,
+/// the same way keyboard navigation does.
+///
+/// In compose mode, emphasis markers (`**`) are concealed. When the cursor
+/// moves into an emphasis span via keyboard, the markers are revealed.
+/// Mouse clicks should trigger the same behavior.
+#[test]
+fn test_compose_mode_mouse_click_auto_exposes_emphasis() {
+    use crate::common::harness::{copy_plugin, copy_plugin_lib};
+    use crate::common::tracing::init_tracing_from_env;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    init_tracing_from_env();
+
+    let md_content = "\
+# Title
+
+Some **bold text** here.
+
+End.
+";
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project");
+    std::fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    std::fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin(&plugins_dir, "markdown_compose");
+    copy_plugin_lib(&plugins_dir);
+
+    let md_path = project_root.join("mouse_expose.md");
+    std::fs::write(&md_path, &md_content).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(80, 20, Default::default(), project_root)
+            .unwrap();
+
+    harness.open_file(&md_path).unwrap();
+    harness.render().unwrap();
+    eprintln!(
+        "[DIAG] after open+render, screen:\n{}",
+        harness.screen_to_string()
+    );
+
+    // Enable compose mode
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("Toggle Compose").unwrap();
+    harness.wait_for_screen_contains("Toggle Compose").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt_closed().unwrap();
+    eprintln!(
+        "[DIAG] after Toggle Compose, screen:\n{}",
+        harness.screen_to_string()
+    );
+
+    // Wait for compose mode to activate: emphasis should be concealed.
+    let init_iter = std::sync::atomic::AtomicUsize::new(0);
+    harness
+        .wait_until_stable(|h| {
+            let s = h.screen_to_string();
+            let i = init_iter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            let has_bold = s.contains("bold text");
+            let has_stars = s.contains("**");
+            eprintln!("[DIAG] init wait iter={i} has_bold={has_bold} has_stars={has_stars}");
+            eprintln!("[DIAG] screen:\n{s}");
+            has_bold && !has_stars
+        })
+        .unwrap();
+    eprintln!(
+        "[DIAG] compose init done after {} iters",
+        init_iter.load(std::sync::atomic::Ordering::Relaxed)
+    );
+
+    // Find the screen position of "bold" to click on it
+    let screen_before = harness.screen_to_string();
+    let bold_row = screen_before
+        .lines()
+        .enumerate()
+        .find(|(_, l)| l.contains("bold text"))
+        .map(|(i, _)| i as u16)
+        .expect("Should find 'bold text' on screen");
+    let bold_line = screen_before.lines().nth(bold_row as usize).unwrap();
+    let bold_col = bold_line.find("bold").unwrap() as u16;
+    eprintln!("[DIAG] clicking at col={}, row={}", bold_col + 2, bold_row);
+
+    // Click on "bold text"
+    harness.mouse_click(bold_col + 2, bold_row).unwrap();
+    eprintln!(
+        "[DIAG] after mouse_click, screen:\n{}",
+        harness.screen_to_string()
+    );
+
+    // Wait for emphasis markers to be auto-exposed by mouse click.
+    let expose_iter = std::sync::atomic::AtomicUsize::new(0);
+    harness
+        .wait_until_stable(|h| {
+            let s = h.screen_to_string();
+            let i = expose_iter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            let has_exposed = s.contains("**bold text**");
+            let has_stars = s.contains("**");
+            let has_bold = s.contains("bold text");
+            if i < 5 || i % 50 == 0 {
+                eprintln!("[DIAG] expose wait iter={i} has_exposed={has_exposed} has_stars={has_stars} has_bold={has_bold}");
+                if i % 50 == 0 {
+                    eprintln!("[DIAG] screen:\n{s}");
+                }
+            }
+            has_exposed
+        })
+        .unwrap();
+    eprintln!(
+        "[DIAG] expose done after {} iters",
+        expose_iter.load(std::sync::atomic::Ordering::Relaxed)
+    );
+
+    let screen_after = harness.screen_to_string();
+
+    // The emphasis markers should now be visible (auto-exposed by mouse click)
+    assert!(
+        screen_after.contains("**bold text**"),
+        "After clicking on emphasis text, markers should be auto-exposed.\nScreen:\n{}",
+        screen_after,
+    );
+}
+
+/// Test setting compose width via command palette, verifying that a long line
+/// wraps at different lengths when the width is changed.
+#[test]
+fn test_compose_mode_set_width_via_command_palette() {
+    use crate::common::harness::{copy_plugin, copy_plugin_lib};
+    use crate::common::tracing::init_tracing_from_env;
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use std::time::Duration;
+
+    init_tracing_from_env();
+
+    // 200 x's — long enough to wrap at any compose width we test
+    let long_line = "x".repeat(200);
+    let md_content = format!("# Width Test\n\n{}\n", long_line);
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project");
+    std::fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    std::fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin(&plugins_dir, "markdown_compose");
+    copy_plugin_lib(&plugins_dir);
+
+    let md_path = project_root.join("width_test.md");
+    std::fs::write(&md_path, &md_content).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(80, 25, Default::default(), project_root)
+            .unwrap();
+
+    harness.open_file(&md_path).unwrap();
+    harness.render().unwrap();
+
+    // Helper: collect lengths of all x-only lines
+    let x_line_lengths = |screen: &str| -> Vec<usize> {
+        screen
+            .lines()
+            .filter(|l| {
+                let trimmed = l.trim();
+                !trimmed.is_empty() && trimmed.chars().all(|c| c == 'x')
+            })
+            .map(|l| l.trim().len())
+            .collect::<Vec<_>>()
+    };
+
+    // Helper: open command palette and run a command by name
+    let run_command = |h: &mut EditorTestHarness, name: &str| {
+        h.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        h.wait_for_prompt().unwrap();
+        h.type_text(name).unwrap();
+        h.wait_for_screen_contains(name).unwrap();
+        h.send_key(KeyCode::Enter, KeyModifiers::NONE).unwrap();
+    };
+
+    // Helper: let compose mode settle after a change
+    let settle = |h: &mut EditorTestHarness| {
+        for _ in 0..10 {
+            h.process_async_and_render().unwrap();
+            std::thread::sleep(Duration::from_millis(50));
+            h.advance_time(Duration::from_millis(50));
+        }
+        h.render().unwrap();
+    };
+
+    // Enable compose mode
+    run_command(&mut harness, "Toggle Compose");
+    harness.wait_for_prompt_closed().unwrap();
+    settle(&mut harness);
+
+    let screen_default = harness.screen_to_string();
+    let default_lengths = x_line_lengths(&screen_default);
+    println!("=== DEFAULT (no compose width set) ===");
+    println!("x-line lengths: {:?}", default_lengths);
+    println!("{}", screen_default);
+
+    // --- Set compose width to 40 by typing ---
+    run_command(&mut harness, "Set Compose Width");
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("40").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt_closed().unwrap();
+    settle(&mut harness);
+
+    let screen_40 = harness.screen_to_string();
+    let w40_lengths = x_line_lengths(&screen_40);
+    println!("=== AFTER SET WIDTH TO 40 ===");
+    println!("x-line lengths: {:?}", w40_lengths);
+    println!("{}", screen_40);
+
+    assert_eq!(
+        w40_lengths[0], 40,
+        "With compose width 40, first x-line should be 40 chars, got {:?}",
+        w40_lengths,
+    );
+
+    // --- Open prompt again and just press Enter (should keep width=40) ---
+    run_command(&mut harness, "Set Compose Width");
+    harness.wait_for_prompt().unwrap();
+    // Don't change anything, just confirm the pre-filled value
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt_closed().unwrap();
+    settle(&mut harness);
+
+    let screen_reconfirm = harness.screen_to_string();
+    let reconfirm_lengths = x_line_lengths(&screen_reconfirm);
+    println!("=== AFTER RE-CONFIRMING (should still be 40) ===");
+    println!("x-line lengths: {:?}", reconfirm_lengths);
+    println!("{}", screen_reconfirm);
+
+    assert_eq!(
+        reconfirm_lengths[0], 40,
+        "After re-confirming without changes, width should still be 40, got {:?}",
+        reconfirm_lengths,
+    );
+
+    // --- Set compose width to 60 by typing ---
+    run_command(&mut harness, "Set Compose Width");
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("60").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt_closed().unwrap();
+    settle(&mut harness);
+
+    let screen_60 = harness.screen_to_string();
+    let w60_lengths = x_line_lengths(&screen_60);
+    println!("=== AFTER SET WIDTH TO 60 ===");
+    println!("x-line lengths: {:?}", w60_lengths);
+    println!("{}", screen_60);
+
+    assert_eq!(
+        w60_lengths[0], 60,
+        "With compose width 60, first x-line should be 60 chars, got {:?}",
+        w60_lengths,
+    );
+}
+
+/// Test that compose width can be changed after restoring a workspace session.
+///
+/// Reproduces a bug where a persisted compose_width (e.g. from a previous session)
+/// is stuck and cannot be changed via the Set Compose Width command, because the
+/// plugin's config.composeWidth is out of sync with the restored view state.
+#[test]
+fn test_compose_mode_width_survives_session_restore() {
+    use crate::common::harness::{copy_plugin, copy_plugin_lib};
+    use crate::common::tracing::init_tracing_from_env;
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use std::time::Duration;
+
+    init_tracing_from_env();
+
+    let long_line = "x".repeat(200);
+    let md_content = format!("# Session Test\n\n{}\n", long_line);
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project");
+    std::fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    std::fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin(&plugins_dir, "markdown_compose");
+    copy_plugin_lib(&plugins_dir);
+
+    let md_path = project_root.join("session_test.md");
+    std::fs::write(&md_path, &md_content).unwrap();
+
+    // A second file that is NOT in compose mode
+    let other_path = project_root.join("other.txt");
+    std::fs::write(
+        &other_path,
+        "This is a plain text file, not in compose mode.\n",
+    )
+    .unwrap();
+
+    // Count consecutive x characters in each screen line that contains x's.
+    // Handles both compose mode (no line numbers, just x's) and source mode
+    // (line numbers like "3 │ xxxx").
+    let x_line_lengths = |screen: &str| -> Vec<usize> {
+        screen
+            .lines()
+            .filter_map(|l| {
+                // Find the longest run of consecutive x's in the line
+                let max_run = l
+                    .chars()
+                    .fold((0usize, 0usize), |(max, cur), c| {
+                        if c == 'x' {
+                            (max.max(cur + 1), cur + 1)
+                        } else {
+                            (max, 0)
+                        }
+                    })
+                    .0;
+                if max_run >= 10 {
+                    Some(max_run)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let run_command = |h: &mut EditorTestHarness, name: &str| {
+        h.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        h.wait_for_prompt().unwrap();
+        h.type_text(name).unwrap();
+        h.wait_for_screen_contains(name).unwrap();
+        h.send_key(KeyCode::Enter, KeyModifiers::NONE).unwrap();
+    };
+
+    let settle = |h: &mut EditorTestHarness| {
+        for _ in 0..10 {
+            h.process_async_and_render().unwrap();
+            std::thread::sleep(Duration::from_millis(50));
+            h.advance_time(Duration::from_millis(50));
+        }
+        h.render().unwrap();
+    };
+
+    // --- Session 1: open both files, enable compose on md, set width to 40, save ---
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            25,
+            Default::default(),
+            project_root.clone(),
+        )
+        .unwrap();
+
+        harness.open_file(&other_path).unwrap();
+        harness.render().unwrap();
+        harness.open_file(&md_path).unwrap();
+        harness.render().unwrap();
+
+        run_command(&mut harness, "Toggle Compose");
+        harness.wait_for_prompt_closed().unwrap();
+        settle(&mut harness);
+
+        run_command(&mut harness, "Set Compose Width");
+        harness.wait_for_prompt().unwrap();
+        harness.type_text("40").unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+        harness.wait_for_prompt_closed().unwrap();
+        settle(&mut harness);
+
+        let screen = harness.screen_to_string();
+        let lengths = x_line_lengths(&screen);
+        println!("=== SESSION 1: width=40 ===");
+        println!("x-line lengths: {:?}", lengths);
+        println!("{}", screen);
+        assert_eq!(
+            lengths[0], 40,
+            "Session 1: width should be 40, got {:?}",
+            lengths,
+        );
+
+        harness.editor_mut().save_workspace().unwrap();
+    }
+
+    // --- Session 2: restore, verify both buffers, then change compose width ---
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            25,
+            Default::default(),
+            project_root.clone(),
+        )
+        .unwrap();
+
+        harness.editor_mut().try_restore_workspace().unwrap();
+        settle(&mut harness);
+
+        // The md file should be active (it was last opened) and in compose mode
+        let screen_restored = harness.screen_to_string();
+        let restored_lengths = x_line_lengths(&screen_restored);
+        println!("=== SESSION 2: md file after restore ===");
+        println!("x-line lengths: {:?}", restored_lengths);
+        println!("{}", screen_restored);
+        assert!(
+            !restored_lengths.is_empty(),
+            "After restore, should see x-lines on screen.\nScreen:\n{}",
+            screen_restored,
+        );
+        assert_eq!(
+            restored_lengths[0], 40,
+            "After restore, compose width should still be 40, got {:?}.\nScreen:\n{}",
+            restored_lengths, screen_restored,
+        );
+
+        // Switch to the other (non-compose) buffer and verify it's normal
+        harness.open_file(&other_path).unwrap();
+        settle(&mut harness);
+        let screen_other = harness.screen_to_string();
+        println!("=== SESSION 2: other.txt (non-compose) ===");
+        println!("{}", screen_other);
+        assert!(
+            screen_other.contains("plain text file"),
+            "other.txt should show its content normally.\nScreen:\n{}",
+            screen_other,
+        );
+
+        // Switch back to the md file
+        harness.open_file(&md_path).unwrap();
+        settle(&mut harness);
+
+        // Now try to change width to 60
+        run_command(&mut harness, "Set Compose Width");
+        harness.wait_for_prompt().unwrap();
+        harness.type_text("60").unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+        harness.wait_for_prompt_closed().unwrap();
+        settle(&mut harness);
+
+        let screen_60 = harness.screen_to_string();
+        let w60_lengths = x_line_lengths(&screen_60);
+        println!("=== SESSION 2: after changing to 60 ===");
+        println!("x-line lengths: {:?}", w60_lengths);
+        println!("{}", screen_60);
+        assert_eq!(
+            w60_lengths[0], 60,
+            "After changing width to 60 in restored session, got {:?}",
+            w60_lengths,
+        );
+    }
+}
+
+/// Compose mode shows a whole-document word count and reading time in the
+/// status bar. Typing words bumps the count, and — as in
+/// `test_compose_mode_typing_no_flicker` — no content row other than the one
+/// being typed on changes, neither mid-frame nor once the count has updated.
+#[test]
+fn test_compose_mode_word_count() {
+    use crate::common::harness::{copy_plugin, copy_plugin_lib};
+    use crate::common::tracing::init_tracing_from_env;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    init_tracing_from_env();
+
+    // Markdown punctuation (heading hashes, bullets, table pipes, fences)
+    // doesn't count: the words are Word, Count, One, two, three, cell, more.
+    let mut md_content =
+        String::from("# Word Count\n\n- One **two** three.\n\n| cell | more |\n\n```\n```\n\n");
+    // Push the document past the viewport so the count can't be viewport-only
+    for _ in 0..60 {
+        md_content.push_str("filler\n\n");
+    }
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project");
+    std::fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    std::fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin(&plugins_dir, "markdown_compose");
+    copy_plugin_lib(&plugins_dir);
+
+    let md_path = project_root.join("word_count.md");
+    std::fs::write(&md_path, &md_content).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(80, 40, Default::default(), project_root)
+            .unwrap();
+
+    harness.open_file(&md_path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("Toggle Compose").unwrap();
+    harness.wait_for_screen_contains("Toggle Compose").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt_closed().unwrap();
+
+    harness
+        .wait_until_stable(|h| h.get_status_bar().contains("67 words, 1 min read"))
+        .unwrap();
+
+    // Type on the first paragraph line, which stays on screen
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key_repeat(KeyCode::Down, KeyModifiers::NONE, 2)
+        .unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    let mut prev = String::new();
+    harness
+        .wait_until_stable(|h| {
+            let s = h.screen_to_string();
+            let stable = s == prev;
+            prev = s;
+            stable
+        })
+        .unwrap();
+
+    let (content_start, content_end) = harness.content_area_rows();
+    let content_rows = content_end - content_start + 1;
+    let extract_content = |screen: &str| -> Vec<String> {
+        screen
+            .lines()
+            .skip(content_start)
+            .take(content_rows)
+            .map(|l| l.to_string())
+            .collect()
+    };
+    let changed_rows = |a: &[String], b: &[String]| -> Vec<usize> {
+        a.iter()
+            .zip(b.iter())
+            .enumerate()
+            .filter(|(_, (la, lb))| la != lb)
+            .map(|(i, _)| i)
+            .collect()
+    };
+
+    let before_content = extract_content(&harness.screen_to_string());
+    let mut typed_row = None;
+
+    for (word, expected) in [(" four", 68), (" five", 69)] {
+        for ch in word.chars() {
+            harness
+                .editor_mut()
+                .handle_key(KeyCode::Char(ch), KeyModifiers::NONE)
+                .unwrap();
+            // Render before the plugin has responded to the edit
+            harness.render().unwrap();
+            let mid_content = extract_content(&harness.screen_to_string());
+            let changed = changed_rows(&before_content, &mid_content);
+            assert!(
+                changed.len() <= 1,
+                "FLICKER: typing {:?} changed content rows {:?}.\n\
+                 Before:\n{}\n\nMid-frame:\n{}",
+                ch,
+                changed,
+                before_content.join("\n"),
+                mid_content.join("\n"),
+            );
+            if let Some(&row) = changed.first() {
+                assert_eq!(
+                    *typed_row.get_or_insert(row),
+                    row,
+                    "Only the row being typed on may change"
+                );
+            }
+        }
+
+        let status = format!("{expected} words, 1 min read");
+        harness
+            .wait_until_stable(|h| h.get_status_bar().contains(&status))
+            .unwrap();
+
+        let after_content = extract_content(&harness.screen_to_string());
+        let changed = changed_rows(&before_content, &after_content);
+        assert_eq!(
+            changed,
+            typed_row.into_iter().collect::<Vec<_>>(),
+            "Only the typed row should differ once the count has updated.\n\
+             After:\n{}",
+            after_content.join("\n"),
+        );
+    }
+
+    let typed = &extract_content(&harness.screen_to_string())[typed_row.unwrap()];
+    assert!(
+        typed.contains("three. four five"),
+        "Typed words should appear on the paragraph row, got: {:?}",
+        typed.trim_end(),
+    );
+}
+
+/// Toggling a task list item from the command palette flips the checkbox in
+/// the buffer and the rendered glyph, including for nested items, and leaves
+/// plain list items alone.
+#[test]
+fn test_compose_mode_toggle_task_checkbox() {
+    use crate::common::harness::{copy_plugin, copy_plugin_lib};
+    use crate::common::tracing::init_tracing_from_env;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    init_tracing_from_env();
+
+    let md_content = "\
+# Tasks
+
+- [ ] write tests
+  - [ ] nested item
+- plain item
+";
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project");
+    std::fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    std::fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin(&plugins_dir, "markdown_compose");
+    copy_plugin_lib(&plugins_dir);
+
+    let md_path = project_root.join("tasks.md");
+    std::fs::write(&md_path, md_content).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(80, 40, Default::default(), project_root)
+            .unwrap();
+
+    harness.open_file(&md_path).unwrap();
+    harness.render().unwrap();
+
+    let run_command = |h: &mut EditorTestHarness, name: &str| {
+        h.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        h.wait_for_prompt().unwrap();
+        h.type_text(name).unwrap();
+        h.wait_for_screen_contains(name).unwrap();
+        h.send_key(KeyCode::Enter, KeyModifiers::NONE).unwrap();
+        h.wait_for_prompt_closed().unwrap();
+    };
+
+    run_command(&mut harness, "Toggle Compose");
+
+    // Cursor at the end of the nested item's text, off the checkbox itself
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key_repeat(KeyCode::Down, KeyModifiers::NONE, 3)
+        .unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness
+        .wait_until_stable(|h| {
+            let s = h.screen_to_string();
+            s.contains("\u{2610} write tests") && s.contains("  \u{2610} nested item")
+        })
+        .unwrap();
+
+    run_command(&mut harness, "Toggle Task Checkbox");
+    harness
+        .wait_until_stable(|h| h.screen_to_string().contains("  \u{2611} nested item"))
+        .unwrap();
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        md_content.replace("  - [ ] nested item", "  - [x] nested item"),
+        "Only the nested item's checkbox should change"
+    );
+    let screen = harness.screen_to_string();
+    assert!(
+        screen.contains("\u{2610} write tests"),
+        "The other task should stay unchecked.\nScreen:\n{}",
+        screen,
+    );
+
+    // Toggling again unchecks it
+    run_command(&mut harness, "Toggle Task Checkbox");
+    harness
+        .wait_until_stable(|h| h.screen_to_string().contains("  \u{2610} nested item"))
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), md_content);
+
+    // A plain list item is not a task
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    run_command(&mut harness, "Toggle Task Checkbox");
+    harness
+        .wait_until_stable(|h| {
+            h.get_status_bar()
+                .contains("Cursor is not on a task list item")
+        })
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), md_content);
+}
+
+/// Set up a compose-mode harness on `content` with an internal clipboard.
+fn compose_paste_harness(content: &str) -> (tempfile::TempDir, PathBuf, EditorTestHarness) {
+    use crate::common::harness::{copy_plugin, copy_plugin_lib};
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project");
+    std::fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    std::fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin(&plugins_dir, "markdown_compose");
+    copy_plugin_lib(&plugins_dir);
+
+    let md_path = project_root.join("paste_test.md");
+    std::fs::write(&md_path, content).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(80, 30, Default::default(), project_root)
+            .unwrap();
+    harness.editor_mut().set_clipboard_for_test(String::new());
+
+    harness.open_file(&md_path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("Toggle Compose").unwrap();
+    harness.wait_for_screen_contains("Toggle Compose").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt_closed().unwrap();
+
+    (temp_dir, md_path, harness)
+}
+
+/// Pasting a bare URL over a selection in compose mode links the selection.
+#[test]
+fn test_compose_mode_paste_url_over_selection() {
+    use crate::common::tracing::init_tracing_from_env;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    init_tracing_from_env();
+
+    let (_temp_dir, _md_path, mut harness) =
+        compose_paste_harness("# Links\n\nRead the docs today.\n");
+
+    // Select "the docs"
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key_repeat(KeyCode::Down, KeyModifiers::NONE, 2)
+        .unwrap();
+    harness
+        .send_key_repeat(KeyCode::Right, KeyModifiers::NONE, 5)
+        .unwrap();
+    harness
+        .send_key_repeat(KeyCode::Right, KeyModifiers::SHIFT, 8)
+        .unwrap();
+
+    harness
+        .editor_mut()
+        .set_clipboard_for_test("https://example.com/guide".to_string());
+    harness
+        .send_key(KeyCode::Char('v'), KeyModifiers::CONTROL)
+        .unwrap();
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "# Links\n\nRead [the docs](https://example.com/guide) today.\n"
+    );
+
+    // Off the link, compose mode conceals the new link syntax
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness
+        .wait_until_stable(|h| h.screen_to_string().contains("Read the docs today."))
+        .unwrap();
+}
+
+/// Pasting tab-separated rows onto an empty line in compose mode inserts a
+/// pipe table, which renders with its column borders lined up.
+#[test]
+fn test_compose_mode_paste_tsv_as_table() {
+    use crate::common::tracing::init_tracing_from_env;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    init_tracing_from_env();
+
+    let (_temp_dir, _md_path, mut harness) =
+        compose_paste_harness("# Table\n\nIntro.\n\n\nAfter.\n");
+
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key_repeat(KeyCode::Down, KeyModifiers::NONE, 4)
+        .unwrap();
+
+    harness
+        .editor_mut()
+        .set_clipboard_for_test("Name\tQty\napple\t3\nkiwi fruit\t12\n".to_string());
+    harness
+        .send_key(KeyCode::Char('v'), KeyModifiers::CONTROL)
+        .unwrap();
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "# Table\n\nIntro.\n\n\
+         | Name       | Qty |\n\
+         | ---------- | --- |\n\
+         | apple      | 3   |\n\
+         | kiwi fruit | 12  |\n\
+         \nAfter.\n"
+    );
+
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_until_stable(|h| {
+            let s = h.screen_to_string();
+            s.lines().filter(|l| l.contains('│')).count() >= 3
+        })
+        .unwrap();
+
+    let screen = harness.screen_to_string();
+    let border_columns = |needle: &str| -> Vec<usize> {
+        let row = screen
+            .lines()
+            .find(|l| l.contains(needle))
+            .unwrap_or_else(|| panic!("no row with {needle:?}.\nScreen:\n{screen}"));
+        row.chars()
+            .enumerate()
+            .filter(|(_, c)| *c == '│')
+            .map(|(i, _)| i)
+            .collect()
+    };
+    let header = border_columns("Name");
+    assert_eq!(header.len(), 3, "Two columns need three borders");
+    assert_eq!(border_columns("apple"), header, "Columns should line up");
+    assert_eq!(
+        border_columns("kiwi fruit"),
+        header,
+        "Columns should line up"
+    );
+}
+
+/// A standalone `---` renders as a full-width horizontal rule, while a `---`
+/// directly under text is a setext underline: it draws no rule and turns the
+/// text into a heading. The paragraph above the rule stays a paragraph.
+#[test]
+fn test_compose_mode_horizontal_rule() {
+    use crate::common::harness::{copy_plugin, copy_plugin_lib};
+    use crate::common::tracing::init_tracing_from_env;
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use ratatui::style::Modifier;
+
+    init_tracing_from_env();
+
+    let md_content = "\
+Intro paragraph text.
+
+---
+
+Setext Title
+---
+
+Closing paragraph.
+";
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project");
+    std::fs::create_dir(&project_root).unwrap();
 
     let plugins_dir = project_root.join("plugins");
     std::fs::create_dir(&plugins_dir).unwrap();
     copy_plugin(&plugins_dir, "markdown_compose");
     copy_plugin_lib(&plugins_dir);
 
-    let md_path = project_root.join("width_test.md");
-    std::fs::write(&md_path, &md_content).unwrap();
+    let md_path = project_root.join("rule_test.md");
+    std::fs::write(&md_path, md_content).unwrap();
 
     let mut harness =
-        EditorTestHarness::with_config_and_working_dir(80, 25, Default::default(), project_root)
+        EditorTestHarness::with_config_and_working_dir(80, 30, Default::default(), project_root)
             .unwrap();
 
     harness.open_file(&md_path).unwrap();
     harness.render().unwrap();
 
-    // Helper: collect lengths of all x-only lines
-    let x_line_lengths = |screen: &str| -> Vec<usize> {
-        screen
-            .lines()
-            .filter(|l| {
-                let trimmed = l.trim();
-                !trimmed.is_empty() && trimmed.chars().all(|c| c == 'x')
-            })
-            .map(|l| l.trim().len())
-            .collect::<Vec<_>>()
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("Toggle Compose").unwrap();
+    harness.wait_for_screen_contains("Toggle Compose").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt_closed().unwrap();
+
+    harness
+        .wait_until_stable(|h| !h.screen_to_string().contains("---"))
+        .unwrap();
+
+    let screen = harness.screen_to_string();
+    let rule_rows: Vec<&str> = screen
+        .lines()
+        .filter(|l| {
+            let t = l.trim();
+            !t.is_empty() && t.chars().all(|c| c == '\u{2500}')
+        })
+        .collect();
+    assert_eq!(
+        rule_rows.len(),
+        1,
+        "Only the standalone `---` should render as a rule.\nScreen:\n{}",
+        screen,
+    );
+    assert!(
+        rule_rows[0].trim().chars().count() >= 40,
+        "The rule should span the compose width, got {:?}",
+        rule_rows[0].trim(),
+    );
+
+    let style_at = |needle: &str| {
+        let (col, row) = harness.find_text_on_screen(needle).unwrap();
+        harness.get_cell_style(col, row).unwrap()
     };
+    let intro = style_at("Intro paragraph");
+    let closing = style_at("Closing paragraph");
+    let title = style_at("Setext Title");
+    assert_eq!(
+        (intro.fg, intro.add_modifier.contains(Modifier::BOLD)),
+        (closing.fg, closing.add_modifier.contains(Modifier::BOLD)),
+        "The paragraph above the rule must not be styled as a heading"
+    );
+    assert!(
+        title.add_modifier.contains(Modifier::BOLD),
+        "Text underlined with `---` should be styled as a heading, got {:?}",
+        title,
+    );
+}
+
+/// Turning off link concealing from the command palette shows link syntax
+/// again while emphasis markers on the same line stay concealed, and the
+/// cursor does not move.
+#[test]
+fn test_compose_mode_toggle_conceal_links() {
+    use crate::common::harness::{copy_plugin, copy_plugin_lib};
+    use crate::common::tracing::init_tracing_from_env;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    init_tracing_from_env();
+
+    let md_content = "\
+# Conceal
+
+Some **bold** and [a link](https://example.com) here.
+";
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project");
+    std::fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    std::fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin(&plugins_dir, "markdown_compose");
+    copy_plugin_lib(&plugins_dir);
+
+    let md_path = project_root.join("conceal.md");
+    std::fs::write(&md_path, md_content).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(80, 30, Default::default(), project_root)
+            .unwrap();
+
+    harness.open_file(&md_path).unwrap();
+    harness.render().unwrap();
 
-    // Helper: open command palette and run a command by name
     let run_command = |h: &mut EditorTestHarness, name: &str| {
         h.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
             .unwrap();
@@ -3560,112 +4959,181 @@ fn test_compose_mode_set_width_via_command_palette() {
         h.type_text(name).unwrap();
         h.wait_for_screen_contains(name).unwrap();
         h.send_key(KeyCode::Enter, KeyModifiers::NONE).unwrap();
+        h.wait_for_prompt_closed().unwrap();
     };
 
-    // Helper: let compose mode settle after a change
-    let settle = |h: &mut EditorTestHarness| {
-        for _ in 0..10 {
-            h.process_async_and_render().unwrap();
-            std::thread::sleep(Duration::from_millis(50));
-            h.advance_time(Duration::from_millis(50));
-        }
-        h.render().unwrap();
-    };
-
-    // Enable compose mode
     run_command(&mut harness, "Toggle Compose");
-    harness.wait_for_prompt_closed().unwrap();
-    settle(&mut harness);
-
-    let screen_default = harness.screen_to_string();
-    let default_lengths = x_line_lengths(&screen_default);
-    println!("=== DEFAULT (no compose width set) ===");
-    println!("x-line lengths: {:?}", default_lengths);
-    println!("{}", screen_default);
 
-    // --- Set compose width to 40 by typing ---
-    run_command(&mut harness, "Set Compose Width");
-    harness.wait_for_prompt().unwrap();
-    harness.type_text("40").unwrap();
+    // Cursor stays on the heading, off the line under test
     harness
-        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
         .unwrap();
-    harness.wait_for_prompt_closed().unwrap();
-    settle(&mut harness);
+    harness
+        .wait_until_stable(|h| h.screen_to_string().contains("Some bold and a link here."))
+        .unwrap();
+    let cursor_before = harness.cursor_position();
 
-    let screen_40 = harness.screen_to_string();
-    let w40_lengths = x_line_lengths(&screen_40);
-    println!("=== AFTER SET WIDTH TO 40 ===");
-    println!("x-line lengths: {:?}", w40_lengths);
-    println!("{}", screen_40);
+    run_command(&mut harness, "Toggle Conceal Links");
+    harness
+        .wait_until_stable(|h| {
+            h.screen_to_string()
+                .contains("a link](https://example.com)")
+        })
+        .unwrap();
 
+    let screen = harness.screen_to_string();
+    let line = screen
+        .lines()
+        .find(|l| l.contains("Some "))
+        .unwrap_or_else(|| panic!("Paragraph line missing.\nScreen:\n{}", screen));
+    assert!(
+        line.contains("[a link](https://example.com)"),
+        "Link syntax should be visible once link conceal is off, got {:?}",
+        line.trim_end(),
+    );
+    assert!(
+        line.contains("Some bold and") && !line.contains("**"),
+        "Emphasis markers should still be concealed, got {:?}",
+        line.trim_end(),
+    );
     assert_eq!(
-        w40_lengths[0], 40,
-        "With compose width 40, first x-line should be 40 chars, got {:?}",
-        w40_lengths,
+        harness.cursor_position(),
+        cursor_before,
+        "Toggling a conceal setting must not move the cursor"
     );
+}
 
-    // --- Open prompt again and just press Enter (should keep width=40) ---
-    run_command(&mut harness, "Set Compose Width");
+/// Block quote markers render as one gutter bar per nesting level, with `>`
+/// concealed off the cursor line. A lazy continuation line (no `>` of its
+/// own) gets the bars of the quote it continues.
+#[test]
+fn test_compose_mode_nested_blockquote_bars() {
+    use crate::common::harness::{copy_plugin, copy_plugin_lib};
+    use crate::common::tracing::init_tracing_from_env;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    init_tracing_from_env();
+
+    let md_content = "\
+# Quotes
+
+> Outer quote line
+> > Inner quote line
+lazy continuation line
+
+After the quote.
+";
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project");
+    std::fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    std::fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin(&plugins_dir, "markdown_compose");
+    copy_plugin_lib(&plugins_dir);
+
+    let md_path = project_root.join("quotes.md");
+    std::fs::write(&md_path, md_content).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(80, 30, Default::default(), project_root)
+            .unwrap();
+
+    harness.open_file(&md_path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
     harness.wait_for_prompt().unwrap();
-    // Don't change anything, just confirm the pre-filled value
+    harness.type_text("Toggle Compose").unwrap();
+    harness.wait_for_screen_contains("Toggle Compose").unwrap();
     harness
         .send_key(KeyCode::Enter, KeyModifiers::NONE)
         .unwrap();
     harness.wait_for_prompt_closed().unwrap();
-    settle(&mut harness);
 
-    let screen_reconfirm = harness.screen_to_string();
-    let reconfirm_lengths = x_line_lengths(&screen_reconfirm);
-    println!("=== AFTER RE-CONFIRMING (should still be 40) ===");
-    println!("x-line lengths: {:?}", reconfirm_lengths);
-    println!("{}", screen_reconfirm);
-
-    assert_eq!(
-        reconfirm_lengths[0], 40,
-        "After re-confirming without changes, width should still be 40, got {:?}",
-        reconfirm_lengths,
-    );
-
-    // --- Set compose width to 60 by typing ---
-    run_command(&mut harness, "Set Compose Width");
-    harness.wait_for_prompt().unwrap();
-    harness.type_text("60").unwrap();
+    // Cursor on the heading, away from the quote
     harness
-        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_until_stable(|h| {
+            h.screen_to_string()
+                .contains("\u{258C} \u{258C} Inner quote line")
+        })
         .unwrap();
-    harness.wait_for_prompt_closed().unwrap();
-    settle(&mut harness);
 
-    let screen_60 = harness.screen_to_string();
-    let w60_lengths = x_line_lengths(&screen_60);
-    println!("=== AFTER SET WIDTH TO 60 ===");
-    println!("x-line lengths: {:?}", w60_lengths);
-    println!("{}", screen_60);
+    let screen = harness.screen_to_string();
+    let row_with = |needle: &str| -> String {
+        screen
+            .lines()
+            .find(|l| l.contains(needle))
+            .unwrap_or_else(|| panic!("{:?} missing.\nScreen:\n{}", needle, screen))
+            .to_string()
+    };
+    let bars = |row: &str| row.matches('\u{258C}').count();
 
+    let outer = row_with("Outer quote line");
+    let inner = row_with("Inner quote line");
+    let lazy = row_with("lazy continuation line");
     assert_eq!(
-        w60_lengths[0], 60,
-        "With compose width 60, first x-line should be 60 chars, got {:?}",
-        w60_lengths,
+        bars(&outer),
+        1,
+        "Outer line should have one bar: {:?}",
+        outer
+    );
+    assert_eq!(
+        bars(&inner),
+        2,
+        "Inner line should have two bars: {:?}",
+        inner
     );
+    assert_eq!(
+        bars(&lazy),
+        2,
+        "Lazy line should continue the inner quote: {:?}",
+        lazy
+    );
+    assert_eq!(bars(&row_with("After the quote.")), 0);
+    for row in [&outer, &inner] {
+        assert!(!row.contains('>'), "`>` should be concealed, got {:?}", row);
+    }
+
+    // The bars are colored unlike the quote text
+    let (col, row) = harness.find_text_on_screen("\u{258C} Outer").unwrap();
+    let bar_style = harness.get_cell_style(col, row).unwrap();
+    let (col, row) = harness.find_text_on_screen("Outer quote").unwrap();
+    let text_style = harness.get_cell_style(col, row).unwrap();
+    assert_ne!(bar_style.fg, text_style.fg, "Quote bars should be colored");
+
+    // Moving onto the inner line exposes its markers
+    harness
+        .send_key_repeat(KeyCode::Down, KeyModifiers::NONE, 3)
+        .unwrap();
+    harness
+        .wait_until_stable(|h| h.screen_to_string().contains("> > Inner quote line"))
+        .unwrap();
 }
 
-/// Test that compose width can be changed after restoring a workspace session.
-///
-/// Reproduces a bug where a persisted compose_width (e.g. from a previous session)
-/// is stuck and cannot be changed via the Set Compose Width command, because the
-/// plugin's config.composeWidth is out of sync with the restored view state.
+/// Test that Home/End on a soft-wrapped compose paragraph work on the visual
+/// row first, and a second press reaches the paragraph (logical line) bounds.
 #[test]
-fn test_compose_mode_width_survives_session_restore() {
+fn test_compose_mode_visual_home_end() {
     use crate::common::harness::{copy_plugin, copy_plugin_lib};
     use crate::common::tracing::init_tracing_from_env;
     use crossterm::event::{KeyCode, KeyModifiers};
-    use std::time::Duration;
 
     init_tracing_from_env();
 
-    let long_line = "x".repeat(200);
-    let md_content = format!("# Session Test\n\n{}\n", long_line);
+    let paragraph = "Compose mode wraps this long paragraph across several screen rows \
+                     so that Home and End have a visual row to work on before they reach \
+                     the logical line bounds, which sit far away at the paragraph start and \
+                     the paragraph end.";
+    let md_content = format!("# Wrapped\n\n{}\n\nAfter.\n", paragraph);
+    let para_start = md_content.find(paragraph).unwrap();
+    let para_end = para_start + paragraph.len();
 
     let temp_dir = tempfile::TempDir::new().unwrap();
     let project_root = temp_dir.path().join("project");
@@ -3676,169 +5144,156 @@ fn test_compose_mode_width_survives_session_restore() {
     copy_plugin(&plugins_dir, "markdown_compose");
     copy_plugin_lib(&plugins_dir);
 
-    let md_path = project_root.join("session_test.md");
+    let md_path = project_root.join("wrapped.md");
     std::fs::write(&md_path, &md_content).unwrap();
 
-    // A second file that is NOT in compose mode
-    let other_path = project_root.join("other.txt");
-    std::fs::write(
-        &other_path,
-        "This is a plain text file, not in compose mode.\n",
-    )
-    .unwrap();
-
-    // Count consecutive x characters in each screen line that contains x's.
-    // Handles both compose mode (no line numbers, just x's) and source mode
-    // (line numbers like "3 │ xxxx").
-    let x_line_lengths = |screen: &str| -> Vec<usize> {
-        screen
-            .lines()
-            .filter_map(|l| {
-                // Find the longest run of consecutive x's in the line
-                let max_run = l
-                    .chars()
-                    .fold((0usize, 0usize), |(max, cur), c| {
-                        if c == 'x' {
-                            (max.max(cur + 1), cur + 1)
-                        } else {
-                            (max, 0)
-                        }
-                    })
-                    .0;
-                if max_run >= 10 {
-                    Some(max_run)
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<_>>()
-    };
-
-    let run_command = |h: &mut EditorTestHarness, name: &str| {
-        h.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(80, 30, Default::default(), project_root)
             .unwrap();
-        h.wait_for_prompt().unwrap();
-        h.type_text(name).unwrap();
-        h.wait_for_screen_contains(name).unwrap();
-        h.send_key(KeyCode::Enter, KeyModifiers::NONE).unwrap();
-    };
 
-    let settle = |h: &mut EditorTestHarness| {
-        for _ in 0..10 {
-            h.process_async_and_render().unwrap();
-            std::thread::sleep(Duration::from_millis(50));
-            h.advance_time(Duration::from_millis(50));
-        }
-        h.render().unwrap();
-    };
+    harness.open_file(&md_path).unwrap();
+    harness.render().unwrap();
 
-    // --- Session 1: open both files, enable compose on md, set width to 40, save ---
-    {
-        let mut harness = EditorTestHarness::with_config_and_working_dir(
-            80,
-            25,
-            Default::default(),
-            project_root.clone(),
-        )
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("Toggle Compose").unwrap();
+    harness.wait_for_screen_contains("Toggle Compose").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
         .unwrap();
+    harness.wait_for_prompt_closed().unwrap();
 
-        harness.open_file(&other_path).unwrap();
-        harness.render().unwrap();
-        harness.open_file(&md_path).unwrap();
-        harness.render().unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_until_stable(|h| h.screen_to_string().contains("the paragraph end."))
+        .unwrap();
 
-        run_command(&mut harness, "Toggle Compose");
-        harness.wait_for_prompt_closed().unwrap();
-        settle(&mut harness);
+    // Into the middle of the paragraph's second visual row: Down keeps the
+    // column at 0, so the first position past the paragraph start is there.
+    for _ in 0..10 {
+        if harness.cursor_position() > para_start {
+            break;
+        }
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness
+        .send_key_repeat(KeyCode::Right, KeyModifiers::NONE, 3)
+        .unwrap();
+    harness.render().unwrap();
+    let (_, row_y) = harness.screen_cursor_position();
+    let mid = harness.cursor_position();
+    assert!(
+        mid > para_start && mid < para_end,
+        "Cursor should be inside the paragraph, got {}",
+        mid
+    );
 
-        run_command(&mut harness, "Set Compose Width");
-        harness.wait_for_prompt().unwrap();
-        harness.type_text("40").unwrap();
-        harness
-            .send_key(KeyCode::Enter, KeyModifiers::NONE)
-            .unwrap();
-        harness.wait_for_prompt_closed().unwrap();
-        settle(&mut harness);
+    // First Home: start of the visual row, not the paragraph start
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    let row_start = harness.cursor_position();
+    assert!(
+        row_start > para_start && row_start < mid,
+        "First Home should stop at the visual row start, got {} (paragraph starts at {})",
+        row_start,
+        para_start
+    );
+    assert_eq!(
+        harness.screen_cursor_position().1,
+        row_y,
+        "First Home should stay on the same screen row"
+    );
 
-        let screen = harness.screen_to_string();
-        let lengths = x_line_lengths(&screen);
-        println!("=== SESSION 1: width=40 ===");
-        println!("x-line lengths: {:?}", lengths);
-        println!("{}", screen);
-        assert_eq!(
-            lengths[0], 40,
-            "Session 1: width should be 40, got {:?}",
-            lengths,
-        );
+    // Second Home: paragraph start
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.cursor_position(),
+        para_start,
+        "Second Home should reach the paragraph start"
+    );
 
-        harness.editor_mut().save_workspace().unwrap();
-    }
+    // First End: end of the first visual row
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    let row_end = harness.cursor_position();
+    assert!(
+        row_end > para_start && row_end < para_end,
+        "First End should stop at the visual row end, got {} (paragraph ends at {})",
+        row_end,
+        para_end
+    );
 
-    // --- Session 2: restore, verify both buffers, then change compose width ---
-    {
-        let mut harness = EditorTestHarness::with_config_and_working_dir(
-            80,
-            25,
-            Default::default(),
-            project_root.clone(),
-        )
-        .unwrap();
+    // Second End: paragraph end
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.cursor_position(),
+        para_end,
+        "Second End should reach the paragraph end"
+    );
+}
 
-        harness.editor_mut().try_restore_workspace().unwrap();
-        settle(&mut harness);
+/// Go to Line in compose mode centers the target through the view
+/// transform: wrapped paragraphs take several rows, so counting source lines
+/// alone would leave the cursor off screen.
+#[test]
+fn test_compose_mode_goto_line_centers_target() {
+    use crate::common::tracing::init_tracing_from_env;
+    use crossterm::event::{KeyCode, KeyModifiers};
 
-        // The md file should be active (it was last opened) and in compose mode
-        let screen_restored = harness.screen_to_string();
-        let restored_lengths = x_line_lengths(&screen_restored);
-        println!("=== SESSION 2: md file after restore ===");
-        println!("x-line lengths: {:?}", restored_lengths);
-        println!("{}", screen_restored);
-        assert!(
-            !restored_lengths.is_empty(),
-            "After restore, should see x-lines on screen.\nScreen:\n{}",
-            screen_restored,
-        );
-        assert_eq!(
-            restored_lengths[0], 40,
-            "After restore, compose width should still be 40, got {:?}.\nScreen:\n{}",
-            restored_lengths, screen_restored,
-        );
+    init_tracing_from_env();
 
-        // Switch to the other (non-compose) buffer and verify it's normal
-        harness.open_file(&other_path).unwrap();
-        settle(&mut harness);
-        let screen_other = harness.screen_to_string();
-        println!("=== SESSION 2: other.txt (non-compose) ===");
-        println!("{}", screen_other);
-        assert!(
-            screen_other.contains("plain text file"),
-            "other.txt should show its content normally.\nScreen:\n{}",
-            screen_other,
-        );
+    let paragraphs: Vec<String> = (1..=40)
+        .map(|i| {
+            format!(
+                "Para{i:02} starts here and keeps going with enough words that compose \
+                 mode has to wrap it across a few screen rows before the paragraph \
+                 finally comes to an end."
+            )
+        })
+        .collect();
+    let md_content = paragraphs.join("\n\n") + "\n";
+    let (_temp_dir, _md_path, mut harness) = compose_paste_harness(&md_content);
+    harness
+        .wait_until_stable(|h| h.screen_to_string().contains("Para01"))
+        .unwrap();
 
-        // Switch back to the md file
-        harness.open_file(&md_path).unwrap();
-        settle(&mut harness);
+    // Paragraph 30 is on line 59 (blank lines between paragraphs)
+    harness
+        .send_key(KeyCode::Char('g'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("59").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_until_stable(|h| h.screen_to_string().contains("Para30"))
+        .unwrap();
 
-        // Now try to change width to 60
-        run_command(&mut harness, "Set Compose Width");
-        harness.wait_for_prompt().unwrap();
-        harness.type_text("60").unwrap();
+    assert_eq!(
+        harness.cursor_position(),
+        md_content.find("Para30").unwrap()
+    );
+    let (_, cursor_row) = harness.screen_cursor_position();
+    assert!(
         harness
-            .send_key(KeyCode::Enter, KeyModifiers::NONE)
-            .unwrap();
-        harness.wait_for_prompt_closed().unwrap();
-        settle(&mut harness);
-
-        let screen_60 = harness.screen_to_string();
-        let w60_lengths = x_line_lengths(&screen_60);
-        println!("=== SESSION 2: after changing to 60 ===");
-        println!("x-line lengths: {:?}", w60_lengths);
-        println!("{}", screen_60);
-        assert_eq!(
-            w60_lengths[0], 60,
-            "After changing width to 60 in restored session, got {:?}",
-            w60_lengths,
-        );
-    }
+            .screen_row_text(cursor_row)
+            .contains("Para30 starts here"),
+        "Cursor row should show the target paragraph:\n{}",
+        harness.screen_to_string()
+    );
+    let (row_start, row_end) = harness.content_area_rows();
+    let middle = (row_start + row_end) / 2;
+    assert!(
+        (cursor_row as usize).abs_diff(middle) <= 1,
+        "Target row {} should be centered (middle row {}):\n{}",
+        cursor_row,
+        middle,
+        harness.screen_to_string()
+    );
 }
@@ -308,11 +308,10 @@ fn test_mouse_click_undo_menu_item() {
     harness.mouse_click(10, 2).unwrap();
     harness.render().unwrap();
 
-    // Undo should have reversed the last text insertion
+    // Undo should have reversed the typed text, which is one undo step
     // Menu should be closed
     harness.assert_screen_not_contains("Undo");
-    // The last character should be undone (type_text inserts char by char)
-    harness.assert_buffer_content("Hello Worl");
+    harness.assert_buffer_content("");
 }
 
 /// Test that View menu File Explorer checkbox syncs with actual file explorer state
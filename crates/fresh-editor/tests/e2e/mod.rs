@@ -1,3 +1,4 @@
+pub mod align;
 pub mod altgr_shift;
 pub mod ansi_cursor;
 pub mod auto_indent;
@@ -6,13 +7,17 @@ pub mod basic;
 pub mod binary_file;
 pub mod block_selection;
 pub mod blog_showcases;
+pub mod bracket_and_indent_guides;
 pub mod buffer_lifecycle;
 pub mod buffer_settings_commands;
 pub mod case_conversion;
+pub mod clipboard_history;
 pub mod command_palette;
 pub mod crash_repro;
 pub mod crlf_rendering;
 pub mod ctrl_end_wrapped;
+pub mod diagnostics_providers;
+pub mod diff_view;
 pub mod document_model;
 pub mod duplicate_line;
 pub mod emacs_actions;
@@ -21,13 +26,16 @@ pub mod explorer_menu;
 pub mod file_browser;
 pub mod file_explorer;
 pub mod file_permissions;
+pub mod goto_line;
 pub mod horizontal_scrollbar;
 pub mod indent_dedent;
+pub mod join_lines;
 pub mod keybinding_editor;
 pub mod language_features_e2e;
 pub mod large_file_inplace_write_bug;
 pub mod large_file_mode;
 pub mod lifecycle;
+pub mod line_bookmarks;
 pub mod line_wrap_scroll_bugs;
 pub mod line_wrapping;
 pub mod live_grep;
@@ -59,7 +67,10 @@ pub mod position_history_debug;
 pub mod position_history_truncate_debug;
 pub mod prompt;
 pub mod prompt_editing;
+#[cfg(unix)]
+pub mod read_only_files;
 pub mod recovery;
+pub mod registers;
 pub mod remote_fs_test;
 pub mod rendering;
 pub mod save_as_language_detection;
@@ -72,6 +83,7 @@ pub mod settings;
 pub mod settings_config_issue_806;
 pub mod settings_paste;
 pub mod shell_command;
+pub mod show_whitespace;
 pub mod side_by_side_diff_scroll;
 pub mod slow_filesystem;
 pub mod smart_editing;
@@ -108,4 +120,5 @@ pub mod vi_mode;
 pub mod virtual_lines;
 pub mod visual_regression;
 pub mod warning_indicators;
+pub mod whitespace_warnings;
 pub mod workspace;
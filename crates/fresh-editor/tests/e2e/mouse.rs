@@ -2063,3 +2063,218 @@ fn test_hover_popup_position_with_file_explorer() {
         screen
     );
 }
+
+/// Open `content` from a temp file so later edits show up as changes
+fn harness_with_saved_file(
+    content: &str,
+    line_wrap: bool,
+) -> (EditorTestHarness, tempfile::TempDir) {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("markers.txt");
+    fs::write(&file_path, content).unwrap();
+
+    let mut config = fresh::config::Config::default();
+    config.editor.line_wrap = line_wrap;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    (harness, temp_dir)
+}
+
+/// Insert "X" at the start of a 1-based line
+fn mark_line(harness: &mut EditorTestHarness, line: usize) {
+    harness
+        .send_key(KeyCode::Char('g'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(&line.to_string()).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("X").unwrap();
+}
+
+/// Scrollbar rows (relative to the content area) showing `symbol` in column 79
+fn scrollbar_rows_with(harness: &EditorTestHarness, symbol: &str) -> Vec<usize> {
+    let (first_row, last_row) = harness.content_area_rows();
+    (first_row..=last_row)
+        .filter(|&row| harness.get_cell(79, row as u16).as_deref() == Some(symbol))
+        .map(|row| row - first_row)
+        .collect()
+}
+
+/// Changed lines and search matches are marked on the scrollbar at rows
+/// proportional to their position, and clicking a marker scrolls there
+#[test]
+fn test_scrollbar_change_markers() {
+    let content: String = (1..=200).map(|i| format!("line {i}\n")).collect();
+    let (mut harness, _temp_dir) = harness_with_saved_file(&content, false);
+    assert!(scrollbar_rows_with(&harness, "▐").is_empty());
+
+    for line in [21, 101, 181] {
+        mark_line(&mut harness, line);
+    }
+    harness.render().unwrap();
+
+    let (first_row, last_row) = harness.content_area_rows();
+    let height = last_row - first_row + 1;
+    let expected: Vec<usize> = [20, 100, 180]
+        .iter()
+        .map(|line| line * height / 200)
+        .collect();
+    assert_eq!(scrollbar_rows_with(&harness, "▐"), expected);
+
+    // Search matches take over the rows they share with changes
+    harness
+        .send_key(KeyCode::Char('f'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Xline").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(scrollbar_rows_with(&harness, "▬"), expected);
+    assert!(scrollbar_rows_with(&harness, "▐").is_empty());
+
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.top_line_number(), 0);
+
+    // Clicking the middle marker brings line 101 into view
+    harness
+        .mouse_click(79, (first_row + expected[1]) as u16)
+        .unwrap();
+    harness.render().unwrap();
+    let top_line = harness.top_line_number();
+    assert!(
+        top_line < 100 && 100 < top_line + height,
+        "line 101 should be visible, top line is {top_line}"
+    );
+    harness.assert_screen_contains("Xline 101");
+}
+
+/// With line wrapping, markers are placed by visual row, so long wrapped
+/// lines above a change push its marker further down
+#[test]
+fn test_scrollbar_change_markers_with_wrapping() {
+    // 20 lines that wrap onto two rows each, then 20 short lines
+    let long_line = "word ".repeat(20);
+    let content: Vec<String> = (0..20)
+        .map(|_| long_line.clone())
+        .chain((0..20).map(|i| format!("short {i}")))
+        .collect();
+    let content = content.join("\n");
+    let (mut harness, _temp_dir) = harness_with_saved_file(&content, true);
+
+    mark_line(&mut harness, 21);
+    harness.render().unwrap();
+
+    // Line 21 starts at visual row 40 of 60
+    let (first_row, last_row) = harness.content_area_rows();
+    let height = last_row - first_row + 1;
+    assert_eq!(scrollbar_rows_with(&harness, "▐"), vec![40 * height / 60]);
+}
+
+/// Dragging a selection past the bottom edge keeps scrolling the view while
+/// the button is held, and the selection follows the rows scrolled in
+#[test]
+fn test_drag_select_autoscrolls_past_bottom_edge() {
+    use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let content: String = (0..200).map(|i| format!("line {i:03}\n")).collect();
+    let _fixture = harness.load_buffer_from_text(&content).unwrap();
+    harness.render().unwrap();
+
+    let (first_row, last_row) = harness.content_area_rows();
+    let height = last_row - first_row + 1;
+    let mouse = |kind, row: usize| MouseEvent {
+        kind,
+        column: 13,
+        row: row as u16,
+        modifiers: KeyModifiers::NONE,
+    };
+
+    // Press on column 5 of line 2, then drag one row below the content area
+    harness
+        .send_mouse(mouse(
+            MouseEventKind::Down(MouseButton::Left),
+            first_row + 2,
+        ))
+        .unwrap();
+    harness
+        .send_mouse(mouse(MouseEventKind::Drag(MouseButton::Left), last_row + 1))
+        .unwrap();
+    harness.render().unwrap();
+
+    // Holding the mouse there scrolls one row per step
+    let steps = 10;
+    for _ in 0..steps {
+        harness.advance_time(std::time::Duration::from_millis(100));
+        assert!(harness.editor_mut().check_drag_autoscroll_timer());
+        harness.render().unwrap();
+    }
+    harness
+        .send_mouse(mouse(MouseEventKind::Up(MouseButton::Left), last_row + 1))
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.top_line_number(), steps);
+
+    // The selection ends on the bottom row at the dragged column
+    let line_len = "line 000\n".len();
+    let end_line = steps + height - 1;
+    assert_eq!(
+        harness.get_selection_range(),
+        Some(2 * line_len + 5..end_line * line_len + 5)
+    );
+
+    // Autoscrolling stops once the button is released
+    harness.advance_time(std::time::Duration::from_millis(100));
+    assert!(!harness.editor_mut().check_drag_autoscroll_timer());
+}
+
+/// Middle-click pastes the last mouse selection at the clicked position,
+/// leaving the cursor, the selection and the clipboard alone
+#[test]
+fn test_middle_click_pastes_primary_selection() {
+    use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    // Keep the system clipboard and primary selection out of the test
+    harness.editor_mut().set_clipboard_for_test(String::new());
+    let _fixture = harness
+        .load_buffer_from_text("héllo wörld\n日本語 text\n")
+        .unwrap();
+    harness.render().unwrap();
+    let (first_row, _) = harness.content_area_rows();
+    let first_row = first_row as u16;
+
+    // Select "wörld" (after the 8-column gutter)
+    harness.mouse_drag(14, first_row, 19, first_row).unwrap();
+    assert_eq!(harness.get_selected_text(), "wörld");
+
+    // Middle-click right after the three double-width characters on line 2
+    for kind in [
+        MouseEventKind::Down(MouseButton::Middle),
+        MouseEventKind::Up(MouseButton::Middle),
+    ] {
+        harness
+            .send_mouse(MouseEvent {
+                kind,
+                column: 14,
+                row: first_row + 1,
+                modifiers: KeyModifiers::NONE,
+            })
+            .unwrap();
+    }
+    harness.render().unwrap();
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "héllo wörld\n日本語wörld text\n"
+    );
+    assert_eq!(harness.get_selection_range(), Some(7..13));
+    assert_eq!(harness.cursor_position(), 13);
+    assert_eq!(harness.editor().clipboard_content_for_test(), "");
+}
@@ -130,6 +130,41 @@ fn test_move_line_up_down_shortcuts() {
     harness.assert_buffer_content("A\nB\nC");
 }
 
+#[test]
+fn test_move_unicode_line_up_keeps_column() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.enable_shadow_validation();
+
+    harness.type_text("αβγ one\n日本語 two\nthird").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    // Cursor after "日本"
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(harness.cursor_position(), "αβγ one\n日本".len());
+
+    harness.send_key(KeyCode::Up, KeyModifiers::ALT).unwrap();
+    harness.assert_buffer_content("日本語 two\nαβγ one\nthird");
+    assert_eq!(harness.cursor_position(), "日本".len());
+
+    // Already on the first line: nothing changes
+    harness.send_key(KeyCode::Up, KeyModifiers::ALT).unwrap();
+    harness.assert_buffer_content("日本語 two\nαβγ one\nthird");
+    assert_eq!(harness.cursor_position(), "日本".len());
+
+    // A single undo restores the original order
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("αβγ one\n日本語 two\nthird");
+}
+
 /// Test basic editing operations: insert, modify, delete, newline
 /// This test verifies both buffer state and rendered screen output
 #[test]
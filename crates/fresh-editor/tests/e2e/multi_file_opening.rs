@@ -339,3 +339,32 @@ fn test_open_files_from_different_directories() {
     harness.render().unwrap();
     harness.assert_buffer_content("fn main() {}");
 }
+
+/// Test that files queued from the command line (`file:line:col`) open at
+/// the requested 1-based position, clamping a column past the line's end
+#[test]
+fn test_queued_file_opens_at_line_and_column() {
+    let temp_dir = TempDir::new().unwrap();
+    let file1 = temp_dir.path().join("file1.txt");
+    let file2 = temp_dir.path().join("file2.txt");
+    std::fs::write(&file1, "first\nsecond\nthird\n").unwrap();
+    std::fs::write(&file2, "first\nsecond\nthird\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness
+        .editor_mut()
+        .queue_file_open(file1.clone(), Some(3), Some(2));
+    assert!(harness.editor_mut().process_pending_file_opens());
+    harness.render().unwrap();
+    // Line 3, column 2 is the "h" of "third"
+    assert_eq!(harness.cursor_position(), "first\nsecond\n".len() + 1);
+
+    harness
+        .editor_mut()
+        .queue_file_open(file2.clone(), Some(2), Some(50));
+    assert!(harness.editor_mut().process_pending_file_opens());
+    harness.render().unwrap();
+    // The column clamps to the end of "second", not past its newline
+    assert_eq!(harness.cursor_position(), "first\nsecond".len());
+}
@@ -189,6 +189,39 @@ fn test_add_cursor_next_match() {
     assert_eq!(cursors.iter().count(), 3);
 }
 
+/// Test Ctrl+D from a bare cursor: selects the word, adds cursors on the next
+/// whole-word matches (skipping `foobar`), and typing edits every occurrence
+#[test]
+fn test_add_cursor_next_match_edits_all_occurrences() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.type_text("foo bar foo baz foobar foo").unwrap();
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+
+    // First Ctrl+D selects the word under the cursor
+    harness
+        .send_key(KeyCode::Char('d'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.get_selected_text(), "foo");
+    assert_eq!(harness.editor().active_cursors().iter().count(), 1);
+
+    // Two more add cursors on the second and last "foo"
+    harness
+        .send_key(KeyCode::Char('d'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('d'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.editor().active_cursors().iter().count(), 3);
+
+    harness.type_text("qux").unwrap();
+    harness.assert_buffer_content("qux bar qux baz foobar qux");
+}
+
 /// Test adding cursor above with Ctrl+Alt+Up
 #[test]
 fn test_add_cursor_above() {
@@ -42,6 +42,7 @@ fn test_format_on_save() {
             grammar: "plaintext".to_string(),
             comment_prefix: None,
             auto_indent: false,
+            auto_close: true,
             highlighter: Default::default(),
             textmate_grammar: None,
             show_whitespace_tabs: true,
@@ -105,6 +106,7 @@ fn test_on_save_linter_style() {
             grammar: "plaintext".to_string(),
             comment_prefix: None,
             auto_indent: false,
+            auto_close: true,
             highlighter: Default::default(),
             textmate_grammar: None,
             show_whitespace_tabs: true,
@@ -168,6 +170,7 @@ fn test_on_save_action_failure() {
             grammar: "plaintext".to_string(),
             comment_prefix: None,
             auto_indent: false,
+            auto_close: true,
             highlighter: Default::default(),
             textmate_grammar: None,
             show_whitespace_tabs: true,
@@ -240,6 +243,7 @@ fn test_on_save_file_placeholder() {
             grammar: "plaintext".to_string(),
             comment_prefix: None,
             auto_indent: false,
+            auto_close: true,
             highlighter: Default::default(),
             textmate_grammar: None,
             show_whitespace_tabs: true,
@@ -306,6 +310,7 @@ fn test_formatter_stdin_mode() {
             grammar: "plaintext".to_string(),
             comment_prefix: None,
             auto_indent: false,
+            auto_close: true,
             highlighter: Default::default(),
             textmate_grammar: None,
             show_whitespace_tabs: true,
@@ -379,6 +384,7 @@ fn test_on_save_stops_on_failure() {
             grammar: "plaintext".to_string(),
             comment_prefix: None,
             auto_indent: false,
+            auto_close: true,
             highlighter: Default::default(),
             textmate_grammar: None,
             show_whitespace_tabs: true,
@@ -473,6 +479,7 @@ fn test_formatter_not_found_shows_message() {
             grammar: "plaintext".to_string(),
             comment_prefix: None,
             auto_indent: false,
+            auto_close: true,
             highlighter: Default::default(),
             textmate_grammar: None,
             show_whitespace_tabs: true,
@@ -647,3 +654,197 @@ fn test_whitespace_cleanup_no_change_needed() {
     // Content should remain the same
     harness.assert_buffer_content("line 1\nline 2\n");
 }
+
+/// Test trimming on save keeps the cursor on the same character and keeps CRLF
+#[test]
+fn test_trim_trailing_whitespace_keeps_cursor() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let file_path = project_dir.join("test.rs");
+    std::fs::write(&file_path, "fn a() {   \r\n    b();\t \r\n}\r\n").unwrap();
+
+    let mut config = Config::default();
+    config.editor.trim_trailing_whitespace_on_save = true;
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 24, config, project_dir).unwrap();
+
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    // Put the cursor on `b` in the second line
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    for _ in 0..4 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::NONE)
+            .unwrap();
+    }
+
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+
+    let expected = "fn a() {\r\n    b();\r\n}\r\n";
+    harness.assert_buffer_content(expected);
+    assert_eq!(std::fs::read_to_string(&file_path).unwrap(), expected);
+
+    // The cursor moved back by the whitespace removed from the first line
+    let cursor = harness.cursor_position();
+    assert!(
+        expected[cursor..].starts_with("b();"),
+        "Cursor should still be on `b`, got offset {}",
+        cursor
+    );
+    assert!(!harness.editor().active_state().buffer.is_modified());
+}
+
+/// Test the per-buffer toggle enables trimming when the config leaves it off
+#[test]
+fn test_toggle_trim_whitespace_on_save() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let file_path = project_dir.join("test.txt");
+    std::fs::write(&file_path, "line 1  \nline 2\n").unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 24, Config::default(), project_dir)
+            .unwrap();
+
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Toggle Trim Whitespace on Save").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("Trailing whitespace will be trimmed on save")
+    );
+
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_buffer_content("line 1\nline 2\n");
+    assert_eq!(
+        std::fs::read_to_string(&file_path).unwrap(),
+        "line 1\nline 2\n"
+    );
+}
+
+/// Test ensure_final_newline_on_save never adds a second newline
+#[test]
+fn test_ensure_final_newline_is_idempotent() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let file_path = project_dir.join("test.txt");
+    std::fs::write(&file_path, "line 1\r\nline 2").unwrap();
+
+    let mut config = Config::default();
+    config.editor.ensure_final_newline_on_save = true;
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 24, config, project_dir).unwrap();
+
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    // Save twice: the first save adds the newline in the file's own format
+    for _ in 0..2 {
+        harness
+            .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.render().unwrap();
+    }
+
+    harness.assert_buffer_content("line 1\r\nline 2\r\n");
+    assert_eq!(
+        std::fs::read_to_string(&file_path).unwrap(),
+        "line 1\r\nline 2\r\n"
+    );
+}
+
+/// Test ensure_final_newline_on_save leaves empty files empty
+#[test]
+fn test_ensure_final_newline_skips_empty_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let file_path = project_dir.join("empty.txt");
+    std::fs::write(&file_path, "").unwrap();
+
+    let mut config = Config::default();
+    config.editor.ensure_final_newline_on_save = true;
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 24, config, project_dir).unwrap();
+
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    harness.type_text("x").unwrap();
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_buffer_content("");
+    assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "");
+}
+
+/// Test the per-buffer toggle turns off the final newline set in the config
+#[test]
+fn test_toggle_final_newline_on_save() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let file_path = project_dir.join("test.txt");
+    std::fs::write(&file_path, "no newline").unwrap();
+
+    let mut config = Config::default();
+    config.editor.ensure_final_newline_on_save = true;
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 24, config, project_dir).unwrap();
+
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .type_text("Toggle Trailing Newline on Save")
+        .unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("No final newline will be added on save");
+
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_buffer_content("no newline");
+    assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "no newline");
+}
@@ -839,3 +839,97 @@ fn test_paste_crlf_into_prompt() {
     // Prompt should contain the text (newlines may be shown differently in prompt)
     harness.assert_screen_contains("line1");
 }
+
+/// Test that a bracketed paste from a client terminal is inserted verbatim
+/// as one undo step, instead of typed key by key with auto-indent
+#[test]
+fn test_bracketed_paste_from_client_is_verbatim() {
+    use crossterm::event::Event;
+    use fresh::server::InputParser;
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    // Typed key by key, each Enter would auto-indent on top of the pasted indent
+    let pasted = "    if x {\n        y();\n    }\n";
+    let input = format!("\x1b[200~{pasted}\x1b[201~");
+    let events = InputParser::new().parse(input.as_bytes());
+    assert_eq!(events.len(), 1, "paste should arrive as one event");
+    for event in events {
+        match event {
+            Event::Paste(text) => harness.editor_mut().paste_text(text),
+            other => panic!("unexpected event {other:?}"),
+        }
+    }
+    harness.render().unwrap();
+    harness.assert_buffer_content(pasted);
+
+    // One undo removes the whole paste
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_buffer_content("");
+}
+
+/// Test that a 10MB paste is applied quickly, in one undo step
+#[test]
+fn test_large_paste_is_one_undo_step() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("before  after").unwrap();
+    for _ in 0.."after".len() + 1 {
+        harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    }
+
+    let line = "pasted line with some text to fill it up\n";
+    let pasted = line.repeat(10 * 1024 * 1024 / line.len());
+    let start = std::time::Instant::now();
+    harness.editor_mut().paste_text(pasted.clone());
+    harness.render().unwrap();
+    let elapsed = start.elapsed();
+    assert!(
+        elapsed < std::time::Duration::from_secs(30),
+        "10MB paste took {elapsed:?}"
+    );
+
+    let content = harness.get_buffer_content().unwrap();
+    assert!(
+        content == format!("before {pasted} after"),
+        "pasted content is wrong ({} bytes)",
+        content.len()
+    );
+    assert!(harness.editor().active_state().buffer.is_modified());
+
+    // One undo removes the whole paste
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("before  after");
+}
+
+/// Test that a large paste in compose mode goes in verbatim instead of being
+/// converted row by row into a markdown table
+#[test]
+fn test_large_paste_skips_compose_transforms() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("notes.md");
+    std::fs::write(&path, "# Data\n\n").unwrap();
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&path).unwrap();
+    harness.editor_mut().handle_toggle_compose_mode();
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+
+    // Tab-separated rows on an empty line would become a pipe table
+    let row = "name\tquantity\tprice\n";
+    let pasted = row.repeat(2 * 1024 * 1024 / row.len());
+    harness.editor_mut().paste_text(pasted.clone());
+    harness.render().unwrap();
+
+    let content = harness.get_buffer_content().unwrap();
+    assert!(
+        content == format!("# Data\n\n{pasted}"),
+        "pasted content was transformed ({} bytes)",
+        content.len()
+    );
+}
@@ -0,0 +1,64 @@
+//! Document outline panel tests
+
+use crate::common::harness::{copy_plugin, copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// The outline lists a code file's definitions in order, and selecting one
+/// moves the cursor to it
+#[test]
+fn test_document_outline_lists_symbols_and_jumps() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project");
+    std::fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    std::fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin(&plugins_dir, "document_outline");
+    copy_plugin_lib(&plugins_dir);
+
+    let source = "fn alpha() {}\n\nstruct Beta;\n\nfn gamma() {\n    alpha();\n}\n";
+    let path = project_root.join("fixture.rs");
+    std::fs::write(&path, source).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 30, Default::default(), project_root)
+            .unwrap();
+    harness.open_file(&path).unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("Show Document Outline").unwrap();
+    harness
+        .wait_for_screen_contains("Show Document Outline")
+        .unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt_closed().unwrap();
+    harness.wait_for_screen_contains("gamma  function").unwrap();
+
+    let screen = harness.screen_to_string();
+    let panel = &screen[screen.find("Outline: fixture.rs").unwrap()..];
+    let alpha = panel.find("alpha  function").unwrap();
+    let beta = panel.find("Beta  struct").unwrap();
+    let gamma = panel.find("gamma  function").unwrap();
+    assert!(
+        alpha < beta && beta < gamma,
+        "outline out of order:\n{panel}"
+    );
+
+    // The panel starts on the first symbol; select the third
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    let gamma_offset = source.find("gamma").unwrap();
+    harness
+        .wait_until(|h| h.cursor_position() == gamma_offset)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), source);
+}
@@ -2348,3 +2348,93 @@ fn test_git_blame_original_buffer_not_decorated() {
         "Original file should NOT have blame headers after closing blame"
     );
 }
+
+/// Test that Toggle Git Blame annotates the cursor line without editing it
+// TODO: Fix git blame tests on Windows - they fail due to git command output differences
+#[test]
+#[cfg_attr(target_os = "windows", ignore)]
+fn test_git_blame_toggle_inline_annotation() {
+    let repo = GitTestRepo::new();
+    repo.setup_typical_project();
+    repo.setup_git_blame_plugin();
+
+    // Change to repo directory
+    let original_dir = repo.change_to_repo_dir();
+    let _guard = DirGuard::new(original_dir);
+
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", "--short=7", "HEAD"])
+        .current_dir(&repo.path)
+        .output()
+        .unwrap();
+    let short_hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        40,
+        Config::default(),
+        repo.path.clone(),
+    )
+    .unwrap();
+
+    let file_path = repo.path.join("src/main.rs");
+    harness.open_file(&file_path).unwrap();
+    harness
+        .wait_until(|h| h.get_buffer_content().unwrap().contains("fn main"))
+        .unwrap();
+    let content_before = harness.get_buffer_content().unwrap();
+
+    let cursor_row = |h: &EditorTestHarness| {
+        h.screen_to_string()
+            .lines()
+            .find(|line| line.contains("fn main() {"))
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    let toggle = |h: &mut EditorTestHarness| {
+        h.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        h.wait_for_prompt().unwrap();
+        h.type_text("Toggle Git Blame").unwrap();
+        h.wait_for_screen_contains("Toggle Git Blame").unwrap();
+        h.send_key(KeyCode::Enter, KeyModifiers::NONE).unwrap();
+        h.wait_for_prompt_closed().unwrap();
+    };
+
+    // The cursor line gets its commit's hash and author at the end
+    toggle(&mut harness);
+    harness
+        .wait_until(|h| {
+            let row = cursor_row(h);
+            row.contains("Test User") && row.contains(&short_hash)
+        })
+        .unwrap();
+    println!("Cursor line with blame: {}", cursor_row(&harness));
+    assert_eq!(harness.get_buffer_content().unwrap(), content_before);
+
+    // Toggling again removes it
+    toggle(&mut harness);
+    harness
+        .wait_until(|h| !cursor_row(h).contains("Test User"))
+        .unwrap();
+    assert!(!cursor_row(&harness).contains(&short_hash));
+    assert_eq!(harness.get_buffer_content().unwrap(), content_before);
+
+    // A commit made outside the editor shows up once blame is turned back on
+    std::process::Command::new("git")
+        .args([
+            "commit",
+            "--amend",
+            "--no-edit",
+            "--author=Other Author <other@example.com>",
+        ])
+        .current_dir(&repo.path)
+        .output()
+        .unwrap();
+    toggle(&mut harness);
+    harness
+        .wait_until(|h| cursor_row(h).contains("Other Author"))
+        .unwrap();
+    assert!(!cursor_row(&harness).contains(&short_hash));
+}
@@ -529,6 +529,145 @@ fn test_git_gutter_untracked_file() {
     );
 }
 
+/// Run a Git Gutter command from the command palette
+fn run_git_gutter_command(harness: &mut EditorTestHarness, name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text(name).unwrap();
+    harness.wait_for_screen_contains(name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt_closed().unwrap();
+}
+
+fn cursor_line(harness: &EditorTestHarness) -> usize {
+    let position = harness.editor().active_cursors().primary().position;
+    harness
+        .editor()
+        .active_state()
+        .buffer
+        .get_line_number(position)
+}
+
+/// Test that unsaved edits are diffed against HEAD, that changes can be
+/// visited in turn, and that reverting them restores the committed bytes
+#[test]
+#[cfg_attr(target_os = "windows", ignore)]
+fn test_git_gutter_tracks_edits_and_reverts_hunks() {
+    let repo = GitTestRepo::new();
+    repo.setup_typical_project();
+    repo.setup_git_gutter_plugin();
+
+    let original_dir = repo.change_to_repo_dir();
+    let _guard = DirGuard::new(original_dir);
+
+    let committed = std::fs::read_to_string(repo.path.join("src/main.rs")).unwrap();
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        40,
+        Config::default(),
+        repo.path.clone(),
+    )
+    .unwrap();
+    open_file(&mut harness, &repo.path, "src/main.rs");
+
+    // Modify line 1 and add a line before the blank line 5, without saving
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.type_text(" // changed").unwrap();
+    for _ in 0..4 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness.type_text("// added\n").unwrap();
+
+    harness
+        .wait_until(|h| get_indicator_lines(&h.screen_to_string(), "│") == vec![1, 5])
+        .unwrap();
+    let (first_row, _) = harness.content_area_rows();
+    let color = |h: &EditorTestHarness, line: usize| {
+        h.get_cell_style(0, (first_row + line) as u16)
+            .and_then(|style| style.fg)
+    };
+    assert_eq!(
+        color(&harness, 1),
+        Some(ratatui::style::Color::Rgb(255, 184, 108))
+    );
+    assert_eq!(
+        color(&harness, 5),
+        Some(ratatui::style::Color::Rgb(80, 250, 123))
+    );
+
+    // Next Change wraps from the end of the buffer to the first change
+    run_git_gutter_command(&mut harness, "Git Gutter: Next Change");
+    assert_eq!(cursor_line(&harness), 1);
+    run_git_gutter_command(&mut harness, "Git Gutter: Next Change");
+    assert_eq!(cursor_line(&harness), 5);
+
+    // Reverting both changes restores the committed file
+    run_git_gutter_command(&mut harness, "Git Gutter: Revert Hunk");
+    harness
+        .wait_until(|h| get_indicator_lines(&h.screen_to_string(), "│") == vec![1])
+        .unwrap();
+    run_git_gutter_command(&mut harness, "Git Gutter: Previous Change");
+    assert_eq!(cursor_line(&harness), 1);
+    run_git_gutter_command(&mut harness, "Git Gutter: Revert Hunk");
+    harness
+        .wait_until(|h| get_indicator_lines(&h.screen_to_string(), "│").is_empty())
+        .unwrap();
+
+    assert_eq!(harness.get_buffer_content().unwrap(), committed);
+}
+
+/// Test that reverting a hunk uses the buffer as it is now, even before the
+/// indicators caught up with the latest typing, and is undone in one step
+#[test]
+#[cfg_attr(target_os = "windows", ignore)]
+fn test_git_gutter_revert_hunk_uses_current_buffer() {
+    let repo = GitTestRepo::new();
+    repo.setup_typical_project();
+    repo.setup_git_gutter_plugin();
+
+    let original_dir = repo.change_to_repo_dir();
+    let _guard = DirGuard::new(original_dir);
+
+    let committed = std::fs::read_to_string(repo.path.join("src/main.rs")).unwrap();
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        40,
+        Config::default(),
+        repo.path.clone(),
+    )
+    .unwrap();
+    open_file(&mut harness, &repo.path, "src/main.rs");
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.type_text(" // changed").unwrap();
+    harness
+        .wait_until(|h| get_indicator_lines(&h.screen_to_string(), "│") == vec![1])
+        .unwrap();
+
+    // Revert right after more typing on the line
+    harness.type_text(" again").unwrap();
+    let edited = harness.get_buffer_content().unwrap();
+    run_git_gutter_command(&mut harness, "Git Gutter: Revert Hunk");
+    harness
+        .wait_until(|h| h.get_buffer_content().unwrap() == committed)
+        .unwrap();
+
+    // One undo brings the whole hunk back
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), edited);
+}
+
 // =============================================================================
 // Buffer Modified Tests
 // =============================================================================
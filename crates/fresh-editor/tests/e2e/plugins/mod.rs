@@ -3,6 +3,7 @@
 
 pub mod audit_mode;
 pub mod diff_cursor;
+pub mod document_outline;
 pub mod find_file;
 pub mod git;
 pub mod gutter;
@@ -250,3 +250,171 @@ fn test_position_history_with_next_prev_buffer() {
     harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
     harness.assert_buffer_content("Third");
 }
+
+/// Go to Definition without a language server finds the definition by
+/// scanning the project, and Go Back returns to where it was invoked
+#[test]
+fn test_goto_definition_fallback_and_navigate_back() {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24).unwrap();
+    let project = harness.project_dir().unwrap();
+    std::fs::create_dir(project.join("src")).unwrap();
+    let lib = "// Helpers\n\npub fn helper() {}\n";
+    std::fs::write(project.join("src/lib.rs"), lib).unwrap();
+    let main = project.join("src/main.rs");
+    std::fs::write(&main, "fn main() {\n    helper();\n}\n").unwrap();
+
+    harness.open_file(&main).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    for _ in 0..5 {
+        harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    }
+    let origin = harness.cursor_position();
+
+    harness
+        .send_key(KeyCode::F(12), KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_until(|h| h.get_buffer_content().as_deref() == Some(lib))
+        .unwrap();
+    assert_eq!(harness.cursor_position(), lib.find("helper").unwrap());
+
+    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    harness
+        .wait_until(|h| h.get_buffer_content().is_some_and(|c| c.contains("main")))
+        .unwrap();
+    assert_eq!(harness.cursor_position(), origin);
+}
+
+/// Write `lines` numbered lines prefixed with `prefix` to a file in `dir`
+fn write_numbered_file(dir: &std::path::Path, name: &str, prefix: &str) -> std::path::PathBuf {
+    let content: String = (1..=30).map(|i| format!("{prefix} line {i}\n")).collect();
+    let path = dir.join(name);
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+/// Jump to a 1-based line with Go to Line
+fn goto_line(harness: &mut EditorTestHarness, line: usize) {
+    harness
+        .send_key(KeyCode::Char('g'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(&line.to_string()).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+}
+
+/// Byte offset of `text` in the active buffer
+fn offset_of(harness: &EditorTestHarness, text: &str) -> usize {
+    harness.get_buffer_content().unwrap().find(text).unwrap()
+}
+
+/// Go to Line, search and opening a file are jumps that back/forward
+/// traverse in order, restoring both the file and the cursor
+#[test]
+fn test_jumps_across_files() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let a = write_numbered_file(temp_dir.path(), "a.txt", "a");
+    let b = write_numbered_file(temp_dir.path(), "b.txt", "b");
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.open_file(&a).unwrap();
+    goto_line(&mut harness, 10);
+    harness
+        .send_key(KeyCode::Char('f'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("line 20").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(harness.cursor_position(), offset_of(&harness, "line 20"));
+    harness.open_file(&b).unwrap();
+    goto_line(&mut harness, 5);
+
+    // (file, text at the cursor) for each jump, oldest first
+    let jumps = [
+        ("a line 1\n", "a line 1\n"),
+        ("a line 1\n", "a line 10"),
+        ("a line 1\n", "line 20"),
+        ("b line 1\n", "b line 1\n"),
+        ("b line 1\n", "b line 5"),
+    ];
+    let assert_at = |harness: &EditorTestHarness, (file, text): (&str, &str)| {
+        assert!(harness.get_buffer_content().unwrap().starts_with(file));
+        assert_eq!(harness.cursor_position(), offset_of(harness, text));
+    };
+
+    for &jump in jumps.iter().rev().skip(1) {
+        harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+        assert_at(&harness, jump);
+    }
+    for &jump in jumps.iter().skip(1) {
+        harness.send_key(KeyCode::Right, KeyModifiers::ALT).unwrap();
+        assert_at(&harness, jump);
+    }
+}
+
+/// History entries stay on the same text when lines are inserted above them
+#[test]
+fn test_jumps_survive_edits() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let a = write_numbered_file(temp_dir.path(), "a.txt", "a");
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.open_file(&a).unwrap();
+    goto_line(&mut harness, 20);
+    goto_line(&mut harness, 5);
+    harness.type_text("inserted\n").unwrap();
+
+    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    assert_eq!(harness.cursor_position(), offset_of(&harness, "a line 20"));
+}
+
+/// Clicking the scrollbar far from the cursor is a jump too
+#[test]
+fn test_scrollbar_jump_is_recorded() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let a = write_numbered_file(temp_dir.path(), "a.txt", "a");
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.open_file(&a).unwrap();
+    harness.render().unwrap();
+    harness.mouse_click(79, 20).unwrap();
+    let scrolled_to = harness.cursor_position();
+    assert!(
+        scrolled_to > 0,
+        "the scroll should move the cursor into view"
+    );
+
+    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    assert_eq!(harness.cursor_position(), 0);
+    harness.send_key(KeyCode::Right, KeyModifiers::ALT).unwrap();
+    assert_eq!(harness.cursor_position(), scrolled_to);
+}
+
+/// Undoing a bulk edit moves history entries back onto their text
+#[test]
+fn test_jumps_survive_undone_bulk_edit() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let a = write_numbered_file(temp_dir.path(), "a.rs", "a");
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.open_file(&a).unwrap();
+    goto_line(&mut harness, 20);
+    goto_line(&mut harness, 5);
+    harness
+        .send_key(KeyCode::Char('/'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert!(harness
+        .get_buffer_content()
+        .unwrap()
+        .contains("// a line 5"));
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert!(!harness.get_buffer_content().unwrap().contains("//"));
+
+    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    assert_eq!(harness.cursor_position(), offset_of(&harness, "a line 20"));
+}
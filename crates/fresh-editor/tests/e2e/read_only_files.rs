@@ -0,0 +1,231 @@
+//! E2E tests for files the user can't write
+//!
+//! A file without write permission opens read-only: edits are refused with
+//! a status message until Edit Anyway, and saving it goes through sudo.
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::model::filesystem::{
+    DirEntry, FileMetadata, FilePermissions, FileReader, FileSystem, FileWriter, StdFileSystem,
+};
+use std::fs::Permissions;
+use std::io;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+
+/// A filesystem whose sudo writes are recorded and done without sudo, by
+/// briefly making the file writable
+struct FakeSudoFileSystem {
+    inner: StdFileSystem,
+    sudo_writes: Mutex<Vec<PathBuf>>,
+}
+
+impl FakeSudoFileSystem {
+    fn new() -> Self {
+        Self {
+            inner: StdFileSystem,
+            sudo_writes: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn sudo_writes(&self) -> Vec<PathBuf> {
+        self.sudo_writes.lock().unwrap().clone()
+    }
+}
+
+impl FileSystem for FakeSudoFileSystem {
+    fn read_file(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.inner.read_file(path)
+    }
+
+    fn read_range(&self, path: &Path, offset: u64, len: usize) -> io::Result<Vec<u8>> {
+        self.inner.read_range(path, offset, len)
+    }
+
+    fn write_file(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        self.inner.write_file(path, data)
+    }
+
+    fn create_file(&self, path: &Path) -> io::Result<Box<dyn FileWriter>> {
+        self.inner.create_file(path)
+    }
+
+    fn open_file(&self, path: &Path) -> io::Result<Box<dyn FileReader>> {
+        self.inner.open_file(path)
+    }
+
+    fn open_file_for_write(&self, path: &Path) -> io::Result<Box<dyn FileWriter>> {
+        self.inner.open_file_for_write(path)
+    }
+
+    fn open_file_for_append(&self, path: &Path) -> io::Result<Box<dyn FileWriter>> {
+        self.inner.open_file_for_append(path)
+    }
+
+    fn set_file_length(&self, path: &Path, len: u64) -> io::Result<()> {
+        self.inner.set_file_length(path, len)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        self.inner.rename(from, to)
+    }
+
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<u64> {
+        self.inner.copy(from, to)
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        self.inner.remove_file(path)
+    }
+
+    fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        self.inner.remove_dir(path)
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.inner.metadata(path)
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.inner.symlink_metadata(path)
+    }
+
+    fn is_dir(&self, path: &Path) -> io::Result<bool> {
+        self.inner.is_dir(path)
+    }
+
+    fn is_file(&self, path: &Path) -> io::Result<bool> {
+        self.inner.is_file(path)
+    }
+
+    fn set_permissions(&self, path: &Path, permissions: &FilePermissions) -> io::Result<()> {
+        self.inner.set_permissions(path, permissions)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<DirEntry>> {
+        self.inner.read_dir(path)
+    }
+
+    fn create_dir(&self, path: &Path) -> io::Result<()> {
+        self.inner.create_dir(path)
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        self.inner.create_dir_all(path)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        self.inner.canonicalize(path)
+    }
+
+    fn current_uid(&self) -> u32 {
+        self.inner.current_uid()
+    }
+
+    fn sudo_write(
+        &self,
+        path: &Path,
+        data: &[u8],
+        mode: u32,
+        _uid: u32,
+        _gid: u32,
+    ) -> io::Result<()> {
+        self.sudo_writes.lock().unwrap().push(path.to_path_buf());
+        std::fs::set_permissions(path, Permissions::from_mode(0o644))?;
+        std::fs::write(path, data)?;
+        std::fs::set_permissions(path, Permissions::from_mode(mode))
+    }
+}
+
+/// Run a command from the command palette
+fn run_command(harness: &mut EditorTestHarness, command: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(command).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+fn read_only_file(temp_dir: &TempDir, content: &str) -> PathBuf {
+    let path = temp_dir.path().join("readonly.conf");
+    std::fs::write(&path, content).unwrap();
+    std::fs::set_permissions(&path, Permissions::from_mode(0o444)).unwrap();
+    path
+}
+
+/// A file without write permission can't be edited until Edit Anyway
+#[test]
+fn test_read_only_file_blocks_edits_until_edit_anyway() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = read_only_file(&temp_dir, "original\n");
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("readonly.conf [RO]");
+
+    harness.type_text("x").unwrap();
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_buffer_content("original\n");
+    assert!(harness
+        .editor()
+        .get_status_message()
+        .is_some_and(|status| status.contains("is read-only")));
+
+    run_command(&mut harness, "Edit Anyway");
+    harness.type_text("x").unwrap();
+    harness.assert_buffer_content("xoriginal\n");
+}
+
+/// After Edit Anyway, saving writes the file with sudo and keeps its mode
+#[test]
+fn test_read_only_file_saves_with_sudo_after_edit_anyway() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = read_only_file(&temp_dir, "original\n");
+    let fs = Arc::new(FakeSudoFileSystem::new());
+
+    let mut harness =
+        EditorTestHarness::create(100, 24, HarnessOptions::new().with_filesystem(fs.clone()))
+            .unwrap();
+    harness.open_file(&file_path).unwrap();
+    run_command(&mut harness, "Edit Anyway");
+    harness.type_text("first ").unwrap();
+
+    // Save asks before using sudo
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Save with sudo?");
+    harness.type_text("y").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(&file_path).unwrap(),
+        "first original\n"
+    );
+    assert!(!harness.editor().active_state().buffer.is_modified());
+
+    // Save with Sudo doesn't ask
+    harness.type_text("second ").unwrap();
+    run_command(&mut harness, "Save with Sudo");
+    assert_eq!(
+        std::fs::read_to_string(&file_path).unwrap(),
+        "first second original\n"
+    );
+
+    let saved_path = file_path.canonicalize().unwrap();
+    assert_eq!(fs.sudo_writes(), vec![saved_path.clone(), saved_path]);
+    let mode = std::fs::metadata(&file_path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o444, "Mode preserved");
+}
@@ -1,7 +1,7 @@
 // End-to-end tests for file recovery feature
 
 use crate::common::fixtures::TestFixture;
-use crate::common::harness::EditorTestHarness;
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
 use crossterm::event::{KeyCode, KeyModifiers};
 use fresh::model::buffer::TextBuffer;
 use fresh::model::event::{CursorId, Event};
@@ -172,7 +172,8 @@ fn test_batch_event_marks_buffer_recovery_dirty() {
 /// 5. Undo back to saved state -> buffer should be NOT modified
 #[test]
 fn test_undo_returns_to_saved_state_not_original() {
-    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let mut harness =
+        EditorTestHarness::create(80, 24, HarnessOptions::new().without_undo_grouping()).unwrap();
 
     // Create a file we can save to
     let fixture = TestFixture::new("test_undo_modified.txt", "").unwrap();
@@ -762,6 +763,7 @@ fn test_large_file_auto_save_creates_small_recovery_file() {
         .auto_recovery_save_dirty_buffers()
         .unwrap();
     assert!(saved > 0, "Should have saved at least one buffer");
+    harness.editor().flush_recovery_writes().unwrap();
 
     // Check the recovery file size using the harness's recovery directory
     let recovery_dir = harness
@@ -807,6 +809,97 @@ fn test_large_file_auto_save_creates_small_recovery_file() {
     }
 }
 
+/// A large-file-mode buffer below the chunked recovery size saves its full content
+#[test]
+fn test_large_file_mode_below_chunked_threshold_saves_full_content() {
+    use std::fs;
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("medium_file.txt");
+    let original_content = "X".repeat(10_000);
+    fs::write(&file_path, &original_content).unwrap();
+
+    let mut config = fresh::config::Config::default();
+    config.editor.large_file_threshold_bytes = 1000; // Force large file mode
+
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&file_path).unwrap();
+    assert!(harness.editor().active_state().buffer.is_large_file());
+
+    harness.type_text("EDIT").unwrap();
+    harness.advance_time(std::time::Duration::from_millis(2100));
+    let saved = harness
+        .editor_mut()
+        .auto_recovery_save_dirty_buffers()
+        .unwrap();
+    assert!(saved > 0, "Should have saved at least one buffer");
+    harness.editor().flush_recovery_writes().unwrap();
+
+    let storage = RecoveryStorage::with_dir(harness.recovery_dir().unwrap());
+    let entry = storage
+        .list_entries()
+        .unwrap()
+        .into_iter()
+        .find(|e| {
+            e.metadata
+                .original_path
+                .as_ref()
+                .is_some_and(|p| p.ends_with("medium_file.txt"))
+        })
+        .expect("Expected a recovery entry for medium_file.txt");
+
+    // Full format: no original file needed, the single chunk is the whole buffer
+    assert_eq!(entry.metadata.original_file_size, 0);
+    let data = storage.read_chunked_content(&entry.id).unwrap().unwrap();
+    assert_eq!(data.chunks.len(), 1);
+    assert_eq!(
+        data.chunks[0].content,
+        format!("EDIT{original_content}").as_bytes()
+    );
+}
+
+/// Marking a buffer recovery-exempt removes its recovery files and stops new ones
+#[test]
+fn test_recovery_exempt_buffer_is_not_auto_saved() {
+    use std::fs;
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("build.log");
+    fs::write(&file_path, "log line\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    let storage = RecoveryStorage::with_dir(harness.recovery_dir().unwrap());
+
+    harness.type_text("edit").unwrap();
+    harness.advance_time(std::time::Duration::from_millis(2100));
+    harness
+        .editor_mut()
+        .auto_recovery_save_dirty_buffers()
+        .unwrap();
+    harness.editor().flush_recovery_writes().unwrap();
+    assert_eq!(storage.list_entries().unwrap().len(), 1);
+
+    let buffer_id = harness.editor().active_buffer();
+    harness
+        .editor_mut()
+        .set_buffer_recovery_exempt(buffer_id, true)
+        .unwrap();
+    assert!(storage.list_entries().unwrap().is_empty());
+
+    harness.type_text(" more").unwrap();
+    harness.advance_time(std::time::Duration::from_millis(2100));
+    let saved = harness
+        .editor_mut()
+        .auto_recovery_save_dirty_buffers()
+        .unwrap();
+    harness.editor().flush_recovery_writes().unwrap();
+    assert_eq!(saved, 0);
+    assert!(storage.list_entries().unwrap().is_empty());
+}
+
 /// Regression test: recovery after saving a modified large file should work
 ///
 /// Bug scenario:
@@ -905,6 +998,7 @@ fn test_recovery_after_save_with_size_change() {
         .auto_recovery_save_dirty_buffers()
         .unwrap();
     assert!(saved > 0, "Should have saved recovery for dirty buffer");
+    harness.editor().flush_recovery_writes().unwrap();
 
     // Get recovery directory and take temp dir before dropping harness
     let recovery_dir = harness
@@ -1005,6 +1099,7 @@ fn test_unnamed_buffer_created_via_new_buffer_has_stable_recovery() {
         .auto_recovery_save_dirty_buffers()
         .unwrap();
     assert_eq!(saved1, 1, "Should save recovery for the new buffer");
+    harness.editor().flush_recovery_writes().unwrap();
 
     // Type more content
     harness.type_text(" more").unwrap();
@@ -1018,6 +1113,7 @@ fn test_unnamed_buffer_created_via_new_buffer_has_stable_recovery() {
         .auto_recovery_save_dirty_buffers()
         .unwrap();
     assert_eq!(saved2, 1, "Should save recovery again");
+    harness.editor().flush_recovery_writes().unwrap();
 
     // Check recovery directory
     let recovery_dir = harness.recovery_dir().expect("should have recovery dir");
@@ -1108,6 +1204,7 @@ fn test_recovery_insert_at_end_of_large_file() {
         .auto_recovery_save_dirty_buffers()
         .unwrap();
     assert!(saved > 0, "Should have saved recovery");
+    harness.editor().flush_recovery_writes().unwrap();
 
     // Get recovery directory and take temp dir before dropping harness
     let recovery_dir = harness
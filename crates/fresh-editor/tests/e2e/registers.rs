@@ -0,0 +1,95 @@
+//! Tests for named clipboard registers
+//!
+//! Tests that:
+//! - Copies into registers `a` and `b` are kept apart from each other and
+//!   from the clipboard
+//! - Pasting from a register inserts its text at the cursor, with multi-byte
+//!   characters intact
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Run a register command from the command palette, answering its prompt
+fn run_register_command(harness: &mut EditorTestHarness, command_name: &str, register: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.type_text(command_name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.type_text(register).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Select `count` characters to the right of the cursor
+fn select_right(harness: &mut EditorTestHarness, count: usize) {
+    for _ in 0..count {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::SHIFT)
+            .unwrap();
+    }
+}
+
+#[test]
+fn test_copy_and_paste_named_registers() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness
+        .editor_mut()
+        .set_clipboard_for_test("clipboard".to_string());
+    harness.type_text("héllo 日本語 end").unwrap();
+
+    // "héllo" into register a
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    select_right(&mut harness, 5);
+    run_register_command(&mut harness, "Copy to Register", "a");
+
+    // "日本語" into register b; Right clears the selection and steps over the space
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    select_right(&mut harness, 3);
+    run_register_command(&mut harness, "Copy to Register", "b");
+
+    // The clipboard itself is untouched
+    assert_eq!(harness.editor().clipboard_content_for_test(), "clipboard");
+
+    // Paste b at the end of the line, then a at the start
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    run_register_command(&mut harness, "Paste from Register", "b");
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "héllo 日本語 end日本語"
+    );
+    assert_eq!(
+        harness.cursor_position(),
+        harness.get_buffer_content().unwrap().len()
+    );
+
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    run_register_command(&mut harness, "Paste from Register", "a");
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "héllohéllo 日本語 end日本語"
+    );
+    assert_eq!(harness.cursor_position(), "héllo".len());
+}
+
+#[test]
+fn test_paste_from_empty_register_changes_nothing() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("text").unwrap();
+
+    run_register_command(&mut harness, "Paste from Register", "z");
+
+    assert_eq!(harness.get_buffer_content().unwrap(), "text");
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("Register 'z' is empty")
+    );
+}
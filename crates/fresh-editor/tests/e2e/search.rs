@@ -2478,3 +2478,296 @@ fn test_regex_replace_with_capture_group() {
     let content = harness.get_buffer_content().unwrap();
     assert_eq!(content, "ooblaoobla");
 }
+
+/// Incremental search jumps as you type, shows a match counter, and Enter
+/// cycles through matches with wrap-around
+#[test]
+fn test_incremental_search_counter_and_wrap() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.txt");
+    let content = "alpha\nneedle one\nbeta\nneedle two\nneedle three\n";
+    std::fs::write(&file_path, content).unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(
+            KeyCode::Char('f'),
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+        )
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("I-search: ");
+
+    // The cursor follows the query as it grows, before Enter is pressed
+    harness.type_text("needle").unwrap();
+    harness.render().unwrap();
+    let first = content.find("needle").unwrap();
+    assert_eq!(harness.cursor_position(), first);
+    harness.assert_screen_contains("1/3");
+
+    // Enter advances through the matches
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.cursor_position(),
+        content.find("needle two").unwrap()
+    );
+    harness.assert_screen_contains("2/3");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.cursor_position(),
+        content.find("needle three").unwrap()
+    );
+    harness.assert_screen_contains("3/3");
+
+    // ...and wraps back to the first match
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.cursor_position(), first);
+    harness.assert_screen_contains("1/3");
+
+    // Shift+Enter wraps backwards to the last match
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::SHIFT)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.cursor_position(),
+        content.find("needle three").unwrap()
+    );
+    harness.assert_screen_contains("3/3");
+
+    // Escape leaves the cursor on the current match
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.cursor_position(),
+        content.find("needle three").unwrap()
+    );
+}
+
+/// With smart case, a lowercase query ignores case but an uppercase letter
+/// makes the search case-sensitive
+#[test]
+fn test_incremental_search_smart_case() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.txt");
+    std::fs::write(&file_path, "Foo foo FOO\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(
+            KeyCode::Char('f'),
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+        )
+        .unwrap();
+    // Case-sensitive off (Alt+C), smart case on (Alt+A)
+    harness
+        .send_key(KeyCode::Char('c'), KeyModifiers::ALT)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::ALT)
+        .unwrap();
+
+    harness.type_text("foo").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("1/3");
+
+    // "Foo" has an uppercase letter, so only the exact spelling matches
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Delete, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("F").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("1/1");
+    assert_eq!(harness.cursor_position(), 0);
+}
+
+/// Regex replace-all swaps capture groups on every line and previews the count
+#[test]
+fn test_regex_replace_swaps_capture_groups() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.txt");
+    std::fs::write(
+        &file_path,
+        "alice@example\nno address here\nbob@test carol@host\n",
+    )
+    .unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('r'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('r'), KeyModifiers::ALT)
+        .unwrap();
+    harness.type_text(r"(\w+)@(\w+)").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    // The match count is shown before anything is replaced
+    harness.assert_screen_contains(r"Replace '(\w+)@(\w+)' with: ");
+    harness.assert_screen_contains("3 match(es) to replace");
+
+    harness.type_text("$2.$1").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "example.alice\nno address here\ntest.bob host.carol\n"
+    );
+}
+
+/// An invalid regex reports an error and never reaches the replace step
+#[test]
+fn test_regex_replace_invalid_pattern_makes_no_edits() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.txt");
+    let content = "alice@example\nbob@test\n";
+    std::fs::write(&file_path, content).unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('r'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('r'), KeyModifiers::ALT)
+        .unwrap();
+    // Unterminated group
+    harness.type_text(r"(\w+@").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    let status = harness
+        .editor()
+        .get_status_message()
+        .cloned()
+        .unwrap_or_default();
+    assert!(
+        status.starts_with("Invalid regex"),
+        "Expected an invalid regex error, got: {status}"
+    );
+    harness.assert_screen_not_contains("with: ");
+    assert_eq!(harness.get_buffer_content().unwrap(), content);
+}
+
+/// Replace in Selection only touches matches inside the selection
+#[test]
+fn test_replace_in_selection() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.txt");
+    std::fs::write(&file_path, "a@b\nc@d\ne@f\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    // Select the last two lines
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Replace in Selection").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    harness
+        .send_key(KeyCode::Char('r'), KeyModifiers::ALT)
+        .unwrap();
+    harness.type_text(r"(\w)@(\w)").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("$2.$1").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(harness.get_buffer_content().unwrap(), "a@b\nd.c\nf.e\n");
+}
+
+/// Test Search in Project lists matches across files, skipping ignored and
+/// binary files, and opens the selected match at its position
+#[test]
+fn test_search_in_project_opens_match() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 30).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    std::fs::create_dir_all(project_dir.join("src")).unwrap();
+    std::fs::create_dir_all(project_dir.join("target")).unwrap();
+    std::fs::write(project_dir.join(".gitignore"), "target/\n").unwrap();
+    std::fs::write(project_dir.join("notes.txt"), "a needle here\n").unwrap();
+    std::fs::write(
+        project_dir.join("src/lib.rs"),
+        "fn alpha() {}\nlet needle = 1;\n",
+    )
+    .unwrap();
+    std::fs::write(project_dir.join("target/out.txt"), "needle\n").unwrap();
+    std::fs::write(project_dir.join("data.bin"), b"needle\0\n").unwrap();
+
+    harness
+        .send_key(
+            KeyCode::Char('f'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        )
+        .unwrap();
+    harness.type_text("needle").unwrap();
+    harness
+        .wait_until(|h| {
+            let screen = h.screen_to_string();
+            screen.contains("notes.txt:1") && screen.contains("src/lib.rs:2")
+        })
+        .unwrap();
+    harness.assert_screen_contains("let needle = 1;");
+    harness.assert_screen_not_contains("out.txt");
+    harness.assert_screen_not_contains("data.bin");
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "fn alpha() {}\nlet needle = 1;\n"
+    );
+    // Line 2, at the start of "needle"
+    assert_eq!(harness.cursor_position(), 18);
+}
@@ -1904,12 +1904,12 @@ fn test_entry_dialog_focus_indicator() {
     harness.assert_screen_contains("Key:");
 
     // The focused field should have a ">" indicator
-    // First editable field (Auto Indent) should be focused by default
+    // First editable field (Auto Close) should be focused by default
     // Format: ">  " or ">● " (3-char indicator area: focus, modified, space)
     let screen = harness.screen_to_string();
     assert!(
-        screen.contains(">  Auto Indent") || screen.contains(">● Auto Indent"),
-        "Focus indicator '>' should appear before Auto Indent. Screen:\n{}",
+        screen.contains(">  Auto Close") || screen.contains(">● Auto Close"),
+        "Focus indicator '>' should appear before Auto Close. Screen:\n{}",
         screen
     );
 
@@ -1917,12 +1917,12 @@ fn test_entry_dialog_focus_indicator() {
     harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
     harness.render().unwrap();
 
-    // Now "Comment Prefix" should be focused with ">" indicator
+    // Now "Auto Indent" should be focused with ">" indicator
     // May have modified indicator if value differs from default
     let screen = harness.screen_to_string();
     assert!(
-        screen.contains(">  Comment Prefix") || screen.contains(">● Comment Prefix"),
-        "Focus indicator '>' should appear before Comment Prefix. Screen:\n{}",
+        screen.contains(">  Auto Indent") || screen.contains(">● Auto Indent"),
+        "Focus indicator '>' should appear before Auto Indent. Screen:\n{}",
         screen
     );
 
@@ -1954,8 +1954,8 @@ fn test_entry_dialog_add_new_textlist_item() {
     harness.assert_screen_contains("Edit Value");
 
     // Navigate to Extensions section which has "[+] Add new"
-    // Fields in order: Key, Auto Indent, Comment Prefix, Extensions (3 downs)
-    for _ in 0..3 {
+    // Fields in order: Key, Auto Close, Auto Indent, Comment Prefix, Extensions (4 downs)
+    for _ in 0..4 {
         harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
     }
     harness.render().unwrap();
@@ -2028,8 +2028,8 @@ fn test_entry_dialog_delete_textlist_item() {
     harness.assert_screen_contains("Edit Value");
 
     // Navigate to Extensions section which has existing items
-    // Fields in order: Key, Auto Indent, Comment Prefix, Extensions (3 downs)
-    for _ in 0..3 {
+    // Fields in order: Key, Auto Close, Auto Indent, Comment Prefix, Extensions (4 downs)
+    for _ in 0..4 {
         harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
     }
     harness.render().unwrap();
@@ -46,7 +46,8 @@ fn test_settings_paste() {
     harness.assert_screen_contains("bash");
 
     // Navigate down to Comment Prefix field (Key is read-only for existing entries)
-    // Order: Key -> Auto Indent -> Comment Prefix
+    // Order: Key -> Auto Close -> Auto Indent -> Comment Prefix
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap(); // Auto Close
     harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap(); // Auto Indent
     harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap(); // Comment Prefix
     harness.render().unwrap();
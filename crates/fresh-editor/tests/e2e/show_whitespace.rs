@@ -0,0 +1,120 @@
+//! E2E tests for drawing whitespace glyphs (`·`, `→`, `↵`).
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use ratatui::style::Modifier;
+use tempfile::TempDir;
+
+/// Gutter width for a small buffer: 1 (indicator) + 4 (digits) + 3 (" │ ") = 8
+const SMALL_BUFFER_GUTTER: u16 = 8;
+
+fn run_command(harness: &mut EditorTestHarness, command_name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(command_name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Symbol drawn in content column `col` of screen row `row`
+fn content_cell(harness: &EditorTestHarness, col: u16, row: u16) -> String {
+    harness.get_cell(SMALL_BUFFER_GUTTER + col, row).unwrap()
+}
+
+#[test]
+fn test_show_whitespace_draws_glyphs_without_changing_buffer() {
+    let temp_dir = TempDir::new().unwrap();
+    // Go hides the usual tab arrow, so any → comes from the whitespace mode
+    let file_path = temp_dir.path().join("main.go");
+    let content = "a b\n\tc\n";
+    std::fs::write(&file_path, content).unwrap();
+
+    let mut harness = EditorTestHarness::with_config(80, 24, Config::default()).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    let first_row = harness.content_area_rows().0 as u16;
+    assert_eq!(content_cell(&harness, 1, first_row), " ");
+    assert_eq!(content_cell(&harness, 0, first_row + 1), " ");
+
+    let cursor_before = harness.cursor_position();
+    let screen_cursor_before = harness.screen_cursor_position();
+
+    run_command(&mut harness, "Toggle Show Whitespace");
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("Whitespace shown")
+    );
+
+    // "a b" + LF
+    assert_eq!(content_cell(&harness, 0, first_row), "a");
+    assert_eq!(content_cell(&harness, 1, first_row), "·");
+    assert_eq!(content_cell(&harness, 2, first_row), "b");
+    assert_eq!(content_cell(&harness, 3, first_row), "↵");
+    let glyph_style = harness
+        .get_cell_style(SMALL_BUFFER_GUTTER + 1, first_row)
+        .unwrap();
+    assert!(glyph_style.add_modifier.contains(Modifier::DIM));
+
+    // The tab shows one arrow followed by blanks up to its tab stop
+    let tab_row = harness.get_screen_row(first_row as usize + 1);
+    assert_eq!(content_cell(&harness, 0, first_row + 1), "→");
+    assert_eq!(content_cell(&harness, 1, first_row + 1), " ");
+    let c_col = tab_row.chars().position(|ch| ch == 'c').unwrap() as u16;
+    assert_eq!(
+        harness.get_cell(c_col + 1, first_row + 1).as_deref(),
+        Some("↵")
+    );
+
+    // Only the drawing changed
+    assert_eq!(harness.get_buffer_content().unwrap(), content);
+    assert_eq!(harness.cursor_position(), cursor_before);
+    assert_eq!(harness.screen_cursor_position(), screen_cursor_before);
+
+    run_command(&mut harness, "Toggle Show Whitespace");
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("Whitespace hidden")
+    );
+    assert_eq!(content_cell(&harness, 1, first_row), " ");
+    assert_eq!(content_cell(&harness, 3, first_row), " ");
+}
+
+#[test]
+fn test_show_whitespace_marks_line_end_only_on_last_wrapped_row() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("wrap.txt");
+    std::fs::write(&file_path, "aa bb cc dd ee ff gg hh ii jj\nx").unwrap();
+
+    let mut config = Config::default();
+    config.editor.line_wrap = true;
+    config.editor.show_whitespace = true;
+    let mut harness = EditorTestHarness::with_config(24, 10, config).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    let first_row = harness.content_area_rows().0 as u16;
+    let rows: Vec<String> = (first_row..first_row + 6)
+        .map(|row| harness.get_screen_row(row as usize))
+        .collect();
+    let x_row = rows.iter().position(|row| row.contains('x')).unwrap();
+    assert!(x_row >= 2, "the first line should wrap: {rows:#?}");
+
+    for (i, row) in rows[..x_row].iter().enumerate() {
+        assert!(
+            row.contains('·'),
+            "spaces are marked on every row: {rows:#?}"
+        );
+        assert_eq!(
+            row.contains('↵'),
+            i == x_row - 1,
+            "only the last row of the line ends with ↵: {rows:#?}"
+        );
+    }
+    // The final line has no line ending
+    assert!(!rows[x_row].contains('↵'));
+}
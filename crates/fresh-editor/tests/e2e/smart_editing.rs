@@ -229,6 +229,80 @@ fn test_no_auto_close_when_config_disabled() {
     );
 }
 
+/// Test auto-close can be turned off for a single language
+#[test]
+fn test_no_auto_close_when_language_disabled() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.rs");
+    std::fs::write(&file_path, "").unwrap();
+
+    let mut config = Config::default();
+    config.languages.get_mut("rust").unwrap().auto_close = false;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&file_path).unwrap();
+
+    harness.type_text("f(\"").unwrap();
+    harness.assert_buffer_content("f(\"");
+}
+
+/// Test that brackets and quotes typed inside strings and comments aren't
+/// auto-closed
+#[test]
+fn test_no_auto_close_in_string_or_comment() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.rs");
+    std::fs::write(&file_path, "").unwrap();
+
+    let mut harness = harness_with_auto_indent();
+    harness.open_file(&file_path).unwrap();
+
+    // The quote auto-closes, but the paren typed inside the string doesn't
+    harness.type_text("const S: &str = \"a").unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "const S: &str = \"a\""
+    );
+    harness.type_text("(").unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "const S: &str = \"a(\""
+    );
+
+    // Nor does one typed at the end of a line comment
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.type_text(";\n// call(").unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "const S: &str = \"a(\";\n// call("
+    );
+}
+
+/// Test typing a function header and Enter: the brace auto-closes and Enter
+/// puts the cursor on an indented line above the dedented `}`
+#[test]
+fn test_auto_close_brace_then_enter_indents_body() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.rs");
+    std::fs::write(&file_path, "").unwrap();
+
+    let mut harness = harness_with_auto_indent();
+    harness.open_file(&file_path).unwrap();
+
+    harness.type_text("fn x() {").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "fn x() {}");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "fn x() {\n    \n}");
+    assert_eq!(harness.cursor_position(), "fn x() {\n    ".len());
+
+    harness.type_text("body();").unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "fn x() {\n    body();\n}"
+    );
+}
+
 // =============================================================================
 // Bracket Skip-Over Tests
 // =============================================================================
@@ -399,3 +399,137 @@ fn test_sort_lines_with_empty_lines() {
         "Empty lines should sort to the beginning"
     );
 }
+
+/// Test that a blank last line is sorted along with the others
+#[test]
+fn test_sort_lines_keeps_trailing_blank_line() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.type_text("b\na\n\n").unwrap();
+    run_palette_command(&mut harness, "sort lines");
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "\na\nb\n",
+        "The blank line should be kept and sort to the top"
+    );
+}
+
+/// Run a command from the command palette by name
+fn run_palette_command(harness: &mut EditorTestHarness, name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains(">command"))
+        .unwrap();
+    harness.type_text(name).unwrap();
+    harness.render().unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Test that without a selection the whole buffer is sorted
+#[test]
+fn test_sort_lines_without_selection_sorts_buffer() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.type_text("cherry\napple\nbanana").unwrap();
+    run_palette_command(&mut harness, "sort lines");
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "apple\nbanana\ncherry",
+        "Whole buffer should be sorted, keeping the missing final newline"
+    );
+}
+
+/// Test that the numeric variant orders by value rather than lexicographically
+#[test]
+fn test_sort_lines_numeric() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness
+        .type_text("10 items\n2 items\nno number\n1 item\n-3 items")
+        .unwrap();
+    run_palette_command(&mut harness, "sort lines numerically");
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "-3 items\n1 item\n2 items\n10 items\nno number",
+        "Lines should be ordered by leading number, unnumbered lines last"
+    );
+}
+
+/// Test descending and case-insensitive variants
+#[test]
+fn test_sort_lines_descending_and_case_insensitive() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.type_text("Banana\napple\nCherry").unwrap();
+    run_palette_command(&mut harness, "sort lines case insensitive");
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "apple\nBanana\nCherry"
+    );
+
+    run_palette_command(&mut harness, "sort lines descending");
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "apple\nCherry\nBanana"
+    );
+}
+
+/// Test that the unique variant removes duplicate lines after sorting
+#[test]
+fn test_sort_lines_unique() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.type_text("pear\nfig\npear\nfig\nPear\n").unwrap();
+    run_palette_command(&mut harness, "sort lines unique");
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "Pear\nfig\npear\n",
+        "Equal lines should be collapsed, distinct-case lines kept"
+    );
+}
+
+/// Test that each cursor's selection is sorted as its own region in one undo step
+#[test]
+fn test_sort_lines_multi_cursor_regions() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    let original = "x b\nx a\n-\nx d\nx c";
+    harness.type_text(original).unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+
+    // Select the "x" on line 1 and add a cursor at every other "x"
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::SHIFT)
+        .unwrap();
+    for _ in 0..3 {
+        harness
+            .send_key(KeyCode::Char('d'), KeyModifiers::CONTROL)
+            .unwrap();
+    }
+    assert_eq!(harness.cursor_count(), 4);
+
+    run_palette_command(&mut harness, "sort lines");
+
+    // Lines 1-2 and 4-5 are sorted separately; the unselected "-" stays put
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "x a\nx b\n-\nx c\nx d"
+    );
+
+    // The whole sort is undone in one step
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), original);
+}
@@ -821,3 +821,136 @@ fn test_cursor_movement_after_split_switch() {
     // Verify screen position changed
     assert_ne!(screen_x1, screen_x2, "Screen cursor X should have moved");
 }
+
+/// Text of the line starting at `byte` in the active buffer
+fn line_at(harness: &EditorTestHarness, byte: usize) -> String {
+    let content = harness.get_buffer_content().unwrap();
+    content[byte..]
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Test that two panes on the same buffer keep independent viewports while
+/// edits made in one pane show up live in both at the right place
+#[test]
+fn test_edit_in_scrolled_pane_visible_in_both_panes() {
+    let mut harness = EditorTestHarness::new(120, 40).unwrap();
+
+    let long_text = (1..=60)
+        .map(|i| format!("Line {}", i))
+        .collect::<Vec<_>>()
+        .join("\n");
+    harness.type_text(&long_text).unwrap();
+    harness.render().unwrap();
+
+    // First pane stays scrolled to the end of the buffer
+    let first_top_line = line_at(&harness, harness.top_byte());
+    assert_ne!(harness.top_byte(), 0);
+
+    // Second pane scrolls back to the top
+    split_vertical(&mut harness);
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.top_byte(), 0);
+
+    // Line 30 is visible in both panes: edit it from the second pane
+    harness
+        .send_key(KeyCode::Char('g'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("30").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.type_text(" EDITED").unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.screen_to_string().matches("Line 30 EDITED").count(),
+        2,
+        "The edit should be shown in both panes"
+    );
+
+    // Insert lines above the first pane's viewport from the second pane
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("New 1\nNew 2\n").unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.top_byte(), 0);
+    harness.assert_screen_contains("New 2");
+
+    // The first pane still shows the same lines, with its cursor at the end
+    prev_split(&mut harness);
+    assert_eq!(line_at(&harness, harness.top_byte()), first_top_line);
+    assert_eq!(
+        harness.cursor_position(),
+        harness.get_buffer_content().unwrap().len()
+    );
+    assert_eq!(
+        harness.screen_to_string().matches("Line 30 EDITED").count(),
+        2
+    );
+}
+
+/// Test that a multi-line edit made in one step (indenting a selection)
+/// keeps the other pane's viewport and cursor on their text, also when it
+/// is undone
+#[test]
+fn test_bulk_edit_above_other_pane_keeps_its_viewport() {
+    let mut harness = EditorTestHarness::new(120, 40).unwrap();
+
+    let long_text = (1..=60)
+        .map(|i| format!("Line {}", i))
+        .collect::<Vec<_>>()
+        .join("\n");
+    harness.type_text(&long_text).unwrap();
+    harness.render().unwrap();
+    let first_top_line = line_at(&harness, harness.top_byte());
+    assert_ne!(harness.top_byte(), 0);
+
+    // Indent the first three lines from a second pane at the top
+    split_vertical(&mut harness);
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    for _ in 0..3 {
+        harness
+            .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+            .unwrap();
+    }
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    assert!(harness
+        .get_buffer_content()
+        .unwrap()
+        .starts_with("    Line 1\n    Line 2\n    Line 3\n"));
+
+    prev_split(&mut harness);
+    assert_eq!(line_at(&harness, harness.top_byte()), first_top_line);
+    assert_eq!(
+        harness.cursor_position(),
+        harness.get_buffer_content().unwrap().len()
+    );
+
+    // Undo the indent from the second pane
+    prev_split(&mut harness);
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    assert!(harness
+        .get_buffer_content()
+        .unwrap()
+        .starts_with("Line 1\n"));
+
+    prev_split(&mut harness);
+    assert_eq!(line_at(&harness, harness.top_byte()), first_top_line);
+    assert_eq!(
+        harness.cursor_position(),
+        harness.get_buffer_content().unwrap().len()
+    );
+}
@@ -283,6 +283,15 @@ fn test_save_root_owned_file_shows_sudo_prompt() {
     harness.open_file(&file_path).unwrap();
     harness.render().unwrap();
 
+    // The file opens read-only; allow editing it
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Edit Anyway").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
     // Modify the content (add a space at the beginning)
     harness.type_text(" ").unwrap();
     harness.render().unwrap();
@@ -290,8 +299,8 @@ fn test_save_root_owned_file_shows_sudo_prompt() {
     // Verify buffer is modified
     harness.assert_screen_contains("*");
 
-    // Try to save - this triggers the in-place write path because file is owned by root
-    // BUG: This crashes with "Permission denied (os error 13)"
+    // Try to save - the file isn't writable, so this goes straight to sudo
+    // BUG: This crashed with "Permission denied (os error 13)"
     // EXPECTED: Shows "Permission denied. Save with sudo?" prompt
     let save_result = harness.send_key(KeyCode::Char('s'), KeyModifiers::CONTROL);
     assert!(
@@ -669,3 +669,97 @@ fn test_issue_384_indent_respects_tab_size_in_calculation() {
         visual_indent
     );
 }
+
+// =============================================================================
+// tab_size in soft wrapping and indentation conversion
+// =============================================================================
+
+fn run_command(harness: &mut EditorTestHarness, command_name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(command_name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Soft wrapping measures tabs with the buffer's tab_size, so a tab-indented
+/// line that fits at tab_size=4 stays on one row
+#[test]
+fn test_tab_size_used_for_soft_wrap_width() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.txt");
+    // Four tabs at tab_size=4 plus "X" take 17 columns (33 at width 8)
+    std::fs::write(&file_path, "\t\t\t\tX\nEND").unwrap();
+
+    let mut config = Config::default();
+    config.editor.tab_size = 4;
+    config.editor.line_wrap = true;
+    let mut harness = EditorTestHarness::with_config(30, 10, config).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    let first_row = harness.content_area_rows().0 as u16;
+    let first_line = harness.get_screen_row(first_row as usize);
+    assert!(
+        first_line.contains('X'),
+        "X should fit on the first row: {first_line:?}"
+    );
+    // X sits 16 columns after the start of the first tab
+    let arrow = first_line.chars().position(|c| c == '→').unwrap();
+    let x = first_line.chars().position(|c| c == 'X').unwrap();
+    assert_eq!(x - arrow, 16);
+    assert!(harness
+        .get_screen_row(first_row as usize + 1)
+        .contains("END"));
+}
+
+/// Converting indentation rewrites only leading whitespace, using tab_size
+/// for tab stops, and each conversion is a single undo step
+#[test]
+fn test_convert_indentation_commands() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.txt");
+    std::fs::write(&file_path, "\tfoo\tbar\n\t  baz\n  qux\n").unwrap();
+
+    let mut config = Config::default();
+    config.editor.tab_size = 4;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    run_command(&mut harness, "Convert Indentation to Spaces");
+    assert!(harness
+        .editor()
+        .get_status_message()
+        .is_some_and(|status| status.contains("Converted indentation to spaces")));
+    // The tab between "foo" and "bar" is not indentation
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "    foo\tbar\n      baz\n  qux\n"
+    );
+
+    run_command(&mut harness, "Convert Indentation to Tabs");
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "\tfoo\tbar\n\t  baz\n  qux\n"
+    );
+    // Pressing Tab now inserts a tab character
+    assert!(harness.editor().active_state().buffer_settings.use_tabs);
+
+    run_command(&mut harness, "Convert Indentation to Tabs");
+    assert!(harness
+        .editor()
+        .get_status_message()
+        .is_some_and(|status| status.contains("Indentation already converted")));
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "    foo\tbar\n      baz\n  qux\n"
+    );
+}
@@ -328,3 +328,59 @@ fn test_tab_indent_preserves_relative_cursor_position() {
         "Selection should be preserved after indent"
     );
 }
+
+/// Test that a partial selection grows to whole lines and stays selected
+/// across repeated Tab and Shift+Tab presses
+#[test]
+fn test_repeated_indent_keeps_full_line_selection() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.rs");
+
+    std::fs::write(&file_path, "fn a() {\n  one\n  two\n  three\n}\n").unwrap();
+
+    let config = Config::default();
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&file_path).unwrap();
+
+    // Select from the middle of "one" to the middle of "three"
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Tab, KeyModifiers::SHIFT).unwrap();
+    harness.render().unwrap();
+
+    // The two-space lines gain one level of indentation; the
+    // outdent removed a full level from the indent it had added
+    let content = harness.get_buffer_content().unwrap();
+    assert_eq!(content, "fn a() {\n      one\n      two\n      three\n}\n");
+
+    // The selection spans the three lines whole
+    let first_line = "fn a() {\n".len();
+    let last_line_end = content.find("three").unwrap() + "three".len();
+    assert_eq!(
+        harness.get_selection_range(),
+        Some(first_line..last_line_end)
+    );
+
+    // Outdenting past the original indentation only removes what's there
+    harness.send_key(KeyCode::Tab, KeyModifiers::SHIFT).unwrap();
+    harness.send_key(KeyCode::Tab, KeyModifiers::SHIFT).unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "fn a() {\none\ntwo\nthree\n}\n"
+    );
+    assert_eq!(
+        harness.get_selection_range(),
+        Some(first_line.."fn a() {\none\ntwo\nthree".len())
+    );
+}
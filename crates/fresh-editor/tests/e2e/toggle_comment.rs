@@ -2,6 +2,7 @@
 //!
 //! Tests that:
 //! - Toggle comment uses language-specific comment prefixes from config
+//! - Comments align to the smallest indentation and toggle back exactly
 //! - Selection is preserved after commenting/uncommenting
 
 use crate::common::harness::{EditorTestHarness, HarnessOptions};
@@ -390,3 +391,66 @@ fn test_toggle_comment_selection_at_buffer_end() {
         content
     );
 }
+
+/// Comments line up at the smallest indentation, and toggling twice restores
+/// the exact original bytes
+#[test]
+fn test_toggle_comment_rust_aligns_to_min_indent() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.rs");
+    let original = "fn main() {\n    let a = 1;\n\n        let b = 2;\n}\n";
+    std::fs::write(&file_path, original).unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    // Select the three lines inside the function body
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    for _ in 0..3 {
+        harness
+            .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+            .unwrap();
+    }
+
+    run_command(&mut harness, "Toggle Comment");
+    harness.assert_buffer_content("fn main() {\n    // let a = 1;\n\n    //     let b = 2;\n}\n");
+
+    run_command(&mut harness, "Toggle Comment");
+    harness.assert_buffer_content(original);
+
+    // Each toggle is a single undo step
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("fn main() {\n    // let a = 1;\n\n    //     let b = 2;\n}\n");
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content(original);
+}
+
+/// Lines are only uncommented when all of them are commented, so a shebang
+/// doesn't stop the rest of the script from being commented
+#[test]
+fn test_toggle_comment_shell_roundtrip() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.sh");
+    let original = "#!/bin/sh\necho hi\n  if true; then\n    echo ok\n  fi\n";
+    std::fs::write(&file_path, original).unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+    run_command(&mut harness, "Toggle Comment");
+    harness.assert_buffer_content(
+        "# #!/bin/sh\n# echo hi\n#   if true; then\n#     echo ok\n#   fi\n",
+    );
+
+    run_command(&mut harness, "Toggle Comment");
+    harness.assert_buffer_content(original);
+}
@@ -1,4 +1,4 @@
-use crate::common::harness::EditorTestHarness;
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
 use crossterm::event::{KeyCode, KeyModifiers};
 
 /// Test that undo skips over readonly actions (like cursor movement) and only undoes write actions
@@ -9,7 +9,8 @@ use crossterm::event::{KeyCode, KeyModifiers};
 /// 3. Undo once should undo the cursor movements AND the last typed character
 #[test]
 fn test_undo_skips_readonly_movement_actions() {
-    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let mut harness =
+        EditorTestHarness::create(80, 24, HarnessOptions::new().without_undo_grouping()).unwrap();
 
     // Type "hello"
     harness.type_text("hello").unwrap();
@@ -46,7 +47,8 @@ fn test_undo_skips_readonly_movement_actions() {
 /// Test that multiple undo steps skip over all readonly actions
 #[test]
 fn test_multiple_undo_skips_all_readonly_actions() {
-    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let mut harness =
+        EditorTestHarness::create(80, 24, HarnessOptions::new().without_undo_grouping()).unwrap();
 
     // Type "abc"
     harness.type_text("abc").unwrap();
@@ -82,7 +84,8 @@ fn test_multiple_undo_skips_all_readonly_actions() {
 /// Test that redo also skips readonly actions
 #[test]
 fn test_redo_skips_readonly_movement_actions() {
-    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let mut harness =
+        EditorTestHarness::create(80, 24, HarnessOptions::new().without_undo_grouping()).unwrap();
 
     // Type "xyz"
     harness.type_text("xyz").unwrap();
@@ -107,7 +110,8 @@ fn test_redo_skips_readonly_movement_actions() {
 /// Test undo/redo with mixed write and readonly actions
 #[test]
 fn test_undo_redo_with_mixed_actions() {
-    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let mut harness =
+        EditorTestHarness::create(80, 24, HarnessOptions::new().without_undo_grouping()).unwrap();
 
     // Type "ab"
     harness.type_text("ab").unwrap();
@@ -497,3 +501,110 @@ fn test_undo_to_empty_after_save_as() {
         final_content
     );
 }
+
+fn undo(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+}
+
+/// A word typed in one go is undone and redone in one step
+#[test]
+fn test_typed_word_is_one_undo_step() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("hello").unwrap();
+
+    undo(&mut harness);
+    harness.assert_buffer_content("");
+
+    harness
+        .send_key(KeyCode::Char('y'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("hello");
+}
+
+/// A newline ends the undo step of the typing before it
+#[test]
+fn test_newline_separates_undo_steps() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("hello").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("world").unwrap();
+    harness.assert_buffer_content("hello\nworld");
+
+    undo(&mut harness);
+    harness.assert_buffer_content("hello\n");
+    undo(&mut harness);
+    harness.assert_buffer_content("hello");
+    undo(&mut harness);
+    harness.assert_buffer_content("");
+}
+
+/// Backspaces in a row are undone together, separately from the typing
+#[test]
+fn test_consecutive_backspaces_are_one_undo_step() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("hello").unwrap();
+    for _ in 0..3 {
+        harness
+            .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+            .unwrap();
+    }
+    harness.assert_buffer_content("he");
+
+    undo(&mut harness);
+    harness.assert_buffer_content("hello");
+    undo(&mut harness);
+    harness.assert_buffer_content("");
+}
+
+/// A pause, a cursor jump or the Undo Boundary command starts a new step
+#[test]
+fn test_undo_step_boundaries() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("ab").unwrap();
+    harness.advance_time(std::time::Duration::from_secs(2));
+    harness.type_text("cd").unwrap();
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness.type_text("ef").unwrap();
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Undo Boundary").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("gh").unwrap();
+    harness.assert_buffer_content("efghabcd");
+
+    undo(&mut harness);
+    harness.assert_buffer_content("efabcd");
+    undo(&mut harness);
+    harness.assert_buffer_content("abcd");
+    undo(&mut harness);
+    harness.assert_buffer_content("ab");
+    undo(&mut harness);
+    harness.assert_buffer_content("");
+}
+
+/// Typing into and over an auto-closed pair is undone with the rest of the
+/// typing, without leaving a stray bracket behind
+#[test]
+fn test_auto_closed_pair_is_undone_with_the_typing() {
+    let mut config = fresh::config::Config::default();
+    config.editor.auto_indent = true;
+    let mut harness =
+        EditorTestHarness::create(80, 24, HarnessOptions::new().with_config(config)).unwrap();
+
+    harness.type_text("foo(bar").unwrap();
+    harness.assert_buffer_content("foo(bar)");
+    undo(&mut harness);
+    harness.assert_buffer_content("");
+
+    harness.type_text("foo(bar)").unwrap();
+    harness.assert_buffer_content("foo(bar)");
+    undo(&mut harness);
+    harness.assert_buffer_content("");
+}
@@ -1122,3 +1122,136 @@ fn test_left_arrow_at_long_position_file_loaded() {
          If this is 51, the bug is present: left arrow fell back to code point movement."
     );
 }
+
+/// Test that Ctrl+Right/Ctrl+Left move by Unicode words
+///
+/// Accented words are single words, each CJK ideograph is a word of its own,
+/// and every emoji is one unit:
+/// café(0-5) naïve(6-12) 日(13-16) 本(16-19) 語(19-22) 😀(23-27) 👍(27-31)
+#[test]
+fn test_ctrl_arrow_unicode_word_movement() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    let text = "café naïve 日本語 😀👍";
+    harness.type_text(text).unwrap();
+    harness.assert_buffer_content(text);
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+
+    let mut stops = Vec::new();
+    for _ in 0..7 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::CONTROL)
+            .unwrap();
+        stops.push(harness.cursor_position());
+    }
+    assert_eq!(
+        stops,
+        vec![5, 12, 16, 19, 22, 27, 31],
+        "Ctrl+Right should stop at the end of each Unicode word"
+    );
+
+    let mut stops = Vec::new();
+    for _ in 0..7 {
+        harness
+            .send_key(KeyCode::Left, KeyModifiers::CONTROL)
+            .unwrap();
+        stops.push(harness.cursor_position());
+    }
+    assert_eq!(
+        stops,
+        vec![27, 23, 19, 16, 13, 6, 0],
+        "Ctrl+Left should stop at the start of each Unicode word"
+    );
+}
+
+/// Test that a ZWJ emoji sequence is a single unit for Ctrl+Right/Ctrl+Left
+///
+/// 👨‍👩‍👧 is three emoji joined by zero width joiners: 4+3+4+3+4 = 18 bytes
+#[test]
+fn test_ctrl_arrow_zwj_emoji_sequence() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    let text = "a \u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467} b";
+    harness.type_text(text).unwrap();
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.cursor_position(), 1, "End of 'a'");
+
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(
+        harness.cursor_position(),
+        20,
+        "Ctrl+Right should skip the whole ZWJ sequence"
+    );
+
+    harness
+        .send_key(KeyCode::Left, KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(
+        harness.cursor_position(),
+        2,
+        "Ctrl+Left should return to the start of the ZWJ sequence"
+    );
+}
+
+/// Double-click selects the same Unicode word units that Ctrl+Left/Right move by:
+/// whole words with accents, single CJK characters, whole emoji clusters, and
+/// runs of punctuation or whitespace.
+#[test]
+fn test_double_click_selects_unicode_word() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    // Each line starts with an ASCII label so its screen column is easy to find;
+    // the characters before each click target are single-width unless noted.
+    let content = "l1 naïve word\n\
+                   l2 日本語\n\
+                   l3 😀👍 ok\n\
+                   l4 👨\u{200D}👩\u{200D}👧 ok\n\
+                   l5 wait...what\n\
+                   l6 a   b\n";
+    let _fixture = harness.load_buffer_from_text(content).unwrap();
+    harness.render().unwrap();
+
+    let range_of = |needle: &str| {
+        let start = content.find(needle).unwrap();
+        start..start + needle.len()
+    };
+
+    // (line label, screen columns after the label, expected selection)
+    let cases = [
+        // Inside "naïve", on the "ï"
+        ("l1 ", 2, range_of("naïve")),
+        // On "本" ("日" is double-width)
+        ("l2 ", 2, range_of("本")),
+        // On "👍" ("😀" is double-width)
+        ("l3 ", 2, range_of("👍")),
+        // On the ZWJ family emoji: the whole cluster
+        ("l4 ", 0, range_of("👨\u{200D}👩\u{200D}👧")),
+        // On the middle "." of "..."
+        ("l5 ", 5, range_of("...")),
+        // On the whitespace run between "a" and "b"
+        ("l6 ", 2, range_of("   ")),
+    ];
+
+    for (label, offset, expected) in cases {
+        let (col, row) = harness
+            .find_text_on_screen(label)
+            .unwrap_or_else(|| panic!("label {label:?} should be on screen"));
+        harness
+            .mouse_double_click(col + label.len() as u16 + offset, row)
+            .unwrap();
+
+        assert_eq!(
+            harness.get_selection_range(),
+            Some(expected.clone()),
+            "double-click on line {label:?} should select {:?}, got {:?}",
+            &content[expected.clone()],
+            harness.get_selected_text()
+        );
+    }
+}
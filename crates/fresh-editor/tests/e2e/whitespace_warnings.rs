@@ -0,0 +1,107 @@
+//! E2E tests for trailing-whitespace and mixed-indentation warnings.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use tempfile::TempDir;
+
+/// Gutter width for a small buffer: 1 (indicator) + 4 (digits) + 3 (" │ ") = 8
+const SMALL_BUFFER_GUTTER: u16 = 8;
+
+fn run_command(harness: &mut EditorTestHarness, command_name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(command_name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Content columns on `row` that carry the warning background
+fn warned_columns(harness: &EditorTestHarness, row: u16) -> Vec<u16> {
+    let warning_bg = harness.editor().theme().diagnostic_warning_bg;
+    (0..30)
+        .filter(|&col| {
+            harness
+                .get_cell_style(SMALL_BUFFER_GUTTER + col, row)
+                .is_some_and(|style| style.bg == Some(warning_bg))
+        })
+        .collect()
+}
+
+#[test]
+fn test_typed_whitespace_issues_are_highlighted() {
+    let temp_dir = TempDir::new().unwrap();
+    // Go indents with tabs, so Tab inserts a literal tab (8 columns wide)
+    let file_path = temp_dir.path().join("main.go");
+    std::fs::write(&file_path, "").unwrap();
+
+    let mut harness = EditorTestHarness::with_config(80, 24, Config::default()).unwrap();
+    harness.open_file(&file_path).unwrap();
+    run_command(&mut harness, "Toggle Whitespace Warnings");
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("Whitespace warnings shown")
+    );
+
+    // Spaces then a tab, some code, then trailing spaces
+    harness.type_text("  ").unwrap();
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.type_text("x := 1").unwrap();
+    harness.render().unwrap();
+
+    let row = harness.content_area_rows().0 as u16;
+    // Only the mixed indentation is flagged so far
+    assert_eq!(warned_columns(&harness, row), (0..8).collect::<Vec<_>>());
+
+    // Trailing spaces are flagged as they are typed
+    harness.type_text("  ").unwrap();
+    harness.render().unwrap();
+    let code_start = 8;
+    let code_end = code_start + "x := 1".len() as u16;
+    let mut expected: Vec<u16> = (0..8).collect();
+    expected.extend([code_end, code_end + 1]);
+    assert_eq!(warned_columns(&harness, row), expected);
+
+    // And cleared as soon as the line is clean again
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Delete, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Delete, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "\tx := 1");
+    assert!(warned_columns(&harness, row).is_empty());
+}
+
+#[test]
+fn test_whitespace_warnings_toggle_off() {
+    let mut config = Config::default();
+    config.editor.show_whitespace_warnings = true;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    let _fixture = harness.load_buffer_from_text("a  \nb\n").unwrap();
+    harness.render().unwrap();
+
+    let row = harness.content_area_rows().0 as u16;
+    assert_eq!(warned_columns(&harness, row), vec![1, 2]);
+    assert!(warned_columns(&harness, row + 1).is_empty());
+
+    run_command(&mut harness, "Toggle Whitespace Warnings");
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("Whitespace warnings hidden")
+    );
+    assert!(warned_columns(&harness, row).is_empty());
+}
@@ -6,7 +6,7 @@
 use crate::common::harness::EditorTestHarness;
 use crossterm::event::{KeyCode, KeyModifiers};
 use fresh::config::Config;
-use fresh::workspace::get_workspace_path;
+use fresh::workspace::{get_workspace_path, SerializedSplitDirection, SerializedSplitNode};
 use tempfile::TempDir;
 
 /// Test that session saves and restores open files
@@ -439,6 +439,115 @@ fn test_session_restores_scroll_position() {
     }
 }
 
+/// Test that the exact cursor offset and viewport top come back after restore
+#[test]
+fn test_session_restores_exact_cursor_and_viewport() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let file = project_dir.join("long.txt");
+    let content: String = (1..=200)
+        .map(|i| format!("Line {:03} content here\n", i))
+        .collect();
+    std::fs::write(&file, &content).unwrap();
+
+    let (saved_cursor, saved_top) = {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+
+        harness.open_file(&file).unwrap();
+        for _ in 0..150 {
+            harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+        }
+        for _ in 0..5 {
+            harness
+                .send_key(KeyCode::Right, KeyModifiers::NONE)
+                .unwrap();
+        }
+        harness.render().unwrap();
+
+        let cursor = harness.cursor_position();
+        let top = harness.top_byte();
+        assert_eq!(cursor, content.find("Line 151").unwrap() + 5);
+        assert!(top > 0, "Viewport should have scrolled");
+
+        harness.editor_mut().save_workspace().unwrap();
+        (cursor, top)
+    };
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        80,
+        24,
+        Config::default(),
+        project_dir.clone(),
+    )
+    .unwrap();
+    harness.editor_mut().try_restore_workspace().unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(harness.cursor_position(), saved_cursor);
+    assert_eq!(harness.top_byte(), saved_top);
+    harness.assert_screen_contains("Line 151");
+}
+
+/// Test that saved positions are clamped when the file shrank on disk
+#[test]
+fn test_session_clamps_positions_when_file_changed() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let file = project_dir.join("long.txt");
+    let content: String = (1..=200)
+        .map(|i| format!("Line {:03} content here\n", i))
+        .collect();
+    std::fs::write(&file, &content).unwrap();
+
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+
+        harness.open_file(&file).unwrap();
+        for _ in 0..150 {
+            harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+        }
+        harness.render().unwrap();
+        harness.editor_mut().save_workspace().unwrap();
+    }
+
+    // Rewrite the file much shorter, with multi-byte characters
+    let shrunk: String = (1..=20).map(|i| format!("Zeile {:02} äöü\n", i)).collect();
+    std::fs::write(&file, &shrunk).unwrap();
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        80,
+        24,
+        Config::default(),
+        project_dir.clone(),
+    )
+    .unwrap();
+    harness.editor_mut().try_restore_workspace().unwrap();
+    harness.render().unwrap();
+
+    let cursor = harness.cursor_position();
+    assert!(cursor <= shrunk.len());
+    assert!(shrunk.is_char_boundary(cursor));
+    let top = harness.top_byte();
+    assert!(top == 0 || shrunk.as_bytes()[top - 1] == b'\n');
+    assert_eq!(harness.get_buffer_content().unwrap(), shrunk);
+}
+
 /// Test that switching tabs before save preserves the active tab
 #[test]
 fn test_session_preserves_active_tab() {
@@ -1104,6 +1213,131 @@ fn test_session_restores_splits() {
     }
 }
 
+/// Helper: Create a horizontal split via command palette
+fn split_horizontal(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.type_text("split horiz").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Test that a horizontal split restores both panes, its orientation and the focused pane
+#[test]
+fn test_session_restores_horizontal_split_and_focus() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let file1 = project_dir.join("top.txt");
+    let file2 = project_dir.join("bottom.txt");
+    std::fs::write(&file1, "Top pane content").unwrap();
+    std::fs::write(&file2, "Bottom pane content").unwrap();
+
+    // First session: top/bottom split, focus moved back to the top pane
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+
+        harness.open_file(&file1).unwrap();
+        split_horizontal(&mut harness);
+        harness.open_file(&file2).unwrap();
+        prev_split(&mut harness);
+        harness.assert_buffer_content("Top pane content");
+
+        harness.editor_mut().save_workspace().unwrap();
+    }
+
+    // Second session: both panes and the focus come back
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+
+        harness.editor_mut().try_restore_workspace().unwrap();
+        harness.render().unwrap();
+
+        assert_eq!(harness.editor().get_split_count(), 2);
+        assert!(matches!(
+            harness.editor().capture_workspace().split_layout,
+            SerializedSplitNode::Split {
+                direction: SerializedSplitDirection::Horizontal,
+                ..
+            }
+        ));
+        harness.assert_screen_contains("Top pane content");
+        harness.assert_screen_contains("Bottom pane content");
+
+        // The top pane had focus when the session was saved
+        harness.assert_buffer_content("Top pane content");
+    }
+}
+
+/// Test that a pane whose file was deleted restores as an empty placeholder
+#[test]
+fn test_session_restores_split_with_missing_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let file1 = project_dir.join("kept.txt");
+    let file2 = project_dir.join("deleted.txt");
+    std::fs::write(&file1, "Kept pane content").unwrap();
+    std::fs::write(&file2, "Deleted pane content").unwrap();
+
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+
+        harness.open_file(&file1).unwrap();
+        split_horizontal(&mut harness);
+        harness.open_file(&file2).unwrap();
+
+        harness.editor_mut().save_workspace().unwrap();
+    }
+
+    std::fs::remove_file(&file2).unwrap();
+
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+
+        harness.editor_mut().try_restore_workspace().unwrap();
+        harness.render().unwrap();
+
+        // The layout survives; the focused pane is an empty placeholder
+        assert_eq!(harness.editor().get_split_count(), 2);
+        harness.assert_buffer_content("");
+        harness.assert_screen_not_contains("Deleted pane content");
+
+        prev_split(&mut harness);
+        harness.assert_buffer_content("Kept pane content");
+    }
+}
+
 /// Test that session saves and restores files outside the project directory
 #[test]
 fn test_session_restores_external_files() {
@@ -1534,3 +1768,207 @@ fn test_session_restores_split_labels() {
         );
     }
 }
+
+/// Test that two named sessions each restore their own set of files
+#[test]
+fn test_named_workspaces_restore_independently() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let alpha1 = project_dir.join("alpha_one.txt");
+    let alpha2 = project_dir.join("alpha_two.txt");
+    let beta = project_dir.join("beta_only.txt");
+    std::fs::write(&alpha1, "alpha one").unwrap();
+    std::fs::write(&alpha2, "alpha two").unwrap();
+    std::fs::write(&beta, "beta only").unwrap();
+
+    let new_harness = || {
+        EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap()
+    };
+
+    {
+        let mut harness = new_harness();
+        harness.open_file(&alpha1).unwrap();
+        harness.open_file(&alpha2).unwrap();
+        harness.editor_mut().save_workspace_as("alpha").unwrap();
+    }
+    {
+        let mut harness = new_harness();
+        harness.open_file(&beta).unwrap();
+        harness.editor_mut().save_workspace_as("beta").unwrap();
+    }
+
+    let mut harness = new_harness();
+
+    let mut workspaces = harness.editor().list_workspaces().unwrap();
+    workspaces.sort_by(|a, b| a.name.cmp(&b.name));
+    let summary: Vec<(&str, usize)> = workspaces
+        .iter()
+        .map(|w| (w.name.as_str(), w.open_files))
+        .collect();
+    assert_eq!(summary, vec![("alpha", 2), ("beta", 1)]);
+
+    assert!(harness.editor_mut().restore_workspace("alpha").unwrap());
+    harness.render().unwrap();
+    harness.assert_screen_contains("alpha_one.txt");
+    harness.assert_screen_contains("alpha_two.txt");
+    harness.assert_screen_not_contains("beta_only.txt");
+    harness.assert_buffer_content("alpha two");
+
+    assert!(harness.editor_mut().restore_workspace("beta").unwrap());
+    harness.render().unwrap();
+    harness.assert_screen_contains("beta_only.txt");
+    harness.assert_screen_not_contains("alpha_one.txt");
+    harness.assert_screen_not_contains("alpha_two.txt");
+    harness.assert_buffer_content("beta only");
+
+    // Named sessions don't touch the implicit workspace
+    assert!(!harness.editor_mut().restore_workspace("missing").unwrap());
+    assert!(!get_workspace_path(&project_dir).unwrap().exists());
+}
+
+/// Test that the workspace switcher asks before discarding unsaved changes
+#[test]
+fn test_switch_workspace_prompts_on_unsaved_changes() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let saved = project_dir.join("saved.txt");
+    let scratch = project_dir.join("scratch.txt");
+    std::fs::write(&saved, "saved session file").unwrap();
+    std::fs::write(&scratch, "scratch").unwrap();
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        80,
+        24,
+        Config::default(),
+        project_dir.clone(),
+    )
+    .unwrap();
+
+    harness.open_file(&saved).unwrap();
+    harness.editor_mut().save_workspace_as("work").unwrap();
+
+    harness.open_file(&scratch).unwrap();
+    harness.type_text("edited ").unwrap();
+
+    let switch_workspace = |harness: &mut EditorTestHarness| {
+        harness
+            .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.type_text("Switch Workspace").unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+        harness.render().unwrap();
+        // The switcher lists the saved session with its file count
+        harness.assert_screen_contains("work");
+        harness.assert_screen_contains("1 file(s)");
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+        harness.render().unwrap();
+        harness.assert_screen_contains("unsaved changes");
+    };
+
+    // Cancelling keeps the edited buffer
+    switch_workspace(&mut harness);
+    harness.type_text("c").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_buffer_content("edited scratch");
+
+    // Discarding closes it and loads the session
+    switch_workspace(&mut harness);
+    harness.type_text("d").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_buffer_content("saved session file");
+    harness.assert_screen_not_contains("scratch.txt");
+}
+
+/// Restoring a workspace after a crash offers the recovered changes for the
+/// buffer that had them, with a summary of the diff
+#[test]
+fn test_restore_offers_recovery_after_crash() {
+    use fresh::config_io::DirectoryContext;
+    use fresh::services::recovery::SessionInfo;
+
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+    let dir_context = DirectoryContext::for_testing(&temp_dir.path().join("context"));
+
+    let untouched = project_dir.join("untouched.txt");
+    let edited = project_dir.join("edited.txt");
+    std::fs::write(&untouched, "untouched file\n").unwrap();
+    std::fs::write(&edited, "original line\n").unwrap();
+
+    let new_harness = || {
+        EditorTestHarness::with_shared_dir_context(
+            120,
+            24,
+            Config::default(),
+            project_dir.clone(),
+            dir_context.clone(),
+        )
+        .unwrap()
+    };
+
+    {
+        let mut harness = new_harness();
+        harness.open_file(&untouched).unwrap();
+        harness.open_file(&edited).unwrap();
+        harness.type_text("crashed edit ").unwrap();
+
+        harness.advance_time(std::time::Duration::from_millis(2100));
+        let saved = harness
+            .editor_mut()
+            .auto_recovery_save_dirty_buffers()
+            .unwrap();
+        assert_eq!(saved, 1, "Only the edited buffer has recovery data");
+        harness.editor().flush_recovery_writes().unwrap();
+        harness.editor_mut().save_workspace().unwrap();
+    }
+
+    // Leave behind the lock of a session whose process is gone
+    let lock = SessionInfo {
+        pid: 4_194_304,
+        process_start_time: Some(0),
+        ..SessionInfo::new()
+    };
+    std::fs::write(
+        dir_context.recovery_dir().join("session.lock"),
+        serde_json::to_string(&lock).unwrap(),
+    )
+    .unwrap();
+
+    let mut harness = new_harness();
+    harness.editor_mut().try_restore_workspace().unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("'edited.txt' has unsaved changes");
+    harness.assert_screen_contains("+1 -1 lines");
+    harness.assert_screen_not_contains("'untouched.txt' has unsaved changes");
+    harness.assert_buffer_content("original line\n");
+
+    harness.type_text("r").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_buffer_content("crashed edit original line\n");
+    harness.assert_screen_contains("Recovered unsaved changes for 'edited.txt'");
+}
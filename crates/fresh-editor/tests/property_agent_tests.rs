@@ -113,12 +113,12 @@ impl AgentHarness {
     }
 
     fn write_file(&mut self, path: &str, data: &[u8]) -> Option<AgentResponse> {
-        self.send_request("write", write_params(path, data))
+        self.send_request("write", write_params(path, data, false))
     }
 
     fn read_file(&mut self, path: &str) -> Option<Vec<u8>> {
         let (data_chunks, _resp) =
-            self.send_request_with_data("read", read_params(path, None, None))?;
+            self.send_request_with_data("read", read_params(path, None, None, false))?;
 
         // Collect and decode all data chunks
         let mut result = Vec::new();
@@ -11,9 +11,11 @@
 use fresh::model::buffer::TextBuffer;
 use fresh::model::filesystem::{FileSystem, WriteOp};
 use fresh::services::remote::{
-    spawn_local_agent, spawn_local_agent_with_capacity, RemoteFileSystem, TEST_RECV_DELAY_US,
+    spawn_local_agent, spawn_local_agent_with_capacity, start_keepalive, watch_params,
+    AgentChannel, AgentResponse, Capabilities, FileChangeKind, RemoteFileSystem, SecretString,
+    SudoPasswordRejected, AGENT_SOURCE, FEATURE_FIND, FEATURE_GZIP, TEST_RECV_DELAY_US,
 };
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 /// Simple pseudo-random number generator (xorshift64) to avoid external deps.
@@ -195,6 +197,358 @@ fn test_metadata() {
     );
 }
 
+#[test]
+fn test_stat_many_matches_metadata() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+
+    let mut paths: Vec<std::path::PathBuf> = (0..100)
+        .map(|i| {
+            let path = temp_dir.path().join(format!("file_{i:03}.txt"));
+            std::fs::write(&path, "x".repeat(i)).unwrap();
+            path
+        })
+        .collect();
+    // One missing path in the middle must not fail the rest of the batch
+    paths.insert(50, temp_dir.path().join("missing.txt"));
+
+    let results = fs.stat_many(&paths);
+    assert_eq!(results.len(), paths.len(), "One result per input path");
+
+    for (path, result) in paths.iter().zip(&results) {
+        match fs.metadata(path) {
+            Ok(expected) => {
+                let meta = result.as_ref().unwrap();
+                assert_eq!(meta.size, expected.size, "Size of {:?}", path);
+                assert_eq!(meta.modified, expected.modified, "Mtime of {:?}", path);
+                assert_eq!(meta.is_readonly, expected.is_readonly);
+            }
+            Err(expected) => {
+                let err = result.as_ref().unwrap_err();
+                assert_eq!(err.kind(), expected.kind(), "Error kind of {:?}", path);
+            }
+        }
+    }
+    assert_eq!(
+        results[50].as_ref().unwrap_err().kind(),
+        std::io::ErrorKind::NotFound
+    );
+}
+
+#[test]
+fn test_read_file_with_progress() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+
+    // Several agent chunks worth of data
+    let path = temp_dir.path().join("progress.bin");
+    let content: Vec<u8> = (0..600_000u32).map(|i| (i % 251) as u8).collect();
+    std::fs::write(&path, &content).unwrap();
+
+    let mut reports = Vec::new();
+    let data = fs
+        .read_file_with_progress(&path, &mut |done, total| reports.push((done, total)))
+        .unwrap();
+    assert_eq!(data, content);
+
+    let size = content.len() as u64;
+    assert!(
+        reports.len() > 3,
+        "Expected per-chunk reports: {:?}",
+        reports
+    );
+    assert_eq!(reports.first(), Some(&(0, Some(size))));
+    assert_eq!(reports.last(), Some(&(size, Some(size))));
+    assert!(
+        reports.windows(2).all(|w| w[0].0 <= w[1].0),
+        "Progress must be monotonic: {:?}",
+        reports
+    );
+
+    // Empty files still report a start and a completion
+    let empty = temp_dir.path().join("empty.bin");
+    std::fs::write(&empty, b"").unwrap();
+    let mut reports = Vec::new();
+    fs.read_file_with_progress(&empty, &mut |done, total| reports.push((done, total)))
+        .unwrap();
+    assert_eq!(reports, vec![(0, Some(0)), (0, Some(0))]);
+}
+
+#[test]
+fn test_find_glob() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+
+    let root = temp_dir.path();
+    for file in [
+        "main.rs",
+        "README.md",
+        "src/lib.rs",
+        "src/util/mod.rs",
+        "src/util/strings.rs",
+        "src/util/notes.txt",
+        "tests/deep/er/still.rs",
+        ".git/hooks/pre-commit.rs",
+    ] {
+        let path = root.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, b"").unwrap();
+    }
+    // A symlink loop must not make the walk run forever
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(root, root.join("src/loop")).unwrap();
+
+    let mut found = fs.find(root, "**/*.rs", 0).unwrap();
+    found.sort();
+    let expected: Vec<std::path::PathBuf> = [
+        "main.rs",
+        "src/lib.rs",
+        "src/util/mod.rs",
+        "src/util/strings.rs",
+        "tests/deep/er/still.rs",
+    ]
+    .iter()
+    .map(std::path::PathBuf::from)
+    .collect();
+    assert_eq!(found, expected);
+
+    // Single-star patterns stay within one directory
+    assert_eq!(
+        fs.find(root, "src/*.rs", 0).unwrap(),
+        vec![std::path::PathBuf::from("src/lib.rs")]
+    );
+
+    // Results stop at max_results
+    assert_eq!(fs.find(root, "**/*.rs", 2).unwrap().len(), 2);
+}
+
+#[test]
+fn test_git_files_keeps_tracked_and_prunes_ignored() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+    let root = temp_dir.path();
+    let not_cancelled = AtomicBool::new(false);
+
+    // Outside a work tree the caller has to walk the tree itself
+    assert!(fs.git_files(root, 0, &not_cancelled).is_err());
+
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(root)
+            .output()
+            .is_ok_and(|output| output.status.success())
+    };
+    if !git(&["init", "-q"]) {
+        eprintln!("Skipping test: git is not available");
+        return;
+    }
+    for (file, contents) in [
+        (".gitignore", "target/\n*.log\n"),
+        ("src/main.rs", ""),
+        ("target/debug/app", ""),
+        ("debug.log", ""),
+        ("release.log", ""),
+    ] {
+        let path = root.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, contents).unwrap();
+    }
+    assert!(git(&["add", "-f", "release.log"]));
+
+    let mut found = fs.git_files(root, 0, &not_cancelled).unwrap();
+    found.sort();
+    let expected: Vec<std::path::PathBuf> = [".gitignore", "release.log", "src/main.rs"]
+        .iter()
+        .map(std::path::PathBuf::from)
+        .collect();
+    assert_eq!(found, expected);
+}
+
+#[test]
+fn test_find_cancelled_by_dropping_receiver() {
+    let Some((fs, temp_dir, rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+
+    let root = temp_dir.path();
+    for dir in 0..50 {
+        let dir = root.join(format!("dir{dir}"));
+        std::fs::create_dir_all(&dir).unwrap();
+        for file in 0..20 {
+            std::fs::write(dir.join(format!("file{file}.txt")), b"").unwrap();
+        }
+    }
+
+    let mut batches = fs.find_streaming(root, "**", 0).unwrap();
+    let first = rt.block_on(batches.recv()).unwrap().unwrap();
+    assert!(!first.is_empty());
+    drop(batches);
+
+    // The agent stops the walk and keeps serving requests
+    std::fs::write(root.join("main.rs"), b"").unwrap();
+    assert_eq!(
+        fs.find(root, "*.rs", 0).unwrap(),
+        vec![std::path::PathBuf::from("main.rs")]
+    );
+
+    // A blocking find gives up as soon as it is cancelled
+    let cancelled = AtomicBool::new(true);
+    let err = fs.find_files(root, "**", 0, &cancelled).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::Interrupted);
+}
+
+#[test]
+fn test_watch_reports_modification() {
+    let Some((fs, temp_dir, rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+
+    let path = temp_dir.path().join("watched.txt");
+    std::fs::write(&path, b"before").unwrap();
+
+    let mut events = fs.watch(&path).unwrap();
+    // Changes to other files in the same directory are filtered out
+    std::fs::write(temp_dir.path().join("other.txt"), b"noise").unwrap();
+    std::fs::write(&path, b"after").unwrap();
+
+    let event = rt.block_on(events.recv()).expect("watch stream ended");
+    assert_eq!(event.path, path);
+    assert_eq!(event.kind, FileChangeKind::Modified);
+
+    std::fs::remove_file(&path).unwrap();
+    let event = rt.block_on(events.recv()).expect("watch stream ended");
+    assert_eq!(event.kind, FileChangeKind::Removed);
+}
+
+#[test]
+fn test_watch_polling_fallback() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let Ok(channel) = rt.block_on(spawn_local_agent()) else {
+        eprintln!("Skipping test: could not spawn local agent");
+        return;
+    };
+
+    let path = temp_dir.path().join("polled.txt");
+    std::fs::write(&path, b"before").unwrap();
+
+    let (id, mut data_rx, _result_rx) = rt
+        .block_on(
+            channel
+                .request_streaming_with_id("watch", watch_params(&path.to_string_lossy(), 0, true)),
+        )
+        .unwrap();
+    // The empty acknowledgement means the first snapshot has been taken
+    let ack = rt.block_on(data_rx.recv()).unwrap();
+    assert_eq!(ack["events"], serde_json::json!([]));
+    std::fs::write(&path, b"after, and longer").unwrap();
+
+    let batch = rt.block_on(data_rx.recv()).expect("watch stream ended");
+    assert_eq!(batch["events"][0]["kind"], "modified");
+    assert_eq!(batch["events"][0]["path"], path.to_string_lossy().as_ref());
+
+    rt.block_on(channel.cancel(id)).unwrap();
+    assert!(
+        rt.block_on(data_rx.recv()).is_none(),
+        "Cancel ends the stream"
+    );
+}
+
+#[test]
+fn test_unsupported_features_are_gated() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let Ok(channel) = rt.block_on(spawn_local_agent()) else {
+        eprintln!("Skipping test: could not spawn local agent");
+        return;
+    };
+    let caps = channel.capabilities();
+    assert!(
+        caps.supports(FEATURE_FIND),
+        "Agent negotiates find: {:?}",
+        caps
+    );
+
+    // Simulate an older agent that only knows about compression
+    channel.set_capabilities(Capabilities {
+        version: caps.version,
+        features: vec![FEATURE_GZIP.to_string()],
+    });
+    let fs = RemoteFileSystem::new(channel.clone(), "test@localhost".to_string());
+    let path = temp_dir.path().join("a.rs");
+    std::fs::write(&path, b"fn main() {}").unwrap();
+
+    let err = fs.find(temp_dir.path(), "**/*.rs", 0).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+    assert_eq!(
+        fs.watch(&path).unwrap_err().kind(),
+        std::io::ErrorKind::Unsupported
+    );
+    assert_eq!(
+        fs.count_lines(&path).unwrap_err().kind(),
+        std::io::ErrorKind::Unsupported
+    );
+
+    // stat_many falls back to one stat per path
+    let results = fs.stat_many(&[path.clone(), temp_dir.path().join("missing")]);
+    assert_eq!(results[0].as_ref().unwrap().size, 12);
+    assert!(results[1].is_err());
+}
+
+#[test]
+fn test_concurrent_reads_over_one_channel() {
+    let Some((fs, temp_dir, rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+
+    // Mixed sizes so small reads complete while large ones are still streaming
+    let paths: Vec<std::path::PathBuf> = (0..10)
+        .map(|i| temp_dir.path().join(format!("concurrent_{i}.bin")))
+        .collect();
+    let contents: Vec<Vec<u8>> = (0..10)
+        .map(|i| {
+            let len = if i % 2 == 0 { 300_000 } else { 100 + i };
+            (0..len).map(|b| (b * 7 + i) as u8).collect()
+        })
+        .collect();
+    for (path, content) in paths.iter().zip(&contents) {
+        std::fs::write(path, content).unwrap();
+    }
+
+    let p = &paths;
+    let results = rt.block_on(async {
+        let (r0, r1, r2, r3, r4, r5, r6, r7, r8, r9) = tokio::join!(
+            fs.read_file_async(&p[0]),
+            fs.read_file_async(&p[1]),
+            fs.read_file_async(&p[2]),
+            fs.read_file_async(&p[3]),
+            fs.read_file_async(&p[4]),
+            fs.read_file_async(&p[5]),
+            fs.read_file_async(&p[6]),
+            fs.read_file_async(&p[7]),
+            fs.read_file_async(&p[8]),
+            fs.read_file_async(&p[9]),
+        );
+        [r0, r1, r2, r3, r4, r5, r6, r7, r8, r9]
+    });
+
+    for (i, (result, expected)) in results.into_iter().zip(&contents).enumerate() {
+        assert_eq!(&result.unwrap(), expected, "Content of file {}", i);
+    }
+}
+
 #[test]
 fn test_read_file_larger_than_threshold() {
     // Test reading a file larger than LARGE_FILE_THRESHOLD_BYTES (1MB)
@@ -533,6 +887,651 @@ fn test_write_patched_preserves_permissions() {
     }
 }
 
+#[test]
+fn test_available_space() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+
+    let dir_space = fs.available_space(temp_dir.path()).unwrap();
+    assert!(dir_space > 0);
+
+    // A file that doesn't exist yet reports the space of its directory
+    let new_file = temp_dir.path().join("not-yet-saved.txt");
+    let file_space = fs.available_space(&new_file).unwrap();
+    assert!(file_space > 0);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_is_writable() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+
+    let path = temp_dir.path().join("file.txt");
+    std::fs::write(&path, b"content").unwrap();
+    assert!(fs.is_writable(&path));
+
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o444)).unwrap();
+    assert!(!fs.is_writable(&path));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlink_metadata_does_not_follow() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+
+    let target = temp_dir.path().join("target.txt");
+    let link = temp_dir.path().join("link.txt");
+    std::fs::write(&target, b"hello world").unwrap();
+    std::os::unix::fs::symlink(&target, &link).unwrap();
+
+    // Following the link describes the target
+    let followed = fs.metadata(&link).unwrap();
+    assert_eq!(followed.size, 11);
+    assert!(!followed.is_symlink);
+    assert!(fs.is_file(&link).unwrap());
+
+    // Not following describes the link itself
+    let unfollowed = fs.symlink_metadata(&link).unwrap();
+    assert!(unfollowed.is_symlink);
+    assert_eq!(unfollowed.symlink_target.as_deref(), Some(target.as_path()));
+    assert_eq!(unfollowed.size, target.as_os_str().len() as u64);
+
+    assert_eq!(fs.read_link(&link).unwrap(), target);
+    assert!(!fs.symlink_metadata(&target).unwrap().is_symlink);
+    assert!(fs.read_link(&target).is_err());
+
+    // A dangling link still has link metadata but no followed metadata
+    std::fs::remove_file(&target).unwrap();
+    assert!(fs.metadata(&link).is_err());
+    assert!(fs.symlink_metadata(&link).unwrap().is_symlink);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_write_file_preserves_permissions() {
+    use std::io::Write;
+    use std::os::unix::fs::PermissionsExt;
+
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+
+    let path = temp_dir.path().join("script.sh");
+    fs.write_file(&path, b"#!/bin/sh\necho old\n").unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    // Overwrite through both write paths
+    fs.write_file(&path, b"#!/bin/sh\necho new\n").unwrap();
+    let perms = std::fs::metadata(&path).unwrap().permissions();
+    assert_eq!(
+        perms.mode() & 0o777,
+        0o755,
+        "Permissions should be preserved after write_file"
+    );
+
+    let mut writer = fs.create_file(&path).unwrap();
+    writer.write_all(b"#!/bin/sh\necho newer\n").unwrap();
+    writer.sync_all().unwrap();
+    drop(writer);
+    let perms = std::fs::metadata(&path).unwrap().permissions();
+    assert_eq!(
+        perms.mode() & 0o777,
+        0o755,
+        "Permissions should be preserved after create_file"
+    );
+    assert_eq!(std::fs::read(&path).unwrap(), b"#!/bin/sh\necho newer\n");
+
+    // A brand-new file gets the same default mode as a locally created one,
+    // i.e. the umask is honored (the agent inherits our umask).
+    let local = temp_dir.path().join("local.txt");
+    std::fs::write(&local, b"").unwrap();
+    let remote = temp_dir.path().join("remote.txt");
+    fs.write_file(&remote, b"").unwrap();
+    assert_eq!(
+        std::fs::metadata(&remote).unwrap().permissions().mode() & 0o777,
+        std::fs::metadata(&local).unwrap().permissions().mode() & 0o777,
+        "New files should honor the umask"
+    );
+}
+
+#[test]
+fn test_remove_file() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+
+    let path = temp_dir.path().join("doomed.txt");
+    fs.write_file(&path, b"bye").unwrap();
+
+    fs.remove_file(&path).unwrap();
+    assert!(!path.exists(), "File should be removed");
+
+    // Removing it again reports NotFound rather than a generic error
+    let err = fs.remove_file(&path).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+
+    // Directories are left alone, even empty ones
+    let dir = temp_dir.path().join("empty");
+    fs.create_dir(&dir).unwrap();
+    let err = fs.remove_file(&dir).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::IsADirectory);
+    assert!(dir.is_dir());
+}
+
+#[test]
+fn test_remove_dir_all_nested() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+
+    let root = temp_dir.path().join("tree");
+    fs.create_dir_all(&root.join("a/b/c")).unwrap();
+    fs.create_dir_all(&root.join("d")).unwrap();
+    fs.write_file(&root.join("top.txt"), b"top").unwrap();
+    fs.write_file(&root.join("a/b/mid.txt"), b"mid").unwrap();
+    fs.write_file(&root.join("a/b/c/leaf.txt"), b"leaf")
+        .unwrap();
+
+    fs.remove_dir_all(&root).unwrap();
+    assert!(!root.exists(), "Whole tree should be removed");
+    assert!(temp_dir.path().exists(), "Parent must be left alone");
+
+    let err = fs.remove_dir_all(&root).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
+
+#[test]
+fn test_remove_non_recursive() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+
+    let root = temp_dir.path().join("tree");
+    fs.create_dir_all(&root.join("sub")).unwrap();
+    fs.write_file(&root.join("sub/file.txt"), b"data").unwrap();
+
+    // Non-empty directory without recursive must fail and leave everything intact
+    let err = fs.remove(&root, false).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::DirectoryNotEmpty);
+    assert!(root.join("sub/file.txt").exists());
+
+    // Emptying it out first makes the non-recursive remove succeed
+    fs.remove(&root.join("sub/file.txt"), false).unwrap();
+    fs.remove(&root.join("sub"), false).unwrap();
+    fs.remove(&root, false).unwrap();
+    assert!(!root.exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_remove_dir_all_does_not_follow_symlinks() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+
+    let target = temp_dir.path().join("target");
+    fs.create_dir(&target).unwrap();
+    fs.write_file(&target.join("keep.txt"), b"keep").unwrap();
+
+    let link = temp_dir.path().join("link");
+    std::os::unix::fs::symlink(&target, &link).unwrap();
+
+    fs.remove_file(&link).unwrap();
+    assert!(std::fs::symlink_metadata(&link).is_err(), "Link removed");
+    assert!(target.join("keep.txt").exists(), "Target left untouched");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_reconnect_after_agent_killed() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let Ok(channel) = rt.block_on(spawn_local_agent()) else {
+        eprintln!("Skipping test: could not spawn local agent");
+        return;
+    };
+    let fs = RemoteFileSystem::new(channel.clone(), "test@localhost".to_string());
+
+    let path = temp_dir.path().join("survivor.txt");
+    std::fs::write(&path, b"still here").unwrap();
+    assert_eq!(fs.read_file(&path).unwrap(), b"still here");
+
+    let agent_pid = |channel: &Arc<AgentChannel>| {
+        channel
+            .request_blocking("info", serde_json::json!({}))
+            .unwrap()["pid"]
+            .as_u64()
+            .unwrap()
+    };
+    let old_pid = agent_pid(&channel);
+    let status = std::process::Command::new("kill")
+        .arg("-9")
+        .arg(old_pid.to_string())
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    // The read is idempotent, so it is replayed against a respawned agent
+    assert_eq!(fs.read_file(&path).unwrap(), b"still here");
+    assert!(channel.is_connected());
+    assert_ne!(
+        agent_pid(&channel),
+        old_pid,
+        "Agent should have been respawned"
+    );
+}
+
+#[test]
+fn test_keepalive_keeps_idle_connection_healthy() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let Ok(channel) = rt.block_on(spawn_local_agent()) else {
+        eprintln!("Skipping test: could not spawn local agent");
+        return;
+    };
+    let keepalive = {
+        let _guard = rt.enter();
+        start_keepalive(&channel, std::time::Duration::from_millis(20))
+    };
+
+    // With no file operations, traffic can only come from answered pings.
+    // Wait for a few heartbeats to go by.
+    let start = channel.bytes_received();
+    while channel.bytes_received() < start + 3 * 20 {
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+    assert!(channel.is_connected());
+    assert!(!keepalive.is_finished());
+
+    let fs = RemoteFileSystem::new(channel.clone(), "test@localhost".to_string());
+    let path = temp_dir.path().join("idle.txt");
+    std::fs::write(&path, b"still alive").unwrap();
+    assert_eq!(fs.read_file(&path).unwrap(), b"still alive");
+    keepalive.abort();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_keepalive_marks_unresponsive_agent_lost() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let Ok(channel) = rt.block_on(spawn_local_agent()) else {
+        eprintln!("Skipping test: could not spawn local agent");
+        return;
+    };
+    let pid = channel
+        .request_blocking("info", serde_json::json!({}))
+        .unwrap()["pid"]
+        .as_u64()
+        .unwrap()
+        .to_string();
+    let signal = |sig: &str| {
+        let status = std::process::Command::new("kill")
+            .args([sig, &pid])
+            .status()
+            .unwrap();
+        assert!(status.success());
+    };
+
+    // A stopped agent keeps the pipe open but never answers
+    signal("-STOP");
+    let keepalive = {
+        let _guard = rt.enter();
+        start_keepalive(&channel, std::time::Duration::from_millis(20))
+    };
+    while channel.is_connected() {
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+    keepalive.abort();
+    signal("-KILL");
+
+    // The next request reconnects to a fresh agent
+    assert!(channel
+        .request_blocking("info", serde_json::json!({}))
+        .is_ok());
+    assert!(channel.is_connected());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_keepalive_detects_unresponsive_agent_during_watch() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let Ok(channel) = rt.block_on(spawn_local_agent()) else {
+        eprintln!("Skipping test: could not spawn local agent");
+        return;
+    };
+    let fs = RemoteFileSystem::new(channel.clone(), "test@localhost".to_string());
+    let path = temp_dir.path().join("watched.txt");
+    std::fs::write(&path, b"before").unwrap();
+    let _events = fs.watch(&path).unwrap();
+
+    let pid = channel
+        .request_blocking("info", serde_json::json!({}))
+        .unwrap()["pid"]
+        .as_u64()
+        .unwrap()
+        .to_string();
+    let signal = |sig: &str| {
+        let status = std::process::Command::new("kill")
+            .args([sig, &pid])
+            .status()
+            .unwrap();
+        assert!(status.success());
+    };
+
+    // The open watch stays pending, but must not stop the keepalive from
+    // noticing that the agent went silent
+    signal("-STOP");
+    let keepalive = {
+        let _guard = rt.enter();
+        start_keepalive(&channel, std::time::Duration::from_millis(20))
+    };
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while channel.is_connected() {
+        assert!(
+            std::time::Instant::now() < deadline,
+            "Keepalive should mark the connection lost despite the open watch"
+        );
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+    keepalive.abort();
+    signal("-KILL");
+
+    // The next request reconnects to a fresh agent
+    assert!(channel
+        .request_blocking("info", serde_json::json!({}))
+        .is_ok());
+    assert!(channel.is_connected());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_keepalive_fails_request_the_agent_never_answers() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let Ok(channel) = rt.block_on(spawn_local_agent()) else {
+        eprintln!("Skipping test: could not spawn local agent");
+        return;
+    };
+    let pid = channel
+        .request_blocking("info", serde_json::json!({}))
+        .unwrap()["pid"]
+        .as_u64()
+        .unwrap()
+        .to_string();
+    let signal = |sig: &str| {
+        let status = std::process::Command::new("kill")
+            .args([sig, &pid])
+            .status()
+            .unwrap();
+        assert!(status.success());
+    };
+
+    // A request sent to a stopped agent stays pending until the keepalive
+    // gives up on the connection, then is retried on a fresh agent
+    signal("-STOP");
+    let keepalive = {
+        let _guard = rt.enter();
+        start_keepalive(&channel, std::time::Duration::from_millis(20))
+    };
+    let stuck = rt.spawn({
+        let channel = channel.clone();
+        async move { channel.request("info", serde_json::json!({})).await }
+    });
+    let result = rt
+        .block_on(async { tokio::time::timeout(std::time::Duration::from_secs(10), stuck).await })
+        .expect("Keepalive should notice the unanswered request")
+        .unwrap();
+    assert!(result.is_ok());
+    keepalive.abort();
+    signal("-KILL");
+}
+
+#[test]
+fn test_compressed_transfer_roundtrip() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let (Ok(plain), Ok(compressed)) = (
+        rt.block_on(spawn_local_agent()),
+        rt.block_on(spawn_local_agent()),
+    ) else {
+        eprintln!("Skipping test: could not spawn local agent");
+        return;
+    };
+
+    assert!(!plain.compression_enabled(), "Compression is opt-in");
+    compressed.set_compression(true);
+    assert!(
+        compressed.compression_enabled(),
+        "Local agent should advertise gzip support"
+    );
+
+    // 2MB of highly compressible text
+    let content: Vec<u8> = b"The quick brown fox jumps over the lazy dog.\n"
+        .iter()
+        .copied()
+        .cycle()
+        .take(2 * 1024 * 1024)
+        .collect();
+
+    // Write then read back through the given channel, returning bytes on the wire
+    let transfer = |channel: &Arc<AgentChannel>, name: &str| -> u64 {
+        let fs = RemoteFileSystem::new(channel.clone(), "test@localhost".to_string());
+        let path = temp_dir.path().join(name);
+        let before = channel.bytes_sent() + channel.bytes_received();
+
+        fs.write_file(&path, &content).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), content, "On-disk bytes");
+        assert_eq!(fs.read_file(&path).unwrap(), content, "read_file bytes");
+        assert_eq!(
+            fs.read_range(&path, 1000, 200_000).unwrap(),
+            &content[1000..201_000],
+            "read_range bytes"
+        );
+
+        channel.bytes_sent() + channel.bytes_received() - before
+    };
+
+    let plain_bytes = transfer(&plain, "plain.txt");
+    let compressed_bytes = transfer(&compressed, "compressed.txt");
+    assert!(
+        compressed_bytes * 10 < plain_bytes,
+        "Compression should shrink the transfer: {} vs {} bytes",
+        compressed_bytes,
+        plain_bytes
+    );
+}
+
+/// Stand-in for sudo: checks the password read from stdin, then runs the
+/// command. Root could write the read-only target in place; the mock
+/// removes it first instead, which its writable directory allows.
+#[cfg(unix)]
+const MOCK_SUDO: &str = r#"#!/bin/sh
+if [ "$1" = "-S" ]; then
+    shift
+    if [ "$1" = "-p" ]; then
+        shift 2
+    fi
+    read -r password
+    if [ "$password" != "hunter2" ]; then
+        echo "sudo: 1 incorrect password attempt" >&2
+        exit 1
+    fi
+fi
+for target; do :; done
+rm -f "$target"
+exec "$@"
+"#;
+
+/// Spawn a local agent whose PATH starts with `bin_dir`
+#[cfg(unix)]
+async fn spawn_agent_with_bin_dir(bin_dir: &std::path::Path) -> Option<Arc<AgentChannel>> {
+    use std::process::Stdio;
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    let path = format!(
+        "{}:{}",
+        bin_dir.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    let mut child = tokio::process::Command::new("python3")
+        .arg("-u")
+        .arg("-c")
+        .arg(AGENT_SOURCE)
+        .env("PATH", path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .ok()?;
+    let mut reader = BufReader::new(child.stdout.take()?);
+    let writer = child.stdin.take()?;
+
+    let mut ready = String::new();
+    reader.read_line(&mut ready).await.ok()?;
+    let ready: AgentResponse = serde_json::from_str(&ready).ok()?;
+    if !ready.is_ready() {
+        return None;
+    }
+    Some(Arc::new(AgentChannel::new(reader, writer)))
+}
+
+/// Runs against a mock sudo so it works without real sudo or a password.
+#[cfg(unix)]
+#[test]
+fn test_write_file_sudo_with_password() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let bin_dir = temp_dir.path().join("bin");
+    std::fs::create_dir(&bin_dir).unwrap();
+    let sudo = bin_dir.join("sudo");
+    std::fs::write(&sudo, MOCK_SUDO).unwrap();
+    std::fs::set_permissions(&sudo, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let Some(channel) = rt.block_on(spawn_agent_with_bin_dir(&bin_dir)) else {
+        eprintln!("Skipping test: could not spawn local agent");
+        return;
+    };
+    let fs = RemoteFileSystem::new(channel, "test@localhost".to_string());
+
+    let path = temp_dir.path().join("system.conf");
+    std::fs::write(&path, b"old\n").unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o444)).unwrap();
+
+    // A rejected password is reported distinctly and leaves the file alone
+    let err = fs
+        .write_file_sudo(&path, b"new\n", &SecretString::from("wrong".to_string()))
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+    assert!(
+        err.get_ref()
+            .is_some_and(|e| e.is::<SudoPasswordRejected>()),
+        "Expected SudoPasswordRejected, got: {:?}",
+        err
+    );
+    assert_eq!(std::fs::read(&path).unwrap(), b"old\n");
+
+    fs.write_file_sudo(&path, b"new\n", &SecretString::from("hunter2".to_string()))
+        .unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), b"new\n");
+}
+
+#[test]
+fn test_count_lines_matches_local_count() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+
+    let large: Vec<u8> = b"a line of text\n"
+        .iter()
+        .copied()
+        .cycle()
+        .take(3 * 65536 + 7)
+        .collect();
+    let fixtures: Vec<(&str, Vec<u8>)> = vec![
+        ("empty.txt", Vec::new()),
+        ("no_trailing.txt", b"one\ntwo\nthree".to_vec()),
+        ("trailing.txt", b"one\ntwo\nthree\n".to_vec()),
+        ("blank_lines.txt", b"\n\n\n".to_vec()),
+        ("large.txt", large),
+    ];
+
+    for (name, content) in fixtures {
+        let path = temp_dir.path().join(name);
+        std::fs::write(&path, &content).unwrap();
+
+        let count = fs.count_lines(&path).unwrap();
+        let expected = content.iter().filter(|&&b| b == b'\n').count();
+        assert_eq!(count.newlines, expected, "{}", name);
+        assert_eq!(
+            count.trailing_newline,
+            content.last() == Some(&b'\n'),
+            "{}",
+            name
+        );
+    }
+
+    assert!(fs
+        .count_lines(&temp_dir.path().join("missing.txt"))
+        .is_err());
+}
+
+#[test]
+fn test_copy_stays_on_remote_host() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let Ok(channel) = rt.block_on(spawn_local_agent()) else {
+        eprintln!("Skipping test: could not spawn local agent");
+        return;
+    };
+    let fs = RemoteFileSystem::new(channel.clone(), "test@localhost".to_string());
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    let content: Vec<u8> = (0..1024 * 1024).map(|i| (i % 251) as u8).collect();
+    let src = temp_dir.path().join("original.bin");
+    let dst = temp_dir.path().join("copy.bin");
+    std::fs::write(&src, &content).unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&src, std::fs::Permissions::from_mode(0o750)).unwrap();
+    }
+
+    let before = channel.bytes_sent() + channel.bytes_received();
+    let copied = fs.copy(&src, &dst).unwrap();
+    let transferred = channel.bytes_sent() + channel.bytes_received() - before;
+
+    assert_eq!(copied, content.len() as u64);
+    assert_eq!(std::fs::read(&dst).unwrap(), content);
+    assert!(
+        transferred < 4096,
+        "Copy should not stream file data: {} bytes on the wire",
+        transferred
+    );
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&dst).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o750, "Mode preserved");
+    }
+}
+
 // =============================================================================
 // TextBuffer + RemoteFileSystem e2e tests
 // =============================================================================
@@ -572,6 +1571,40 @@ fn test_buffer_save_new_file_through_remote() {
     assert_eq!(content, b"Hello, World!\nLine 2\n");
 }
 
+#[test]
+fn test_buffer_save_with_backup_through_remote() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem_arc() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+
+    let file_path = temp_dir.path().join("backup_test.txt");
+    std::fs::write(&file_path, b"before\n").unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&file_path, std::fs::Permissions::from_mode(0o640)).unwrap();
+    }
+
+    let mut buffer = TextBuffer::load_from_file(&file_path, 0, fs).unwrap();
+    buffer.insert_bytes(0, b"after\n".to_vec());
+    buffer.request_backup();
+    buffer.save_to_file(&file_path).unwrap();
+
+    // The agent copied the old version aside before replacing the file
+    let backup = fresh::model::buffer::backup_path(&file_path);
+    assert_eq!(std::fs::read(&backup).unwrap(), b"before\n");
+    assert_eq!(std::fs::read(&file_path).unwrap(), b"after\nbefore\n");
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = |path| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&file_path), 0o640, "Saved file keeps its mode");
+        assert_eq!(mode(&backup), 0o640, "Backup keeps the original's mode");
+    }
+}
+
 #[test]
 fn test_buffer_save_edited_file_through_remote() {
     let Some((fs, temp_dir, _rt)) = create_test_filesystem_arc() else {
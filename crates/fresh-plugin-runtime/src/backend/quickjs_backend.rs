@@ -1001,6 +1001,17 @@ impl JsEditorApi {
             .is_ok()
     }
 
+    /// Replace a range in a buffer with text, undone in one step
+    pub fn replace_range(&self, buffer_id: u32, start: u32, end: u32, text: String) -> bool {
+        self.command_sender
+            .send(PluginCommand::ReplaceRange {
+                buffer_id: BufferId(buffer_id as usize),
+                range: (start as usize)..(end as usize),
+                text,
+            })
+            .is_ok()
+    }
+
     /// Insert text at cursor position in active buffer
     pub fn insert_at_cursor(&self, text: String) -> bool {
         self.command_sender
@@ -1516,6 +1527,81 @@ impl JsEditorApi {
         Ok(id)
     }
 
+    /// Request syntax highlights for a code string in the given language (async)
+    ///
+    /// Span offsets are bytes into `code`. Resolves to an empty list when the
+    /// language has no grammar.
+    #[plugin_api(
+        async_promise,
+        js_name = "highlightCode",
+        ts_return = "TsHighlightSpan[]"
+    )]
+    #[qjs(rename = "_highlightCodeStart")]
+    pub fn highlight_code_start<'js>(
+        &self,
+        _ctx: rquickjs::Ctx<'js>,
+        code: String,
+        language: String,
+    ) -> rquickjs::Result<u64> {
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            // Record plugin name for this callback
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
+
+        let _ = self
+            .command_sender
+            .send(PluginCommand::RequestCodeHighlights {
+                code,
+                language,
+                request_id: id,
+            });
+
+        Ok(id)
+    }
+
+    /// Request the outline of a buffer (async)
+    ///
+    /// Lists functions, classes, structs and similar definitions parsed from
+    /// code, or headings in markdown, in document order. Resolves to an empty
+    /// list when the buffer's language has no outline.
+    #[plugin_api(
+        async_promise,
+        js_name = "getDocumentSymbols",
+        ts_return = "TsDocumentSymbol[]"
+    )]
+    #[qjs(rename = "_getDocumentSymbolsStart")]
+    pub fn get_document_symbols_start<'js>(
+        &self,
+        _ctx: rquickjs::Ctx<'js>,
+        buffer_id: u32,
+    ) -> rquickjs::Result<u64> {
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            // Record plugin name for this callback
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
+
+        let _ = self
+            .command_sender
+            .send(PluginCommand::RequestDocumentSymbols {
+                buffer_id: BufferId(buffer_id as usize),
+                request_id: id,
+            });
+
+        Ok(id)
+    }
+
     // === Overlays ===
 
     /// Add an overlay with styling options
@@ -3476,6 +3562,8 @@ impl QuickJsBackend {
                 editor.getBufferText = _wrapAsync("_getBufferTextStart", "getBufferText");
                 editor.createCompositeBuffer = _wrapAsync("_createCompositeBufferStart", "createCompositeBuffer");
                 editor.getHighlights = _wrapAsync("_getHighlightsStart", "getHighlights");
+                editor.highlightCode = _wrapAsync("_highlightCodeStart", "highlightCode");
+                editor.getDocumentSymbols = _wrapAsync("_getDocumentSymbolsStart", "getDocumentSymbols");
                 editor.loadPlugin = _wrapAsync("_loadPluginStart", "loadPlugin");
                 editor.unloadPlugin = _wrapAsync("_unloadPluginStart", "unloadPlugin");
                 editor.reloadPlugin = _wrapAsync("_reloadPluginStart", "reloadPlugin");
@@ -4559,6 +4647,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_api_replace_range() {
+        let (mut backend, rx) = create_test_backend();
+
+        // replaceRange takes (buffer_id, start, end, text)
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            editor.replaceRange(0, 10, 20, "new");
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        let cmd = rx.try_recv().unwrap();
+        match cmd {
+            PluginCommand::ReplaceRange { range, text, .. } => {
+                assert_eq!(range.start, 10);
+                assert_eq!(range.end, 20);
+                assert_eq!(text, "new");
+            }
+            _ => panic!("Expected ReplaceRange, got {:?}", cmd),
+        }
+    }
+
     #[test]
     fn test_api_insert_text() {
         let (mut backend, rx) = create_test_backend();
@@ -324,6 +324,13 @@ impl PluginThreadHandle {
                 let result = serde_json::to_string(&spans).unwrap_or_else(|_| "[]".to_string());
                 self.resolve_callback(JsCallbackId(request_id), result);
             }
+            PluginResponse::DocumentSymbols {
+                request_id,
+                symbols,
+            } => {
+                let result = serde_json::to_string(&symbols).unwrap_or_else(|_| "[]".to_string());
+                self.resolve_callback(JsCallbackId(request_id), result);
+            }
             PluginResponse::BufferText { request_id, text } => match text {
                 Ok(content) => {
                     // JSON stringify the content string
@@ -703,6 +710,7 @@ fn respond_to_pending(
         fresh_core::api::PluginResponse::VirtualBufferCreated { request_id, .. } => *request_id,
         fresh_core::api::PluginResponse::LspRequest { request_id, .. } => *request_id,
         fresh_core::api::PluginResponse::HighlightsComputed { request_id, .. } => *request_id,
+        fresh_core::api::PluginResponse::DocumentSymbols { request_id, .. } => *request_id,
         fresh_core::api::PluginResponse::BufferText { request_id, .. } => *request_id,
         fresh_core::api::PluginResponse::CompositeBufferCreated { request_id, .. } => *request_id,
         fresh_core::api::PluginResponse::LineStartPosition { request_id, .. } => *request_id,
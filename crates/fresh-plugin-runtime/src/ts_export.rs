@@ -21,8 +21,8 @@ use fresh_core::api::{
     CreateVirtualBufferInExistingSplitOptions, CreateVirtualBufferInSplitOptions,
     CreateVirtualBufferOptions, CursorInfo, DirEntry, FormatterPackConfig, JsDiagnostic,
     JsPosition, JsRange, JsTextPropertyEntry, LanguagePackConfig, LayoutHints, LspServerPackConfig,
-    SpawnResult, TerminalResult, TextPropertiesAtCursor, TsHighlightSpan, ViewTokenStyle,
-    ViewTokenWire, ViewTokenWireKind, ViewportInfo, VirtualBufferResult,
+    SpawnResult, TerminalResult, TextPropertiesAtCursor, TsDocumentSymbol, TsHighlightSpan,
+    ViewTokenStyle, ViewTokenWire, ViewTokenWireKind, ViewportInfo, VirtualBufferResult,
 };
 use fresh_core::command::Suggestion;
 use fresh_core::file_explorer::FileExplorerDecoration;
@@ -69,6 +69,7 @@ fn get_type_decl(type_name: &str) -> Option<String> {
         "TsActionPopupAction" | "ActionPopupAction" => Some(ActionPopupAction::decl()),
         "ActionPopupOptions" => Some(ActionPopupOptions::decl()),
         "TsHighlightSpan" => Some(TsHighlightSpan::decl()),
+        "TsDocumentSymbol" => Some(TsDocumentSymbol::decl()),
         "FileExplorerDecoration" => Some(FileExplorerDecoration::decl()),
 
         // Virtual buffer option types
@@ -353,6 +354,7 @@ mod tests {
             "TsActionPopupAction",
             "ActionPopupOptions",
             "TsHighlightSpan",
+            "TsDocumentSymbol",
             "FileExplorerDecoration",
             "TextPropertyEntry",
             "CreateVirtualBufferOptions",
@@ -747,6 +749,7 @@ mod tests {
             "getBufferSavedDiff",
             "insertText",
             "deleteRange",
+            "replaceRange",
             "insertAtCursor",
             "openFile",
             "openFileInSplit",
@@ -788,6 +791,8 @@ mod tests {
             "updateCompositeAlignment",
             "closeCompositeBuffer",
             "getHighlights",
+            "highlightCode",
+            "getDocumentSymbols",
             "addOverlay",
             "clearNamespace",
             "clearAllOverlays",